@@ -0,0 +1,313 @@
+//! bottom's system data collection, for other tools that want the data without the interface.
+//!
+//! ```no_run
+//! let mut collector = bottom_core::Collector::builder().cpu(true).memory(true).build();
+//! loop {
+//!     let snapshot = collector.collect();
+//!     println!("{:?}", snapshot.memory);
+//!     std::thread::sleep(std::time::Duration::from_secs(1));
+//! }
+//! ```
+//!
+//! Everything here is kept stable between releases.  The data types are `#[non_exhaustive]`, so
+//! new readings can be added to them without breaking anyone, and they can't be built outside
+//! this crate.  bottom's own harvesters, which this wraps, may change at any time.
+
+#![warn(rust_2018_idioms)]
+
+use std::collections::HashMap;
+
+use bottom::app::{
+    data_harvester::{self, DataCollector},
+    layout_manager::UsedWidgets,
+    DataFilters,
+};
+
+/// Picks what a [`Collector`] harvests.  Nothing is harvested unless it's turned on.
+#[derive(Clone, Debug)]
+pub struct CollectorBuilder {
+    used_widgets: UsedWidgets,
+    show_average_cpu: bool,
+    use_current_cpu_total: bool,
+}
+
+impl Default for CollectorBuilder {
+    fn default() -> Self {
+        CollectorBuilder {
+            used_widgets: UsedWidgets::default(),
+            show_average_cpu: true,
+            use_current_cpu_total: false,
+        }
+    }
+}
+
+impl CollectorBuilder {
+    /// Per-core CPU usage, and the average across all of them unless turned off with
+    /// [`CollectorBuilder::average_cpu`].
+    pub fn cpu(mut self, cpu: bool) -> Self {
+        self.used_widgets.use_cpu = cpu;
+        self
+    }
+
+    /// Whether CPU usage includes the average across all cores.  On by default.
+    pub fn average_cpu(mut self, average_cpu: bool) -> Self {
+        self.show_average_cpu = average_cpu;
+        self
+    }
+
+    /// Whether process CPU usage is a share of the CPU currently in use, rather than of the total.
+    pub fn current_cpu_total(mut self, current_cpu_total: bool) -> Self {
+        self.use_current_cpu_total = current_cpu_total;
+        self
+    }
+
+    /// RAM and swap usage.
+    pub fn memory(mut self, memory: bool) -> Self {
+        self.used_widgets.use_mem = memory;
+        self
+    }
+
+    /// Network usage across all interfaces.
+    pub fn network(mut self, network: bool) -> Self {
+        self.used_widgets.use_net = network;
+        self
+    }
+
+    /// The process list.
+    pub fn processes(mut self, processes: bool) -> Self {
+        self.used_widgets.use_proc = processes;
+        self
+    }
+
+    /// Disk usage.
+    pub fn disks(mut self, disks: bool) -> Self {
+        self.used_widgets.use_disk = disks;
+        self
+    }
+
+    /// Temperature, fan, voltage, and power sensors, depending on what the platform has.
+    pub fn sensors(mut self, sensors: bool) -> Self {
+        self.used_widgets.use_temp = sensors;
+        self
+    }
+
+    /// Battery charge and health.
+    pub fn batteries(mut self, batteries: bool) -> Self {
+        self.used_widgets.use_battery = batteries;
+        self
+    }
+
+    /// Sets up the collector, including a first harvest to measure rates against.  This blocks
+    /// for a moment, so the first [`Collector::collect`] has something to compare to.
+    pub fn build(self) -> Collector {
+        let mut collector = DataCollector::new(DataFilters {
+            disk_filter: None,
+            mount_filter: None,
+            fs_filter: None,
+            temp_filter: None,
+            net_filter: None,
+            user_filter: None,
+            temp_aliases: HashMap::new(),
+        });
+        collector.set_collected_data(self.used_widgets);
+        collector.set_show_average_cpu(self.show_average_cpu);
+        collector.set_use_current_cpu_total(self.use_current_cpu_total);
+        collector.init();
+
+        Collector { collector }
+    }
+}
+
+/// Collects system data on demand.  Rates, like CPU usage and network throughput, are worked out
+/// since the last call to [`Collector::collect`].
+#[derive(Debug)]
+pub struct Collector {
+    collector: DataCollector,
+}
+
+impl Collector {
+    pub fn builder() -> CollectorBuilder {
+        CollectorBuilder::default()
+    }
+
+    /// Harvests everything that was turned on, blocking until it's done.  Anything that wasn't
+    /// turned on, or couldn't be read on this platform, is `None`.
+    pub fn collect(&mut self) -> Snapshot {
+        futures::executor::block_on(self.collector.update_data());
+        to_snapshot(&self.collector.data)
+    }
+}
+
+/// Everything harvested by one call to [`Collector::collect`].
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct Snapshot {
+    pub cpu: Option<Vec<CpuUsage>>,
+    pub memory: Option<MemoryUsage>,
+    pub swap: Option<MemoryUsage>,
+    pub network: Option<NetworkUsage>,
+    pub processes: Option<Vec<Process>>,
+    pub disks: Option<Vec<Disk>>,
+    pub sensors: Option<Vec<Sensor>>,
+    pub batteries: Option<Vec<Battery>>,
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct CpuUsage {
+    /// Like `CPU0`, or `AVG` for the average.
+    pub name: String,
+    /// Whether this is the average across all cores, rather than a single core.
+    pub is_average: bool,
+    pub usage_percent: f64,
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct MemoryUsage {
+    pub total_kib: u64,
+    pub used_kib: u64,
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct NetworkUsage {
+    pub rx_bits_per_sec: u64,
+    pub tx_bits_per_sec: u64,
+    pub total_rx_bytes: u64,
+    pub total_tx_bytes: u64,
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Process {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    pub command: String,
+    pub cpu_percent: f64,
+    pub mem_bytes: u64,
+    pub mem_percent: f64,
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Disk {
+    pub name: String,
+    pub mount_point: String,
+    pub used_bytes: Option<u64>,
+    pub total_bytes: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum SensorKind {
+    /// In Celsius.
+    Temperature,
+    /// In RPM.
+    Fan,
+    /// In volts.
+    Voltage,
+    /// In watts.
+    Power,
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Sensor {
+    pub name: String,
+    pub kind: SensorKind,
+    /// The reading, in the unit for its [`SensorKind`].
+    pub value: f32,
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Battery {
+    pub charge_percent: f64,
+    pub health_percent: f64,
+    pub is_discharging: bool,
+}
+
+/// Copies what's kept stable out of bottom's own data, which can change at any time.
+fn to_snapshot(data: &data_harvester::Data) -> Snapshot {
+    let to_memory_usage = |memory: &data_harvester::memory::MemHarvest| MemoryUsage {
+        total_kib: memory.mem_total_in_kib,
+        used_kib: memory.mem_used_in_kib,
+    };
+
+    Snapshot {
+        cpu: data.cpu.as_ref().map(|cpu| {
+            cpu.iter()
+                .map(|cpu_data| CpuUsage {
+                    name: match cpu_data.cpu_count {
+                        Some(count) => format!("{}{}", cpu_data.cpu_prefix, count),
+                        None => cpu_data.cpu_prefix.clone(),
+                    },
+                    is_average: cpu_data.cpu_count.is_none(),
+                    usage_percent: cpu_data.cpu_usage,
+                })
+                .collect()
+        }),
+        memory: data.memory.as_ref().map(to_memory_usage),
+        swap: data.swap.as_ref().map(to_memory_usage),
+        network: data.network.as_ref().map(|network| NetworkUsage {
+            rx_bits_per_sec: network.rx,
+            tx_bits_per_sec: network.tx,
+            total_rx_bytes: network.total_rx,
+            total_tx_bytes: network.total_tx,
+        }),
+        processes: data.list_of_processes.as_ref().map(|processes| {
+            processes
+                .iter()
+                .map(|process| Process {
+                    pid: process.pid as u32,
+                    parent_pid: process.parent_pid.map(|parent_pid| parent_pid as u32),
+                    name: process.name.to_string(),
+                    command: process.command.to_string(),
+                    cpu_percent: process.cpu_usage_percent,
+                    mem_bytes: process.mem_usage_bytes,
+                    mem_percent: process.mem_usage_percent,
+                })
+                .collect()
+        }),
+        disks: data.disks.as_ref().map(|disks| {
+            disks
+                .iter()
+                .map(|disk| Disk {
+                    name: disk.name.clone(),
+                    mount_point: disk.mount_point.clone(),
+                    used_bytes: disk.used_space,
+                    total_bytes: disk.total_space,
+                })
+                .collect()
+        }),
+        sensors: data.temperature_sensors.as_ref().map(|sensors| {
+            sensors
+                .iter()
+                .map(|sensor| Sensor {
+                    name: sensor.name.clone(),
+                    kind: match sensor.kind {
+                        data_harvester::temperature::SensorKind::Temperature => {
+                            SensorKind::Temperature
+                        }
+                        data_harvester::temperature::SensorKind::Fan => SensorKind::Fan,
+                        data_harvester::temperature::SensorKind::Voltage => SensorKind::Voltage,
+                        data_harvester::temperature::SensorKind::Power => SensorKind::Power,
+                    },
+                    value: sensor.temperature,
+                })
+                .collect()
+        }),
+        batteries: data.list_of_batteries.as_ref().map(|batteries| {
+            batteries
+                .iter()
+                .map(|battery| Battery {
+                    charge_percent: battery.charge_percent,
+                    health_percent: battery.health_percent,
+                    is_discharging: battery.is_discharging,
+                })
+                .collect()
+        }),
+    }
+}
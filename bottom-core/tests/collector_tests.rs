@@ -0,0 +1,48 @@
+//! Runs the collector against this machine, to check that what's turned on, and only that, is
+//! harvested.
+
+use bottom_core::Collector;
+
+#[test]
+fn test_collects_only_what_is_turned_on() {
+    let mut collector = Collector::builder().memory(true).build();
+    let snapshot = collector.collect();
+
+    assert!(snapshot.cpu.is_none());
+    assert!(snapshot.network.is_none());
+    assert!(snapshot.processes.is_none());
+    assert!(snapshot.disks.is_none());
+    assert!(snapshot.sensors.is_none());
+    assert!(snapshot.batteries.is_none());
+
+    let memory = snapshot.memory.expect("memory was turned on");
+    assert!(memory.total_kib > 0);
+    assert!(memory.used_kib <= memory.total_kib);
+}
+
+#[test]
+fn test_cpu_average() {
+    let mut collector = Collector::builder().cpu(true).build();
+    let cpu = collector.collect().cpu.expect("CPU usage was turned on");
+    assert_eq!(
+        cpu.iter().filter(|cpu_usage| cpu_usage.is_average).count(),
+        1
+    );
+    assert!(cpu.iter().any(|cpu_usage| !cpu_usage.is_average));
+
+    let mut collector = Collector::builder().cpu(true).average_cpu(false).build();
+    let cpu = collector.collect().cpu.expect("CPU usage was turned on");
+    assert!(cpu.iter().all(|cpu_usage| !cpu_usage.is_average));
+}
+
+#[test]
+fn test_processes_include_this_one() {
+    let mut collector = Collector::builder().processes(true).build();
+    let processes = collector
+        .collect()
+        .processes
+        .expect("processes were turned on");
+    assert!(processes
+        .iter()
+        .any(|process| process.pid == std::process::id()));
+}
@@ -1,9 +1,11 @@
 use std::{
     cmp::{max, min},
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     // io::Write,
     path::PathBuf,
-    time::Instant,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
 };
 
 use unicode_segmentation::GraphemeCursor;
@@ -12,53 +14,166 @@ use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use typed_builder::*;
 
 use data_farmer::*;
-use data_harvester::{processes, temperature};
+use data_harvester::{disk_usage::DiskUsageScan, docker, processes, services, temperature};
+use keybinds::Keybinds;
 use layout_manager::*;
+use process_rules::{get_signal_name, ProcessRuleMode, ProcessRules};
 pub use states::*;
 
 use crate::{
     canvas, constants,
+    data_conversion::stringify_process_data,
+    dump::SummaryOutput,
     options::Config,
     options::ConfigFlags,
     options::WidgetIdEnabled,
-    units::data_units::DataUnit,
+    units::{data_units::DataUnit, formatting::NumberFormat},
     utils::error::{BottomError, Result},
-    Pid,
+    BottomEvent, Pid, ThreadControlEvent,
 };
 
+mod clipboard;
 pub mod data_farmer;
 pub mod data_harvester;
+pub mod keybinds;
 pub mod layout_manager;
-mod process_killer;
+pub mod notifications;
+mod process_affinity;
+pub mod process_killer;
+pub mod process_rules;
+mod process_threads;
 pub mod query;
 pub mod states;
 
 const MAX_SEARCH_LENGTH: usize = 200;
 
+/// The process search used to show which processes are writing to disk.
+const DISK_WRITERS_QUERY: &str = "w/s > 0";
+
+/// How long a status message stays on screen.
+const STATUS_MESSAGE_DURATION: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone)]
 pub enum AxisScaling {
     Log,
     Linear,
 }
 
+/// How a graph's lines are drawn.  Braille is the most detailed, but some fonts and terminals
+/// don't draw it well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphStyle {
+    Braille,
+    Block,
+    Dot,
+    /// Braille, with the area under each line filled in.
+    Filled,
+}
+
+/// The style of each kind of graph.
+#[derive(Debug, Clone, Copy)]
+pub struct GraphStyles {
+    pub cpu: GraphStyle,
+    pub mem: GraphStyle,
+    pub net: GraphStyle,
+    pub battery: GraphStyle,
+    pub custom: GraphStyle,
+    pub sched: GraphStyle,
+    pub power: GraphStyle,
+}
+
+/// What the time axis of graphs is labelled with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeLabelStyle {
+    /// How long ago each point is, e.g. "60s".
+    Relative,
+    /// The time of day each point was collected at.
+    Absolute,
+}
+
+impl std::str::FromStr for TimeLabelStyle {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "relative" => Ok(TimeLabelStyle::Relative),
+            "absolute" | "clock" => Ok(TimeLabelStyle::Absolute),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid time label style, use \"relative\" or \"absolute\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// Whether times of day are shown on a 24-hour or a 12-hour clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockFormat {
+    TwentyFourHour,
+    TwelveHour,
+}
+
+impl ClockFormat {
+    /// The `strftime` format for a time of day, to the second.
+    pub fn time_format(&self) -> &'static str {
+        match self {
+            ClockFormat::TwentyFourHour => "%H:%M:%S",
+            ClockFormat::TwelveHour => "%I:%M:%S %p",
+        }
+    }
+}
+
+impl std::str::FromStr for ClockFormat {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "24h" | "24" => Ok(ClockFormat::TwentyFourHour),
+            "12h" | "12" => Ok(ClockFormat::TwelveHour),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid time format, use \"24h\" or \"12h\".",
+                s
+            ))),
+        }
+    }
+}
+
 /// AppConfigFields is meant to cover basic fields that would normally be set
 /// by config files or launch options.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AppConfigFields {
     pub update_rate_in_milliseconds: u64,
     pub temperature_type: temperature::TemperatureType,
-    pub use_dot: bool,
+    /// Units for particular temperature sensors that differ from `temperature_type`, keyed by the
+    /// name the sensor is shown with.
+    pub temperature_units: HashMap<String, temperature::TemperatureType>,
+    pub graph_styles: GraphStyles,
     pub left_legend: bool,
     pub show_average_cpu: bool,
     pub use_current_cpu_total: bool,
+    /// Whether to overlay the busiest processes' CPU usage on the CPU graph.
+    pub show_top_cpu_processes: bool,
     pub use_basic_mode: bool,
+    /// Only shows basic mode's meters, on one line.
+    pub use_mini_mode: bool,
     pub default_time_value: u64,
     pub time_interval: u64,
     pub hide_time: bool,
     pub autohide_time: bool,
+    pub time_label_style: TimeLabelStyle,
+    pub clock_format: ClockFormat,
     pub use_old_network_legend: bool,
+    /// Whether to graph download and upload in separate panes, each with its own y-axis.
+    pub network_split: bool,
     pub table_gap: u16,
     pub disable_click: bool,
+    /// Whether to show the inodes used column in the disk widget.
+    pub show_disk_inodes: bool,
+    /// Whether the disk widget shows the I/O of the whole device each disk is on, rather than of
+    /// the partition itself.
+    pub disk_io_per_device: bool,
+    /// Whether the disk widget lists disks of the same filesystem type together.
+    pub group_disks_by_fs: bool,
     pub no_write: bool,
     pub show_table_scroll_position: bool,
     pub is_advanced_kill: bool,
@@ -66,6 +181,37 @@ pub struct AppConfigFields {
     pub network_unit_type: DataUnit,
     pub network_scale_type: AxisScaling,
     pub network_use_binary_prefix: bool,
+    /// The rate, in bits per second, to pin the top of network graphs to by default.
+    pub network_max: Option<f64>,
+    pub memory_use_decimal_prefix: bool,
+    /// How sizes and rates are formatted across widgets.
+    pub number_format: NumberFormat,
+    pub show_gpu_process_columns: bool,
+    pub show_tcp_process_columns: bool,
+    pub show_k8s_process_columns: bool,
+    /// Whether processes' CPU usage includes the CPU time of children they've waited for.  Only
+    /// supported on Linux.
+    pub include_child_cpu_time: bool,
+    /// Whether the connections widget shows remote hostnames instead of addresses.  Only
+    /// supported on Linux.
+    pub resolve_hostnames: bool,
+    /// Where to save graph history on exit; `None` if history isn't persisted.
+    pub history_path: Option<PathBuf>,
+    pub history_retention: u64,
+    /// Where to put the session summary on exit; `None` if there isn't one.
+    pub session_summary: Option<SummaryOutput>,
+    /// The address to serve the websocket stream on, if any.
+    pub stream_address: Option<String>,
+    /// The token clients have to send to connect to the stream, if any.
+    pub stream_token: Option<String>,
+    /// Where to save data snapshots; `None` for the current directory.
+    pub snapshot_dir: Option<PathBuf>,
+    /// How long each graph keeps its history for.
+    pub retention: DataRetention,
+    /// How much each part of the pressure widget's score counts for.
+    pub pressure_weights: PressureWeights,
+    /// How long drawing a frame should take at most before unchanged widgets are reused.
+    pub frame_budget: Option<Duration>,
 }
 
 /// For filtering out information
@@ -73,8 +219,14 @@ pub struct AppConfigFields {
 pub struct DataFilters {
     pub disk_filter: Option<Filter>,
     pub mount_filter: Option<Filter>,
+    /// Which filesystem types to show in the disk widget, like "tmpfs" or "nfs4".
+    pub fs_filter: Option<Filter>,
     pub temp_filter: Option<Filter>,
     pub net_filter: Option<Filter>,
+    /// Which users' processes to show.  Only supported on Unix-like systems.
+    pub user_filter: Option<Filter>,
+    /// Friendly names for temperature sensors, keyed by their original names.
+    pub temp_aliases: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone)]
@@ -83,6 +235,29 @@ pub struct Filter {
     pub list: Vec<regex::Regex>,
 }
 
+/// The outcome of an action run in the background, like restarting a service, to be shown in the
+/// widget it was run from.
+#[derive(Debug)]
+pub struct ActionResult {
+    pub widget_id: u64,
+    /// What to show in the widget, such as why the action failed; `None` to clear it.
+    pub status_text: Option<String>,
+}
+
+/// The parts of a graph widget's state that zooming changes.
+struct GraphZoom<'a> {
+    current_display_time: &'a mut u64,
+    autohide_timer: &'a mut Option<Instant>,
+    /// The `force_update` of the state of all widgets of the graph's type.
+    force_update: &'a mut Option<u64>,
+    /// How far back the graph can show, which is how long its data is kept.
+    max_display_time: u64,
+}
+
+/// Where actions run in the background send their results, which is the main event loop.
+pub type EventSender =
+    mpsc::Sender<BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>>;
+
 #[derive(TypedBuilder)]
 pub struct App {
     #[builder(default = false, setter(skip))]
@@ -94,6 +269,9 @@ pub struct App {
     #[builder(default, setter(skip))]
     pub dd_err: Option<String>,
 
+    #[builder(default, setter(skip))]
+    pub event_sender: Option<EventSender>,
+
     #[builder(default, setter(skip))]
     to_delete_process_list: Option<(String, Vec<Pid>)>,
 
@@ -106,6 +284,9 @@ pub struct App {
     #[builder(default, setter(skip))]
     pub canvas_data: canvas::DisplayableData,
 
+    #[builder(default, setter(skip))]
+    pub frame_budget: canvas::FrameBudget,
+
     #[builder(default, setter(skip))]
     pub data_collection: DataCollection,
 
@@ -115,9 +296,39 @@ pub struct App {
     #[builder(default, setter(skip))]
     pub help_dialog_state: AppHelpDialogState,
 
+    #[builder(default, setter(skip))]
+    pub column_picker_state: AppColumnPickerState,
+
+    #[builder(default, setter(skip))]
+    pub disk_usage_state: AppDiskUsageState,
+
+    #[builder(default, setter(skip))]
+    pub affinity_state: AppAffinityState,
+
+    #[builder(default, setter(skip))]
+    pub renice_state: AppReniceState,
+
+    #[builder(default, setter(skip))]
+    pub threads_state: AppThreadsState,
+
+    #[builder(default, setter(skip))]
+    pub global_search_state: AppGlobalSearchState,
+
+    /// A message to show at the bottom of the screen, and when it was set.
+    #[builder(default, setter(skip))]
+    pub status_message: Option<(String, Instant)>,
+
     #[builder(default = false, setter(skip))]
     pub is_expanded: bool,
 
+    /// Where the crosshair shown across all graphs is, if it's shown.
+    #[builder(default, setter(skip))]
+    pub crosshair_time: Option<Instant>,
+
+    /// Widgets the user has hidden, along with anything attached to them (like a CPU legend).
+    #[builder(default, setter(skip))]
+    pub hidden_widgets: HashSet<u64>,
+
     #[builder(default = false, setter(skip))]
     pub is_force_redraw: bool,
 
@@ -133,6 +344,10 @@ pub struct App {
     #[builder(default = false, setter(skip))]
     pub did_config_fail_to_save: bool,
 
+    /// Messages to pass on to the collection thread after handling an event.
+    #[builder(default, setter(skip))]
+    pub collection_thread_events: Vec<ThreadControlEvent>,
+
     #[cfg(target_family = "unix")]
     #[builder(default, setter(skip))]
     pub user_table: processes::UserTable,
@@ -144,12 +359,26 @@ pub struct App {
     pub temp_state: TempState,
     pub disk_state: DiskState,
     pub battery_state: BatteryState,
+    pub services_state: ServicesState,
+    pub ports_state: PortsState,
+    pub connections_state: ConnectionsState,
+    pub cgroups_state: CgroupsState,
+    pub docker_state: DockerState,
+    pub vms_state: VmsState,
+    pub custom_state: CustomState,
+    pub sched_state: SchedState,
+    pub power_state: PowerState,
+    pub remote_state: RemoteState,
+    pub log_state: LogState,
+    pub proc_events_state: ProcEventsState,
     pub basic_table_widget_state: Option<BasicTableWidgetState>,
     pub app_config_fields: AppConfigFields,
     pub widget_map: HashMap<u64, BottomWidget>,
     pub current_widget: BottomWidget,
     pub used_widgets: UsedWidgets,
     pub filters: DataFilters,
+    pub keybinds: Keybinds,
+    pub process_rules: ProcessRules,
     pub config: Config,
     pub config_path: Option<PathBuf>,
 }
@@ -169,6 +398,13 @@ impl App {
         // Reset dialog state
         self.help_dialog_state.is_showing_help = false;
         self.delete_dialog_state.is_showing_dd = false;
+        self.column_picker_state.is_showing_picker = false;
+        self.disk_usage_state.is_showing_disk_usage = false;
+        self.disk_usage_state.scan = None;
+        self.affinity_state.is_showing_affinity = false;
+        self.renice_state.is_showing_renice = false;
+        self.threads_state.is_showing_threads = false;
+        self.global_search_state = AppGlobalSearchState::default();
 
         // Close all searches and reset it
         self.proc_state
@@ -185,11 +421,10 @@ impl App {
 
         // Unfreeze.
         self.is_frozen = false;
+        self.crosshair_time = None;
 
         // Reset zoom
-        self.reset_cpu_zoom();
-        self.reset_mem_zoom();
-        self.reset_net_zoom();
+        self.reset_zoom();
 
         // Reset data
         self.data_collection.reset();
@@ -203,6 +438,7 @@ impl App {
         self.delete_dialog_state.is_showing_dd = false;
         self.delete_dialog_state.selected_signal = KillSignal::default();
         self.delete_dialog_state.scroll_pos = 0;
+        self.delete_dialog_state.is_killing_marked = false;
         self.to_delete_process_list = None;
         self.dd_err = None;
     }
@@ -213,6 +449,16 @@ impl App {
             if self.help_dialog_state.is_showing_help {
                 self.help_dialog_state.is_showing_help = false;
                 self.help_dialog_state.scroll_state.current_scroll_index = 0;
+            } else if self.column_picker_state.is_showing_picker {
+                self.close_column_picker();
+            } else if self.disk_usage_state.is_showing_disk_usage {
+                self.close_disk_usage();
+            } else if self.affinity_state.is_showing_affinity {
+                self.close_affinity_dialog();
+            } else if self.renice_state.is_showing_renice {
+                self.close_renice_dialog();
+            } else if self.threads_state.is_showing_threads {
+                self.close_threads_dialog();
             } else {
                 self.close_dd();
             }
@@ -270,10 +516,34 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Services => {
+                    if let Some(services_widget_state) = self
+                        .services_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if services_widget_state.pending_action.is_some() {
+                            services_widget_state.clear_pending_action();
+                            return;
+                        }
+                    }
+                }
+                BottomWidgetType::Docker => {
+                    if let Some(docker_widget_state) = self
+                        .docker_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if docker_widget_state.pending_action.is_some() {
+                            docker_widget_state.clear_pending_action();
+                            return;
+                        }
+                    }
+                }
                 _ => {}
             }
 
-            if self.is_expanded {
+            if self.crosshair_time.is_some() {
+                self.crosshair_time = None;
+            } else if self.is_expanded {
                 self.is_expanded = false;
                 self.is_force_redraw = true;
             }
@@ -293,13 +563,31 @@ impl App {
     }
 
     fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.delete_dialog_state.is_showing_dd
+        self.help_dialog_state.is_showing_help
+            || self.delete_dialog_state.is_showing_dd
+            || self.column_picker_state.is_showing_picker
+            || self.disk_usage_state.is_showing_disk_usage
+            || self.affinity_state.is_showing_affinity
+            || self.renice_state.is_showing_renice
+            || self.threads_state.is_showing_threads
     }
 
     fn ignore_normal_keybinds(&self) -> bool {
         self.is_config_open || self.is_in_dialog()
     }
 
+    /// Translates a key press according to the user's remapped keys, or returns `None` if it
+    /// should be ignored.  Remaps don't apply in dialogs or while typing in the search widget.
+    pub fn remap_key_event(
+        &self, event: crossterm::event::KeyEvent,
+    ) -> Option<crossterm::event::KeyEvent> {
+        if self.ignore_normal_keybinds() || self.is_in_search_widget() {
+            Some(event)
+        } else {
+            self.keybinds.remap(event, &self.current_widget.widget_type)
+        }
+    }
+
     pub fn on_tab(&mut self) {
         // Allow usage whilst only in processes
 
@@ -321,47 +609,113 @@ impl App {
                     {
                         // Do NOT allow when in tree mode!
                         if !proc_widget_state.is_tree_mode {
-                            // Toggles process widget grouping state
-                            proc_widget_state.is_grouped = !(proc_widget_state.is_grouped);
-
-                            // Forcefully switch off column if we were on it...
-                            if (proc_widget_state.is_grouped
-                                && (proc_widget_state.process_sorting_type
-                                    == processes::ProcessSorting::Pid
-                                    || proc_widget_state.process_sorting_type
-                                        == processes::ProcessSorting::User
-                                    || proc_widget_state.process_sorting_type
-                                        == processes::ProcessSorting::State))
-                                || (!proc_widget_state.is_grouped
-                                    && proc_widget_state.process_sorting_type
-                                        == processes::ProcessSorting::Count)
-                            {
-                                proc_widget_state.process_sorting_type =
-                                    processes::ProcessSorting::CpuPercent; // Go back to default, negate PID for group
-                                proc_widget_state.is_process_sort_descending = true;
-                            }
+                            let show_k8s_process_columns =
+                                self.app_config_fields.show_k8s_process_columns;
 
-                            proc_widget_state.columns.set_to_sorted_index_from_type(
-                                &proc_widget_state.process_sorting_type,
-                            );
+                            if show_k8s_process_columns
+                                && proc_widget_state.is_grouped
+                                && !proc_widget_state.is_grouped_by_pod
+                            {
+                                // With pods, grouping goes from by name, to by pod, to ungrouped.
+                                proc_widget_state.is_grouped_by_pod = true;
+                                proc_widget_state
+                                    .columns
+                                    .try_enable(&processes::ProcessSorting::Pod);
+                                proc_widget_state
+                                    .columns
+                                    .try_enable(&processes::ProcessSorting::Namespace);
+                                proc_widget_state.columns.set_to_sorted_index_from_type(
+                                    &proc_widget_state.process_sorting_type,
+                                );
+                            } else {
+                                // Toggles process widget grouping state
+                                proc_widget_state.is_grouped_by_pod = false;
+                                proc_widget_state.is_grouped = !(proc_widget_state.is_grouped);
+
+                                // Forcefully switch off column if we were on it...
+                                if (proc_widget_state.is_grouped
+                                    && (proc_widget_state.process_sorting_type
+                                        == processes::ProcessSorting::Pid
+                                        || proc_widget_state.process_sorting_type
+                                            == processes::ProcessSorting::User
+                                        || proc_widget_state.process_sorting_type
+                                            == processes::ProcessSorting::State
+                                        || proc_widget_state.process_sorting_type
+                                            == processes::ProcessSorting::GpuPercent
+                                        || proc_widget_state.process_sorting_type
+                                            == processes::ProcessSorting::GpuMem
+                                        || proc_widget_state.process_sorting_type
+                                            == processes::ProcessSorting::TcpRetransmits
+                                        || proc_widget_state.process_sorting_type
+                                            == processes::ProcessSorting::TcpRtt
+                                        || proc_widget_state.process_sorting_type
+                                            == processes::ProcessSorting::Pod
+                                        || proc_widget_state.process_sorting_type
+                                            == processes::ProcessSorting::Namespace))
+                                    || (!proc_widget_state.is_grouped
+                                        && proc_widget_state.process_sorting_type
+                                            == processes::ProcessSorting::Count)
+                                {
+                                    proc_widget_state.process_sorting_type =
+                                        processes::ProcessSorting::CpuPercent; // Go back to default, negate PID for group
+                                    proc_widget_state.is_process_sort_descending = true;
+                                }
 
-                            proc_widget_state.columns.try_set(
-                                &processes::ProcessSorting::State,
-                                !(proc_widget_state.is_grouped),
-                            );
+                                proc_widget_state.columns.set_to_sorted_index_from_type(
+                                    &proc_widget_state.process_sorting_type,
+                                );
+
+                                proc_widget_state.columns.try_set(
+                                    &processes::ProcessSorting::State,
+                                    !(proc_widget_state.is_grouped),
+                                );
+
+                                #[cfg(target_family = "unix")]
+                                proc_widget_state.columns.try_set(
+                                    &processes::ProcessSorting::User,
+                                    !(proc_widget_state.is_grouped),
+                                );
+
+                                // GPU usage isn't summed up when grouping.
+                                let show_gpu_columns = !(proc_widget_state.is_grouped)
+                                    && self.app_config_fields.show_gpu_process_columns;
+                                proc_widget_state.columns.try_set(
+                                    &processes::ProcessSorting::GpuPercent,
+                                    show_gpu_columns,
+                                );
+                                proc_widget_state
+                                    .columns
+                                    .try_set(&processes::ProcessSorting::GpuMem, show_gpu_columns);
+
+                                // Neither are TCP stats.
+                                let show_tcp_columns = !(proc_widget_state.is_grouped)
+                                    && self.app_config_fields.show_tcp_process_columns;
+                                proc_widget_state.columns.try_set(
+                                    &processes::ProcessSorting::TcpRetransmits,
+                                    show_tcp_columns,
+                                );
+                                proc_widget_state
+                                    .columns
+                                    .try_set(&processes::ProcessSorting::TcpRtt, show_tcp_columns);
 
-                            #[cfg(target_family = "unix")]
-                            proc_widget_state.columns.try_set(
-                                &processes::ProcessSorting::User,
-                                !(proc_widget_state.is_grouped),
-                            );
+                                // Pods only make sense when not grouped, or grouped by pod.
+                                let show_k8s_columns =
+                                    !(proc_widget_state.is_grouped) && show_k8s_process_columns;
+                                proc_widget_state
+                                    .columns
+                                    .try_set(&processes::ProcessSorting::Pod, show_k8s_columns);
+                                proc_widget_state.columns.try_set(
+                                    &processes::ProcessSorting::Namespace,
+                                    show_k8s_columns,
+                                );
 
-                            proc_widget_state
-                                .columns
-                                .toggle(&processes::ProcessSorting::Count);
-                            proc_widget_state
-                                .columns
-                                .toggle(&processes::ProcessSorting::Pid);
+                                proc_widget_state
+                                    .columns
+                                    .toggle(&processes::ProcessSorting::Count);
+                                proc_widget_state
+                                    .columns
+                                    .toggle(&processes::ProcessSorting::Pid);
+                            }
 
                             proc_widget_state.requires_redraw = true;
                             self.proc_state.force_update = Some(self.current_widget.widget_id);
@@ -403,9 +757,193 @@ impl App {
                         self.is_force_redraw = true;
                     }
                 }
-                _ => {}
+                BottomWidgetType::ProcSearch => {}
+                _ => {
+                    if !self.app_config_fields.use_basic_mode {
+                        self.global_search_state = AppGlobalSearchState {
+                            is_searching: true,
+                            ..AppGlobalSearchState::default()
+                        };
+                        self.is_force_redraw = true;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn close_global_search(&mut self) {
+        self.global_search_state.is_searching = false;
+        self.is_force_redraw = true;
+    }
+
+    pub fn on_global_search_char(&mut self, caught_char: char) {
+        if !caught_char.is_control()
+            && UnicodeWidthStr::width(self.global_search_state.query.as_str()) <= MAX_SEARCH_LENGTH
+        {
+            self.global_search_state.query.push(caught_char);
+            self.update_global_search_matches();
+            self.jump_to_global_search_match(0);
+        }
+    }
+
+    pub fn on_global_search_backspace(&mut self) {
+        if self.global_search_state.query.pop().is_some() {
+            self.update_global_search_matches();
+            self.jump_to_global_search_match(0);
+        }
+    }
+
+    pub fn clear_global_search(&mut self) {
+        self.global_search_state.query.clear();
+        self.update_global_search_matches();
+    }
+
+    /// Jumps to the next or previous match, wrapping around at either end.
+    pub fn cycle_global_search_match(&mut self, is_forward: bool) {
+        // The tables may have changed since the last jump, so look again.
+        self.update_global_search_matches();
+
+        let num_matches = self.global_search_state.matches.len();
+        if num_matches > 0 {
+            let next_match = match self.global_search_state.current_match {
+                Some(current_match) if is_forward => (current_match + 1) % num_matches,
+                Some(current_match) => (current_match + num_matches - 1) % num_matches,
+                None if is_forward => 0,
+                None => num_matches - 1,
+            };
+            self.jump_to_global_search_match(next_match);
+        }
+    }
+
+    /// Finds every row matching the global search, case-insensitively, in the process, disk,
+    /// temperature, and port tables that are shown.  Matches are ordered by widget, then by row.
+    fn update_global_search_matches(&mut self) {
+        let query = self.global_search_state.query.to_lowercase();
+        let mut matches = Vec::new();
+
+        if !query.is_empty() {
+            let mut widget_ids = self
+                .widget_map
+                .keys()
+                .filter(|widget_id| !self.hidden_widgets.contains(widget_id))
+                .copied()
+                .collect::<Vec<_>>();
+            widget_ids.sort_unstable();
+
+            let row_matches = |row: &Vec<String>| {
+                row.iter()
+                    .any(|entry| entry.to_lowercase().contains(&query))
+            };
+            for widget_id in widget_ids {
+                let rows = match self.widget_map[&widget_id].widget_type {
+                    BottomWidgetType::Proc => self
+                        .canvas_data
+                        .finalized_process_data_map
+                        .get(&widget_id)
+                        .map(|processes| {
+                            processes
+                                .iter()
+                                .map(|process| {
+                                    process.name.to_lowercase().contains(&query)
+                                        || process.command.to_lowercase().contains(&query)
+                                })
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default(),
+                    BottomWidgetType::Disk => {
+                        self.canvas_data.disk_data.iter().map(row_matches).collect()
+                    }
+                    BottomWidgetType::Temp => self
+                        .canvas_data
+                        .temp_sensor_data
+                        .iter()
+                        .map(row_matches)
+                        .collect(),
+                    BottomWidgetType::Ports => {
+                        self.canvas_data.port_data.iter().map(row_matches).collect()
+                    }
+                    BottomWidgetType::Connections => self
+                        .canvas_data
+                        .connection_data
+                        .iter()
+                        .map(row_matches)
+                        .collect(),
+                    _ => Vec::new(),
+                };
+
+                matches.extend(
+                    rows.into_iter()
+                        .enumerate()
+                        .filter(|(_, is_match)| *is_match)
+                        .map(|(row, _)| GlobalSearchMatch { widget_id, row }),
+                );
             }
         }
+
+        // Stay on the same match if it's still there.
+        let current_match = self
+            .global_search_state
+            .current_match
+            .and_then(|current_match| self.global_search_state.matches.get(current_match))
+            .and_then(|current_match| {
+                matches
+                    .iter()
+                    .position(|new_match| new_match == current_match)
+            });
+        self.global_search_state.matches = matches;
+        self.global_search_state.current_match = current_match;
+    }
+
+    /// Selects the widget a match is in and scrolls its table to the matching row.
+    fn jump_to_global_search_match(&mut self, match_index: usize) {
+        let GlobalSearchMatch { widget_id, row } =
+            match self.global_search_state.matches.get(match_index) {
+                Some(global_search_match) => *global_search_match,
+                None => return,
+            };
+        self.global_search_state.current_match = Some(match_index);
+
+        if self.current_widget.widget_id != widget_id {
+            if let Some(widget) = self.widget_map.get(&widget_id) {
+                self.current_widget = widget.clone();
+                if self.is_expanded {
+                    self.is_expanded = false;
+                    self.is_force_redraw = true;
+                }
+            }
+        }
+
+        let scroll_state = match self.current_widget.widget_type {
+            BottomWidgetType::Proc => self
+                .proc_state
+                .get_mut_widget_state(widget_id)
+                .map(|widget_state| &mut widget_state.scroll_state),
+            BottomWidgetType::Disk => self
+                .disk_state
+                .get_mut_widget_state(widget_id)
+                .map(|widget_state| &mut widget_state.scroll_state),
+            BottomWidgetType::Temp => self
+                .temp_state
+                .get_mut_widget_state(widget_id)
+                .map(|widget_state| &mut widget_state.scroll_state),
+            BottomWidgetType::Ports => self
+                .ports_state
+                .get_mut_widget_state(widget_id)
+                .map(|widget_state| &mut widget_state.scroll_state),
+            BottomWidgetType::Connections => self
+                .connections_state
+                .get_mut_widget_state(widget_id)
+                .map(|widget_state| &mut widget_state.scroll_state),
+            _ => None,
+        };
+        if let Some(scroll_state) = scroll_state {
+            scroll_state.scroll_direction = if row < scroll_state.current_scroll_position {
+                ScrollDirection::Up
+            } else {
+                ScrollDirection::Down
+            };
+            scroll_state.current_scroll_position = row;
+        }
     }
 
     pub fn toggle_sort(&mut self) {
@@ -464,39 +1002,36 @@ impl App {
         }
     }
 
+    pub fn toggle_secondary_sort(&mut self) {
+        if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
+            let widget_id = self.current_widget.widget_id - 2;
+            if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget_id) {
+                proc_widget_state.toggle_secondary_sort_with_columns();
+                self.proc_state.force_update = Some(widget_id);
+            }
+        }
+    }
+
     pub fn toggle_percentages(&mut self) {
         match &self.current_widget.widget_type {
             BottomWidgetType::BasicMem => {
                 self.basic_mode_use_percent = !self.basic_mode_use_percent; // Oh god this is so lazy.
             }
+            BottomWidgetType::Mem => {
+                if let Some(mem_widget_state) = self
+                    .mem_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    mem_widget_state.show_as_value = !mem_widget_state.show_as_value;
+                }
+            }
             BottomWidgetType::Proc => {
                 if let Some(proc_widget_state) = self
                     .proc_state
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
-                    proc_widget_state
-                        .columns
-                        .toggle(&processes::ProcessSorting::Mem);
-                    if let Some(mem_percent_state) = proc_widget_state
-                        .columns
-                        .toggle(&processes::ProcessSorting::MemPercent)
-                    {
-                        if proc_widget_state.process_sorting_type
-                            == processes::ProcessSorting::MemPercent
-                            || proc_widget_state.process_sorting_type
-                                == processes::ProcessSorting::Mem
-                        {
-                            if mem_percent_state {
-                                proc_widget_state.process_sorting_type =
-                                    processes::ProcessSorting::MemPercent;
-                            } else {
-                                proc_widget_state.process_sorting_type =
-                                    processes::ProcessSorting::Mem;
-                            }
-                        }
-                    }
-
+                    proc_widget_state.set_mem_type(proc_widget_state.mem_type.next());
                     proc_widget_state.requires_redraw = true;
                     self.proc_state.force_update = Some(self.current_widget.widget_id);
                 }
@@ -676,6 +1211,24 @@ impl App {
         }
     }
 
+    /// Switches the search between filtering out processes that don't match, and only
+    /// highlighting the ones that do.
+    pub fn toggle_search_highlight_only(&mut self) {
+        let is_in_search_widget = self.is_in_search_widget();
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get_mut(&(self.current_widget.widget_id - 1))
+        {
+            if is_in_search_widget && proc_widget_state.is_search_enabled() {
+                proc_widget_state
+                    .process_search_state
+                    .search_toggle_highlight_only();
+                self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
+            }
+        }
+    }
+
     pub fn toggle_tree_mode(&mut self) {
         if let Some(proc_widget_state) = self
             .proc_state
@@ -688,6 +1241,7 @@ impl App {
             if proc_widget_state.is_tree_mode {
                 // Disable grouping if so!
                 proc_widget_state.is_grouped = false;
+                proc_widget_state.is_grouped_by_pod = false;
 
                 proc_widget_state
                     .columns
@@ -698,6 +1252,33 @@ impl App {
                     .columns
                     .try_enable(&processes::ProcessSorting::User);
 
+                if self.app_config_fields.show_gpu_process_columns {
+                    proc_widget_state
+                        .columns
+                        .try_enable(&processes::ProcessSorting::GpuPercent);
+                    proc_widget_state
+                        .columns
+                        .try_enable(&processes::ProcessSorting::GpuMem);
+                }
+
+                if self.app_config_fields.show_tcp_process_columns {
+                    proc_widget_state
+                        .columns
+                        .try_enable(&processes::ProcessSorting::TcpRetransmits);
+                    proc_widget_state
+                        .columns
+                        .try_enable(&processes::ProcessSorting::TcpRtt);
+                }
+
+                if self.app_config_fields.show_k8s_process_columns {
+                    proc_widget_state
+                        .columns
+                        .try_enable(&processes::ProcessSorting::Pod);
+                    proc_widget_state
+                        .columns
+                        .try_enable(&processes::ProcessSorting::Namespace);
+                }
+
                 proc_widget_state
                     .columns
                     .try_disable(&processes::ProcessSorting::Count);
@@ -742,17 +1323,29 @@ impl App {
                 self.delete_dialog_state.is_showing_dd = false;
             }
             self.is_force_redraw = true;
+        } else if self.column_picker_state.is_showing_picker {
+            self.toggle_picker_column();
+        } else if self.disk_usage_state.is_showing_disk_usage {
+            self.enter_disk_usage_entry();
+        } else if self.affinity_state.is_showing_affinity {
+            self.apply_affinity();
+        } else if self.renice_state.is_showing_renice {
+            self.apply_renice();
         } else if !self.is_in_dialog() {
-            if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
-                if let Some(proc_widget_state) = self
-                    .proc_state
-                    .widget_states
-                    .get_mut(&(self.current_widget.widget_id - 2))
-                {
-                    proc_widget_state.update_sorting_with_columns();
-                    self.proc_state.force_update = Some(self.current_widget.widget_id - 2);
-                    self.toggle_sort();
+            match self.current_widget.widget_type {
+                BottomWidgetType::ProcSort => {
+                    if let Some(proc_widget_state) = self
+                        .proc_state
+                        .widget_states
+                        .get_mut(&(self.current_widget.widget_id - 2))
+                    {
+                        proc_widget_state.update_sorting_with_columns();
+                        self.proc_state.force_update = Some(self.current_widget.widget_id - 2);
+                        self.toggle_sort();
+                    }
                 }
+                BottomWidgetType::Disk => self.open_disk_usage(),
+                _ => {}
             }
         }
     }
@@ -812,7 +1405,11 @@ impl App {
     }
 
     pub fn on_backspace(&mut self) {
-        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+        if self.disk_usage_state.is_showing_disk_usage {
+            self.leave_disk_usage_entry();
+        } else if self.renice_state.is_showing_renice {
+            self.renice_state.niceness.pop();
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             let is_in_search_widget = self.is_in_search_widget();
             if let Some(proc_widget_state) = self
                 .proc_state
@@ -915,6 +1512,16 @@ impl App {
             self.decrement_position_count();
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if self.column_picker_state.is_showing_picker {
+            self.move_picker_selection(true);
+        } else if self.disk_usage_state.is_showing_disk_usage {
+            self.move_disk_usage_selection(true);
+        } else if self.affinity_state.is_showing_affinity {
+            self.move_affinity_selection(true);
+        } else if self.renice_state.is_showing_renice {
+            self.step_renice_niceness(true);
+        } else if self.threads_state.is_showing_threads {
+            self.scroll_threads(true);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_os = "windows")]
             self.on_right_key();
@@ -937,6 +1544,16 @@ impl App {
             self.increment_position_count();
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if self.column_picker_state.is_showing_picker {
+            self.move_picker_selection(false);
+        } else if self.disk_usage_state.is_showing_disk_usage {
+            self.move_disk_usage_selection(false);
+        } else if self.affinity_state.is_showing_affinity {
+            self.move_affinity_selection(false);
+        } else if self.renice_state.is_showing_renice {
+            self.step_renice_niceness(false);
+        } else if self.threads_state.is_showing_threads {
+            self.scroll_threads(false);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_os = "windows")]
             self.on_left_key();
@@ -957,6 +1574,11 @@ impl App {
         if self.is_config_open {
         } else if !self.is_in_dialog() {
             match self.current_widget.widget_type {
+                _ if self.crosshair_time.is_some()
+                    && self.current_widget.widget_type.is_widget_graph() =>
+                {
+                    self.move_crosshair(true);
+                }
                 BottomWidgetType::ProcSearch => {
                     let is_in_search_widget = self.is_in_search_widget();
                     if let Some(proc_widget_state) = self
@@ -999,6 +1621,8 @@ impl App {
                 }
                 _ => {}
             }
+        } else if self.disk_usage_state.is_showing_disk_usage {
+            self.leave_disk_usage_entry();
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -1029,6 +1653,11 @@ impl App {
         if self.is_config_open {
         } else if !self.is_in_dialog() {
             match self.current_widget.widget_type {
+                _ if self.crosshair_time.is_some()
+                    && self.current_widget.widget_type.is_widget_graph() =>
+                {
+                    self.move_crosshair(false);
+                }
                 BottomWidgetType::ProcSearch => {
                     let is_in_search_widget = self.is_in_search_widget();
                     if let Some(proc_widget_state) = self
@@ -1075,6 +1704,8 @@ impl App {
                 }
                 _ => {}
             }
+        } else if self.disk_usage_state.is_showing_disk_usage {
+            self.enter_disk_usage_entry();
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -1303,43 +1934,82 @@ impl App {
         }
     }
 
-    pub fn start_killing_process(&mut self) {
-        self.reset_multi_tap_keys();
-
-        if let Some(proc_widget_state) = self
+    /// Returns the name and PIDs of the process selected in the current process widget.  If
+    /// grouped, this covers every process in the group.
+    fn get_selected_process(&self) -> Option<(String, Vec<Pid>)> {
+        let proc_widget_state = self
             .proc_state
             .widget_states
-            .get(&self.current_widget.widget_id)
-        {
-            if let Some(corresponding_filtered_process_list) = self
-                .canvas_data
-                .finalized_process_data_map
-                .get(&self.current_widget.widget_id)
-            {
-                if proc_widget_state.scroll_state.current_scroll_position
-                    < corresponding_filtered_process_list.len()
-                {
-                    let current_process: (String, Vec<Pid>);
-                    if self.is_grouped(self.current_widget.widget_id) {
-                        if let Some(process) = &corresponding_filtered_process_list
-                            .get(proc_widget_state.scroll_state.current_scroll_position)
-                        {
-                            current_process = (process.name.to_string(), process.group_pids.clone())
-                        } else {
-                            return;
-                        }
-                    } else {
-                        let process = corresponding_filtered_process_list
-                            [proc_widget_state.scroll_state.current_scroll_position]
-                            .clone();
-                        current_process = (process.name.clone(), vec![process.pid])
-                    };
+            .get(&self.current_widget.widget_id)?;
+        let process = self
+            .canvas_data
+            .finalized_process_data_map
+            .get(&self.current_widget.widget_id)?
+            .get(proc_widget_state.scroll_state.current_scroll_position)?;
+
+        if self.is_grouped(self.current_widget.widget_id) {
+            Some((process.name.to_string(), process.group_pids.clone()))
+        } else {
+            Some((process.name.to_string(), vec![process.pid]))
+        }
+    }
 
-                    self.to_delete_process_list = Some(current_process);
-                    self.delete_dialog_state.is_showing_dd = true;
-                    self.is_determining_widget_boundary = true;
-                }
+    /// Returns the processes marked in the current process widget, if there are any, or the
+    /// selected process otherwise.  Marked processes are named by how many there are.
+    fn get_target_processes(&self) -> Option<(String, Vec<Pid>)> {
+        match self
+            .proc_state
+            .get_widget_state(self.current_widget.widget_id)
+        {
+            Some(proc_widget_state) if !proc_widget_state.marked_pids.is_empty() => {
+                let mut pids = proc_widget_state
+                    .marked_pids
+                    .iter()
+                    .copied()
+                    .collect::<Vec<_>>();
+                pids.sort_unstable();
+                Some((format!("{} marked processes", pids.len()), pids))
             }
+            _ => self.get_selected_process(),
+        }
+    }
+
+    fn has_marked_processes(&self) -> bool {
+        self.proc_state
+            .get_widget_state(self.current_widget.widget_id)
+            .map(|proc_widget_state| !proc_widget_state.marked_pids.is_empty())
+            .unwrap_or(false)
+    }
+
+    pub fn start_killing_process(&mut self) {
+        self.reset_multi_tap_keys();
+
+        if let Some(current_process) = self.get_target_processes() {
+            self.delete_dialog_state.is_killing_marked = self.has_marked_processes();
+            self.to_delete_process_list = Some(current_process);
+            self.delete_dialog_state.is_showing_dd = true;
+            self.is_determining_widget_boundary = true;
+        }
+    }
+
+    /// Suspends or resumes the selected process, or every process in the selected group.  If any
+    /// processes are marked, those are suspended or resumed instead.
+    fn on_process_action(&mut self, action: process_killer::ProcessAction) {
+        if let Some((process_name, pids)) = self.get_target_processes() {
+            let result = pids
+                .iter()
+                .try_for_each(|pid| process_killer::control_process(*pid, action));
+
+            let message = match result {
+                Ok(()) => format!("{} {}", action.as_done(), process_name),
+                Err(err) => format!(
+                    "Failed to {} {}: {}",
+                    action.as_command(),
+                    process_name,
+                    err
+                ),
+            };
+            self.status_message = Some((message, Instant::now()));
         }
     }
 
@@ -1419,8 +2089,12 @@ impl App {
             self.handle_char(caught_char);
         } else if self.help_dialog_state.is_showing_help {
             match caught_char {
-                '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
-                    let potential_index = caught_char.to_digit(10);
+                '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
+                    // 0 comes after 9, like on the keyboard.
+                    let potential_index = match caught_char {
+                        '0' => Some(10),
+                        _ => caught_char.to_digit(10),
+                    };
                     if let Some(potential_index) = potential_index {
                         if (potential_index as usize) < self.help_dialog_state.index_shortcuts.len()
                         {
@@ -1433,10 +2107,52 @@ impl App {
                 'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
                 _ => {}
             }
-        } else if self.delete_dialog_state.is_showing_dd {
+        } else if self.column_picker_state.is_showing_picker {
             match caught_char {
-                'h' => self.on_left_key(),
-                'j' => self.on_down_key(),
+                'k' => self.move_picker_selection(true),
+                'j' => self.move_picker_selection(false),
+                'K' => self.move_picker_column(true),
+                'J' => self.move_picker_column(false),
+                ' ' => self.toggle_picker_column(),
+                'o' => self.close_column_picker(),
+                _ => {}
+            }
+        } else if self.disk_usage_state.is_showing_disk_usage {
+            match caught_char {
+                'k' => self.move_disk_usage_selection(true),
+                'j' => self.move_disk_usage_selection(false),
+                'l' => self.enter_disk_usage_entry(),
+                'h' => self.leave_disk_usage_entry(),
+                _ => {}
+            }
+        } else if self.affinity_state.is_showing_affinity {
+            match caught_char {
+                'k' => self.move_affinity_selection(true),
+                'j' => self.move_affinity_selection(false),
+                ' ' => self.toggle_affinity_cpu(),
+                'a' => self.select_all_affinity_cpus(),
+                'b' => self.close_affinity_dialog(),
+                _ => {}
+            }
+        } else if self.renice_state.is_showing_renice {
+            match caught_char {
+                'k' => self.step_renice_niceness(true),
+                'j' => self.step_renice_niceness(false),
+                'r' => self.close_renice_dialog(),
+                _ => self.type_renice_char(caught_char),
+            }
+        } else if self.threads_state.is_showing_threads {
+            match caught_char {
+                'k' => self.scroll_threads(true),
+                'j' => self.scroll_threads(false),
+                'g' | 'G' => self.handle_char(caught_char),
+                'T' => self.close_threads_dialog(),
+                _ => {}
+            }
+        } else if self.delete_dialog_state.is_showing_dd {
+            match caught_char {
+                'h' => self.on_left_key(),
+                'j' => self.on_down_key(),
                 'k' => self.on_up_key(),
                 'l' => self.on_right_key(),
                 #[cfg(target_family = "unix")]
@@ -1517,6 +2233,12 @@ impl App {
                     self.data_collection.set_frozen_time();
                 }
             }
+            'E' => self.save_snapshot(),
+            'y' => self.copy_process_rows(false),
+            'Y' => self.copy_process_rows(true),
+            '[' => self.shift_frozen_time(true),
+            ']' => self.shift_frozen_time(false),
+            'X' => self.toggle_crosshair(),
             'C' => {
                 // self.open_config(),
             }
@@ -1541,14 +2263,7 @@ impl App {
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
                         proc_widget_state.columns.set_to_sorted_index_from_type(
-                            &(if proc_widget_state
-                                .columns
-                                .is_enabled(&processes::ProcessSorting::MemPercent)
-                            {
-                                processes::ProcessSorting::MemPercent
-                            } else {
-                                processes::ProcessSorting::Mem
-                            }),
+                            &proc_widget_state.mem_type.sorting_type(),
                         );
                         proc_widget_state.update_sorting_with_columns();
                         self.proc_state.force_update = Some(self.current_widget.widget_id);
@@ -1578,23 +2293,7 @@ impl App {
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        proc_widget_state.is_using_command = !proc_widget_state.is_using_command;
-                        proc_widget_state
-                            .toggle_command_and_name(proc_widget_state.is_using_command);
-
-                        match &proc_widget_state.process_sorting_type {
-                            processes::ProcessSorting::Command
-                            | processes::ProcessSorting::ProcessName => {
-                                if proc_widget_state.is_using_command {
-                                    proc_widget_state.process_sorting_type =
-                                        processes::ProcessSorting::Command;
-                                } else {
-                                    proc_widget_state.process_sorting_type =
-                                        processes::ProcessSorting::ProcessName;
-                                }
-                            }
-                            _ => {}
-                        }
+                        proc_widget_state.set_name_type(proc_widget_state.name_type.next());
                         proc_widget_state.requires_redraw = true;
                         self.proc_state.force_update = Some(self.current_widget.widget_id);
                     }
@@ -1607,11 +2306,7 @@ impl App {
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
                         proc_widget_state.columns.set_to_sorted_index_from_type(
-                            &(if proc_widget_state.is_using_command {
-                                processes::ProcessSorting::Command
-                            } else {
-                                processes::ProcessSorting::ProcessName
-                            }),
+                            &proc_widget_state.name_type.sorting_type(),
                         );
                         proc_widget_state.update_sorting_with_columns();
                         self.proc_state.force_update = Some(self.current_widget.widget_id);
@@ -1622,18 +2317,59 @@ impl App {
                 self.help_dialog_state.is_showing_help = true;
                 self.is_force_redraw = true;
             }
+            'o' => self.open_column_picker(),
+            'b' => self.open_affinity_dialog(),
+            'T' => self.open_threads_dialog(),
+            'F' => self.toggle_pinned_process(),
             'H' | 'A' => self.move_widget_selection(&WidgetDirection::Left),
             'L' | 'D' => self.move_widget_selection(&WidgetDirection::Right),
             'K' | 'W' => self.move_widget_selection(&WidgetDirection::Up),
             'J' | 'S' => self.move_widget_selection(&WidgetDirection::Down),
-            't' => self.toggle_tree_mode(),
+            't' => match self.current_widget.widget_type {
+                BottomWidgetType::Disk => self.toggle_disk_fs_grouping(),
+                _ => self.toggle_tree_mode(),
+            },
             '+' => self.on_plus(),
             '-' => self.on_minus(),
             '=' => self.reset_zoom(),
             'e' => self.toggle_expand_widget(),
+            'z' => self.hide_current_widget(),
+            'Z' => self.show_hidden_widgets(),
             's' => self.toggle_sort(),
             'I' => self.invert_sort(),
+            'a' => self.toggle_secondary_sort(),
             '%' => self.toggle_percentages(),
+            'N' => self.toggle_current_cpu_total(),
+            'u' | 'x' | 'r' => match self.current_widget.widget_type {
+                BottomWidgetType::Services => self.on_service_action(match caught_char {
+                    'u' => services::ServiceAction::Start,
+                    'x' => services::ServiceAction::Stop,
+                    _ => services::ServiceAction::Restart,
+                }),
+                BottomWidgetType::Docker => self.on_container_action(match caught_char {
+                    'u' => docker::ContainerAction::Start,
+                    'x' => docker::ContainerAction::Stop,
+                    _ => docker::ContainerAction::Restart,
+                }),
+                BottomWidgetType::Proc => match caught_char {
+                    'u' => self.on_process_action(process_killer::ProcessAction::Resume),
+                    'x' => self.on_process_action(process_killer::ProcessAction::Suspend),
+                    _ => self.open_renice_dialog(),
+                },
+                _ => {}
+            },
+            'B' => self.toggle_network_unit(),
+            'U' => match self.current_widget.widget_type {
+                BottomWidgetType::Proc => self.toggle_own_processes(),
+                BottomWidgetType::Temp => self.cycle_temperature_unit(),
+                _ => {}
+            },
+            'i' => self.toggle_disk_io_granularity(),
+            'w' => self.show_disk_writers(),
+            'O' => self.toggle_network_scale(),
+            'M' => self.toggle_network_max_pin(),
+            'V' => self.toggle_network_ip_versions(),
+            'v' => self.toggle_cpu_legend_expanded(),
             ' ' => self.on_space(),
             _ => {}
         }
@@ -1645,7 +2381,830 @@ impl App {
         }
     }
 
-    pub fn on_space(&mut self) {}
+    /// Marks the selected process, or every process in the selected group, and moves down to the
+    /// next one.  If they're all marked already, they're unmarked instead.
+    pub fn on_space(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some((_name, pids)) = self.get_selected_process() {
+                if let Some(proc_widget_state) = self
+                    .proc_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    let marked_pids = &mut proc_widget_state.marked_pids;
+                    if pids.iter().all(|pid| marked_pids.contains(pid)) {
+                        for pid in &pids {
+                            marked_pids.remove(pid);
+                        }
+                    } else {
+                        marked_pids.extend(pids);
+                    }
+                }
+                self.increment_position_count();
+            }
+        }
+    }
+
+    fn open_column_picker(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if self
+                .proc_state
+                .get_widget_state(self.current_widget.widget_id)
+                .is_some()
+            {
+                self.column_picker_state = AppColumnPickerState {
+                    is_showing_picker: true,
+                    widget_id: self.current_widget.widget_id,
+                    selected_index: 0,
+                };
+                self.is_force_redraw = true;
+            }
+        }
+    }
+
+    fn close_column_picker(&mut self) {
+        self.column_picker_state.is_showing_picker = false;
+        self.is_force_redraw = true;
+    }
+
+    fn move_picker_selection(&mut self, is_up: bool) {
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .get_widget_state(self.column_picker_state.widget_id)
+        {
+            let num_slots = proc_widget_state.columns.get_picker_slots().len();
+            let selected_index = &mut self.column_picker_state.selected_index;
+            if is_up {
+                *selected_index = selected_index.saturating_sub(1);
+            } else if *selected_index + 1 < num_slots {
+                *selected_index += 1;
+            }
+        }
+    }
+
+    /// Shows or hides the column currently selected in the column picker.
+    fn toggle_picker_column(&mut self) {
+        let widget_id = self.column_picker_state.widget_id;
+        let selected_index = self.column_picker_state.selected_index;
+        if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget_id) {
+            if let Some(slot) = proc_widget_state
+                .columns
+                .get_picker_slots()
+                .get(selected_index)
+            {
+                proc_widget_state.columns.toggle_slot_hidden(slot);
+                proc_widget_state.on_columns_changed();
+                self.proc_state.force_update = Some(widget_id);
+
+                match slot {
+                    processes::ProcessSorting::FdCount => {
+                        self.collection_thread_events.push(
+                            ThreadControlEvent::UpdateCollectFdCounts(self.is_showing_fd_counts()),
+                        );
+                    }
+                    processes::ProcessSorting::Swap
+                    | processes::ProcessSorting::Capabilities
+                    | processes::ProcessSorting::Seccomp => {
+                        self.collection_thread_events.push(
+                            ThreadControlEvent::UpdateCollectProcessStatus(
+                                self.is_showing_process_status(),
+                            ),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Whether any process widget is showing open file descriptor counts, which are only
+    /// collected while they're shown.
+    pub fn is_showing_fd_counts(&self) -> bool {
+        self.proc_state
+            .widget_states
+            .values()
+            .any(|proc_widget_state| {
+                proc_widget_state
+                    .columns
+                    .is_shown(&processes::ProcessSorting::FdCount)
+            })
+    }
+
+    /// Whether any process widget is showing the swap, capabilities, or seccomp columns, which
+    /// are read from each process' status file only while one is shown.
+    pub fn is_showing_process_status(&self) -> bool {
+        self.proc_state
+            .widget_states
+            .values()
+            .any(|proc_widget_state| {
+                let columns = &proc_widget_state.columns;
+                columns.is_shown(&processes::ProcessSorting::Swap)
+                    || columns.is_shown(&processes::ProcessSorting::Capabilities)
+                    || columns.is_shown(&processes::ProcessSorting::Seccomp)
+            })
+    }
+
+    /// Moves the column currently selected in the column picker earlier or later.
+    fn move_picker_column(&mut self, is_earlier: bool) {
+        let widget_id = self.column_picker_state.widget_id;
+        let selected_index = &mut self.column_picker_state.selected_index;
+        if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget_id) {
+            if let Some(slot) = proc_widget_state
+                .columns
+                .get_picker_slots()
+                .get(*selected_index)
+            {
+                if proc_widget_state.columns.move_slot(slot, is_earlier) {
+                    if is_earlier {
+                        *selected_index -= 1;
+                    } else {
+                        *selected_index += 1;
+                    }
+                    proc_widget_state.on_columns_changed();
+                    self.proc_state.force_update = Some(widget_id);
+                }
+            }
+        }
+    }
+
+    /// Opens the affinity dialog for the selected process, showing which CPUs it may run on.
+    fn open_affinity_dialog(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(process) = self.get_selected_process() {
+                // Grouped processes may not agree, so just start from the first one.
+                let (cpus, error) = match process
+                    .1
+                    .first()
+                    .map(|pid| process_affinity::get_process_affinity(*pid))
+                {
+                    Some(Ok(cpus)) => (cpus, None),
+                    Some(Err(err)) => (Vec::new(), Some(err.to_string())),
+                    None => return,
+                };
+
+                self.affinity_state = AppAffinityState {
+                    is_showing_affinity: true,
+                    process,
+                    cpus,
+                    selected_index: 0,
+                    error,
+                };
+                self.is_force_redraw = true;
+            }
+        }
+    }
+
+    fn close_affinity_dialog(&mut self) {
+        self.affinity_state.is_showing_affinity = false;
+        self.is_force_redraw = true;
+    }
+
+    fn move_affinity_selection(&mut self, is_up: bool) {
+        let num_cpus = self.affinity_state.cpus.len();
+        let selected_index = &mut self.affinity_state.selected_index;
+        if is_up {
+            *selected_index = selected_index.saturating_sub(1);
+        } else if *selected_index + 1 < num_cpus {
+            *selected_index += 1;
+        }
+    }
+
+    fn toggle_affinity_cpu(&mut self) {
+        let selected_index = self.affinity_state.selected_index;
+        if let Some(is_allowed) = self.affinity_state.cpus.get_mut(selected_index) {
+            *is_allowed = !*is_allowed;
+        }
+    }
+
+    fn select_all_affinity_cpus(&mut self) {
+        self.affinity_state
+            .cpus
+            .iter_mut()
+            .for_each(|is_allowed| *is_allowed = true);
+    }
+
+    /// Restricts the process in the affinity dialog to the chosen CPUs, closing the dialog if
+    /// that worked.
+    fn apply_affinity(&mut self) {
+        if self.affinity_state.cpus.is_empty() {
+            self.close_affinity_dialog();
+            return;
+        }
+
+        if !self
+            .affinity_state
+            .cpus
+            .iter()
+            .any(|is_allowed| *is_allowed)
+        {
+            self.affinity_state.error = Some("At least one CPU must be selected.".to_string());
+            return;
+        }
+
+        let cpus = &self.affinity_state.cpus;
+        let result = self
+            .affinity_state
+            .process
+            .1
+            .iter()
+            .try_for_each(|pid| process_affinity::set_process_affinity(*pid, cpus));
+
+        match result {
+            Ok(()) => self.close_affinity_dialog(),
+            Err(err) => self.affinity_state.error = Some(err.to_string()),
+        }
+    }
+
+    /// Opens the renice dialog for the marked processes, or the selected one, starting from the
+    /// niceness of the first.
+    fn open_renice_dialog(&mut self) {
+        if let Some(process) = self.get_target_processes() {
+            let (niceness, error) = match process
+                .1
+                .first()
+                .map(|pid| process_killer::get_process_niceness(*pid))
+            {
+                Some(Ok(niceness)) => (niceness.to_string(), None),
+                Some(Err(err)) => (String::new(), Some(err.to_string())),
+                None => return,
+            };
+
+            self.renice_state = AppReniceState {
+                is_showing_renice: true,
+                process,
+                niceness,
+                error,
+            };
+            self.is_force_redraw = true;
+        }
+    }
+
+    fn close_renice_dialog(&mut self) {
+        self.renice_state.is_showing_renice = false;
+        self.is_force_redraw = true;
+    }
+
+    /// Adds a typed character to the niceness, ignoring any that can't be part of one.
+    fn type_renice_char(&mut self, caught_char: char) {
+        let niceness = &mut self.renice_state.niceness;
+        match caught_char {
+            '-' if niceness.is_empty() => niceness.push(caught_char),
+            '0'..='9' if niceness.trim_start_matches('-').len() < 2 => niceness.push(caught_char),
+            _ => {}
+        }
+    }
+
+    /// Raises or lowers the niceness by one, keeping it within the range it can take.
+    fn step_renice_niceness(&mut self, is_up: bool) {
+        let niceness = self.renice_state.niceness.parse::<i32>().unwrap_or(0);
+        let niceness = if is_up { niceness + 1 } else { niceness - 1 };
+        self.renice_state.niceness = niceness
+            .max(process_killer::MIN_NICENESS)
+            .min(process_killer::MAX_NICENESS)
+            .to_string();
+    }
+
+    /// Sets the niceness of the processes in the renice dialog, closing the dialog if that
+    /// worked.
+    fn apply_renice(&mut self) {
+        let niceness = match self.renice_state.niceness.parse::<i32>() {
+            Ok(niceness)
+                if niceness >= process_killer::MIN_NICENESS
+                    && niceness <= process_killer::MAX_NICENESS =>
+            {
+                niceness
+            }
+            _ => {
+                self.renice_state.error = Some(format!(
+                    "The niceness must be from {} to {}.",
+                    process_killer::MIN_NICENESS,
+                    process_killer::MAX_NICENESS
+                ));
+                return;
+            }
+        };
+
+        let (process_name, pids) = &self.renice_state.process;
+        let result = pids
+            .iter()
+            .try_for_each(|pid| process_killer::set_process_niceness(*pid, niceness));
+
+        match result {
+            Ok(()) => {
+                self.status_message = Some((
+                    format!("Set the niceness of {} to {}", process_name, niceness),
+                    Instant::now(),
+                ));
+                self.close_renice_dialog();
+            }
+            Err(err) => self.renice_state.error = Some(err.to_string()),
+        }
+    }
+
+    /// Opens the threads dialog for the selected process.
+    fn open_threads_dialog(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some((name, pids)) = self.get_selected_process() {
+                // Grouped processes are shown as the first one, like the affinity dialog does.
+                if let Some(pid) = pids.first() {
+                    self.threads_state = AppThreadsState {
+                        is_showing_threads: true,
+                        process: (name, *pid),
+                        ..AppThreadsState::default()
+                    };
+                    self.update_threads_dialog();
+                    self.is_force_redraw = true;
+                }
+            }
+        }
+    }
+
+    /// Pins the selected process to the top of the table, or unpins it if it's already pinned.
+    fn toggle_pinned_process(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some((_name, pids)) = self.get_selected_process() {
+                if let (Some(pid), Some(proc_widget_state)) = (
+                    pids.first(),
+                    self.proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id),
+                ) {
+                    let is_pinned = proc_widget_state
+                        .pinned_process
+                        .as_ref()
+                        .map(|pinned_process| pinned_process.pid == *pid)
+                        .unwrap_or(false);
+                    if is_pinned {
+                        proc_widget_state.pinned_process = None;
+                    } else {
+                        // Follow the process to the top of the table.
+                        proc_widget_state.pinned_process = Some(PinnedProcess::new(*pid));
+                        proc_widget_state.scroll_state.current_scroll_position = 0;
+                        proc_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                    self.proc_state.force_update = Some(self.current_widget.widget_id);
+                }
+            }
+        }
+    }
+
+    fn close_threads_dialog(&mut self) {
+        self.threads_state.is_showing_threads = false;
+        self.is_force_redraw = true;
+    }
+
+    fn scroll_threads(&mut self, is_up: bool) {
+        let num_threads = self.threads_state.threads.len();
+        let scroll_index = &mut self.threads_state.scroll_index;
+        if is_up {
+            *scroll_index = scroll_index.saturating_sub(1);
+        } else if *scroll_index + 1 < num_threads {
+            *scroll_index += 1;
+        }
+    }
+
+    /// Re-reads the threads of the process in the threads dialog, if it's open.
+    pub fn update_threads_dialog(&mut self) {
+        if !self.threads_state.is_showing_threads {
+            return;
+        }
+
+        let threads_state = &mut self.threads_state;
+        let now = Instant::now();
+        let elapsed_secs = threads_state
+            .last_update
+            .map(|last_update| now.duration_since(last_update).as_secs_f64())
+            .unwrap_or(0.0);
+        threads_state.last_update = Some(now);
+
+        match process_threads::get_process_threads(
+            threads_state.process.1,
+            &mut threads_state.prev_cpu_times,
+            elapsed_secs,
+        ) {
+            Ok(threads) => {
+                threads_state.scroll_index =
+                    std::cmp::min(threads_state.scroll_index, threads.len().saturating_sub(1));
+                threads_state.threads = threads;
+                threads_state.error = None;
+            }
+            Err(err) => {
+                threads_state.threads.clear();
+                threads_state.scroll_index = 0;
+                threads_state.error = Some(err.to_string());
+            }
+        }
+    }
+
+    /// Starts scanning the disk usage of the mount point of the selected disk.
+    fn open_disk_usage(&mut self) {
+        if let Some(disk_widget_state) = self
+            .disk_state
+            .get_widget_state(self.current_widget.widget_id)
+        {
+            if let Some(mount_point) = self
+                .canvas_data
+                .disk_data
+                .get(disk_widget_state.scroll_state.current_scroll_position)
+                .and_then(|disk_row| disk_row.get(1))
+            {
+                if !mount_point.is_empty() {
+                    let root_path = PathBuf::from(mount_point);
+                    self.disk_usage_state = AppDiskUsageState {
+                        is_showing_disk_usage: true,
+                        root_path: root_path.clone(),
+                        scan: Some(DiskUsageScan::start(root_path)),
+                        selected_path: None,
+                    };
+                    self.is_force_redraw = true;
+                }
+            }
+        }
+    }
+
+    /// Closes the disk usage dialog, cancelling any scan that is still running.
+    fn close_disk_usage(&mut self) {
+        self.disk_usage_state.is_showing_disk_usage = false;
+        self.disk_usage_state.scan = None;
+        self.is_force_redraw = true;
+    }
+
+    fn move_disk_usage_selection(&mut self, is_up: bool) {
+        if let Some(scan) = &self.disk_usage_state.scan {
+            let entries = scan.get_progress().entries;
+            let selected_index = self.disk_usage_state.get_selected_index(&entries);
+            let new_index = if is_up {
+                selected_index.saturating_sub(1)
+            } else {
+                std::cmp::min(selected_index + 1, entries.len().saturating_sub(1))
+            };
+
+            if let Some(entry) = entries.get(new_index) {
+                self.disk_usage_state.selected_path = Some(entry.path.clone());
+            }
+        }
+    }
+
+    /// Drills down into the selected directory in the disk usage dialog.
+    fn enter_disk_usage_entry(&mut self) {
+        let selected_entry = self.disk_usage_state.scan.as_ref().and_then(|scan| {
+            let entries = scan.get_progress().entries;
+            entries
+                .get(self.disk_usage_state.get_selected_index(&entries))
+                .cloned()
+        });
+
+        if let Some(selected_entry) = selected_entry {
+            if selected_entry.is_dir {
+                self.disk_usage_state.scan = Some(DiskUsageScan::start(selected_entry.path));
+                self.disk_usage_state.selected_path = None;
+            }
+        }
+    }
+
+    /// Goes back up a directory in the disk usage dialog, as long as we stay within the mount point.
+    fn leave_disk_usage_entry(&mut self) {
+        let parent_path = self.disk_usage_state.scan.as_ref().and_then(|scan| {
+            if scan.get_path() == self.disk_usage_state.root_path {
+                None
+            } else {
+                scan.get_path().parent().map(|parent| parent.to_path_buf())
+            }
+        });
+
+        if let Some(parent_path) = parent_path {
+            // Keep the directory we just left selected.
+            self.disk_usage_state.selected_path = self
+                .disk_usage_state
+                .scan
+                .as_ref()
+                .map(|scan| scan.get_path().to_path_buf());
+            self.disk_usage_state.scan = Some(DiskUsageScan::start(parent_path));
+        }
+    }
+
+    /// Scrolls the graphs of a frozen display earlier or later in time.
+    pub fn shift_frozen_time(&mut self, is_earlier: bool) {
+        if self.is_frozen
+            && self
+                .data_collection
+                .shift_frozen_time(self.app_config_fields.time_interval, is_earlier)
+        {
+            let widget_id = self.current_widget.widget_id;
+            self.cpu_state.force_update = Some(widget_id);
+            self.mem_state.force_update = Some(widget_id);
+            self.net_state.force_update = Some(widget_id);
+            self.custom_state.force_update = Some(widget_id);
+            self.sched_state.force_update = Some(widget_id);
+            self.power_state.force_update = Some(widget_id);
+        }
+    }
+
+    /// Shows or hides the crosshair.  It starts at the latest data shown.
+    pub fn toggle_crosshair(&mut self) {
+        self.crosshair_time = if self.crosshair_time.is_some() {
+            None
+        } else {
+            Some(self.data_collection.get_graph_end_time(self.is_frozen))
+        };
+    }
+
+    /// Moves the crosshair to the previous or next collected entry.  It can't go past the latest
+    /// data shown, or before the oldest data kept.
+    fn move_crosshair(&mut self, is_earlier: bool) {
+        if let Some(crosshair_time) = self.crosshair_time {
+            let graph_end_time = self.data_collection.get_graph_end_time(self.is_frozen);
+            let mut instants = self
+                .data_collection
+                .timed_data_vec
+                .iter()
+                .map(|(instant, _)| *instant)
+                .filter(|instant| *instant <= graph_end_time);
+
+            let new_crosshair_time = if is_earlier {
+                instants.rev().find(|instant| *instant < crosshair_time)
+            } else {
+                instants.find(|instant| *instant > crosshair_time)
+            };
+
+            if let Some(new_crosshair_time) = new_crosshair_time {
+                self.crosshair_time = Some(new_crosshair_time);
+            }
+        }
+    }
+
+    /// Saves the latest harvested data to a file, and says where it went (or why it didn't).
+    pub fn save_snapshot(&mut self) {
+        let snapshot_dir = self
+            .app_config_fields
+            .snapshot_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let status_message = match self.data_collection.save_snapshot(&snapshot_dir) {
+            Ok(path) => format!("Saved a snapshot to {}", path.display()),
+            Err(err) => format!("Failed to save a snapshot: {}", err),
+        };
+        self.status_message = Some((status_message, Instant::now()));
+    }
+
+    /// Copies the selected process, or every process in the table, to the clipboard as
+    /// tab-separated text with a header row.  The rows are copied as shown, so the current
+    /// filter, sort, and columns all apply.
+    fn copy_process_rows(&mut self, is_copying_all: bool) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            let widget_id = self.current_widget.widget_id;
+            if let (Some(proc_widget_state), Some(finalized_process_data)) = (
+                self.proc_state.widget_states.get(&widget_id),
+                self.canvas_data.finalized_process_data_map.get(&widget_id),
+            ) {
+                let processes = if is_copying_all {
+                    &finalized_process_data[..]
+                } else {
+                    let position = proc_widget_state.scroll_state.current_scroll_position;
+                    finalized_process_data
+                        .get(position..=position)
+                        .unwrap_or_default()
+                };
+                if processes.is_empty() {
+                    return;
+                }
+
+                // Only the visible rows are formatted for drawing, so format the copied ones here.
+                let rows = stringify_process_data(
+                    proc_widget_state,
+                    processes,
+                    &self.app_config_fields.number_format,
+                );
+
+                let mut text = proc_widget_state
+                    .columns
+                    .get_shown_columns()
+                    .iter()
+                    .map(|column| column.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\t");
+                text.push('\n');
+                for (row, _) in &rows {
+                    text.push_str(
+                        &row.iter()
+                            .map(|(cell, _)| cell.as_str())
+                            .collect::<Vec<_>>()
+                            .join("\t"),
+                    );
+                    text.push('\n');
+                }
+
+                let status_message = match clipboard::copy_to_clipboard(&text) {
+                    Ok(()) if rows.len() == 1 => "Copied the selected process".to_string(),
+                    Ok(()) => format!("Copied {} processes", rows.len()),
+                    Err(err) => format!("Failed to copy to the clipboard: {}", err),
+                };
+                self.status_message = Some((status_message, Instant::now()));
+            }
+        }
+    }
+
+    /// Returns the status message, if it was set recently enough to still be shown.
+    pub fn get_status_message(&self) -> Option<&str> {
+        self.status_message
+            .as_ref()
+            .filter(|(_, set_instant)| set_instant.elapsed() < STATUS_MESSAGE_DURATION)
+            .map(|(status_message, _)| status_message.as_str())
+    }
+
+    /// Toggles whether process CPU usage is relative to the current total CPU usage.  The
+    /// collection thread picks this up on the next harvest, so no restart is needed.
+    pub fn toggle_current_cpu_total(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            self.app_config_fields.use_current_cpu_total =
+                !self.app_config_fields.use_current_cpu_total;
+            self.collection_thread_events
+                .push(ThreadControlEvent::UpdateConfig(Box::new(
+                    self.app_config_fields.clone(),
+                )));
+        }
+    }
+
+    /// Shows or hides each CPU's average and max usage in the legend.  The CPU widget is
+    /// selected through either its graph or its legend, so both are handled.
+    pub fn toggle_cpu_legend_expanded(&mut self) {
+        let cpu_widget_id = match self.current_widget.widget_type {
+            BottomWidgetType::Cpu => self.current_widget.widget_id,
+            BottomWidgetType::CpuLegend => self.current_widget.widget_id - 1,
+            _ => return,
+        };
+
+        if let Some(cpu_widget_state) = self.cpu_state.get_mut_widget_state(cpu_widget_id) {
+            cpu_widget_state.is_legend_expanded = !cpu_widget_state.is_legend_expanded;
+            // The legend's columns need to be worked out again.
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Toggles only showing processes run by the current user.  Only supported on Unix-like
+    /// systems, as that's the only place process owners are known.
+    pub fn toggle_own_processes(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                proc_widget_state.is_showing_own_processes =
+                    !proc_widget_state.is_showing_own_processes;
+                self.proc_state.force_update = Some(self.current_widget.widget_id);
+            }
+        }
+    }
+
+    /// Cycles the temperature unit between Celsius, Fahrenheit, and Kelvin.  Sensors given their
+    /// own unit in the config file keep it.
+    pub fn cycle_temperature_unit(&mut self) {
+        if let BottomWidgetType::Temp = self.current_widget.widget_type {
+            self.app_config_fields.temperature_type = match self.app_config_fields.temperature_type
+            {
+                temperature::TemperatureType::Celsius => temperature::TemperatureType::Fahrenheit,
+                temperature::TemperatureType::Fahrenheit => temperature::TemperatureType::Kelvin,
+                temperature::TemperatureType::Kelvin => temperature::TemperatureType::Celsius,
+            };
+            self.temp_state.force_update = Some(self.current_widget.widget_id);
+        }
+    }
+
+    /// Switches the disk widget between the I/O of each partition and of the device it's on.
+    pub fn toggle_disk_io_granularity(&mut self) {
+        if let BottomWidgetType::Disk = self.current_widget.widget_type {
+            self.app_config_fields.disk_io_per_device = !self.app_config_fields.disk_io_per_device;
+            self.disk_state.force_update = Some(self.current_widget.widget_id);
+        }
+    }
+
+    /// Switches the disk widget between listing disks by name and grouping them by filesystem type.
+    pub fn toggle_disk_fs_grouping(&mut self) {
+        if let BottomWidgetType::Disk = self.current_widget.widget_type {
+            self.app_config_fields.group_disks_by_fs = !self.app_config_fields.group_disks_by_fs;
+            self.disk_state.force_update = Some(self.current_widget.widget_id);
+        }
+    }
+
+    /// Switches from the disk widget to the first process widget shown, searching for processes that
+    /// are writing and sorting them by how much.  Which disk a process writes to isn't known, so
+    /// this shows writers to every disk, heaviest first.
+    pub fn show_disk_writers(&mut self) {
+        if let BottomWidgetType::Disk = self.current_widget.widget_type {
+            let proc_widget = self
+                .widget_map
+                .values()
+                .filter(|widget| {
+                    matches!(widget.widget_type, BottomWidgetType::Proc)
+                        && !self.hidden_widgets.contains(&widget.widget_id)
+                })
+                .min_by_key(|widget| widget.widget_id)
+                .cloned();
+
+            if let Some(proc_widget) = proc_widget {
+                if let Some(proc_widget_state) =
+                    self.proc_state.get_mut_widget_state(proc_widget.widget_id)
+                {
+                    proc_widget_state.set_search_query(DISK_WRITERS_QUERY);
+
+                    let write_sorting = processes::ProcessSorting::WritePerSecond;
+                    if let Some(mapping) = proc_widget_state
+                        .columns
+                        .column_mapping
+                        .get_mut(&write_sorting)
+                    {
+                        mapping.hidden = false;
+                    }
+                    proc_widget_state
+                        .columns
+                        .set_to_sorted_index_from_type(&write_sorting);
+                    proc_widget_state.process_sorting_type = write_sorting;
+                    proc_widget_state.is_process_sort_descending = true;
+                    proc_widget_state.requires_redraw = true;
+                    self.proc_state.force_update = Some(proc_widget.widget_id);
+                }
+
+                self.current_widget = proc_widget;
+                self.is_expanded = false;
+                self.is_force_redraw = true;
+            }
+        }
+    }
+
+    pub fn toggle_network_unit(&mut self) {
+        if let BottomWidgetType::Net | BottomWidgetType::BasicNet = self.current_widget.widget_type
+        {
+            self.app_config_fields.network_unit_type =
+                match self.app_config_fields.network_unit_type {
+                    DataUnit::Bit => DataUnit::Byte,
+                    DataUnit::Byte => DataUnit::Bit,
+                };
+            self.net_state.force_update = Some(self.current_widget.widget_id);
+        }
+    }
+
+    pub fn toggle_network_scale(&mut self) {
+        if let BottomWidgetType::Net = self.current_widget.widget_type {
+            self.app_config_fields.network_scale_type =
+                match self.app_config_fields.network_scale_type {
+                    AxisScaling::Linear => AxisScaling::Log,
+                    AxisScaling::Log => AxisScaling::Linear,
+                };
+            self.net_state.force_update = Some(self.current_widget.widget_id);
+        }
+    }
+
+    /// Pins the top of the current network graph to `network_max`, or to the highest rate shown
+    /// if that isn't set.  If the graph is already pinned, it goes back to scaling to fit.
+    pub fn toggle_network_max_pin(&mut self) {
+        if let BottomWidgetType::Net = self.current_widget.widget_type {
+            let network_max = self.app_config_fields.network_max;
+            let current_time = match self.data_collection.frozen_instant {
+                Some(frozen_instant) if self.is_frozen => frozen_instant,
+                _ => self.data_collection.current_instant,
+            };
+            let timed_data_vec = &self.data_collection.timed_data_vec;
+
+            if let Some(net_widget_state) = self
+                .net_state
+                .widget_states
+                .get_mut(&self.current_widget.widget_id)
+            {
+                net_widget_state.max_pin = if net_widget_state.max_pin.is_some() {
+                    None
+                } else if network_max.is_some() {
+                    network_max
+                } else {
+                    let display_time = u128::from(net_widget_state.current_display_time);
+                    let highest_rate = timed_data_vec
+                        .iter()
+                        .filter(|(time, _data)| {
+                            current_time.duration_since(*time).as_millis() <= display_time
+                        })
+                        .map(|(_time, data)| data.rx_data.max(data.tx_data))
+                        .fold(0.0, f64::max);
+
+                    if highest_rate > 0.0 {
+                        Some(highest_rate)
+                    } else {
+                        None
+                    }
+                };
+            }
+        }
+    }
+
+    /// Toggles splitting the current network widget's legend by IP version.
+    pub fn toggle_network_ip_versions(&mut self) {
+        if let BottomWidgetType::Net = self.current_widget.widget_type {
+            if let Some(net_widget_state) = self
+                .net_state
+                .widget_states
+                .get_mut(&self.current_widget.widget_id)
+            {
+                net_widget_state.is_showing_ip_versions = !net_widget_state.is_showing_ip_versions;
+            }
+        }
+    }
 
     pub fn open_config_screen(&mut self) {
         self.is_config_open = true;
@@ -1680,35 +3239,112 @@ impl App {
     //     Ok(())
     // }
 
+    /// Kills the processes in the kill dialog.  These are usually from the process widget, but
+    /// may also be from a process rule, so this doesn't care which widget is selected.
     pub fn kill_highlighted_process(&mut self) -> Result<()> {
-        if let BottomWidgetType::Proc = self.current_widget.widget_type {
-            if let Some(current_selected_processes) = &self.to_delete_process_list {
+        if let Some(current_selected_processes) = &self.to_delete_process_list {
+            #[cfg(target_family = "unix")]
+            let signal = match self.delete_dialog_state.selected_signal {
+                KillSignal::Kill(sig) => sig,
+                KillSignal::Cancel => 15, // should never happen, so just TERM
+            };
+            for pid in &current_selected_processes.1 {
                 #[cfg(target_family = "unix")]
-                let signal = match self.delete_dialog_state.selected_signal {
-                    KillSignal::Kill(sig) => sig,
-                    KillSignal::Cancel => 15, // should never happen, so just TERM
-                };
-                for pid in &current_selected_processes.1 {
+                {
+                    process_killer::kill_process_given_pid(*pid, signal)?;
+                }
+                #[cfg(target_os = "windows")]
+                {
+                    process_killer::kill_process_given_pid(*pid)?;
+                }
+            }
+
+            if self.delete_dialog_state.is_killing_marked {
+                if let Some(proc_widget_state) = self
+                    .proc_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    proc_widget_state.marked_pids.clear();
+                }
+            }
+        }
+        self.delete_dialog_state.is_killing_marked = false;
+        self.to_delete_process_list = None;
+        Ok(())
+    }
+
+    /// Checks the latest processes against the process rules, and acts on any that broke one.
+    pub fn check_process_rules(&mut self) {
+        if self.process_rules.is_empty() {
+            return;
+        }
+
+        for rule_match in self.process_rules.check(
+            &self.data_collection.process_harvest,
+            &self.app_config_fields.number_format,
+        ) {
+            let signal_name = get_signal_name(rule_match.signal);
+            match rule_match.mode {
+                ProcessRuleMode::DryRun => {
+                    let message = format!(
+                        "Rule would send {} to {} (PID {}), which is {}",
+                        signal_name, rule_match.name, rule_match.pid, rule_match.reason
+                    );
+                    self.alert_process_rule(&rule_match, message);
+                }
+                ProcessRuleMode::Auto => {
                     #[cfg(target_family = "unix")]
-                    {
-                        process_killer::kill_process_given_pid(*pid, signal)?;
-                    }
+                    let result =
+                        process_killer::kill_process_given_pid(rule_match.pid, rule_match.signal);
                     #[cfg(target_os = "windows")]
-                    {
-                        process_killer::kill_process_given_pid(*pid)?;
+                    let result = process_killer::kill_process_given_pid(rule_match.pid);
+
+                    let message = match result {
+                        Ok(()) => format!(
+                            "Rule sent {} to {} (PID {}), which was {}",
+                            signal_name, rule_match.name, rule_match.pid, rule_match.reason
+                        ),
+                        Err(err) => format!(
+                            "Rule failed to send {} to {} (PID {}): {}",
+                            signal_name, rule_match.name, rule_match.pid, err
+                        ),
+                    };
+                    self.alert_process_rule(&rule_match, message);
+                }
+                ProcessRuleMode::Confirm => {
+                    // Don't interrupt whatever the user is doing; ask again on the next update
+                    // instead, if the process is still over.
+                    if self.ignore_normal_keybinds() {
+                        self.process_rules.rearm(&rule_match);
+                        continue;
                     }
+
+                    self.reset_multi_tap_keys();
+                    self.to_delete_process_list =
+                        Some((rule_match.name.clone(), vec![rule_match.pid]));
+                    self.delete_dialog_state.selected_signal = KillSignal::Kill(rule_match.signal);
+                    self.delete_dialog_state.is_showing_dd = true;
+                    self.is_determining_widget_boundary = true;
+                    let message = format!(
+                        "Rule matched {} (PID {}), which is {}",
+                        rule_match.name, rule_match.pid, rule_match.reason
+                    );
+                    self.alert_process_rule(&rule_match, message);
                 }
             }
-            self.to_delete_process_list = None;
-            Ok(())
-        } else {
-            Err(BottomError::GenericError(
-                "Cannot kill processes if the current widget is not the Process widget!"
-                    .to_string(),
-            ))
         }
     }
 
+    /// Sends what a process rule did to the rule's webhook and by email, to the terminal like
+    /// the battery alerts, and shows it as a status message.
+    fn alert_process_rule(
+        &mut self, rule_match: &process_rules::ProcessRuleMatch, message: String,
+    ) {
+        self.process_rules.notify(rule_match, &message);
+        self.data_collection.pending_alerts.push(message.clone());
+        self.status_message = Some((message, Instant::now()));
+    }
+
     pub fn get_to_delete_processes(&self) -> Option<(String, Vec<Pid>)> {
         self.to_delete_process_list.clone()
     }
@@ -1737,10 +3373,123 @@ impl App {
         }
     }
 
+    /// Hides the currently selected widget, with the rest of the layout taking up its space.
+    /// Anything that no longer has a widget shown also stops being harvested.
+    fn hide_current_widget(&mut self) {
+        if self.ignore_normal_keybinds()
+            || self.is_expanded
+            || self.app_config_fields.use_basic_mode
+        {
+            return;
+        }
+
+        // Hide anything attached to the widget too, like the process search and sort widgets.
+        let parent_widget_id = match &self.current_widget.parent_reflector {
+            Some((_, offset)) => self.current_widget.widget_id - offset,
+            None => self.current_widget.widget_id,
+        };
+        let widget_group = self
+            .widget_map
+            .values()
+            .filter(|widget| {
+                let widget_parent_id = match &widget.parent_reflector {
+                    Some((_, offset)) => widget.widget_id.checked_sub(*offset),
+                    None => Some(widget.widget_id),
+                };
+                widget_parent_id == Some(parent_widget_id)
+            })
+            .map(|widget| widget.widget_id)
+            .collect::<Vec<_>>();
+        self.hidden_widgets.extend(widget_group.iter());
+
+        // Move off of the widget first.  If there's nowhere to go, it's the last widget shown,
+        // so leave it be.
+        let previous_widget = self.current_widget.clone();
+        for direction in &[
+            WidgetDirection::Left,
+            WidgetDirection::Right,
+            WidgetDirection::Up,
+            WidgetDirection::Down,
+        ] {
+            self.move_widget_selection(direction);
+            if !self.hidden_widgets.contains(&self.current_widget.widget_id) {
+                break;
+            }
+        }
+        if self.hidden_widgets.contains(&self.current_widget.widget_id) {
+            for widget_id in &widget_group {
+                self.hidden_widgets.remove(widget_id);
+            }
+            self.current_widget = previous_widget;
+            return;
+        }
+
+        for widget_id in &widget_group {
+            if let Some(widget) = self.widget_map.get_mut(widget_id) {
+                widget.top_left_corner = None;
+                widget.bottom_right_corner = None;
+            }
+        }
+
+        self.update_used_widgets();
+        self.is_force_redraw = true;
+        self.is_determining_widget_boundary = true;
+    }
+
+    /// Shows any widgets that were hidden.
+    fn show_hidden_widgets(&mut self) {
+        if !self.ignore_normal_keybinds() && !self.hidden_widgets.is_empty() {
+            self.hidden_widgets.clear();
+
+            self.update_used_widgets();
+            self.is_force_redraw = true;
+            self.is_determining_widget_boundary = true;
+        }
+    }
+
+    /// Works out what needs to be harvested from the widgets that are shown, and passes it on
+    /// to the collection thread.
+    fn update_used_widgets(&mut self) {
+        let hidden_widgets = &self.hidden_widgets;
+        let shown_widgets = self
+            .widget_map
+            .values()
+            .filter(|widget| !hidden_widgets.contains(&widget.widget_id));
+        let has_basic_battery = shown_widgets
+            .clone()
+            .any(|widget| widget.widget_type == BottomWidgetType::BasicBattery);
+        self.used_widgets =
+            UsedWidgets::from_widget_types(shown_widgets.map(|widget| &widget.widget_type));
+        self.used_widgets.apply_config_overrides(
+            &self.app_config_fields,
+            has_basic_battery,
+            !self.process_rules.is_empty(),
+        );
+        self.data_collection.is_logging_process_events = self.used_widgets.use_proc_events;
+        self.collection_thread_events
+            .push(ThreadControlEvent::UpdateUsedWidgets(Box::new(
+                self.used_widgets.clone(),
+            )));
+    }
+
     pub fn move_widget_selection(&mut self, direction: &WidgetDirection) {
+        let previous_widget = self.current_widget.clone();
+
         // Since we only want to call reset once, we do it like this to avoid
         // redundant calls on recursion.
         self.move_widget_selection_logic(direction);
+
+        // Skip over any widgets the user has hidden, staying put if there's only hidden
+        // widgets in that direction.
+        let mut visited_widget_ids = HashSet::new();
+        while self.hidden_widgets.contains(&self.current_widget.widget_id) {
+            if !visited_widget_ids.insert(self.current_widget.widget_id) {
+                self.current_widget = previous_widget;
+                break;
+            }
+            self.move_widget_selection_logic(direction);
+        }
+
         self.reset_multi_tap_keys();
     }
 
@@ -2216,6 +3965,83 @@ impl App {
                         temp_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
                     }
                 }
+                BottomWidgetType::Services => {
+                    if let Some(services_widget_state) = self
+                        .services_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        services_widget_state.clear_pending_action();
+                        services_widget_state.scroll_state.current_scroll_position = 0;
+                        services_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Ports => {
+                    if let Some(ports_widget_state) = self
+                        .ports_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        ports_widget_state.scroll_state.current_scroll_position = 0;
+                        ports_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Connections => {
+                    if let Some(connections_widget_state) = self
+                        .connections_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        connections_widget_state
+                            .scroll_state
+                            .current_scroll_position = 0;
+                        connections_widget_state.scroll_state.scroll_direction =
+                            ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Cgroups => {
+                    if let Some(cgroups_widget_state) = self
+                        .cgroups_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        cgroups_widget_state.scroll_state.current_scroll_position = 0;
+                        cgroups_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Docker => {
+                    if let Some(docker_widget_state) = self
+                        .docker_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        docker_widget_state.clear_pending_action();
+                        docker_widget_state.scroll_state.current_scroll_position = 0;
+                        docker_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Vms => {
+                    if let Some(vms_widget_state) = self
+                        .vms_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        vms_widget_state.scroll_state.current_scroll_position = 0;
+                        vms_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Log => {
+                    if let Some(log_widget_state) = self
+                        .log_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        log_widget_state.scroll_offset =
+                            log_widget_state.lines.len().saturating_sub(1);
+                    }
+                }
+                BottomWidgetType::ProcEvents => {
+                    if let Some(proc_events_widget_state) = self
+                        .proc_events_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        proc_events_widget_state.scroll_offset =
+                            self.data_collection.process_events.len().saturating_sub(1);
+                    }
+                }
                 BottomWidgetType::Disk => {
                     if let Some(disk_widget_state) = self
                         .disk_state
@@ -2241,6 +4067,12 @@ impl App {
         } else if self.is_config_open {
         } else if self.help_dialog_state.is_showing_help {
             self.help_dialog_state.scroll_state.current_scroll_index = 0;
+        } else if self.disk_usage_state.is_showing_disk_usage {
+            self.disk_usage_state.selected_path = None;
+        } else if self.affinity_state.is_showing_affinity {
+            self.affinity_state.selected_index = 0;
+        } else if self.threads_state.is_showing_threads {
+            self.threads_state.scroll_index = 0;
         } else if self.delete_dialog_state.is_showing_dd {
             self.delete_dialog_state.selected_signal = KillSignal::Cancel;
         }
@@ -2290,6 +4122,103 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Services => {
+                    if let Some(services_widget_state) = self
+                        .services_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        services_widget_state.clear_pending_action();
+                        if !self.canvas_data.service_data.is_empty() {
+                            services_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.service_data.len() - 1;
+                            services_widget_state.scroll_state.scroll_direction =
+                                ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Ports => {
+                    if let Some(ports_widget_state) = self
+                        .ports_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.port_data.is_empty() {
+                            ports_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.port_data.len() - 1;
+                            ports_widget_state.scroll_state.scroll_direction =
+                                ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Connections => {
+                    if let Some(connections_widget_state) = self
+                        .connections_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.connection_data.is_empty() {
+                            connections_widget_state
+                                .scroll_state
+                                .current_scroll_position =
+                                self.canvas_data.connection_data.len() - 1;
+                            connections_widget_state.scroll_state.scroll_direction =
+                                ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Cgroups => {
+                    if let Some(cgroups_widget_state) = self
+                        .cgroups_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.cgroup_data.is_empty() {
+                            cgroups_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.cgroup_data.len() - 1;
+                            cgroups_widget_state.scroll_state.scroll_direction =
+                                ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Docker => {
+                    if let Some(docker_widget_state) = self
+                        .docker_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        docker_widget_state.clear_pending_action();
+                        if !self.canvas_data.container_data.is_empty() {
+                            docker_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.container_data.len() - 1;
+                            docker_widget_state.scroll_state.scroll_direction =
+                                ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Vms => {
+                    if let Some(vms_widget_state) = self
+                        .vms_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.canvas_data.vm_data.is_empty() {
+                            vms_widget_state.scroll_state.current_scroll_position =
+                                self.canvas_data.vm_data.len() - 1;
+                            vms_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Log => {
+                    if let Some(log_widget_state) = self
+                        .log_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        log_widget_state.scroll_offset = 0;
+                    }
+                }
+                BottomWidgetType::ProcEvents => {
+                    if let Some(proc_events_widget_state) = self
+                        .proc_events_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        proc_events_widget_state.scroll_offset = 0;
+                    }
+                }
                 BottomWidgetType::Disk => {
                     if let Some(disk_widget_state) = self
                         .disk_state
@@ -2324,6 +4253,18 @@ impl App {
                 .scroll_state
                 .max_scroll_index
                 .saturating_sub(1);
+        } else if self.disk_usage_state.is_showing_disk_usage {
+            if let Some(scan) = &self.disk_usage_state.scan {
+                self.disk_usage_state.selected_path = scan
+                    .get_progress()
+                    .entries
+                    .last()
+                    .map(|entry| entry.path.clone());
+            }
+        } else if self.affinity_state.is_showing_affinity {
+            self.affinity_state.selected_index = self.affinity_state.cpus.len().saturating_sub(1);
+        } else if self.threads_state.is_showing_threads {
+            self.threads_state.scroll_index = self.threads_state.threads.len().saturating_sub(1);
         } else if self.delete_dialog_state.is_showing_dd {
             self.delete_dialog_state.selected_signal = KillSignal::Kill(MAX_SIGNAL);
         }
@@ -2337,6 +4278,14 @@ impl App {
                 }
                 BottomWidgetType::ProcSort => self.increment_process_sort_position(-1),
                 BottomWidgetType::Temp => self.increment_temp_position(-1),
+                BottomWidgetType::Services => self.increment_services_position(-1),
+                BottomWidgetType::Ports => self.increment_ports_position(-1),
+                BottomWidgetType::Connections => self.increment_connections_position(-1),
+                BottomWidgetType::Cgroups => self.increment_cgroups_position(-1),
+                BottomWidgetType::Docker => self.increment_docker_position(-1),
+                BottomWidgetType::Vms => self.increment_vms_position(-1),
+                BottomWidgetType::Log => self.increment_log_position(-1),
+                BottomWidgetType::ProcEvents => self.increment_proc_events_position(-1),
                 BottomWidgetType::Disk => self.increment_disk_position(-1),
                 BottomWidgetType::CpuLegend => self.increment_cpu_legend_position(-1),
                 _ => {}
@@ -2352,6 +4301,14 @@ impl App {
                 }
                 BottomWidgetType::ProcSort => self.increment_process_sort_position(1),
                 BottomWidgetType::Temp => self.increment_temp_position(1),
+                BottomWidgetType::Services => self.increment_services_position(1),
+                BottomWidgetType::Ports => self.increment_ports_position(1),
+                BottomWidgetType::Connections => self.increment_connections_position(1),
+                BottomWidgetType::Cgroups => self.increment_cgroups_position(1),
+                BottomWidgetType::Docker => self.increment_docker_position(1),
+                BottomWidgetType::Vms => self.increment_vms_position(1),
+                BottomWidgetType::Log => self.increment_log_position(1),
+                BottomWidgetType::ProcEvents => self.increment_proc_events_position(1),
                 BottomWidgetType::Disk => self.increment_disk_position(1),
                 BottomWidgetType::CpuLegend => self.increment_cpu_legend_position(1),
                 _ => {}
@@ -2359,130 +4316,450 @@ impl App {
         }
     }
 
-    fn increment_process_sort_position(&mut self, num_to_change_by: i64) {
-        if let Some(proc_widget_state) = self
-            .proc_state
-            .get_mut_widget_state(self.current_widget.widget_id - 2)
+    fn increment_process_sort_position(&mut self, num_to_change_by: i64) {
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id - 2)
+        {
+            let current_posn = proc_widget_state.columns.current_scroll_position;
+            let num_columns = proc_widget_state.columns.get_enabled_columns_len();
+
+            if current_posn as i64 + num_to_change_by >= 0
+                && current_posn as i64 + num_to_change_by < num_columns as i64
+            {
+                proc_widget_state.columns.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                proc_widget_state.columns.scroll_direction = ScrollDirection::Up;
+            } else {
+                proc_widget_state.columns.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    fn increment_cpu_legend_position(&mut self, num_to_change_by: i64) {
+        if let Some(cpu_widget_state) = self
+            .cpu_state
+            .widget_states
+            .get_mut(&(self.current_widget.widget_id - 1))
+        {
+            let current_posn = cpu_widget_state.scroll_state.current_scroll_position;
+
+            let cap = self.canvas_data.cpu_data.len();
+            if current_posn as i64 + num_to_change_by >= 0
+                && current_posn as i64 + num_to_change_by < cap as i64
+            {
+                cpu_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                cpu_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                cpu_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    /// Returns the new position.
+    fn increment_process_position(&mut self, num_to_change_by: i64) -> Option<usize> {
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            let current_posn = proc_widget_state.scroll_state.current_scroll_position;
+            if let Some(finalized_process_data) = self
+                .canvas_data
+                .finalized_process_data_map
+                .get(&self.current_widget.widget_id)
+            {
+                if current_posn as i64 + num_to_change_by >= 0
+                    && current_posn as i64 + num_to_change_by < finalized_process_data.len() as i64
+                {
+                    proc_widget_state.scroll_state.current_scroll_position =
+                        (current_posn as i64 + num_to_change_by) as usize;
+                } else {
+                    return None;
+                }
+            }
+
+            if num_to_change_by < 0 {
+                proc_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                proc_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+
+            return Some(proc_widget_state.scroll_state.current_scroll_position);
+        }
+
+        None
+    }
+
+    fn increment_temp_position(&mut self, num_to_change_by: i64) {
+        if let Some(temp_widget_state) = self
+            .temp_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            let current_posn = temp_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by >= 0
+                && current_posn as i64 + num_to_change_by
+                    < self.canvas_data.temp_sensor_data.len() as i64
+            {
+                temp_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                temp_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                temp_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    fn increment_disk_position(&mut self, num_to_change_by: i64) {
+        if let Some(disk_widget_state) = self
+            .disk_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            let current_posn = disk_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by >= 0
+                && current_posn as i64 + num_to_change_by < self.canvas_data.disk_data.len() as i64
+            {
+                disk_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                disk_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                disk_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    fn increment_services_position(&mut self, num_to_change_by: i64) {
+        if let Some(services_widget_state) = self
+            .services_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            services_widget_state.clear_pending_action();
+            let current_posn = services_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by >= 0
+                && current_posn as i64 + num_to_change_by
+                    < self.canvas_data.service_data.len() as i64
+            {
+                services_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
+            }
+
+            if num_to_change_by < 0 {
+                services_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+            } else {
+                services_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    fn increment_ports_position(&mut self, num_to_change_by: i64) {
+        if let Some(ports_widget_state) = self
+            .ports_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
         {
-            let current_posn = proc_widget_state.columns.current_scroll_position;
-            let num_columns = proc_widget_state.columns.get_enabled_columns_len();
+            let current_posn = ports_widget_state.scroll_state.current_scroll_position;
 
             if current_posn as i64 + num_to_change_by >= 0
-                && current_posn as i64 + num_to_change_by < num_columns as i64
+                && current_posn as i64 + num_to_change_by < self.canvas_data.port_data.len() as i64
             {
-                proc_widget_state.columns.current_scroll_position =
+                ports_widget_state.scroll_state.current_scroll_position =
                     (current_posn as i64 + num_to_change_by) as usize;
             }
 
             if num_to_change_by < 0 {
-                proc_widget_state.columns.scroll_direction = ScrollDirection::Up;
+                ports_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
             } else {
-                proc_widget_state.columns.scroll_direction = ScrollDirection::Down;
+                ports_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
             }
         }
     }
 
-    fn increment_cpu_legend_position(&mut self, num_to_change_by: i64) {
-        if let Some(cpu_widget_state) = self
-            .cpu_state
+    fn increment_connections_position(&mut self, num_to_change_by: i64) {
+        if let Some(connections_widget_state) = self
+            .connections_state
             .widget_states
-            .get_mut(&(self.current_widget.widget_id - 1))
+            .get_mut(&self.current_widget.widget_id)
         {
-            let current_posn = cpu_widget_state.scroll_state.current_scroll_position;
+            let current_posn = connections_widget_state
+                .scroll_state
+                .current_scroll_position;
 
-            let cap = self.canvas_data.cpu_data.len();
             if current_posn as i64 + num_to_change_by >= 0
-                && current_posn as i64 + num_to_change_by < cap as i64
+                && current_posn as i64 + num_to_change_by
+                    < self.canvas_data.connection_data.len() as i64
             {
-                cpu_widget_state.scroll_state.current_scroll_position =
-                    (current_posn as i64 + num_to_change_by) as usize;
+                connections_widget_state
+                    .scroll_state
+                    .current_scroll_position = (current_posn as i64 + num_to_change_by) as usize;
             }
 
             if num_to_change_by < 0 {
-                cpu_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                connections_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
             } else {
-                cpu_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+                connections_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
             }
         }
     }
 
-    /// Returns the new position.
-    fn increment_process_position(&mut self, num_to_change_by: i64) -> Option<usize> {
-        if let Some(proc_widget_state) = self
-            .proc_state
-            .get_mut_widget_state(self.current_widget.widget_id)
+    fn increment_cgroups_position(&mut self, num_to_change_by: i64) {
+        if let Some(cgroups_widget_state) = self
+            .cgroups_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
         {
-            let current_posn = proc_widget_state.scroll_state.current_scroll_position;
-            if let Some(finalized_process_data) = self
-                .canvas_data
-                .finalized_process_data_map
-                .get(&self.current_widget.widget_id)
+            let current_posn = cgroups_widget_state.scroll_state.current_scroll_position;
+
+            if current_posn as i64 + num_to_change_by >= 0
+                && current_posn as i64 + num_to_change_by
+                    < self.canvas_data.cgroup_data.len() as i64
             {
-                if current_posn as i64 + num_to_change_by >= 0
-                    && current_posn as i64 + num_to_change_by < finalized_process_data.len() as i64
-                {
-                    proc_widget_state.scroll_state.current_scroll_position =
-                        (current_posn as i64 + num_to_change_by) as usize;
-                } else {
-                    return None;
-                }
+                cgroups_widget_state.scroll_state.current_scroll_position =
+                    (current_posn as i64 + num_to_change_by) as usize;
             }
 
             if num_to_change_by < 0 {
-                proc_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                cgroups_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
             } else {
-                proc_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+                cgroups_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
             }
-
-            return Some(proc_widget_state.scroll_state.current_scroll_position);
         }
-
-        None
     }
 
-    fn increment_temp_position(&mut self, num_to_change_by: i64) {
-        if let Some(temp_widget_state) = self
-            .temp_state
+    fn increment_docker_position(&mut self, num_to_change_by: i64) {
+        if let Some(docker_widget_state) = self
+            .docker_state
             .widget_states
             .get_mut(&self.current_widget.widget_id)
         {
-            let current_posn = temp_widget_state.scroll_state.current_scroll_position;
+            docker_widget_state.clear_pending_action();
+            let current_posn = docker_widget_state.scroll_state.current_scroll_position;
 
             if current_posn as i64 + num_to_change_by >= 0
                 && current_posn as i64 + num_to_change_by
-                    < self.canvas_data.temp_sensor_data.len() as i64
+                    < self.canvas_data.container_data.len() as i64
             {
-                temp_widget_state.scroll_state.current_scroll_position =
+                docker_widget_state.scroll_state.current_scroll_position =
                     (current_posn as i64 + num_to_change_by) as usize;
             }
 
             if num_to_change_by < 0 {
-                temp_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                docker_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
             } else {
-                temp_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+                docker_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
             }
         }
     }
 
-    fn increment_disk_position(&mut self, num_to_change_by: i64) {
-        if let Some(disk_widget_state) = self
-            .disk_state
+    fn increment_vms_position(&mut self, num_to_change_by: i64) {
+        if let Some(vms_widget_state) = self
+            .vms_state
             .widget_states
             .get_mut(&self.current_widget.widget_id)
         {
-            let current_posn = disk_widget_state.scroll_state.current_scroll_position;
+            let current_posn = vms_widget_state.scroll_state.current_scroll_position;
 
             if current_posn as i64 + num_to_change_by >= 0
-                && current_posn as i64 + num_to_change_by < self.canvas_data.disk_data.len() as i64
+                && current_posn as i64 + num_to_change_by < self.canvas_data.vm_data.len() as i64
             {
-                disk_widget_state.scroll_state.current_scroll_position =
+                vms_widget_state.scroll_state.current_scroll_position =
                     (current_posn as i64 + num_to_change_by) as usize;
             }
 
             if num_to_change_by < 0 {
-                disk_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
+                vms_widget_state.scroll_state.scroll_direction = ScrollDirection::Up;
             } else {
-                disk_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+                vms_widget_state.scroll_state.scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    /// Scrolls a log widget, where scrolling down towards the end goes back to following new lines.
+    fn increment_log_position(&mut self, num_to_change_by: i64) {
+        if let Some(log_widget_state) = self
+            .log_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            let new_offset = log_widget_state.scroll_offset as i64 - num_to_change_by;
+            log_widget_state.scroll_offset = new_offset
+                .max(0)
+                .min(log_widget_state.lines.len().saturating_sub(1) as i64)
+                as usize;
+        }
+    }
+
+    /// Scrolls a process events widget, which works like a log widget.
+    fn increment_proc_events_position(&mut self, num_to_change_by: i64) {
+        let num_events = self.data_collection.process_events.len();
+        if let Some(proc_events_widget_state) = self
+            .proc_events_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            let new_offset = proc_events_widget_state.scroll_offset as i64 - num_to_change_by;
+            proc_events_widget_state.scroll_offset =
+                new_offset.max(0).min(num_events.saturating_sub(1) as i64) as usize;
+        }
+    }
+
+    /// Starts, stops, or restarts the selected service.  The first press only asks for
+    /// confirmation; pressing the same key again actually runs the action.
+    fn on_service_action(&mut self, action: services::ServiceAction) {
+        if let Some(services_widget_state) = self
+            .services_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            // Make sure we aren't trying to act on a placeholder row.
+            let service_harvest = &self.data_collection.service_harvest;
+            let selected_service = self
+                .canvas_data
+                .service_data
+                .get(services_widget_state.scroll_state.current_scroll_position)
+                .and_then(|row| row.first())
+                .filter(|name| service_harvest.iter().any(|service| &service.name == *name))
+                .cloned();
+
+            if let Some(service_name) = selected_service {
+                let pending_action = Some((action, service_name.clone()));
+                if services_widget_state.pending_action == pending_action {
+                    services_widget_state.pending_action = None;
+                    services_widget_state.status_text =
+                        Some(format!("{} {}...", action.as_progress(), service_name));
+
+                    // Stopping a service can take a while, so don't hold up the interface.
+                    self.run_in_background(self.current_widget.widget_id, move || {
+                        match services::control_service(&service_name, action) {
+                            Ok(()) => None,
+                            Err(err) => Some(format!(
+                                "Failed to {} {}: {}",
+                                action.as_command(),
+                                service_name,
+                                err
+                            )),
+                        }
+                    });
+                } else {
+                    services_widget_state.status_text = Some(format!(
+                        "Press again to {} {}",
+                        action.as_command(),
+                        service_name
+                    ));
+                    services_widget_state.pending_action = pending_action;
+                }
+            }
+        }
+    }
+
+    /// Runs a slow action, like stopping a service, on its own thread.  Whatever status text it
+    /// returns is shown in the given widget once it's done.
+    fn run_in_background<F>(&mut self, widget_id: u64, action: F)
+    where
+        F: FnOnce() -> Option<String> + Send + 'static,
+    {
+        match &self.event_sender {
+            Some(event_sender) => {
+                let event_sender = event_sender.clone();
+                thread::spawn(move || {
+                    let status_text = action();
+                    let _ = event_sender.send(BottomEvent::ActionResult(ActionResult {
+                        widget_id,
+                        status_text,
+                    }));
+                });
+            }
+            None => {
+                let status_text = action();
+                self.on_action_result(ActionResult {
+                    widget_id,
+                    status_text,
+                });
+            }
+        }
+    }
+
+    /// Shows the result of an action run in the background in the widget it was run from, if
+    /// that widget is still around.
+    pub fn on_action_result(&mut self, action_result: ActionResult) {
+        let ActionResult {
+            widget_id,
+            status_text,
+        } = action_result;
+
+        if let Some(services_widget_state) = self.services_state.widget_states.get_mut(&widget_id) {
+            services_widget_state.status_text = status_text;
+        } else if let Some(docker_widget_state) =
+            self.docker_state.widget_states.get_mut(&widget_id)
+        {
+            docker_widget_state.status_text = status_text;
+        }
+    }
+
+    /// Starts, stops, or restarts the selected container, confirming it first like
+    /// [`App::on_service_action`].
+    fn on_container_action(&mut self, action: docker::ContainerAction) {
+        if let Some(docker_widget_state) = self
+            .docker_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            // Make sure we aren't trying to act on a placeholder row.
+            let container_harvest = &self.data_collection.container_harvest;
+            let selected_container = self
+                .canvas_data
+                .container_data
+                .get(docker_widget_state.scroll_state.current_scroll_position)
+                .and_then(|row| row.first())
+                .filter(|name| {
+                    container_harvest
+                        .iter()
+                        .any(|container| &container.name == *name)
+                })
+                .cloned();
+
+            if let Some(container_name) = selected_container {
+                let pending_action = Some((action, container_name.clone()));
+                if docker_widget_state.pending_action == pending_action {
+                    docker_widget_state.pending_action = None;
+                    docker_widget_state.status_text =
+                        Some(format!("{} {}...", action.as_progress(), container_name));
+
+                    // Docker waits for a container to exit before stopping it outright.
+                    self.run_in_background(self.current_widget.widget_id, move || {
+                        match docker::control_container(&container_name, action) {
+                            Ok(()) => None,
+                            Err(err) => Some(format!(
+                                "Failed to {} {}: {}",
+                                action.as_command(),
+                                container_name,
+                                err
+                            )),
+                        }
+                    });
+                } else {
+                    docker_widget_state.status_text = Some(format!(
+                        "Press again to {} {}",
+                        action.as_command(),
+                        container_name
+                    ));
+                    docker_widget_state.pending_action = pending_action;
+                }
             }
         }
     }
@@ -2522,7 +4799,9 @@ impl App {
             self.help_scroll_up();
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_in();
-        } else if self.current_widget.widget_type.is_widget_table() {
+        } else if self.current_widget.widget_type.is_widget_table()
+            || self.current_widget.widget_type == BottomWidgetType::Log
+        {
             self.decrement_position_count();
         }
     }
@@ -2539,7 +4818,9 @@ impl App {
             self.help_scroll_down();
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_out();
-        } else if self.current_widget.widget_type.is_widget_table() {
+        } else if self.current_widget.widget_type.is_widget_table()
+            || self.current_widget.widget_type == BottomWidgetType::Log
+        {
             self.increment_position_count();
         }
     }
@@ -2591,217 +4872,105 @@ impl App {
         }
     }
 
-    fn zoom_out(&mut self) {
+    /// Returns what zooming needs from the current widget's state, if it's a graph.
+    fn get_current_graph_zoom(&mut self) -> Option<GraphZoom<'_>> {
+        let widget_id = self.current_widget.widget_id;
+        let retention = self.app_config_fields.retention;
         match self.current_widget.widget_type {
             BottomWidgetType::Cpu => {
-                if let Some(cpu_widget_state) = self
-                    .cpu_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = cpu_widget_state.current_display_time
-                        + self.app_config_fields.time_interval;
-                    if new_time <= constants::STALE_MAX_MILLISECONDS {
-                        cpu_widget_state.current_display_time = new_time;
-                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            cpu_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if cpu_widget_state.current_display_time
-                        != constants::STALE_MAX_MILLISECONDS
-                    {
-                        cpu_widget_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
-                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            cpu_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+                let widget_state = self.cpu_state.widget_states.get_mut(&widget_id)?;
+                Some(GraphZoom {
+                    current_display_time: &mut widget_state.current_display_time,
+                    autohide_timer: &mut widget_state.autohide_timer,
+                    force_update: &mut self.cpu_state.force_update,
+                    max_display_time: retention.cpu,
+                })
             }
             BottomWidgetType::Mem => {
-                if let Some(mem_widget_state) = self
-                    .mem_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = mem_widget_state.current_display_time
-                        + self.app_config_fields.time_interval;
-                    if new_time <= constants::STALE_MAX_MILLISECONDS {
-                        mem_widget_state.current_display_time = new_time;
-                        self.mem_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            mem_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if mem_widget_state.current_display_time
-                        != constants::STALE_MAX_MILLISECONDS
-                    {
-                        mem_widget_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
-                        self.mem_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            mem_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+                let widget_state = self.mem_state.widget_states.get_mut(&widget_id)?;
+                Some(GraphZoom {
+                    current_display_time: &mut widget_state.current_display_time,
+                    autohide_timer: &mut widget_state.autohide_timer,
+                    force_update: &mut self.mem_state.force_update,
+                    max_display_time: retention.mem,
+                })
             }
             BottomWidgetType::Net => {
-                if let Some(net_widget_state) = self
-                    .net_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = net_widget_state.current_display_time
-                        + self.app_config_fields.time_interval;
-                    if new_time <= constants::STALE_MAX_MILLISECONDS {
-                        net_widget_state.current_display_time = new_time;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if net_widget_state.current_display_time
-                        != constants::STALE_MAX_MILLISECONDS
-                    {
-                        net_widget_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+                let widget_state = self.net_state.widget_states.get_mut(&widget_id)?;
+                Some(GraphZoom {
+                    current_display_time: &mut widget_state.current_display_time,
+                    autohide_timer: &mut widget_state.autohide_timer,
+                    force_update: &mut self.net_state.force_update,
+                    max_display_time: retention.net,
+                })
             }
-            _ => {}
-        }
-    }
-
-    fn zoom_in(&mut self) {
-        match self.current_widget.widget_type {
-            BottomWidgetType::Cpu => {
-                if let Some(cpu_widget_state) = self
-                    .cpu_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = cpu_widget_state.current_display_time
-                        - self.app_config_fields.time_interval;
-                    if new_time >= constants::STALE_MIN_MILLISECONDS {
-                        cpu_widget_state.current_display_time = new_time;
-                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            cpu_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if cpu_widget_state.current_display_time
-                        != constants::STALE_MIN_MILLISECONDS
-                    {
-                        cpu_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            cpu_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+            BottomWidgetType::Custom => {
+                let widget_state = self.custom_state.widget_states.get_mut(&widget_id)?;
+                Some(GraphZoom {
+                    current_display_time: &mut widget_state.current_display_time,
+                    autohide_timer: &mut widget_state.autohide_timer,
+                    force_update: &mut self.custom_state.force_update,
+                    max_display_time: retention.custom,
+                })
             }
-            BottomWidgetType::Mem => {
-                if let Some(mem_widget_state) = self
-                    .mem_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = mem_widget_state.current_display_time
-                        - self.app_config_fields.time_interval;
-                    if new_time >= constants::STALE_MIN_MILLISECONDS {
-                        mem_widget_state.current_display_time = new_time;
-                        self.mem_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            mem_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if mem_widget_state.current_display_time
-                        != constants::STALE_MIN_MILLISECONDS
-                    {
-                        mem_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.mem_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            mem_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+            BottomWidgetType::Sched => {
+                let widget_state = self.sched_state.widget_states.get_mut(&widget_id)?;
+                Some(GraphZoom {
+                    current_display_time: &mut widget_state.current_display_time,
+                    autohide_timer: &mut widget_state.autohide_timer,
+                    force_update: &mut self.sched_state.force_update,
+                    max_display_time: retention.sched,
+                })
             }
-            BottomWidgetType::Net => {
-                if let Some(net_widget_state) = self
-                    .net_state
-                    .widget_states
-                    .get_mut(&self.current_widget.widget_id)
-                {
-                    let new_time = net_widget_state.current_display_time
-                        - self.app_config_fields.time_interval;
-                    if new_time >= constants::STALE_MIN_MILLISECONDS {
-                        net_widget_state.current_display_time = new_time;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    } else if net_widget_state.current_display_time
-                        != constants::STALE_MIN_MILLISECONDS
-                    {
-                        net_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
-                        if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
-                        }
-                    }
-                }
+            BottomWidgetType::Power => {
+                let widget_state = self.power_state.widget_states.get_mut(&widget_id)?;
+                Some(GraphZoom {
+                    current_display_time: &mut widget_state.current_display_time,
+                    autohide_timer: &mut widget_state.autohide_timer,
+                    force_update: &mut self.power_state.force_update,
+                    max_display_time: retention.power,
+                })
             }
-            _ => {}
+            _ => None,
         }
     }
 
-    fn reset_cpu_zoom(&mut self) {
-        if let Some(cpu_widget_state) = self
-            .cpu_state
-            .widget_states
-            .get_mut(&self.current_widget.widget_id)
-        {
-            cpu_widget_state.current_display_time = self.app_config_fields.default_time_value;
-            self.cpu_state.force_update = Some(self.current_widget.widget_id);
-            if self.app_config_fields.autohide_time {
-                cpu_widget_state.autohide_timer = Some(Instant::now());
+    /// Sets how far back the current graph shows to what `get_new_time` returns, given how far
+    /// back it shows now and how far back it can show.
+    fn set_graph_display_time(&mut self, get_new_time: impl FnOnce(u64, u64) -> u64) {
+        let widget_id = self.current_widget.widget_id;
+        let autohide_time = self.app_config_fields.autohide_time;
+        if let Some(zoom) = self.get_current_graph_zoom() {
+            let new_time = get_new_time(*zoom.current_display_time, zoom.max_display_time);
+            if new_time != *zoom.current_display_time {
+                *zoom.current_display_time = new_time;
+                *zoom.force_update = Some(widget_id);
+                if autohide_time {
+                    *zoom.autohide_timer = Some(Instant::now());
+                }
             }
         }
     }
 
-    fn reset_mem_zoom(&mut self) {
-        if let Some(mem_widget_state) = self
-            .mem_state
-            .widget_states
-            .get_mut(&self.current_widget.widget_id)
-        {
-            mem_widget_state.current_display_time = self.app_config_fields.default_time_value;
-            self.mem_state.force_update = Some(self.current_widget.widget_id);
-            if self.app_config_fields.autohide_time {
-                mem_widget_state.autohide_timer = Some(Instant::now());
-            }
-        }
+    fn zoom_out(&mut self) {
+        let time_interval = self.app_config_fields.time_interval;
+        self.set_graph_display_time(|current_display_time, max_display_time| {
+            (current_display_time + time_interval).min(max_display_time)
+        });
     }
 
-    fn reset_net_zoom(&mut self) {
-        if let Some(net_widget_state) = self
-            .net_state
-            .widget_states
-            .get_mut(&self.current_widget.widget_id)
-        {
-            net_widget_state.current_display_time = self.app_config_fields.default_time_value;
-            self.net_state.force_update = Some(self.current_widget.widget_id);
-            if self.app_config_fields.autohide_time {
-                net_widget_state.autohide_timer = Some(Instant::now());
-            }
-        }
+    fn zoom_in(&mut self) {
+        let time_interval = self.app_config_fields.time_interval;
+        self.set_graph_display_time(|current_display_time, _max_display_time| {
+            current_display_time
+                .saturating_sub(time_interval)
+                .max(constants::STALE_MIN_MILLISECONDS)
+        });
     }
 
     fn reset_zoom(&mut self) {
-        match self.current_widget.widget_type {
-            BottomWidgetType::Cpu => self.reset_cpu_zoom(),
-            BottomWidgetType::Mem => self.reset_mem_zoom(),
-            BottomWidgetType::Net => self.reset_net_zoom(),
-            _ => {}
-        }
+        let default_time_value = self.app_config_fields.default_time_value;
+        self.set_graph_display_time(|_current_display_time, _max_display_time| default_time_value);
     }
 
     /// Moves the mouse to the widget that was clicked on, then propagates the click down to be
@@ -2955,7 +5124,13 @@ impl App {
                     | BottomWidgetType::ProcSort
                     | BottomWidgetType::CpuLegend
                     | BottomWidgetType::Temp
-                    | BottomWidgetType::Disk => {
+                    | BottomWidgetType::Disk
+                    | BottomWidgetType::Services
+                    | BottomWidgetType::Ports
+                    | BottomWidgetType::Connections
+                    | BottomWidgetType::Cgroups
+                    | BottomWidgetType::Docker
+                    | BottomWidgetType::Vms => {
                         // Get our index...
                         let clicked_entry = y - *tlc_y;
                         // + 1 so we start at 0.
@@ -3056,6 +5231,94 @@ impl App {
                                         }
                                     }
                                 }
+                                BottomWidgetType::Services => {
+                                    if let Some(services_widget_state) = self
+                                        .services_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) = services_widget_state
+                                            .scroll_state
+                                            .table_state
+                                            .selected()
+                                        {
+                                            self.increment_services_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Ports => {
+                                    if let Some(ports_widget_state) = self
+                                        .ports_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            ports_widget_state.scroll_state.table_state.selected()
+                                        {
+                                            self.increment_ports_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Connections => {
+                                    if let Some(connections_widget_state) = self
+                                        .connections_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) = connections_widget_state
+                                            .scroll_state
+                                            .table_state
+                                            .selected()
+                                        {
+                                            self.increment_connections_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Cgroups => {
+                                    if let Some(cgroups_widget_state) = self
+                                        .cgroups_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            cgroups_widget_state.scroll_state.table_state.selected()
+                                        {
+                                            self.increment_cgroups_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Docker => {
+                                    if let Some(docker_widget_state) = self
+                                        .docker_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            docker_widget_state.scroll_state.table_state.selected()
+                                        {
+                                            self.increment_docker_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Vms => {
+                                    if let Some(vms_widget_state) = self
+                                        .vms_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            vms_widget_state.scroll_state.table_state.selected()
+                                        {
+                                            self.increment_vms_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
                                 _ => {}
                             }
                         } else {
@@ -0,0 +1,66 @@
+//! This file is meant to house (OS specific) implementations on how to copy text to the system
+//! clipboard.  Rather than talking to the display server directly, this hands the text off to
+//! whichever clipboard tool is installed, which also keeps the clipboard alive after bottom exits.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::utils::error::{self, BottomError};
+
+/// The clipboard tools to try, in order, along with their arguments.
+#[cfg(target_os = "macos")]
+fn get_clipboard_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    vec![("pbcopy", &[])]
+}
+
+/// The clipboard tools to try, in order, along with their arguments.
+#[cfg(target_os = "windows")]
+fn get_clipboard_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    vec![("clip", &[])]
+}
+
+/// The clipboard tools to try, in order, along with their arguments.  Wayland's is only tried
+/// under a Wayland session, as it can't reach the clipboard of an X11 one.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn get_clipboard_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    let mut commands: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        commands.push(("wl-copy", &[]));
+    }
+    commands.push(("xclip", &["-selection", "clipboard"]));
+    commands.push(("xsel", &["--clipboard", "--input"]));
+    commands
+}
+
+/// Copies text to the system clipboard.
+pub fn copy_to_clipboard(text: &str) -> error::Result<()> {
+    for (program, args) in get_clipboard_commands() {
+        let mut child = match Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(_) => continue,
+        };
+
+        // Closing stdin is what tells the tool that all the text has been sent.
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+
+        return if child.wait()?.success() {
+            Ok(())
+        } else {
+            Err(BottomError::GenericError(format!("{} failed", program)))
+        };
+    }
+
+    Err(BottomError::GenericError(
+        "no clipboard tool was found".to_string(),
+    ))
+}
@@ -14,14 +14,31 @@
 /// more points as this is used!
 use once_cell::sync::Lazy;
 
-use std::{time::Instant, vec::Vec};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+    vec::Vec,
+};
 
 use crate::{
-    data_harvester::{batteries, cpu, disks, memory, network, processes, temperature, Data},
-    utils::gen_util::{get_decimal_bytes, GIGA_LIMIT},
+    data_harvester::{
+        batteries, cgroups, cpu, disks, docker, kernel_limits, libvirt, log_tail, memory, network,
+        permissions, plugins, ports, power, pressure, processes, sched, services, temperature,
+        wireless, Data,
+    },
+    Pid,
 };
 use regex::Regex;
 
+mod history;
+mod process_events;
+mod session_stats;
+mod snapshot;
+
+pub use history::HistoryWriter;
+pub use process_events::{ProcessEvent, ProcessEventKind};
+pub use session_stats::{RunningStat, SessionStats};
+
 pub type TimeOffset = f64;
 pub type Value = f64;
 
@@ -33,6 +50,135 @@ pub struct TimedData {
     pub load_avg_data: [f32; 3],
     pub mem_data: Option<Value>,
     pub swap_data: Option<Value>,
+    pub numa_data: Vec<(u32, Value)>,
+    pub plugin_data: Vec<(String, Value)>,
+    /// Context switches and interrupts per second.
+    pub sched_data: Option<(Value, Value)>,
+    /// Power drawn by the CPU packages and by memory, in watts.
+    pub power_data: (Option<Value>, Option<Value>),
+    /// The CPU usage of the busiest processes, busiest first.
+    pub top_process_cpu_data: Vec<(Pid, Value)>,
+    /// Power draw in watts, by battery.
+    pub battery_power_data: Vec<Value>,
+}
+
+/// How many of the busiest processes to keep the CPU usage of for the CPU graph.
+pub const TOP_PROCESS_COUNT: usize = 3;
+
+/// How far back the average usage in the CPU legend looks.
+const CPU_STATS_AVERAGE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Usage stats for one entry of the CPU legend.  These are updated as each harvest comes in,
+/// rather than worked out from the graph data on each draw.
+#[derive(Debug, Clone, Default)]
+pub struct CpuStats {
+    /// The usage over the last minute, oldest first, and the sum of it.
+    recent_usage: VecDeque<(Instant, Value)>,
+    recent_usage_sum: Value,
+    /// The highest usage seen this session.
+    pub max_usage: Value,
+}
+
+impl CpuStats {
+    fn update(&mut self, usage: Value, harvested_time: Instant) {
+        self.recent_usage.push_back((harvested_time, usage));
+        self.recent_usage_sum += usage;
+        while let Some((instant, old_usage)) = self.recent_usage.front() {
+            if harvested_time.duration_since(*instant) > CPU_STATS_AVERAGE_WINDOW {
+                self.recent_usage_sum -= old_usage;
+                self.recent_usage.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if usage > self.max_usage {
+            self.max_usage = usage;
+        }
+    }
+
+    /// The average usage over the last minute.
+    pub fn get_average_usage(&self) -> Value {
+        if self.recent_usage.is_empty() {
+            0.0
+        } else {
+            // Rounding errors from adding and removing could leave this slightly below zero.
+            (self.recent_usage_sum / self.recent_usage.len() as Value).max(0.0)
+        }
+    }
+}
+
+/// How long each graph keeps its history for, in milliseconds.
+#[derive(Debug, Clone, Copy)]
+pub struct DataRetention {
+    pub cpu: u64,
+    pub mem: u64,
+    pub net: u64,
+    pub custom: u64,
+    pub sched: u64,
+    pub power: u64,
+}
+
+impl DataRetention {
+    /// How long the longest-kept graph keeps its history for.
+    pub fn get_max(&self) -> u64 {
+        self.cpu
+            .max(self.mem)
+            .max(self.net)
+            .max(self.custom)
+            .max(self.sched)
+            .max(self.power)
+    }
+}
+
+/// How many of the oldest entries of `timed_data_vec` have already had each graph's data cleared,
+/// so cleaning doesn't go over them again.
+#[derive(Debug, Clone, Copy, Default)]
+struct ClearedEntries {
+    cpu: usize,
+    mem: usize,
+    custom: usize,
+    sched: usize,
+    power: usize,
+}
+
+impl ClearedEntries {
+    /// Shifts each count back after the oldest `num_removed` entries are removed.
+    fn remove_entries(&mut self, num_removed: usize) {
+        self.cpu = self.cpu.saturating_sub(num_removed);
+        self.mem = self.mem.saturating_sub(num_removed);
+        self.custom = self.custom.saturating_sub(num_removed);
+        self.sched = self.sched.saturating_sub(num_removed);
+        self.power = self.power.saturating_sub(num_removed);
+    }
+}
+
+/// Which of an interface's error counters went up between the last two harvests.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InterfaceCounterIncreases {
+    pub errors: bool,
+    pub drops: bool,
+    pub collisions: bool,
+}
+
+/// How much each part of the system pressure score counts for, relative to the others.
+#[derive(Debug, Clone, Copy)]
+pub struct PressureWeights {
+    pub cpu: f64,
+    pub mem: f64,
+    pub psi: f64,
+    pub io_wait: f64,
+}
+
+impl Default for PressureWeights {
+    fn default() -> Self {
+        PressureWeights {
+            cpu: 1.0,
+            mem: 1.0,
+            psi: 1.0,
+            io_wait: 1.0,
+        }
+    }
 }
 
 /// AppCollection represents the pooled data stored within the main app
@@ -49,18 +195,69 @@ pub struct DataCollection {
     pub current_instant: Instant,
     pub frozen_instant: Option<Instant>,
     pub timed_data_vec: Vec<(Instant, TimedData)>,
+    cleared_entries: ClearedEntries,
+    /// When the system was rebooted between entries, which can only happen with history loaded
+    /// from an earlier run.
+    pub boot_markers: Vec<Instant>,
     pub network_harvest: network::NetworkHarvest,
+    /// Which interfaces' error counters went up in the last harvest, by interface name.
+    pub network_counter_increases: HashMap<String, InterfaceCounterIncreases>,
     pub memory_harvest: memory::MemHarvest,
     pub swap_harvest: memory::MemHarvest,
+    pub numa_harvest: Vec<memory::NumaHarvest>,
+    pub shmem_harvest: Option<memory::ShmemHarvest>,
+    pub hugepages_harvest: Option<memory::HugepagesHarvest>,
     pub cpu_harvest: cpu::CpuHarvest,
+    /// Usage stats for each entry of `cpu_harvest`, in the same order.
+    pub cpu_stats: Vec<CpuStats>,
     pub load_avg_harvest: cpu::LoadAvgHarvest,
+    pub cpu_breakdown_harvest: Option<cpu::CpuBreakdown>,
+    pub throttle_harvest: Option<cpu::ThrottleHarvest>,
     pub process_harvest: Vec<processes::ProcessHarvest>,
+    /// Processes that started, exited, or changed state, oldest first.
+    pub process_events: VecDeque<ProcessEvent>,
+    /// How many process events have been logged in total, so a widget scrolled back through them
+    /// can stay on the same events as new ones come in.
+    pub num_process_events_logged: usize,
+    /// Only set if a process events widget is shown, as logging them means diffing every process.
+    pub is_logging_process_events: bool,
     pub disk_harvest: Vec<disks::DiskHarvest>,
     pub io_harvest: disks::IoHarvest,
-    pub io_labels_and_prev: Vec<((u64, u64), (u64, u64))>,
-    pub io_labels: Vec<(String, String)>,
+    /// The bytes read and written as of the last harvest, by partition or device name.
+    pub io_prev: HashMap<String, (u64, u64)>,
+    /// Each disk's read and write rates, in bytes per second, if it has any I/O data.
+    pub io_rates: Vec<Option<(u64, u64)>>,
+    /// The read and write rates of the whole device each disk is on, in bytes per second.  For a
+    /// disk that isn't a partition, this is the same as its entry in `io_rates`.
+    pub device_io_rates: Vec<Option<(u64, u64)>>,
+    /// How many bytes of each disk were used over the retained data, oldest first, in the same
+    /// order as `disk_harvest`.  This is kept by disk so its trend doesn't need to look through
+    /// every entry of `timed_data_vec`.
+    pub disk_used_history: Vec<VecDeque<(Instant, Value)>>,
     pub temp_harvest: Vec<temperature::TempHarvest>,
     pub battery_harvest: Vec<batteries::BatteryHarvest>,
+    pub power_source: Option<batteries::PowerSource>,
+    /// When the power source last changed, such as from the AC adapter being unplugged.
+    pub power_source_changed_instant: Option<Instant>,
+    pub service_harvest: Vec<services::ServiceHarvest>,
+    pub port_harvest: Vec<ports::PortHarvest>,
+    pub connection_harvest: Vec<ports::ConnectionHarvest>,
+    pub cgroup_harvest: Vec<cgroups::CgroupHarvest>,
+    pub container_harvest: Vec<docker::ContainerHarvest>,
+    pub vm_harvest: Vec<libvirt::VmHarvest>,
+    pub sched_harvest: Option<sched::SchedHarvest>,
+    pub power_harvest: Option<power::PowerHarvest>,
+    pub plugin_harvest: Vec<plugins::PluginHarvest>,
+    pub wireless_harvest: Vec<wireless::WirelessHarvest>,
+    pub kernel_limits_harvest: kernel_limits::KernelLimitsHarvest,
+    pub pressure_harvest: pressure::PressureHarvest,
+    pub restricted_harvest: permissions::RestrictedHarvest,
+    /// Messages of alerts that fired, which still need to be sent to the terminal.
+    pub pending_alerts: Vec<String>,
+    /// Lines read from log widgets' files, which still need to be added to the widgets.
+    pub pending_log_lines: Vec<log_tail::LogHarvest>,
+    /// CPU, memory, and network usage over the whole session.
+    pub session_stats: SessionStats,
 }
 
 impl Default for DataCollection {
@@ -69,58 +266,355 @@ impl Default for DataCollection {
             current_instant: Instant::now(),
             frozen_instant: None,
             timed_data_vec: Vec::default(),
+            cleared_entries: ClearedEntries::default(),
+            boot_markers: Vec::default(),
             network_harvest: network::NetworkHarvest::default(),
+            network_counter_increases: HashMap::new(),
             memory_harvest: memory::MemHarvest::default(),
             swap_harvest: memory::MemHarvest::default(),
+            numa_harvest: Vec::default(),
+            shmem_harvest: None,
+            hugepages_harvest: None,
             cpu_harvest: cpu::CpuHarvest::default(),
+            cpu_stats: Vec::default(),
             load_avg_harvest: cpu::LoadAvgHarvest::default(),
+            cpu_breakdown_harvest: None,
+            throttle_harvest: None,
             process_harvest: Vec::default(),
+            process_events: VecDeque::default(),
+            num_process_events_logged: 0,
+            is_logging_process_events: false,
             disk_harvest: Vec::default(),
             io_harvest: disks::IoHarvest::default(),
-            io_labels_and_prev: Vec::default(),
-            io_labels: Vec::default(),
+            io_prev: HashMap::default(),
+            io_rates: Vec::default(),
+            device_io_rates: Vec::default(),
+            disk_used_history: Vec::default(),
             temp_harvest: Vec::default(),
             battery_harvest: Vec::default(),
+            power_source: None,
+            power_source_changed_instant: None,
+            service_harvest: Vec::default(),
+            port_harvest: Vec::default(),
+            connection_harvest: Vec::default(),
+            cgroup_harvest: Vec::default(),
+            container_harvest: Vec::default(),
+            vm_harvest: Vec::default(),
+            sched_harvest: None,
+            power_harvest: None,
+            plugin_harvest: Vec::default(),
+            wireless_harvest: Vec::default(),
+            kernel_limits_harvest: kernel_limits::KernelLimitsHarvest::default(),
+            pressure_harvest: pressure::PressureHarvest::default(),
+            restricted_harvest: permissions::RestrictedHarvest::default(),
+            pending_alerts: Vec::new(),
+            pending_log_lines: Vec::new(),
+            session_stats: SessionStats::default(),
         }
     }
 }
 
+/// Returns the index of the first entry that's no older than `max_time_millis`.
+fn get_stale_index(
+    timed_data_vec: &[(Instant, TimedData)], current_time: Instant, max_time_millis: u64,
+) -> usize {
+    match timed_data_vec.binary_search_by(|(instant, _timed_data)| {
+        current_time
+            .duration_since(*instant)
+            .as_millis()
+            .cmp(&(max_time_millis as u128))
+            .reverse()
+    }) {
+        Ok(index) => index,
+        Err(index) => index,
+    }
+}
+
+/// Returns the entries that went stale since the last clean, after the `cleared` oldest ones that
+/// were cleared already, and counts them as cleared.
+fn get_newly_stale<'a>(
+    timed_data_vec: &'a mut [(Instant, TimedData)], cleared: &mut usize, current_time: Instant,
+    max_time_millis: u64,
+) -> &'a mut [(Instant, TimedData)] {
+    let start = *cleared;
+    *cleared = get_stale_index(timed_data_vec, current_time, max_time_millis).max(start);
+
+    &mut timed_data_vec[start..*cleared]
+}
+
+/// Returns the name a disk's I/O counters are under, which is the last part of its path.
+fn get_io_name(disk_name: &str) -> Option<&str> {
+    let trim = disk_name.split('/').last()?;
+    if cfg!(target_os = "macos") {
+        // Must trim one level further!
+        static DISK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"disk\d+").unwrap());
+        DISK_REGEX.find(trim).map(|disk_trim| disk_trim.as_str())
+    } else {
+        Some(trim)
+    }
+}
+
 impl DataCollection {
     pub fn reset(&mut self) {
         self.timed_data_vec = Vec::default();
+        self.cleared_entries = ClearedEntries::default();
+        self.boot_markers = Vec::default();
         self.network_harvest = network::NetworkHarvest::default();
+        self.network_counter_increases = HashMap::new();
         self.memory_harvest = memory::MemHarvest::default();
         self.swap_harvest = memory::MemHarvest::default();
+        self.numa_harvest = Vec::default();
+        self.shmem_harvest = None;
+        self.hugepages_harvest = None;
         self.cpu_harvest = cpu::CpuHarvest::default();
+        self.cpu_stats = Vec::default();
+        self.cpu_breakdown_harvest = None;
+        self.throttle_harvest = None;
         self.process_harvest = Vec::default();
+        self.process_events = VecDeque::default();
         self.disk_harvest = Vec::default();
         self.io_harvest = disks::IoHarvest::default();
-        self.io_labels_and_prev = Vec::default();
+        self.io_prev = HashMap::default();
+        self.disk_used_history = Vec::default();
         self.temp_harvest = Vec::default();
         self.battery_harvest = Vec::default();
+        self.power_source = None;
+        self.power_source_changed_instant = None;
+        self.service_harvest = Vec::default();
+        self.port_harvest = Vec::default();
+        self.connection_harvest = Vec::default();
+        self.cgroup_harvest = Vec::default();
+        self.container_harvest = Vec::default();
+        self.vm_harvest = Vec::default();
+        self.sched_harvest = None;
+        self.power_harvest = None;
+        self.plugin_harvest = Vec::default();
+        self.wireless_harvest = Vec::default();
+        self.kernel_limits_harvest = kernel_limits::KernelLimitsHarvest::default();
+        self.pressure_harvest = pressure::PressureHarvest::default();
+        self.restricted_harvest = permissions::RestrictedHarvest::default();
     }
 
     pub fn set_frozen_time(&mut self) {
         self.frozen_instant = Some(self.current_instant);
     }
 
-    pub fn clean_data(&mut self, max_time_millis: u64) {
-        let current_time = Instant::now();
+    /// Moves the frozen time earlier or later by `offset_millis`, snapping to a collected entry
+    /// so the frozen view can be scrolled through anything collected before or since freezing.
+    /// Returns whether the frozen time changed.
+    pub fn shift_frozen_time(&mut self, offset_millis: u64, is_earlier: bool) -> bool {
+        let frozen_instant = match self.frozen_instant {
+            Some(frozen_instant) => frozen_instant,
+            None => return false,
+        };
+        let offset = Duration::from_millis(offset_millis);
 
-        let remove_index = match self
+        let new_frozen_instant = if is_earlier {
+            let target_instant = frozen_instant.checked_sub(offset);
+            self.timed_data_vec
+                .iter()
+                .rev()
+                .map(|(instant, _)| *instant)
+                .find(|instant| match target_instant {
+                    Some(target_instant) => *instant <= target_instant,
+                    None => false,
+                })
+                .or_else(|| self.timed_data_vec.first().map(|(instant, _)| *instant))
+        } else {
+            let target_instant = frozen_instant + offset;
+            self.timed_data_vec
+                .iter()
+                .map(|(instant, _)| *instant)
+                .find(|instant| *instant >= target_instant)
+                .or_else(|| self.timed_data_vec.last().map(|(instant, _)| *instant))
+        };
+
+        match new_frozen_instant {
+            Some(new_frozen_instant) if new_frozen_instant != frozen_instant => {
+                self.frozen_instant = Some(new_frozen_instant);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The time that graphs are drawn up to.
+    pub fn get_graph_end_time(&self, is_frozen: bool) -> Instant {
+        match self.frozen_instant {
+            Some(frozen_instant) if is_frozen => frozen_instant,
+            _ => self.current_instant,
+        }
+    }
+
+    /// Returns the data collected at `instant`, along with how far back it is from the end of
+    /// the graphs, in milliseconds and as a negative number like the graphs' x-axis.
+    pub fn get_graph_data_at(
+        &self, instant: Instant, is_frozen: bool,
+    ) -> Option<(f64, &TimedData)> {
+        let offset = self
+            .get_graph_end_time(is_frozen)
+            .checked_duration_since(instant)?
+            .as_millis() as f64;
+        let (_, data) = self
             .timed_data_vec
-            .binary_search_by(|(instant, _timed_data)| {
-                current_time
-                    .duration_since(*instant)
-                    .as_millis()
-                    .cmp(&(max_time_millis as u128))
-                    .reverse()
-            }) {
-            Ok(index) => index,
-            Err(index) => index,
+            .iter()
+            .rev()
+            .find(|(data_instant, _)| *data_instant <= instant)?;
+
+        Some((-offset, data))
+    }
+
+    /// Returns how far back each reboot is from the end of the graphs, in milliseconds and as a
+    /// negative number like the graphs' x-axis.
+    pub fn get_boot_marker_offsets(&self, is_frozen: bool) -> Vec<f64> {
+        let end_time = self.get_graph_end_time(is_frozen);
+        self.boot_markers
+            .iter()
+            .filter_map(|boot_instant| {
+                Some(-(end_time.checked_duration_since(*boot_instant)?.as_millis() as f64))
+            })
+            .collect()
+    }
+
+    /// Returns a score out of 100 for how much pressure the system is under, blended from the CPU
+    /// usage, memory usage, worst pressure stall, and I/O wait by `weights`.  The parts that went
+    /// into it are returned as well.  Parts that can't be read here or have no weight are left
+    /// out, and if that's all of them, there's no score.
+    pub fn get_pressure_score(
+        &self, weights: &PressureWeights,
+    ) -> Option<(f64, Vec<(&'static str, f64)>)> {
+        let online_cpus = self
+            .cpu_harvest
+            .iter()
+            .filter(|cpu| !cpu.is_offline)
+            .collect::<Vec<_>>();
+        let cpu_usage = match online_cpus.iter().find(|cpu| cpu.cpu_count.is_none()) {
+            Some(avg_cpu) => Some(avg_cpu.cpu_usage),
+            None if !online_cpus.is_empty() => Some(
+                online_cpus.iter().map(|cpu| cpu.cpu_usage).sum::<f64>() / online_cpus.len() as f64,
+            ),
+            None => None,
         };
 
+        let parts = vec![
+            ("CPU", cpu_usage, weights.cpu),
+            ("Memory", self.memory_harvest.use_percent, weights.mem),
+            ("PSI", self.pressure_harvest.get_max(), weights.psi),
+            (
+                "I/O wait",
+                self.cpu_breakdown_harvest
+                    .map(|cpu_breakdown| cpu_breakdown.io_wait_percent),
+                weights.io_wait,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, value, weight)| {
+            Some((name, value?.max(0.0).min(100.0), weight)).filter(|_| weight > 0.0)
+        })
+        .collect::<Vec<_>>();
+
+        let total_weight = parts.iter().map(|(_, _, weight)| weight).sum::<f64>();
+        if total_weight <= 0.0 {
+            return None;
+        }
+
+        let score = parts
+            .iter()
+            .map(|(_, value, weight)| value * weight)
+            .sum::<f64>()
+            / total_weight;
+
+        Some((
+            score,
+            parts
+                .into_iter()
+                .map(|(name, value, _)| (name, value))
+                .collect(),
+        ))
+    }
+
+    /// Removes entries older than any graph keeps.  For graphs that keep less, their part of the
+    /// older entries is cleared out, so memory use follows how much each graph keeps.
+    pub fn clean_data(&mut self, retention: &DataRetention) {
+        let current_time = Instant::now();
+
+        let remove_index = get_stale_index(&self.timed_data_vec, current_time, retention.get_max());
         self.timed_data_vec.drain(0..remove_index);
+        self.cleared_entries.remove_entries(remove_index);
+
+        let max_retention = Duration::from_millis(retention.get_max());
+        for used_history in &mut self.disk_used_history {
+            while let Some((instant, _)) = used_history.front() {
+                if current_time.duration_since(*instant) > max_retention {
+                    used_history.pop_front();
+                } else {
+                    break;
+                }
+            }
+        }
+
+        // A reboot only needs marking while there's data from before it.
+        if let Some((first_instant, _)) = self.timed_data_vec.first() {
+            let first_instant = *first_instant;
+            self.boot_markers
+                .retain(|boot_instant| *boot_instant > first_instant);
+        } else {
+            self.boot_markers.clear();
+        }
+
+        // Only the entries that went stale since the last clean need clearing.
+        for (_instant, timed_data) in get_newly_stale(
+            &mut self.timed_data_vec,
+            &mut self.cleared_entries.cpu,
+            current_time,
+            retention.cpu,
+        ) {
+            timed_data.cpu_data = Vec::new();
+            timed_data.top_process_cpu_data = Vec::new();
+        }
+
+        for (_instant, timed_data) in get_newly_stale(
+            &mut self.timed_data_vec,
+            &mut self.cleared_entries.mem,
+            current_time,
+            retention.mem,
+        ) {
+            timed_data.mem_data = None;
+            timed_data.swap_data = None;
+            timed_data.numa_data = Vec::new();
+        }
+
+        for (_instant, timed_data) in get_newly_stale(
+            &mut self.timed_data_vec,
+            &mut self.cleared_entries.custom,
+            current_time,
+            retention.custom,
+        ) {
+            timed_data.plugin_data = Vec::new();
+        }
+
+        for (_instant, timed_data) in get_newly_stale(
+            &mut self.timed_data_vec,
+            &mut self.cleared_entries.sched,
+            current_time,
+            retention.sched,
+        ) {
+            timed_data.sched_data = None;
+        }
+
+        // The battery power graph is a power graph too, so it keeps as much.
+        for (_instant, timed_data) in get_newly_stale(
+            &mut self.timed_data_vec,
+            &mut self.cleared_entries.power,
+            current_time,
+            retention.power,
+        ) {
+            timed_data.power_data = (None, None);
+            timed_data.battery_power_data = Vec::new();
+        }
+
+        // The network rates are only two numbers per entry, so there's nothing worth clearing;
+        // the network graph just won't zoom out past what it keeps.
     }
 
     pub fn eat_data(&mut self, harvested_data: Box<Data>) {
@@ -129,6 +623,13 @@ impl DataCollection {
         // trace!("New current instant: {:?}", self.current_instant);
         let mut new_entry = TimedData::default();
 
+        self.update_session_stats(
+            harvested_data.cpu.as_deref(),
+            harvested_data.memory.as_ref(),
+            harvested_data.swap.as_ref(),
+            harvested_data.network.as_ref(),
+        );
+
         // Network
         if let Some(network) = harvested_data.network {
             self.eat_network(network, &mut new_entry);
@@ -139,9 +640,24 @@ impl DataCollection {
             self.eat_memory_and_swap(memory, swap, &mut new_entry);
         }
 
+        // NUMA nodes
+        if let Some(numa) = harvested_data.numa {
+            self.eat_numa(numa, &mut new_entry);
+        }
+
+        // Shared memory and tmpfs
+        if let Some(shmem) = harvested_data.shmem {
+            self.eat_shmem(shmem);
+        }
+
+        // Huge pages
+        if let Some(hugepages) = harvested_data.hugepages {
+            self.eat_hugepages(hugepages);
+        }
+
         // CPU
         if let Some(cpu) = harvested_data.cpu {
-            self.eat_cpu(cpu, &mut new_entry);
+            self.eat_cpu(cpu, harvested_time, &mut new_entry);
         }
 
         // Load Average
@@ -149,6 +665,16 @@ impl DataCollection {
             self.eat_load_avg(load_avg, &mut new_entry);
         }
 
+        // CPU breakdown
+        if let Some(cpu_breakdown) = harvested_data.cpu_breakdown {
+            self.cpu_breakdown_harvest = Some(cpu_breakdown);
+        }
+
+        // Throttling and core voltage
+        if let Some(throttle) = harvested_data.throttle {
+            self.throttle_harvest = Some(throttle);
+        }
+
         // Temp
         if let Some(temperature_sensors) = harvested_data.temperature_sensors {
             self.eat_temp(temperature_sensors);
@@ -163,14 +689,88 @@ impl DataCollection {
 
         // Processes
         if let Some(list_of_processes) = harvested_data.list_of_processes {
-            self.eat_proc(list_of_processes);
+            self.eat_proc(list_of_processes, &mut new_entry);
         }
 
         // Battery
         if let Some(list_of_batteries) = harvested_data.list_of_batteries {
-            self.eat_battery(list_of_batteries);
+            self.eat_battery(list_of_batteries, &mut new_entry);
+        }
+        if let Some(power_source) = harvested_data.power_source {
+            self.eat_power_source(power_source, harvested_time);
+        }
+
+        // Services
+        if let Some(services) = harvested_data.services {
+            self.eat_services(services);
+        }
+
+        // Listening ports
+        if let Some(ports) = harvested_data.ports {
+            self.eat_ports(ports);
+        }
+
+        // Established connections
+        if let Some(connections) = harvested_data.connections {
+            self.eat_connections(connections);
+        }
+
+        // Control groups
+        if let Some(cgroups) = harvested_data.cgroups {
+            self.eat_cgroups(cgroups);
+        }
+
+        // Docker containers
+        if let Some(containers) = harvested_data.containers {
+            self.eat_containers(containers);
+        }
+
+        // Virtual machines
+        if let Some(vms) = harvested_data.vms {
+            self.eat_vms(vms);
+        }
+
+        // Context switches and interrupts
+        if let Some(sched) = harvested_data.sched {
+            self.eat_sched(sched, &mut new_entry);
+        }
+
+        // Power consumption
+        if let Some(power) = harvested_data.power {
+            self.eat_power(power, &mut new_entry);
+        }
+
+        // Plugins
+        if let Some(plugins) = harvested_data.plugins {
+            self.eat_plugins(plugins, &mut new_entry);
+        }
+
+        // Wireless
+        if let Some(wireless) = harvested_data.wireless {
+            self.eat_wireless(wireless);
         }
 
+        // Kernel limits
+        if let Some(kernel_limits) = harvested_data.kernel_limits {
+            self.eat_kernel_limits(kernel_limits);
+        }
+
+        // Pressure stall information
+        if let Some(pressure) = harvested_data.pressure {
+            self.eat_pressure(pressure);
+        }
+
+        // Data that couldn't be read without more permissions
+        if let Some(restricted) = harvested_data.restricted {
+            self.eat_restricted(restricted);
+        }
+
+        // Log files
+        self.eat_log_lines(harvested_data.log_lines);
+
+        // Alerts
+        self.pending_alerts.extend(harvested_data.alerts);
+
         // And we're done eating.  Update time and push the new entry!
         self.current_instant = harvested_time;
         self.timed_data_vec.push((harvested_time, new_entry));
@@ -190,6 +790,23 @@ impl DataCollection {
         self.swap_harvest = swap;
     }
 
+    fn eat_numa(&mut self, numa: Vec<memory::NumaHarvest>, new_entry: &mut TimedData) {
+        new_entry.numa_data = numa
+            .iter()
+            .filter_map(|node| Some((node.node, node.use_percent?)))
+            .collect();
+
+        self.numa_harvest = numa;
+    }
+
+    fn eat_shmem(&mut self, shmem: memory::ShmemHarvest) {
+        self.shmem_harvest = Some(shmem);
+    }
+
+    fn eat_hugepages(&mut self, hugepages: memory::HugepagesHarvest) {
+        self.hugepages_harvest = Some(hugepages);
+    }
+
     fn eat_network(&mut self, network: network::NetworkHarvest, new_entry: &mut TimedData) {
         // RX
         if network.rx > 0 {
@@ -201,16 +818,57 @@ impl DataCollection {
             new_entry.tx_data = network.tx as f64;
         }
 
+        // Counters only go up while an interface is up, so any rise means new errors.
+        let prev_counters = &self.network_harvest.interface_counters;
+        self.network_counter_increases = network
+            .interface_counters
+            .iter()
+            .filter_map(|counters| {
+                let prev = prev_counters
+                    .iter()
+                    .find(|prev| prev.name == counters.name)?;
+                let is_increase =
+                    |curr: Option<u64>, prev: Option<u64>| matches!((curr, prev), (Some(curr), Some(prev)) if curr > prev);
+
+                Some((
+                    counters.name.clone(),
+                    InterfaceCounterIncreases {
+                        errors: counters.errors > prev.errors,
+                        drops: is_increase(counters.drops, prev.drops),
+                        collisions: is_increase(counters.collisions, prev.collisions),
+                    },
+                ))
+            })
+            .collect();
+
         // In addition copy over latest data for easy reference
         self.network_harvest = network;
     }
 
-    fn eat_cpu(&mut self, cpu: Vec<cpu::CpuData>, new_entry: &mut TimedData) {
+    fn eat_cpu(
+        &mut self, cpu: Vec<cpu::CpuData>, harvested_time: Instant, new_entry: &mut TimedData,
+    ) {
         // Note this only pre-calculates the data points - the names will be
         // within the local copy of cpu_harvest.  Since it's all sequential
         // it probably doesn't matter anyways.
-        cpu.iter()
-            .for_each(|cpu| new_entry.cpu_data.push(cpu.cpu_usage));
+        // Offline CPUs have no usage to graph, which is stored as NaN like missing memory data.
+        cpu.iter().for_each(|cpu| {
+            new_entry.cpu_data.push(if cpu.is_offline {
+                Value::NAN
+            } else {
+                cpu.cpu_usage
+            })
+        });
+
+        // If the CPUs changed, the old stats no longer line up with them.
+        if self.cpu_stats.len() != cpu.len() {
+            self.cpu_stats = vec![CpuStats::default(); cpu.len()];
+        }
+        self.cpu_stats
+            .iter_mut()
+            .zip(&cpu)
+            .filter(|(_, cpu)| !cpu.is_offline)
+            .for_each(|(cpu_stats, cpu)| cpu_stats.update(cpu.cpu_usage, harvested_time));
 
         self.cpu_harvest = cpu.to_vec();
     }
@@ -235,72 +893,72 @@ impl DataCollection {
             .duration_since(self.current_instant)
             .as_secs_f64();
 
-        for (itx, device) in disks.iter().enumerate() {
-            if let Some(trim) = device.name.split('/').last() {
-                let io_device = if cfg!(target_os = "macos") {
-                    // Must trim one level further!
-                    static DISK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"disk\d+").unwrap());
-                    if let Some(disk_trim) = DISK_REGEX.find(trim) {
-                        io.get(disk_trim.as_str())
-                    } else {
-                        None
-                    }
-                } else {
-                    io.get(trim)
-                };
-
-                if let Some(io_device) = io_device {
-                    let (io_r_pt, io_w_pt) = if let Some(io) = io_device {
-                        (io.read_bytes, io.write_bytes)
-                    } else {
-                        (0, 0)
-                    };
-
-                    if self.io_labels.len() <= itx {
-                        self.io_labels.push((String::default(), String::default()));
-                    }
-
-                    if self.io_labels_and_prev.len() <= itx {
-                        self.io_labels_and_prev.push(((0, 0), (io_r_pt, io_w_pt)));
-                    }
-
-                    if let Some((io_curr, io_prev)) = self.io_labels_and_prev.get_mut(itx) {
-                        let r_rate = ((io_r_pt.saturating_sub(io_prev.0)) as f64
-                            / time_since_last_harvest)
-                            .round() as u64;
-                        let w_rate = ((io_w_pt.saturating_sub(io_prev.1)) as f64
-                            / time_since_last_harvest)
-                            .round() as u64;
-
-                        *io_curr = (r_rate, w_rate);
-                        *io_prev = (io_r_pt, io_w_pt);
-
-                        if let Some(io_labels) = self.io_labels.get_mut(itx) {
-                            let converted_read = get_decimal_bytes(r_rate);
-                            let converted_write = get_decimal_bytes(w_rate);
-                            *io_labels = (
-                                if r_rate >= GIGA_LIMIT {
-                                    format!("{:.*}{}/s", 1, converted_read.0, converted_read.1)
-                                } else {
-                                    format!("{:.*}{}/s", 0, converted_read.0, converted_read.1)
-                                },
-                                if w_rate >= GIGA_LIMIT {
-                                    format!("{:.*}{}/s", 1, converted_write.0, converted_write.1)
-                                } else {
-                                    format!("{:.*}{}/s", 0, converted_write.0, converted_write.1)
-                                },
-                            );
-                        }
-                    }
-                } else {
-                    if self.io_labels.len() <= itx {
-                        self.io_labels.push((String::default(), String::default()));
-                    }
+        let get_rate = |bytes: u64, prev_bytes: u64| {
+            (bytes.saturating_sub(prev_bytes) as f64 / time_since_last_harvest).round() as u64
+        };
 
-                    if let Some(io_labels) = self.io_labels.get_mut(itx) {
-                        *io_labels = ("N/A".to_string(), "N/A".to_string());
-                    }
-                }
+        // Work out the rates of every partition and device, not just the ones that are disks, as
+        // a disk's whole device usually isn't one.
+        let mut io_rates_by_name = HashMap::new();
+        let mut io_prev = HashMap::new();
+        for (name, io_data) in &io {
+            let (read_bytes, write_bytes) = io_data
+                .as_ref()
+                .map(|io_data| (io_data.read_bytes, io_data.write_bytes))
+                .unwrap_or((0, 0));
+            let (prev_read_bytes, prev_write_bytes) = self
+                .io_prev
+                .get(name)
+                .copied()
+                .unwrap_or((read_bytes, write_bytes));
+
+            io_rates_by_name.insert(
+                name.as_str(),
+                (
+                    get_rate(read_bytes, prev_read_bytes),
+                    get_rate(write_bytes, prev_write_bytes),
+                ),
+            );
+            io_prev.insert(name.clone(), (read_bytes, write_bytes));
+        }
+        self.io_prev = io_prev;
+
+        self.io_rates = disks
+            .iter()
+            .map(|disk| io_rates_by_name.get(get_io_name(&disk.name)?).copied())
+            .collect();
+        self.device_io_rates = disks
+            .iter()
+            .map(|disk| {
+                let io_name = get_io_name(&disk.name)?;
+                let device_name = io
+                    .get(io_name)?
+                    .as_ref()
+                    .and_then(|io_data| io_data.device.as_deref())
+                    .unwrap_or(io_name);
+
+                io_rates_by_name.get(device_name).copied()
+            })
+            .collect();
+
+        // A disk's history only carries on if the same disk is still in the same place.
+        self.disk_used_history
+            .resize_with(disks.len(), VecDeque::default);
+        for (index, (disk, used_history)) in
+            disks.iter().zip(&mut self.disk_used_history).enumerate()
+        {
+            let is_same_disk = self
+                .disk_harvest
+                .get(index)
+                .map(|prev_disk| prev_disk.mount_point == disk.mount_point)
+                .unwrap_or(false);
+            if !is_same_disk {
+                used_history.clear();
+            }
+
+            if let (Some(free_space), Some(total_space)) = (disk.free_space, disk.total_space) {
+                used_history
+                    .push_back((harvested_time, total_space as Value - free_space as Value));
             }
         }
 
@@ -308,11 +966,121 @@ impl DataCollection {
         self.io_harvest = io;
     }
 
-    fn eat_proc(&mut self, list_of_processes: Vec<processes::ProcessHarvest>) {
+    fn eat_proc(
+        &mut self, list_of_processes: Vec<processes::ProcessHarvest>, new_entry: &mut TimedData,
+    ) {
+        let mut process_cpu_data = list_of_processes
+            .iter()
+            .map(|process| (process.pid, process.cpu_usage_percent))
+            .collect::<Vec<_>>();
+        process_cpu_data.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        });
+        process_cpu_data.truncate(TOP_PROCESS_COUNT);
+        new_entry.top_process_cpu_data = process_cpu_data;
+
+        if self.is_logging_process_events {
+            self.log_process_events(&list_of_processes);
+        }
         self.process_harvest = list_of_processes;
     }
 
-    fn eat_battery(&mut self, list_of_batteries: Vec<batteries::BatteryHarvest>) {
+    fn eat_battery(
+        &mut self, list_of_batteries: Vec<batteries::BatteryHarvest>, new_entry: &mut TimedData,
+    ) {
+        new_entry.battery_power_data = list_of_batteries
+            .iter()
+            .map(|battery| battery.power_consumption_rate_watts)
+            .collect();
+
         self.battery_harvest = list_of_batteries;
     }
+
+    fn eat_power_source(&mut self, power_source: batteries::PowerSource, harvested_time: Instant) {
+        if self
+            .power_source
+            .map(|prev_power_source| prev_power_source != power_source)
+            .unwrap_or(false)
+        {
+            self.power_source_changed_instant = Some(harvested_time);
+        }
+        self.power_source = Some(power_source);
+    }
+
+    fn eat_services(&mut self, services: Vec<services::ServiceHarvest>) {
+        self.service_harvest = services;
+    }
+
+    fn eat_ports(&mut self, ports: Vec<ports::PortHarvest>) {
+        self.port_harvest = ports;
+    }
+
+    fn eat_connections(&mut self, connections: Vec<ports::ConnectionHarvest>) {
+        self.connection_harvest = connections;
+    }
+
+    fn eat_cgroups(&mut self, cgroups: Vec<cgroups::CgroupHarvest>) {
+        self.cgroup_harvest = cgroups;
+    }
+
+    fn eat_containers(&mut self, containers: Vec<docker::ContainerHarvest>) {
+        self.container_harvest = containers;
+    }
+
+    fn eat_vms(&mut self, vms: Vec<libvirt::VmHarvest>) {
+        self.vm_harvest = vms;
+    }
+
+    fn eat_sched(&mut self, sched: sched::SchedHarvest, new_entry: &mut TimedData) {
+        new_entry.sched_data = Some((sched.context_switches_per_sec, sched.interrupts_per_sec));
+        self.sched_harvest = Some(sched);
+    }
+
+    fn eat_power(&mut self, power: power::PowerHarvest, new_entry: &mut TimedData) {
+        new_entry.power_data = (power.package_watts, power.dram_watts);
+        self.power_harvest = Some(power);
+    }
+
+    fn eat_plugins(&mut self, plugins: Vec<plugins::PluginHarvest>, new_entry: &mut TimedData) {
+        new_entry.plugin_data = plugins
+            .iter()
+            .map(|plugin| (plugin.name.clone(), plugin.value))
+            .collect();
+
+        self.plugin_harvest = plugins;
+    }
+
+    fn eat_wireless(&mut self, wireless: Vec<wireless::WirelessHarvest>) {
+        self.wireless_harvest = wireless;
+    }
+
+    fn eat_log_lines(&mut self, log_lines: Vec<log_tail::LogHarvest>) {
+        // While frozen, these build up until they're added to the widgets, so keep one harvest
+        // per file.
+        for harvest in log_lines {
+            if let Some(pending) = self
+                .pending_log_lines
+                .iter_mut()
+                .find(|pending| pending.widget_id == harvest.widget_id)
+            {
+                pending.merge(harvest);
+            } else {
+                self.pending_log_lines.push(harvest);
+            }
+        }
+    }
+
+    fn eat_kernel_limits(&mut self, kernel_limits: kernel_limits::KernelLimitsHarvest) {
+        self.kernel_limits_harvest = kernel_limits;
+    }
+
+    fn eat_pressure(&mut self, pressure: pressure::PressureHarvest) {
+        self.pressure_harvest = pressure;
+    }
+
+    fn eat_restricted(&mut self, restricted: permissions::RestrictedHarvest) {
+        self.restricted_harvest = restricted;
+    }
 }
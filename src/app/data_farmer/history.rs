@@ -0,0 +1,308 @@
+//! Saving and loading graph history, so graphs can be pre-populated when bottom restarts.
+//!
+//! The file is a small header followed by fixed-layout little-endian records, oldest first.
+//! Since an [`Instant`] can't be written out, each record stores the wall-clock time it was
+//! collected at instead.  The header also lists when any reboots in the history happened, so
+//! graphs can mark them.
+//!
+//! A day of history can take a while to write out, so it's written by a [`HistoryWriter`] on its
+//! own thread rather than holding up drawing.
+
+use std::{
+    convert::TryInto,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Sender},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use super::{ClearedEntries, DataCollection, TimedData};
+use crate::utils::error::{self, BottomError};
+
+const HISTORY_MAGIC: &[u8; 8] = b"BTMHIST\0";
+const HISTORY_VERSION: u8 = 2;
+/// The first version, which is still read, didn't list reboots.
+const HISTORY_VERSION_WITHOUT_BOOTS: u8 = 1;
+
+/// Reads little-endian values out of a byte slice, returning `None` once it runs out.
+struct HistoryReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> HistoryReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.bytes.len() < len {
+            None
+        } else {
+            let (taken, rest) = self.bytes.split_at(len);
+            self.bytes = rest;
+            Some(taken)
+        }
+    }
+
+    fn read_u16(&mut self) -> Option<u16> {
+        Some(u16::from_le_bytes(self.take(2)?.try_into().ok()?))
+    }
+
+    fn read_u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    fn read_f32(&mut self) -> Option<f32> {
+        Some(f32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn read_f64(&mut self) -> Option<f64> {
+        Some(f64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    fn read_entry(&mut self) -> Option<(u64, TimedData)> {
+        let unix_millis = self.read_u64()?;
+        let rx_data = self.read_f64()?;
+        let tx_data = self.read_f64()?;
+        let load_avg_data = [self.read_f32()?, self.read_f32()?, self.read_f32()?];
+        let mem_data = Some(self.read_f64()?).filter(|mem| !mem.is_nan());
+        let swap_data = Some(self.read_f64()?).filter(|swap| !swap.is_nan());
+        let num_cpus = self.read_u16()?;
+        let cpu_data = (0..num_cpus)
+            .map(|_| self.read_f64())
+            .collect::<Option<Vec<_>>>()?;
+
+        Some((
+            unix_millis,
+            TimedData {
+                rx_data,
+                tx_data,
+                cpu_data,
+                load_avg_data,
+                mem_data,
+                swap_data,
+                numa_data: Vec::new(),
+                plugin_data: Vec::new(),
+                sched_data: None,
+                power_data: (None, None),
+                top_process_cpu_data: Vec::new(),
+                battery_power_data: Vec::new(),
+            },
+        ))
+    }
+}
+
+fn write_entry(buffer: &mut Vec<u8>, unix_millis: u64, data: &TimedData) {
+    buffer.extend_from_slice(&unix_millis.to_le_bytes());
+    buffer.extend_from_slice(&data.rx_data.to_le_bytes());
+    buffer.extend_from_slice(&data.tx_data.to_le_bytes());
+    for load_avg in &data.load_avg_data {
+        buffer.extend_from_slice(&load_avg.to_le_bytes());
+    }
+    buffer.extend_from_slice(&data.mem_data.unwrap_or(f64::NAN).to_le_bytes());
+    buffer.extend_from_slice(&data.swap_data.unwrap_or(f64::NAN).to_le_bytes());
+
+    let num_cpus = std::cmp::min(data.cpu_data.len(), u16::MAX as usize);
+    buffer.extend_from_slice(&(num_cpus as u16).to_le_bytes());
+    for cpu in &data.cpu_data[..num_cpus] {
+        buffer.extend_from_slice(&cpu.to_le_bytes());
+    }
+}
+
+fn get_unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Returns when the system was booted, in milliseconds since the Unix epoch.
+#[cfg(target_os = "linux")]
+fn get_boot_unix_millis() -> Option<u64> {
+    procfs::boot_time_secs()
+        .ok()
+        .map(|boot_time_secs| boot_time_secs * 1000)
+}
+
+/// Returns when the system was booted, in milliseconds since the Unix epoch.
+#[cfg(not(target_os = "linux"))]
+fn get_boot_unix_millis() -> Option<u64> {
+    use sysinfo::{System, SystemExt};
+
+    Some(System::new().get_boot_time() * 1000).filter(|boot_unix_millis| *boot_unix_millis > 0)
+}
+
+/// Writes history to a file on its own thread.
+pub struct HistoryWriter {
+    sender: Option<Sender<Vec<u8>>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HistoryWriter {
+    pub fn start(path: PathBuf) -> Self {
+        let (sender, receiver) = mpsc::channel::<Vec<u8>>();
+        let thread = thread::spawn(move || {
+            while let Ok(buffer) = receiver.recv() {
+                // If writes are falling behind, only the newest history is worth writing.
+                let buffer = receiver.try_iter().last().unwrap_or(buffer);
+                let _ = write_history(&path, &buffer);
+            }
+        });
+
+        HistoryWriter {
+            sender: Some(sender),
+            thread: Some(thread),
+        }
+    }
+
+    /// Queues up any entries newer than `retention_millis` to be written out.
+    pub fn save(&self, data_collection: &DataCollection, retention_millis: u64) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(data_collection.encode_history(retention_millis));
+        }
+    }
+
+    /// Waits for anything that's been queued up to be written.
+    pub fn finish(mut self) {
+        drop(self.sender.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn write_history(path: &Path, buffer: &[u8]) -> error::Result<()> {
+    if let Some(parent_path) = path.parent() {
+        fs::create_dir_all(parent_path)?;
+    }
+
+    // Write to a temporary file first so a crash mid-write doesn't clobber the old history.
+    let temp_path = path.with_extension("tmp");
+    fs::File::create(&temp_path)?.write_all(buffer)?;
+    fs::rename(&temp_path, path)?;
+
+    Ok(())
+}
+
+impl DataCollection {
+    /// Lays out any entries newer than `retention_millis` as a history file.
+    fn encode_history(&self, retention_millis: u64) -> Vec<u8> {
+        let current_instant = Instant::now();
+        let current_time = SystemTime::now();
+
+        // Returns when something within the retention happened, in milliseconds since the Unix
+        // epoch.
+        let get_saved_unix_millis = |instant: Instant| {
+            let age = current_instant.duration_since(instant);
+            if age.as_millis() > retention_millis as u128 {
+                None
+            } else {
+                current_time.checked_sub(age).map(get_unix_millis)
+            }
+        };
+
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(HISTORY_MAGIC);
+        buffer.push(HISTORY_VERSION);
+
+        let boot_unix_millis = self
+            .boot_markers
+            .iter()
+            .filter_map(|boot_instant| get_saved_unix_millis(*boot_instant))
+            .take(u16::MAX as usize)
+            .collect::<Vec<_>>();
+        buffer.extend_from_slice(&(boot_unix_millis.len() as u16).to_le_bytes());
+        for boot_unix_millis in boot_unix_millis {
+            buffer.extend_from_slice(&boot_unix_millis.to_le_bytes());
+        }
+
+        for (instant, data) in &self.timed_data_vec {
+            if let Some(unix_millis) = get_saved_unix_millis(*instant) {
+                write_entry(&mut buffer, unix_millis, data);
+            }
+        }
+
+        buffer
+    }
+
+    /// Reads entries newer than `retention_millis` from `path`, and puts them in front of any
+    /// existing entries.  If the system has been rebooted since, that's marked as well.
+    pub fn load_history(&mut self, path: &Path, retention_millis: u64) -> error::Result<()> {
+        let bytes = fs::read(path)?;
+        let mut reader = HistoryReader { bytes: &bytes };
+
+        if reader.take(HISTORY_MAGIC.len()) != Some(&HISTORY_MAGIC[..]) {
+            return Err(BottomError::GenericError(format!(
+                "{:?} is not a bottom history file.",
+                path
+            )));
+        }
+
+        let saved_boot_unix_millis = match reader.take(1) {
+            Some([HISTORY_VERSION]) => {
+                let num_boots = reader.read_u16().unwrap_or(0);
+                (0..num_boots)
+                    .map(|_| reader.read_u64())
+                    .collect::<Option<Vec<_>>>()
+                    .unwrap_or_default()
+            }
+            Some([HISTORY_VERSION_WITHOUT_BOOTS]) => Vec::new(),
+            _ => {
+                return Err(BottomError::GenericError(format!(
+                    "{:?} has an unsupported history version.",
+                    path
+                )));
+            }
+        };
+
+        let current_instant = Instant::now();
+        let current_unix_millis = get_unix_millis(SystemTime::now());
+        let get_instant = |unix_millis: u64| {
+            let age_millis = current_unix_millis.saturating_sub(unix_millis);
+            if age_millis > retention_millis {
+                None
+            } else {
+                current_instant.checked_sub(Duration::from_millis(age_millis))
+            }
+        };
+
+        let mut loaded_data = Vec::new();
+        let mut last_unix_millis = None;
+
+        // A truncated last entry is just ignored.
+        while let Some((unix_millis, data)) = reader.read_entry() {
+            if let Some(instant) = get_instant(unix_millis) {
+                // Guard against clock changes putting entries out of order.
+                if let Some((last_instant, _)) = loaded_data.last() {
+                    if instant <= *last_instant {
+                        continue;
+                    }
+                }
+                loaded_data.push((instant, data));
+                last_unix_millis = Some(unix_millis);
+            }
+        }
+
+        let mut boot_markers = saved_boot_unix_millis
+            .into_iter()
+            .filter_map(get_instant)
+            .collect::<Vec<_>>();
+
+        // Anything saved before the system last booted is from before a reboot.
+        if let (Some(last_unix_millis), Some(boot_unix_millis)) =
+            (last_unix_millis, get_boot_unix_millis())
+        {
+            if last_unix_millis < boot_unix_millis {
+                if let Some(boot_instant) = get_instant(boot_unix_millis) {
+                    boot_markers.push(boot_instant);
+                }
+            }
+        }
+
+        loaded_data.append(&mut self.timed_data_vec);
+        self.timed_data_vec = loaded_data;
+        // The loaded entries go before anything already cleaned, so check everything again.
+        self.cleared_entries = ClearedEntries::default();
+        boot_markers.append(&mut self.boot_markers);
+        self.boot_markers = boot_markers;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+//! Working out when processes start, exit, or change state, by comparing each process harvest
+//! against the one before it.
+
+use chrono::{DateTime, Local};
+use fxhash::FxHashMap;
+
+use super::DataCollection;
+use crate::{data_harvester::processes::ProcessHarvest, Pid};
+
+/// How many events are kept, with the oldest dropped first.
+pub const MAX_PROCESS_EVENTS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessEventKind {
+    Started,
+    Exited,
+    Zombie,
+    Stopped,
+    Resumed,
+}
+
+impl ProcessEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProcessEventKind::Started => "started",
+            ProcessEventKind::Exited => "exited",
+            ProcessEventKind::Zombie => "became a zombie",
+            ProcessEventKind::Stopped => "stopped",
+            ProcessEventKind::Resumed => "resumed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessEvent {
+    pub time: DateTime<Local>,
+    pub pid: Pid,
+    pub name: String,
+    pub kind: ProcessEventKind,
+}
+
+fn is_stopped(state: char) -> bool {
+    matches!(state, 'T' | 't')
+}
+
+impl DataCollection {
+    /// Logs the processes that started, exited, or changed state between the last process
+    /// harvest and `new_processes`.
+    pub(super) fn log_process_events(&mut self, new_processes: &[ProcessHarvest]) {
+        // Without a harvest to compare against, every process would look like it just started.
+        if self.process_harvest.is_empty() {
+            return;
+        }
+
+        let time = Local::now();
+        let mut prev_processes = self
+            .process_harvest
+            .iter()
+            .map(|process| (process.pid, process))
+            .collect::<FxHashMap<_, _>>();
+        let mut events = Vec::new();
+        let mut push_event = |process: &ProcessHarvest, kind: ProcessEventKind| {
+            events.push(ProcessEvent {
+                time,
+                pid: process.pid,
+                name: process.name.to_string(),
+                kind,
+            });
+        };
+
+        for process in new_processes {
+            // A PID that was reused by a new process counts as the old one exiting, which is
+            // picked up below as it's left in the map.
+            let prev_process = match prev_processes.get(&process.pid) {
+                Some(prev_process) if prev_process.start_time == process.start_time => {
+                    prev_processes.remove(&process.pid)
+                }
+                _ => None,
+            };
+
+            match prev_process {
+                None => push_event(process, ProcessEventKind::Started),
+                Some(prev_process) => {
+                    let (prev_state, state) =
+                        (prev_process.process_state_char, process.process_state_char);
+                    if state == 'Z' && prev_state != 'Z' {
+                        push_event(process, ProcessEventKind::Zombie);
+                    } else if is_stopped(state) && !is_stopped(prev_state) {
+                        push_event(process, ProcessEventKind::Stopped);
+                    } else if !is_stopped(state) && is_stopped(prev_state) {
+                        push_event(process, ProcessEventKind::Resumed);
+                    }
+                }
+            }
+        }
+
+        let mut exited_processes = prev_processes
+            .into_iter()
+            .map(|(_, process)| process)
+            .collect::<Vec<_>>();
+        exited_processes.sort_by_key(|process| process.pid);
+        for process in exited_processes {
+            push_event(process, ProcessEventKind::Exited);
+        }
+
+        self.num_process_events_logged += events.len();
+        self.process_events.extend(events);
+        let num_excess_events = self.process_events.len().saturating_sub(MAX_PROCESS_EVENTS);
+        self.process_events.drain(..num_excess_events);
+    }
+}
@@ -0,0 +1,101 @@
+//! Keeping the lowest, average, and highest CPU, memory, and network usage over the whole session,
+//! for the summary that can be printed when bottom exits.
+
+use chrono::{DateTime, Local};
+
+use super::{DataCollection, Value};
+use crate::data_harvester::{cpu, memory, network};
+
+/// The lowest, average, and highest of a value, without keeping every reading.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunningStat {
+    min: Value,
+    max: Value,
+    sum: Value,
+    count: u64,
+}
+
+impl RunningStat {
+    fn update(&mut self, value: Value) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// The lowest, average, and highest readings, or `None` if there weren't any.
+    pub fn get_min_avg_max(&self) -> Option<(Value, Value, Value)> {
+        if self.count == 0 {
+            None
+        } else {
+            Some((self.min, self.sum / self.count as Value, self.max))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    pub start_time: DateTime<Local>,
+    /// Overall CPU usage, as a percentage.
+    pub cpu: RunningStat,
+    /// RAM and swap usage, as percentages.
+    pub mem: RunningStat,
+    pub swap: RunningStat,
+    /// Network rates, in bits per second.
+    pub rx: RunningStat,
+    pub tx: RunningStat,
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        SessionStats {
+            start_time: Local::now(),
+            cpu: RunningStat::default(),
+            mem: RunningStat::default(),
+            swap: RunningStat::default(),
+            rx: RunningStat::default(),
+            tx: RunningStat::default(),
+        }
+    }
+}
+
+impl DataCollection {
+    /// Adds a harvest to the session stats.  Unlike the rest of the collection, these aren't
+    /// cleared by a reset, as they cover the whole session.
+    pub(super) fn update_session_stats(
+        &mut self, cpu: Option<&[cpu::CpuData]>, memory: Option<&memory::MemHarvest>,
+        swap: Option<&memory::MemHarvest>, network: Option<&network::NetworkHarvest>,
+    ) {
+        let stats = &mut self.session_stats;
+
+        if let Some(cpu) = cpu {
+            // The average entry is only there if it's shown, so work it out from the cores.
+            let (usage_sum, num_cores) = cpu
+                .iter()
+                .filter(|cpu| cpu.cpu_count.is_some() && !cpu.is_offline)
+                .fold((0.0, 0), |(sum, count), cpu| {
+                    (sum + cpu.cpu_usage, count + 1)
+                });
+            if num_cores > 0 {
+                stats.cpu.update(usage_sum / num_cores as Value);
+            }
+        }
+
+        if let Some(use_percent) = memory.and_then(|memory| memory.use_percent) {
+            stats.mem.update(use_percent);
+        }
+        if let Some(use_percent) = swap.and_then(|swap| swap.use_percent) {
+            stats.swap.update(use_percent);
+        }
+
+        if let Some(network) = network {
+            stats.rx.update(network.rx as Value);
+            stats.tx.update(network.tx as Value);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Saving the latest harvested data to a JSON file, to capture exactly what bottom saw at a
+//! given moment.  This uses the same format as the websocket stream (see
+//! [`crate::data_harvester::stream`]).
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::DataCollection;
+use crate::{
+    data_harvester::{stream::data_to_json, Data},
+    utils::error,
+};
+
+impl DataCollection {
+    /// Rebuilds a [`Data`] snapshot out of the latest harvest of everything.
+    fn latest_data(&self) -> Data {
+        Data {
+            last_collection_time: self.current_instant,
+            cpu: Some(self.cpu_harvest.clone()),
+            load_avg: if cfg!(target_family = "unix") {
+                Some(self.load_avg_harvest)
+            } else {
+                None
+            },
+            cpu_breakdown: self.cpu_breakdown_harvest,
+            throttle: self.throttle_harvest,
+            memory: Some(self.memory_harvest.clone()),
+            swap: Some(self.swap_harvest.clone()),
+            numa: Some(self.numa_harvest.clone()),
+            shmem: self.shmem_harvest.clone(),
+            hugepages: self.hugepages_harvest.clone(),
+            temperature_sensors: Some(self.temp_harvest.clone()),
+            network: Some(self.network_harvest.clone()),
+            list_of_processes: Some(self.process_harvest.clone()),
+            disks: Some(self.disk_harvest.clone()),
+            io: Some(self.io_harvest.clone()),
+            list_of_batteries: Some(self.battery_harvest.clone()),
+            power_source: self.power_source,
+            services: Some(self.service_harvest.clone()),
+            ports: Some(self.port_harvest.clone()),
+            connections: Some(self.connection_harvest.clone()),
+            cgroups: Some(self.cgroup_harvest.clone()),
+            containers: Some(self.container_harvest.clone()),
+            vms: Some(self.vm_harvest.clone()),
+            sched: self.sched_harvest,
+            power: self.power_harvest,
+            plugins: Some(self.plugin_harvest.clone()),
+            wireless: Some(self.wireless_harvest.clone()),
+            log_lines: Vec::new(),
+            kernel_limits: Some(self.kernel_limits_harvest),
+            pressure: Some(self.pressure_harvest),
+            restricted: Some(self.restricted_harvest),
+            alerts: Vec::new(),
+        }
+    }
+
+    /// Writes the latest harvested data to a timestamped file in `snapshot_dir`, and returns
+    /// the path of that file.
+    pub fn save_snapshot(&self, snapshot_dir: &Path) -> error::Result<PathBuf> {
+        fs::create_dir_all(snapshot_dir)?;
+
+        let path = snapshot_dir.join(format!(
+            "btm_snapshot_{}.json",
+            chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")
+        ));
+        fs::write(&path, format!("{:#}", data_to_json(&self.latest_data())))?;
+
+        Ok(path)
+    }
+}
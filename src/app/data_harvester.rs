@@ -1,6 +1,9 @@
 //! This is the main file to house data collection functions.
 
-use std::time::Instant;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 #[cfg(target_os = "linux")]
 use fxhash::FxHashMap;
@@ -16,27 +19,70 @@ use futures::join;
 
 use super::DataFilters;
 
+/// How long to wait after the first harvest before the next, so there's something to measure.
+const FIRST_HARVEST_WAIT: Duration = Duration::from_millis(250);
+
 pub mod batteries;
+pub mod cgroups;
+pub mod command;
 pub mod cpu;
+pub mod disk_usage;
 pub mod disks;
+pub mod docker;
+pub mod exporter;
+pub mod kernel_limits;
+pub mod libvirt;
+pub mod log_tail;
 pub mod memory;
 pub mod network;
+pub mod permissions;
+pub mod plugins;
+pub mod ports;
+pub mod power;
+pub mod pressure;
 pub mod processes;
+pub mod remote;
+pub mod sched;
+pub mod services;
+pub mod stream;
 pub mod temperature;
+pub mod wireless;
 
 #[derive(Clone, Debug)]
 pub struct Data {
     pub last_collection_time: Instant,
     pub cpu: Option<cpu::CpuHarvest>,
     pub load_avg: Option<cpu::LoadAvgHarvest>,
+    pub cpu_breakdown: Option<cpu::CpuBreakdown>,
+    pub throttle: Option<cpu::ThrottleHarvest>,
     pub memory: Option<memory::MemHarvest>,
     pub swap: Option<memory::MemHarvest>,
+    pub numa: Option<Vec<memory::NumaHarvest>>,
+    pub shmem: Option<memory::ShmemHarvest>,
+    pub hugepages: Option<memory::HugepagesHarvest>,
     pub temperature_sensors: Option<Vec<temperature::TempHarvest>>,
     pub network: Option<network::NetworkHarvest>,
     pub list_of_processes: Option<Vec<processes::ProcessHarvest>>,
     pub disks: Option<Vec<disks::DiskHarvest>>,
     pub io: Option<disks::IoHarvest>,
     pub list_of_batteries: Option<Vec<batteries::BatteryHarvest>>,
+    pub power_source: Option<batteries::PowerSource>,
+    pub services: Option<Vec<services::ServiceHarvest>>,
+    pub ports: Option<Vec<ports::PortHarvest>>,
+    pub connections: Option<Vec<ports::ConnectionHarvest>>,
+    pub cgroups: Option<Vec<cgroups::CgroupHarvest>>,
+    pub containers: Option<Vec<docker::ContainerHarvest>>,
+    pub vms: Option<Vec<libvirt::VmHarvest>>,
+    pub sched: Option<sched::SchedHarvest>,
+    pub power: Option<power::PowerHarvest>,
+    pub plugins: Option<Vec<plugins::PluginHarvest>>,
+    pub wireless: Option<Vec<wireless::WirelessHarvest>>,
+    pub log_lines: Vec<log_tail::LogHarvest>,
+    pub kernel_limits: Option<kernel_limits::KernelLimitsHarvest>,
+    pub pressure: Option<pressure::PressureHarvest>,
+    pub restricted: Option<permissions::RestrictedHarvest>,
+    /// The messages of any alerts that fired during this harvest.
+    pub alerts: Vec<String>,
 }
 
 impl Default for Data {
@@ -45,14 +91,35 @@ impl Default for Data {
             last_collection_time: Instant::now(),
             cpu: None,
             load_avg: None,
+            cpu_breakdown: None,
+            throttle: None,
             memory: None,
             swap: None,
+            numa: None,
+            shmem: None,
+            hugepages: None,
             temperature_sensors: None,
             list_of_processes: None,
             disks: None,
             io: None,
             network: None,
             list_of_batteries: None,
+            power_source: None,
+            services: None,
+            ports: None,
+            connections: None,
+            cgroups: None,
+            containers: None,
+            vms: None,
+            sched: None,
+            power: None,
+            plugins: None,
+            wireless: None,
+            log_lines: Vec::new(),
+            kernel_limits: None,
+            pressure: None,
+            restricted: None,
+            alerts: Vec::new(),
         }
     }
 }
@@ -65,8 +132,27 @@ impl Data {
         self.disks = None;
         self.memory = None;
         self.swap = None;
+        self.numa = None;
+        self.shmem = None;
+        self.hugepages = None;
         self.cpu = None;
         self.load_avg = None;
+        self.cpu_breakdown = None;
+        self.throttle = None;
+        self.services = None;
+        self.ports = None;
+        self.connections = None;
+        self.cgroups = None;
+        self.containers = None;
+        self.vms = None;
+        self.sched = None;
+        self.power = None;
+        self.plugins = None;
+        self.wireless = None;
+        self.kernel_limits = None;
+        self.pressure = None;
+        self.restricted = None;
+        self.alerts = Vec::new();
 
         if let Some(network) = &mut self.network {
             network.first_run_cleanup();
@@ -79,25 +165,76 @@ pub struct DataCollector {
     pub data: Data,
     #[cfg(not(target_os = "linux"))]
     sys: System,
-    previous_cpu_times: Vec<(cpu::PastCpuWork, cpu::PastCpuTotal)>,
+    #[cfg(not(target_os = "linux"))]
+    has_components_list: bool,
+    #[cfg(not(target_os = "linux"))]
+    has_networks_list: bool,
+    previous_cpu_times: FxHashMap<usize, (cpu::PastCpuWork, cpu::PastCpuTotal)>,
     previous_average_cpu_time: Option<(cpu::PastCpuWork, cpu::PastCpuTotal)>,
     #[cfg(target_os = "linux")]
+    previous_cpu_state_times: Option<cpu::CpuStateTimes>,
+    #[cfg(target_os = "linux")]
+    throttle_state: cpu::ThrottleState,
+    #[cfg(target_os = "linux")]
     pid_mapping: FxHashMap<crate::Pid, processes::PrevProcDetails>,
+    process_string_interner: processes::StringInterner,
     #[cfg(target_os = "linux")]
     prev_idle: f64,
     #[cfg(target_os = "linux")]
     prev_non_idle: f64,
+    #[cfg(target_os = "linux")]
+    service_units: services::ServiceUnits,
+    #[cfg(target_os = "linux")]
+    prev_cgroup_stats: cgroups::PrevCgroupStats,
+    #[cfg(unix)]
+    prev_container_stats: docker::PrevContainerStats,
+    #[cfg(target_os = "linux")]
+    prev_vm_stats: libvirt::PrevVmStats,
+    #[cfg(target_os = "linux")]
+    prev_sched_counts: sched::PrevSchedCounts,
+    #[cfg(target_os = "linux")]
+    prev_energy_counts: power::PrevEnergyCounts,
+    #[cfg(target_os = "linux")]
+    smartctl_cache: temperature::SmartctlCache,
+    #[cfg(all(feature = "libsensors", target_os = "linux"))]
+    libsensors: Option<temperature::Libsensors>,
     mem_total_kb: u64,
-    temperature_type: temperature::TemperatureType,
     use_current_cpu_total: bool,
     last_collection_time: Instant,
     total_rx: u64,
     total_tx: u64,
+    prev_ipv4: Option<network::IpTraffic>,
+    prev_ipv6: Option<network::IpTraffic>,
+    prev_links: Vec<network::InterfaceLink>,
     show_average_cpu: bool,
     widgets_to_harvest: UsedWidgets,
     battery_manager: Option<Manager>,
     battery_list: Option<Vec<Battery>>,
+    battery_alerter: Option<batteries::alert::BatteryAlerter>,
     filters: DataFilters,
+    plugin_runner: plugins::PluginRunner,
+    collect_gpu_processes: bool,
+    gpu_monitor: Option<processes::gpu::GpuMonitor>,
+    #[cfg(all(feature = "ebpf", target_os = "linux"))]
+    collect_tcp_processes: bool,
+    collect_k8s_processes: bool,
+    include_child_cpu_time: bool,
+    collect_fd_counts: bool,
+    /// Whether to read each process' status file, which only the swap, capabilities, and seccomp
+    /// columns need.
+    collect_process_status: bool,
+    fd_counter: processes::fds::FdCounter,
+    port_owners: ports::PortOwners,
+    connection_owners: ports::PortOwners,
+    /// Whether to look up the hostnames of connections' remote addresses.
+    resolve_hostnames: bool,
+    hostname_cache: Option<ports::HostnameCache>,
+    wireless_links: wireless::WirelessLinks,
+    log_tails: Vec<log_tail::LogTail>,
+    #[cfg(target_family = "unix")]
+    user_table: processes::UserTable,
+    #[cfg(all(feature = "ebpf", target_os = "linux"))]
+    tcp_tracer: Option<processes::tcp::TcpTracer>,
 }
 
 impl DataCollector {
@@ -106,29 +243,80 @@ impl DataCollector {
             data: Data::default(),
             #[cfg(not(target_os = "linux"))]
             sys: System::new_with_specifics(sysinfo::RefreshKind::new()),
-            previous_cpu_times: vec![],
+            #[cfg(not(target_os = "linux"))]
+            has_components_list: false,
+            #[cfg(not(target_os = "linux"))]
+            has_networks_list: false,
+            previous_cpu_times: FxHashMap::default(),
             previous_average_cpu_time: None,
             #[cfg(target_os = "linux")]
+            previous_cpu_state_times: None,
+            #[cfg(target_os = "linux")]
+            throttle_state: cpu::ThrottleState::default(),
+            #[cfg(target_os = "linux")]
             pid_mapping: FxHashMap::default(),
+            process_string_interner: processes::StringInterner::default(),
             #[cfg(target_os = "linux")]
             prev_idle: 0_f64,
             #[cfg(target_os = "linux")]
             prev_non_idle: 0_f64,
+            #[cfg(target_os = "linux")]
+            service_units: services::ServiceUnits::default(),
+            #[cfg(target_os = "linux")]
+            prev_cgroup_stats: cgroups::PrevCgroupStats::default(),
+            #[cfg(unix)]
+            prev_container_stats: docker::PrevContainerStats::default(),
+            #[cfg(target_os = "linux")]
+            prev_vm_stats: libvirt::PrevVmStats::default(),
+            #[cfg(target_os = "linux")]
+            prev_sched_counts: None,
+            #[cfg(target_os = "linux")]
+            prev_energy_counts: power::PrevEnergyCounts::default(),
+            #[cfg(target_os = "linux")]
+            smartctl_cache: temperature::SmartctlCache::default(),
+            #[cfg(all(feature = "libsensors", target_os = "linux"))]
+            libsensors: None,
             mem_total_kb: 0,
-            temperature_type: temperature::TemperatureType::Celsius,
             use_current_cpu_total: false,
             last_collection_time: Instant::now(),
             total_rx: 0,
             total_tx: 0,
+            prev_ipv4: None,
+            prev_ipv6: None,
+            prev_links: Vec::new(),
             show_average_cpu: false,
             widgets_to_harvest: UsedWidgets::default(),
             battery_manager: None,
             battery_list: None,
+            battery_alerter: None,
             filters,
+            plugin_runner: plugins::PluginRunner::default(),
+            collect_gpu_processes: false,
+            gpu_monitor: None,
+            #[cfg(all(feature = "ebpf", target_os = "linux"))]
+            collect_tcp_processes: false,
+            collect_k8s_processes: false,
+            include_child_cpu_time: false,
+            collect_fd_counts: false,
+            collect_process_status: false,
+            fd_counter: processes::fds::FdCounter::default(),
+            port_owners: ports::PortOwners::default(),
+            connection_owners: ports::PortOwners::default(),
+            resolve_hostnames: false,
+            hostname_cache: None,
+            wireless_links: wireless::WirelessLinks::default(),
+            log_tails: Vec::new(),
+            #[cfg(target_family = "unix")]
+            user_table: processes::UserTable::default(),
+            #[cfg(all(feature = "ebpf", target_os = "linux"))]
+            tcp_tracer: None,
         }
     }
 
     pub fn init(&mut self) {
+        #[cfg(feature = "log")]
+        let init_start_time = Instant::now();
+
         #[cfg(target_os = "linux")]
         {
             futures::executor::block_on(self.initialize_memory_size());
@@ -138,19 +326,48 @@ impl DataCollector {
             self.sys.refresh_memory();
             self.mem_total_kb = self.sys.get_total_memory();
 
-            // TODO: Would be good to get this and network list running on a timer instead...?
-            // Refresh components list once...
-            if self.widgets_to_harvest.use_temp {
-                self.sys.refresh_components_list();
-            }
+            // These are quick to set up, and the first harvest needs the network list to measure
+            // rates against.
+            self.set_up_sysinfo_lists();
+        }
 
-            // Refresh network list once...
-            if cfg!(target_os = "windows") && self.widgets_to_harvest.use_net {
-                self.sys.refresh_networks_list();
-            }
+        // The first harvest is only there so later ones have something to work out CPU usage and
+        // rates against, and it needs some time to pass before the next one.  The harvesters that
+        // are slow to start are set up during that wait, rather than holding up startup before it.
+        futures::executor::block_on(self.update_data());
+        let first_harvest_time = Instant::now();
+
+        self.set_up_harvesters();
+
+        if let Some(remaining_wait) = FIRST_HARVEST_WAIT.checked_sub(first_harvest_time.elapsed()) {
+            std::thread::sleep(remaining_wait);
+        }
+
+        self.data.cleanup();
+
+        #[cfg(feature = "log")]
+        debug!(
+            "Collector started in {:?}, with the first harvest taking {:?}",
+            init_start_time.elapsed(),
+            first_harvest_time.duration_since(init_start_time)
+        );
+
+        // trace!("Enabled widgets to harvest: {:#?}", self.widgets_to_harvest);
+    }
+
+    /// Sets up the harvesters that are slow to start, like the battery manager, if the widgets
+    /// that need them are in use and they aren't set up yet.  This is called again whenever the
+    /// widgets in use change, so nothing is set up for a widget until it's actually shown.
+    pub fn set_up_harvesters(&mut self) {
+        #[cfg(not(target_os = "linux"))]
+        {
+            self.set_up_sysinfo_lists();
         }
 
-        if self.widgets_to_harvest.use_battery {
+        // Alerts need battery data even if there's no battery widget to show it.
+        if (self.widgets_to_harvest.use_battery || self.battery_alerter.is_some())
+            && self.battery_manager.is_none()
+        {
             if let Ok(battery_manager) = Manager::new() {
                 if let Ok(batteries) = battery_manager.batteries() {
                     let battery_list: Vec<Battery> = batteries.filter_map(Result::ok).collect();
@@ -162,13 +379,51 @@ impl DataCollector {
             }
         }
 
-        futures::executor::block_on(self.update_data());
+        #[cfg(all(feature = "libsensors", target_os = "linux"))]
+        {
+            if self.widgets_to_harvest.use_temp && self.libsensors.is_none() {
+                self.libsensors = temperature::Libsensors::init();
+            }
+        }
 
-        std::thread::sleep(std::time::Duration::from_millis(250));
+        if self.widgets_to_harvest.use_proc
+            && self.collect_gpu_processes
+            && self.gpu_monitor.is_none()
+        {
+            self.gpu_monitor = Some(processes::gpu::GpuMonitor::start());
+        }
 
-        self.data.cleanup();
+        if self.widgets_to_harvest.use_connections
+            && self.resolve_hostnames
+            && self.hostname_cache.is_none()
+        {
+            self.hostname_cache = Some(ports::HostnameCache::start());
+        }
 
-        // trace!("Enabled widgets to harvest: {:#?}", self.widgets_to_harvest);
+        #[cfg(all(feature = "ebpf", target_os = "linux"))]
+        {
+            if self.widgets_to_harvest.use_proc
+                && self.collect_tcp_processes
+                && self.tcp_tracer.is_none()
+            {
+                self.tcp_tracer = processes::tcp::TcpTracer::start();
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn set_up_sysinfo_lists(&mut self) {
+        // TODO: Would be good to get this and network list running on a timer instead...?
+        if self.widgets_to_harvest.use_temp && !self.has_components_list {
+            self.sys.refresh_components_list();
+            self.has_components_list = true;
+        }
+
+        if cfg!(target_os = "windows") && self.widgets_to_harvest.use_net && !self.has_networks_list
+        {
+            self.sys.refresh_networks_list();
+            self.has_networks_list = true;
+        }
     }
 
     #[cfg(target_os = "linux")]
@@ -184,10 +439,6 @@ impl DataCollector {
         self.widgets_to_harvest = used_widgets;
     }
 
-    pub fn set_temperature_type(&mut self, temperature_type: temperature::TemperatureType) {
-        self.temperature_type = temperature_type;
-    }
-
     pub fn set_use_current_cpu_total(&mut self, use_current_cpu_total: bool) {
         self.use_current_cpu_total = use_current_cpu_total;
     }
@@ -196,6 +447,61 @@ impl DataCollector {
         self.show_average_cpu = show_average_cpu;
     }
 
+    pub fn set_collect_gpu_processes(&mut self, collect_gpu_processes: bool) {
+        self.collect_gpu_processes = collect_gpu_processes;
+        if !collect_gpu_processes {
+            self.gpu_monitor = None;
+        }
+    }
+
+    pub fn set_resolve_hostnames(&mut self, resolve_hostnames: bool) {
+        self.resolve_hostnames = resolve_hostnames;
+        if !resolve_hostnames {
+            self.hostname_cache = None;
+        }
+    }
+
+    #[cfg(all(feature = "ebpf", target_os = "linux"))]
+    pub fn set_collect_tcp_processes(&mut self, collect_tcp_processes: bool) {
+        self.collect_tcp_processes = collect_tcp_processes;
+        if !collect_tcp_processes {
+            self.tcp_tracer = None;
+        }
+    }
+
+    pub fn set_collect_k8s_processes(&mut self, collect_k8s_processes: bool) {
+        self.collect_k8s_processes = collect_k8s_processes;
+    }
+
+    pub fn set_include_child_cpu_time(&mut self, include_child_cpu_time: bool) {
+        self.include_child_cpu_time = include_child_cpu_time;
+    }
+
+    pub fn set_collect_fd_counts(&mut self, collect_fd_counts: bool) {
+        self.collect_fd_counts = collect_fd_counts;
+    }
+
+    pub fn set_collect_process_status(&mut self, collect_process_status: bool) {
+        self.collect_process_status = collect_process_status;
+    }
+
+    pub fn set_battery_alert(
+        &mut self, battery_alert: Option<batteries::alert::BatteryAlertConfig>,
+    ) {
+        self.battery_alerter = battery_alert.and_then(batteries::alert::BatteryAlerter::new);
+    }
+
+    pub fn set_plugins(&mut self, plugins: Vec<plugins::PluginConfig>) {
+        self.plugin_runner = plugins::PluginRunner::new(plugins);
+    }
+
+    pub fn set_log_files(&mut self, log_files: Vec<(u64, PathBuf)>) {
+        self.log_tails = log_files
+            .into_iter()
+            .map(|(widget_id, path)| log_tail::LogTail::new(widget_id, path))
+            .collect();
+    }
+
     pub async fn update_data(&mut self) {
         #[cfg(not(target_os = "linux"))]
         {
@@ -225,6 +531,21 @@ impl DataCollector {
                 self.data.cpu = Some(cpu_data);
             }
 
+            #[cfg(target_os = "linux")]
+            {
+                // User, system, I/O wait, steal, and IRQ time
+                if let Ok(cpu_breakdown) =
+                    cpu::get_cpu_breakdown(&mut self.previous_cpu_state_times).await
+                {
+                    self.data.cpu_breakdown = cpu_breakdown;
+                }
+
+                // Throttling and core voltage
+                if let Ok(throttle) = cpu::get_throttle_data(&mut self.throttle_state) {
+                    self.data.throttle = throttle;
+                }
+            }
+
             #[cfg(target_family = "unix")]
             {
                 // Load Average
@@ -237,13 +558,21 @@ impl DataCollector {
         // Batteries
         if let Some(battery_manager) = &self.battery_manager {
             if let Some(battery_list) = &mut self.battery_list {
-                self.data.list_of_batteries =
-                    Some(batteries::refresh_batteries(&battery_manager, battery_list));
+                let battery_harvest = batteries::refresh_batteries(&battery_manager, battery_list);
+                let power_source = batteries::get_power_source(&battery_harvest);
+                if let Some(battery_alerter) = &mut self.battery_alerter {
+                    self.data
+                        .alerts
+                        .extend(battery_alerter.check(&battery_harvest, power_source));
+                }
+                self.data.list_of_batteries = Some(battery_harvest);
+                self.data.power_source = power_source;
             }
         }
 
         if self.widgets_to_harvest.use_proc {
-            if let Ok(process_list) = {
+            self.process_string_interner.prune();
+            if let Ok(mut process_list) = {
                 #[cfg(target_os = "linux")]
                 {
                     processes::get_process_data(
@@ -251,10 +580,13 @@ impl DataCollector {
                         &mut self.prev_non_idle,
                         &mut self.pid_mapping,
                         self.use_current_cpu_total,
+                        self.include_child_cpu_time,
                         current_instant
                             .duration_since(self.last_collection_time)
                             .as_secs(),
                         self.mem_total_kb,
+                        self.collect_process_status,
+                        &mut self.process_string_interner,
                     )
                 }
                 #[cfg(not(target_os = "linux"))]
@@ -263,13 +595,184 @@ impl DataCollector {
                         &self.sys,
                         self.use_current_cpu_total,
                         self.mem_total_kb,
+                        &mut self.process_string_interner,
                     )
                 }
             } {
+                #[cfg(target_family = "unix")]
+                {
+                    if let Some(user_filter) = &self.filters.user_filter {
+                        processes::filter_processes_by_user(
+                            &mut process_list,
+                            user_filter,
+                            &mut self.user_table,
+                        );
+                    }
+                }
+
+                if let Some(gpu_monitor) = &self.gpu_monitor {
+                    gpu_monitor.fill_process_usage(&mut process_list);
+                }
+
+                if self.collect_fd_counts {
+                    self.fd_counter.fill_process_fd_counts(&mut process_list);
+                }
+
+                #[cfg(all(feature = "ebpf", target_os = "linux"))]
+                {
+                    if let Some(tcp_tracer) = &self.tcp_tracer {
+                        tcp_tracer.fill_process_stats(&mut process_list);
+                    }
+                }
+
+                #[cfg(target_os = "linux")]
+                {
+                    if self.collect_k8s_processes {
+                        processes::kubernetes::fill_process_pods(&mut process_list);
+                    }
+                }
+
                 self.data.list_of_processes = Some(process_list);
             }
         }
 
+        #[cfg(target_os = "linux")]
+        {
+            // Services
+            if let Ok(services) = services::get_service_data(
+                &mut self.service_units,
+                current_instant
+                    .duration_since(self.last_collection_time)
+                    .as_secs_f64(),
+                self.widgets_to_harvest.use_services,
+            ) {
+                self.data.services = services;
+            }
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            // Services
+            if let Ok(services) = services::get_service_data(self.widgets_to_harvest.use_services) {
+                self.data.services = services;
+            }
+        }
+
+        // Listening ports
+        if let Ok(ports) =
+            ports::get_port_data(self.widgets_to_harvest.use_ports, &mut self.port_owners)
+        {
+            self.data.ports = ports;
+        }
+
+        // Established connections
+        if let Ok(connections) = ports::get_connection_data(
+            self.widgets_to_harvest.use_connections,
+            &mut self.connection_owners,
+            self.hostname_cache.as_mut(),
+        ) {
+            self.data.connections = connections;
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            // Control groups
+            if let Ok(cgroups) = cgroups::get_cgroup_data(
+                &mut self.prev_cgroup_stats,
+                current_instant
+                    .duration_since(self.last_collection_time)
+                    .as_secs_f64(),
+                self.widgets_to_harvest.use_cgroups,
+            ) {
+                self.data.cgroups = cgroups;
+            }
+
+            // Context switches and interrupts
+            if let Ok(sched) = sched::get_sched_data(
+                &mut self.prev_sched_counts,
+                current_instant
+                    .duration_since(self.last_collection_time)
+                    .as_secs_f64(),
+                self.widgets_to_harvest.use_sched,
+            ) {
+                self.data.sched = sched;
+            }
+
+            // Power consumption
+            if let Ok(power) = power::get_power_data(
+                &mut self.prev_energy_counts,
+                current_instant
+                    .duration_since(self.last_collection_time)
+                    .as_secs_f64(),
+                self.widgets_to_harvest.use_power,
+            ) {
+                self.data.power = power;
+            }
+
+            // Virtual machines
+            if let Ok(vms) =
+                libvirt::get_vm_data(&mut self.prev_vm_stats, self.widgets_to_harvest.use_vms)
+            {
+                self.data.vms = vms;
+            }
+        }
+
+        #[cfg(unix)]
+        {
+            // Docker containers
+            if let Ok(containers) = docker::get_docker_data(
+                &mut self.prev_container_stats,
+                current_instant
+                    .duration_since(self.last_collection_time)
+                    .as_secs_f64(),
+                self.widgets_to_harvest.use_docker,
+            ) {
+                self.data.containers = containers;
+            }
+        }
+
+        // Kernel limits
+        if let Ok(kernel_limits) =
+            kernel_limits::get_kernel_limits_data(self.widgets_to_harvest.use_kernel_limits)
+        {
+            self.data.kernel_limits = kernel_limits;
+        }
+
+        // Pressure stall information
+        if let Ok(pressure) = pressure::get_pressure_data(self.widgets_to_harvest.use_pressure) {
+            self.data.pressure = pressure;
+        }
+
+        // NUMA nodes
+        if let Ok(numa) = memory::get_numa_data(self.widgets_to_harvest.use_mem) {
+            self.data.numa = numa;
+        }
+
+        // Shared memory and tmpfs
+        if let Ok(shmem) = memory::get_shmem_data(self.widgets_to_harvest.use_mem) {
+            self.data.shmem = shmem;
+        }
+
+        // Huge pages
+        if let Ok(hugepages) = memory::get_hugepages_data(self.widgets_to_harvest.use_mem) {
+            self.data.hugepages = hugepages;
+        }
+
+        // Wireless
+        if let Ok(wireless) =
+            wireless::get_wireless_data(self.widgets_to_harvest.use_net, &mut self.wireless_links)
+        {
+            self.data.wireless = wireless;
+        }
+
+        // Log files
+        self.data.log_lines = log_tail::get_log_data(&mut self.log_tails);
+
+        // Plugins
+        self.data.plugins = self
+            .plugin_runner
+            .get_plugin_data(self.widgets_to_harvest.use_custom);
+
         let network_data_fut = {
             #[cfg(target_os = "windows")]
             {
@@ -300,6 +803,7 @@ impl DataCollector {
             self.widgets_to_harvest.use_disk,
             &self.filters.disk_filter,
             &self.filters.mount_filter,
+            &self.filters.fs_filter,
         );
         let disk_io_usage_fut = disks::get_io_usage(self.widgets_to_harvest.use_disk);
         let temp_data_fut = {
@@ -307,18 +811,18 @@ impl DataCollector {
             {
                 temperature::get_temperature_data(
                     &self.sys,
-                    &self.temperature_type,
                     self.widgets_to_harvest.use_temp,
                     &self.filters.temp_filter,
+                    &self.filters.temp_aliases,
                 )
             }
 
             #[cfg(target_os = "linux")]
             {
                 temperature::get_temperature_data(
-                    &self.temperature_type,
                     self.widgets_to_harvest.use_temp,
                     &self.filters.temp_filter,
+                    &self.filters.temp_aliases,
                 )
             }
         };
@@ -331,10 +835,30 @@ impl DataCollector {
             temp_data_fut
         );
 
-        if let Ok(net_data) = net_data {
-            if let Some(net_data) = &net_data {
+        if let Ok(mut net_data) = net_data {
+            if let Some(net_data) = &mut net_data {
                 self.total_rx = net_data.total_rx;
                 self.total_tx = net_data.total_tx;
+
+                let elapsed_secs = current_instant
+                    .duration_since(self.last_collection_time)
+                    .as_secs_f64();
+                let (ipv4, ipv6) =
+                    network::get_ip_traffic(self.prev_ipv4, self.prev_ipv6, elapsed_secs);
+                self.prev_ipv4 = ipv4;
+                self.prev_ipv6 = ipv6;
+                net_data.ipv4 = ipv4;
+                net_data.ipv6 = ipv6;
+
+                net_data.links = network::get_interface_links(
+                    net_data
+                        .interface_counters
+                        .iter()
+                        .map(|counters| counters.name.as_str()),
+                    &self.prev_links,
+                    elapsed_secs,
+                );
+                self.prev_links = net_data.links.clone();
             }
             self.data.network = net_data;
         }
@@ -359,6 +883,56 @@ impl DataCollector {
             self.data.temperature_sensors = temp;
         }
 
+        #[cfg(target_os = "linux")]
+        {
+            // This is only set if temperatures are being harvested at all.
+            if let Some(temperature_sensors) = &mut self.data.temperature_sensors {
+                temperature::add_disk_temperature_data(
+                    temperature_sensors,
+                    &self.filters.temp_filter,
+                    &self.filters.temp_aliases,
+                    &mut self.smartctl_cache,
+                );
+
+                #[cfg(feature = "libsensors")]
+                {
+                    if let Some(libsensors) = &self.libsensors {
+                        temperature::add_libsensors_data(
+                            temperature_sensors,
+                            libsensors,
+                            &self.filters.temp_filter,
+                            &self.filters.temp_aliases,
+                        );
+                    }
+                }
+            }
+        }
+
+        // Data that couldn't be read without more permissions
+        self.data.restricted = Some(permissions::RestrictedHarvest {
+            process_io_count: self
+                .data
+                .list_of_processes
+                .as_ref()
+                .map(|processes| {
+                    processes
+                        .iter()
+                        .filter(|process| process.is_io_restricted)
+                        .count()
+                })
+                .unwrap_or(0),
+            #[cfg(target_os = "linux")]
+            is_smart_restricted: self.data.temperature_sensors.is_some()
+                && self.smartctl_cache.is_restricted(),
+            #[cfg(not(target_os = "linux"))]
+            is_smart_restricted: false,
+            #[cfg(target_os = "linux")]
+            is_power_restricted: self.widgets_to_harvest.use_power
+                && self.prev_energy_counts.is_restricted,
+            #[cfg(not(target_os = "linux"))]
+            is_power_restricted: false,
+        });
+
         // Update time
         self.data.last_collection_time = current_instant;
         self.last_collection_time = current_instant;
@@ -0,0 +1,284 @@
+//! Alerts for when a battery is running low, or when switching between AC and battery power.
+//!
+//! Each low battery alert fires once per discharge; it's only re-armed once the battery stops
+//! discharging, so a fluctuating time estimate or a charge hovering around the threshold won't
+//! keep firing.
+//!
+//! Alerts can also be sent to a webhook or by email, and to the terminal bottom is running in.  Since only the main thread can
+//! safely write to the terminal, the messages are passed back for it to send.
+
+use std::{
+    process::{Command, Stdio},
+    thread,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{BatteryHarvest, PowerSource};
+use crate::app::notifications::{send_notifications, EmailConfig, WebhookConfig};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BatteryAlertConfig {
+    /// Alert when a discharging battery's charge drops below this percentage.
+    pub charge_below: Option<f64>,
+    /// Alert when a discharging battery's estimated time remaining drops below this many
+    /// minutes.
+    pub minutes_below: Option<u64>,
+    /// Alert when the AC adapter is plugged in or unplugged.
+    #[serde(default)]
+    pub on_power_change: bool,
+    /// A command to run instead of showing a desktop notification.
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Ways to get the terminal's attention, on top of the notification or command.
+    #[serde(default)]
+    pub terminal: Vec<TerminalAlert>,
+    /// Also post alerts to a webhook.
+    pub webhook: Option<WebhookConfig>,
+    /// Also email alerts.
+    pub email: Option<EmailConfig>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TerminalAlert {
+    /// Rings the terminal bell.
+    Bell,
+    /// Sends an OSC 9 notification, which some terminals show as a desktop notification.
+    Osc9,
+    /// Sets the terminal's title to the alert.
+    Title,
+}
+
+impl TerminalAlert {
+    /// Returns the escape sequence that sends the alert.
+    pub fn get_escape_sequence(self, message: &str) -> String {
+        match self {
+            TerminalAlert::Bell => "\x07".to_string(),
+            TerminalAlert::Osc9 => format!("\x1b]9;{}\x07", message),
+            TerminalAlert::Title => format!("\x1b]2;bottom: {}\x07", message),
+        }
+    }
+}
+
+/// Which alerts have already fired for a battery during its current discharge.
+#[derive(Clone, Copy, Debug, Default)]
+struct AlertState {
+    has_charge_alerted: bool,
+    has_time_alerted: bool,
+}
+
+#[derive(Debug)]
+pub struct BatteryAlerter {
+    config: BatteryAlertConfig,
+    states: Vec<AlertState>,
+    power_source: Option<PowerSource>,
+}
+
+impl BatteryAlerter {
+    /// Returns [`None`] if the config doesn't set any thresholds or ask for power alerts.
+    pub fn new(config: BatteryAlertConfig) -> Option<Self> {
+        if config.charge_below.is_none()
+            && config.minutes_below.is_none()
+            && !config.on_power_change
+        {
+            None
+        } else {
+            Some(BatteryAlerter {
+                config,
+                states: Vec::new(),
+                power_source: None,
+            })
+        }
+    }
+
+    /// Checks the latest battery data against the thresholds, and alerts for any battery that
+    /// just crossed one, as well as for the power source changing if asked to.  Returns the
+    /// messages of the alerts that were sent.
+    pub fn check(
+        &mut self, batteries: &[BatteryHarvest], power_source: Option<PowerSource>,
+    ) -> Vec<String> {
+        let mut messages = Vec::new();
+        if self.config.on_power_change {
+            if let (Some(prev_power_source), Some(power_source)) = (self.power_source, power_source)
+            {
+                if prev_power_source != power_source {
+                    messages.push(send_power_alert(&self.config, power_source));
+                }
+            }
+        }
+        if power_source.is_some() {
+            self.power_source = power_source;
+        }
+
+        if self.states.len() != batteries.len() {
+            self.states.resize(batteries.len(), AlertState::default());
+        }
+
+        for (index, (battery, state)) in batteries.iter().zip(self.states.iter_mut()).enumerate() {
+            if !battery.is_discharging {
+                *state = AlertState::default();
+                continue;
+            }
+
+            let minutes_until_empty = battery.secs_until_empty.map(|secs| secs.max(0) as u64 / 60);
+
+            let is_charge_low = self
+                .config
+                .charge_below
+                .map(|charge_below| battery.charge_percent < charge_below)
+                .unwrap_or(false);
+            let is_time_low = match (self.config.minutes_below, minutes_until_empty) {
+                (Some(minutes_below), Some(minutes_until_empty)) => {
+                    minutes_until_empty < minutes_below
+                }
+                _ => false,
+            };
+
+            // If both cross at the same time, one alert is enough.
+            if (is_charge_low && !state.has_charge_alerted)
+                || (is_time_low && !state.has_time_alerted)
+            {
+                messages.push(send_alert(
+                    &self.config,
+                    index,
+                    battery,
+                    minutes_until_empty,
+                ));
+            }
+
+            state.has_charge_alerted |= is_charge_low;
+            state.has_time_alerted |= is_time_low;
+        }
+
+        messages
+    }
+}
+
+/// Sends an alert, and returns its message.
+fn send_alert(
+    config: &BatteryAlertConfig, index: usize, battery: &BatteryHarvest,
+    minutes_until_empty: Option<u64>,
+) -> String {
+    let message = match minutes_until_empty {
+        Some(minutes) => format!(
+            "Battery {} is at {:.0}% ({}h {}m remaining)",
+            index,
+            battery.charge_percent,
+            minutes / 60,
+            minutes % 60
+        ),
+        None => format!("Battery {} is at {:.0}%", index, battery.charge_percent),
+    };
+
+    run_alert(
+        config,
+        &message,
+        &[
+            ("BTM_BATTERY_INDEX", index.to_string()),
+            (
+                "BTM_BATTERY_CHARGE",
+                format!("{:.0}", battery.charge_percent),
+            ),
+            (
+                "BTM_BATTERY_MINUTES_LEFT",
+                minutes_until_empty
+                    .map(|minutes| minutes.to_string())
+                    .unwrap_or_default(),
+            ),
+        ],
+    );
+
+    message
+}
+
+/// Sends an alert that the power source changed, and returns its message.
+fn send_power_alert(config: &BatteryAlertConfig, power_source: PowerSource) -> String {
+    let (message, power_source_name) = match power_source {
+        PowerSource::Ac => ("Switched to AC power", "ac"),
+        PowerSource::Battery => ("Switched to battery power", "battery"),
+    };
+    run_alert(
+        config,
+        message,
+        &[("BTM_POWER_SOURCE", power_source_name.to_string())],
+    );
+
+    message.to_string()
+}
+
+/// Runs the alert's command with the given environment variables, or shows a desktop
+/// notification if there's no command.  It's also sent to the webhook and by email, if set.
+fn run_alert(config: &BatteryAlertConfig, message: &str, envs: &[(&str, String)]) {
+    let mut command = if let Some(alert_command) = &config.command {
+        let mut command = Command::new(alert_command);
+        command
+            .args(&config.args)
+            .envs(envs.iter().map(|(key, value)| (*key, value)))
+            .env("BTM_ALERT_MESSAGE", message);
+        command
+    } else {
+        get_notification_command(message)
+    };
+
+    // Don't block harvesting on the alert, but still reap it once it's done.
+    if let Ok(mut child) = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        thread::spawn(move || child.wait());
+    }
+
+    let details = envs
+        .iter()
+        .map(|(key, value)| (key.trim_start_matches("BTM_").to_lowercase(), value.clone()))
+        .collect::<Vec<_>>();
+    send_notifications(
+        config.webhook.as_ref(),
+        config.email.as_ref(),
+        message,
+        &details,
+    );
+}
+
+/// Returns a command that shows a desktop notification.  The message is generated by bottom, so
+/// it doesn't need any escaping.
+#[cfg(target_os = "macos")]
+fn get_notification_command(message: &str) -> Command {
+    let mut command = Command::new("osascript");
+    command.args(&[
+        "-e",
+        &format!("display notification \"{}\" with title \"bottom\"", message),
+    ]);
+    command
+}
+
+#[cfg(target_os = "windows")]
+fn get_notification_command(message: &str) -> Command {
+    let mut command = Command::new("powershell");
+    command.args(&[
+        "-NoProfile",
+        "-Command",
+        &format!(
+            "Add-Type -AssemblyName System.Windows.Forms; \
+            $notification = New-Object System.Windows.Forms.NotifyIcon; \
+            $notification.Icon = [System.Drawing.SystemIcons]::Warning; \
+            $notification.Visible = $true; \
+            $notification.ShowBalloonTip(10000, 'bottom', '{}', 'Warning'); \
+            Start-Sleep -Seconds 10; \
+            $notification.Dispose()",
+            message
+        ),
+    ]);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn get_notification_command(message: &str) -> Command {
+    let mut command = Command::new("notify-send");
+    command.args(&["--urgency=critical", "bottom", message]);
+    command
+}
@@ -11,7 +11,7 @@
 
 use battery::{
     units::{power::watt, ratio::percent, time::second},
-    Battery, Manager,
+    Battery, Manager, State,
 };
 
 #[derive(Debug, Clone)]
@@ -21,6 +21,67 @@ pub struct BatteryHarvest {
     pub secs_until_empty: Option<i64>,
     pub power_consumption_rate_watts: f64,
     pub health_percent: f64,
+    pub is_discharging: bool,
+}
+
+/// Where the system is drawing its power from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerSource {
+    Ac,
+    Battery,
+}
+
+impl PowerSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PowerSource::Ac => "AC adapter",
+            PowerSource::Battery => "Battery",
+        }
+    }
+}
+
+/// Returns where the system is drawing its power from.  On Linux, this is read from the AC
+/// adapter itself.  Elsewhere, or if no adapter is listed, it's on battery if any battery is
+/// discharging.
+pub fn get_power_source(batteries: &[BatteryHarvest]) -> Option<PowerSource> {
+    if let Some(is_ac_online) = is_ac_adapter_online() {
+        Some(if is_ac_online {
+            PowerSource::Ac
+        } else {
+            PowerSource::Battery
+        })
+    } else if batteries.is_empty() {
+        None
+    } else if batteries.iter().any(|battery| battery.is_discharging) {
+        Some(PowerSource::Battery)
+    } else {
+        Some(PowerSource::Ac)
+    }
+}
+
+/// Returns whether any AC adapter is plugged in, or `None` if there are no adapters listed.
+#[cfg(target_os = "linux")]
+fn is_ac_adapter_online() -> Option<bool> {
+    let mut is_ac_online = None;
+    for power_supply in std::fs::read_dir("/sys/class/power_supply").ok()?.flatten() {
+        let path = power_supply.path();
+        let is_ac_adapter = std::fs::read_to_string(path.join("type"))
+            .map(|power_supply_type| power_supply_type.trim() == "Mains")
+            .unwrap_or(false);
+        if is_ac_adapter {
+            let is_online = std::fs::read_to_string(path.join("online"))
+                .map(|online| online.trim() == "1")
+                .unwrap_or(false);
+            is_ac_online = Some(is_ac_online.unwrap_or(false) || is_online);
+        }
+    }
+
+    is_ac_online
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_ac_adapter_online() -> Option<bool> {
+    None
 }
 
 pub fn refresh_batteries(manager: &Manager, batteries: &mut [Battery]) -> Vec<BatteryHarvest> {
@@ -40,6 +101,7 @@ pub fn refresh_batteries(manager: &Manager, batteries: &mut [Battery]) -> Vec<Ba
                     charge_percent: f64::from(battery.state_of_charge().get::<percent>()),
                     power_consumption_rate_watts: f64::from(battery.energy_rate().get::<watt>()),
                     health_percent: f64::from(battery.state_of_health().get::<percent>()),
+                    is_discharging: battery.state() == State::Discharging,
                 })
             } else {
                 None
@@ -0,0 +1,170 @@
+//! Control groups on Linux.
+
+use std::path::Path;
+
+use fxhash::FxHashMap;
+
+use super::CgroupHarvest;
+use crate::utils::error::{self, BottomError};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// How many levels below the top-level slices and scopes to show.
+const MAX_DEPTH: usize = 1;
+
+/// The last seen CPU time in microseconds, and bytes read and written, of each cgroup by path.
+pub type PrevCgroupStats = FxHashMap<String, (Option<u64>, Option<(u64, u64)>)>;
+
+/// Reads a single integer value from a cgroup file.  Limits of `max`, meaning there is no limit,
+/// are read as `None`.
+fn read_cgroup_value(path: &Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Returns the total CPU time of a cgroup in microseconds, from its `cpu.stat`.
+fn read_cpu_usage(path: &Path) -> Option<u64> {
+    std::fs::read_to_string(path.join("cpu.stat"))
+        .ok()?
+        .lines()
+        .find_map(|line| {
+            let mut split = line.split_whitespace();
+            match (split.next(), split.next()) {
+                (Some("usage_usec"), Some(value)) => value.parse().ok(),
+                _ => None,
+            }
+        })
+}
+
+/// Returns the total bytes read and written by a cgroup across all devices, from its `io.stat`.
+/// Each line is a device followed by `key=value` pairs.
+fn read_io_usage(path: &Path) -> Option<(u64, u64)> {
+    let io_stat = std::fs::read_to_string(path.join("io.stat")).ok()?;
+    let mut read_bytes = 0;
+    let mut write_bytes = 0;
+
+    for entry in io_stat.split_whitespace() {
+        let mut split = entry.splitn(2, '=');
+        match (
+            split.next(),
+            split.next().and_then(|value| value.parse::<u64>().ok()),
+        ) {
+            (Some("rbytes"), Some(value)) => read_bytes += value,
+            (Some("wbytes"), Some(value)) => write_bytes += value,
+            _ => {}
+        }
+    }
+
+    Some((read_bytes, write_bytes))
+}
+
+/// Returns the slices and scopes directly in a cgroup, sorted by name.
+fn get_child_cgroups(path: &Path) -> Vec<String> {
+    let mut children: Vec<String> = std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| name.ends_with(".slice") || name.ends_with(".scope"))
+                .collect()
+        })
+        .unwrap_or_default();
+    children.sort();
+
+    children
+}
+
+/// Reads a cgroup and, up to [`MAX_DEPTH`], its children, appending them in tree order.
+fn walk_cgroup(
+    relative_path: &str, depth: usize, prev_stats: &PrevCgroupStats,
+    new_stats: &mut PrevCgroupStats, time_difference_in_secs: f64,
+    cgroup_vec: &mut Vec<CgroupHarvest>,
+) {
+    let path = Path::new(CGROUP_ROOT).join(relative_path);
+    let cpu_time = read_cpu_usage(&path);
+    let io_bytes = read_io_usage(&path);
+    let (prev_cpu_time, prev_io_bytes) = match prev_stats.get(relative_path) {
+        Some(prev) if time_difference_in_secs > 0.0 => *prev,
+        _ => (None, None),
+    };
+    new_stats.insert(relative_path.to_string(), (cpu_time, io_bytes));
+
+    let cpu_usage_percent = match (cpu_time, prev_cpu_time) {
+        (Some(cpu_time), Some(prev_cpu_time)) => Some(
+            cpu_time.saturating_sub(prev_cpu_time) as f64 / (time_difference_in_secs * 1_000_000.0)
+                * 100.0,
+        ),
+        _ => None,
+    };
+    let (read_bytes_per_sec, write_bytes_per_sec) = match (io_bytes, prev_io_bytes) {
+        (Some((read_bytes, write_bytes)), Some((prev_read_bytes, prev_write_bytes))) => (
+            Some(
+                (read_bytes.saturating_sub(prev_read_bytes) as f64 / time_difference_in_secs)
+                    as u64,
+            ),
+            Some(
+                (write_bytes.saturating_sub(prev_write_bytes) as f64 / time_difference_in_secs)
+                    as u64,
+            ),
+        ),
+        _ => (None, None),
+    };
+
+    cgroup_vec.push(CgroupHarvest {
+        name: relative_path
+            .rsplit('/')
+            .next()
+            .unwrap_or(relative_path)
+            .to_string(),
+        depth,
+        cpu_usage_percent,
+        mem_usage_bytes: read_cgroup_value(&path.join("memory.current")),
+        mem_limit_bytes: read_cgroup_value(&path.join("memory.max")),
+        read_bytes_per_sec,
+        write_bytes_per_sec,
+    });
+
+    if depth < MAX_DEPTH {
+        for child in get_child_cgroups(&path) {
+            walk_cgroup(
+                &format!("{}/{}", relative_path, child),
+                depth + 1,
+                prev_stats,
+                new_stats,
+                time_difference_in_secs,
+                cgroup_vec,
+            );
+        }
+    }
+}
+
+pub fn get_cgroup_data(
+    prev_stats: &mut PrevCgroupStats, time_difference_in_secs: f64, actually_get: bool,
+) -> error::Result<Option<Vec<CgroupHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let root = Path::new(CGROUP_ROOT);
+    if !root.join("cgroup.controllers").exists() {
+        return Err(BottomError::GenericError(
+            "the unified cgroup v2 hierarchy is not mounted".to_string(),
+        ));
+    }
+
+    let mut new_stats = PrevCgroupStats::default();
+    let mut cgroup_vec = Vec::new();
+    for top_level in get_child_cgroups(root) {
+        walk_cgroup(
+            &top_level,
+            0,
+            prev_stats,
+            &mut new_stats,
+            time_difference_in_secs,
+            &mut cgroup_vec,
+        );
+    }
+    *prev_stats = new_stats;
+
+    Ok(Some(cgroup_vec))
+}
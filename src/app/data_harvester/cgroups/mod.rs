@@ -0,0 +1,25 @@
+//! Data collection for control groups.
+//!
+//! For Linux, this is read from the unified (v2) cgroup hierarchy in `/sys/fs/cgroup`.  The
+//! legacy (v1) hierarchies are not supported.
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CgroupHarvest {
+    pub name: String,
+    /// How far down the hierarchy the cgroup is; top-level slices and scopes are 0.
+    pub depth: usize,
+    /// How much of one CPU the cgroup used since the last read.
+    pub cpu_usage_percent: Option<f64>,
+    pub mem_usage_bytes: Option<u64>,
+    /// The cgroup's hard memory limit, if it has one.
+    pub mem_limit_bytes: Option<u64>,
+    pub read_bytes_per_sec: Option<u64>,
+    pub write_bytes_per_sec: Option<u64>,
+}
@@ -0,0 +1,69 @@
+//! Running external commands from the collection thread, which shouldn't get stuck if one hangs.
+
+use std::{
+    io::Read,
+    process::{Command, Output, Stdio},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How often to check whether a command has finished.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Runs a command and returns its output, or `None` if it couldn't be started or didn't finish
+/// within `timeout`, in which case it's killed.  Its output is read on other threads as it runs,
+/// so a command that writes more than a pipe can hold doesn't block until it's killed.
+///
+/// Anything the command started that's still holding its output open is also given up on at the
+/// timeout.  The reader threads are left to finish whenever that lets go of the pipes.
+pub fn output_with_timeout(command: &mut Command, timeout: Duration) -> Option<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+    let stdout_reader = read_on_thread(child.stdout.take());
+    let stderr_reader = read_on_thread(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(POLL_INTERVAL),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+
+    let stdout = stdout_reader
+        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        .ok()?;
+    let stderr = stderr_reader
+        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        .ok()?;
+
+    Some(Output {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
+/// Reads all of a pipe on its own thread, and sends it back once it's closed.
+fn read_on_thread<R: Read + Send + 'static>(pipe: Option<R>) -> Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut output);
+        }
+        let _ = sender.send(output);
+    });
+
+    receiver
+}
@@ -1,6 +1,70 @@
 //! Linux-specific functions regarding CPU usage.
 
 use heim::cpu::os::linux::CpuTimeExt;
+
+use crate::app::data_harvester::cpu::CpuBreakdown;
+
+/// The time spent across all CPUs in each state, in seconds.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CpuStateTimes {
+    user: f64,
+    system: f64,
+    io_wait: f64,
+    steal: f64,
+    irq: f64,
+    total: f64,
+}
+
+impl From<&heim::cpu::CpuTime> for CpuStateTimes {
+    fn from(cpu_time: &heim::cpu::CpuTime) -> Self {
+        let user = (cpu_time.user() + cpu_time.nice()).get::<heim::units::time::second>();
+        let system = cpu_time.system().get::<heim::units::time::second>();
+        let io_wait = cpu_time.io_wait().get::<heim::units::time::second>();
+        let steal = cpu_time.steal().get::<heim::units::time::second>();
+        let irq = (cpu_time.irq() + cpu_time.soft_irq()).get::<heim::units::time::second>();
+        let idle = cpu_time.idle().get::<heim::units::time::second>();
+
+        CpuStateTimes {
+            user,
+            system,
+            io_wait,
+            steal,
+            irq,
+            total: user + system + io_wait + steal + irq + idle,
+        }
+    }
+}
+
+/// Returns how the time across all CPUs was spent since the last call.  The first call only
+/// records the current times, so there is nothing to return yet.
+pub async fn get_cpu_breakdown(
+    previous_cpu_state_times: &mut Option<CpuStateTimes>,
+) -> crate::error::Result<Option<CpuBreakdown>> {
+    let current = CpuStateTimes::from(&heim::cpu::time().await?);
+
+    let cpu_breakdown = previous_cpu_state_times.map(|previous| {
+        let total_delta = current.total - previous.total;
+        let percentage = |current_time: f64, previous_time: f64| {
+            if total_delta > 0.0 && current_time > previous_time {
+                (current_time - previous_time) * 100.0 / total_delta
+            } else {
+                0.0
+            }
+        };
+
+        CpuBreakdown {
+            user_percent: percentage(current.user, previous.user),
+            system_percent: percentage(current.system, previous.system),
+            io_wait_percent: percentage(current.io_wait, previous.io_wait),
+            steal_percent: percentage(current.steal, previous.steal),
+            irq_percent: percentage(current.irq, previous.irq),
+        }
+    });
+
+    *previous_cpu_state_times = Some(current);
+    Ok(cpu_breakdown)
+}
+
 pub fn convert_cpu_times(cpu_time: &heim::cpu::CpuTime) -> (f64, f64) {
     let working_time: f64 = (cpu_time.user()
         + cpu_time.nice()
@@ -14,3 +78,44 @@ pub fn convert_cpu_times(cpu_time: &heim::cpu::CpuTime) -> (f64, f64) {
         working_time + (cpu_time.idle() + cpu_time.io_wait()).get::<heim::units::time::second>(),
     )
 }
+
+/// Returns the number of each CPU listed in `/proc/stat`, in the order heim reads their times.
+/// Offline CPUs aren't listed.
+pub fn get_online_cpu_numbers() -> Option<Vec<usize>> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    Some(
+        stat.lines()
+            .filter_map(|line| {
+                line.split_whitespace()
+                    .next()?
+                    .strip_prefix("cpu")?
+                    .parse()
+                    .ok()
+            })
+            .collect(),
+    )
+}
+
+/// Returns the numbers of the CPUs that are present, whether they're online or not.
+pub fn get_present_cpu_numbers() -> Vec<usize> {
+    std::fs::read_to_string("/sys/devices/system/cpu/present")
+        .map(|present| parse_cpu_list(present.trim()))
+        .unwrap_or_default()
+}
+
+/// Parses a list of CPUs in the kernel's format, like `0-3,6,8-9`.
+fn parse_cpu_list(cpu_list: &str) -> Vec<usize> {
+    cpu_list
+        .split(',')
+        .filter_map(|range| {
+            let mut bounds = range.splitn(2, '-');
+            let start = bounds.next()?.parse::<usize>().ok()?;
+            let end = match bounds.next() {
+                Some(end) => end.parse::<usize>().ok()?,
+                None => start,
+            };
+            Some(start..=end)
+        })
+        .flatten()
+        .collect()
+}
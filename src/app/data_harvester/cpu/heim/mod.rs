@@ -23,6 +23,9 @@ pub struct CpuData {
     pub cpu_prefix: String,
     pub cpu_count: Option<usize>,
     pub cpu_usage: f64,
+    /// Whether the CPU has been taken offline, such as by core parking or hot-unplugging.  Its
+    /// usage is always 0 then.  Only detected on Linux.
+    pub is_offline: bool,
 }
 
 pub type CpuHarvest = Vec<CpuData>;
@@ -31,10 +34,30 @@ pub type PastCpuWork = f64;
 pub type PastCpuTotal = f64;
 
 use futures::StreamExt;
+use fxhash::{FxHashMap, FxHashSet};
 use std::collections::VecDeque;
 
+/// Returns the times of each CPU, by its number.  A CPU whose times couldn't be read has none.
+async fn get_cpu_times() -> crate::error::Result<Vec<(usize, Option<(PastCpuWork, PastCpuTotal)>)>>
+{
+    let cpu_times = heim::cpu::times()
+        .await?
+        .map(|cpu_time| cpu_time.ok().map(|cpu_time| convert_cpu_times(&cpu_time)))
+        .collect::<Vec<_>>()
+        .await;
+
+    // Offline CPUs aren't listed, so when some are, a CPU's position isn't its number.  If the
+    // numbers can't be matched up with the times, fall back to the positions.
+    let cpu_numbers = match get_online_cpu_numbers() {
+        Some(cpu_numbers) if cpu_numbers.len() == cpu_times.len() => cpu_numbers,
+        _ => (0..cpu_times.len()).collect(),
+    };
+
+    Ok(cpu_numbers.into_iter().zip(cpu_times).collect())
+}
+
 pub async fn get_cpu_data_list(
-    show_average_cpu: bool, previous_cpu_times: &mut Vec<(PastCpuWork, PastCpuTotal)>,
+    show_average_cpu: bool, previous_cpu_times: &mut FxHashMap<usize, (PastCpuWork, PastCpuTotal)>,
     previous_average_cpu_time: &mut Option<(PastCpuWork, PastCpuTotal)>,
 ) -> crate::error::Result<CpuHarvest> {
     fn calculate_cpu_usage_percentage(
@@ -53,84 +76,62 @@ pub async fn get_cpu_data_list(
             })
     }
 
-    // Get all CPU times...
-    let cpu_times = heim::cpu::times().await?;
-    futures::pin_mut!(cpu_times);
-
-    let mut cpu_deque: VecDeque<CpuData> = if previous_cpu_times.is_empty() {
+    if previous_cpu_times.is_empty() {
         // Must initialize ourselves.  Use a very quick timeout to calculate an initial.
+        *previous_cpu_times = get_cpu_times()
+            .await?
+            .into_iter()
+            .filter_map(|(cpu_number, cpu_time)| Some((cpu_number, cpu_time?)))
+            .collect();
         futures_timer::Delay::new(std::time::Duration::from_millis(100)).await;
+    }
 
-        let second_cpu_times = heim::cpu::times().await?;
-        futures::pin_mut!(second_cpu_times);
-
-        let mut new_cpu_times: Vec<(PastCpuWork, PastCpuTotal)> = Vec::new();
-        let mut cpu_deque: VecDeque<CpuData> = VecDeque::new();
-        let mut collected_zip = cpu_times.zip(second_cpu_times).enumerate(); // Gotta move it here, can't on while line.
-
-        while let Some((itx, (past, present))) = collected_zip.next().await {
-            if let (Ok(past), Ok(present)) = (past, present) {
-                let present_times = convert_cpu_times(&present);
-                new_cpu_times.push(present_times);
-                cpu_deque.push_back(CpuData {
-                    cpu_prefix: "CPU".to_string(),
-                    cpu_count: Some(itx),
-                    cpu_usage: calculate_cpu_usage_percentage(
-                        convert_cpu_times(&past),
-                        present_times,
-                    ),
-                });
-            } else {
-                new_cpu_times.push((0.0, 0.0));
-                cpu_deque.push_back(CpuData {
-                    cpu_prefix: "CPU".to_string(),
-                    cpu_count: Some(itx),
-                    cpu_usage: 0.0,
-                });
+    // CPUs can come and go at runtime, so they're matched up with their previous times by number.
+    // One that just came online has nothing to compare against yet, so it starts at 0.
+    let mut new_cpu_times = FxHashMap::default();
+    let mut cpu_deque: VecDeque<CpuData> = get_cpu_times()
+        .await?
+        .into_iter()
+        .map(|(cpu_number, cpu_time)| {
+            let previous_cpu_time = previous_cpu_times.get(&cpu_number).copied();
+            let cpu_usage = match (previous_cpu_time, cpu_time) {
+                (Some(previous_cpu_time), Some(cpu_time)) => {
+                    calculate_cpu_usage_percentage(previous_cpu_time, cpu_time)
+                }
+                _ => 0.0,
+            };
+            if let Some(cpu_time) = cpu_time.or(previous_cpu_time) {
+                new_cpu_times.insert(cpu_number, cpu_time);
             }
-        }
 
-        *previous_cpu_times = new_cpu_times;
-        cpu_deque
-    } else {
-        let (new_cpu_times, cpu_deque): (Vec<(PastCpuWork, PastCpuTotal)>, VecDeque<CpuData>) =
-            cpu_times
-                .collect::<Vec<_>>()
-                .await
-                .iter()
-                .zip(&*previous_cpu_times)
-                .enumerate()
-                .map(|(itx, (current_cpu, (past_cpu_work, past_cpu_total)))| {
-                    if let Ok(cpu_time) = current_cpu {
-                        let present_times = convert_cpu_times(&cpu_time);
-
-                        (
-                            present_times,
-                            CpuData {
-                                cpu_prefix: "CPU".to_string(),
-                                cpu_count: Some(itx),
-                                cpu_usage: calculate_cpu_usage_percentage(
-                                    (*past_cpu_work, *past_cpu_total),
-                                    present_times,
-                                ),
-                            },
-                        )
-                    } else {
-                        (
-                            (*past_cpu_work, *past_cpu_total),
-                            CpuData {
-                                cpu_prefix: "CPU".to_string(),
-                                cpu_count: Some(itx),
-                                cpu_usage: 0.0,
-                            },
-                        )
-                    }
-                })
-                .unzip();
-
-        *previous_cpu_times = new_cpu_times;
-        cpu_deque
-    };
+            CpuData {
+                cpu_prefix: "CPU".to_string(),
+                cpu_count: Some(cpu_number),
+                cpu_usage,
+                is_offline: false,
+            }
+        })
+        .collect();
+    *previous_cpu_times = new_cpu_times;
+
+    // Offline CPUs are still listed, just marked as such, so that the other CPUs keep their spots.
+    let online_cpu_numbers = cpu_deque
+        .iter()
+        .filter_map(|cpu_data| cpu_data.cpu_count)
+        .collect::<FxHashSet<_>>();
+    for cpu_number in get_present_cpu_numbers() {
+        if !online_cpu_numbers.contains(&cpu_number) {
+            cpu_deque.push_back(CpuData {
+                cpu_prefix: "CPU".to_string(),
+                cpu_count: Some(cpu_number),
+                cpu_usage: 0.0,
+                is_offline: true,
+            });
+        }
+    }
+    cpu_deque
+        .make_contiguous()
+        .sort_by_key(|cpu_data| cpu_data.cpu_count);
 
     // Get average CPU if needed... and slap it at the top
     if show_average_cpu {
@@ -161,6 +162,7 @@ pub async fn get_cpu_data_list(
             cpu_prefix: "AVG".to_string(),
             cpu_count: None,
             cpu_usage,
+            is_offline: false,
         })
     }
 
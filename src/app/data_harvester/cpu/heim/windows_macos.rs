@@ -8,3 +8,14 @@ pub fn convert_cpu_times(cpu_time: &heim::cpu::CpuTime) -> (f64, f64) {
         working_time + cpu_time.idle().get::<heim::units::time::second>(),
     )
 }
+
+/// Returns the number of each CPU whose times are read.  They're always in order here.
+pub fn get_online_cpu_numbers() -> Option<Vec<usize>> {
+    None
+}
+
+/// Returns the numbers of the CPUs that are present, whether they're online or not.  Offline CPUs
+/// aren't detected here, so there are none.
+pub fn get_present_cpu_numbers() -> Vec<usize> {
+    Vec::new()
+}
@@ -3,6 +3,11 @@
 //! For CPU usage, Linux, macOS, and Windows are handled by Heim.
 //!
 //! For load average, macOS and Linux are supported through Heim.
+//!
+//! For the breakdown of CPU time into user, system, I/O wait, steal, and IRQ time, only Linux is
+//! supported.
+//!
+//! For throttling and core voltage, only Raspberry Pis running Linux are supported.
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))] {
@@ -11,4 +16,32 @@ cfg_if::cfg_if! {
     }
 }
 
+#[cfg(target_os = "linux")]
+pub mod throttle;
+#[cfg(target_os = "linux")]
+pub use self::throttle::*;
+
 pub type LoadAvgHarvest = [f32; 3];
+
+/// How the time across all CPUs was spent since the last harvest, as percentages of it.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CpuBreakdown {
+    pub user_percent: f64,
+    pub system_percent: f64,
+    pub io_wait_percent: f64,
+    pub steal_percent: f64,
+    pub irq_percent: f64,
+}
+
+/// Whether the CPU is being held back by the firmware, for boards that report it.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ThrottleHarvest {
+    pub is_under_voltage: bool,
+    /// Whether the frequency is capped or throttled, including by the soft temperature limit.
+    pub is_throttled: bool,
+    /// Whether there's been an under-voltage since boot.
+    pub has_under_voltage_occurred: bool,
+    /// Whether there's been throttling since boot.
+    pub has_throttled_occurred: bool,
+    pub core_voltage: Option<f64>,
+}
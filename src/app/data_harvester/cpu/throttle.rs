@@ -0,0 +1,110 @@
+//! Throttling and core voltage on Raspberry Pis, as reported by the VideoCore firmware.
+//!
+//! Newer kernels expose the throttle flags through sysfs; otherwise, and for the core voltage,
+//! this falls back to `vcgencmd`, which is only run every few seconds.
+
+use std::{
+    fs,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use super::ThrottleHarvest;
+use crate::{app::data_harvester::command::output_with_timeout, utils::error};
+
+const MODEL_PATH: &str = "/proc/device-tree/model";
+const GET_THROTTLED_PATH: &str = "/sys/devices/platform/soc/soc:firmware/get_throttled";
+
+/// How often to ask `vcgencmd` again.  Each run asks the firmware over a mailbox, which is slow.
+const VCGENCMD_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long `vcgencmd` may take before it's given up on until the next refresh.
+const VCGENCMD_TIMEOUT: Duration = Duration::from_secs(1);
+
+// Bits of the throttle flags reported by the firmware.
+const UNDER_VOLTAGE_NOW: u32 = 1 << 0;
+const FREQUENCY_CAPPED_NOW: u32 = 1 << 1;
+const THROTTLED_NOW: u32 = 1 << 2;
+const SOFT_TEMP_LIMIT_NOW: u32 = 1 << 3;
+const UNDER_VOLTAGE_OCCURRED: u32 = 1 << 16;
+const THROTTLED_OCCURRED: u32 = 1 << 18;
+
+/// Whether this is a Raspberry Pi, which is only checked once, and what `vcgencmd` last said.
+#[derive(Debug, Default)]
+pub struct ThrottleState {
+    is_raspberry_pi: Option<bool>,
+    last_vcgencmd_refresh: Option<Instant>,
+    vcgencmd_flags: Option<u32>,
+    core_voltage: Option<f64>,
+}
+
+/// Returns [`None`] if this isn't a Raspberry Pi, or if the firmware couldn't be asked.
+pub fn get_throttle_data(
+    throttle_state: &mut ThrottleState,
+) -> error::Result<Option<ThrottleHarvest>> {
+    let is_raspberry_pi = *throttle_state.is_raspberry_pi.get_or_insert_with(|| {
+        fs::read_to_string(MODEL_PATH)
+            .map(|model| model.starts_with("Raspberry Pi"))
+            .unwrap_or(false)
+    });
+    if !is_raspberry_pi {
+        return Ok(None);
+    }
+
+    let is_vcgencmd_due = throttle_state
+        .last_vcgencmd_refresh
+        .map(|last_refresh| last_refresh.elapsed() >= VCGENCMD_REFRESH_INTERVAL)
+        .unwrap_or(true);
+    let sysfs_flags = fs::read_to_string(GET_THROTTLED_PATH)
+        .ok()
+        .and_then(|flags| parse_throttle_flags(&flags));
+    if is_vcgencmd_due {
+        if sysfs_flags.is_none() {
+            throttle_state.vcgencmd_flags =
+                run_vcgencmd(&["get_throttled"]).and_then(|flags| parse_throttle_flags(&flags));
+        }
+        throttle_state.core_voltage = get_core_voltage();
+        throttle_state.last_vcgencmd_refresh = Some(Instant::now());
+    }
+
+    let flags = match sysfs_flags.or(throttle_state.vcgencmd_flags) {
+        Some(flags) => flags,
+        None => return Ok(None),
+    };
+
+    Ok(Some(ThrottleHarvest {
+        is_under_voltage: flags & UNDER_VOLTAGE_NOW != 0,
+        is_throttled: flags & (FREQUENCY_CAPPED_NOW | THROTTLED_NOW | SOFT_TEMP_LIMIT_NOW) != 0,
+        has_under_voltage_occurred: flags & UNDER_VOLTAGE_OCCURRED != 0,
+        has_throttled_occurred: flags & THROTTLED_OCCURRED != 0,
+        core_voltage: throttle_state.core_voltage,
+    }))
+}
+
+/// The flags are written out in hex, like `50005`, or `throttled=0x50005` from `vcgencmd`.
+fn parse_throttle_flags(flags: &str) -> Option<u32> {
+    let flags = flags.trim();
+    let flags = flags.strip_prefix("throttled=").unwrap_or(flags);
+    let flags = flags.strip_prefix("0x").unwrap_or(flags);
+
+    u32::from_str_radix(flags, 16).ok()
+}
+
+/// Reads the core voltage from output like `volt=0.8563V`.
+fn get_core_voltage() -> Option<f64> {
+    run_vcgencmd(&["measure_volts", "core"])?
+        .trim()
+        .strip_prefix("volt=")?
+        .strip_suffix('V')?
+        .parse()
+        .ok()
+}
+
+fn run_vcgencmd(args: &[&str]) -> Option<String> {
+    let output = output_with_timeout(Command::new("vcgencmd").args(args), VCGENCMD_TIMEOUT)?;
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        None
+    }
+}
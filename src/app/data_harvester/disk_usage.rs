@@ -0,0 +1,227 @@
+//! An on-demand, "du"-style scan of how much space is used under a directory.
+
+use std::{
+    fs::{self, Metadata},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+/// How many files to scan before publishing the running totals.
+const FILES_PER_PROGRESS_UPDATE: u64 = 1000;
+
+#[derive(Clone, Debug)]
+pub struct DiskUsageEntry {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub is_dir: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DiskUsageProgress {
+    /// Everything directly under the scanned directory.
+    pub entries: Vec<DiskUsageEntry>,
+    pub files_scanned: u64,
+    pub bytes_scanned: u64,
+    /// How many files or directories couldn't be read, usually due to permissions.
+    pub num_errors: u64,
+    pub is_done: bool,
+}
+
+/// A scan running in the background.  Dropping it cancels the scan.
+pub struct DiskUsageScan {
+    path: PathBuf,
+    progress: Arc<Mutex<DiskUsageProgress>>,
+    is_cancelled: Arc<AtomicBool>,
+}
+
+impl DiskUsageScan {
+    pub fn start(path: PathBuf) -> Self {
+        let progress = Arc::new(Mutex::new(DiskUsageProgress::default()));
+        let is_cancelled = Arc::new(AtomicBool::new(false));
+
+        let scan_path = path.clone();
+        let scan_progress = progress.clone();
+        let scan_is_cancelled = is_cancelled.clone();
+        thread::spawn(move || {
+            scan_directory(&scan_path, &scan_progress, &scan_is_cancelled);
+            if let Ok(mut progress) = scan_progress.lock() {
+                progress.is_done = true;
+            }
+        });
+
+        DiskUsageScan {
+            path,
+            progress,
+            is_cancelled,
+        }
+    }
+
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the scan's progress so far, with the largest entries first.
+    pub fn get_progress(&self) -> DiskUsageProgress {
+        let mut progress = if let Ok(progress) = self.progress.lock() {
+            progress.clone()
+        } else {
+            DiskUsageProgress::default()
+        };
+        progress
+            .entries
+            .sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+
+        progress
+    }
+
+    pub fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::SeqCst);
+    }
+}
+
+impl Drop for DiskUsageScan {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Tallies up everything under each direct child of the path, publishing progress as it goes.
+fn scan_directory(path: &Path, progress: &Mutex<DiskUsageProgress>, is_cancelled: &AtomicBool) {
+    let root_device = fs::symlink_metadata(path)
+        .ok()
+        .and_then(|metadata| get_device(&metadata));
+
+    let children = match fs::read_dir(path) {
+        Ok(children) => children,
+        Err(_) => {
+            if let Ok(mut progress) = progress.lock() {
+                progress.num_errors += 1;
+            }
+            return;
+        }
+    };
+
+    for child in children {
+        if is_cancelled.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let child = match child {
+            Ok(child) => child.path(),
+            Err(_) => {
+                if let Ok(mut progress) = progress.lock() {
+                    progress.num_errors += 1;
+                }
+                continue;
+            }
+        };
+
+        let metadata = match fs::symlink_metadata(&child) {
+            Ok(metadata) => metadata,
+            Err(_) => {
+                if let Ok(mut progress) = progress.lock() {
+                    progress.num_errors += 1;
+                }
+                continue;
+            }
+        };
+
+        let entry_index = if let Ok(mut progress) = progress.lock() {
+            progress.entries.push(DiskUsageEntry {
+                path: child.clone(),
+                size_bytes: 0,
+                is_dir: metadata.is_dir(),
+            });
+            progress.entries.len() - 1
+        } else {
+            return;
+        };
+
+        // Walk the child with our own stack, so deep trees can't overflow ours.
+        let mut to_visit = vec![(child, metadata)];
+        let mut size_bytes = 0;
+        let mut files_scanned = 0;
+        let mut num_errors = 0;
+
+        while let Some((current_path, current_metadata)) = to_visit.pop() {
+            if is_cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+
+            size_bytes += current_metadata.len();
+            files_scanned += 1;
+
+            // Like "du -x", stay on the filesystem we started on.
+            if current_metadata.is_dir() && get_device(&current_metadata) == root_device {
+                match fs::read_dir(&current_path) {
+                    Ok(grandchildren) => {
+                        for grandchild in grandchildren {
+                            match grandchild.and_then(|grandchild| {
+                                let grandchild_path = grandchild.path();
+                                fs::symlink_metadata(&grandchild_path)
+                                    .map(|metadata| (grandchild_path, metadata))
+                            }) {
+                                Ok(grandchild) => to_visit.push(grandchild),
+                                Err(_) => num_errors += 1,
+                            }
+                        }
+                    }
+                    Err(_) => num_errors += 1,
+                }
+            }
+
+            if files_scanned % FILES_PER_PROGRESS_UPDATE == 0 {
+                publish_progress(
+                    progress,
+                    entry_index,
+                    &mut size_bytes,
+                    &mut files_scanned,
+                    &mut num_errors,
+                );
+            }
+        }
+
+        publish_progress(
+            progress,
+            entry_index,
+            &mut size_bytes,
+            &mut files_scanned,
+            &mut num_errors,
+        );
+    }
+}
+
+/// Adds what has been tallied since the last update to the shared progress, and resets the tallies.
+fn publish_progress(
+    progress: &Mutex<DiskUsageProgress>, entry_index: usize, size_bytes: &mut u64,
+    files_scanned: &mut u64, num_errors: &mut u64,
+) {
+    if let Ok(mut progress) = progress.lock() {
+        if let Some(entry) = progress.entries.get_mut(entry_index) {
+            entry.size_bytes += *size_bytes;
+        }
+        progress.bytes_scanned += *size_bytes;
+        progress.files_scanned += *files_scanned;
+        progress.num_errors += *num_errors;
+    }
+
+    *size_bytes = 0;
+    *files_scanned = 0;
+    *num_errors = 0;
+}
+
+#[cfg(target_family = "unix")]
+fn get_device(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(metadata.dev())
+}
+
+#[cfg(not(target_family = "unix"))]
+fn get_device(_metadata: &Metadata) -> Option<u64> {
+    None
+}
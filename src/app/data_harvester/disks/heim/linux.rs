@@ -32,3 +32,78 @@ pub fn get_device_name(partition: &Partition) -> String {
         "Name Unavailable".to_string()
     }
 }
+
+/// How deep volumes stacked on other volumes are followed, such as LVM on top of an encrypted
+/// volume on top of a RAID array.
+const MAX_VOLUME_DEPTH: usize = 8;
+
+/// Returns the name of the whole device a partition is on.  For a device-mapper volume, like an
+/// LVM logical volume, or a RAID array, it's the one physical device the volume is on.  It's
+/// `None` if there's no such device, or if the volume spans several.
+pub fn get_parent_device(name: &str) -> Option<String> {
+    let backing_devices = get_backing_devices(name);
+    if backing_devices.is_empty() {
+        return get_partition_parent(name);
+    }
+
+    let mut devices = backing_devices
+        .into_iter()
+        .map(|device| get_partition_parent(&device).unwrap_or(device))
+        .collect::<Vec<_>>();
+    devices.sort();
+    devices.dedup();
+    if devices.len() == 1 {
+        devices.pop()
+    } else {
+        None
+    }
+}
+
+/// Returns the physical partitions or devices that a device-mapper volume or RAID array is built
+/// on, following stacked volumes all the way down.  Anything else isn't built on anything, so it
+/// has none.
+pub fn get_backing_devices(name: &str) -> Vec<String> {
+    let mut backing_devices = Vec::new();
+    add_backing_devices(name, 0, &mut backing_devices);
+    backing_devices.sort();
+    backing_devices.dedup();
+    backing_devices
+}
+
+fn add_backing_devices(name: &str, depth: usize, backing_devices: &mut Vec<String>) {
+    let slaves_path = std::path::Path::new("/sys/class/block")
+        .join(name)
+        .join("slaves");
+    let slaves = match std::fs::read_dir(slaves_path) {
+        Ok(slaves) => slaves,
+        Err(_) => return,
+    };
+
+    for slave in slaves.flatten() {
+        if let Ok(slave_name) = slave.file_name().into_string() {
+            let num_backing_devices = backing_devices.len();
+            if depth < MAX_VOLUME_DEPTH {
+                add_backing_devices(&slave_name, depth + 1, backing_devices);
+            }
+            if backing_devices.len() == num_backing_devices {
+                backing_devices.push(slave_name);
+            }
+        }
+    }
+}
+
+/// Returns the name of the whole device a partition is on, or `None` if it isn't a partition.
+fn get_partition_parent(name: &str) -> Option<String> {
+    let sys_path = std::path::Path::new("/sys/class/block").join(name);
+    if !sys_path.join("partition").exists() {
+        return None;
+    }
+
+    // A partition's sysfs entry is nested in its device's.
+    std::fs::canonicalize(sys_path)
+        .ok()?
+        .parent()?
+        .file_name()?
+        .to_str()
+        .map(|device| device.to_string())
+}
@@ -14,15 +14,24 @@ cfg_if::cfg_if! {
 pub struct DiskHarvest {
     pub name: String,
     pub mount_point: String,
+    /// The type of filesystem, like "ext4" or "nfs".
+    pub fs_type: String,
+    /// The physical partitions or devices the disk is on, if it's a logical volume, like one
+    /// from LVM, or a RAID array.  Only supported on Linux.
+    pub backing_devices: Vec<String>,
     pub free_space: Option<u64>,
     pub used_space: Option<u64>,
     pub total_space: Option<u64>,
+    pub used_inodes: Option<u64>,
+    pub total_inodes: Option<u64>,
 }
 
 #[derive(Clone, Debug)]
 pub struct IoData {
     pub read_bytes: u64,
     pub write_bytes: u64,
+    /// If this is a partition, the name of the whole device it's on.
+    pub device: Option<String>,
 }
 
 pub type IoHarvest = std::collections::HashMap<String, Option<IoData>>;
@@ -49,6 +58,7 @@ pub async fn get_io_usage(actually_get: bool) -> crate::utils::error::Result<Opt
                 Some(IoData {
                     read_bytes: io.read_bytes().get::<heim::units::information::byte>(),
                     write_bytes: io.write_bytes().get::<heim::units::information::byte>(),
+                    device: get_parent_device(mount_point),
                 }),
             );
         }
@@ -59,6 +69,7 @@ pub async fn get_io_usage(actually_get: bool) -> crate::utils::error::Result<Opt
 
 pub async fn get_disk_usage(
     actually_get: bool, disk_filter: &Option<Filter>, mount_filter: &Option<Filter>,
+    fs_filter: &Option<Filter>,
 ) -> crate::utils::error::Result<Option<Vec<DiskHarvest>>> {
     if !actually_get {
         return Ok(None);
@@ -67,11 +78,28 @@ pub async fn get_disk_usage(
     use futures::StreamExt;
 
     let mut vec_disks: Vec<DiskHarvest> = Vec::new();
-    let partitions_stream = heim::disk::partitions_physical().await?;
+    let mut btrfs_devices = std::collections::HashSet::new();
+
+    // Only physical filesystems are normally listed, but if only certain filesystem types are to
+    // be shown, those may well be virtual or network ones, like tmpfs or NFS.
+    let partitions_stream = match fs_filter {
+        Some(fs_filter) if !fs_filter.is_list_ignored => {
+            heim::disk::partitions().await?.left_stream()
+        }
+        _ => heim::disk::partitions_physical().await?.right_stream(),
+    };
     futures::pin_mut!(partitions_stream);
 
     while let Some(part) = partitions_stream.next().await {
         if let Ok(partition) = part {
+            let fs_type = partition.file_system().as_str().to_string();
+            if let Some(fs_filter) = fs_filter {
+                let is_listed = fs_filter.list.iter().any(|regex| regex.is_match(&fs_type));
+                if is_listed == fs_filter.is_list_ignored {
+                    continue;
+                }
+            }
+
             let name = get_device_name(&partition);
 
             let mount_point = (partition
@@ -123,16 +151,32 @@ pub async fn get_disk_usage(
                 })
             };
 
-            if to_keep {
+            // Each mounted subvolume of a Btrfs filesystem shows the usage of the whole filesystem,
+            // so only the first one kept is listed, rather than counting the same space again.
+            let is_repeated_btrfs =
+                to_keep && fs_type == "btrfs" && !btrfs_devices.insert(name.clone());
+
+            if to_keep && !is_repeated_btrfs {
+                let backing_devices = name
+                    .rsplit('/')
+                    .next()
+                    .map(get_backing_devices)
+                    .unwrap_or_default();
+
                 // The usage line can fail in some cases (for example, if you use Void Linux + LUKS,
                 // see https://github.com/ClementTsang/bottom/issues/419 for details).  As such, check
                 // it like this instead.
+                let (used_inodes, total_inodes) = get_inode_usage(&mount_point);
                 if let Ok(usage) = heim::disk::usage(partition.mount_point().to_path_buf()).await {
                     vec_disks.push(DiskHarvest {
                         free_space: Some(usage.free().get::<heim::units::information::byte>()),
                         used_space: Some(usage.used().get::<heim::units::information::byte>()),
                         total_space: Some(usage.total().get::<heim::units::information::byte>()),
+                        used_inodes,
+                        total_inodes,
                         mount_point,
+                        fs_type,
+                        backing_devices,
                         name,
                     });
                 } else {
@@ -140,7 +184,11 @@ pub async fn get_disk_usage(
                         free_space: None,
                         used_space: None,
                         total_space: None,
+                        used_inodes,
+                        total_inodes,
                         mount_point,
+                        fs_type,
+                        backing_devices,
                         name,
                     });
                 }
@@ -152,3 +200,28 @@ pub async fn get_disk_usage(
 
     Ok(Some(vec_disks))
 }
+
+/// Returns how many inodes are used out of how many the filesystem has.  Some filesystems, like
+/// Btrfs, allocate inodes on the fly and report a total of 0, which is treated as unknown.
+#[cfg(target_family = "unix")]
+fn get_inode_usage(mount_point: &str) -> (Option<u64>, Option<u64>) {
+    let path = match std::ffi::CString::new(mount_point) {
+        Ok(path) => path,
+        Err(_) => return (None, None),
+    };
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stats) } != 0 || stats.f_files == 0 {
+        return (None, None);
+    }
+
+    let total_inodes = stats.f_files as u64;
+    (
+        Some(total_inodes.saturating_sub(stats.f_ffree as u64)),
+        Some(total_inodes),
+    )
+}
+
+#[cfg(not(target_family = "unix"))]
+fn get_inode_usage(_mount_point: &str) -> (Option<u64>, Option<u64>) {
+    (None, None)
+}
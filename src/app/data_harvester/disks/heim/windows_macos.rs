@@ -12,3 +12,14 @@ pub fn get_device_name(partition: &Partition) -> String {
         "Name Unavailable".to_string()
     }
 }
+
+/// Returns the name of the whole device a partition is on.  I/O isn't split by partition here, so
+/// this is always `None`.
+pub fn get_parent_device(_name: &str) -> Option<String> {
+    None
+}
+
+/// Returns the physical devices a volume is built on.  Only supported on Linux.
+pub fn get_backing_devices(_name: &str) -> Vec<String> {
+    Vec::new()
+}
@@ -0,0 +1,62 @@
+//! Data collection for Docker containers.
+//!
+//! For Unix-like systems, this is read from the Docker Engine API over its local socket, rather
+//! than guessed at from cgroups.  Docker's Windows named pipe is not supported.
+
+cfg_if::cfg_if! {
+    if #[cfg(unix)] {
+        pub mod unix;
+        pub use self::unix::*;
+    } else {
+        /// Controlling containers is not supported on this platform.
+        pub fn control_container(
+            _container_name: &str, _action: ContainerAction,
+        ) -> crate::utils::error::Result<()> {
+            Err(crate::utils::error::BottomError::GenericError(
+                "controlling containers is not supported on this platform.".to_string(),
+            ))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl ContainerAction {
+    /// The action's name, which is also its endpoint in the Docker Engine API.
+    pub fn as_command(&self) -> &'static str {
+        match self {
+            ContainerAction::Start => "start",
+            ContainerAction::Stop => "stop",
+            ContainerAction::Restart => "restart",
+        }
+    }
+
+    /// How the action is shown while it's running.
+    pub fn as_progress(&self) -> &'static str {
+        match self {
+            ContainerAction::Start => "Starting",
+            ContainerAction::Stop => "Stopping",
+            ContainerAction::Restart => "Restarting",
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ContainerHarvest {
+    pub name: String,
+    pub image: String,
+    /// Docker's summary of the container's state, e.g. "Up 2 hours".
+    pub status: String,
+    pub is_running: bool,
+    /// How much of one CPU the container used since the last read.
+    pub cpu_usage_percent: Option<f64>,
+    pub mem_usage_bytes: Option<u64>,
+    pub mem_limit_bytes: Option<u64>,
+    pub rx_bytes_per_sec: Option<u64>,
+    pub tx_bytes_per_sec: Option<u64>,
+}
@@ -0,0 +1,317 @@
+//! Docker containers on Unix-like systems, via the Docker Engine API's local socket.
+
+use std::{
+    io::{Read, Write},
+    os::unix::net::UnixStream,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use fxhash::FxHashMap;
+use serde_json::Value;
+
+use super::{ContainerAction, ContainerHarvest};
+use crate::utils::error::{self, BottomError};
+
+const DEFAULT_SOCKET: &str = "/var/run/docker.sock";
+
+/// How long to wait on the Docker daemon before giving up on a request for data.  This is also
+/// how long all the containers' stats together may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long to wait on the Docker daemon to start, stop, or restart a container.  Docker gives a
+/// container 10 seconds to exit before killing it, so this has to be longer than that.
+const CONTROL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How many containers' stats to request at once.
+const MAX_STATS_THREADS: usize = 8;
+
+/// The last seen CPU time of each container and of the whole system in nanoseconds, and bytes
+/// received and sent, by container ID.
+pub type PrevContainerStats = FxHashMap<String, (Option<(u64, u64)>, Option<(u64, u64)>)>;
+
+/// Returns the path to the Docker socket, respecting `DOCKER_HOST` if it points at a socket.
+fn get_socket_path() -> String {
+    std::env::var("DOCKER_HOST")
+        .ok()
+        .and_then(|host| host.strip_prefix("unix://").map(|path| path.to_string()))
+        .unwrap_or_else(|| DEFAULT_SOCKET.to_string())
+}
+
+/// Sends a request to the Docker daemon, returning the status code and body of the response.
+/// HTTP/1.0 is used so the response is never chunked, and ends when the connection closes.
+fn request(method: &str, path: &str, timeout: Duration) -> error::Result<(u16, String)> {
+    let mut stream = UnixStream::connect(get_socket_path())?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    write!(
+        stream,
+        "{} {} HTTP/1.0\r\nHost: docker\r\nContent-Length: 0\r\n\r\n",
+        method, path
+    )?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+    let response = String::from_utf8_lossy(&response);
+    let (head, body) = response.split_once("\r\n\r\n").ok_or_else(|| {
+        BottomError::GenericError("malformed response from the Docker daemon".to_string())
+    })?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| {
+            BottomError::GenericError("malformed response from the Docker daemon".to_string())
+        })?;
+
+    Ok((status, body.to_string()))
+}
+
+/// Sends a `GET` request to the Docker daemon and parses the JSON it responds with.
+fn get_json(path: &str) -> error::Result<Value> {
+    let (status, body) = request("GET", path, REQUEST_TIMEOUT)?;
+    if status != 200 {
+        return Err(BottomError::GenericError(get_error_message(status, &body)));
+    }
+
+    serde_json::from_str(&body).map_err(|err| BottomError::ConversionError(err.to_string()))
+}
+
+/// Errors from the daemon come with a JSON body holding a message.
+fn get_error_message(status: u16, body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|body| body["message"].as_str().map(|message| message.to_string()))
+        .unwrap_or_else(|| format!("the Docker daemon responded with status {}", status))
+}
+
+/// What's needed from the stats the Docker daemon reports for a container.
+struct ContainerStats {
+    /// The CPU time of the container and of the whole system, in nanoseconds.
+    cpu_times: Option<(u64, u64)>,
+    num_cpus: u64,
+    mem_usage_bytes: Option<u64>,
+    mem_limit_bytes: Option<u64>,
+    /// The total bytes received and sent across all of the container's networks.
+    net_bytes: Option<(u64, u64)>,
+}
+
+fn read_stats(stats: &Value) -> ContainerStats {
+    let cpu_times = match (
+        stats
+            .pointer("/cpu_stats/cpu_usage/total_usage")
+            .and_then(Value::as_u64),
+        stats
+            .pointer("/cpu_stats/system_cpu_usage")
+            .and_then(Value::as_u64),
+    ) {
+        (Some(container_time), Some(system_time)) => Some((container_time, system_time)),
+        _ => None,
+    };
+    let num_cpus = stats
+        .pointer("/cpu_stats/online_cpus")
+        .and_then(Value::as_u64)
+        .or_else(|| {
+            stats
+                .pointer("/cpu_stats/cpu_usage/percpu_usage")
+                .and_then(Value::as_array)
+                .map(|percpu_usage| percpu_usage.len() as u64)
+        })
+        .unwrap_or(1);
+
+    // Like `docker stats`, don't count the page cache that could be freed.
+    let mem_usage_bytes = stats
+        .pointer("/memory_stats/usage")
+        .and_then(Value::as_u64)
+        .map(|usage| {
+            let cache = stats
+                .pointer("/memory_stats/stats/inactive_file")
+                .or_else(|| stats.pointer("/memory_stats/stats/total_inactive_file"))
+                .and_then(Value::as_u64)
+                .unwrap_or(0);
+            usage.saturating_sub(cache)
+        });
+    let mem_limit_bytes = stats.pointer("/memory_stats/limit").and_then(Value::as_u64);
+
+    let net_bytes = stats
+        .get("networks")
+        .and_then(Value::as_object)
+        .map(|networks| {
+            networks.values().fold((0, 0), |(rx, tx), network| {
+                (
+                    rx + network["rx_bytes"].as_u64().unwrap_or(0),
+                    tx + network["tx_bytes"].as_u64().unwrap_or(0),
+                )
+            })
+        });
+
+    ContainerStats {
+        cpu_times,
+        num_cpus,
+        mem_usage_bytes,
+        mem_limit_bytes,
+        net_bytes,
+    }
+}
+
+/// Gets the stats of each of the given containers, by ID.  These are requested a few at a time,
+/// and whatever hasn't come back by the deadline is left out.
+fn get_all_stats(ids: Vec<String>) -> FxHashMap<String, Value> {
+    let mut all_stats = FxHashMap::default();
+    if ids.is_empty() {
+        return all_stats;
+    }
+
+    let deadline = Instant::now() + REQUEST_TIMEOUT;
+    let num_ids = ids.len();
+    let num_threads = num_ids.min(MAX_STATS_THREADS);
+    let ids = Arc::new(Mutex::new(ids));
+    let (sender, receiver) = mpsc::channel();
+
+    for _ in 0..num_threads {
+        let ids = ids.clone();
+        let sender = sender.clone();
+        thread::spawn(move || loop {
+            let id = match ids.lock().ok().and_then(|mut ids| ids.pop()) {
+                Some(id) => id,
+                None => break,
+            };
+            let stats = get_json(&format!(
+                "/containers/{}/stats?stream=false&one-shot=true",
+                id
+            ))
+            .ok();
+
+            // Once the deadline has passed nothing is listening, so just stop.
+            if sender.send((id, stats)).is_err() {
+                break;
+            }
+        });
+    }
+    drop(sender);
+
+    for _ in 0..num_ids {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(timeout) {
+            Ok((id, Some(stats))) => {
+                all_stats.insert(id, stats);
+            }
+            Ok((_, None)) => {}
+            Err(_) => break,
+        }
+    }
+
+    all_stats
+}
+
+pub fn get_docker_data(
+    prev_stats: &mut PrevContainerStats, time_difference_in_secs: f64, actually_get: bool,
+) -> error::Result<Option<Vec<ContainerHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let containers = get_json("/containers/json?all=1")?;
+    let containers = containers.as_array().map(Vec::as_slice).unwrap_or_default();
+    let mut all_stats = get_all_stats(
+        containers
+            .iter()
+            .filter(|container| container["State"].as_str() == Some("running"))
+            .filter_map(|container| container["Id"].as_str().map(|id| id.to_string()))
+            .collect(),
+    );
+    let mut new_stats = PrevContainerStats::default();
+    let mut container_vec = Vec::new();
+
+    for container in containers {
+        let id = match container["Id"].as_str() {
+            Some(id) => id,
+            None => continue,
+        };
+        let mut container_harvest = ContainerHarvest {
+            name: container["Names"][0]
+                .as_str()
+                .map(|name| name.trim_start_matches('/'))
+                .unwrap_or(id)
+                .to_string(),
+            image: container["Image"].as_str().unwrap_or_default().to_string(),
+            status: container["Status"].as_str().unwrap_or_default().to_string(),
+            is_running: container["State"].as_str() == Some("running"),
+            ..ContainerHarvest::default()
+        };
+
+        // One-shot stats come back right away, without a previous sample to compare against, so
+        // the rates are worked out from what was seen last time instead.
+        if container_harvest.is_running {
+            if let Some(stats) = all_stats.remove(id) {
+                let ContainerStats {
+                    cpu_times,
+                    num_cpus,
+                    mem_usage_bytes,
+                    mem_limit_bytes,
+                    net_bytes,
+                } = read_stats(&stats);
+                let (prev_cpu_times, prev_net_bytes) = match prev_stats.get(id) {
+                    Some(prev) if time_difference_in_secs > 0.0 => *prev,
+                    _ => (None, None),
+                };
+                new_stats.insert(id.to_string(), (cpu_times, net_bytes));
+
+                container_harvest.cpu_usage_percent = match (cpu_times, prev_cpu_times) {
+                    (
+                        Some((container_time, system_time)),
+                        Some((prev_container_time, prev_system_time)),
+                    ) if system_time > prev_system_time => Some(
+                        container_time.saturating_sub(prev_container_time) as f64
+                            / (system_time - prev_system_time) as f64
+                            * num_cpus as f64
+                            * 100.0,
+                    ),
+                    _ => None,
+                };
+                container_harvest.mem_usage_bytes = mem_usage_bytes;
+                container_harvest.mem_limit_bytes = mem_limit_bytes;
+                if let (Some((rx_bytes, tx_bytes)), Some((prev_rx_bytes, prev_tx_bytes))) =
+                    (net_bytes, prev_net_bytes)
+                {
+                    container_harvest.rx_bytes_per_sec = Some(
+                        (rx_bytes.saturating_sub(prev_rx_bytes) as f64 / time_difference_in_secs)
+                            as u64,
+                    );
+                    container_harvest.tx_bytes_per_sec = Some(
+                        (tx_bytes.saturating_sub(prev_tx_bytes) as f64 / time_difference_in_secs)
+                            as u64,
+                    );
+                }
+            }
+        }
+
+        container_vec.push(container_harvest);
+    }
+    *prev_stats = new_stats;
+
+    // Running containers first, then by name.
+    container_vec.sort_by(|a, b| {
+        b.is_running
+            .cmp(&a.is_running)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(Some(container_vec))
+}
+
+/// Starts, stops, or restarts a container, by name or ID.
+pub fn control_container(container_name: &str, action: ContainerAction) -> error::Result<()> {
+    let (status, body) = request(
+        "POST",
+        &format!("/containers/{}/{}", container_name, action.as_command()),
+        CONTROL_TIMEOUT,
+    )?;
+
+    // 304 means the container was already started or stopped.
+    match status {
+        200..=299 | 304 => Ok(()),
+        _ => Err(BottomError::GenericError(get_error_message(status, &body))),
+    }
+}
@@ -0,0 +1,414 @@
+//! Pushes harvested metrics to a time series database, in either InfluxDB's line protocol or
+//! Graphite's plaintext protocol.
+
+use std::{
+    collections::HashMap,
+    io::Write,
+    net::{TcpStream, ToSocketAddrs, UdpSocket},
+    sync::mpsc::{self, Receiver, SyncSender},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::Data;
+
+/// How often to push metrics if an exporter doesn't say otherwise.
+const DEFAULT_INTERVAL_MS: u64 = 10_000;
+
+const DEFAULT_PREFIX: &str = "btm";
+
+/// How long to wait on an endpoint before giving up on a push.
+const SEND_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExporterFormat {
+    Influx,
+    Graphite,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExporterTransport {
+    Tcp,
+    Udp,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ExporterConfig {
+    pub format: ExporterFormat,
+    /// The address to push to, for example `127.0.0.1:8089`.
+    pub address: String,
+    /// Defaults to TCP.
+    pub transport: Option<ExporterTransport>,
+    /// How often to push, in milliseconds.  Metrics can't be pushed more often than they're
+    /// harvested, so this is effectively rounded up to the update rate.
+    pub interval: Option<u64>,
+    /// Prepended to every measurement (InfluxDB) or metric path (Graphite).
+    pub prefix: Option<String>,
+    /// Extra tags to attach to every metric, such as the host name.
+    pub tags: Option<HashMap<String, String>>,
+}
+
+/// A single measurement, made up of one or more values that share the same tags.
+struct Metric {
+    name: &'static str,
+    tags: Vec<(&'static str, String)>,
+    fields: Vec<(&'static str, f64)>,
+}
+
+impl Metric {
+    fn new(name: &'static str) -> Self {
+        Metric {
+            name,
+            tags: Vec::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    fn tag(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.tags.push((key, value.into()));
+        self
+    }
+
+    fn field(mut self, key: &'static str, value: impl Into<Option<f64>>) -> Self {
+        // Neither protocol has a way to write out NaN or infinity.
+        if let Some(value) = value.into().filter(|value| value.is_finite()) {
+            self.fields.push((key, value));
+        }
+        self
+    }
+}
+
+pub struct Exporter {
+    format: ExporterFormat,
+    prefix: String,
+    /// Sorted by key, as InfluxDB recommends.
+    tags: Vec<(String, String)>,
+    interval: Duration,
+    last_push: Option<Instant>,
+    sender: SyncSender<String>,
+}
+
+impl Exporter {
+    /// Starts a thread to send this exporter's metrics, so a slow endpoint can't hold up
+    /// collection.
+    pub fn start(config: &ExporterConfig) -> Self {
+        // Only keep one push waiting; if the endpoint can't keep up, newer metrics win.
+        let (sender, receiver) = mpsc::sync_channel(1);
+
+        let address = config.address.clone();
+        let transport = config.transport.unwrap_or(ExporterTransport::Tcp);
+        thread::spawn(move || send_payloads(&address, transport, receiver));
+
+        let mut tags: Vec<(String, String)> = config
+            .tags
+            .as_ref()
+            .map(|tags| {
+                tags.iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        tags.sort();
+
+        Exporter {
+            format: config.format,
+            prefix: config
+                .prefix
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PREFIX.to_string()),
+            tags,
+            interval: Duration::from_millis(config.interval.unwrap_or(DEFAULT_INTERVAL_MS)),
+            last_push: None,
+            sender,
+        }
+    }
+
+    /// Queues up the harvested data to be pushed, if enough time has passed since the last push.
+    pub fn push(&mut self, data: &Data) {
+        if let Some(last_push) = self.last_push {
+            if last_push.elapsed() < self.interval {
+                return;
+            }
+        }
+        self.last_push = Some(Instant::now());
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let metrics = get_metrics(data);
+        let payload = match self.format {
+            ExporterFormat::Influx => self.to_influx(&metrics, timestamp),
+            ExporterFormat::Graphite => self.to_graphite(&metrics, timestamp),
+        };
+
+        if !payload.is_empty() {
+            // If the last push is still in flight, just skip this one.
+            let _ = self.sender.try_send(payload);
+        }
+    }
+
+    /// Formats metrics as InfluxDB line protocol, like
+    /// `btm_cpu,cpu=cpu0,host=server usage=12.5 1620000000000000000`.
+    fn to_influx(&self, metrics: &[Metric], timestamp: Duration) -> String {
+        let mut payload = String::new();
+
+        for metric in metrics {
+            if metric.fields.is_empty() {
+                continue;
+            }
+
+            let mut tags: Vec<(&str, &str)> = metric
+                .tags
+                .iter()
+                .map(|(key, value)| (*key, value.as_str()))
+                .chain(
+                    self.tags
+                        .iter()
+                        .map(|(key, value)| (key.as_str(), value.as_str())),
+                )
+                .filter(|(_, value)| !value.is_empty())
+                .collect();
+            tags.sort();
+
+            payload.push_str(&escape_influx(
+                &format!("{}_{}", self.prefix, metric.name),
+                false,
+            ));
+            for (key, value) in tags {
+                payload.push_str(&format!(
+                    ",{}={}",
+                    escape_influx(key, true),
+                    escape_influx(value, true)
+                ));
+            }
+
+            let fields: Vec<String> = metric
+                .fields
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            payload.push_str(&format!(" {} {}\n", fields.join(","), timestamp.as_nanos()));
+        }
+
+        payload
+    }
+
+    /// Formats metrics as Graphite's plaintext protocol, using Graphite's tag support, like
+    /// `btm.cpu.usage;cpu=cpu0;host=server 12.5 1620000000`.
+    fn to_graphite(&self, metrics: &[Metric], timestamp: Duration) -> String {
+        let mut payload = String::new();
+
+        for metric in metrics {
+            let mut tags = String::new();
+            for (key, value) in metric
+                .tags
+                .iter()
+                .map(|(key, value)| (*key, value.as_str()))
+                .chain(
+                    self.tags
+                        .iter()
+                        .map(|(key, value)| (key.as_str(), value.as_str())),
+                )
+                .filter(|(_, value)| !value.is_empty())
+            {
+                tags.push_str(&format!(
+                    ";{}={}",
+                    escape_graphite(key),
+                    escape_graphite(value)
+                ));
+            }
+
+            for (key, value) in &metric.fields {
+                payload.push_str(&format!(
+                    "{}.{}.{}{} {} {}\n",
+                    self.prefix,
+                    metric.name,
+                    key,
+                    tags,
+                    value,
+                    timestamp.as_secs()
+                ));
+            }
+        }
+
+        payload
+    }
+}
+
+/// Starts an exporter for each entry in the config.
+pub fn start_exporters(configs: &[ExporterConfig]) -> Vec<Exporter> {
+    configs.iter().map(Exporter::start).collect()
+}
+
+/// Sends payloads as they come in, reconnecting as needed.  Failed pushes are dropped, as
+/// they would be out of date by the time the endpoint is back.
+fn send_payloads(address: &str, transport: ExporterTransport, receiver: Receiver<String>) {
+    let mut tcp_stream: Option<TcpStream> = None;
+    let mut udp_socket: Option<UdpSocket> = None;
+
+    for payload in receiver {
+        let result = match transport {
+            ExporterTransport::Tcp => {
+                if tcp_stream.is_none() {
+                    tcp_stream = connect_tcp(address).ok();
+                }
+
+                match &mut tcp_stream {
+                    Some(stream) => stream.write_all(payload.as_bytes()),
+                    None => continue,
+                }
+            }
+            ExporterTransport::Udp => {
+                if udp_socket.is_none() {
+                    udp_socket = UdpSocket::bind("0.0.0.0:0")
+                        .and_then(|socket| socket.connect(address).map(|_| socket))
+                        .ok();
+                }
+
+                match &udp_socket {
+                    // Keep each datagram below a typical MTU by sending a line at a time.
+                    Some(socket) => payload.lines().try_for_each(|line| {
+                        socket.send(format!("{}\n", line).as_bytes()).map(|_| ())
+                    }),
+                    None => continue,
+                }
+            }
+        };
+
+        if result.is_err() {
+            tcp_stream = None;
+            udp_socket = None;
+        }
+    }
+}
+
+fn connect_tcp(address: &str) -> std::io::Result<TcpStream> {
+    let mut last_err = None;
+    for socket_address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&socket_address, SEND_TIMEOUT) {
+            Ok(stream) => {
+                stream.set_write_timeout(Some(SEND_TIMEOUT))?;
+                return Ok(stream);
+            }
+            Err(err) => last_err = Some(err),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} did not resolve to any addresses", address),
+        )
+    }))
+}
+
+/// Gathers whatever was harvested into metrics.  What gets harvested depends on the widgets in
+/// use, so some of these may be missing.
+fn get_metrics(data: &Data) -> Vec<Metric> {
+    let mut metrics = Vec::new();
+
+    if let Some(cpu) = &data.cpu {
+        metrics.extend(cpu.iter().map(|cpu_data| {
+            Metric::new("cpu")
+                .tag(
+                    "cpu",
+                    match cpu_data.cpu_count {
+                        Some(count) => format!("{}{}", cpu_data.cpu_prefix, count),
+                        None => cpu_data.cpu_prefix.clone(),
+                    },
+                )
+                .field("usage", cpu_data.cpu_usage)
+        }));
+    }
+
+    if let Some(load_avg) = &data.load_avg {
+        metrics.push(
+            Metric::new("load_avg")
+                .field("one", f64::from(load_avg[0]))
+                .field("five", f64::from(load_avg[1]))
+                .field("fifteen", f64::from(load_avg[2])),
+        );
+    }
+
+    for (name, memory) in &[("memory", &data.memory), ("swap", &data.swap)] {
+        if let Some(memory) = memory {
+            metrics.push(
+                Metric::new(*name)
+                    .field("total_kib", memory.mem_total_in_kib as f64)
+                    .field("used_kib", memory.mem_used_in_kib as f64)
+                    .field("use_percent", memory.use_percent),
+            );
+        }
+    }
+
+    if let Some(network) = &data.network {
+        metrics.push(
+            Metric::new("network")
+                .field("rx_bits_per_sec", network.rx as f64)
+                .field("tx_bits_per_sec", network.tx as f64)
+                // The totals are kept in bits, like the rates.
+                .field("total_rx_bytes", (network.total_rx / 8) as f64)
+                .field("total_tx_bytes", (network.total_tx / 8) as f64),
+        );
+    }
+
+    if let Some(disks) = &data.disks {
+        metrics.extend(disks.iter().map(|disk| {
+            Metric::new("disk")
+                .tag("disk", disk.name.clone())
+                .tag("mount", disk.mount_point.clone())
+                .field("free_bytes", disk.free_space.map(|space| space as f64))
+                .field("used_bytes", disk.used_space.map(|space| space as f64))
+                .field("total_bytes", disk.total_space.map(|space| space as f64))
+        }));
+    }
+
+    if let Some(sensors) = &data.temperature_sensors {
+        metrics.extend(sensors.iter().map(|sensor| {
+            Metric::new(sensor.kind.as_str())
+                .tag("sensor", sensor.name.clone())
+                .field("value", f64::from(sensor.temperature))
+        }));
+    }
+
+    if let Some(batteries) = &data.list_of_batteries {
+        metrics.extend(batteries.iter().enumerate().map(|(itx, battery)| {
+            Metric::new("battery")
+                .tag("battery", itx.to_string())
+                .field("charge_percent", battery.charge_percent)
+        }));
+    }
+
+    if let Some(processes) = &data.list_of_processes {
+        metrics.push(Metric::new("processes").field("count", processes.len() as f64));
+    }
+
+    metrics
+}
+
+/// Escapes a measurement name, or a tag's key or value, for InfluxDB line protocol.
+fn escape_influx(value: &str, is_tag: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == ',' || c == ' ' || (is_tag && c == '=') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Graphite tags can't contain spaces or any of `;!^=~`, so swap those out.
+fn escape_graphite(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            ' ' | ';' | '!' | '^' | '=' | '~' => '_',
+            c => c,
+        })
+        .collect()
+}
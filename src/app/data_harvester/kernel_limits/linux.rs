@@ -0,0 +1,58 @@
+//! Kernel limits data collection for Linux.
+
+use std::fs;
+
+use super::KernelLimitsHarvest;
+use crate::utils::error;
+
+const FILE_NR_PATH: &str = "/proc/sys/fs/file-nr";
+const ENTROPY_AVAIL_PATH: &str = "/proc/sys/kernel/random/entropy_avail";
+const POOLSIZE_PATH: &str = "/proc/sys/kernel/random/poolsize";
+const LOADAVG_PATH: &str = "/proc/loadavg";
+const PID_MAX_PATH: &str = "/proc/sys/kernel/pid_max";
+
+pub fn get_kernel_limits_data(actually_get: bool) -> error::Result<Option<KernelLimitsHarvest>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    Ok(Some(KernelLimitsHarvest {
+        file_handles: get_file_handles(),
+        entropy: get_entropy(),
+        pids: get_pids(),
+    }))
+}
+
+fn read_number(path: &str) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn get_file_handles() -> Option<(u64, u64)> {
+    // This is the number of allocated handles, how many of those are unused, and the maximum.
+    let file_nr = fs::read_to_string(FILE_NR_PATH).ok()?;
+    let mut values = file_nr
+        .split_whitespace()
+        .map(|value| value.parse::<u64>().ok());
+    let allocated = values.next()??;
+    let unused = values.next()??;
+    let max = values.next()??;
+
+    Some((allocated.saturating_sub(unused), max))
+}
+
+fn get_entropy() -> Option<(u64, u64)> {
+    Some((
+        read_number(ENTROPY_AVAIL_PATH)?,
+        read_number(POOLSIZE_PATH)?,
+    ))
+}
+
+fn get_pids() -> Option<(u64, u64)> {
+    // The fourth field is the number of running and total scheduling entities (processes and
+    // threads), like "2/1234".
+    let loadavg = fs::read_to_string(LOADAVG_PATH).ok()?;
+    let entities = loadavg.split_whitespace().nth(3)?;
+    let (_running, total) = entities.split_once('/')?;
+
+    Some((total.parse().ok()?, read_number(PID_MAX_PATH)?))
+}
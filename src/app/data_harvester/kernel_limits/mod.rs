@@ -0,0 +1,30 @@
+//! Data collection for kernel limits, like how many file handles are open out of the most the
+//! kernel will hand out.
+//!
+//! For Linux, these are read from `/proc`.
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else {
+        /// Kernel limits are not supported on this platform.
+        pub fn get_kernel_limits_data(
+            _actually_get: bool,
+        ) -> crate::utils::error::Result<Option<KernelLimitsHarvest>> {
+            Ok(None)
+        }
+    }
+}
+
+/// Each counter is how much is in use and its limit, if it could be read.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct KernelLimitsHarvest {
+    /// Allocated file handles, out of `fs.file-max`.
+    pub file_handles: Option<(u64, u64)>,
+    /// Bits of entropy available, out of the size of the entropy pool.  Unlike the others,
+    /// this is a problem when it's low rather than high.
+    pub entropy: Option<(u64, u64)>,
+    /// Processes and threads, which each take up a PID, out of `kernel.pid_max`.
+    pub pids: Option<(u64, u64)>,
+}
@@ -0,0 +1,231 @@
+//! Virtual machines on Linux, read through `virsh`.
+
+use std::{
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use fxhash::FxHashMap;
+
+use super::VmHarvest;
+use crate::{
+    app::data_harvester::command::output_with_timeout,
+    utils::error::{self, BottomError},
+};
+
+/// How long to wait between listing VMs.  `virsh` has to ask libvirtd, which can be slow.
+const VM_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+/// How long to wait before trying again after `virsh` failed or hung.
+const VM_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+/// How long `virsh` may run before it's killed, so a hung libvirtd doesn't stall harvesting.
+const VIRSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The running totals a VM's rates are worked out from.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct VmCounters {
+    cpu_time_ns: Option<u64>,
+    disk_read_bytes: u64,
+    disk_write_bytes: u64,
+    rx_bytes: u64,
+    tx_bytes: u64,
+}
+
+/// The last seen counters of each VM by name, and the VMs as of the last time they were listed,
+/// which are reused until it's time to list them again.
+#[derive(Debug, Default)]
+pub struct PrevVmStats {
+    counters: FxHashMap<String, VmCounters>,
+    last_read: Option<Instant>,
+    next_read: Option<Instant>,
+    vms: Option<Vec<VmHarvest>>,
+}
+
+/// A VM's stats as listed by `virsh domstats`, before any rates are worked out.
+#[derive(Debug, Default)]
+struct DomainStats {
+    name: String,
+    vcpus: Option<u64>,
+    balloon_kib: Option<u64>,
+    balloon_max_kib: Option<u64>,
+    counters: VmCounters,
+}
+
+/// Parses the output of `virsh domstats --raw`, which lists each VM followed by its stats as
+/// indented `key=value` lines, something like:
+///
+/// ```text
+/// Domain: 'debian'
+///   cpu.time=1289503214000
+///   balloon.current=2097152
+///   balloon.maximum=4194304
+///   vcpu.current=2
+///   net.count=1
+///   net.0.name=vnet0
+///   net.0.rx.bytes=58391022
+///   net.0.tx.bytes=1203984
+///   block.count=1
+///   block.0.name=vda
+///   block.0.rd.bytes=402651136
+///   block.0.wr.bytes=98566144
+/// ```
+///
+/// Devices are numbered, so their counters are summed across all of a VM's disks and interfaces.
+fn parse_domstats(output: &str) -> Vec<DomainStats> {
+    let mut domains: Vec<DomainStats> = Vec::new();
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix("Domain: ") {
+            domains.push(DomainStats {
+                name: name.trim_matches('\'').to_string(),
+                ..DomainStats::default()
+            });
+            continue;
+        }
+
+        let domain = match domains.last_mut() {
+            Some(domain) => domain,
+            None => continue,
+        };
+        let mut split = line.splitn(2, '=');
+        let (key, value) = match (
+            split.next(),
+            split.next().and_then(|value| value.parse::<u64>().ok()),
+        ) {
+            (Some(key), Some(value)) => (key, value),
+            _ => continue,
+        };
+
+        // Per-device keys look like `block.0.rd.bytes`; the device's index doesn't matter here.
+        let device_stat = |prefix: &str| {
+            key.strip_prefix(prefix)
+                .and_then(|rest| rest.splitn(2, '.').nth(1))
+        };
+        let counters = &mut domain.counters;
+        match key {
+            "cpu.time" => counters.cpu_time_ns = Some(value),
+            "balloon.current" => domain.balloon_kib = Some(value),
+            "balloon.maximum" => domain.balloon_max_kib = Some(value),
+            "vcpu.current" => domain.vcpus = Some(value),
+            _ => match (device_stat("block."), device_stat("net.")) {
+                (Some("rd.bytes"), _) => counters.disk_read_bytes += value,
+                (Some("wr.bytes"), _) => counters.disk_write_bytes += value,
+                (_, Some("rx.bytes")) => counters.rx_bytes += value,
+                (_, Some("tx.bytes")) => counters.tx_bytes += value,
+                _ => {}
+            },
+        }
+    }
+
+    domains
+}
+
+pub fn get_vm_data(
+    prev_stats: &mut PrevVmStats, actually_get: bool,
+) -> error::Result<Option<Vec<VmHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let now = Instant::now();
+    if prev_stats
+        .next_read
+        .map(|next_read| now < next_read)
+        .unwrap_or(false)
+    {
+        return Ok(prev_stats.vms.clone());
+    }
+
+    match read_vms(prev_stats, now) {
+        Ok(vm_vec) => {
+            prev_stats.next_read = Some(now + VM_REFRESH_INTERVAL);
+            prev_stats.vms = Some(vm_vec);
+            Ok(prev_stats.vms.clone())
+        }
+        Err(err) => {
+            prev_stats.next_read = Some(now + VM_RETRY_INTERVAL);
+            prev_stats.vms = None;
+            Err(err)
+        }
+    }
+}
+
+fn read_vms(prev_stats: &mut PrevVmStats, now: Instant) -> error::Result<Vec<VmHarvest>> {
+    let output = output_with_timeout(
+        Command::new("virsh").args(&[
+            "--readonly",
+            "domstats",
+            "--list-running",
+            "--raw",
+            "--cpu-total",
+            "--balloon",
+            "--vcpu",
+            "--interface",
+            "--block",
+        ]),
+        VIRSH_TIMEOUT,
+    )
+    .ok_or_else(|| {
+        BottomError::GenericError("virsh could not be run, or timed out.".to_string())
+    })?;
+
+    if !output.status.success() {
+        return Err(BottomError::GenericError(format!(
+            "virsh failed to list VMs: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let time_difference_in_secs = prev_stats
+        .last_read
+        .map(|last_read| now.duration_since(last_read).as_secs_f64())
+        .unwrap_or(0.0);
+    prev_stats.last_read = Some(now);
+
+    let mut new_counters = FxHashMap::default();
+    let mut vm_vec: Vec<VmHarvest> = parse_domstats(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .map(|domain| {
+            let counters = domain.counters;
+            let prev_counters = match prev_stats.counters.get(&domain.name) {
+                Some(prev_counters) if time_difference_in_secs > 0.0 => Some(*prev_counters),
+                _ => None,
+            };
+            new_counters.insert(domain.name.clone(), counters);
+
+            let get_rate = |get_bytes: fn(&VmCounters) -> u64| {
+                prev_counters.map(|prev_counters| {
+                    (get_bytes(&counters).saturating_sub(get_bytes(&prev_counters)) as f64
+                        / time_difference_in_secs) as u64
+                })
+            };
+            let cpu_usage_percent = match (
+                counters.cpu_time_ns,
+                prev_counters.and_then(|prev_counters| prev_counters.cpu_time_ns),
+            ) {
+                (Some(cpu_time_ns), Some(prev_cpu_time_ns)) => Some(
+                    cpu_time_ns.saturating_sub(prev_cpu_time_ns) as f64
+                        / (time_difference_in_secs * 1_000_000_000.0)
+                        * 100.0,
+                ),
+                _ => None,
+            };
+
+            VmHarvest {
+                vcpus: domain.vcpus,
+                cpu_usage_percent,
+                balloon_bytes: domain.balloon_kib.map(|kib| kib * 1024),
+                balloon_max_bytes: domain.balloon_max_kib.map(|kib| kib * 1024),
+                disk_read_bytes_per_sec: get_rate(|counters| counters.disk_read_bytes),
+                disk_write_bytes_per_sec: get_rate(|counters| counters.disk_write_bytes),
+                rx_bytes_per_sec: get_rate(|counters| counters.rx_bytes),
+                tx_bytes_per_sec: get_rate(|counters| counters.tx_bytes),
+                name: domain.name,
+            }
+        })
+        .collect();
+    vm_vec.sort_by(|a, b| a.name.cmp(&b.name));
+    prev_stats.counters = new_counters;
+
+    Ok(vm_vec)
+}
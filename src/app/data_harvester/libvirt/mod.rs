@@ -0,0 +1,28 @@
+//! Data collection for virtual machines managed by libvirt, such as KVM/QEMU guests.
+//!
+//! For Linux, this is read from `virsh domstats`, using libvirt's default connection for the user
+//! running bottom.  Set `LIBVIRT_DEFAULT_URI` to pick another, e.g. `qemu:///system`.  As `virsh`
+//! has to go through libvirtd, it's only run every couple of seconds, and is killed if it hangs.
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct VmHarvest {
+    pub name: String,
+    pub vcpus: Option<u64>,
+    /// How much of one host CPU the VM's vCPUs used since the last read.
+    pub cpu_usage_percent: Option<f64>,
+    /// The memory currently given to the VM by its balloon driver.
+    pub balloon_bytes: Option<u64>,
+    /// The most memory the balloon can grow to.
+    pub balloon_max_bytes: Option<u64>,
+    pub disk_read_bytes_per_sec: Option<u64>,
+    pub disk_write_bytes_per_sec: Option<u64>,
+    pub rx_bytes_per_sec: Option<u64>,
+    pub tx_bytes_per_sec: Option<u64>,
+}
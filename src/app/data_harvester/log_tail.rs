@@ -0,0 +1,174 @@
+//! Follows the end of a file, like `tail -f`, for log widgets.
+//!
+//! Each harvest only reads what was appended since the last one, and passes the new lines on to
+//! be added to the widget's lines.
+
+use std::{
+    fs::{File, Metadata},
+    io::{Read, Seek, SeekFrom},
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// How many lines to keep around for scrolling back.
+pub const MAX_LINES: usize = 1000;
+
+/// The most to read at once.  When first opening a file, or if a lot was written since the last
+/// read, everything before this is skipped.
+const MAX_READ_BYTES: u64 = 256 * 1024;
+
+/// Colours the lines of a log widget that match a pattern.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LogHighlightConfig {
+    /// A regex to match against each line.
+    pub pattern: String,
+    pub color: String,
+}
+
+/// What was read from a log widget's file since the last harvest.
+#[derive(Clone, Debug)]
+pub struct LogHarvest {
+    pub widget_id: u64,
+    pub new_lines: Vec<String>,
+    /// Why the file couldn't be read, if it couldn't.
+    pub error: Option<String>,
+}
+
+impl LogHarvest {
+    /// Adds on a later harvest of the same file, keeping at most [`MAX_LINES`] lines.
+    pub fn merge(&mut self, later: LogHarvest) {
+        self.new_lines.extend(later.new_lines);
+        if self.new_lines.len() > MAX_LINES {
+            self.new_lines.drain(..self.new_lines.len() - MAX_LINES);
+        }
+        self.error = later.error;
+    }
+}
+
+#[derive(Debug)]
+pub struct LogTail {
+    widget_id: u64,
+    path: PathBuf,
+    offset: Option<u64>,
+    /// The device and inode of the file that was read last, to tell when it's been replaced.
+    file_id: Option<(u64, u64)>,
+    /// The end of the last read, if it didn't end with a newline.
+    partial_line: String,
+}
+
+impl LogTail {
+    pub fn new(widget_id: u64, path: PathBuf) -> Self {
+        LogTail {
+            widget_id,
+            path,
+            offset: None,
+            file_id: None,
+            partial_line: String::new(),
+        }
+    }
+
+    /// Reads anything appended to the file since the last read.
+    pub fn read(&mut self) -> LogHarvest {
+        let mut new_lines = Vec::new();
+        let error = match self.read_new_lines(&mut new_lines) {
+            Ok(()) => None,
+            Err(err) => {
+                // Start over once the file is readable again, as it may have been replaced.
+                self.offset = None;
+                Some(err.to_string())
+            }
+        };
+
+        LogHarvest {
+            widget_id: self.widget_id,
+            new_lines,
+            error,
+        }
+    }
+
+    fn read_new_lines(&mut self, new_lines: &mut Vec<String>) -> std::io::Result<()> {
+        let mut file = File::open(&self.path)?;
+        let metadata = file.metadata()?;
+        let len = metadata.len();
+
+        // A file that was replaced, like when logs are rotated, is read from its start.  So is
+        // one that was truncated, which has to be caught by its length.
+        let file_id = get_file_id(&metadata);
+        let offset = match self.offset {
+            Some(offset) if file_id != self.file_id || len < offset => Some(0),
+            offset => offset,
+        };
+        self.file_id = file_id;
+
+        let mut skip_first_line = false;
+        let start = match offset {
+            Some(offset) if len - offset > MAX_READ_BYTES => {
+                self.partial_line.clear();
+                skip_first_line = true;
+                len - MAX_READ_BYTES
+            }
+            Some(0) => {
+                self.partial_line.clear();
+                0
+            }
+            Some(offset) => offset,
+            None => {
+                self.partial_line.clear();
+                skip_first_line = len > MAX_READ_BYTES;
+                len.saturating_sub(MAX_READ_BYTES)
+            }
+        };
+        self.offset = Some(len);
+
+        if start == len {
+            return Ok(());
+        }
+
+        let mut buffer = Vec::with_capacity((len - start) as usize);
+        file.seek(SeekFrom::Start(start))?;
+        file.take(len - start).read_to_end(&mut buffer)?;
+
+        let text = String::from_utf8_lossy(&buffer);
+        let mut text = text.as_ref();
+        if skip_first_line {
+            // This most likely starts partway through a line.
+            text = text.split_once('\n').map(|(_, rest)| rest).unwrap_or("");
+        }
+
+        let mut pieces = text.split('\n').peekable();
+        while let Some(piece) = pieces.next() {
+            if pieces.peek().is_none() {
+                // The last piece is whatever comes after the last newline.
+                self.partial_line.push_str(piece);
+                break;
+            }
+
+            let mut line = std::mem::take(&mut self.partial_line);
+            line.push_str(piece.trim_end_matches('\r'));
+            new_lines.push(line);
+        }
+
+        if new_lines.len() > MAX_LINES {
+            new_lines.drain(..new_lines.len() - MAX_LINES);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn get_file_id(metadata: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn get_file_id(_metadata: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+pub fn get_log_data(log_tails: &mut [LogTail]) -> Vec<LogHarvest> {
+    log_tails.iter_mut().map(LogTail::read).collect()
+}
@@ -0,0 +1,44 @@
+//! Huge page usage on Linux.  Memory reserved for huge pages is taken out of what's free up front,
+//! whether or not anything uses it, which matters on database and VM hosts that set aside a lot.
+
+use std::fs;
+
+use super::HugepagesHarvest;
+use crate::utils::error;
+
+const THP_ENABLED_PATH: &str = "/sys/kernel/mm/transparent_hugepage/enabled";
+
+pub fn get_hugepages_data(actually_get: bool) -> error::Result<Option<HugepagesHarvest>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    // Counts are in pages, and sizes are in KiB, like the rest of /proc/meminfo.
+    let mut hugepages = HugepagesHarvest::default();
+    for line in fs::read_to_string("/proc/meminfo")?.lines() {
+        let mut fields = line.split_whitespace();
+        let field = match fields.next() {
+            Some("HugePages_Total:") => &mut hugepages.total_pages,
+            Some("HugePages_Free:") => &mut hugepages.free_pages,
+            Some("HugePages_Rsvd:") => &mut hugepages.reserved_pages,
+            Some("HugePages_Surp:") => &mut hugepages.surplus_pages,
+            Some("Hugepagesize:") => &mut hugepages.page_size_in_kib,
+            Some("AnonHugePages:") => &mut hugepages.thp_anon_in_kib,
+            Some("ShmemHugePages:") => &mut hugepages.thp_shmem_in_kib,
+            Some("FileHugePages:") => &mut hugepages.thp_file_in_kib,
+            _ => continue,
+        };
+        if let Some(value) = fields.next().and_then(|value| value.parse().ok()) {
+            *field = value;
+        }
+    }
+
+    // The mode in use is the one in brackets, like "always [madvise] never".
+    hugepages.thp_mode = fs::read_to_string(THP_ENABLED_PATH).ok().and_then(|modes| {
+        let start = modes.find('[')?;
+        let end = modes[start..].find(']')?;
+        Some(modes[start + 1..start + end].to_string())
+    });
+
+    Ok(Some(hugepages))
+}
@@ -1,6 +1,8 @@
 //! Data collection for memory.
 //!
-//! For Linux, macOS, and Windows, this is handled by Heim.
+//! For Linux, macOS, and Windows, this is handled by Heim.  Per-NUMA-node memory is only
+//! supported on Linux, where it is read from `/sys/devices/system/node`, as is shared memory,
+//! tmpfs, and huge page usage.
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))] {
@@ -8,3 +10,88 @@ cfg_if::cfg_if! {
         pub use self::general::*;
     }
 }
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod numa;
+        pub use self::numa::*;
+    } else {
+        /// NUMA node information is not supported on this platform.
+        pub fn get_numa_data(
+            _actually_get: bool,
+        ) -> crate::utils::error::Result<Option<Vec<NumaHarvest>>> {
+            Ok(None)
+        }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod shmem;
+        pub use self::shmem::*;
+    } else {
+        /// Shared memory and tmpfs usage is not supported on this platform.
+        pub fn get_shmem_data(
+            _actually_get: bool,
+        ) -> crate::utils::error::Result<Option<ShmemHarvest>> {
+            Ok(None)
+        }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod hugepages;
+        pub use self::hugepages::*;
+    } else {
+        /// Huge page usage is not supported on this platform.
+        pub fn get_hugepages_data(
+            _actually_get: bool,
+        ) -> crate::utils::error::Result<Option<HugepagesHarvest>> {
+            Ok(None)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NumaHarvest {
+    pub node: u32,
+    pub mem_total_in_kib: u64,
+    pub mem_used_in_kib: u64,
+    pub free_pages: u64,
+    pub use_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ShmemHarvest {
+    /// Memory used by shared memory and tmpfs, as the kernel reports it.
+    pub shmem_in_kib: u64,
+    /// Each tmpfs mount, most used first.
+    pub tmpfs: Vec<TmpfsHarvest>,
+    /// The largest files in `/dev/shm` and how many bytes of memory each takes, largest first.
+    pub largest_shm_files: Vec<(String, u64)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TmpfsHarvest {
+    pub mount_point: String,
+    pub used_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HugepagesHarvest {
+    /// How many huge pages are set aside, how many aren't in use, how many are promised to
+    /// mappings but not yet used, and how many were allocated past the total on demand.
+    pub total_pages: u64,
+    pub free_pages: u64,
+    pub reserved_pages: u64,
+    pub surplus_pages: u64,
+    pub page_size_in_kib: u64,
+    /// Memory backed by transparent huge pages, by what it's used for.
+    pub thp_anon_in_kib: u64,
+    pub thp_shmem_in_kib: u64,
+    pub thp_file_in_kib: u64,
+    /// When transparent huge pages are used: "always", "madvise", or "never".
+    pub thp_mode: Option<String>,
+}
@@ -0,0 +1,97 @@
+//! Per-NUMA-node memory data collection for Linux.
+
+use std::fs;
+
+use super::NumaHarvest;
+use crate::utils::error;
+
+const SYS_NODE_PATH: &str = "/sys/devices/system/node";
+
+/// Returns the memory usage of each NUMA node.  Machines with a single node have nothing to
+/// break down, so nothing is returned for them.
+pub fn get_numa_data(actually_get: bool) -> error::Result<Option<Vec<NumaHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let mut nodes = fs::read_dir(SYS_NODE_PATH)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("node")?
+                .parse::<u32>()
+                .ok()
+        })
+        .collect::<Vec<_>>();
+
+    if nodes.len() < 2 {
+        return Ok(None);
+    }
+    nodes.sort_unstable();
+
+    Ok(Some(nodes.into_iter().filter_map(get_node_data).collect()))
+}
+
+fn get_node_data(node: u32) -> Option<NumaHarvest> {
+    // Lines look like "Node 0 MemTotal:       32768000 kB", and are all in KiB.
+    let meminfo = fs::read_to_string(format!("{}/node{}/meminfo", SYS_NODE_PATH, node)).ok()?;
+
+    let mut mem_total = 0;
+    let mut mem_free = 0;
+    let mut file_pages = 0;
+    let mut s_reclaimable = 0;
+    let mut shmem = 0;
+
+    for line in meminfo.lines() {
+        if let Some((label, value)) = line.split_once(':') {
+            let to_write = match label.split_whitespace().last() {
+                Some("MemTotal") => &mut mem_total,
+                Some("MemFree") => &mut mem_free,
+                Some("FilePages") => &mut file_pages,
+                Some("SReclaimable") => &mut s_reclaimable,
+                Some("Shmem") => &mut shmem,
+                _ => {
+                    continue;
+                }
+            };
+
+            if let Some(Ok(number)) = value.split_whitespace().next().map(str::parse::<u64>) {
+                *to_write = number;
+            }
+        }
+    }
+
+    // This mirrors the calculation for the system as a whole; the per-node files just don't
+    // split out buffers, which are instead counted as part of the file pages.
+    let cached_mem = (file_pages + s_reclaimable).saturating_sub(shmem);
+    let used_diff = mem_free + cached_mem;
+    let mem_used = if mem_total >= used_diff {
+        mem_total - used_diff
+    } else {
+        mem_total.saturating_sub(mem_free)
+    };
+
+    let free_pages = fs::read_to_string(format!("{}/node{}/vmstat", SYS_NODE_PATH, node))
+        .ok()
+        .and_then(|vmstat| {
+            vmstat.lines().find_map(|line| {
+                line.strip_prefix("nr_free_pages ")
+                    .and_then(|value| value.trim().parse::<u64>().ok())
+            })
+        })
+        .unwrap_or(0);
+
+    Some(NumaHarvest {
+        node,
+        mem_total_in_kib: mem_total,
+        mem_used_in_kib: mem_used,
+        free_pages,
+        use_percent: if mem_total == 0 {
+            None
+        } else {
+            Some(mem_used as f64 / mem_total as f64 * 100.0)
+        },
+    })
+}
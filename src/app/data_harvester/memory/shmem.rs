@@ -0,0 +1,115 @@
+//! Shared memory and tmpfs usage on Linux.  Files in tmpfs live in RAM (or swap), so a full
+//! `/dev/shm` or `/tmp` quietly eats into memory without showing up in any process' usage.
+
+use std::{ffi::CString, fs, os::unix::fs::MetadataExt};
+
+use super::{ShmemHarvest, TmpfsHarvest};
+use crate::utils::error;
+
+const SHM_PATH: &str = "/dev/shm";
+
+/// How many of the largest files in `/dev/shm` to keep.
+const LARGEST_SHM_FILE_COUNT: usize = 5;
+
+pub fn get_shmem_data(actually_get: bool) -> error::Result<Option<ShmemHarvest>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    // In KiB, like the rest of /proc/meminfo.
+    let shmem_in_kib = fs::read_to_string("/proc/meminfo")?
+        .lines()
+        .find_map(|line| line.strip_prefix("Shmem:"))
+        .and_then(|value| value.split_whitespace().next()?.parse().ok())
+        .unwrap_or(0);
+
+    // Lines look like "tmpfs /dev/shm tmpfs rw,nosuid,nodev 0 0".
+    let mut tmpfs = fs::read_to_string("/proc/mounts")?
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = fields.nth(1)?;
+            if fields.next()? == "tmpfs" {
+                get_tmpfs_usage(&unescape_mount_point(mount_point))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    tmpfs.sort_by(|a, b| b.used_bytes.cmp(&a.used_bytes));
+
+    // Count the blocks a file actually takes up rather than its length, as files in tmpfs can be
+    // sparse.  Files we can't read are skipped.
+    let mut largest_shm_files = fs::read_dir(SHM_PATH)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let metadata = entry.metadata().ok()?;
+                    if metadata.is_file() {
+                        Some((
+                            entry.file_name().to_string_lossy().into_owned(),
+                            metadata.blocks() * 512,
+                        ))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    largest_shm_files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    largest_shm_files.truncate(LARGEST_SHM_FILE_COUNT);
+
+    Ok(Some(ShmemHarvest {
+        shmem_in_kib,
+        tmpfs,
+        largest_shm_files,
+    }))
+}
+
+fn get_tmpfs_usage(mount_point: &str) -> Option<TmpfsHarvest> {
+    let path = CString::new(mount_point).ok()?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stats) } != 0 {
+        return None;
+    }
+
+    let block_size = stats.f_frsize as u64;
+    let total_bytes = stats.f_blocks as u64 * block_size;
+    if total_bytes == 0 {
+        return None;
+    }
+
+    Some(TmpfsHarvest {
+        mount_point: mount_point.to_string(),
+        used_bytes: (stats.f_blocks as u64).saturating_sub(stats.f_bfree as u64) * block_size,
+        total_bytes,
+    })
+}
+
+/// Mount points in `/proc/mounts` have spaces and other special characters escaped as octal,
+/// like `\040`.
+fn unescape_mount_point(mount_point: &str) -> String {
+    let mut unescaped = String::with_capacity(mount_point.len());
+    let mut rest = mount_point;
+    while let Some(index) = rest.find('\\') {
+        unescaped.push_str(&rest[..index]);
+        match rest
+            .get(index + 1..index + 4)
+            .and_then(|octal| u8::from_str_radix(octal, 8).ok())
+        {
+            Some(byte) => {
+                unescaped.push(byte as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                unescaped.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+
+    unescaped
+}
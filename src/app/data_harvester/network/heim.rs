@@ -1,8 +1,26 @@
 //! Gets network data via heim.
 
-use super::NetworkHarvest;
+use super::{InterfaceCounters, NetworkHarvest};
 use std::time::Instant;
 
+/// Collisions aren't reported by heim, so they're read from sysfs instead.
+#[cfg(target_os = "linux")]
+fn get_collisions(interface: &str) -> Option<u64> {
+    std::fs::read_to_string(format!(
+        "/sys/class/net/{}/statistics/collisions",
+        interface
+    ))
+    .ok()?
+    .trim()
+    .parse()
+    .ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_collisions(_interface: &str) -> Option<u64> {
+    None
+}
+
 // FIXME: Eventually make it so that this thing also takes individual usage into account, so we can allow for showing per-interface!
 pub async fn get_network_data(
     prev_net_access_time: Instant, prev_net_rx: &mut u64, prev_net_tx: &mut u64,
@@ -18,6 +36,7 @@ pub async fn get_network_data(
     futures::pin_mut!(io_data);
     let mut total_rx: u64 = 0;
     let mut total_tx: u64 = 0;
+    let mut interface_counters = Vec::new();
 
     while let Some(io) = io_data.next().await {
         if let Ok(io) = io {
@@ -45,6 +64,21 @@ pub async fn get_network_data(
                 // you only do one conversion...
                 total_rx += io.bytes_recv().get::<heim::units::information::bit>();
                 total_tx += io.bytes_sent().get::<heim::units::information::bit>();
+
+                #[cfg(target_os = "linux")]
+                let drops = {
+                    use heim::net::os::linux::IoCountersExt;
+                    io.drop_recv() + io.drop_sent()
+                };
+                #[cfg(not(target_os = "linux"))]
+                let drops = io.drop_recv();
+
+                interface_counters.push(InterfaceCounters {
+                    name: io.interface().to_string(),
+                    errors: io.errors_recv() + io.errors_sent(),
+                    drops: Some(drops),
+                    collisions: get_collisions(io.interface()),
+                });
             }
         }
     }
@@ -67,5 +101,9 @@ pub async fn get_network_data(
         tx,
         total_rx,
         total_tx,
+        interface_counters,
+        ipv4: None,
+        ipv6: None,
+        links: Vec::new(),
     }))
 }
@@ -2,6 +2,14 @@
 //!
 //! For Linux and macOS, this is handled by Heim.
 //! For Windows, this is handled by sysinfo.
+//!
+//! Error counters are read through the same, except for collisions, which are only read on Linux,
+//! from sysfs.  Windows only has errors, not drops.
+//!
+//! Traffic split by IP version is only read on Linux, from procfs.
+//!
+//! Link speed, duplex, and carrier are only read on Linux, from sysfs, which reports the same link
+//! settings as ethtool.
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "linux", target_os = "macos"))] {
@@ -20,6 +28,219 @@ pub struct NetworkHarvest {
     pub tx: u64,
     pub total_rx: u64,
     pub total_tx: u64,
+    /// Error counters for each interface, by name.
+    pub interface_counters: Vec<InterfaceCounters>,
+    /// Traffic over IPv4 and IPv6, if they're counted separately here.
+    pub ipv4: Option<IpTraffic>,
+    pub ipv6: Option<IpTraffic>,
+    /// The links of physical interfaces, by name.
+    pub links: Vec<InterfaceLink>,
+}
+
+/// Traffic over one IP version, counted across the whole system.  As it isn't counted per
+/// interface, the interface filter doesn't apply, and loopback traffic is included.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct IpTraffic {
+    pub rx: u64,
+    pub tx: u64,
+    pub total_rx: u64,
+    pub total_tx: u64,
+}
+
+impl IpTraffic {
+    /// Works out the rates since `prev`, the last reading.  Without one, the rates are zero.
+    fn new(total_rx: u64, total_tx: u64, prev: Option<IpTraffic>, elapsed_secs: f64) -> Self {
+        let (rx, tx) = match prev {
+            Some(prev) => (
+                get_rate(total_rx, prev.total_rx, elapsed_secs),
+                get_rate(total_tx, prev.total_tx, elapsed_secs),
+            ),
+            None => (0, 0),
+        };
+
+        IpTraffic {
+            rx,
+            tx,
+            total_rx,
+            total_tx,
+        }
+    }
+}
+
+/// Works out a rate from a counter's current and last totals.
+fn get_rate(total: u64, prev_total: u64, elapsed_secs: f64) -> u64 {
+    if elapsed_secs == 0.0 {
+        0
+    } else {
+        (total.saturating_sub(prev_total) as f64 / elapsed_secs) as u64
+    }
+}
+
+/// Reads the traffic over IPv4 and IPv6, with rates since the last readings, `prev_ipv4` and
+/// `prev_ipv6`.  Either is `None` if it isn't counted here, which includes IPv6 being turned off.
+#[cfg(target_os = "linux")]
+pub fn get_ip_traffic(
+    prev_ipv4: Option<IpTraffic>, prev_ipv6: Option<IpTraffic>, elapsed_secs: f64,
+) -> (Option<IpTraffic>, Option<IpTraffic>) {
+    // IPv4 octets are on the line after the `IpExt:` header, which names each column.
+    let ipv4 = std::fs::read_to_string("/proc/net/netstat")
+        .ok()
+        .and_then(|netstat| {
+            let mut ip_ext_lines = netstat.lines().filter(|line| line.starts_with("IpExt:"));
+            let (header, values) = (ip_ext_lines.next()?, ip_ext_lines.next()?);
+            let get_field = |name: &str| {
+                header
+                    .split_whitespace()
+                    .zip(values.split_whitespace())
+                    .find(|(field, _value)| *field == name)
+                    .and_then(|(_field, value)| value.parse::<u64>().ok())
+            };
+
+            Some((get_field("InOctets")?, get_field("OutOctets")?))
+        });
+
+    // Each IPv6 counter is on its own line, followed by its value.
+    let ipv6 = std::fs::read_to_string("/proc/net/snmp6")
+        .ok()
+        .and_then(|snmp6| {
+            let get_field = |name: &str| {
+                snmp6.lines().find_map(|line| {
+                    let mut parts = line.split_whitespace();
+                    if parts.next()? == name {
+                        parts.next()?.parse::<u64>().ok()
+                    } else {
+                        None
+                    }
+                })
+            };
+
+            Some((get_field("Ip6InOctets")?, get_field("Ip6OutOctets")?))
+        });
+
+    let to_traffic = |octets: Option<(u64, u64)>, prev: Option<IpTraffic>| {
+        octets.map(|(in_octets, out_octets)| {
+            IpTraffic::new(in_octets * 8, out_octets * 8, prev, elapsed_secs)
+        })
+    };
+
+    (to_traffic(ipv4, prev_ipv4), to_traffic(ipv6, prev_ipv6))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_ip_traffic(
+    _prev_ipv4: Option<IpTraffic>, _prev_ipv6: Option<IpTraffic>, _elapsed_secs: f64,
+) -> (Option<IpTraffic>, Option<IpTraffic>) {
+    (None, None)
+}
+
+/// A physical interface's link, as its driver reports it, and its traffic.  Drivers that don't know
+/// the link's speed or duplex, like most wireless ones, leave them as `None`.
+#[derive(Default, Clone, Debug)]
+pub struct InterfaceLink {
+    pub name: String,
+    /// In megabits per second.
+    pub speed_mbps: Option<u64>,
+    pub is_full_duplex: Option<bool>,
+    /// Whether there's a link at all, such as a cable being plugged in.
+    pub has_carrier: bool,
+    pub rx: u64,
+    pub tx: u64,
+    pub total_rx: u64,
+    pub total_tx: u64,
+}
+
+impl InterfaceLink {
+    /// How much of the link's speed the traffic each way is using, as percentages.  `None` if the
+    /// speed isn't known.
+    pub fn get_utilization(&self) -> Option<(f64, f64)> {
+        let speed_in_bits = self.speed_mbps.filter(|speed| *speed > 0)? as f64 * 1_000_000.0;
+        Some((
+            self.rx as f64 / speed_in_bits * 100.0,
+            self.tx as f64 / speed_in_bits * 100.0,
+        ))
+    }
+}
+
+/// Reads the links of whichever of `interface_names` are physical interfaces, with their traffic
+/// rates since the last readings, `prev_links`.  An interface without a previous reading has rates
+/// of zero.
+#[cfg(target_os = "linux")]
+pub fn get_interface_links<'a>(
+    interface_names: impl Iterator<Item = &'a str>, prev_links: &[InterfaceLink], elapsed_secs: f64,
+) -> Vec<InterfaceLink> {
+    let read_field = |interface: &str, field: &str| {
+        std::fs::read_to_string(format!("/sys/class/net/{}/{}", interface, field))
+            .ok()
+            .map(|value| value.trim().to_string())
+    };
+    let read_bits = |interface: &str, field: &str| {
+        read_field(interface, field)
+            .and_then(|bytes| bytes.parse::<u64>().ok())
+            .map(|bytes| bytes * 8)
+            .unwrap_or(0)
+    };
+
+    interface_names
+        // Only physical interfaces have a device behind them; virtual ones have no link to speak of.
+        .filter(|interface| {
+            std::path::Path::new(&format!("/sys/class/net/{}/device", interface)).exists()
+        })
+        .map(|interface| {
+            // Reading the speed, duplex, or carrier of an interface that's down fails, and the
+            // speed is -1 if the driver doesn't know it.
+            let has_carrier = read_field(interface, "carrier").as_deref() == Some("1");
+            let speed_mbps = read_field(interface, "speed")
+                .and_then(|speed| speed.parse::<u64>().ok())
+                .filter(|_| has_carrier);
+            let is_full_duplex = match read_field(interface, "duplex").as_deref() {
+                Some("full") if has_carrier => Some(true),
+                Some("half") if has_carrier => Some(false),
+                _ => None,
+            };
+            let total_rx = read_bits(interface, "statistics/rx_bytes");
+            let total_tx = read_bits(interface, "statistics/tx_bytes");
+
+            let (rx, tx) = match prev_links
+                .iter()
+                .find(|prev_link| prev_link.name == interface)
+            {
+                Some(prev_link) => (
+                    get_rate(total_rx, prev_link.total_rx, elapsed_secs),
+                    get_rate(total_tx, prev_link.total_tx, elapsed_secs),
+                ),
+                None => (0, 0),
+            };
+
+            InterfaceLink {
+                name: interface.to_string(),
+                speed_mbps,
+                is_full_duplex,
+                has_carrier,
+                rx,
+                tx,
+                total_rx,
+                total_tx,
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_interface_links<'a>(
+    _interface_names: impl Iterator<Item = &'a str>, _prev_links: &[InterfaceLink],
+    _elapsed_secs: f64,
+) -> Vec<InterfaceLink> {
+    Vec::new()
+}
+
+/// How many errors, dropped packets, and collisions an interface has had since it came up, in
+/// both directions.  Counters that can't be read on this platform are `None`.
+#[derive(Default, Clone, Debug)]
+pub struct InterfaceCounters {
+    pub name: String,
+    pub errors: u64,
+    pub drops: Option<u64>,
+    pub collisions: Option<u64>,
 }
 
 impl NetworkHarvest {
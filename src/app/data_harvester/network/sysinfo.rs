@@ -1,6 +1,6 @@
 //! Gets network data via sysinfo.
 
-use super::NetworkHarvest;
+use super::{InterfaceCounters, NetworkHarvest};
 use std::time::Instant;
 
 pub async fn get_network_data(
@@ -16,6 +16,7 @@ pub async fn get_network_data(
 
     let mut total_rx: u64 = 0;
     let mut total_tx: u64 = 0;
+    let mut interface_counters = Vec::new();
 
     let networks = sys.get_networks();
     for (name, network) in networks {
@@ -35,6 +36,13 @@ pub async fn get_network_data(
         if to_keep {
             total_rx += network.get_total_received() * 8;
             total_tx += network.get_total_transmitted() * 8;
+            interface_counters.push(InterfaceCounters {
+                name: name.to_string(),
+                errors: network.get_total_errors_on_received()
+                    + network.get_total_errors_on_transmitted(),
+                drops: None,
+                collisions: None,
+            });
         }
     }
 
@@ -56,5 +64,9 @@ pub async fn get_network_data(
         tx,
         total_rx,
         total_tx,
+        interface_counters,
+        ipv4: None,
+        ipv6: None,
+        links: Vec::new(),
     }))
 }
@@ -0,0 +1,22 @@
+//! Works out which data couldn't be read for lack of permissions, so widgets can say their data
+//! is partial rather than quietly showing zeros or leaving things out.
+
+/// Shown in a widget's title when some of its data couldn't be read.
+pub const PARTIAL_DATA_HINT: &str = "partial data, run with sudo/setcap";
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct RestrictedHarvest {
+    /// How many processes' I/O counters couldn't be read.
+    pub process_io_count: usize,
+    /// Whether `smartctl` couldn't open some drives to read their temperatures.
+    pub is_smart_restricted: bool,
+    /// Whether some of the CPU's energy counters couldn't be read.
+    pub is_power_restricted: bool,
+}
+
+/// Whether bottom is running as root, in which case nothing should be off limits.
+#[cfg(target_os = "linux")]
+pub fn is_elevated() -> bool {
+    // SAFETY: geteuid can't fail.
+    unsafe { libc::geteuid() == 0 }
+}
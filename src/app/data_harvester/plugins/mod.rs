@@ -0,0 +1,146 @@
+//! Data gathered from user-defined plugin commands.
+//!
+//! Each plugin is an external command that prints JSON to stdout every time it is run.  The
+//! output is either a single number, which becomes a series named after the plugin, or an object
+//! whose numeric fields each become a series named `<plugin>/<field>`.  Anything else is ignored.
+//!
+//! Plugins run in the background, so a slow one never holds up a harvest.  Each harvest uses what
+//! each plugin printed the last time it finished, and runs it again if it isn't still running.
+//!
+//! Only external commands are supported.  WASM modules would need a runtime embedded in bottom,
+//! which isn't worth its size for what a command can already do.
+
+use std::{
+    process::Command,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::command::output_with_timeout;
+
+/// How long a plugin command may run before it's killed, which counts as it failing.
+const PLUGIN_TIMEOUT: Duration = Duration::from_millis(1000);
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct PluginConfig {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PluginHarvest {
+    pub name: String,
+    pub value: f64,
+}
+
+#[derive(Debug)]
+struct PluginTask {
+    plugin: PluginConfig,
+    /// Gets the output of the run that's still going, if there is one.
+    running: Option<Receiver<Option<String>>>,
+    /// The output of the last run that finished, or `None` if it failed.
+    last_output: Option<String>,
+}
+
+/// Runs each plugin on its own thread, one run at a time.
+#[derive(Debug, Default)]
+pub struct PluginRunner {
+    tasks: Vec<PluginTask>,
+}
+
+impl PluginRunner {
+    pub fn new(plugins: Vec<PluginConfig>) -> Self {
+        PluginRunner {
+            tasks: plugins
+                .into_iter()
+                .map(|plugin| PluginTask {
+                    plugin,
+                    running: None,
+                    last_output: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns what the plugins printed the last time they finished, and starts any that aren't
+    /// still running.  Nothing has finished yet on the first call.
+    pub fn get_plugin_data(&mut self, actually_get: bool) -> Option<Vec<PluginHarvest>> {
+        if !actually_get || self.tasks.is_empty() {
+            return None;
+        }
+
+        let mut plugin_harvest = Vec::new();
+        for task in &mut self.tasks {
+            if let Some(running) = &task.running {
+                match running.try_recv() {
+                    Ok(output) => {
+                        task.last_output = output;
+                        task.running = None;
+                    }
+                    Err(TryRecvError::Disconnected) => {
+                        task.last_output = None;
+                        task.running = None;
+                    }
+                    Err(TryRecvError::Empty) => {}
+                }
+            }
+
+            if task.running.is_none() {
+                let (sender, receiver) = mpsc::channel();
+                let plugin = task.plugin.clone();
+                thread::spawn(move || {
+                    let _ = sender.send(run_plugin(&plugin));
+                });
+                task.running = Some(receiver);
+            }
+
+            if let Some(output) = &task.last_output {
+                parse_plugin_output(&task.plugin.name, output, &mut plugin_harvest);
+            }
+        }
+
+        Some(plugin_harvest)
+    }
+}
+
+fn run_plugin(plugin: &PluginConfig) -> Option<String> {
+    let output = output_with_timeout(
+        Command::new(&plugin.command).args(&plugin.args),
+        PLUGIN_TIMEOUT,
+    )?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout).ok()
+    } else {
+        None
+    }
+}
+
+fn parse_plugin_output(name: &str, output: &str, plugin_harvest: &mut Vec<PluginHarvest>) {
+    match serde_json::from_str::<serde_json::Value>(output) {
+        Ok(serde_json::Value::Number(value)) => {
+            if let Some(value) = value.as_f64() {
+                plugin_harvest.push(PluginHarvest {
+                    name: name.to_string(),
+                    value,
+                });
+            }
+        }
+        Ok(serde_json::Value::Object(fields)) => {
+            for (field, value) in fields {
+                if let Some(value) = value.as_f64() {
+                    plugin_harvest.push(PluginHarvest {
+                        name: format!("{}/{}", name, field),
+                        value,
+                    });
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,177 @@
+//! Looks up the hostnames of connections' remote addresses with reverse DNS.  A lookup can take
+//! seconds if a DNS server is slow or doesn't answer, so they're done on a few threads of their
+//! own, and each harvest uses whatever has been found so far.
+
+use std::{
+    ffi::CStr,
+    net::IpAddr,
+    sync::{
+        mpsc::{self, Receiver, SyncSender},
+        Arc, Mutex,
+    },
+    thread,
+    time::Instant,
+};
+
+use fxhash::{FxHashMap, FxHashSet};
+
+/// The most hostnames to keep around.  Once there are more, the ones that haven't been asked for
+/// in the longest are dropped, down to [`NUM_HOSTNAMES_AFTER_EVICTION`].
+const MAX_CACHED_HOSTNAMES: usize = 4096;
+
+/// How many hostnames are left after evicting, so it doesn't happen on every harvest.
+const NUM_HOSTNAMES_AFTER_EVICTION: usize = MAX_CACHED_HOSTNAMES * 3 / 4;
+
+/// How many lookups can run at once, so one slow lookup doesn't hold up the rest.
+const NUM_LOOKUP_THREADS: usize = 4;
+
+/// The most addresses that can wait to be looked up.  Past this, addresses are only queued once
+/// there's room again.
+const MAX_PENDING_LOOKUPS: usize = 256;
+
+/// Hostnames can be at most 253 characters, but this is the size glibc uses.
+const MAX_HOSTNAME_LEN: usize = 1025;
+
+#[derive(Debug)]
+struct CachedHostname {
+    /// `None` for addresses that have no hostname, or whose lookup failed.
+    hostname: Option<String>,
+    last_used: Instant,
+}
+
+/// The hostnames found so far, by address.  Dropping it stops the lookup threads.
+#[derive(Debug)]
+pub struct HostnameCache {
+    hostnames: FxHashMap<IpAddr, CachedHostname>,
+    /// Addresses that were sent off to be looked up, but haven't come back yet.
+    pending: FxHashSet<IpAddr>,
+    lookup_sender: SyncSender<IpAddr>,
+    result_receiver: Receiver<(IpAddr, Option<String>)>,
+}
+
+impl HostnameCache {
+    pub fn start() -> Self {
+        let (lookup_sender, lookup_receiver) = mpsc::sync_channel::<IpAddr>(MAX_PENDING_LOOKUPS);
+        let (result_sender, result_receiver) = mpsc::channel();
+        let lookup_receiver = Arc::new(Mutex::new(lookup_receiver));
+
+        for _ in 0..NUM_LOOKUP_THREADS {
+            let lookup_receiver = lookup_receiver.clone();
+            let result_sender = result_sender.clone();
+            thread::spawn(move || loop {
+                // Only hold the lock while waiting for an address, so the lookups run in parallel.
+                let address = match lookup_receiver.lock() {
+                    Ok(lookup_receiver) => match lookup_receiver.recv() {
+                        Ok(address) => address,
+                        Err(_) => break,
+                    },
+                    Err(_) => break,
+                };
+
+                if result_sender
+                    .send((address, lookup_hostname(address)))
+                    .is_err()
+                {
+                    break;
+                }
+            });
+        }
+
+        HostnameCache {
+            hostnames: FxHashMap::default(),
+            pending: FxHashSet::default(),
+            lookup_sender,
+            result_receiver,
+        }
+    }
+
+    /// Returns the hostname of `address` if it's been found.  If it hasn't been looked up yet,
+    /// it's sent off to be, if there's room in the queue.
+    pub fn get_hostname(&mut self, address: IpAddr) -> Option<String> {
+        let now = Instant::now();
+        for (address, hostname) in self.result_receiver.try_iter() {
+            self.pending.remove(&address);
+            self.hostnames.insert(
+                address,
+                CachedHostname {
+                    hostname,
+                    last_used: now,
+                },
+            );
+        }
+        if self.hostnames.len() > MAX_CACHED_HOSTNAMES {
+            self.evict_least_recently_used();
+        }
+
+        if let Some(cached_hostname) = self.hostnames.get_mut(&address) {
+            cached_hostname.last_used = now;
+            return cached_hostname.hostname.clone();
+        }
+        // If the queue is full, it'll be queued on a later harvest, once some lookups finish.
+        if !self.pending.contains(&address) && self.lookup_sender.try_send(address).is_ok() {
+            self.pending.insert(address);
+        }
+
+        None
+    }
+
+    /// Drops the hostnames that haven't been asked for in the longest.
+    fn evict_least_recently_used(&mut self) {
+        let mut by_last_used = self
+            .hostnames
+            .iter()
+            .map(|(address, cached_hostname)| (cached_hostname.last_used, *address))
+            .collect::<Vec<_>>();
+        by_last_used.sort_unstable();
+
+        let num_to_evict = self.hostnames.len() - NUM_HOSTNAMES_AFTER_EVICTION;
+        for (_, address) in &by_last_used[..num_to_evict] {
+            self.hostnames.remove(address);
+        }
+    }
+}
+
+/// Looks up the hostname of `address`, if it has one.
+fn lookup_hostname(address: IpAddr) -> Option<String> {
+    let mut hostname = [0 as libc::c_char; MAX_HOSTNAME_LEN];
+    let result = match address {
+        IpAddr::V4(address) => {
+            let mut socket_address: libc::sockaddr_in = unsafe { std::mem::zeroed() };
+            socket_address.sin_family = libc::AF_INET as libc::sa_family_t;
+            socket_address.sin_addr.s_addr = u32::from_ne_bytes(address.octets());
+            unsafe {
+                libc::getnameinfo(
+                    &socket_address as *const libc::sockaddr_in as *const libc::sockaddr,
+                    std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+                    hostname.as_mut_ptr(),
+                    hostname.len() as libc::socklen_t,
+                    std::ptr::null_mut(),
+                    0,
+                    libc::NI_NAMEREQD,
+                )
+            }
+        }
+        IpAddr::V6(address) => {
+            let mut socket_address: libc::sockaddr_in6 = unsafe { std::mem::zeroed() };
+            socket_address.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            socket_address.sin6_addr.s6_addr = address.octets();
+            unsafe {
+                libc::getnameinfo(
+                    &socket_address as *const libc::sockaddr_in6 as *const libc::sockaddr,
+                    std::mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t,
+                    hostname.as_mut_ptr(),
+                    hostname.len() as libc::socklen_t,
+                    std::ptr::null_mut(),
+                    0,
+                    libc::NI_NAMEREQD,
+                )
+            }
+        }
+    };
+    if result != 0 {
+        return None;
+    }
+
+    let hostname = unsafe { CStr::from_ptr(hostname.as_ptr()) };
+    Some(hostname.to_string_lossy().into_owned())
+}
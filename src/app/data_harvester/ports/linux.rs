@@ -0,0 +1,295 @@
+//! Listening ports and established connections on Linux.
+
+use std::{
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+use fxhash::{FxHashMap, FxHashSet};
+
+use super::{ConnectionHarvest, HostnameCache, PortHarvest, PortProtocol};
+use crate::{utils::error, Pid};
+
+/// How long to wait between walking every process' file descriptors to find the owners of new
+/// sockets.
+const OWNER_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The socket tables to read, their protocol, and the state a socket is in when it's waiting
+/// for connections.  Unconnected UDP sockets are reported as closed.
+const SOCKET_TABLES: [(&str, PortProtocol, &str); 4] = [
+    ("/proc/net/tcp", PortProtocol::Tcp, "0A"),
+    ("/proc/net/tcp6", PortProtocol::Tcp, "0A"),
+    ("/proc/net/udp", PortProtocol::Udp, "07"),
+    ("/proc/net/udp6", PortProtocol::Udp, "07"),
+];
+
+/// The TCP socket tables, for connections.
+const TCP_SOCKET_TABLES: [&str; 2] = ["/proc/net/tcp", "/proc/net/tcp6"];
+
+/// The state a TCP socket is in once it's connected.
+const ESTABLISHED_STATE: &str = "01";
+
+/// The owning process and its name of each socket seen so far, by inode.  Finding
+/// owners means walking every process' file descriptors, so it's only done when there's a
+/// socket with an unknown owner, and at most every so often.
+#[derive(Debug, Default)]
+pub struct PortOwners {
+    last_refresh: Option<Instant>,
+    owners: FxHashMap<u64, (Pid, Option<String>)>,
+}
+
+impl PortOwners {
+    /// Forgets sockets that are gone, and looks for the owners of new ones if it's time to.
+    fn update(&mut self, inodes: &FxHashSet<u64>) {
+        self.owners.retain(|inode, _| inodes.contains(inode));
+
+        let unknown_inodes = inodes
+            .iter()
+            .filter(|inode| !self.owners.contains_key(inode))
+            .copied()
+            .collect::<FxHashSet<_>>();
+        let is_stale = self
+            .last_refresh
+            .map(|last_refresh| last_refresh.elapsed() >= OWNER_REFRESH_INTERVAL)
+            .unwrap_or(true);
+
+        if !unknown_inodes.is_empty() && is_stale {
+            self.last_refresh = Some(Instant::now());
+
+            let mut process_names: FxHashMap<Pid, Option<String>> = FxHashMap::default();
+            for (inode, pid) in get_socket_owners(&unknown_inodes) {
+                let process_name = process_names
+                    .entry(pid)
+                    .or_insert_with(|| {
+                        std::fs::read_to_string(format!("/proc/{}/comm", pid))
+                            .ok()
+                            .map(|comm| comm.trim().to_string())
+                    })
+                    .clone();
+                self.owners.insert(inode, (pid, process_name));
+            }
+        }
+    }
+}
+
+pub fn get_port_data(
+    actually_get: bool, port_owners: &mut PortOwners,
+) -> error::Result<Option<Vec<PortHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let mut sockets: Vec<(PortProtocol, IpAddr, u16, u64)> = Vec::new();
+    for (path, protocol, listening_state) in &SOCKET_TABLES {
+        // IPv6 may be disabled, in which case its tables just don't exist.
+        if let Ok(table) = std::fs::read_to_string(path) {
+            sockets.extend(
+                table
+                    .lines()
+                    .skip(1)
+                    .filter_map(parse_socket_line)
+                    .filter(|socket| {
+                        // UDP sockets that are only used to send things are connected to a
+                        // remote port.
+                        socket.state == *listening_state
+                            && socket.remote_port == 0
+                            && socket.local_port != 0
+                    })
+                    .map(|socket| {
+                        (
+                            *protocol,
+                            socket.local_address,
+                            socket.local_port,
+                            socket.inode,
+                        )
+                    }),
+            );
+        }
+    }
+
+    port_owners.update(&sockets.iter().map(|socket| socket.3).collect());
+
+    let mut ports: Vec<PortHarvest> = sockets
+        .into_iter()
+        .map(|(protocol, address, port, inode)| {
+            let (pid, process_name) = match port_owners.owners.get(&inode) {
+                Some((pid, process_name)) => (Some(*pid), process_name.clone()),
+                None => (None, None),
+            };
+
+            PortHarvest {
+                protocol,
+                address,
+                port,
+                pid,
+                process_name,
+            }
+        })
+        .collect();
+
+    ports.sort_by(|a, b| {
+        (a.port, a.protocol, a.address, a.pid).cmp(&(b.port, b.protocol, b.address, b.pid))
+    });
+    // Processes with several workers on one port (like with SO_REUSEPORT) only need one row.
+    ports.dedup_by(|a, b| {
+        a.port == b.port && a.protocol == b.protocol && a.address == b.address && a.pid == b.pid
+    });
+
+    Ok(Some(ports))
+}
+
+pub fn get_connection_data(
+    actually_get: bool, connection_owners: &mut PortOwners,
+    mut hostname_cache: Option<&mut HostnameCache>,
+) -> error::Result<Option<Vec<ConnectionHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let mut sockets: Vec<SocketLine<'_>> = Vec::new();
+    let tables = TCP_SOCKET_TABLES
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .collect::<Vec<_>>();
+    for table in &tables {
+        sockets.extend(
+            table
+                .lines()
+                .skip(1)
+                .filter_map(parse_socket_line)
+                .filter(|socket| socket.state == ESTABLISHED_STATE),
+        );
+    }
+
+    connection_owners.update(&sockets.iter().map(|socket| socket.inode).collect());
+
+    let mut connections: Vec<ConnectionHarvest> = sockets
+        .into_iter()
+        .map(|socket| {
+            let (pid, process_name) = match connection_owners.owners.get(&socket.inode) {
+                Some((pid, process_name)) => (Some(*pid), process_name.clone()),
+                None => (None, None),
+            };
+            let remote_hostname = hostname_cache
+                .as_mut()
+                .and_then(|hostname_cache| hostname_cache.get_hostname(socket.remote_address));
+
+            ConnectionHarvest {
+                protocol: PortProtocol::Tcp,
+                local_address: socket.local_address,
+                local_port: socket.local_port,
+                remote_address: socket.remote_address,
+                remote_port: socket.remote_port,
+                remote_hostname,
+                pid,
+                process_name,
+            }
+        })
+        .collect();
+
+    connections.sort_by(|a, b| {
+        (a.remote_address, a.remote_port, a.local_port).cmp(&(
+            b.remote_address,
+            b.remote_port,
+            b.local_port,
+        ))
+    });
+
+    Ok(Some(connections))
+}
+
+/// A socket in a socket table.
+struct SocketLine<'a> {
+    local_address: IpAddr,
+    local_port: u16,
+    remote_address: IpAddr,
+    remote_port: u16,
+    state: &'a str,
+    inode: u64,
+}
+
+/// Parses a line of a socket table, which looks like:
+///
+/// `0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000 1000 0 12345 ...`
+fn parse_socket_line(line: &str) -> Option<SocketLine<'_>> {
+    let mut fields = line.split_whitespace().skip(1);
+    let (local_address, local_port) = fields.next()?.split_once(':')?;
+    let (remote_address, remote_port) = fields.next()?.split_once(':')?;
+    let state = fields.next()?;
+    let inode = fields.nth(5)?.parse::<u64>().ok()?;
+
+    // Inodes of 0 are sockets that are being torn down.
+    if inode == 0 {
+        return None;
+    }
+
+    Some(SocketLine {
+        local_address: parse_address(local_address)?,
+        local_port: u16::from_str_radix(local_port, 16).ok()?,
+        remote_address: parse_address(remote_address)?,
+        remote_port: u16::from_str_radix(remote_port, 16).ok()?,
+        state,
+        inode,
+    })
+}
+
+/// Addresses are written out as 32-bit words in the host's byte order, so converting each word
+/// back to native bytes gives the address in network order.
+fn parse_address(address: &str) -> Option<IpAddr> {
+    let mut bytes = Vec::with_capacity(16);
+    for word_start in (0..address.len()).step_by(8) {
+        let word = u32::from_str_radix(address.get(word_start..word_start + 8)?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+
+    match bytes.len() {
+        4 => {
+            let mut octets = [0; 4];
+            octets.copy_from_slice(&bytes);
+            Some(IpAddr::from(octets))
+        }
+        16 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(&bytes);
+            Some(IpAddr::from(octets))
+        }
+        _ => None,
+    }
+}
+
+/// Maps socket inodes to the process that has them open.  Processes we don't have permission to
+/// look into are skipped.
+fn get_socket_owners(inodes: &FxHashSet<u64>) -> FxHashMap<u64, Pid> {
+    let mut socket_owners = FxHashMap::default();
+    if inodes.is_empty() {
+        return socket_owners;
+    }
+
+    if let Ok(entries) = std::fs::read_dir("/proc") {
+        for entry in entries.flatten() {
+            let pid = match entry.file_name().to_string_lossy().parse::<Pid>() {
+                Ok(pid) => pid,
+                Err(_) => continue,
+            };
+
+            if let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) {
+                for fd in fds.flatten() {
+                    let inode = std::fs::read_link(fd.path()).ok().and_then(|target| {
+                        target
+                            .to_str()?
+                            .strip_prefix("socket:[")?
+                            .strip_suffix(']')?
+                            .parse::<u64>()
+                            .ok()
+                    });
+
+                    if let Some(inode) = inode.filter(|inode| inodes.contains(inode)) {
+                        socket_owners.entry(inode).or_insert(pid);
+                    }
+                }
+            }
+        }
+    }
+
+    socket_owners
+}
@@ -0,0 +1,106 @@
+//! Data collection for listening ports and established connections.
+//!
+//! For Linux, this is read from the socket tables in `/proc/net`, with the owning processes
+//! found by matching socket inodes against each process' file descriptors.  The owners are
+//! cached, so that walking the file descriptors is only done when new sockets show up.  The
+//! hostnames of connections' remote addresses can be looked up too, which is done on a separate
+//! thread and cached, as reverse DNS can take a while.
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod hostnames;
+        pub mod linux;
+        pub use self::hostnames::HostnameCache;
+        pub use self::linux::*;
+    } else {
+        #[derive(Debug, Default)]
+        pub struct PortOwners;
+
+        #[derive(Debug)]
+        pub struct HostnameCache;
+
+        impl HostnameCache {
+            pub fn start() -> Self {
+                HostnameCache
+            }
+        }
+
+        /// Listening ports are not supported on this platform.
+        pub fn get_port_data(
+            _actually_get: bool, _port_owners: &mut PortOwners,
+        ) -> crate::utils::error::Result<Option<Vec<PortHarvest>>> {
+            Ok(None)
+        }
+
+        /// Connections are not supported on this platform.
+        pub fn get_connection_data(
+            _actually_get: bool, _connection_owners: &mut PortOwners,
+            _hostname_cache: Option<&mut HostnameCache>,
+        ) -> crate::utils::error::Result<Option<Vec<ConnectionHarvest>>> {
+            Ok(None)
+        }
+    }
+}
+
+use std::net::IpAddr;
+
+use crate::Pid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PortProtocol {
+    Tcp,
+    Udp,
+}
+
+#[derive(Debug, Clone)]
+pub struct PortHarvest {
+    pub protocol: PortProtocol,
+    /// The local address the socket is bound to; unspecified if it's bound to all of them.
+    pub address: IpAddr,
+    pub port: u16,
+    /// The owning process, if it could be found.  Finding the owner of another user's socket
+    /// usually requires root.
+    pub pid: Option<Pid>,
+    pub process_name: Option<String>,
+}
+
+impl PortProtocol {
+    /// The protocol's name, like `tcp` or `udp6`.
+    fn get_name(self, is_ipv6: bool) -> &'static str {
+        match (self, is_ipv6) {
+            (PortProtocol::Tcp, false) => "tcp",
+            (PortProtocol::Tcp, true) => "tcp6",
+            (PortProtocol::Udp, false) => "udp",
+            (PortProtocol::Udp, true) => "udp6",
+        }
+    }
+}
+
+impl PortHarvest {
+    /// The protocol's name, like `tcp` or `udp6`.
+    pub fn protocol_name(&self) -> &'static str {
+        self.protocol.get_name(self.address.is_ipv6())
+    }
+}
+
+/// An established TCP connection.
+#[derive(Debug, Clone)]
+pub struct ConnectionHarvest {
+    pub protocol: PortProtocol,
+    pub local_address: IpAddr,
+    pub local_port: u16,
+    pub remote_address: IpAddr,
+    pub remote_port: u16,
+    /// The remote address' hostname, if looking them up is turned on and it's been found.
+    pub remote_hostname: Option<String>,
+    /// The owning process, if it could be found.
+    pub pid: Option<Pid>,
+    pub process_name: Option<String>,
+}
+
+impl ConnectionHarvest {
+    /// The protocol's name, like `tcp` or `tcp6`.
+    pub fn protocol_name(&self) -> &'static str {
+        self.protocol.get_name(self.local_address.is_ipv6())
+    }
+}
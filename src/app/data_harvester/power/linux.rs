@@ -0,0 +1,204 @@
+//! Power data collection for Linux.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use super::PowerHarvest;
+use crate::utils::error;
+
+const POWERCAP_PATH: &str = "/sys/class/powercap";
+const HWMON_PATH: &str = "/sys/class/hwmon";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PowerDomain {
+    Package,
+    Dram,
+}
+
+/// A counter of the energy a domain has used, in microjoules.
+#[derive(Debug)]
+struct EnergyCounter {
+    path: PathBuf,
+    domain: PowerDomain,
+    /// The value the counter wraps around at, if it does.
+    max_energy_uj: Option<u64>,
+}
+
+/// The energy counters as of the last read, which are needed to work out the power drawn since.
+#[derive(Debug, Default)]
+pub struct PrevEnergyCounts {
+    /// The counters found the last time they were looked for.  They're looked for again only if
+    /// one can't be read, like when a driver is reloaded.
+    counters: Option<Vec<EnergyCounter>>,
+    energy_uj: HashMap<PathBuf, u64>,
+    /// Whether some counters couldn't be read for lack of permissions.  Since the PLATYPUS attack,
+    /// RAPL counters can usually only be read by root.
+    pub is_restricted: bool,
+}
+
+/// Returns the power drawn since the last read.  There's nothing to compare against on the first
+/// read, or if there are no energy counters at all, so those return `None`.
+pub fn get_power_data(
+    prev_counts: &mut PrevEnergyCounts, time_difference_in_secs: f64, actually_get: bool,
+) -> error::Result<Option<PowerHarvest>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let counters = prev_counts
+        .counters
+        .take()
+        .unwrap_or_else(get_energy_counters);
+
+    let mut harvest = PowerHarvest::default();
+    let mut energy_uj = HashMap::new();
+    let mut is_restricted = false;
+    let mut is_stale = false;
+    for counter in &counters {
+        let energy = match fs::read_to_string(&counter.path) {
+            Ok(energy) => match energy.trim().parse::<u64>() {
+                Ok(energy) => energy,
+                Err(_) => continue,
+            },
+            Err(err) => {
+                // Lacking permissions won't change by looking for the counters again.
+                if err.kind() == ErrorKind::PermissionDenied {
+                    is_restricted = true;
+                } else {
+                    is_stale = true;
+                }
+                continue;
+            }
+        };
+
+        if let Some(prev_energy) = prev_counts.energy_uj.get(&counter.path) {
+            if time_difference_in_secs > 0.0 {
+                let energy_used = if energy >= *prev_energy {
+                    energy - prev_energy
+                } else {
+                    // The counter wrapped around since the last read.
+                    counter
+                        .max_energy_uj
+                        .map(|max_energy| max_energy.saturating_sub(*prev_energy) + energy)
+                        .unwrap_or(0)
+                };
+                let watts = energy_used as f64 / 1_000_000.0 / time_difference_in_secs;
+                let domain_watts = match counter.domain {
+                    PowerDomain::Package => &mut harvest.package_watts,
+                    PowerDomain::Dram => &mut harvest.dram_watts,
+                };
+                *domain_watts.get_or_insert(0.0) += watts;
+            }
+        }
+        energy_uj.insert(counter.path.clone(), energy);
+    }
+
+    if !is_stale {
+        prev_counts.counters = Some(counters);
+    }
+    prev_counts.energy_uj = energy_uj;
+    prev_counts.is_restricted = is_restricted;
+
+    if harvest.package_watts.is_none() && harvest.dram_watts.is_none() {
+        Ok(None)
+    } else {
+        Ok(Some(harvest))
+    }
+}
+
+/// Returns the RAPL counters, or the `amd_energy` driver's if RAPL has no package counters.
+fn get_energy_counters() -> Vec<EnergyCounter> {
+    let mut counters = get_rapl_counters();
+    if !counters
+        .iter()
+        .any(|counter| counter.domain == PowerDomain::Package)
+    {
+        counters.extend(get_amd_energy_counters());
+    }
+
+    counters
+}
+
+/// Returns the package and DRAM RAPL counters.  Each package's zone is at the top level, with the
+/// package's core, uncore, and DRAM counters as subzones; some servers put DRAM at the top level
+/// instead.  Both show up directly under the powercap class.
+fn get_rapl_counters() -> Vec<EnergyCounter> {
+    let entries = match fs::read_dir(POWERCAP_PATH) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            // Skip the MMIO interface, which some CPUs also provide, to not count packages twice.
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("intel-rapl:")
+        })
+        .filter_map(|entry| {
+            let zone_path = entry.path();
+            let name = read_trimmed(&zone_path.join("name"))?;
+            let domain = if name.starts_with("package") {
+                PowerDomain::Package
+            } else if name == "dram" {
+                PowerDomain::Dram
+            } else {
+                return None;
+            };
+
+            Some(EnergyCounter {
+                path: zone_path.join("energy_uj"),
+                domain,
+                max_energy_uj: read_trimmed(&zone_path.join("max_energy_range_uj"))
+                    .and_then(|max_energy| max_energy.parse().ok()),
+            })
+        })
+        .collect()
+}
+
+/// Returns the per-socket counters of the `amd_energy` driver.
+fn get_amd_energy_counters() -> Vec<EnergyCounter> {
+    let entries = match fs::read_dir(HWMON_PATH) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut counters = Vec::new();
+    for hwmon_path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if read_trimmed(&hwmon_path.join("name")).as_deref() != Some("amd_energy") {
+            continue;
+        }
+
+        // Sockets are labelled like "Esocket0", and cores like "Ecore000".
+        for index in 1.. {
+            let label = match read_trimmed(&hwmon_path.join(format!("energy{}_label", index))) {
+                Some(label) => label,
+                None => break,
+            };
+            if label.starts_with("Esocket") {
+                counters.push(EnergyCounter {
+                    path: hwmon_path.join(format!("energy{}_input", index)),
+                    domain: PowerDomain::Package,
+                    max_energy_uj: None,
+                });
+            }
+        }
+    }
+
+    counters
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+}
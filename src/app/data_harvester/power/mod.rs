@@ -0,0 +1,20 @@
+//! Data collection for power consumption, from the CPU's energy counters.
+//!
+//! For Linux, this is read from Intel's RAPL (Running Average Power Limit) counters, which recent
+//! AMD CPUs also provide, under `/sys/class/powercap`.  Older AMD CPUs are read through the
+//! `amd_energy` hwmon driver instead.
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct PowerHarvest {
+    /// The power drawn by every CPU package, in watts.
+    pub package_watts: Option<f64>,
+    /// The power drawn by memory, in watts.  Not every CPU reports this.
+    pub dram_watts: Option<f64>,
+}
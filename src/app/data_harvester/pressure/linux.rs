@@ -0,0 +1,35 @@
+//! Pressure stall information collection for Linux.
+
+use std::fs;
+
+use super::PressureHarvest;
+use crate::utils::error;
+
+const CPU_PRESSURE_PATH: &str = "/proc/pressure/cpu";
+const MEMORY_PRESSURE_PATH: &str = "/proc/pressure/memory";
+const IO_PRESSURE_PATH: &str = "/proc/pressure/io";
+
+pub fn get_pressure_data(actually_get: bool) -> error::Result<Option<PressureHarvest>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    Ok(Some(PressureHarvest {
+        cpu: get_some_avg10(CPU_PRESSURE_PATH),
+        memory: get_some_avg10(MEMORY_PRESSURE_PATH),
+        io: get_some_avg10(IO_PRESSURE_PATH),
+    }))
+}
+
+/// Reads the 10 second average from the "some" line, which looks like
+/// `some avg10=1.23 avg60=0.45 avg300=0.10 total=123456`.
+fn get_some_avg10(path: &str) -> Option<f64> {
+    let pressure = fs::read_to_string(path).ok()?;
+    let some_line = pressure.lines().find(|line| line.starts_with("some "))?;
+
+    some_line
+        .split_whitespace()
+        .find_map(|field| field.strip_prefix("avg10="))?
+        .parse()
+        .ok()
+}
@@ -0,0 +1,40 @@
+//! Data collection for pressure stall information (PSI), which is how much of the time tasks
+//! were held up waiting on the CPU, memory, or I/O.
+//!
+//! For Linux, this is read from `/proc/pressure`.  It needs a kernel built with PSI support.
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else {
+        /// Pressure stall information is not supported on this platform.
+        pub fn get_pressure_data(
+            _actually_get: bool,
+        ) -> crate::utils::error::Result<Option<PressureHarvest>> {
+            Ok(None)
+        }
+    }
+}
+
+/// The percentage of the last 10 seconds that at least some tasks were stalled on each resource,
+/// if it could be read.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct PressureHarvest {
+    pub cpu: Option<f64>,
+    pub memory: Option<f64>,
+    pub io: Option<f64>,
+}
+
+impl PressureHarvest {
+    /// Returns the worst of the stalls, if any could be read.
+    pub fn get_max(&self) -> Option<f64> {
+        [self.cpu, self.memory, self.io]
+            .iter()
+            .flatten()
+            .fold(None, |max: Option<f64>, stall| match max {
+                Some(max) if max >= *stall => Some(max),
+                _ => Some(*stall),
+            })
+    }
+}
@@ -0,0 +1,96 @@
+//! Open file descriptor counts of processes.
+//!
+//! On Linux, counting a process' descriptors means listing `/proc/<PID>/fd`, and on Windows it
+//! means opening a handle to every process.  Neither is free when done for every process, so the
+//! counts are only collected while the column is shown, and are cached and refreshed every so
+//! often rather than on every update.
+
+use std::time::{Duration, Instant};
+
+use fxhash::FxHashMap;
+
+use super::ProcessHarvest;
+use crate::Pid;
+
+/// How long to wait between refreshing the counts.
+const FD_COUNT_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The last open file descriptor counts, by PID.
+#[derive(Debug, Default)]
+pub struct FdCounter {
+    last_refresh: Option<Instant>,
+    counts: FxHashMap<Pid, u64>,
+}
+
+impl FdCounter {
+    /// Fills in the open file descriptor count of each process, refreshing the counts first if
+    /// they're stale.  Processes that started since the last refresh are counted right away.
+    pub fn fill_process_fd_counts(&mut self, process_list: &mut [ProcessHarvest]) {
+        if self
+            .last_refresh
+            .map(|last_refresh| last_refresh.elapsed() >= FD_COUNT_REFRESH_INTERVAL)
+            .unwrap_or(true)
+        {
+            self.last_refresh = Some(Instant::now());
+            self.counts.clear();
+        }
+
+        for process in process_list {
+            process.fd_count = match self.counts.get(&process.pid) {
+                Some(count) => Some(*count),
+                None => {
+                    let count = count_fds(process.pid);
+                    if let Some(count) = count {
+                        self.counts.insert(process.pid, count);
+                    }
+                    count
+                }
+            };
+        }
+    }
+}
+
+/// Returns how many file descriptors a process has open.  This usually needs the same
+/// permissions as reading the process' memory, so it fails for other users' processes.
+#[cfg(target_os = "linux")]
+fn count_fds(pid: Pid) -> Option<u64> {
+    std::fs::read_dir(format!("/proc/{}/fd", pid))
+        .ok()
+        .map(|entries| entries.count() as u64)
+}
+
+/// Returns how many handles a process has open.
+#[cfg(target_os = "windows")]
+fn count_fds(pid: Pid) -> Option<u64> {
+    use winapi::{
+        shared::minwindef::{DWORD, FALSE},
+        um::{
+            handleapi::CloseHandle,
+            processthreadsapi::{GetProcessHandleCount, OpenProcess},
+            winnt::PROCESS_QUERY_LIMITED_INFORMATION,
+        },
+    };
+
+    // Safety: the handle is checked before use, and closed before returning.
+    unsafe {
+        let process_handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, pid as DWORD);
+        if process_handle.is_null() {
+            return None;
+        }
+
+        let mut handle_count: DWORD = 0;
+        let result = GetProcessHandleCount(process_handle, &mut handle_count);
+        CloseHandle(process_handle);
+
+        if result != 0 {
+            Some(u64::from(handle_count))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn count_fds(_pid: Pid) -> Option<u64> {
+    None
+}
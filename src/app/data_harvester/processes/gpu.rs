@@ -0,0 +1,147 @@
+//! Per-process GPU usage.
+//!
+//! This relies on `nvidia-smi pmon`, which ships with the NVIDIA driver on both Linux and
+//! Windows; other vendors aren't supported yet.  Note that `pmon` takes a sample over roughly a
+//! second, so it's run on its own thread, and each harvest uses the latest sample.  This is only
+//! done if the GPU columns are enabled.
+
+use std::{
+    io,
+    process::Command,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use fxhash::FxHashMap;
+
+use super::ProcessHarvest;
+use crate::Pid;
+
+/// How long to wait between samples, on top of the second each one takes.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GpuProcessUsage {
+    pub util_percent: Option<f64>,
+    pub mem_bytes: Option<u64>,
+}
+
+/// Samples per-process GPU usage in the background.  Dropping it stops sampling.
+#[derive(Debug)]
+pub struct GpuMonitor {
+    usage: Arc<Mutex<Option<FxHashMap<Pid, GpuProcessUsage>>>>,
+    is_stopped: Arc<AtomicBool>,
+}
+
+impl GpuMonitor {
+    pub fn start() -> Self {
+        let usage = Arc::new(Mutex::new(None));
+        let is_stopped = Arc::new(AtomicBool::new(false));
+
+        let thread_usage = usage.clone();
+        let thread_is_stopped = is_stopped.clone();
+        thread::spawn(move || {
+            while !thread_is_stopped.load(Ordering::Relaxed) {
+                match get_gpu_process_usage() {
+                    Ok(sample) => {
+                        if let Ok(mut usage) = thread_usage.lock() {
+                            *usage = sample;
+                        }
+                    }
+                    // There's no point trying again if nvidia-smi isn't installed.
+                    Err(_) => break,
+                }
+                thread::sleep(SAMPLE_INTERVAL);
+            }
+        });
+
+        GpuMonitor { usage, is_stopped }
+    }
+
+    /// Fills in the GPU usage of each process from the latest sample, if there is one.
+    pub fn fill_process_usage(&self, process_list: &mut [ProcessHarvest]) {
+        if let Ok(usage) = self.usage.lock() {
+            if let Some(gpu_usage) = usage.as_ref() {
+                for process in process_list {
+                    if let Some(usage) = gpu_usage.get(&process.pid) {
+                        process.gpu_util_percent = usage.util_percent;
+                        process.gpu_mem_bytes = usage.mem_bytes;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Drop for GpuMonitor {
+    fn drop(&mut self) {
+        self.is_stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Takes one sample, which blocks for about a second.  This fails if `nvidia-smi` couldn't be
+/// run at all, and returns `None` if it ran but couldn't sample, like when no GPU is found.
+fn get_gpu_process_usage() -> io::Result<Option<FxHashMap<Pid, GpuProcessUsage>>> {
+    let output = Command::new("nvidia-smi")
+        .args(&["pmon", "-c", "1", "-s", "um"])
+        .output()?;
+
+    if output.status.success() {
+        Ok(Some(parse_pmon_output(&String::from_utf8_lossy(
+            &output.stdout,
+        ))))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Parses `pmon` output, which looks something like:
+///
+/// ```text
+/// # gpu        pid  type    sm   mem   enc   dec    fb   command
+/// # Idx          #   C/G     %     %     %     %    MB   name
+///     0       1234     G     3     1     -     -    45   Xorg
+/// ```
+///
+/// Usage is summed up if a process is using more than one GPU.
+fn parse_pmon_output(output: &str) -> FxHashMap<Pid, GpuProcessUsage> {
+    let mut gpu_usage: FxHashMap<Pid, GpuProcessUsage> = FxHashMap::default();
+    let mut columns: Vec<&str> = Vec::new();
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('#') {
+            // Only the first header line has the column names.
+            if columns.is_empty() {
+                columns = header.split_whitespace().collect();
+            }
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let get_field = |name: &str| {
+            columns
+                .iter()
+                .position(|column| *column == name)
+                .and_then(|index| fields.get(index))
+                .filter(|field| **field != "-")
+        };
+
+        if let Some(pid) = get_field("pid").and_then(|pid| pid.parse::<Pid>().ok()) {
+            let usage = gpu_usage.entry(pid).or_default();
+
+            if let Some(sm) = get_field("sm").and_then(|sm| sm.parse::<f64>().ok()) {
+                usage.util_percent = Some(usage.util_percent.unwrap_or(0.0) + sm);
+            }
+            if let Some(fb) = get_field("fb").and_then(|fb| fb.parse::<u64>().ok()) {
+                usage.mem_bytes = Some(usage.mem_bytes.unwrap_or(0) + fb * 1024 * 1024);
+            }
+        }
+    }
+
+    gpu_usage
+}
@@ -0,0 +1,90 @@
+//! Kubernetes pods of processes.
+//!
+//! The kubelet places each container's processes in a cgroup nested under its pod's cgroup,
+//! which is named after the pod's UID.  It also creates a `<namespace>_<name>_<uid>` directory
+//! under `/var/log/pods` for every pod on the node, which is what maps those UIDs back to names.
+
+use fxhash::FxHashMap;
+
+use super::ProcessHarvest;
+use crate::Pid;
+
+const POD_LOG_DIR: &str = "/var/log/pods";
+
+/// The length of a pod UID, which is a hyphenated UUID.
+const POD_UID_LEN: usize = 36;
+
+/// Fills in the pod name and namespace of each process running in a pod.  If a pod's UID can't
+/// be mapped back to its name, the UID is used instead and the namespace is left empty.
+pub fn fill_process_pods(process_list: &mut [ProcessHarvest]) {
+    let pod_names = get_pod_names();
+
+    for process in process_list {
+        if let Some(pod_uid) = get_pod_uid(process.pid) {
+            if let Some((namespace, name)) = pod_names.get(&pod_uid) {
+                process.pod_name = Some(name.clone());
+                process.pod_namespace = Some(namespace.clone());
+            } else {
+                process.pod_name = Some(pod_uid);
+            }
+        }
+    }
+}
+
+/// Returns a mapping of pod UIDs to their namespace and name.
+fn get_pod_names() -> FxHashMap<String, (String, String)> {
+    let mut pod_names = FxHashMap::default();
+
+    if let Ok(entries) = std::fs::read_dir(POD_LOG_DIR) {
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+
+            // Namespaces and pod names can't contain underscores, so this split is unambiguous.
+            let mut split = file_name.splitn(3, '_');
+            if let (Some(namespace), Some(name), Some(uid)) =
+                (split.next(), split.next(), split.next())
+            {
+                pod_names.insert(uid.to_string(), (namespace.to_string(), name.to_string()));
+            }
+        }
+    }
+
+    pod_names
+}
+
+/// Returns the UID of the pod a process is running in, if any.
+fn get_pod_uid(pid: Pid) -> Option<String> {
+    let cgroups = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+
+    // Each line looks like `<hierarchy id>:<controllers>:<path>`.
+    cgroups.lines().find_map(|line| {
+        let path = line.splitn(3, ':').nth(2)?;
+        if path.contains("kubepods") {
+            path.split('/').find_map(parse_pod_uid)
+        } else {
+            None
+        }
+    })
+}
+
+/// Parses a pod UID out of a single component of a cgroup path.  With the cgroupfs driver this
+/// looks like `pod<uid>`, and with the systemd driver it looks like
+/// `kubepods-burstable-pod<uid>.slice`, where the UID's hyphens are swapped for underscores.
+fn parse_pod_uid(component: &str) -> Option<String> {
+    let component = component.trim_end_matches(".slice");
+    let uid = if let Some(uid) = component.strip_prefix("pod") {
+        uid
+    } else {
+        &component[component.rfind("-pod")? + "-pod".len()..]
+    };
+
+    if uid.len() == POD_UID_LEN
+        && uid
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() || c == '-' || c == '_')
+    {
+        Some(uid.replace('_', "-"))
+    } else {
+        None
+    }
+}
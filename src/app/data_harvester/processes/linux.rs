@@ -1,15 +1,18 @@
 //! Process data collection for Linux.
 
-use std::collections::hash_map::Entry;
+use std::{collections::hash_map::Entry, convert::TryFrom, sync::Arc};
 
 use crate::utils::error::{self, BottomError};
 use crate::Pid;
 
-use super::ProcessHarvest;
+use super::{ProcessHarvest, StringInterner};
 
 use sysinfo::ProcessStatus;
 
-use procfs::process::{Process, Stat};
+use procfs::{
+    process::{Process, Stat},
+    ProcError,
+};
 
 use fxhash::{FxHashMap, FxHashSet};
 
@@ -23,6 +26,15 @@ pub struct PrevProcDetails {
     pub total_write_bytes: u64,
     pub cpu_time: u64,
     pub process: Process,
+    /// The name and command of the process, read once rather than every harvest.
+    pub name: Arc<str>,
+    pub command: Arc<str>,
+    /// The path of the process' executable, also read once.
+    pub exe_path: Option<Arc<str>>,
+    /// The process name from `stat` that the name, command, and executable's path were read
+    /// with.  These only change if the process execs something else, which also changes its name,
+    /// so they're read again then.
+    pub comm: Option<String>,
 }
 
 impl PrevProcDetails {
@@ -32,6 +44,10 @@ impl PrevProcDetails {
             total_write_bytes: 0,
             cpu_time: 0,
             process: Process::new(pid)?,
+            name: Arc::from(""),
+            command: Arc::from(""),
+            exe_path: None,
+            comm: None,
         })
     }
 }
@@ -98,11 +114,16 @@ fn cpu_usage_calculation(
 /// Returns the usage and a new set of process times. Note: cpu_fraction should be represented WITHOUT the x100 factor!
 fn get_linux_cpu_usage(
     stat: &Stat, cpu_usage: f64, cpu_fraction: f64, prev_proc_times: u64,
-    use_current_cpu_total: bool,
+    use_current_cpu_total: bool, include_child_cpu_time: bool,
 ) -> (f64, u64) {
     // Based heavily on https://stackoverflow.com/a/23376195 and https://stackoverflow.com/a/1424556
-    let new_proc_times = stat.utime + stat.stime;
-    let diff = (new_proc_times - prev_proc_times) as f64; // I HATE that it's done like this but there isn't a try_from for u64 -> f64... we can accept a bit of loss in the worst case though
+    let mut new_proc_times = stat.utime + stat.stime;
+    if include_child_cpu_time {
+        // Children's time is only added once they've exited and been waited for.
+        new_proc_times +=
+            u64::try_from(stat.cutime).unwrap_or(0) + u64::try_from(stat.cstime).unwrap_or(0);
+    }
+    let diff = new_proc_times.saturating_sub(prev_proc_times) as f64; // I HATE that it's done like this but there isn't a try_from for u64 -> f64... we can accept a bit of loss in the worst case though
 
     if cpu_usage == 0.0 {
         (0.0, new_proc_times)
@@ -113,44 +134,55 @@ fn get_linux_cpu_usage(
     }
 }
 
+/// Reads the name and command of a process, given its name from `stat`.
+fn read_name_and_command(
+    process: &Process, truncated_name: &str, string_interner: &mut StringInterner,
+) -> (Arc<str>, Arc<str>) {
+    if let Ok(cmdline) = process.cmdline() {
+        if cmdline.is_empty() {
+            (
+                string_interner.intern(truncated_name),
+                string_interner.intern(&format!("[{}]", truncated_name)),
+            )
+        } else {
+            let name = if truncated_name.len() >= MAX_STAT_NAME_LEN {
+                if let Some(first_part) = cmdline.first() {
+                    // We're only interested in the executable part... not the file path.
+                    // That's for command.
+                    first_part
+                        .rsplit_once('/')
+                        .map(|(_prefix, suffix)| suffix)
+                        .unwrap_or(truncated_name)
+                } else {
+                    truncated_name
+                }
+            } else {
+                truncated_name
+            };
+
+            (
+                string_interner.intern(name),
+                string_interner.intern(&cmdline.join(" ")),
+            )
+        }
+    } else {
+        let name = string_interner.intern(truncated_name);
+        (name.clone(), name)
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn read_proc(
     prev_proc: &PrevProcDetails, stat: &Stat, cpu_usage: f64, cpu_fraction: f64,
-    use_current_cpu_total: bool, time_difference_in_secs: u64, mem_total_kb: u64,
+    use_current_cpu_total: bool, include_child_cpu_time: bool, time_difference_in_secs: u64,
+    mem_total_kb: u64, boot_time_secs: Option<u64>, collect_status: bool,
 ) -> error::Result<(ProcessHarvest, u64)> {
-    use std::convert::TryFrom;
-
     let process = &prev_proc.process;
 
-    let (command, name) = {
-        let truncated_name = stat.comm.as_str();
-        if let Ok(cmdline) = process.cmdline() {
-            if cmdline.is_empty() {
-                (format!("[{}]", truncated_name), truncated_name.to_string())
-            } else {
-                (
-                    cmdline.join(" "),
-                    if truncated_name.len() >= MAX_STAT_NAME_LEN {
-                        if let Some(first_part) = cmdline.first() {
-                            // We're only interested in the executable part... not the file path.
-                            // That's for command.
-                            first_part
-                                .rsplit_once('/')
-                                .map(|(_prefix, suffix)| suffix)
-                                .unwrap_or(&truncated_name)
-                                .to_string()
-                        } else {
-                            truncated_name.to_string()
-                        }
-                    } else {
-                        truncated_name.to_string()
-                    },
-                )
-            }
-        } else {
-            (truncated_name.to_string(), truncated_name.to_string())
-        }
-    };
+    let ticks_per_second = procfs::ticks_per_second()
+        .ok()
+        .filter(|ticks_per_second| *ticks_per_second > 0)
+        .map(|ticks_per_second| ticks_per_second as u64);
 
     let process_state_char = stat.state;
     let process_state = ProcessStatus::from(process_state_char).to_string();
@@ -160,16 +192,23 @@ fn read_proc(
         cpu_fraction,
         prev_proc.cpu_time,
         use_current_cpu_total,
+        include_child_cpu_time,
     );
+    // Process times are in clock ticks.
+    let cpu_time_delta_ms = ticks_per_second.map(|ticks_per_second| {
+        new_process_times.saturating_sub(prev_proc.cpu_time) * 1000 / ticks_per_second
+    });
     let parent_pid = Some(stat.ppid);
     let mem_usage_bytes = u64::try_from(stat.rss_bytes()).unwrap_or(0);
     let mem_usage_kb = mem_usage_bytes / 1024;
     let mem_usage_percent = mem_usage_kb as f64 / mem_total_kb as f64 * 100.0;
+    let virt_mem_bytes = stat.vsize;
 
-    // This can fail if permission is denied!
-
+    // This can fail if permission is denied, which is noted so it isn't mistaken for no I/O.
+    let io = process.io();
+    let is_io_restricted = matches!(io, Err(ProcError::PermissionDenied(_)));
     let (total_read_bytes, total_write_bytes, read_bytes_per_sec, write_bytes_per_sec) =
-        if let Ok(io) = process.io() {
+        if let Ok(io) = io {
             let total_read_bytes = io.read_bytes;
             let total_write_bytes = io.write_bytes;
 
@@ -198,36 +237,74 @@ fn read_proc(
 
     let uid = Some(process.owner);
 
+    // Only the swap, capabilities, and seccomp columns need the status file, so skip reading it
+    // for every process if none of them are shown.
+    let status = if collect_status {
+        process.status().ok()
+    } else {
+        None
+    };
+    // VmSwap is reported in kB.  Kernel threads don't have it, nor can we read it without permission.
+    let swap_bytes = status
+        .as_ref()
+        .and_then(|status| status.vmswap)
+        .map(|vmswap| vmswap * 1024);
+    let cap_eff = status.as_ref().map(|status| status.capeff);
+    let seccomp_mode = status.as_ref().and_then(|status| status.seccomp);
+
+    // The start time is given in clock ticks since boot.
+    let start_time = boot_time_secs.and_then(|boot_time_secs| {
+        ticks_per_second.map(|ticks_per_second| boot_time_secs + stat.starttime / ticks_per_second)
+    });
+
     Ok((
         ProcessHarvest {
             pid: process.pid,
             parent_pid,
             cpu_usage_percent,
+            cpu_time_delta_ms,
             mem_usage_percent,
             mem_usage_bytes,
-            name,
-            command,
+            virt_mem_bytes,
+            name: prev_proc.name.clone(),
+            command: prev_proc.command.clone(),
+            exe_path: prev_proc.exe_path.clone(),
             read_bytes_per_sec,
             write_bytes_per_sec,
             total_read_bytes,
             total_write_bytes,
             process_state,
             process_state_char,
+            gpu_util_percent: None,
+            gpu_mem_bytes: None,
+            swap_bytes,
+            tcp_retransmits: None,
+            tcp_rtt_us: None,
+            pod_name: None,
+            pod_namespace: None,
+            start_time,
+            fd_count: None,
+            cap_eff,
+            seccomp_mode,
+            is_io_restricted,
             uid,
         },
         new_process_times,
     ))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn get_process_data(
     prev_idle: &mut f64, prev_non_idle: &mut f64,
     pid_mapping: &mut FxHashMap<Pid, PrevProcDetails>, use_current_cpu_total: bool,
-    time_difference_in_secs: u64, mem_total_kb: u64,
+    include_child_cpu_time: bool, time_difference_in_secs: u64, mem_total_kb: u64,
+    collect_status: bool, string_interner: &mut StringInterner,
 ) -> crate::utils::error::Result<Vec<ProcessHarvest>> {
     // TODO: [PROC THREADS] Add threads
 
     if let Ok((cpu_usage, cpu_fraction)) = cpu_usage_calculation(prev_idle, prev_non_idle) {
         let mut pids_to_clear: FxHashSet<Pid> = pid_mapping.keys().cloned().collect();
+        let boot_time_secs = procfs::boot_time_secs().ok();
 
         let process_vector: Vec<ProcessHarvest> = std::fs::read_dir("/proc")?
             .filter_map(|dir| {
@@ -245,26 +322,41 @@ pub fn get_process_data(
                         };
 
                         if let Some(prev_proc_details) = pid_mapping.get_mut(&pid) {
-                            let stat;
-                            let stat_live;
-                            if fresh {
-                                stat = &prev_proc_details.process.stat;
-                            } else if let Ok(s) = prev_proc_details.process.stat() {
-                                stat_live = s;
-                                stat = &stat_live;
+                            let stat = if fresh {
+                                prev_proc_details.process.stat.clone()
+                            } else if let Ok(stat) = prev_proc_details.process.stat() {
+                                stat
                             } else {
                                 // Bail early.
                                 return None;
+                            };
+
+                            if prev_proc_details.comm.as_deref() != Some(stat.comm.as_str()) {
+                                let (name, command) = read_name_and_command(
+                                    &prev_proc_details.process,
+                                    &stat.comm,
+                                    string_interner,
+                                );
+                                prev_proc_details.name = name;
+                                prev_proc_details.command = command;
+                                prev_proc_details.exe_path =
+                                    prev_proc_details.process.exe().ok().map(|exe_path| {
+                                        string_interner.intern(&exe_path.to_string_lossy())
+                                    });
+                                prev_proc_details.comm = Some(stat.comm.clone());
                             }
 
                             if let Ok((process_harvest, new_process_times)) = read_proc(
                                 &prev_proc_details,
-                                stat,
+                                &stat,
                                 cpu_usage,
                                 cpu_fraction,
                                 use_current_cpu_total,
+                                include_child_cpu_time,
                                 time_difference_in_secs,
                                 mem_total_kb,
+                                boot_time_secs,
+                                collect_status,
                             ) {
                                 prev_proc_details.cpu_time = new_process_times;
                                 prev_proc_details.total_read_bytes =
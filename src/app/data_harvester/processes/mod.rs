@@ -23,8 +23,50 @@ cfg_if::cfg_if! {
     }
 }
 
+pub mod fds;
+pub mod gpu;
+
+#[cfg(all(feature = "ebpf", target_os = "linux"))]
+pub mod tcp;
+
+#[cfg(target_os = "linux")]
+pub mod kubernetes;
+
+use std::sync::Arc;
+
+use fxhash::FxHashSet;
+use once_cell::sync::Lazy;
+
 use crate::Pid;
 
+/// Shares one copy of each process name, command, and executable path between harvests, rather
+/// than allocating a fresh string for every process every time.  Most processes keep the same
+/// name and command for as long as they run, and many (shells, workers, kernel threads) share
+/// them with others.
+#[derive(Debug, Default)]
+pub struct StringInterner {
+    strings: FxHashSet<Arc<str>>,
+}
+
+impl StringInterner {
+    /// Returns the shared copy of `string`, adding it if it's new.
+    pub fn intern(&mut self, string: &str) -> Arc<str> {
+        if let Some(interned) = self.strings.get(string) {
+            interned.clone()
+        } else {
+            let interned: Arc<str> = Arc::from(string);
+            self.strings.insert(interned.clone());
+            interned
+        }
+    }
+
+    /// Forgets strings that nothing else holds on to anymore, such as the names of processes
+    /// that have exited and are no longer shown.
+    pub fn prune(&mut self) {
+        self.strings.retain(|string| Arc::strong_count(string) > 1);
+    }
+}
+
 // TODO: Add value so we know if it's sorted ascending or descending by default?
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ProcessSorting {
@@ -34,6 +76,7 @@ pub enum ProcessSorting {
     Pid,
     ProcessName,
     Command,
+    ExePath,
     ReadPerSecond,
     WritePerSecond,
     TotalRead,
@@ -41,6 +84,21 @@ pub enum ProcessSorting {
     State,
     User,
     Count,
+    GpuPercent,
+    GpuMem,
+    Swap,
+    TcpRetransmits,
+    TcpRtt,
+    Pod,
+    Namespace,
+    StartTime,
+    Runtime,
+    FdCount,
+    CpuTrend,
+    CpuTime,
+    VirtMem,
+    Capabilities,
+    Seccomp,
 }
 
 impl std::fmt::Display for ProcessSorting {
@@ -59,9 +117,25 @@ impl std::fmt::Display for ProcessSorting {
                 ProcessSorting::State => "State",
                 ProcessSorting::ProcessName => "Name",
                 ProcessSorting::Command => "Command",
+                ProcessSorting::ExePath => "Path",
                 ProcessSorting::Pid => "PID",
                 ProcessSorting::Count => "Count",
                 ProcessSorting::User => "User",
+                ProcessSorting::GpuPercent => "GPU%",
+                ProcessSorting::GpuMem => "GMem",
+                ProcessSorting::Swap => "Swap",
+                ProcessSorting::TcpRetransmits => "Retr",
+                ProcessSorting::TcpRtt => "RTT",
+                ProcessSorting::Pod => "Pod",
+                ProcessSorting::Namespace => "Namespace",
+                ProcessSorting::StartTime => "Start",
+                ProcessSorting::Runtime => "Runtime",
+                ProcessSorting::FdCount => "FDs",
+                ProcessSorting::CpuTrend => "CPU Trend",
+                ProcessSorting::CpuTime => "CPU ms",
+                ProcessSorting::VirtMem => "Virt",
+                ProcessSorting::Capabilities => "Caps",
+                ProcessSorting::Seccomp => "Seccomp",
             }
         )
     }
@@ -73,25 +147,174 @@ impl Default for ProcessSorting {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+/// What the process table's name column shows.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProcessNameType {
+    Name,
+    /// The full path of the process' executable, or its name if that can't be read.
+    Path,
+    Command,
+}
+
+impl ProcessNameType {
+    /// The type shown after this one when cycling through them.
+    pub fn next(self) -> Self {
+        match self {
+            ProcessNameType::Name => ProcessNameType::Path,
+            ProcessNameType::Path => ProcessNameType::Command,
+            ProcessNameType::Command => ProcessNameType::Name,
+        }
+    }
+
+    /// The column that shows this type.
+    pub fn sorting_type(self) -> ProcessSorting {
+        match self {
+            ProcessNameType::Name => ProcessSorting::ProcessName,
+            ProcessNameType::Path => ProcessSorting::ExePath,
+            ProcessNameType::Command => ProcessSorting::Command,
+        }
+    }
+}
+
+/// What the process table's memory column shows.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProcessMemType {
+    Percent,
+    /// The resident set size.
+    Resident,
+    Virtual,
+}
+
+impl ProcessMemType {
+    /// The type shown after this one when cycling through them.
+    pub fn next(self) -> Self {
+        match self {
+            ProcessMemType::Percent => ProcessMemType::Resident,
+            ProcessMemType::Resident => ProcessMemType::Virtual,
+            ProcessMemType::Virtual => ProcessMemType::Percent,
+        }
+    }
+
+    /// The column that shows this type.
+    pub fn sorting_type(self) -> ProcessSorting {
+        match self {
+            ProcessMemType::Percent => ProcessSorting::MemPercent,
+            ProcessMemType::Resident => ProcessSorting::Mem,
+            ProcessMemType::Virtual => ProcessSorting::VirtMem,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct ProcessHarvest {
     pub pid: Pid,
     pub parent_pid: Option<Pid>, // Remember, parent_pid 0 is root...
     pub cpu_usage_percent: f64,
+    /// CPU time used since the last harvest, in milliseconds.  Only supported on Linux.
+    pub cpu_time_delta_ms: Option<u64>,
     pub mem_usage_percent: f64,
+    /// The process' resident set size.
     pub mem_usage_bytes: u64,
-    // pub rss_kb: u64,
-    // pub virt_kb: u64,
-    pub name: String,
-    pub command: String,
+    /// The process' virtual memory size.
+    pub virt_mem_bytes: u64,
+    /// Shared through a [`StringInterner`], as are the commands.
+    pub name: Arc<str>,
+    pub command: Arc<str>,
+    /// The full path of the process' executable, if it can be read.  Kernel threads don't have
+    /// one, and other users' processes may need more permissions.
+    pub exe_path: Option<Arc<str>>,
     pub read_bytes_per_sec: u64,
     pub write_bytes_per_sec: u64,
     pub total_read_bytes: u64,
     pub total_write_bytes: u64,
     pub process_state: String,
     pub process_state_char: char,
+    pub gpu_util_percent: Option<f64>,
+    pub gpu_mem_bytes: Option<u64>,
+    /// How much of the process has been swapped out.  Only supported on Linux.
+    pub swap_bytes: Option<u64>,
+    /// TCP retransmits since tracing started.  Requires the `ebpf` feature.
+    pub tcp_retransmits: Option<u64>,
+    /// The process' latest smoothed TCP round-trip time.  Requires the `ebpf` feature.
+    pub tcp_rtt_us: Option<f64>,
+    /// The Kubernetes pod the process is running in.  Only supported on Linux.
+    pub pod_name: Option<String>,
+    /// The namespace of the process' Kubernetes pod.  Only supported on Linux.
+    pub pod_namespace: Option<String>,
+    /// When the process started, in seconds since the Unix epoch.
+    pub start_time: Option<u64>,
+    /// Open file descriptors, or handles on Windows.  Only collected while the column is shown.
+    pub fd_count: Option<u64>,
+    /// The process' effective capabilities, as a bitmask.  Only supported on Linux.
+    pub cap_eff: Option<u64>,
+    /// The process' seccomp mode: 0 if it isn't confined, 1 for strict mode, and 2 for filters.
+    /// Only supported on Linux, on kernels built with seccomp.
+    pub seccomp_mode: Option<u32>,
+    /// Whether the process' I/O couldn't be read without more permissions.  Only detected on
+    /// Linux.
+    pub is_io_restricted: bool,
 
     /// This is the *effective* user ID.
     #[cfg(target_family = "unix")]
     pub uid: Option<libc::uid_t>,
 }
+
+/// The names of Linux capabilities, indexed by their bit.
+pub const CAPABILITY_NAMES: [&str; 41] = [
+    "chown",
+    "dac_override",
+    "dac_read_search",
+    "fowner",
+    "fsetid",
+    "kill",
+    "setgid",
+    "setuid",
+    "setpcap",
+    "linux_immutable",
+    "net_bind_service",
+    "net_broadcast",
+    "net_admin",
+    "net_raw",
+    "ipc_lock",
+    "ipc_owner",
+    "sys_module",
+    "sys_rawio",
+    "sys_chroot",
+    "sys_ptrace",
+    "sys_pacct",
+    "sys_admin",
+    "sys_boot",
+    "sys_nice",
+    "sys_resource",
+    "sys_time",
+    "sys_tty_config",
+    "mknod",
+    "lease",
+    "audit_write",
+    "audit_control",
+    "setfcap",
+    "mac_override",
+    "mac_admin",
+    "syslog",
+    "wake_alarm",
+    "block_suspend",
+    "audit_read",
+    "perfmon",
+    "bpf",
+    "checkpoint_restore",
+];
+
+/// Every capability the running kernel supports, as a bitmask.  If that can't be read, it's every
+/// capability in [`CAPABILITY_NAMES`].
+pub static ALL_CAPABILITIES: Lazy<u64> = Lazy::new(|| {
+    let last_cap = std::fs::read_to_string("/proc/sys/kernel/cap_last_cap")
+        .ok()
+        .and_then(|last_cap| last_cap.trim().parse::<u32>().ok())
+        .unwrap_or(CAPABILITY_NAMES.len() as u32 - 1);
+
+    if last_cap >= 63 {
+        u64::MAX
+    } else {
+        (1 << (last_cap + 1)) - 1
+    }
+});
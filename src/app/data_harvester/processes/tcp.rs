@@ -0,0 +1,167 @@
+//! Per-process TCP retransmits and round-trip times.
+//!
+//! This relies on `bpftrace`, which loads a small eBPF program that attributes each socket to
+//! the last process that sent or received on it, and then counts that socket's retransmits and
+//! samples its smoothed RTT.  Loading it requires root (or `CAP_BPF` and `CAP_PERFMON`); if it
+//! can't be started, the columns are just left empty.
+
+use std::{
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use fxhash::FxHashMap;
+
+use super::ProcessHarvest;
+use crate::Pid;
+
+/// Prints the retransmits and average RTT per PID since the last print, once a second, followed
+/// by a `--` line to mark the end of each batch.
+const TCP_TRACE_SCRIPT: &str = r#"
+kprobe:tcp_sendmsg,
+kprobe:tcp_recvmsg
+{
+    @sock_pid[arg0] = pid;
+}
+
+kprobe:tcp_close
+{
+    delete(@sock_pid[arg0]);
+}
+
+tracepoint:tcp:tcp_retransmit_skb
+{
+    $pid = @sock_pid[(uint64) args->skaddr];
+    if ($pid > 0) {
+        @retransmits[$pid] = count();
+    }
+}
+
+kprobe:tcp_rcv_established
+{
+    $pid = @sock_pid[arg0];
+    if ($pid > 0) {
+        @rtt_us[$pid] = avg(((struct tcp_sock *) arg0)->srtt_us >> 3);
+    }
+}
+
+interval:s:1
+{
+    print(@retransmits);
+    print(@rtt_us);
+    clear(@retransmits);
+    clear(@rtt_us);
+    printf("--\n");
+}
+
+END
+{
+    clear(@sock_pid);
+    clear(@retransmits);
+    clear(@rtt_us);
+}
+"#;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TcpProcessStats {
+    /// Retransmits since tracing started.
+    pub retransmits: u64,
+    /// The most recently seen smoothed round-trip time, in microseconds.
+    pub rtt_us: Option<f64>,
+}
+
+/// A running `bpftrace` process.  Dropping it stops tracing.
+#[derive(Debug)]
+pub struct TcpTracer {
+    child: Child,
+    stats: Arc<Mutex<FxHashMap<Pid, TcpProcessStats>>>,
+}
+
+impl TcpTracer {
+    pub fn start() -> Option<Self> {
+        let mut child = Command::new("bpftrace")
+            .args(&["-B", "line", "-e", TCP_TRACE_SCRIPT])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+
+        let stdout = child.stdout.take()?;
+        let stats: Arc<Mutex<FxHashMap<Pid, TcpProcessStats>>> =
+            Arc::new(Mutex::new(FxHashMap::default()));
+
+        let thread_stats = stats.clone();
+        thread::spawn(move || {
+            let mut retransmits: FxHashMap<Pid, u64> = FxHashMap::default();
+            let mut rtt_us: FxHashMap<Pid, f64> = FxHashMap::default();
+
+            for line in BufReader::new(stdout).lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+
+                if line.trim() == "--" {
+                    if let Ok(mut stats) = thread_stats.lock() {
+                        for (pid, count) in retransmits.drain() {
+                            stats.entry(pid).or_default().retransmits += count;
+                        }
+                        for (pid, rtt) in rtt_us.drain() {
+                            stats.entry(pid).or_default().rtt_us = Some(rtt);
+                        }
+                    }
+                } else if let Some((name, pid, value)) = parse_map_line(&line) {
+                    match name {
+                        "retransmits" => {
+                            retransmits.insert(pid, value as u64);
+                        }
+                        "rtt_us" => {
+                            rtt_us.insert(pid, value);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        });
+
+        Some(TcpTracer { child, stats })
+    }
+
+    /// Fills in the TCP stats of each process, and forgets about any process that has exited.
+    pub fn fill_process_stats(&self, process_list: &mut [ProcessHarvest]) {
+        if let Ok(mut stats) = self.stats.lock() {
+            let mut live_stats = FxHashMap::default();
+            for process in process_list {
+                if let Some(process_stats) = stats.remove(&process.pid) {
+                    process.tcp_retransmits = Some(process_stats.retransmits);
+                    process.tcp_rtt_us = process_stats.rtt_us;
+                    live_stats.insert(process.pid, process_stats);
+                }
+            }
+            *stats = live_stats;
+        }
+    }
+}
+
+impl Drop for TcpTracer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Parses a line of a printed map, which looks like `@rtt_us[1234]: 250`.
+fn parse_map_line(line: &str) -> Option<(&str, Pid, f64)> {
+    let line = line.trim().strip_prefix('@')?;
+    let (name, rest) = line.split_at(line.find('[')?);
+    let (key, value) = rest[1..].split_at(rest[1..].find("]:")?);
+
+    Some((
+        name,
+        key.trim().parse().ok()?,
+        value[2..].trim().parse().ok()?,
+    ))
+}
@@ -1,6 +1,7 @@
 //! Unix-specific parts of process collection.
 
-use crate::utils::error;
+use super::ProcessHarvest;
+use crate::{app::Filter, utils::error};
 
 #[derive(Debug, Default)]
 pub struct UserTable {
@@ -28,3 +29,21 @@ impl UserTable {
         }
     }
 }
+
+/// Drops any processes whose owner doesn't pass the user filter.  Owners without a name are
+/// checked by their user ID instead, and processes whose owner couldn't be read are kept.
+pub fn filter_processes_by_user(
+    process_list: &mut Vec<ProcessHarvest>, filter: &Filter, user_table: &mut UserTable,
+) {
+    process_list.retain(|process| {
+        let user = match process.uid {
+            Some(uid) => user_table
+                .get_uid_to_username_mapping(uid)
+                .unwrap_or_else(|_| uid.to_string()),
+            None => return true,
+        };
+        let is_listed = filter.list.iter().any(|regex| regex.is_match(&user));
+
+        is_listed != filter.is_list_ignored
+    });
+}
@@ -1,10 +1,11 @@
 //! Process data collection for Windows.  Uses sysinfo.
 
-use super::ProcessHarvest;
+use super::{ProcessHarvest, StringInterner};
 use sysinfo::{ProcessExt, ProcessorExt, System, SystemExt};
 
 pub fn get_process_data(
     sys: &System, use_current_cpu_total: bool, mem_total_kb: u64,
+    string_interner: &mut StringInterner,
 ) -> crate::utils::error::Result<Vec<ProcessHarvest>> {
     let mut process_vector: Vec<ProcessHarvest> = Vec::new();
     let process_hashmap = sys.get_processes();
@@ -58,21 +59,39 @@ pub fn get_process_data(
         process_vector.push(ProcessHarvest {
             pid: process_val.pid(),
             parent_pid: process_val.parent(),
-            name,
-            command,
+            name: string_interner.intern(&name),
+            command: string_interner.intern(&command),
+            // sysinfo only reads the path when it first sees a process.
+            exe_path: Some(process_val.exe())
+                .filter(|exe_path| !exe_path.as_os_str().is_empty())
+                .map(|exe_path| string_interner.intern(&exe_path.to_string_lossy())),
             mem_usage_percent: if mem_total_kb > 0 {
                 process_val.memory() as f64 * 100.0 / mem_total_kb as f64
             } else {
                 0.0
             },
             mem_usage_bytes: process_val.memory() * 1024,
+            virt_mem_bytes: process_val.virtual_memory() * 1024,
             cpu_usage_percent: process_cpu_usage,
+            cpu_time_delta_ms: None,
             read_bytes_per_sec: disk_usage.read_bytes,
             write_bytes_per_sec: disk_usage.written_bytes,
             total_read_bytes: disk_usage.total_read_bytes,
             total_write_bytes: disk_usage.total_written_bytes,
             process_state: process_val.status().to_string(),
             process_state_char: 'R',
+            gpu_util_percent: None,
+            gpu_mem_bytes: None,
+            swap_bytes: None,
+            tcp_retransmits: None,
+            tcp_rtt_us: None,
+            pod_name: None,
+            pod_namespace: None,
+            start_time: Some(process_val.start_time()),
+            fd_count: None,
+            cap_eff: None,
+            seccomp_mode: None,
+            is_io_restricted: false,
         });
     }
 
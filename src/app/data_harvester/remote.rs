@@ -0,0 +1,374 @@
+//! Reads data from other bottom instances that are serving a stream (see [`super::stream`]),
+//! optionally through an SSH tunnel.
+
+use std::{
+    collections::HashMap,
+    net::{TcpListener, TcpStream},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex, Weak},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tungstenite::{
+    client::IntoClientRequest,
+    http::header::{HeaderValue, AUTHORIZATION},
+    Message,
+};
+
+use super::stream::STREAM_VERSION;
+
+/// How long to wait before trying to reconnect to a remote.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// How many of a remote's top processes (by CPU usage) to keep.
+const NUM_TOP_PROCESSES: usize = 5;
+
+/// How long to wait for an SSH tunnel to start accepting connections.
+const TUNNEL_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RemoteConfig {
+    pub name: String,
+    /// The address the remote's stream is served on, for example `10.0.0.2:9000`.  If `ssh` is
+    /// set, this is the address as seen from the SSH host, for example `localhost:9000`.
+    pub address: String,
+    /// Where to SSH to, for example `user@10.0.0.2`, to reach the stream through a tunnel rather
+    /// than connecting to it directly.
+    pub ssh: Option<String>,
+    /// The token the remote's stream was started with, if it was.
+    pub token: Option<String>,
+}
+
+/// The latest data received from a remote.
+#[derive(Clone, Debug, Default)]
+pub struct RemoteSnapshot {
+    pub last_update: Option<Instant>,
+    pub error: Option<String>,
+    pub cpu_usage: Option<f64>,
+    /// Used and total memory in KiB.
+    pub mem: Option<(u64, u64)>,
+    /// Used and total swap in KiB.
+    pub swap: Option<(u64, u64)>,
+    pub load_avg: Option<Vec<f64>>,
+    /// The receive and transmit rates in bits per second.
+    pub network: Option<(u64, u64)>,
+    /// The hottest temperature sensor's reading, in Celsius.
+    pub max_temperature: Option<f64>,
+    pub process_count: Option<usize>,
+    pub top_processes: Vec<(String, f64)>,
+}
+
+/// Snapshots of all remotes, keyed by their names.
+pub type RemoteData = Arc<Mutex<HashMap<String, RemoteSnapshot>>>;
+
+/// An `ssh -L` process forwarding a local port to a remote's stream.  Dropping it closes the
+/// tunnel.
+#[derive(Debug)]
+pub struct SshTunnel {
+    child: Child,
+    local_address: String,
+}
+
+impl SshTunnel {
+    /// Starts `ssh`, without waiting for the tunnel to come up.  `ssh` is run in batch mode, so it
+    /// has to be able to log in without asking for anything, for example with a key loaded into an
+    /// agent.
+    fn start(destination: &str, address: &str) -> Result<Self, String> {
+        // Let the OS pick a free port.  Something else could grab it before ssh does, in which
+        // case ssh exits and this is tried again later.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .map_err(|err| err.to_string())?
+            .port();
+        let local_address = format!("127.0.0.1:{}", port);
+
+        let child = Command::new("ssh")
+            .args(&[
+                "-N",
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "ExitOnForwardFailure=yes",
+                "-o",
+                "ServerAliveInterval=15",
+                "-L",
+            ])
+            .arg(format!("{}:{}", local_address, address))
+            .arg(destination)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|err| format!("failed to run ssh: {}", err))?;
+
+        Ok(SshTunnel {
+            child,
+            local_address,
+        })
+    }
+
+    fn is_running(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// The SSH tunnels of all remotes.  The client threads only hold weak references to these, so
+/// dropping this closes every tunnel and stops new ones from being started.
+#[derive(Debug, Default)]
+pub struct SshTunnels(Vec<Arc<Mutex<Option<SshTunnel>>>>);
+
+/// Returns the address to connect to a remote's stream on, first (re)starting its SSH tunnel if
+/// it goes through one that isn't running.
+fn get_connect_address(
+    remote: &RemoteConfig, tunnel: &Weak<Mutex<Option<SshTunnel>>>,
+) -> Result<String, String> {
+    let destination = match &remote.ssh {
+        Some(destination) => destination,
+        None => return Ok(remote.address.clone()),
+    };
+    let closed_error = || "the SSH tunnel was closed".to_string();
+
+    let local_address = {
+        let tunnel = tunnel.upgrade().ok_or_else(closed_error)?;
+        let mut tunnel = tunnel.lock().map_err(|err| err.to_string())?;
+        if let Some(tunnel) = tunnel.as_mut() {
+            if tunnel.is_running() {
+                return Ok(tunnel.local_address.clone());
+            }
+        }
+
+        let new_tunnel = SshTunnel::start(destination, &remote.address)?;
+        let local_address = new_tunnel.local_address.clone();
+        *tunnel = Some(new_tunnel);
+        local_address
+    };
+
+    // Wait for ssh to log in and start listening, without holding on to the tunnel, so it can
+    // still be closed in the meantime.
+    let start = Instant::now();
+    while TcpStream::connect(&local_address).is_err() {
+        let tunnel = tunnel.upgrade().ok_or_else(closed_error)?;
+        let mut tunnel = tunnel.lock().map_err(|err| err.to_string())?;
+        let is_running = tunnel
+            .as_mut()
+            .map(|tunnel| tunnel.is_running())
+            .unwrap_or(false);
+
+        if !is_running {
+            *tunnel = None;
+            return Err(format!("failed to tunnel through {} with ssh", destination));
+        } else if start.elapsed() >= TUNNEL_TIMEOUT {
+            *tunnel = None;
+            return Err(format!("timed out tunnelling through {}", destination));
+        }
+
+        drop(tunnel);
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    Ok(local_address)
+}
+
+/// Starts a client thread for each remote, and returns where their data will be stored, along
+/// with any SSH tunnels they go through.
+pub fn start_remote_clients(remotes: &[RemoteConfig]) -> (RemoteData, SshTunnels) {
+    let remote_data: RemoteData = Arc::new(Mutex::new(HashMap::new()));
+    let mut ssh_tunnels = SshTunnels::default();
+
+    for remote in remotes {
+        if let Ok(mut remote_data) = remote_data.lock() {
+            remote_data.insert(remote.name.clone(), RemoteSnapshot::default());
+        }
+
+        let tunnel = Arc::new(Mutex::new(None));
+        let weak_tunnel = Arc::downgrade(&tunnel);
+        ssh_tunnels.0.push(tunnel);
+
+        let remote = remote.clone();
+        let remote_data = remote_data.clone();
+        thread::spawn(move || loop {
+            let address = match get_connect_address(&remote, &weak_tunnel) {
+                Ok(address) => address,
+                Err(err) => {
+                    set_error(&remote_data, &remote.name, err);
+                    thread::sleep(RECONNECT_DELAY);
+                    continue;
+                }
+            };
+
+            let request = match create_request(&address, remote.token.as_deref()) {
+                Ok(request) => request,
+                Err(err) => {
+                    set_error(&remote_data, &remote.name, err);
+                    thread::sleep(RECONNECT_DELAY);
+                    continue;
+                }
+            };
+
+            match tungstenite::connect(request) {
+                Ok((mut socket, _response)) => loop {
+                    match socket.read_message() {
+                        Ok(Message::Text(text)) => {
+                            if let Ok(snapshot) = serde_json::from_str::<Value>(&text) {
+                                match parse_snapshot(&snapshot) {
+                                    Ok(snapshot) => {
+                                        set_snapshot(&remote_data, &remote.name, snapshot)
+                                    }
+                                    Err(err) => {
+                                        set_error(&remote_data, &remote.name, err);
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(err) => {
+                            set_error(&remote_data, &remote.name, err.to_string());
+                            break;
+                        }
+                    }
+                },
+                Err(err) => {
+                    set_error(&remote_data, &remote.name, err.to_string());
+                }
+            }
+
+            thread::sleep(RECONNECT_DELAY);
+        });
+    }
+
+    (remote_data, ssh_tunnels)
+}
+
+/// Builds the request to connect to a stream with, sending the token if there is one.
+fn create_request(
+    address: &str, token: Option<&str>,
+) -> Result<tungstenite::handshake::client::Request, String> {
+    let mut request = format!("ws://{}", address)
+        .into_client_request()
+        .map_err(|err| err.to_string())?;
+    if let Some(token) = token {
+        let authorization = HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|_| "the token can't be sent in a header".to_string())?;
+        request.headers_mut().insert(AUTHORIZATION, authorization);
+    }
+
+    Ok(request)
+}
+
+fn set_snapshot(remote_data: &RemoteData, name: &str, snapshot: RemoteSnapshot) {
+    if let Ok(mut remote_data) = remote_data.lock() {
+        remote_data.insert(name.to_string(), snapshot);
+    }
+}
+
+/// Marks a remote as having failed, but keeps its last data around.
+fn set_error(remote_data: &RemoteData, name: &str, error: String) {
+    if let Ok(mut remote_data) = remote_data.lock() {
+        remote_data.entry(name.to_string()).or_default().error = Some(error);
+    }
+}
+
+/// Reads the parts of a stream snapshot that remote widgets show.  This fails if the snapshot is in
+/// a version of the format this doesn't understand.
+pub fn parse_snapshot(snapshot: &Value) -> Result<RemoteSnapshot, String> {
+    match snapshot["version"].as_u64() {
+        Some(STREAM_VERSION) => {}
+        Some(version) => {
+            return Err(format!(
+                "the remote streams version {}, but only version {} is supported",
+                version, STREAM_VERSION
+            ))
+        }
+        None => return Err("the remote isn't streaming bottom's format".to_string()),
+    }
+
+    let cpu_usage = snapshot["cpu"].as_array().and_then(|cpus| {
+        if let Some(avg) = cpus.iter().find(|cpu| cpu["name"] == "AVG") {
+            avg["usage"].as_f64()
+        } else {
+            let usages = cpus
+                .iter()
+                .filter_map(|cpu| cpu["usage"].as_f64())
+                .collect::<Vec<_>>();
+            if usages.is_empty() {
+                None
+            } else {
+                Some(usages.iter().sum::<f64>() / usages.len() as f64)
+            }
+        }
+    });
+
+    let get_mem = |value: &Value| match (value["used_kib"].as_u64(), value["total_kib"].as_u64()) {
+        (Some(used), Some(total)) => Some((used, total)),
+        _ => None,
+    };
+
+    let network = match (
+        snapshot["network"]["rx"].as_u64(),
+        snapshot["network"]["tx"].as_u64(),
+    ) {
+        (Some(rx), Some(tx)) => Some((rx, tx)),
+        _ => None,
+    };
+
+    let max_temperature = snapshot["temperatures"].as_array().and_then(|sensors| {
+        sensors
+            .iter()
+            // Fans and voltages are listed along with temperatures.
+            .filter(|sensor| {
+                sensor["kind"]
+                    .as_str()
+                    .map(|kind| kind == "temperature")
+                    .unwrap_or(true)
+            })
+            .filter_map(|sensor| sensor["temperature"].as_f64())
+            .fold(None, |max: Option<f64>, temperature| match max {
+                Some(max) if max >= temperature => Some(max),
+                _ => Some(temperature),
+            })
+    });
+
+    let processes = snapshot["processes"].as_array();
+    let top_processes = if let Some(processes) = processes {
+        let mut processes = processes
+            .iter()
+            .filter_map(|process| {
+                Some((
+                    process["name"].as_str()?.to_string(),
+                    process["cpu_percent"].as_f64()?,
+                ))
+            })
+            .collect::<Vec<_>>();
+        processes.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        processes.truncate(NUM_TOP_PROCESSES);
+        processes
+    } else {
+        Vec::new()
+    };
+
+    Ok(RemoteSnapshot {
+        last_update: Some(Instant::now()),
+        error: None,
+        cpu_usage,
+        mem: get_mem(&snapshot["memory"]),
+        swap: get_mem(&snapshot["swap"]),
+        load_avg: snapshot["load_avg"]
+            .as_array()
+            .map(|load_avg| load_avg.iter().filter_map(|load| load.as_f64()).collect()),
+        network,
+        max_temperature,
+        process_count: processes.map(|processes| processes.len()),
+        top_processes,
+    })
+}
@@ -0,0 +1,55 @@
+//! Scheduler activity data collection for Linux.
+
+use std::fs;
+
+use super::SchedHarvest;
+use crate::utils::error;
+
+const STAT_PATH: &str = "/proc/stat";
+
+/// The total context switches and interrupts as of the last read, which are needed to work out
+/// the rates since then.
+pub type PrevSchedCounts = Option<(u64, u64)>;
+
+/// Returns the rates since the last read.  There's nothing to compare against on the first read,
+/// so that returns `None`.
+pub fn get_sched_data(
+    prev_counts: &mut PrevSchedCounts, time_difference_in_secs: f64, actually_get: bool,
+) -> error::Result<Option<SchedHarvest>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let stat = fs::read_to_string(STAT_PATH)?;
+    let mut context_switches = None;
+    let mut interrupts = None;
+    for line in stat.lines() {
+        // The first value of "intr" is the total; the rest are counts for each interrupt.
+        let mut values = line.split_whitespace();
+        match values.next() {
+            Some("ctxt") => context_switches = values.next().and_then(|value| value.parse().ok()),
+            Some("intr") => interrupts = values.next().and_then(|value| value.parse().ok()),
+            _ => {}
+        }
+    }
+
+    let counts = match (context_switches, interrupts) {
+        (Some(context_switches), Some(interrupts)) => (context_switches, interrupts),
+        _ => return Ok(None),
+    };
+
+    let harvest = match *prev_counts {
+        Some((prev_context_switches, prev_interrupts)) if time_difference_in_secs > 0.0 => {
+            Some(SchedHarvest {
+                context_switches_per_sec: counts.0.saturating_sub(prev_context_switches) as f64
+                    / time_difference_in_secs,
+                interrupts_per_sec: counts.1.saturating_sub(prev_interrupts) as f64
+                    / time_difference_in_secs,
+            })
+        }
+        _ => None,
+    };
+    *prev_counts = Some(counts);
+
+    Ok(harvest)
+}
@@ -0,0 +1,16 @@
+//! Data collection for scheduler activity, like how often the system switches between tasks.
+//!
+//! For Linux, this is read from the `ctxt` and `intr` counters in `/proc/stat`.
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct SchedHarvest {
+    pub context_switches_per_sec: f64,
+    pub interrupts_per_sec: f64,
+}
@@ -0,0 +1,63 @@
+//! Data collection for system services.
+//!
+//! For Linux, this is handled by systemd (via `systemctl`) and the unit's cgroup.
+//! For Windows, this is handled by the service control manager.
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod systemd;
+        pub use self::systemd::*;
+    } else if #[cfg(target_os = "windows")] {
+        pub mod windows;
+        pub use self::windows::*;
+    } else {
+        /// Controlling services is not supported on this platform.
+        pub fn control_service(
+            _service_name: &str, _action: ServiceAction,
+        ) -> crate::utils::error::Result<()> {
+            Err(crate::utils::error::BottomError::GenericError(
+                "controlling services is not supported on this platform.".to_string(),
+            ))
+        }
+    }
+}
+
+use crate::Pid;
+
+#[derive(Default, Debug, Clone)]
+pub struct ServiceHarvest {
+    pub name: String,
+    pub description: String,
+    pub active_state: String,
+    pub sub_state: String,
+    /// The service's main process, if it's running.
+    pub pid: Option<Pid>,
+    pub mem_usage_bytes: Option<u64>,
+    pub cpu_usage_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl ServiceAction {
+    pub fn as_command(&self) -> &'static str {
+        match self {
+            ServiceAction::Start => "start",
+            ServiceAction::Stop => "stop",
+            ServiceAction::Restart => "restart",
+        }
+    }
+
+    /// How the action is shown while it's running.
+    pub fn as_progress(&self) -> &'static str {
+        match self {
+            ServiceAction::Start => "Starting",
+            ServiceAction::Stop => "Stopping",
+            ServiceAction::Restart => "Restarting",
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! Gets service data via `systemctl`, and memory/CPU usage from each unit's cgroup.
+
+use std::{
+    path::Path,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use fxhash::FxHashMap;
+
+use super::{ServiceAction, ServiceHarvest};
+use crate::{
+    app::data_harvester::command::output_with_timeout,
+    utils::error::{self, BottomError},
+    Pid,
+};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// How often to list the units with `systemctl` again.  Their states rarely change from one
+/// harvest to the next, and each unit's usage is still read from its cgroup every harvest.
+const SYSTEMCTL_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long `systemctl` may take, like when systemd or D-Bus is stuck, before it's given up on
+/// until the next refresh.
+const SYSTEMCTL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The units last listed with `systemctl`, as spawning it for every harvest is slow, along with
+/// the last seen CPU time of each unit's cgroup, in microseconds.
+#[derive(Debug, Default)]
+pub struct ServiceUnits {
+    last_refresh: Option<Instant>,
+    units: Vec<ServiceUnit>,
+    prev_cpu_times: FxHashMap<String, u64>,
+}
+
+#[derive(Debug)]
+struct ServiceUnit {
+    harvest: ServiceHarvest,
+    control_group: Option<String>,
+}
+
+/// Reads a single integer value from a cgroup file.
+fn read_cgroup_value(path: &Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Returns the memory usage in bytes and the total CPU time in microseconds of a cgroup.
+/// Handles both the unified (v2) and legacy (v1) hierarchies.
+fn get_cgroup_usage(control_group: &str, is_unified: bool) -> (Option<u64>, Option<u64>) {
+    let control_group = control_group.trim_start_matches('/');
+
+    if is_unified {
+        let cgroup_path = Path::new(CGROUP_ROOT).join(control_group);
+        let mem_usage = read_cgroup_value(&cgroup_path.join("memory.current"));
+        let cpu_usage = std::fs::read_to_string(cgroup_path.join("cpu.stat"))
+            .ok()
+            .and_then(|cpu_stat| {
+                cpu_stat.lines().find_map(|line| {
+                    let mut split = line.split_whitespace();
+                    match (split.next(), split.next()) {
+                        (Some("usage_usec"), Some(value)) => value.parse().ok(),
+                        _ => None,
+                    }
+                })
+            });
+
+        (mem_usage, cpu_usage)
+    } else {
+        let mem_usage = read_cgroup_value(
+            &Path::new(CGROUP_ROOT)
+                .join("memory")
+                .join(control_group)
+                .join("memory.usage_in_bytes"),
+        );
+        let cpu_usage = read_cgroup_value(
+            &Path::new(CGROUP_ROOT)
+                .join("cpuacct")
+                .join(control_group)
+                .join("cpuacct.usage"),
+        )
+        .map(|nanoseconds| nanoseconds / 1000);
+
+        (mem_usage, cpu_usage)
+    }
+}
+
+/// Parses the output of `systemctl show`, which is a set of `key=value` blocks separated by
+/// blank lines, one block per unit.
+fn parse_systemctl_show(output: &str) -> Vec<FxHashMap<&str, &str>> {
+    output
+        .split("\n\n")
+        .map(|block| {
+            block
+                .lines()
+                .filter_map(|line| {
+                    let mut split = line.splitn(2, '=');
+                    match (split.next(), split.next()) {
+                        (Some(key), Some(value)) => Some((key, value)),
+                        _ => None,
+                    }
+                })
+                .collect::<FxHashMap<_, _>>()
+        })
+        .filter(|properties| properties.contains_key("Id"))
+        .collect()
+}
+
+pub fn get_service_data(
+    service_units: &mut ServiceUnits, time_difference_in_secs: f64, actually_get: bool,
+) -> error::Result<Option<Vec<ServiceHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let is_due = service_units
+        .last_refresh
+        .map(|last_refresh| last_refresh.elapsed() >= SYSTEMCTL_REFRESH_INTERVAL)
+        .unwrap_or(true);
+    if is_due {
+        // Set this first, so a stuck systemctl isn't waited on again every harvest.
+        service_units.last_refresh = Some(Instant::now());
+        service_units.units = list_units()?;
+    }
+
+    let is_unified = Path::new(CGROUP_ROOT).join("cgroup.controllers").exists();
+    let prev_cpu_times = &service_units.prev_cpu_times;
+    let mut new_cpu_times = FxHashMap::default();
+
+    let service_vec = service_units
+        .units
+        .iter()
+        .map(|unit| {
+            let mut harvest = unit.harvest.clone();
+            if let Some(control_group) = &unit.control_group {
+                let (mem_usage, cpu_time) = get_cgroup_usage(control_group, is_unified);
+                harvest.mem_usage_bytes = mem_usage;
+                harvest.cpu_usage_percent = cpu_time.and_then(|cpu_time| {
+                    new_cpu_times.insert(harvest.name.clone(), cpu_time);
+                    match prev_cpu_times.get(&harvest.name) {
+                        Some(prev_cpu_time) if time_difference_in_secs > 0.0 => Some(
+                            cpu_time.saturating_sub(*prev_cpu_time) as f64
+                                / (time_difference_in_secs * 1_000_000.0)
+                                * 100.0,
+                        ),
+                        _ => None,
+                    }
+                });
+            }
+
+            harvest
+        })
+        .collect();
+
+    service_units.prev_cpu_times = new_cpu_times;
+
+    Ok(Some(service_vec))
+}
+
+/// Lists the loaded services with `systemctl`, sorted by name.
+fn list_units() -> error::Result<Vec<ServiceUnit>> {
+    let output = output_with_timeout(
+        Command::new("systemctl").args(&[
+            "show",
+            "--no-pager",
+            "--property=Id,Description,LoadState,ActiveState,SubState,MainPID,ControlGroup",
+            "*.service",
+        ]),
+        SYSTEMCTL_TIMEOUT,
+    )
+    .ok_or_else(|| {
+        BottomError::GenericError("systemctl could not be run or did not finish".to_string())
+    })?;
+
+    if !output.status.success() {
+        return Err(BottomError::GenericError(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let output = String::from_utf8(output.stdout)?;
+    let mut units: Vec<ServiceUnit> = parse_systemctl_show(&output)
+        .into_iter()
+        .filter(|properties| properties.get("LoadState") != Some(&"not-found"))
+        .map(|properties| ServiceUnit {
+            harvest: ServiceHarvest {
+                name: properties.get("Id").unwrap_or(&"").to_string(),
+                description: properties.get("Description").unwrap_or(&"").to_string(),
+                active_state: properties.get("ActiveState").unwrap_or(&"").to_string(),
+                sub_state: properties.get("SubState").unwrap_or(&"").to_string(),
+                pid: properties
+                    .get("MainPID")
+                    .and_then(|pid| pid.parse::<Pid>().ok())
+                    .filter(|pid| *pid != 0),
+                mem_usage_bytes: None,
+                cpu_usage_percent: None,
+            },
+            control_group: properties
+                .get("ControlGroup")
+                .filter(|control_group| !control_group.is_empty())
+                .map(|control_group| control_group.to_string()),
+        })
+        .collect();
+    units.sort_by(|a, b| a.harvest.name.cmp(&b.harvest.name));
+
+    Ok(units)
+}
+
+/// Starts, stops, or restarts a unit.  This does not ask for a password, so the user
+/// must have the permissions to do so already.
+pub fn control_service(service_name: &str, action: ServiceAction) -> error::Result<()> {
+    let output = Command::new("systemctl")
+        .args(&["--no-ask-password", action.as_command(), service_name])
+        .output()?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(BottomError::GenericError(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
@@ -0,0 +1,248 @@
+//! Gets service data from, and controls services through, the service control manager.
+
+use std::{
+    ffi::OsStr,
+    os::windows::ffi::OsStrExt,
+    ptr,
+    time::{Duration, Instant},
+};
+
+use winapi::{
+    shared::{
+        minwindef::{DWORD, LPBYTE},
+        winerror::ERROR_MORE_DATA,
+    },
+    um::{
+        winnt::SERVICE_WIN32,
+        winsvc::{
+            CloseServiceHandle, ControlService, EnumServicesStatusExW, OpenSCManagerW,
+            OpenServiceW, QueryServiceStatus, StartServiceW, ENUM_SERVICE_STATUS_PROCESSW,
+            SC_ENUM_PROCESS_INFO, SC_HANDLE, SC_MANAGER_CONNECT, SC_MANAGER_ENUMERATE_SERVICE,
+            SERVICE_CONTINUE_PENDING, SERVICE_CONTROL_STOP, SERVICE_PAUSED, SERVICE_PAUSE_PENDING,
+            SERVICE_QUERY_STATUS, SERVICE_RUNNING, SERVICE_START, SERVICE_START_PENDING,
+            SERVICE_STATE_ALL, SERVICE_STATUS, SERVICE_STOP, SERVICE_STOPPED, SERVICE_STOP_PENDING,
+        },
+    },
+};
+
+use super::{ServiceAction, ServiceHarvest};
+use crate::{
+    utils::error::{self, BottomError},
+    Pid,
+};
+
+/// How long a restart waits for the service to stop before giving up.
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A handle to the service control manager or to a service, which is closed when dropped.
+struct ScHandle(SC_HANDLE);
+
+impl ScHandle {
+    fn open_manager(access: DWORD) -> error::Result<Self> {
+        let handle = unsafe { OpenSCManagerW(ptr::null(), ptr::null(), access) };
+        if handle.is_null() {
+            Err(std::io::Error::last_os_error().into())
+        } else {
+            Ok(ScHandle(handle))
+        }
+    }
+
+    fn open_service(&self, service_name: &str, access: DWORD) -> error::Result<Self> {
+        let service_name = to_wide_string(service_name);
+        let handle = unsafe { OpenServiceW(self.0, service_name.as_ptr(), access) };
+        if handle.is_null() {
+            Err(std::io::Error::last_os_error().into())
+        } else {
+            Ok(ScHandle(handle))
+        }
+    }
+}
+
+impl Drop for ScHandle {
+    fn drop(&mut self) {
+        unsafe {
+            CloseServiceHandle(self.0);
+        }
+    }
+}
+
+fn to_wide_string(string: &str) -> Vec<u16> {
+    OsStr::new(string)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect()
+}
+
+/// Reads a null-terminated wide string.
+///
+/// # Safety
+/// The pointer must be null or point to a null-terminated wide string.
+unsafe fn from_wide_ptr(wide_ptr: *const u16) -> String {
+    if wide_ptr.is_null() {
+        return String::default();
+    }
+
+    let mut len = 0;
+    while *wide_ptr.add(len) != 0 {
+        len += 1;
+    }
+    String::from_utf16_lossy(std::slice::from_raw_parts(wide_ptr, len))
+}
+
+/// Maps a service's state onto systemd's active states, with the service's own state as the
+/// sub-state, so both show up in the widget the same way.
+fn get_states(current_state: DWORD) -> (&'static str, &'static str) {
+    match current_state {
+        SERVICE_RUNNING => ("active", "running"),
+        SERVICE_PAUSED => ("active", "paused"),
+        SERVICE_PAUSE_PENDING => ("active", "pausing"),
+        SERVICE_CONTINUE_PENDING => ("active", "continuing"),
+        SERVICE_START_PENDING => ("activating", "starting"),
+        SERVICE_STOP_PENDING => ("deactivating", "stopping"),
+        SERVICE_STOPPED => ("inactive", "stopped"),
+        _ => ("unknown", "unknown"),
+    }
+}
+
+pub fn get_service_data(actually_get: bool) -> error::Result<Option<Vec<ServiceHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let manager = ScHandle::open_manager(SC_MANAGER_ENUMERATE_SERVICE)?;
+    let mut service_vec = Vec::new();
+
+    // The entries are read straight out of this buffer, so it's made of u64s to keep them
+    // aligned.  If it fills up, the rest are fetched with the resume handle.
+    let mut buffer: Vec<u64> = vec![0; 8192];
+    let mut resume_handle: DWORD = 0;
+    loop {
+        let mut bytes_needed: DWORD = 0;
+        let mut services_returned: DWORD = 0;
+        let result = unsafe {
+            EnumServicesStatusExW(
+                manager.0,
+                SC_ENUM_PROCESS_INFO,
+                SERVICE_WIN32,
+                SERVICE_STATE_ALL,
+                buffer.as_mut_ptr() as LPBYTE,
+                (buffer.len() * std::mem::size_of::<u64>()) as DWORD,
+                &mut bytes_needed,
+                &mut services_returned,
+                &mut resume_handle,
+                ptr::null(),
+            )
+        };
+
+        let has_more = if result != 0 {
+            false
+        } else {
+            let err = std::io::Error::last_os_error();
+            if err.raw_os_error() != Some(ERROR_MORE_DATA as i32) {
+                return Err(err.into());
+            }
+            true
+        };
+
+        let services = unsafe {
+            std::slice::from_raw_parts(
+                buffer.as_ptr() as *const ENUM_SERVICE_STATUS_PROCESSW,
+                services_returned as usize,
+            )
+        };
+        for service in services {
+            let status = &service.ServiceStatusProcess;
+            let (active_state, sub_state) = get_states(status.dwCurrentState);
+
+            service_vec.push(ServiceHarvest {
+                name: unsafe { from_wide_ptr(service.lpServiceName) },
+                description: unsafe { from_wide_ptr(service.lpDisplayName) },
+                active_state: active_state.to_string(),
+                sub_state: sub_state.to_string(),
+                pid: if status.dwProcessId != 0 {
+                    Some(status.dwProcessId as Pid)
+                } else {
+                    None
+                },
+                mem_usage_bytes: None,
+                cpu_usage_percent: None,
+            });
+        }
+
+        if !has_more {
+            break;
+        }
+
+        // Make room if not even a single entry fit.
+        if services_returned == 0 {
+            buffer.resize(bytes_needed as usize / std::mem::size_of::<u64>() + 1, 0);
+        }
+    }
+
+    service_vec.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+
+    Ok(Some(service_vec))
+}
+
+fn get_current_state(service: &ScHandle) -> error::Result<DWORD> {
+    let mut status: SERVICE_STATUS = unsafe { std::mem::zeroed() };
+    if unsafe { QueryServiceStatus(service.0, &mut status) } == 0 {
+        Err(std::io::Error::last_os_error().into())
+    } else {
+        Ok(status.dwCurrentState)
+    }
+}
+
+fn start_service(service: &ScHandle) -> error::Result<()> {
+    if unsafe { StartServiceW(service.0, 0, ptr::null_mut()) } == 0 {
+        Err(std::io::Error::last_os_error().into())
+    } else {
+        Ok(())
+    }
+}
+
+fn stop_service(service: &ScHandle) -> error::Result<()> {
+    let mut status: SERVICE_STATUS = unsafe { std::mem::zeroed() };
+    if unsafe { ControlService(service.0, SERVICE_CONTROL_STOP, &mut status) } == 0 {
+        Err(std::io::Error::last_os_error().into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Starts, stops, or restarts a service.  This does not ask for elevation, so bottom must
+/// already be running as an administrator to control most services.
+///
+/// A restart waits for the service to stop, which can take up to [`STOP_TIMEOUT`], so this must
+/// only be called from a worker thread, never from the UI thread.
+pub fn control_service(service_name: &str, action: ServiceAction) -> error::Result<()> {
+    let manager = ScHandle::open_manager(SC_MANAGER_CONNECT)?;
+    let service = manager.open_service(
+        service_name,
+        SERVICE_START | SERVICE_STOP | SERVICE_QUERY_STATUS,
+    )?;
+
+    match action {
+        ServiceAction::Start => start_service(&service),
+        ServiceAction::Stop => stop_service(&service),
+        ServiceAction::Restart => {
+            // Unlike systemd, the service control manager has no restart, so stop the service
+            // and wait for it to finish stopping before starting it again.
+            if get_current_state(&service)? != SERVICE_STOPPED {
+                stop_service(&service)?;
+
+                let stop_instant = Instant::now();
+                while get_current_state(&service)? != SERVICE_STOPPED {
+                    if stop_instant.elapsed() > STOP_TIMEOUT {
+                        return Err(BottomError::GenericError(
+                            "timed out waiting for the service to stop".to_string(),
+                        ));
+                    }
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+            }
+
+            start_service(&service)
+        }
+    }
+}
@@ -0,0 +1,560 @@
+//! A websocket server that pushes each harvested [`Data`] snapshot to connected clients as JSON.
+//!
+//! This is what remote widgets read other bottom instances through (see [`super::remote`]).  The
+//! format is described in the stream docs, and carries a [`STREAM_VERSION`] so clients can tell
+//! when it changes in a way they don't understand.  If the server is given a token, clients have
+//! to send it as an `Authorization: Bearer <token>` header to connect.
+
+use std::{
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, SyncSender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::{json, Value};
+use tungstenite::{
+    handshake::server::{ErrorResponse, Request, Response},
+    http::{header::AUTHORIZATION, StatusCode},
+    Message,
+};
+
+use super::{batteries::PowerSource, network::IpTraffic, Data};
+use crate::utils::error;
+
+/// The version of the stream's format.  It's bumped whenever a field is removed or changes meaning,
+/// but not when one is added.
+pub const STREAM_VERSION: u64 = 1;
+
+/// The most clients, counting ones still connecting, that are served at once.  Anyone past this
+/// is disconnected straight away.
+const MAX_CLIENTS: usize = 16;
+
+/// How many snapshots can wait to be sent to a client.  A client that falls further behind than
+/// this is too slow to keep up, and is dropped.
+const CLIENT_QUEUE_LEN: usize = 4;
+
+/// How long a client may take to connect, or to take a snapshot.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Each client is served on its own thread, which is handed snapshots through a bounded queue,
+/// so the collection thread never waits on a client.
+type ClientQueues = Arc<Mutex<Vec<SyncSender<Arc<str>>>>>;
+
+pub struct StreamServer {
+    clients: ClientQueues,
+}
+
+impl StreamServer {
+    /// Binds to the given address and starts accepting clients in the background.  If there's a
+    /// token, only clients that send it are served.
+    pub fn start(address: &str, token: Option<String>) -> error::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let token: Option<Arc<str>> = token.map(Arc::from);
+        let clients: ClientQueues = Arc::new(Mutex::new(Vec::new()));
+        let num_clients = Arc::new(AtomicUsize::new(0));
+
+        let accepted_clients = clients.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if let Ok(stream) = stream {
+                    if num_clients.load(Ordering::SeqCst) >= MAX_CLIENTS {
+                        continue;
+                    }
+
+                    if stream.set_read_timeout(Some(CLIENT_TIMEOUT)).is_err()
+                        || stream.set_write_timeout(Some(CLIENT_TIMEOUT)).is_err()
+                    {
+                        continue;
+                    }
+
+                    num_clients.fetch_add(1, Ordering::SeqCst);
+                    let accepted_clients = accepted_clients.clone();
+                    let num_clients = num_clients.clone();
+                    let token = token.clone();
+                    thread::spawn(move || {
+                        serve_client(stream, &accepted_clients, token.as_deref());
+                        num_clients.fetch_sub(1, Ordering::SeqCst);
+                    });
+                }
+            }
+        });
+
+        Ok(StreamServer { clients })
+    }
+
+    /// Queues a snapshot for all clients, dropping any that have disconnected or fallen too far
+    /// behind.
+    pub fn broadcast(&self, data: &Data) {
+        if let Ok(mut clients) = self.clients.lock() {
+            if clients.is_empty() {
+                return;
+            }
+
+            let snapshot: Arc<str> = Arc::from(data_to_json(data).to_string());
+            clients.retain(|client| client.try_send(snapshot.clone()).is_ok());
+        }
+    }
+}
+
+/// Finishes connecting a client, then sends it each snapshot queued for it, until it disconnects
+/// or is dropped for being too slow.  A client without the right token is turned away.
+fn serve_client(
+    stream: TcpStream, clients: &Mutex<Vec<SyncSender<Arc<str>>>>, token: Option<&str>,
+) {
+    let check_token = |request: &Request, response: Response| {
+        let is_authorized = match token {
+            Some(token) => request
+                .headers()
+                .get(AUTHORIZATION)
+                .and_then(|authorization| authorization.to_str().ok())
+                .and_then(|authorization| authorization.strip_prefix("Bearer "))
+                .map(|given_token| is_same_token(given_token, token))
+                .unwrap_or(false),
+            None => true,
+        };
+
+        if is_authorized {
+            Ok(response)
+        } else {
+            let mut error_response = ErrorResponse::new(None);
+            *error_response.status_mut() = StatusCode::UNAUTHORIZED;
+            Err(error_response)
+        }
+    };
+
+    let mut websocket = match tungstenite::accept_hdr(stream, check_token) {
+        Ok(websocket) => websocket,
+        Err(_) => return,
+    };
+
+    let (sender, receiver) = mpsc::sync_channel(CLIENT_QUEUE_LEN);
+    match clients.lock() {
+        Ok(mut clients) => clients.push(sender),
+        Err(_) => return,
+    }
+
+    for snapshot in receiver {
+        if websocket
+            .write_message(Message::Text(snapshot.to_string()))
+            .is_err()
+        {
+            break;
+        }
+    }
+    let _ = websocket.close(None);
+}
+
+/// Compares tokens in the same amount of time no matter where they differ, so a client can't work
+/// out the token a character at a time.
+fn is_same_token(given_token: &str, token: &str) -> bool {
+    given_token.len() == token.len()
+        && given_token
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// Converts a snapshot to JSON.  This is also what snapshots saved to a file look like.
+pub fn data_to_json(data: &Data) -> Value {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0);
+
+    json!({
+        "version": STREAM_VERSION,
+        "timestamp": timestamp,
+        "cpu": data.cpu.as_ref().map(|cpu| {
+            cpu.iter()
+                .map(|cpu_data| {
+                    json!({
+                        "name": match cpu_data.cpu_count {
+                            Some(count) => format!("{}{}", cpu_data.cpu_prefix, count),
+                            None => cpu_data.cpu_prefix.clone(),
+                        },
+                        "usage": cpu_data.cpu_usage,
+                        "offline": cpu_data.is_offline,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "load_avg": data.load_avg.as_ref().map(|load_avg| load_avg.to_vec()),
+        "cpu_breakdown": data.cpu_breakdown.as_ref().map(|cpu_breakdown| {
+            json!({
+                "user_percent": cpu_breakdown.user_percent,
+                "system_percent": cpu_breakdown.system_percent,
+                "io_wait_percent": cpu_breakdown.io_wait_percent,
+                "steal_percent": cpu_breakdown.steal_percent,
+                "irq_percent": cpu_breakdown.irq_percent,
+            })
+        }),
+        "throttle": data.throttle.as_ref().map(|throttle| {
+            json!({
+                "is_under_voltage": throttle.is_under_voltage,
+                "is_throttled": throttle.is_throttled,
+                "has_under_voltage_occurred": throttle.has_under_voltage_occurred,
+                "has_throttled_occurred": throttle.has_throttled_occurred,
+                "core_voltage": throttle.core_voltage,
+            })
+        }),
+        "memory": data.memory.as_ref().map(|memory| {
+            json!({
+                "total_kib": memory.mem_total_in_kib,
+                "used_kib": memory.mem_used_in_kib,
+                "use_percent": memory.use_percent,
+            })
+        }),
+        "swap": data.swap.as_ref().map(|swap| {
+            json!({
+                "total_kib": swap.mem_total_in_kib,
+                "used_kib": swap.mem_used_in_kib,
+                "use_percent": swap.use_percent,
+            })
+        }),
+        "numa": data.numa.as_ref().map(|numa| {
+            numa.iter()
+                .map(|node| {
+                    json!({
+                        "node": node.node,
+                        "total_kib": node.mem_total_in_kib,
+                        "used_kib": node.mem_used_in_kib,
+                        "free_pages": node.free_pages,
+                        "use_percent": node.use_percent,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "shmem": data.shmem.as_ref().map(|shmem| {
+            json!({
+                "shmem_kib": shmem.shmem_in_kib,
+                "tmpfs": shmem
+                    .tmpfs
+                    .iter()
+                    .map(|tmpfs| {
+                        json!({
+                            "mount_point": tmpfs.mount_point,
+                            "used_bytes": tmpfs.used_bytes,
+                            "total_bytes": tmpfs.total_bytes,
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+                "largest_shm_files": shmem
+                    .largest_shm_files
+                    .iter()
+                    .map(|(name, bytes)| json!({ "name": name, "bytes": bytes }))
+                    .collect::<Vec<_>>(),
+            })
+        }),
+        "hugepages": data.hugepages.as_ref().map(|hugepages| {
+            json!({
+                "total_pages": hugepages.total_pages,
+                "free_pages": hugepages.free_pages,
+                "reserved_pages": hugepages.reserved_pages,
+                "surplus_pages": hugepages.surplus_pages,
+                "page_size_kib": hugepages.page_size_in_kib,
+                "thp_anon_kib": hugepages.thp_anon_in_kib,
+                "thp_shmem_kib": hugepages.thp_shmem_in_kib,
+                "thp_file_kib": hugepages.thp_file_in_kib,
+                "thp_mode": hugepages.thp_mode,
+            })
+        }),
+        "temperatures": data.temperature_sensors.as_ref().map(|sensors| {
+            sensors
+                .iter()
+                .map(|sensor| {
+                    json!({
+                        "name": sensor.name,
+                        "kind": sensor.kind.as_str(),
+                        "temperature": sensor.temperature,
+                        "min": sensor.thresholds.min,
+                        "max": sensor.thresholds.max,
+                        "critical": sensor.thresholds.critical,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "network": data.network.as_ref().map(|network| {
+            json!({
+                "rx": network.rx,
+                "tx": network.tx,
+                "total_rx": network.total_rx,
+                "total_tx": network.total_tx,
+                "interfaces": network
+                    .interface_counters
+                    .iter()
+                    .map(|interface| {
+                        json!({
+                            "name": interface.name,
+                            "errors": interface.errors,
+                            "drops": interface.drops,
+                            "collisions": interface.collisions,
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+                "ipv4": network.ipv4.as_ref().map(ip_traffic_to_json),
+                "ipv6": network.ipv6.as_ref().map(ip_traffic_to_json),
+                "links": network
+                    .links
+                    .iter()
+                    .map(|link| {
+                        json!({
+                            "name": link.name,
+                            "speed_mbps": link.speed_mbps,
+                            "full_duplex": link.is_full_duplex,
+                            "carrier": link.has_carrier,
+                            "rx": link.rx,
+                            "tx": link.tx,
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            })
+        }),
+        "processes": data.list_of_processes.as_ref().map(|processes| {
+            processes
+                .iter()
+                .map(|process| {
+                    json!({
+                        "pid": process.pid,
+                        "parent_pid": process.parent_pid,
+                        "name": &*process.name,
+                        "command": &*process.command,
+                        "exe_path": process.exe_path.as_deref(),
+                        "cpu_percent": process.cpu_usage_percent,
+                        "cpu_time_delta_ms": process.cpu_time_delta_ms,
+                        "mem_percent": process.mem_usage_percent,
+                        "mem_bytes": process.mem_usage_bytes,
+                        "virt_mem_bytes": process.virt_mem_bytes,
+                        "cap_eff": process.cap_eff,
+                        "seccomp_mode": process.seccomp_mode,
+                        "read_bytes_per_sec": process.read_bytes_per_sec,
+                        "write_bytes_per_sec": process.write_bytes_per_sec,
+                        "total_read_bytes": process.total_read_bytes,
+                        "total_write_bytes": process.total_write_bytes,
+                        "swap_bytes": process.swap_bytes,
+                        "start_time": process.start_time,
+                        "fd_count": process.fd_count,
+                        "state": process.process_state,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "disks": data.disks.as_ref().map(|disks| {
+            disks
+                .iter()
+                .map(|disk| {
+                    json!({
+                        "name": disk.name,
+                        "mount_point": disk.mount_point,
+                        "fs_type": disk.fs_type,
+                        "backing_devices": disk.backing_devices,
+                        "free_bytes": disk.free_space,
+                        "used_bytes": disk.used_space,
+                        "total_bytes": disk.total_space,
+                        "used_inodes": disk.used_inodes,
+                        "total_inodes": disk.total_inodes,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "io": data.io.as_ref().map(|io| {
+            io.iter()
+                .map(|(name, io_data)| {
+                    json!({
+                        "name": name,
+                        "read_bytes": io_data.as_ref().map(|io_data| io_data.read_bytes),
+                        "write_bytes": io_data.as_ref().map(|io_data| io_data.write_bytes),
+                        "device": io_data.as_ref().and_then(|io_data| io_data.device.as_ref()),
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "batteries": data.list_of_batteries.as_ref().map(|batteries| {
+            batteries
+                .iter()
+                .map(|battery| {
+                    json!({
+                        "charge_percent": battery.charge_percent,
+                        "secs_until_full": battery.secs_until_full,
+                        "secs_until_empty": battery.secs_until_empty,
+                        "power_consumption_rate_watts": battery.power_consumption_rate_watts,
+                        "health_percent": battery.health_percent,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "power_source": data.power_source.map(|power_source| match power_source {
+            PowerSource::Ac => "ac",
+            PowerSource::Battery => "battery",
+        }),
+        "plugins": data.plugins.as_ref().map(|plugins| {
+            plugins
+                .iter()
+                .map(|plugin| json!({ "name": plugin.name, "value": plugin.value }))
+                .collect::<Vec<_>>()
+        }),
+        "services": data.services.as_ref().map(|services| {
+            services
+                .iter()
+                .map(|service| {
+                    json!({
+                        "name": service.name,
+                        "description": service.description,
+                        "active_state": service.active_state,
+                        "sub_state": service.sub_state,
+                        "pid": service.pid,
+                        "mem_bytes": service.mem_usage_bytes,
+                        "cpu_percent": service.cpu_usage_percent,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "ports": data.ports.as_ref().map(|ports| {
+            ports
+                .iter()
+                .map(|port| {
+                    json!({
+                        "protocol": port.protocol_name(),
+                        "address": port.address.to_string(),
+                        "port": port.port,
+                        "pid": port.pid,
+                        "process_name": port.process_name,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "connections": data.connections.as_ref().map(|connections| {
+            connections
+                .iter()
+                .map(|connection| {
+                    json!({
+                        "protocol": connection.protocol_name(),
+                        "local_address": connection.local_address.to_string(),
+                        "local_port": connection.local_port,
+                        "remote_address": connection.remote_address.to_string(),
+                        "remote_port": connection.remote_port,
+                        "remote_hostname": connection.remote_hostname,
+                        "pid": connection.pid,
+                        "process_name": connection.process_name,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "cgroups": data.cgroups.as_ref().map(|cgroups| {
+            cgroups
+                .iter()
+                .map(|cgroup| {
+                    json!({
+                        "name": cgroup.name,
+                        "depth": cgroup.depth,
+                        "cpu_percent": cgroup.cpu_usage_percent,
+                        "mem_bytes": cgroup.mem_usage_bytes,
+                        "mem_limit_bytes": cgroup.mem_limit_bytes,
+                        "read_bytes_per_sec": cgroup.read_bytes_per_sec,
+                        "write_bytes_per_sec": cgroup.write_bytes_per_sec,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "containers": data.containers.as_ref().map(|containers| {
+            containers
+                .iter()
+                .map(|container| {
+                    json!({
+                        "name": container.name,
+                        "image": container.image,
+                        "status": container.status,
+                        "running": container.is_running,
+                        "cpu_percent": container.cpu_usage_percent,
+                        "mem_bytes": container.mem_usage_bytes,
+                        "mem_limit_bytes": container.mem_limit_bytes,
+                        "rx_bytes_per_sec": container.rx_bytes_per_sec,
+                        "tx_bytes_per_sec": container.tx_bytes_per_sec,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "vms": data.vms.as_ref().map(|vms| {
+            vms.iter()
+                .map(|vm| {
+                    json!({
+                        "name": vm.name,
+                        "vcpus": vm.vcpus,
+                        "cpu_percent": vm.cpu_usage_percent,
+                        "balloon_bytes": vm.balloon_bytes,
+                        "balloon_max_bytes": vm.balloon_max_bytes,
+                        "disk_read_bytes_per_sec": vm.disk_read_bytes_per_sec,
+                        "disk_write_bytes_per_sec": vm.disk_write_bytes_per_sec,
+                        "rx_bytes_per_sec": vm.rx_bytes_per_sec,
+                        "tx_bytes_per_sec": vm.tx_bytes_per_sec,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "wireless": data.wireless.as_ref().map(|wireless| {
+            wireless
+                .iter()
+                .map(|interface| {
+                    json!({
+                        "interface": interface.interface,
+                        "ssid": interface.ssid,
+                        "signal_dbm": interface.signal_dbm,
+                        "tx_bitrate_mbps": interface.tx_bitrate_mbps,
+                        "rx_bitrate_mbps": interface.rx_bitrate_mbps,
+                        "frequency_mhz": interface.frequency_mhz,
+                    })
+                })
+                .collect::<Vec<_>>()
+        }),
+        "sched": data.sched.as_ref().map(|sched| {
+            json!({
+                "context_switches_per_sec": sched.context_switches_per_sec,
+                "interrupts_per_sec": sched.interrupts_per_sec,
+            })
+        }),
+        "power": data.power.as_ref().map(|power| {
+            json!({
+                "package_watts": power.package_watts,
+                "dram_watts": power.dram_watts,
+            })
+        }),
+        "kernel_limits": data.kernel_limits.as_ref().map(|kernel_limits| {
+            json!({
+                "file_handles": limit_to_json(kernel_limits.file_handles),
+                "entropy": limit_to_json(kernel_limits.entropy),
+                "pids": limit_to_json(kernel_limits.pids),
+            })
+        }),
+        "pressure": data.pressure.as_ref().map(|pressure| {
+            json!({
+                "cpu": pressure.cpu,
+                "memory": pressure.memory,
+                "io": pressure.io,
+            })
+        }),
+        "restricted": data.restricted.as_ref().map(|restricted| {
+            json!({
+                "process_io_count": restricted.process_io_count,
+                "smart": restricted.is_smart_restricted,
+            })
+        }),
+    })
+}
+
+fn limit_to_json(limit: Option<(u64, u64)>) -> Option<Value> {
+    limit.map(|(used, max)| json!({ "used": used, "max": max }))
+}
+
+fn ip_traffic_to_json(ip_traffic: &IpTraffic) -> Value {
+    json!({
+        "rx": ip_traffic.rx,
+        "tx": ip_traffic.tx,
+        "total_rx": ip_traffic.total_rx,
+        "total_tx": ip_traffic.total_tx,
+    })
+}
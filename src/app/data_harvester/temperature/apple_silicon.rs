@@ -0,0 +1,380 @@
+//! Gets temperature and power sensor data on Apple Silicon Macs.
+//!
+//! Apple Silicon doesn't expose its temperature sensors through the SMC keys that sysinfo reads,
+//! so they're read from IOKit's HID event system instead, the same way `powermetrics` does.  Power
+//! readings are still in the SMC, so those are read from its IOKit user client.  None of this is
+//! public API, so every step is checked and simply returns no sensors if anything fails.
+
+use std::{
+    collections::HashMap,
+    ffi::{c_void, CStr, CString},
+    mem,
+    os::raw::{c_char, c_int},
+};
+
+type CFAllocatorRef = *const c_void;
+type CFArrayRef = *const c_void;
+type CFDictionaryRef = *const c_void;
+type CFIndex = isize;
+type CFNumberRef = *const c_void;
+type CFStringRef = *const c_void;
+type CFTypeRef = *const c_void;
+type IOHIDEventRef = *const c_void;
+type IOHIDEventSystemClientRef = *const c_void;
+type IOHIDServiceClientRef = *const c_void;
+type IOObject = u32;
+type KernReturn = c_int;
+
+#[repr(C)]
+struct CFDictionaryCallBacks {
+    _private: [u8; 0],
+}
+
+const CF_NUMBER_SINT32_TYPE: CFIndex = 3;
+const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+/// The usage page and usage that Apple's temperature sensors are listed under.
+const HID_USAGE_PAGE_APPLE_VENDOR: i32 = 0xff00;
+const HID_USAGE_APPLE_VENDOR_TEMPERATURE_SENSOR: i32 = 5;
+
+const HID_EVENT_TYPE_TEMPERATURE: i64 = 15;
+const HID_EVENT_FIELD_TEMPERATURE_LEVEL: i32 = (HID_EVENT_TYPE_TEMPERATURE as i32) << 16;
+
+const KERN_SUCCESS: KernReturn = 0;
+const IO_MASTER_PORT_DEFAULT: u32 = 0;
+
+/// The user client method that all SMC commands go through, and the commands used here.
+const SMC_METHOD_HANDLE_COMMAND: u32 = 2;
+const SMC_CMD_READ_BYTES: u8 = 5;
+const SMC_CMD_READ_KEY_INFO: u8 = 9;
+
+/// The SMC keys of the power readings to show, in watts, and what to call them.
+const SMC_POWER_KEYS: [(&[u8; 4], &str); 3] = [
+    (b"PSTR", "System Total"),
+    (b"PDTR", "DC In"),
+    (b"PPBR", "Battery"),
+];
+
+/// The SMC type of a little-endian 32-bit float, which is how power is reported.
+const SMC_TYPE_FLOAT: &[u8; 4] = b"flt ";
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    static kCFAllocatorDefault: CFAllocatorRef;
+    static kCFTypeDictionaryKeyCallBacks: CFDictionaryCallBacks;
+    static kCFTypeDictionaryValueCallBacks: CFDictionaryCallBacks;
+
+    fn CFArrayGetCount(array: CFArrayRef) -> CFIndex;
+    fn CFArrayGetValueAtIndex(array: CFArrayRef, index: CFIndex) -> *const c_void;
+    fn CFDictionaryCreate(
+        allocator: CFAllocatorRef, keys: *const *const c_void, values: *const *const c_void,
+        num_values: CFIndex, key_callbacks: *const CFDictionaryCallBacks,
+        value_callbacks: *const CFDictionaryCallBacks,
+    ) -> CFDictionaryRef;
+    fn CFNumberCreate(
+        allocator: CFAllocatorRef, number_type: CFIndex, value: *const c_void,
+    ) -> CFNumberRef;
+    fn CFRelease(cf: CFTypeRef);
+    fn CFStringCreateWithCString(
+        allocator: CFAllocatorRef, c_str: *const c_char, encoding: u32,
+    ) -> CFStringRef;
+    fn CFStringGetCString(
+        string: CFStringRef, buffer: *mut c_char, buffer_size: CFIndex, encoding: u32,
+    ) -> u8;
+}
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IOHIDEventSystemClientCreate(allocator: CFAllocatorRef) -> IOHIDEventSystemClientRef;
+    fn IOHIDEventSystemClientSetMatching(
+        client: IOHIDEventSystemClientRef, matching: CFDictionaryRef,
+    ) -> c_int;
+    fn IOHIDEventSystemClientCopyServices(client: IOHIDEventSystemClientRef) -> CFArrayRef;
+    fn IOHIDServiceClientCopyProperty(
+        service: IOHIDServiceClientRef, key: CFStringRef,
+    ) -> CFTypeRef;
+    fn IOHIDServiceClientCopyEvent(
+        service: IOHIDServiceClientRef, event_type: i64, options: i32, timestamp: i64,
+    ) -> IOHIDEventRef;
+    fn IOHIDEventGetFloatValue(event: IOHIDEventRef, field: i32) -> f64;
+
+    fn IOServiceMatching(name: *const c_char) -> CFDictionaryRef;
+    fn IOServiceGetMatchingService(master_port: u32, matching: CFDictionaryRef) -> IOObject;
+    fn IOServiceOpen(
+        service: IOObject, owning_task: u32, connection_type: u32, connection: *mut IOObject,
+    ) -> KernReturn;
+    fn IOServiceClose(connection: IOObject) -> KernReturn;
+    fn IOObjectRelease(object: IOObject) -> KernReturn;
+    fn IOConnectCallStructMethod(
+        connection: IOObject, selector: u32, input: *const c_void, input_size: usize,
+        output: *mut c_void, output_size: *mut usize,
+    ) -> KernReturn;
+}
+
+extern "C" {
+    static mach_task_self_: u32;
+}
+
+/// What the SMC's user client takes and returns for every command.  This has to match the
+/// layout of the SMC driver's own struct exactly.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct SmcKeyData {
+    key: u32,
+    version: [u8; 6],
+    power_limit_data: [u32; 4],
+    key_info: SmcKeyInfo,
+    result: u8,
+    status: u8,
+    command: u8,
+    data32: u32,
+    bytes: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct SmcKeyInfo {
+    data_size: u32,
+    data_type: u32,
+    data_attributes: u8,
+}
+
+/// An owned Core Foundation object, which is released when dropped.
+struct CfObject(CFTypeRef);
+
+impl CfObject {
+    fn new(cf: CFTypeRef) -> Option<Self> {
+        if cf.is_null() {
+            None
+        } else {
+            Some(CfObject(cf))
+        }
+    }
+}
+
+impl Drop for CfObject {
+    fn drop(&mut self) {
+        unsafe { CFRelease(self.0) }
+    }
+}
+
+fn create_cf_string(string: &str) -> Option<CfObject> {
+    let c_string = CString::new(string).ok()?;
+    CfObject::new(unsafe {
+        CFStringCreateWithCString(
+            kCFAllocatorDefault,
+            c_string.as_ptr(),
+            CF_STRING_ENCODING_UTF8,
+        )
+    })
+}
+
+fn create_cf_number(number: i32) -> Option<CfObject> {
+    CfObject::new(unsafe {
+        CFNumberCreate(
+            kCFAllocatorDefault,
+            CF_NUMBER_SINT32_TYPE,
+            &number as *const i32 as *const c_void,
+        )
+    })
+}
+
+/// Reads a CFString, which must not be null.
+fn read_cf_string(string: CFStringRef) -> Option<String> {
+    let mut buffer = [0 as c_char; 256];
+    let result = unsafe {
+        CFStringGetCString(
+            string,
+            buffer.as_mut_ptr(),
+            buffer.len() as CFIndex,
+            CF_STRING_ENCODING_UTF8,
+        )
+    };
+
+    if result != 0 {
+        Some(
+            unsafe { CStr::from_ptr(buffer.as_ptr()) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    } else {
+        None
+    }
+}
+
+/// An open connection to the SMC, which is closed when dropped.
+struct SmcConnection(IOObject);
+
+impl SmcConnection {
+    fn open() -> Option<Self> {
+        let name = CString::new("AppleSMC").ok()?;
+        let service = unsafe {
+            // This takes ownership of the matching dictionary, so it isn't released here.
+            IOServiceGetMatchingService(IO_MASTER_PORT_DEFAULT, IOServiceMatching(name.as_ptr()))
+        };
+        if service == 0 {
+            return None;
+        }
+
+        let mut connection = 0;
+        let result = unsafe { IOServiceOpen(service, mach_task_self_, 0, &mut connection) };
+        unsafe {
+            IOObjectRelease(service);
+        }
+
+        if result == KERN_SUCCESS {
+            Some(SmcConnection(connection))
+        } else {
+            None
+        }
+    }
+
+    fn call(&self, input: &SmcKeyData) -> Option<SmcKeyData> {
+        let mut output = SmcKeyData::default();
+        let mut output_size = mem::size_of::<SmcKeyData>();
+        let result = unsafe {
+            IOConnectCallStructMethod(
+                self.0,
+                SMC_METHOD_HANDLE_COMMAND,
+                input as *const SmcKeyData as *const c_void,
+                mem::size_of::<SmcKeyData>(),
+                &mut output as *mut SmcKeyData as *mut c_void,
+                &mut output_size,
+            )
+        };
+
+        if result == KERN_SUCCESS && output.result == 0 {
+            Some(output)
+        } else {
+            None
+        }
+    }
+
+    /// Reads a key holding a float, or returns [`None`] if this Mac doesn't have it.
+    fn read_float(&self, key: &[u8; 4]) -> Option<f32> {
+        let key = u32::from_be_bytes(*key);
+        let key_info = self.call(&SmcKeyData {
+            key,
+            command: SMC_CMD_READ_KEY_INFO,
+            ..SmcKeyData::default()
+        })?;
+        if key_info.key_info.data_type != u32::from_be_bytes(*SMC_TYPE_FLOAT)
+            || key_info.key_info.data_size != 4
+        {
+            return None;
+        }
+
+        let output = self.call(&SmcKeyData {
+            key,
+            key_info: key_info.key_info,
+            command: SMC_CMD_READ_BYTES,
+            ..SmcKeyData::default()
+        })?;
+        Some(f32::from_le_bytes([
+            output.bytes[0],
+            output.bytes[1],
+            output.bytes[2],
+            output.bytes[3],
+        ]))
+    }
+}
+
+impl Drop for SmcConnection {
+    fn drop(&mut self) {
+        unsafe {
+            IOServiceClose(self.0);
+        }
+    }
+}
+
+/// Returns each power reading's name and value in watts.
+pub fn get_power_readings() -> Vec<(String, f32)> {
+    let connection = match SmcConnection::open() {
+        Some(connection) => connection,
+        None => return Vec::new(),
+    };
+
+    SMC_POWER_KEYS
+        .iter()
+        .filter_map(|(key, name)| {
+            connection
+                .read_float(key)
+                .filter(|watts| watts.is_finite() && *watts >= 0.0)
+                .map(|watts| (name.to_string(), watts))
+        })
+        .collect()
+}
+
+/// Returns each temperature sensor's name and reading in Celsius.  Several sensors often share a
+/// name, like the per-core dies, so those are averaged into one reading.
+pub fn get_sensor_temperatures() -> Vec<(String, f32)> {
+    let mut readings: HashMap<String, (f32, u32)> = HashMap::new();
+    read_sensors(&mut readings);
+
+    readings
+        .into_iter()
+        .map(|(name, (total, count))| (name, total / count as f32))
+        .collect()
+}
+
+/// Adds each sensor's reading to the running totals by name.  Returns [`None`] if the sensors
+/// can't be listed at all.
+fn read_sensors(readings: &mut HashMap<String, (f32, u32)>) -> Option<()> {
+    let page_key = create_cf_string("PrimaryUsagePage")?;
+    let usage_key = create_cf_string("PrimaryUsage")?;
+    let page = create_cf_number(HID_USAGE_PAGE_APPLE_VENDOR)?;
+    let usage = create_cf_number(HID_USAGE_APPLE_VENDOR_TEMPERATURE_SENSOR)?;
+    let product_key = create_cf_string("Product")?;
+
+    let keys = [page_key.0, usage_key.0];
+    let values = [page.0, usage.0];
+    let matching = CfObject::new(unsafe {
+        CFDictionaryCreate(
+            kCFAllocatorDefault,
+            keys.as_ptr(),
+            values.as_ptr(),
+            keys.len() as CFIndex,
+            &kCFTypeDictionaryKeyCallBacks,
+            &kCFTypeDictionaryValueCallBacks,
+        )
+    })?;
+
+    let client = CfObject::new(unsafe { IOHIDEventSystemClientCreate(kCFAllocatorDefault) })?;
+    unsafe {
+        IOHIDEventSystemClientSetMatching(client.0, matching.0);
+    }
+    let services = CfObject::new(unsafe { IOHIDEventSystemClientCopyServices(client.0) })?;
+
+    for index in 0..unsafe { CFArrayGetCount(services.0) } {
+        let service = unsafe { CFArrayGetValueAtIndex(services.0, index) };
+        if service.is_null() {
+            continue;
+        }
+
+        let name =
+            match CfObject::new(unsafe { IOHIDServiceClientCopyProperty(service, product_key.0) })
+                .and_then(|name| read_cf_string(name.0))
+            {
+                Some(name) => name,
+                None => continue,
+            };
+
+        let event = match CfObject::new(unsafe {
+            IOHIDServiceClientCopyEvent(service, HID_EVENT_TYPE_TEMPERATURE, 0, 0)
+        }) {
+            Some(event) => event,
+            None => continue,
+        };
+        let temperature =
+            unsafe { IOHIDEventGetFloatValue(event.0, HID_EVENT_FIELD_TEMPERATURE_LEVEL) } as f32;
+
+        // Sensors that aren't in use tend to report nonsense, like 0 or -127.
+        if temperature > 0.0 && temperature < 150.0 {
+            let reading = readings.entry(name).or_insert((0.0, 0));
+            reading.0 += temperature;
+            reading.1 += 1;
+        }
+    }
+
+    Some(())
+}
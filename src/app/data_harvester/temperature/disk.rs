@@ -0,0 +1,266 @@
+//! Gets drive temperatures.
+//!
+//! Drives that expose a hwmon device (SATA drives with the `drivetemp` driver loaded, and NVMe
+//! drives) are read straight from sysfs.  Any other drive falls back to `smartctl`, which
+//! usually needs root.  As that means spawning a process per drive, which can be slow, those
+//! results are cached and only refreshed every so often.  Drives in standby are skipped rather
+//! than woken up just to read their temperature.
+
+use std::{
+    collections::HashMap,
+    io::ErrorKind,
+    path::Path,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use super::{
+    get_temp_alias, is_temp_filtered, temp_vec_sort, SensorKind, SensorThresholds, TempHarvest,
+};
+use crate::app::{
+    data_harvester::{command::output_with_timeout, permissions::is_elevated},
+    Filter,
+};
+
+/// The names of hwmon devices that belong to drives.  These are read here rather than by heim,
+/// so they can be tagged with the drive's device name.
+pub const DISK_HWMON_NAMES: [&str; 2] = ["drivetemp", "nvme"];
+
+const HWMON_DIR: &str = "/sys/class/hwmon";
+const BLOCK_DIR: &str = "/sys/block";
+
+/// The bit of `smartctl`'s exit status that's set if the device couldn't be opened.
+const SMARTCTL_OPEN_FAILED: i32 = 1 << 1;
+
+/// The exit status `smartctl` is told to use if a drive is in standby.  Its usual exit status is
+/// a bit mask, which can't have every bit set, as the lowest means it couldn't parse its
+/// arguments and gave up straight away.
+const SMARTCTL_STANDBY_STATUS: i32 = 255;
+
+/// How long to wait between `smartctl` runs.
+const SMARTCTL_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long to give `smartctl` to read a drive.
+const SMARTCTL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The last drive temperatures and limits read by `smartctl`, in Celsius.
+#[derive(Debug, Default)]
+pub struct SmartctlCache {
+    last_refresh: Option<Instant>,
+    /// Whether some drives couldn't be opened for lack of permissions.
+    is_restricted: bool,
+    temperatures: Vec<(String, f32, SensorThresholds)>,
+}
+
+impl SmartctlCache {
+    pub fn is_restricted(&self) -> bool {
+        self.is_restricted
+    }
+}
+
+/// Adds drive temperatures to the other sensors, and re-sorts them all.
+pub fn add_disk_temperature_data(
+    temperature_vec: &mut Vec<TempHarvest>, filter: &Option<Filter>,
+    aliases: &HashMap<String, String>, smartctl_cache: &mut SmartctlCache,
+) {
+    let (mut celsius_temps, hwmon_devices) = get_hwmon_disk_temperatures();
+
+    if smartctl_cache
+        .last_refresh
+        .map(|last_refresh| last_refresh.elapsed() >= SMARTCTL_REFRESH_INTERVAL)
+        .unwrap_or(true)
+    {
+        smartctl_cache.last_refresh = Some(Instant::now());
+        smartctl_cache.is_restricted = false;
+        smartctl_cache.temperatures.clear();
+
+        for device in get_block_devices() {
+            let is_covered_by_hwmon = hwmon_devices.iter().any(|hwmon_device| {
+                // NVMe hwmon devices are the controller (nvme0), not the namespace (nvme0n1).
+                device
+                    .strip_prefix(hwmon_device.as_str())
+                    .map(|rest| rest.is_empty() || rest.starts_with('n'))
+                    .unwrap_or(false)
+            });
+
+            // Don't bother spawning smartctl for drives that would just get filtered out.
+            if is_covered_by_hwmon || !is_temp_filtered(filter, &device) {
+                continue;
+            }
+
+            match get_smartctl_temperature(&device) {
+                Ok(Some((temperature, thresholds))) => {
+                    smartctl_cache
+                        .temperatures
+                        .push((device, temperature, thresholds))
+                }
+                Ok(None) => {}
+                Err(err) if err.kind() == ErrorKind::PermissionDenied => {
+                    smartctl_cache.is_restricted = true;
+                }
+                // Either smartctl isn't installed, or it hung, so don't hold up collection by
+                // trying any other drives until the next refresh.
+                Err(_) => break,
+            }
+        }
+    }
+    celsius_temps.extend(smartctl_cache.temperatures.iter().cloned());
+
+    temperature_vec.extend(
+        celsius_temps
+            .into_iter()
+            .filter(|(name, _, _)| is_temp_filtered(filter, name))
+            .map(|(name, celsius, thresholds)| TempHarvest {
+                name: get_temp_alias(aliases, name),
+                temperature: celsius,
+                kind: SensorKind::Temperature,
+                thresholds,
+            }),
+    );
+
+    temp_vec_sort(temperature_vec);
+}
+
+/// Returns the temperatures and limits of drives with a hwmon device, in Celsius, along with the
+/// device names those drives were found under.
+fn get_hwmon_disk_temperatures() -> (Vec<(String, f32, SensorThresholds)>, Vec<String>) {
+    let mut temperatures = Vec::new();
+    let mut devices = Vec::new();
+
+    if let Ok(entries) = std::fs::read_dir(HWMON_DIR) {
+        for entry in entries.flatten() {
+            let hwmon_path = entry.path();
+            let hwmon_name = read_trimmed(&hwmon_path.join("name")).unwrap_or_default();
+            if !DISK_HWMON_NAMES.contains(&hwmon_name.as_str()) {
+                continue;
+            }
+
+            let device = match get_hwmon_device_name(&hwmon_path) {
+                Some(device) => device,
+                None => continue,
+            };
+
+            if let Ok(sensor_entries) = std::fs::read_dir(&hwmon_path) {
+                for sensor_entry in sensor_entries.flatten() {
+                    let file_name = sensor_entry.file_name().to_string_lossy().to_string();
+                    if !(file_name.starts_with("temp") && file_name.ends_with("_input")) {
+                        continue;
+                    }
+
+                    // Temperatures are given in millidegrees Celsius.
+                    let read_celsius = |suffix: &str| {
+                        read_trimmed(&hwmon_path.join(file_name.replace("_input", suffix)))
+                            .and_then(|value| value.parse::<f32>().ok())
+                            .map(|millidegrees| millidegrees / 1000.0)
+                    };
+                    let temperature = match read_celsius("_input") {
+                        Some(temperature) => temperature,
+                        None => continue,
+                    };
+                    let thresholds = SensorThresholds {
+                        min: read_celsius("_min"),
+                        max: read_celsius("_max"),
+                        critical: read_celsius("_crit"),
+                    };
+
+                    let label_path = hwmon_path.join(file_name.replace("_input", "_label"));
+                    let name = match read_trimmed(&label_path) {
+                        Some(label) => format!("{}: {}", device, label),
+                        None => device.clone(),
+                    };
+
+                    temperatures.push((name, temperature, thresholds));
+                }
+            }
+
+            devices.push(device);
+        }
+    }
+
+    (temperatures, devices)
+}
+
+/// Returns the name of the drive a hwmon device belongs to, like `sda` or `nvme0`.
+fn get_hwmon_device_name(hwmon_path: &Path) -> Option<String> {
+    let device_path = hwmon_path.join("device");
+
+    // SCSI devices list their block device, while NVMe devices are the controller itself.
+    if let Some(block_device) = std::fs::read_dir(device_path.join("block"))
+        .ok()
+        .and_then(|mut entries| entries.next())
+        .and_then(|entry| entry.ok())
+    {
+        return Some(block_device.file_name().to_string_lossy().to_string());
+    }
+
+    std::fs::canonicalize(&device_path)
+        .ok()?
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+/// Returns the names of all block devices backed by actual hardware, skipping things like loop
+/// and device-mapper devices.
+fn get_block_devices() -> Vec<String> {
+    std::fs::read_dir(BLOCK_DIR)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().join("device").exists())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the temperature of a drive according to `smartctl`, in Celsius, along with the limits
+/// it recommends operating within, if it reports them.  There's no reading if the drive is in
+/// standby.  Fails if `smartctl` couldn't be run or timed out, or with
+/// [`ErrorKind::PermissionDenied`] if it couldn't open the drive while not running as root.
+fn get_smartctl_temperature(device: &str) -> std::io::Result<Option<(f32, SensorThresholds)>> {
+    let output = output_with_timeout(
+        Command::new("smartctl").args(&[
+            "-A",
+            "-j",
+            "-n",
+            &format!("standby,{}", SMARTCTL_STANDBY_STATUS),
+            &format!("/dev/{}", device),
+        ]),
+        SMARTCTL_TIMEOUT,
+    )
+    .ok_or(ErrorKind::TimedOut)?;
+
+    if output.status.code() == Some(SMARTCTL_STANDBY_STATUS) {
+        return Ok(None);
+    }
+
+    // Bit 1 of the exit status means the device couldn't be opened, which without root is
+    // almost always down to permissions.
+    if output.status.code().unwrap_or(0) & SMARTCTL_OPEN_FAILED != 0 && !is_elevated() {
+        return Err(ErrorKind::PermissionDenied.into());
+    }
+
+    // smartctl's exit status is a bit mask that is often non-zero even when it succeeds, so
+    // just check whether it reported a temperature.
+    Ok(serde_json::from_slice::<serde_json::Value>(&output.stdout)
+        .ok()
+        .and_then(|smartctl_output| {
+            let temperature = &smartctl_output["temperature"];
+            let read_celsius = |key: &str| temperature[key].as_f64().map(|value| value as f32);
+
+            Some((
+                read_celsius("current")?,
+                SensorThresholds {
+                    min: read_celsius("op_limit_min"),
+                    max: read_celsius("op_limit_max"),
+                    critical: read_celsius("limit_max"),
+                },
+            ))
+        }))
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+}
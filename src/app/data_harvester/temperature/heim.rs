@@ -1,10 +1,15 @@
 //! Gets temperature data via heim.
 
-use super::{is_temp_filtered, temp_vec_sort, TempHarvest, TemperatureType};
+use std::collections::HashMap;
+
+use super::{
+    get_temp_alias, is_temp_filtered, temp_vec_sort, SensorKind, SensorThresholds, TempHarvest,
+    DISK_HWMON_NAMES,
+};
 use crate::app::Filter;
 
 pub async fn get_temperature_data(
-    temp_type: &TemperatureType, actually_get: bool, filter: &Option<Filter>,
+    actually_get: bool, filter: &Option<Filter>, aliases: &HashMap<String, String>,
 ) -> crate::utils::error::Result<Option<Vec<TempHarvest>>> {
     use futures::StreamExt;
     use heim::units::thermodynamic_temperature;
@@ -18,6 +23,11 @@ pub async fn get_temperature_data(
     let mut sensor_data = heim::sensors::temperatures().boxed_local();
     while let Some(sensor) = sensor_data.next().await {
         if let Ok(sensor) = sensor {
+            // Drives are handled separately, so they can be tagged with their device name.
+            if DISK_HWMON_NAMES.contains(&sensor.unit()) {
+                continue;
+            }
+
             let component_name = Some(sensor.unit().to_string());
             let component_label = sensor.label().map(|label| label.to_string());
 
@@ -30,19 +40,19 @@ pub async fn get_temperature_data(
 
             if is_temp_filtered(filter, &name) {
                 temperature_vec.push(TempHarvest {
-                    name,
-                    temperature: match temp_type {
-                        TemperatureType::Celsius => sensor
-                            .current()
-                            .get::<thermodynamic_temperature::degree_celsius>(
-                        ),
-                        TemperatureType::Kelvin => {
-                            sensor.current().get::<thermodynamic_temperature::kelvin>()
-                        }
-                        TemperatureType::Fahrenheit => sensor
-                            .current()
-                            .get::<thermodynamic_temperature::degree_fahrenheit>(
-                        ),
+                    name: get_temp_alias(aliases, name),
+                    temperature: sensor
+                        .current()
+                        .get::<thermodynamic_temperature::degree_celsius>(),
+                    kind: SensorKind::Temperature,
+                    thresholds: SensorThresholds {
+                        min: None,
+                        max: sensor
+                            .high()
+                            .map(|high| high.get::<thermodynamic_temperature::degree_celsius>()),
+                        critical: sensor.critical().map(|critical| {
+                            critical.get::<thermodynamic_temperature::degree_celsius>()
+                        }),
                     },
                 });
             }
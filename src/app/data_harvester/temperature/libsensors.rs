@@ -0,0 +1,273 @@
+//! Gets temperatures, fan speeds, voltages, and power draw through lm-sensors' libsensors.
+//!
+//! This covers sensors that aren't read otherwise, like voltages, fans, and power, and applies any
+//! labels, scaling, and ignores set up in `sensors.conf`.  Temperatures from chips that were
+//! already read otherwise are skipped, so nothing shows up twice.
+
+use std::{
+    collections::HashMap,
+    ffi::CStr,
+    os::raw::{c_char, c_double, c_int, c_short, c_uint},
+    ptr,
+};
+
+use super::{
+    get_temp_alias, is_temp_filtered, temp_vec_sort, SensorKind, SensorThresholds, TempHarvest,
+    DISK_HWMON_NAMES,
+};
+use crate::app::Filter;
+
+const SENSORS_FEATURE_IN: c_int = 0x00;
+const SENSORS_FEATURE_FAN: c_int = 0x01;
+const SENSORS_FEATURE_TEMP: c_int = 0x02;
+const SENSORS_FEATURE_POWER: c_int = 0x03;
+
+const SENSORS_SUBFEATURE_IN_INPUT: c_int = SENSORS_FEATURE_IN << 8;
+const SENSORS_SUBFEATURE_IN_MIN: c_int = SENSORS_SUBFEATURE_IN_INPUT + 1;
+const SENSORS_SUBFEATURE_IN_MAX: c_int = SENSORS_SUBFEATURE_IN_INPUT + 2;
+const SENSORS_SUBFEATURE_IN_CRIT: c_int = SENSORS_SUBFEATURE_IN_INPUT + 4;
+const SENSORS_SUBFEATURE_FAN_INPUT: c_int = SENSORS_FEATURE_FAN << 8;
+const SENSORS_SUBFEATURE_FAN_MIN: c_int = SENSORS_SUBFEATURE_FAN_INPUT + 1;
+const SENSORS_SUBFEATURE_FAN_MAX: c_int = SENSORS_SUBFEATURE_FAN_INPUT + 2;
+const SENSORS_SUBFEATURE_TEMP_INPUT: c_int = SENSORS_FEATURE_TEMP << 8;
+const SENSORS_SUBFEATURE_TEMP_MAX: c_int = SENSORS_SUBFEATURE_TEMP_INPUT + 1;
+const SENSORS_SUBFEATURE_TEMP_MIN: c_int = SENSORS_SUBFEATURE_TEMP_INPUT + 3;
+const SENSORS_SUBFEATURE_TEMP_CRIT: c_int = SENSORS_SUBFEATURE_TEMP_INPUT + 4;
+const SENSORS_SUBFEATURE_POWER_AVERAGE: c_int = SENSORS_FEATURE_POWER << 8;
+const SENSORS_SUBFEATURE_POWER_INPUT: c_int = SENSORS_SUBFEATURE_POWER_AVERAGE + 3;
+const SENSORS_SUBFEATURE_POWER_MAX: c_int = SENSORS_SUBFEATURE_POWER_AVERAGE + 8;
+const SENSORS_SUBFEATURE_POWER_CRIT: c_int = SENSORS_SUBFEATURE_POWER_AVERAGE + 9;
+const SENSORS_SUBFEATURE_POWER_MIN: c_int = SENSORS_SUBFEATURE_POWER_AVERAGE + 10;
+
+/// The subfeature flag that's set if it can be read.
+const SENSORS_MODE_R: c_uint = 1;
+
+// These mirror the structs in libsensors' `sensors.h`, so they need every field, even the ones
+// that aren't read here.
+
+#[repr(C)]
+#[allow(dead_code)]
+struct SensorsBusId {
+    bus_type: c_short,
+    nr: c_short,
+}
+
+#[repr(C)]
+#[allow(dead_code)]
+struct SensorsChipName {
+    prefix: *mut c_char,
+    bus: SensorsBusId,
+    addr: c_int,
+    path: *mut c_char,
+}
+
+#[repr(C)]
+#[allow(dead_code)]
+struct SensorsFeature {
+    name: *mut c_char,
+    number: c_int,
+    feature_type: c_int,
+    first_subfeature: c_int,
+    padding1: c_int,
+}
+
+#[repr(C)]
+#[allow(dead_code)]
+struct SensorsSubfeature {
+    name: *mut c_char,
+    number: c_int,
+    subfeature_type: c_int,
+    mapping: c_int,
+    flags: c_uint,
+}
+
+#[link(name = "sensors")]
+extern "C" {
+    fn sensors_init(input: *mut libc::FILE) -> c_int;
+    fn sensors_cleanup();
+    fn sensors_get_detected_chips(
+        chip_match: *const SensorsChipName, nr: *mut c_int,
+    ) -> *const SensorsChipName;
+    fn sensors_get_features(name: *const SensorsChipName, nr: *mut c_int) -> *const SensorsFeature;
+    fn sensors_get_subfeature(
+        name: *const SensorsChipName, feature: *const SensorsFeature, subfeature_type: c_int,
+    ) -> *const SensorsSubfeature;
+    fn sensors_get_label(
+        name: *const SensorsChipName, feature: *const SensorsFeature,
+    ) -> *mut c_char;
+    fn sensors_get_value(
+        name: *const SensorsChipName, subfeat_nr: c_int, value: *mut c_double,
+    ) -> c_int;
+}
+
+/// A handle to libsensors, which is set up when created and cleaned up when dropped.  libsensors
+/// keeps global state, so only one of these should exist at a time.
+#[derive(Debug)]
+pub struct Libsensors(());
+
+impl Libsensors {
+    /// Sets up libsensors with the system's `sensors.conf`.  Returns `None` if that failed.
+    pub fn init() -> Option<Self> {
+        // SAFETY: A null file tells libsensors to load its default config.
+        if unsafe { sensors_init(ptr::null_mut()) } == 0 {
+            Some(Libsensors(()))
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for Libsensors {
+    fn drop(&mut self) {
+        // SAFETY: libsensors was set up when this was created, and nothing it returned outlives it.
+        unsafe { sensors_cleanup() }
+    }
+}
+
+/// Adds the sensors found by libsensors to the other sensors, and re-sorts them all.
+pub fn add_libsensors_data(
+    temperature_vec: &mut Vec<TempHarvest>, _libsensors: &Libsensors, filter: &Option<Filter>,
+    aliases: &HashMap<String, String>,
+) {
+    let mut new_sensors = Vec::new();
+
+    // SAFETY: libsensors is set up, as there's a handle to it, and the chips, features, and
+    // subfeatures it returns stay valid until it's cleaned up.  Labels are allocated for us, and
+    // freed once copied.
+    unsafe {
+        let mut chip_nr: c_int = 0;
+        loop {
+            let chip = sensors_get_detected_chips(ptr::null(), &mut chip_nr);
+            if chip.is_null() {
+                break;
+            }
+            if (*chip).prefix.is_null() {
+                continue;
+            }
+            let prefix = CStr::from_ptr((*chip).prefix)
+                .to_string_lossy()
+                .into_owned();
+
+            // heim and the drive temperature reader go through the same hwmon devices, so leave
+            // the temperatures of any chips they've read to them.
+            let is_temp_read_elsewhere = DISK_HWMON_NAMES.contains(&prefix.as_str())
+                || temperature_vec.iter().any(|sensor| {
+                    sensor.name == prefix || sensor.name.starts_with(&format!("{}: ", prefix))
+                });
+
+            let mut feature_nr: c_int = 0;
+            loop {
+                let feature = sensors_get_features(chip, &mut feature_nr);
+                if feature.is_null() {
+                    break;
+                }
+
+                // The reading's subfeature, followed by those of its min, max, and critical
+                // limits, where the sensor has them.
+                let (kind, [input, min, max, critical]) = match (*feature).feature_type {
+                    SENSORS_FEATURE_TEMP if !is_temp_read_elsewhere => (
+                        SensorKind::Temperature,
+                        [
+                            Some(SENSORS_SUBFEATURE_TEMP_INPUT),
+                            Some(SENSORS_SUBFEATURE_TEMP_MIN),
+                            Some(SENSORS_SUBFEATURE_TEMP_MAX),
+                            Some(SENSORS_SUBFEATURE_TEMP_CRIT),
+                        ],
+                    ),
+                    SENSORS_FEATURE_FAN => (
+                        SensorKind::Fan,
+                        [
+                            Some(SENSORS_SUBFEATURE_FAN_INPUT),
+                            Some(SENSORS_SUBFEATURE_FAN_MIN),
+                            Some(SENSORS_SUBFEATURE_FAN_MAX),
+                            None,
+                        ],
+                    ),
+                    SENSORS_FEATURE_IN => (
+                        SensorKind::Voltage,
+                        [
+                            Some(SENSORS_SUBFEATURE_IN_INPUT),
+                            Some(SENSORS_SUBFEATURE_IN_MIN),
+                            Some(SENSORS_SUBFEATURE_IN_MAX),
+                            Some(SENSORS_SUBFEATURE_IN_CRIT),
+                        ],
+                    ),
+                    SENSORS_FEATURE_POWER => (
+                        SensorKind::Power,
+                        [
+                            Some(SENSORS_SUBFEATURE_POWER_INPUT),
+                            Some(SENSORS_SUBFEATURE_POWER_MIN),
+                            Some(SENSORS_SUBFEATURE_POWER_MAX),
+                            Some(SENSORS_SUBFEATURE_POWER_CRIT),
+                        ],
+                    ),
+                    _ => continue,
+                };
+                let read_subfeature = |subfeature_type: Option<c_int>| {
+                    get_subfeature_value(chip, feature, subfeature_type?)
+                };
+
+                // Some drivers, like amdgpu's, only report an average power draw.
+                let value = match read_subfeature(input).or_else(|| {
+                    if kind == SensorKind::Power {
+                        read_subfeature(Some(SENSORS_SUBFEATURE_POWER_AVERAGE))
+                    } else {
+                        None
+                    }
+                }) {
+                    Some(value) => value,
+                    None => continue,
+                };
+                let thresholds = SensorThresholds {
+                    min: read_subfeature(min),
+                    max: read_subfeature(max),
+                    critical: read_subfeature(critical),
+                };
+
+                let label_ptr = sensors_get_label(chip, feature);
+                if label_ptr.is_null() {
+                    continue;
+                }
+                let label = CStr::from_ptr(label_ptr).to_string_lossy().into_owned();
+                libc::free(label_ptr as *mut libc::c_void);
+
+                new_sensors.push((format!("{}: {}", prefix, label), kind, value, thresholds));
+            }
+        }
+    }
+
+    temperature_vec.extend(
+        new_sensors
+            .into_iter()
+            .filter(|(name, _, _, _)| is_temp_filtered(filter, name))
+            .map(|(name, kind, value, thresholds)| TempHarvest {
+                name: get_temp_alias(aliases, name),
+                temperature: value,
+                kind,
+                thresholds,
+            }),
+    );
+
+    temp_vec_sort(temperature_vec);
+}
+
+/// Reads a chip feature's subfeature, if it has one of the given type and it can be read.
+///
+/// # Safety
+///
+/// libsensors must be set up, and `chip` and `feature` must have come from it.
+unsafe fn get_subfeature_value(
+    chip: *const SensorsChipName, feature: *const SensorsFeature, subfeature_type: c_int,
+) -> Option<f32> {
+    let subfeature = sensors_get_subfeature(chip, feature, subfeature_type);
+    if subfeature.is_null() || (*subfeature).flags & SENSORS_MODE_R == 0 {
+        return None;
+    }
+
+    let mut value: c_double = 0.0;
+    if sensors_get_value(chip, (*subfeature).number, &mut value) != 0 {
+        return None;
+    }
+
+    Some(value as f32)
+}
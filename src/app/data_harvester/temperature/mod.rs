@@ -1,29 +1,132 @@
 //! Data collection for temperature metrics.
 //!
-//! For Linux and macOS, this is handled by Heim.
-//! For Windows, this is handled by sysinfo.
+//! For Linux, this is handled by Heim.
+//! For macOS and Windows, this is handled by sysinfo, except on Apple Silicon, where the sensors
+//! are read through IOKit.
+//!
+//! On Linux, drive temperatures are also read from sysfs or `smartctl`.  With the `libsensors`
+//! feature, fans, voltages, power, and any temperatures heim missed are read through libsensors.
 
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
         pub mod heim;
         pub use self::heim::*;
+
+        pub mod disk;
+        pub use self::disk::*;
     } else if #[cfg(any(target_os = "macos", target_os = "windows"))] {
         pub mod sysinfo;
         pub use self::sysinfo::*;
     }
 }
 
-use std::cmp::Ordering;
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+pub mod apple_silicon;
+
+#[cfg(all(feature = "libsensors", target_os = "linux"))]
+pub mod libsensors;
+#[cfg(all(feature = "libsensors", target_os = "linux"))]
+pub use self::libsensors::*;
+
+use std::{cmp::Ordering, collections::HashMap};
 
 use crate::app::Filter;
 
 #[derive(Default, Debug, Clone)]
 pub struct TempHarvest {
     pub name: String,
+    /// The reading, in Celsius for temperatures, RPM for fans, volts for voltages, and watts for
+    /// power.  Temperatures are converted to the chosen unit when they're shown.
     pub temperature: f32,
+    pub kind: SensorKind,
+    pub thresholds: SensorThresholds,
+}
+
+/// The limits a sensor reports for itself, in the same unit as its reading.  Most sensors only
+/// report some of these, if any.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct SensorThresholds {
+    pub min: Option<f32>,
+    pub max: Option<f32>,
+    pub critical: Option<f32>,
+}
+
+/// How close a sensor's reading is to its limits.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SensorStatus {
+    Normal,
+    Warning,
+    Critical,
+}
+
+/// How close to its highest limit a reading can get, as a fraction of that limit, before it's a
+/// warning.
+const SENSOR_WARNING_FRACTION: f32 = 0.9;
+
+impl TempHarvest {
+    /// Returns how close the reading is to the sensor's own limits, or `None` if it doesn't
+    /// report any.  Going over the critical limit is critical, and going over the max or dropping
+    /// under the min is a warning.  Temperatures are also critical over the max if there's no
+    /// critical limit, and a warning once they get close to their highest limit.
+    pub fn get_status(&self) -> Option<SensorStatus> {
+        let SensorThresholds { min, max, critical } = self.thresholds;
+        if min.is_none() && max.is_none() && critical.is_none() {
+            return None;
+        }
+
+        let value = self.temperature;
+        let is_temperature = self.kind == SensorKind::Temperature;
+        let highest_limit = if is_temperature {
+            critical.or(max)
+        } else {
+            critical
+        };
+        let status = match highest_limit {
+            Some(limit) if value >= limit => SensorStatus::Critical,
+            // Fans, voltages, and the like usually run near their limits, so they only warn once
+            // they're over them.
+            Some(limit)
+                if is_temperature && limit > 0.0 && value >= limit * SENSOR_WARNING_FRACTION =>
+            {
+                SensorStatus::Warning
+            }
+            _ if max.map(|max| value >= max).unwrap_or(false) => SensorStatus::Warning,
+            _ if min.map(|min| value < min).unwrap_or(false) => SensorStatus::Warning,
+            _ => SensorStatus::Normal,
+        };
+
+        Some(status)
+    }
 }
 
-#[derive(Clone, Debug)]
+/// What a sensor measures.  Anything other than temperatures is only read through libsensors, or
+/// for power, from the SMC on Apple Silicon.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SensorKind {
+    Temperature,
+    Fan,
+    Voltage,
+    Power,
+}
+
+impl Default for SensorKind {
+    fn default() -> Self {
+        SensorKind::Temperature
+    }
+}
+
+impl SensorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SensorKind::Temperature => "temperature",
+            SensorKind::Fan => "fan",
+            SensorKind::Voltage => "voltage",
+            SensorKind::Power => "power",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TemperatureType {
     Celsius,
     Kelvin,
@@ -36,25 +139,50 @@ impl Default for TemperatureType {
     }
 }
 
+impl TemperatureType {
+    /// The unit's symbol, as shown after a temperature.
+    pub fn as_symbol(self) -> &'static str {
+        match self {
+            TemperatureType::Celsius => "°C",
+            TemperatureType::Kelvin => "K",
+            TemperatureType::Fahrenheit => "°F",
+        }
+    }
+}
+
+/// Converts a temperature in Celsius to the given unit.
+pub fn convert_celsius(celsius: f32, temp_type: &TemperatureType) -> f32 {
+    match temp_type {
+        TemperatureType::Celsius => celsius,
+        TemperatureType::Kelvin => celsius + 273.15,
+        TemperatureType::Fahrenheit => (celsius * (9.0 / 5.0)) + 32.0,
+    }
+}
+
 fn is_temp_filtered(filter: &Option<Filter>, text: &str) -> bool {
     if let Some(filter) = filter {
-        if filter.is_list_ignored {
-            let mut ret = true;
-            for r in &filter.list {
-                if r.is_match(text) {
-                    ret = false;
-                    break;
-                }
+        let mut ret = filter.is_list_ignored;
+        for r in &filter.list {
+            if r.is_match(text) {
+                ret = !filter.is_list_ignored;
+                break;
             }
-            ret
-        } else {
-            true
         }
+        ret
     } else {
         true
     }
 }
 
+/// Returns the user-given alias for a sensor if there is one, or its original name otherwise.
+fn get_temp_alias(aliases: &HashMap<String, String>, name: String) -> String {
+    if let Some(alias) = aliases.get(&name) {
+        alias.clone()
+    } else {
+        name
+    }
+}
+
 fn temp_vec_sort(temperature_vec: &mut Vec<TempHarvest>) {
     // By default, sort temperature, then by alphabetically!
     // TODO: [TEMPS] Allow users to control this.
@@ -1,43 +1,58 @@
 //! Gets temperature data via sysinfo.
 
-use super::{is_temp_filtered, temp_vec_sort, TempHarvest, TemperatureType};
+use std::collections::HashMap;
+
+use super::{
+    get_temp_alias, is_temp_filtered, temp_vec_sort, SensorKind, SensorThresholds, TempHarvest,
+};
 use crate::app::Filter;
 
 pub async fn get_temperature_data(
-    sys: &sysinfo::System, temp_type: &TemperatureType, actually_get: bool, filter: &Option<Filter>,
+    sys: &sysinfo::System, actually_get: bool, filter: &Option<Filter>,
+    aliases: &HashMap<String, String>,
 ) -> crate::utils::error::Result<Option<Vec<TempHarvest>>> {
-    use sysinfo::{ComponentExt, SystemExt};
-
     if !actually_get {
         return Ok(None);
     }
 
-    fn convert_celsius_to_kelvin(celsius: f32) -> f32 {
-        celsius + 273.15
-    }
-
-    fn convert_celsius_to_fahrenheit(celsius: f32) -> f32 {
-        (celsius * (9.0 / 5.0)) + 32.0
-    }
-
     let mut temperature_vec: Vec<TempHarvest> = Vec::new();
 
-    let sensor_data = sys.get_components();
-    for component in sensor_data {
-        let name = component.get_label().to_string();
+    // sysinfo finds few or no sensors on Apple Silicon, so read them from IOKit there, falling
+    // back to sysinfo if that turns up nothing.
+    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+    let sensor_data = {
+        let sensor_data = super::apple_silicon::get_sensor_temperatures();
+        if sensor_data.is_empty() {
+            get_sysinfo_temperatures(sys)
+        } else {
+            sensor_data
+                .into_iter()
+                .map(|(name, temperature)| (name, temperature, SensorThresholds::default()))
+                .collect()
+        }
+    };
+    #[cfg(not(all(target_os = "macos", target_arch = "aarch64")))]
+    let sensor_data = get_sysinfo_temperatures(sys);
 
+    for (name, temperature, thresholds) in sensor_data {
         if is_temp_filtered(filter, &name) {
             temperature_vec.push(TempHarvest {
-                name,
-                temperature: match temp_type {
-                    TemperatureType::Celsius => component.get_temperature(),
-                    TemperatureType::Kelvin => {
-                        convert_celsius_to_kelvin(component.get_temperature())
-                    }
-                    TemperatureType::Fahrenheit => {
-                        convert_celsius_to_fahrenheit(component.get_temperature())
-                    }
-                },
+                name: get_temp_alias(aliases, name),
+                temperature,
+                kind: SensorKind::Temperature,
+                thresholds,
+            });
+        }
+    }
+
+    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+    for (name, watts) in super::apple_silicon::get_power_readings() {
+        if is_temp_filtered(filter, &name) {
+            temperature_vec.push(TempHarvest {
+                name: get_temp_alias(aliases, name),
+                temperature: watts,
+                kind: SensorKind::Power,
+                thresholds: SensorThresholds::default(),
             });
         }
     }
@@ -45,3 +60,23 @@ pub async fn get_temperature_data(
     temp_vec_sort(&mut temperature_vec);
     Ok(Some(temperature_vec))
 }
+
+/// Returns each of sysinfo's sensors' names, readings, and limits in Celsius.  sysinfo's max is
+/// the highest reading it has seen rather than a limit, so only the critical limit is kept.
+fn get_sysinfo_temperatures(sys: &sysinfo::System) -> Vec<(String, f32, SensorThresholds)> {
+    use sysinfo::{ComponentExt, SystemExt};
+
+    sys.get_components()
+        .iter()
+        .map(|component| {
+            (
+                component.get_label().to_string(),
+                component.get_temperature(),
+                SensorThresholds {
+                    critical: component.get_critical(),
+                    ..SensorThresholds::default()
+                },
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,181 @@
+//! Wireless data collection for Linux.
+
+use std::{
+    fs,
+    path::Path,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use super::WirelessHarvest;
+use crate::{app::data_harvester::command::output_with_timeout, utils::error};
+
+const SYS_NET_PATH: &str = "/sys/class/net";
+const PROC_WIRELESS_PATH: &str = "/proc/net/wireless";
+
+/// How often to look for interfaces and run `iw` again.  The network and bitrates rarely change
+/// from one harvest to the next, and the signal strength is read every harvest where it can be.
+const IW_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long `iw` may take before it's given up on until the next refresh.
+const IW_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The link information last read with `iw`, as spawning it for every harvest is slow.
+#[derive(Debug, Default)]
+pub struct WirelessLinks {
+    last_refresh: Option<Instant>,
+    links: Vec<WirelessHarvest>,
+}
+
+pub fn get_wireless_data(
+    actually_get: bool, wireless_links: &mut WirelessLinks,
+) -> error::Result<Option<Vec<WirelessHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let is_due = wireless_links
+        .last_refresh
+        .map(|last_refresh| last_refresh.elapsed() >= IW_REFRESH_INTERVAL)
+        .unwrap_or(true);
+    if is_due {
+        wireless_links.links = read_links()?;
+        wireless_links.last_refresh = Some(Instant::now());
+    }
+
+    // The signal strength changes all the time, and /proc/net/wireless is cheap to read, so
+    // prefer it over the one `iw` last gave.
+    let proc_signals = get_proc_signals();
+    let wireless_vec = wireless_links
+        .links
+        .iter()
+        .map(|link| {
+            let mut harvest = link.clone();
+            if let Some((_, signal_dbm)) = proc_signals
+                .iter()
+                .find(|(interface, _)| *interface == harvest.interface)
+            {
+                harvest.signal_dbm = Some(*signal_dbm);
+            }
+
+            harvest
+        })
+        .collect();
+
+    Ok(Some(wireless_vec))
+}
+
+/// Finds the wireless interfaces, and reads each one's link information with `iw`.
+fn read_links() -> error::Result<Vec<WirelessHarvest>> {
+    let mut interfaces = fs::read_dir(SYS_NET_PATH)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry.path().join("wireless").exists() || entry.path().join("phy80211").exists()
+        })
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    interfaces.sort();
+
+    Ok(interfaces
+        .into_iter()
+        .map(|interface| {
+            get_iw_link(&interface).unwrap_or_else(|| WirelessHarvest {
+                interface,
+                ..WirelessHarvest::default()
+            })
+        })
+        .collect())
+}
+
+/// Reads the link information for an interface with `iw`, which talks to the kernel over nl80211.
+fn get_iw_link(interface: &str) -> Option<WirelessHarvest> {
+    let output = output_with_timeout(
+        Command::new("iw").args(&["dev", interface, "link"]),
+        IW_TIMEOUT,
+    )?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(parse_iw_link(
+        interface,
+        &String::from_utf8_lossy(&output.stdout),
+    ))
+}
+
+/// Parses the output of `iw dev <interface> link`, which looks something like:
+///
+/// ```text
+/// Connected to 11:22:33:44:55:66 (on wlan0)
+///         SSID: home
+///         freq: 5180
+///         signal: -52 dBm
+///         rx bitrate: 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2
+///         tx bitrate: 780.0 MBit/s VHT-MCS 8 80MHz short GI VHT-NSS 2
+/// ```
+///
+/// or just `Not connected.` otherwise.
+fn parse_iw_link(interface: &str, output: &str) -> WirelessHarvest {
+    let mut harvest = WirelessHarvest {
+        interface: interface.to_string(),
+        ..WirelessHarvest::default()
+    };
+
+    let get_first_number = |value: &str| {
+        value
+            .split_whitespace()
+            .next()
+            .and_then(|number| number.parse::<f64>().ok())
+    };
+
+    for line in output.lines() {
+        if let Some((key, value)) = line.trim().split_once(':') {
+            let value = value.trim();
+            match key {
+                "SSID" => harvest.ssid = Some(value.to_string()),
+                "freq" => harvest.frequency_mhz = get_first_number(value).map(|freq| freq as u32),
+                "signal" => harvest.signal_dbm = get_first_number(value),
+                "rx bitrate" => harvest.rx_bitrate_mbps = get_first_number(value),
+                "tx bitrate" => harvest.tx_bitrate_mbps = get_first_number(value),
+                _ => {}
+            }
+        }
+    }
+
+    harvest
+}
+
+/// Reads the signal level of each interface from `/proc/net/wireless`, which looks something
+/// like:
+///
+/// ```text
+/// Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+///  face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+///  wlan0: 0000   58.  -52.  -256        0      0      0      0     12        0
+/// ```
+fn get_proc_signals() -> Vec<(String, f64)> {
+    if !Path::new(PROC_WIRELESS_PATH).exists() {
+        return Vec::new();
+    }
+
+    fs::read_to_string(PROC_WIRELESS_PATH)
+        .map(|contents| {
+            contents
+                .lines()
+                .skip(2)
+                .filter_map(|line| {
+                    let (name, values) = line.trim().split_once(':')?;
+                    let level = values
+                        .split_whitespace()
+                        .nth(2)?
+                        .trim_end_matches('.')
+                        .parse::<f64>()
+                        .ok()?;
+
+                    Some((name.to_string(), level))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
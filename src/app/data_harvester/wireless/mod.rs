@@ -0,0 +1,32 @@
+//! Data collection for wireless network interfaces.
+//!
+//! For Linux, this is handled by nl80211 (via `iw`) every few seconds, with the signal strength
+//! read from `/proc/net/wireless` every harvest where it can be.
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else {
+        #[derive(Debug, Default)]
+        pub struct WirelessLinks;
+
+        /// Wireless information is not supported on this platform.
+        pub fn get_wireless_data(
+            _actually_get: bool, _wireless_links: &mut WirelessLinks,
+        ) -> crate::utils::error::Result<Option<Vec<WirelessHarvest>>> {
+            Ok(None)
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct WirelessHarvest {
+    pub interface: String,
+    /// The name of the network, if the interface is connected to one.
+    pub ssid: Option<String>,
+    pub signal_dbm: Option<f64>,
+    pub tx_bitrate_mbps: Option<f64>,
+    pub rx_bitrate_mbps: Option<f64>,
+    pub frequency_mhz: Option<u32>,
+}
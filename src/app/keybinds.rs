@@ -0,0 +1,500 @@
+//! Remapping actions to different keys, via the `[keybinds]` section of the config file.
+//!
+//! Rather than teaching every key handler about remaps, a remapped key is translated into the
+//! key that its action is bound to by default before it is handled.
+//!
+//! Some actions only apply to certain widgets, and share their default keys with actions of
+//! other widgets, so their remaps only apply in those widgets.
+
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{
+    app::layout_manager::BottomWidgetType,
+    utils::error::{self, BottomError},
+};
+
+/// An action that can be remapped.
+struct Action {
+    name: &'static str,
+    /// The keys bound to this action by default.  Remapping the action unbinds these.
+    default_keys: &'static [char],
+    /// How the default keys show up in the help menu.
+    help_keys: &'static [&'static str],
+    /// The key a remapped key is translated into.
+    target: KeyCode,
+    /// The widgets this action applies to, or all of them if empty.
+    widgets: &'static [BottomWidgetType],
+}
+
+const fn action(
+    name: &'static str, default_keys: &'static [char], help_keys: &'static [&'static str],
+    target: KeyCode,
+) -> Action {
+    Action {
+        name,
+        default_keys,
+        help_keys,
+        target,
+        widgets: &[],
+    }
+}
+
+const fn widget_action(
+    name: &'static str, default_keys: &'static [char], help_keys: &'static [&'static str],
+    target: KeyCode, widgets: &'static [BottomWidgetType],
+) -> Action {
+    Action {
+        name,
+        default_keys,
+        help_keys,
+        target,
+        widgets,
+    }
+}
+
+/// Whether an action for some widgets applies to any of the given widgets, where no widgets
+/// means all of them.
+fn is_in_scope(widgets: &[BottomWidgetType], other_widgets: &[BottomWidgetType]) -> bool {
+    widgets.is_empty()
+        || other_widgets.is_empty()
+        || widgets.iter().any(|widget| other_widgets.contains(widget))
+}
+
+const ACTIONS: [Action; 61] = [
+    action("quit", &['q'], &["q"], KeyCode::Char('q')),
+    action("kill", &['d'], &["dd"], KeyCode::F(9)),
+    action("search", &['/'], &["/"], KeyCode::Char('/')),
+    action("sort", &['s'], &["s"], KeyCode::Char('s')),
+    action("invert_sort", &['I'], &["I"], KeyCode::Char('I')),
+    action("secondary_sort", &['a'], &["a"], KeyCode::Char('a')),
+    action("sort_cpu", &['c'], &["c"], KeyCode::Char('c')),
+    action("sort_mem", &['m'], &["m"], KeyCode::Char('m')),
+    action("sort_pid", &['p'], &["p"], KeyCode::Char('p')),
+    action("sort_name", &['n'], &["n"], KeyCode::Char('n')),
+    action("toggle_command", &['P'], &["P"], KeyCode::Char('P')),
+    action("toggle_percentages", &['%'], &["%"], KeyCode::Char('%')),
+    widget_action(
+        "toggle_tree",
+        &['t'],
+        &["t"],
+        KeyCode::Char('t'),
+        &[BottomWidgetType::Proc],
+    ),
+    action("toggle_total_cpu", &['N'], &["N"], KeyCode::Char('N')),
+    action("column_picker", &['o'], &["o"], KeyCode::Char('o')),
+    action("affinity", &['b'], &["b"], KeyCode::Char('b')),
+    action("threads", &['T'], &["T"], KeyCode::Char('T')),
+    action("pin", &['F'], &["F"], KeyCode::Char('F')),
+    action("copy", &['y'], &["y"], KeyCode::Char('y')),
+    action("copy_all", &['Y'], &["Y"], KeyCode::Char('Y')),
+    action("help", &['?'], &["?"], KeyCode::Char('?')),
+    action("freeze", &['f'], &["f"], KeyCode::Char('f')),
+    action("scroll_earlier", &['['], &["["], KeyCode::Char('[')),
+    action("scroll_later", &[']'], &["]"], KeyCode::Char(']')),
+    action("snapshot", &['E'], &["E"], KeyCode::Char('E')),
+    action("expand", &['e'], &["e"], KeyCode::Char('e')),
+    action("hide_widget", &['z'], &["z"], KeyCode::Char('z')),
+    action("show_widgets", &['Z'], &["Z"], KeyCode::Char('Z')),
+    action("zoom_in", &['+'], &["+"], KeyCode::Char('+')),
+    action("zoom_out", &['-'], &["-"], KeyCode::Char('-')),
+    action("reset_zoom", &['='], &["="], KeyCode::Char('=')),
+    action("up", &['k'], &["k"], KeyCode::Char('k')),
+    action("down", &['j'], &["j"], KeyCode::Char('j')),
+    action("left", &['h'], &["h"], KeyCode::Char('h')),
+    action("right", &['l'], &["l"], KeyCode::Char('l')),
+    action("first", &['g'], &["gg"], KeyCode::Home),
+    action("last", &['G'], &["G"], KeyCode::End),
+    action("widget_left", &['H', 'A'], &["H", "A"], KeyCode::Char('H')),
+    action("widget_right", &['L', 'D'], &["L", "D"], KeyCode::Char('L')),
+    action("widget_up", &['K', 'W'], &["K", "W"], KeyCode::Char('K')),
+    action("widget_down", &['J', 'S'], &["J", "S"], KeyCode::Char('J')),
+    widget_action(
+        "process_suspend",
+        &['x'],
+        &["x"],
+        KeyCode::Char('x'),
+        &[BottomWidgetType::Proc],
+    ),
+    widget_action(
+        "process_resume",
+        &['u'],
+        &["u"],
+        KeyCode::Char('u'),
+        &[BottomWidgetType::Proc],
+    ),
+    widget_action(
+        "process_renice",
+        &['r'],
+        &["r"],
+        KeyCode::Char('r'),
+        &[BottomWidgetType::Proc],
+    ),
+    widget_action(
+        "service_start",
+        &['u'],
+        &["u"],
+        KeyCode::Char('u'),
+        &[BottomWidgetType::Services],
+    ),
+    widget_action(
+        "service_stop",
+        &['x'],
+        &["x"],
+        KeyCode::Char('x'),
+        &[BottomWidgetType::Services],
+    ),
+    widget_action(
+        "service_restart",
+        &['r'],
+        &["r"],
+        KeyCode::Char('r'),
+        &[BottomWidgetType::Services],
+    ),
+    widget_action(
+        "container_start",
+        &['u'],
+        &["u"],
+        KeyCode::Char('u'),
+        &[BottomWidgetType::Docker],
+    ),
+    widget_action(
+        "container_stop",
+        &['x'],
+        &["x"],
+        KeyCode::Char('x'),
+        &[BottomWidgetType::Docker],
+    ),
+    widget_action(
+        "container_restart",
+        &['r'],
+        &["r"],
+        KeyCode::Char('r'),
+        &[BottomWidgetType::Docker],
+    ),
+    action("network_unit", &['B'], &["B"], KeyCode::Char('B')),
+    action("network_scale", &['O'], &["O"], KeyCode::Char('O')),
+    action("network_pin", &['M'], &["M"], KeyCode::Char('M')),
+    action("network_ip", &['V'], &["V"], KeyCode::Char('V')),
+    action("crosshair", &['X'], &["X"], KeyCode::Char('X')),
+    widget_action(
+        "cpu_legend",
+        &['v'],
+        &["v"],
+        KeyCode::Char('v'),
+        &[BottomWidgetType::Cpu, BottomWidgetType::CpuLegend],
+    ),
+    widget_action(
+        "temp_unit",
+        &['U'],
+        &["U"],
+        KeyCode::Char('U'),
+        &[BottomWidgetType::Temp],
+    ),
+    widget_action(
+        "own_processes",
+        &['U'],
+        &["U"],
+        KeyCode::Char('U'),
+        &[BottomWidgetType::Proc],
+    ),
+    widget_action(
+        "disk_io",
+        &['i'],
+        &["i"],
+        KeyCode::Char('i'),
+        &[BottomWidgetType::Disk],
+    ),
+    widget_action(
+        "disk_writers",
+        &['w'],
+        &["w"],
+        KeyCode::Char('w'),
+        &[BottomWidgetType::Disk],
+    ),
+    widget_action(
+        "disk_group",
+        &['t'],
+        &["t"],
+        KeyCode::Char('t'),
+        &[BottomWidgetType::Disk],
+    ),
+];
+
+/// Keys with modifiers that are always bound, and so can't be remapped to.
+const RESERVED_KEYS: [(KeyCode, KeyModifiers); 19] = [
+    (KeyCode::Char('c'), KeyModifiers::CONTROL),
+    (KeyCode::Char('f'), KeyModifiers::CONTROL),
+    (KeyCode::Char('r'), KeyModifiers::CONTROL),
+    (KeyCode::Char('a'), KeyModifiers::CONTROL),
+    (KeyCode::Char('e'), KeyModifiers::CONTROL),
+    (KeyCode::Char('u'), KeyModifiers::CONTROL),
+    (KeyCode::Char('w'), KeyModifiers::CONTROL),
+    (KeyCode::Char('h'), KeyModifiers::CONTROL),
+    (KeyCode::Char('c'), KeyModifiers::ALT),
+    (KeyCode::Char('w'), KeyModifiers::ALT),
+    (KeyCode::Char('r'), KeyModifiers::ALT),
+    (KeyCode::Char('h'), KeyModifiers::ALT),
+    (KeyCode::Char('l'), KeyModifiers::ALT),
+    (KeyCode::F(1), KeyModifiers::NONE),
+    (KeyCode::F(2), KeyModifiers::NONE),
+    (KeyCode::F(3), KeyModifiers::NONE),
+    (KeyCode::F(5), KeyModifiers::NONE),
+    (KeyCode::F(6), KeyModifiers::NONE),
+    (KeyCode::F(9), KeyModifiers::NONE),
+];
+
+/// The width of the key column in the help menu.
+const HELP_KEY_WIDTH: usize = 17;
+
+#[derive(Clone, Debug, Default)]
+pub struct Keybinds {
+    /// Maps each remapped key to the key its action is bound to by default, along with the
+    /// widgets the remap applies to.
+    remapped_keys: HashMap<KeyEvent, Vec<(&'static [BottomWidgetType], KeyCode)>>,
+    /// Default keys that no longer do anything, as their actions were remapped, along with the
+    /// widgets they no longer do anything in.
+    unbound_keys: Vec<(char, &'static [BottomWidgetType])>,
+    /// How each remapped action's default keys show up in the help menu, the widgets the action
+    /// applies to, and what it was remapped to.
+    help_remaps: Vec<(&'static [&'static str], &'static [BottomWidgetType], String)>,
+}
+
+impl Keybinds {
+    /// Builds the remaps from the `[keybinds]` section, which maps action names to keys.  This
+    /// fails if an action is unknown, a key can't be parsed, or two actions end up on one key.
+    pub fn from_config(config: &HashMap<String, String>) -> error::Result<Self> {
+        let mut keybinds = Keybinds::default();
+
+        // Sort them, so any errors are consistent.
+        let mut remaps = config.iter().collect::<Vec<_>>();
+        remaps.sort();
+
+        let mut remapped_actions = Vec::new();
+        for (name, key) in remaps {
+            let action = ACTIONS
+                .iter()
+                .find(|action| action.name == name)
+                .ok_or_else(|| {
+                    BottomError::ConfigError(format!("\"{}\" is not a valid action.", name))
+                })?;
+            let key_event = parse_key(key)?;
+
+            if RESERVED_KEYS.contains(&(key_event.code, key_event.modifiers)) {
+                return Err(BottomError::ConfigError(format!(
+                    "\"{}\" is already reserved and can't be bound to \"{}\".",
+                    key, name
+                )));
+            }
+
+            if let Some((other_name, _, _)) =
+                remapped_actions
+                    .iter()
+                    .find(|(_, other_key_event, other_widgets)| {
+                        *other_key_event == key_event && is_in_scope(other_widgets, action.widgets)
+                    })
+            {
+                return Err(BottomError::ConfigError(format!(
+                    "\"{}\" is bound to both \"{}\" and \"{}\".",
+                    key, other_name, name
+                )));
+            }
+            remapped_actions.push((name.as_str(), key_event, action.widgets));
+
+            keybinds
+                .remapped_keys
+                .entry(key_event)
+                .or_default()
+                .push((action.widgets, action.target));
+            keybinds.unbound_keys.extend(
+                action
+                    .default_keys
+                    .iter()
+                    .map(|default_key| (*default_key, action.widgets)),
+            );
+            keybinds
+                .help_remaps
+                .push((action.help_keys, action.widgets, get_key_name(&key_event)));
+        }
+
+        // Any remapped key that is also the default key of an action that wasn't remapped, in the
+        // same widgets, would take that action over.
+        for (name, key_event, widgets) in &remapped_actions {
+            if let (KeyCode::Char(c), true) = (key_event.code, key_event.modifiers.is_empty()) {
+                if let Some(other_action) = ACTIONS.iter().find(|action| {
+                    action.default_keys.contains(&c)
+                        && is_in_scope(action.widgets, widgets)
+                        && !remapped_actions
+                            .iter()
+                            .any(|(remapped_name, _, _)| *remapped_name == action.name)
+                }) {
+                    return Err(BottomError::ConfigError(format!(
+                        "\"{}\" is bound to both \"{}\" and \"{}\".",
+                        c, other_action.name, name
+                    )));
+                }
+            }
+        }
+
+        Ok(keybinds)
+    }
+
+    /// Translates a key press in a widget into the key its action is bound to by default.
+    /// Returns `None` if the key's action was remapped elsewhere, so it should be ignored.
+    pub fn remap(&self, event: KeyEvent, widget_type: &BottomWidgetType) -> Option<KeyEvent> {
+        let mut modifiers = event.modifiers;
+        if let KeyCode::Char(_) = event.code {
+            // Shift is already reflected in the character.
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+
+        let widget_types = std::slice::from_ref(widget_type);
+        if let Some((_, target)) = self
+            .remapped_keys
+            .get(&KeyEvent::new(event.code, modifiers))
+            .and_then(|targets| {
+                targets
+                    .iter()
+                    .find(|(widgets, _)| is_in_scope(widgets, widget_types))
+            })
+        {
+            Some(KeyEvent::new(*target, KeyModifiers::NONE))
+        } else {
+            match event.code {
+                KeyCode::Char(c)
+                    if modifiers.is_empty()
+                        && self.unbound_keys.iter().any(|(unbound_key, widgets)| {
+                            *unbound_key == c && is_in_scope(widgets, widget_types)
+                        }) =>
+                {
+                    None
+                }
+                _ => Some(event),
+            }
+        }
+    }
+
+    /// Rewrites the keys of a line of the help menu to reflect any remaps, given the widgets
+    /// that the line's section is about, or none for the general section.
+    pub fn remap_help_line(&self, line: &str, section_widgets: &[BottomWidgetType]) -> String {
+        if self.help_remaps.is_empty() {
+            return line.to_string();
+        }
+
+        let (keys, description) = match line.find("  ") {
+            Some(index) => (&line[..index], line[index..].trim_start()),
+            None => return line.to_string(),
+        };
+
+        let mut is_changed = false;
+        let mut remapped_keys: Vec<String> = Vec::new();
+        for key in keys.split(',').map(str::trim).filter(|key| !key.is_empty()) {
+            let unquoted_key = key.trim_matches('\'');
+            if let Some((_, _, new_key)) =
+                self.help_remaps.iter().find(|(help_keys, widgets, _)| {
+                    help_keys.contains(&unquoted_key)
+                        && (widgets.is_empty()
+                            || widgets
+                                .iter()
+                                .any(|widget| section_widgets.contains(widget)))
+                })
+            {
+                is_changed = true;
+                if !remapped_keys.contains(new_key) {
+                    remapped_keys.push(new_key.clone());
+                }
+            } else {
+                remapped_keys.push(key.to_string());
+            }
+        }
+
+        if !is_changed {
+            return line.to_string();
+        }
+
+        let mut new_keys = remapped_keys.join(", ");
+        if keys.trim_end().ends_with(',') {
+            new_keys.push(',');
+        }
+
+        if new_keys.len() + 2 > HELP_KEY_WIDTH {
+            format!("{}  {}", new_keys, description)
+        } else {
+            format!(
+                "{:<width$}{}",
+                new_keys,
+                description,
+                width = HELP_KEY_WIDTH
+            )
+        }
+    }
+}
+
+/// Parses a key like `x`, `ctrl-x`, `alt-x`, or `f4`.
+fn parse_key(key: &str) -> error::Result<KeyEvent> {
+    let invalid_key = || {
+        BottomError::ConfigError(format!(
+            "\"{}\" is not a valid key. Use a single character, optionally prefixed with \"ctrl-\" or \"alt-\", or a function key like \"f4\".",
+            key
+        ))
+    };
+
+    let get_char = |rest: &str| {
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if !c.is_control() && !c.is_whitespace() => Ok(c),
+            _ => Err(invalid_key()),
+        }
+    };
+
+    if let Some(rest) = strip_prefix_ignore_case(key, "ctrl-") {
+        Ok(KeyEvent::new(
+            KeyCode::Char(get_char(rest)?),
+            KeyModifiers::CONTROL,
+        ))
+    } else if let Some(rest) = strip_prefix_ignore_case(key, "alt-") {
+        Ok(KeyEvent::new(
+            KeyCode::Char(get_char(rest)?),
+            KeyModifiers::ALT,
+        ))
+    } else if let (Some(number), true) = (strip_prefix_ignore_case(key, "f"), key.len() > 1) {
+        match number.parse::<u8>() {
+            Ok(number) if (1..=12).contains(&number) => {
+                Ok(KeyEvent::new(KeyCode::F(number), KeyModifiers::NONE))
+            }
+            _ => Err(invalid_key()),
+        }
+    } else {
+        Ok(KeyEvent::new(
+            KeyCode::Char(get_char(key)?),
+            KeyModifiers::NONE,
+        ))
+    }
+}
+
+/// Strips a modifier or key name from the start of a key regardless of its case, leaving the rest
+/// as it was, as the character a key ends with is case-sensitive.
+fn strip_prefix_ignore_case<'a>(key: &'a str, prefix: &str) -> Option<&'a str> {
+    match key.get(..prefix.len()) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => Some(&key[prefix.len()..]),
+        _ => None,
+    }
+}
+
+/// Returns how a key is shown in the help menu.
+fn get_key_name(key_event: &KeyEvent) -> String {
+    let key = match key_event.code {
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(number) => format!("F{}", number),
+        _ => String::default(),
+    };
+
+    if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+        format!("Ctrl-{}", key)
+    } else if key_event.modifiers.contains(KeyModifiers::ALT) {
+        format!("Alt-{}", key)
+    } else {
+        key
+    }
+}
@@ -2,7 +2,10 @@ use crate::error::{BottomError, Result};
 use std::collections::BTreeMap;
 use typed_builder::*;
 
-use crate::constants::DEFAULT_WIDGET_ID;
+use crate::{
+    app::{data_harvester::log_tail::LogHighlightConfig, AppConfigFields},
+    constants::DEFAULT_WIDGET_ID,
+};
 
 /// Represents a more usable representation of the layout, derived from the
 /// config.
@@ -532,7 +535,7 @@ impl BottomLayout {
         }
     }
 
-    pub fn init_basic_default(use_battery: bool) -> Self {
+    pub fn init_basic_default(use_battery: bool, meters: &[BasicMeter]) -> Self {
         let table_widgets = if use_battery {
             vec![
                 BottomCol::builder()
@@ -696,71 +699,149 @@ impl BottomLayout {
             ]
         };
 
-        BottomLayout {
-            total_row_height_ratio: 3,
-            rows: vec![
-                BottomRow::builder()
-                    .canvas_handle_height(true)
-                    .children(vec![BottomCol::builder()
-                        .canvas_handle_width(true)
-                        .children(vec![BottomColRow::builder()
-                            .canvas_handle_height(true)
-                            .children(vec![BottomWidget::builder()
-                                .canvas_handle_width(true)
-                                .widget_type(BottomWidgetType::BasicCpu)
-                                .widget_id(1)
-                                .down_neighbour(Some(2))
-                                .build()])
-                            .build()])
-                        .build()])
-                    .build(),
+        // The CPU meter gets a row to itself, as it can take up a few lines.  The other meters
+        // are shown two to a row.
+        let mut meter_rows: Vec<Vec<BasicMeter>> = Vec::new();
+        for meter in meters {
+            match meter_rows.last_mut() {
+                Some(row)
+                    if *meter != BasicMeter::Cpu && row.len() < 2 && row[0] != BasicMeter::Cpu =>
+                {
+                    row.push(*meter)
+                }
+                _ => meter_rows.push(vec![*meter]),
+            }
+        }
+
+        // Moving up or down between rows goes to the widget in the same position, or the
+        // closest one to it if the other row is shorter.
+        let neighbour_in = |row: Option<&Vec<BasicMeter>>, index: usize| {
+            row.and_then(|row| row.get(index).or_else(|| row.last()))
+                .map(|meter| meter.widget_id())
+        };
+
+        let mut rows: Vec<BottomRow> = meter_rows
+            .iter()
+            .enumerate()
+            .map(|(row_index, row)| {
+                let up_row = row_index.checked_sub(1).and_then(|i| meter_rows.get(i));
+                let down_row = meter_rows.get(row_index + 1);
+
                 BottomRow::builder()
                     .canvas_handle_height(true)
                     .children(vec![BottomCol::builder()
                         .canvas_handle_width(true)
                         .children(vec![BottomColRow::builder()
                             .canvas_handle_height(true)
-                            .children(vec![
-                                BottomWidget::builder()
-                                    .canvas_handle_width(true)
-                                    .widget_type(BottomWidgetType::BasicMem)
-                                    .widget_id(2)
-                                    .up_neighbour(Some(1))
-                                    .down_neighbour(Some(100))
-                                    .right_neighbour(Some(3))
-                                    .build(),
-                                BottomWidget::builder()
-                                    .canvas_handle_width(true)
-                                    .widget_type(BottomWidgetType::BasicNet)
-                                    .widget_id(3)
-                                    .up_neighbour(Some(1))
-                                    .down_neighbour(Some(100))
-                                    .left_neighbour(Some(2))
-                                    .build(),
-                            ])
+                            .children(
+                                row.iter()
+                                    .enumerate()
+                                    .map(|(index, meter)| {
+                                        BottomWidget::builder()
+                                            .canvas_handle_width(true)
+                                            .widget_type(meter.widget_type())
+                                            .widget_id(meter.widget_id())
+                                            .up_neighbour(neighbour_in(up_row, index))
+                                            .down_neighbour(
+                                                neighbour_in(down_row, index).or(Some(100)),
+                                            )
+                                            .left_neighbour(
+                                                index
+                                                    .checked_sub(1)
+                                                    .and_then(|i| row.get(i))
+                                                    .map(|meter| meter.widget_id()),
+                                            )
+                                            .right_neighbour(
+                                                row.get(index + 1).map(|meter| meter.widget_id()),
+                                            )
+                                            .build()
+                                    })
+                                    .collect(),
+                            )
                             .build()])
                         .build()])
-                    .build(),
-                BottomRow::builder()
-                    .canvas_handle_height(true)
-                    .children(vec![BottomCol::builder()
-                        .canvas_handle_width(true)
-                        .children(vec![BottomColRow::builder()
-                            .canvas_handle_height(true)
-                            .children(vec![BottomWidget::builder()
-                                .canvas_handle_width(true)
-                                .widget_type(BottomWidgetType::BasicTables)
-                                .widget_id(100)
-                                .up_neighbour(Some(2))
-                                .build()])
+                    .build()
+            })
+            .collect();
+
+        rows.push(
+            BottomRow::builder()
+                .canvas_handle_height(true)
+                .children(vec![BottomCol::builder()
+                    .canvas_handle_width(true)
+                    .children(vec![BottomColRow::builder()
+                        .canvas_handle_height(true)
+                        .children(vec![BottomWidget::builder()
+                            .canvas_handle_width(true)
+                            .widget_type(BottomWidgetType::BasicTables)
+                            .widget_id(100)
+                            .up_neighbour(neighbour_in(meter_rows.last(), 0))
                             .build()])
                         .build()])
-                    .build(),
-                BottomRow::builder()
-                    .canvas_handle_height(true)
-                    .children(table_widgets)
-                    .build(),
-            ],
+                    .build()])
+                .build(),
+        );
+        rows.push(
+            BottomRow::builder()
+                .canvas_handle_height(true)
+                .children(table_widgets)
+                .build(),
+        );
+
+        BottomLayout {
+            total_row_height_ratio: meter_rows.len() as u32 + 1,
+            rows,
+        }
+    }
+}
+
+/// The default meters shown at the top of basic mode, in order.
+pub const DEFAULT_BASIC_METERS: [BasicMeter; 3] =
+    [BasicMeter::Cpu, BasicMeter::Mem, BasicMeter::Net];
+
+/// A meter that can be shown at the top of basic mode.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BasicMeter {
+    Cpu,
+    Mem,
+    Net,
+    Battery,
+}
+
+impl BasicMeter {
+    pub fn widget_type(self) -> BottomWidgetType {
+        match self {
+            BasicMeter::Cpu => BottomWidgetType::BasicCpu,
+            BasicMeter::Mem => BottomWidgetType::BasicMem,
+            BasicMeter::Net => BottomWidgetType::BasicNet,
+            BasicMeter::Battery => BottomWidgetType::BasicBattery,
+        }
+    }
+
+    /// The IDs are fixed, as basic mode isn't built from a config layout.
+    pub fn widget_id(self) -> u64 {
+        match self {
+            BasicMeter::Cpu => 1,
+            BasicMeter::Mem => 2,
+            BasicMeter::Net => 3,
+            BasicMeter::Battery => 5,
+        }
+    }
+}
+
+impl std::str::FromStr for BasicMeter {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "cpu" => Ok(BasicMeter::Cpu),
+            "mem" | "memory" => Ok(BasicMeter::Mem),
+            "net" | "network" => Ok(BasicMeter::Net),
+            "batt" | "battery" => Ok(BasicMeter::Battery),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid basic mode meter.  Supported meters are cpu, mem, net, and battery.",
+                s
+            ))),
         }
     }
 }
@@ -879,6 +960,18 @@ pub struct BottomWidget {
     /// Bottom right corner when drawn, for mouse click detection.  (x, y)
     #[builder(default = None)]
     pub bottom_right_corner: Option<(u16, u16)>,
+
+    /// The name of the remote host this widget shows, if it shows one.
+    #[builder(default = None)]
+    pub host: Option<String>,
+
+    /// The file this widget follows, if it's a log widget.
+    #[builder(default = None)]
+    pub file: Option<String>,
+
+    /// Patterns to colour lines by, if it's a log widget.
+    #[builder(default = None)]
+    pub highlight: Option<Vec<LogHighlightConfig>>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -896,19 +989,47 @@ pub enum BottomWidgetType {
     BasicCpu,
     BasicMem,
     BasicNet,
+    BasicBattery,
     BasicTables,
     Battery,
+    Services,
+    Ports,
+    Connections,
+    Custom,
+    Remote,
+    CpuHeatmap,
+    Log,
+    KernelLimits,
+    Cgroups,
+    Sched,
+    Docker,
+    Pressure,
+    ProcEvents,
+    Vms,
+    Power,
 }
 
 impl BottomWidgetType {
     pub fn is_widget_table(&self) -> bool {
         use BottomWidgetType::*;
-        matches!(self, Disk | Proc | ProcSort | Temp | CpuLegend)
+        matches!(
+            self,
+            Disk | Proc
+                | ProcSort
+                | Temp
+                | CpuLegend
+                | Services
+                | Ports
+                | Connections
+                | Cgroups
+                | Docker
+                | Vms
+        )
     }
 
     pub fn is_widget_graph(&self) -> bool {
         use BottomWidgetType::*;
-        matches!(self, Cpu | Net | Mem)
+        matches!(self, Cpu | Net | Mem | Custom | Sched | Power)
     }
 
     pub fn get_pretty_name(&self) -> &str {
@@ -921,6 +1042,21 @@ impl BottomWidgetType {
             Temp => "Temperature",
             Disk => "Disks",
             Battery => "Battery",
+            Services => "Services",
+            Ports => "Ports",
+            Connections => "Connections",
+            Custom => "Custom",
+            Remote => "Remote",
+            CpuHeatmap => "CPU Heatmap",
+            Log => "Log",
+            KernelLimits => "Kernel Limits",
+            Cgroups => "Cgroups",
+            Sched => "Scheduler",
+            Docker => "Docker",
+            Pressure => "Pressure",
+            ProcEvents => "Process Events",
+            Vms => "Virtual Machines",
+            Power => "Power",
             _ => "",
         }
     }
@@ -946,6 +1082,21 @@ impl std::str::FromStr for BottomWidgetType {
             "disk" => Ok(BottomWidgetType::Disk),
             "empty" => Ok(BottomWidgetType::Empty),
             "battery" | "batt" => Ok(BottomWidgetType::Battery),
+            "services" | "service" => Ok(BottomWidgetType::Services),
+            "ports" | "port" => Ok(BottomWidgetType::Ports),
+            "connections" | "conn" => Ok(BottomWidgetType::Connections),
+            "custom" | "plugin" => Ok(BottomWidgetType::Custom),
+            "remote" | "host" => Ok(BottomWidgetType::Remote),
+            "cpu_heatmap" | "heatmap" => Ok(BottomWidgetType::CpuHeatmap),
+            "log" | "tail" => Ok(BottomWidgetType::Log),
+            "limits" | "kernel_limits" => Ok(BottomWidgetType::KernelLimits),
+            "cgroups" | "cgroup" => Ok(BottomWidgetType::Cgroups),
+            "sched" | "scheduler" => Ok(BottomWidgetType::Sched),
+            "docker" | "containers" => Ok(BottomWidgetType::Docker),
+            "pressure" | "health" => Ok(BottomWidgetType::Pressure),
+            "proc_events" | "events" => Ok(BottomWidgetType::ProcEvents),
+            "vms" | "libvirt" => Ok(BottomWidgetType::Vms),
+            "power" | "rapl" => Ok(BottomWidgetType::Power),
             _ => Err(BottomError::ConfigError(format!(
                 "\"{}\" is an invalid widget name.
 
@@ -964,6 +1115,36 @@ Supported widget names:
 |           disk           |
 +--------------------------+
 |       batt, battery      |
++--------------------------+
+|     services, service    |
++--------------------------+
+|        ports, port       |
++--------------------------+
+|     connections, conn    |
++--------------------------+
+|      custom, plugin      |
++--------------------------+
+|       remote, host       |
++--------------------------+
+|   cpu_heatmap, heatmap   |
++--------------------------+
+|         log, tail        |
++--------------------------+
+|   limits, kernel_limits  |
++--------------------------+
+|      cgroups, cgroup     |
++--------------------------+
+|     sched, scheduler     |
++--------------------------+
+|    docker, containers    |
++--------------------------+
+|     pressure, health     |
++--------------------------+
+|    proc_events, events   |
++--------------------------+
+|       vms, libvirt       |
++--------------------------+
+|        power, rapl       |
 +--------------------------+
                 ",
                 s
@@ -978,7 +1159,98 @@ pub struct UsedWidgets {
     pub use_mem: bool,
     pub use_net: bool,
     pub use_proc: bool,
+    /// Whether to log process events, which means diffing every process harvest.
+    pub use_proc_events: bool,
     pub use_disk: bool,
     pub use_temp: bool,
     pub use_battery: bool,
+    pub use_services: bool,
+    pub use_ports: bool,
+    pub use_connections: bool,
+    pub use_custom: bool,
+    pub use_remote: bool,
+    pub use_kernel_limits: bool,
+    pub use_cgroups: bool,
+    pub use_sched: bool,
+    pub use_docker: bool,
+    pub use_pressure: bool,
+    pub use_vms: bool,
+    pub use_power: bool,
+}
+
+impl UsedWidgets {
+    /// Works out what data is needed for a set of widgets.
+    pub fn from_widget_types<'a>(widget_types: impl Iterator<Item = &'a BottomWidgetType>) -> Self {
+        use BottomWidgetType::*;
+
+        let mut used_widgets = UsedWidgets::default();
+        for widget_type in widget_types {
+            match widget_type {
+                Cpu | BasicCpu | CpuHeatmap => used_widgets.use_cpu = true,
+                Mem | BasicMem => used_widgets.use_mem = true,
+                Net | BasicNet => used_widgets.use_net = true,
+                Proc => used_widgets.use_proc = true,
+                ProcEvents => {
+                    used_widgets.use_proc = true;
+                    used_widgets.use_proc_events = true;
+                }
+                Disk => used_widgets.use_disk = true,
+                Temp => used_widgets.use_temp = true,
+                Battery | BasicBattery => used_widgets.use_battery = true,
+                Services => used_widgets.use_services = true,
+                Ports => used_widgets.use_ports = true,
+                Connections => used_widgets.use_connections = true,
+                Custom => used_widgets.use_custom = true,
+                Remote => used_widgets.use_remote = true,
+                KernelLimits => used_widgets.use_kernel_limits = true,
+                Cgroups => used_widgets.use_cgroups = true,
+                Sched => used_widgets.use_sched = true,
+                Docker => used_widgets.use_docker = true,
+                Vms => used_widgets.use_vms = true,
+                Power => used_widgets.use_power = true,
+                Pressure => {
+                    // The score is blended from CPU and memory usage too.
+                    used_widgets.use_pressure = true;
+                    used_widgets.use_cpu = true;
+                    used_widgets.use_mem = true;
+                }
+                _ => {}
+            }
+        }
+
+        used_widgets
+    }
+
+    /// Adjusts what's harvested for the parts of the config outside the layout.  This should be
+    /// applied whenever the used widgets are worked out, so these aren't lost.
+    pub fn apply_config_overrides(
+        &mut self, app_config_fields: &AppConfigFields, has_basic_battery: bool,
+        has_process_rules: bool,
+    ) {
+        // Mini mode only shows the meters, so there's no point harvesting anything for the tables.
+        if app_config_fields.use_mini_mode {
+            self.use_proc = false;
+            self.use_proc_events = false;
+            self.use_disk = false;
+            self.use_temp = false;
+            self.use_battery = has_basic_battery;
+        }
+
+        // Process rules need processes to be harvested, even if no process widget is shown.
+        if has_process_rules {
+            self.use_proc = true;
+        }
+
+        // So does showing the busiest processes on the CPU graph.
+        if app_config_fields.show_top_cpu_processes && self.use_cpu {
+            self.use_proc = true;
+        }
+
+        // The session summary covers CPU, memory, and network usage, whether they're shown or not.
+        if app_config_fields.session_summary.is_some() {
+            self.use_cpu = true;
+            self.use_mem = true;
+            self.use_net = true;
+        }
+    }
 }
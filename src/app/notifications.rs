@@ -0,0 +1,305 @@
+//! Sending alerts off the machine, as JSON posted to a webhook or as an email through an SMTP
+//! server, so bottom can act as a small watchdog on a server nobody is looking at.
+//!
+//! Both are sent with `curl`, which takes care of TLS and SMTP, one at a time on a thread of their
+//! own, so a slow or unreachable server doesn't hold up harvesting or drawing.  Failures are
+//! ignored, the same as a desktop notification that can't be shown, and if alerts come in faster
+//! than they can be sent, the extra ones are dropped.
+//!
+//! Anything on curl's command line can be read by every user on the machine through `ps`, so
+//! headers, passwords, and the alerts themselves are instead handed to curl in files only this
+//! user can read.
+
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    process::Command,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, SyncSender},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use chrono::Local;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use super::data_harvester::command::output_with_timeout;
+
+/// How long to give a server before giving up on sending to it, in seconds.
+const SEND_TIMEOUT_SECS: &str = "10";
+
+/// How long to let curl run before killing it.  This is a bit longer than it's told to give the
+/// server, in case it gets stuck before it even starts sending.
+const CURL_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How many alerts can wait to be sent.  Past this, new alerts are dropped.
+const MAX_QUEUED_NOTIFICATIONS: usize = 16;
+
+/// How the alert is given to curl.
+enum CurlInput {
+    /// Posted as the body of an HTTP request.
+    PostData(String),
+    /// Uploaded, which for SMTP sends it as the email.
+    Upload(String),
+}
+
+struct CurlRequest {
+    args: Vec<String>,
+    config: String,
+    input: CurlInput,
+}
+
+/// Queues requests for the thread that runs curl, which is started the first time an alert is
+/// sent.
+static CURL_QUEUE: Lazy<Mutex<SyncSender<CurlRequest>>> = Lazy::new(|| {
+    let (sender, receiver) = mpsc::sync_channel::<CurlRequest>(MAX_QUEUED_NOTIFICATIONS);
+    thread::spawn(move || {
+        for request in receiver {
+            run_curl(request);
+        }
+    });
+    Mutex::new(sender)
+});
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WebhookConfig {
+    /// Where to post the alert, like `https://hooks.example.com/bottom`.
+    pub url: String,
+    /// Extra headers to send, such as for authorization.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EmailConfig {
+    /// The SMTP server, like `smtp://localhost` or `smtps://smtp.example.com:465`.
+    pub url: String,
+    pub from: String,
+    pub to: Vec<String>,
+    /// If there's no username, credentials for the server are looked up in `~/.netrc`, if any.
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+/// Sends an alert to the webhook and by email, for whichever are set.  The details describe the
+/// alert for anything reading it, as names and values like `battery_charge` and `14`.
+pub fn send_notifications(
+    webhook: Option<&WebhookConfig>, email: Option<&EmailConfig>, message: &str,
+    details: &[(String, String)],
+) {
+    if webhook.is_none() && email.is_none() {
+        return;
+    }
+
+    let host_name = get_host_name().unwrap_or_else(|| "unknown".to_string());
+    let time = Local::now();
+
+    if let Some(webhook) = webhook {
+        let body = serde_json::json!({
+            "source": "bottom",
+            "host": host_name,
+            "time": time.to_rfc3339(),
+            "message": message,
+            "details": details.iter().cloned().collect::<HashMap<_, _>>(),
+        });
+
+        let args = vec![
+            "--fail".to_string(),
+            "--silent".to_string(),
+            "--max-time".to_string(),
+            SEND_TIMEOUT_SECS.to_string(),
+            "--header".to_string(),
+            "Content-Type: application/json".to_string(),
+            webhook.url.clone(),
+        ];
+        // Headers often hold tokens, so they all go in the config file.
+        let config = webhook
+            .headers
+            .iter()
+            .map(|(name, value)| get_config_line("header", &format!("{}: {}", name, value)))
+            .collect::<String>();
+        queue_curl(CurlRequest {
+            args,
+            config,
+            input: CurlInput::PostData(body.to_string()),
+        });
+    }
+
+    if let Some(email) = email {
+        let mut body = format!(
+            "From: {}\r\nTo: {}\r\nSubject: bottom on {}: {}\r\nDate: {}\r\n\r\n{}\r\n",
+            email.from,
+            email.to.join(", "),
+            host_name,
+            message,
+            time.to_rfc2822(),
+            message
+        );
+        if !details.is_empty() {
+            body.push_str("\r\n");
+            for (key, value) in details {
+                body.push_str(&format!("{}: {}\r\n", key, value));
+            }
+        }
+
+        // Use STARTTLS if the server offers it; smtps:// URLs always use TLS.
+        let mut args = vec![
+            "--silent".to_string(),
+            "--ssl".to_string(),
+            "--max-time".to_string(),
+            SEND_TIMEOUT_SECS.to_string(),
+            "--url".to_string(),
+            email.url.clone(),
+            "--mail-from".to_string(),
+            email.from.clone(),
+        ];
+        for to in &email.to {
+            args.push("--mail-rcpt".to_string());
+            args.push(to.clone());
+        }
+        let config = match &email.username {
+            Some(username) => get_config_line(
+                "user",
+                &format!(
+                    "{}:{}",
+                    username,
+                    email.password.as_deref().unwrap_or_default()
+                ),
+            ),
+            None => {
+                args.push("--netrc-optional".to_string());
+                String::new()
+            }
+        };
+        queue_curl(CurlRequest {
+            args,
+            config,
+            input: CurlInput::Upload(body),
+        });
+    }
+}
+
+/// Formats an option for a curl config file, quoted so any value is taken as it is.
+fn get_config_line(option: &str, value: &str) -> String {
+    let mut quoted_value = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => quoted_value.push_str("\\\\"),
+            '"' => quoted_value.push_str("\\\""),
+            '\n' => quoted_value.push_str("\\n"),
+            '\r' => quoted_value.push_str("\\r"),
+            '\t' => quoted_value.push_str("\\t"),
+            c => quoted_value.push(c),
+        }
+    }
+    format!("{} = \"{}\"\n", option, quoted_value)
+}
+
+/// Writes a file for curl to read that only this user can read, returning where it is.
+fn write_private_file(contents: &str) -> std::io::Result<PathBuf> {
+    static CONFIG_FILE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    let path = std::env::temp_dir().join(format!(
+        "bottom-curl-{}-{}",
+        std::process::id(),
+        CONFIG_FILE_COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(&path)?;
+    if let Err(err) = file.write_all(contents.as_bytes()) {
+        let _ = fs::remove_file(&path);
+        return Err(err);
+    }
+    Ok(path)
+}
+
+/// Queues a request to be sent in the background, or drops it if too many are already waiting.
+fn queue_curl(request: CurlRequest) {
+    if let Ok(curl_queue) = CURL_QUEUE.lock() {
+        let _ = curl_queue.try_send(request);
+    }
+}
+
+/// Runs `curl`, giving up on it if it takes too long.  The input, and any options in `config`, are
+/// read from private files, which are removed once curl is done.
+fn run_curl(request: CurlRequest) {
+    let CurlRequest {
+        args,
+        config,
+        input,
+    } = request;
+
+    // Never fall back to putting secrets on the command line.
+    let config_path = if config.is_empty() {
+        None
+    } else {
+        match write_private_file(&config) {
+            Ok(config_path) => Some(config_path),
+            Err(_) => return,
+        }
+    };
+    // curl reads data to post from a file if it's given as `@path`.
+    let (input_option, input_prefix, input) = match &input {
+        CurlInput::PostData(input) => ("--data-binary", "@", input),
+        CurlInput::Upload(input) => ("--upload-file", "", input),
+    };
+    let input_path = match write_private_file(input) {
+        Ok(input_path) => input_path,
+        Err(_) => {
+            if let Some(config_path) = config_path {
+                let _ = fs::remove_file(config_path);
+            }
+            return;
+        }
+    };
+
+    let mut command = Command::new("curl");
+    if let Some(config_path) = &config_path {
+        command.arg("--config").arg(config_path);
+    }
+    let input_arg = format!("{}{}", input_prefix, input_path.display());
+    let _ = output_with_timeout(
+        command.args(&args).arg(input_option).arg(input_arg),
+        CURL_TIMEOUT,
+    );
+
+    if let Some(config_path) = config_path {
+        let _ = fs::remove_file(config_path);
+    }
+    let _ = fs::remove_file(input_path);
+}
+
+/// Returns the name of this machine, so alerts from different servers can be told apart.
+#[cfg(target_family = "unix")]
+fn get_host_name() -> Option<String> {
+    let mut buffer = [0_u8; 256];
+    let result =
+        unsafe { libc::gethostname(buffer.as_mut_ptr() as *mut libc::c_char, buffer.len()) };
+    if result != 0 {
+        return None;
+    }
+
+    let length = buffer
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(buffer.len());
+    Some(String::from_utf8_lossy(&buffer[..length]).into_owned())
+}
+
+#[cfg(target_os = "windows")]
+fn get_host_name() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok()
+}
@@ -0,0 +1,175 @@
+//! This file is meant to house (OS specific) implementations on how to read and change which CPUs
+//! a process may run on.
+
+#[cfg(target_os = "windows")]
+use winapi::{
+    shared::{basetsd::DWORD_PTR, minwindef::DWORD, ntdef::HANDLE},
+    um::{
+        handleapi::CloseHandle,
+        processthreadsapi::OpenProcess,
+        winbase::{GetProcessAffinityMask, SetProcessAffinityMask},
+        winnt::{PROCESS_QUERY_INFORMATION, PROCESS_SET_INFORMATION},
+    },
+};
+
+use crate::utils::error::{self, BottomError};
+use crate::Pid;
+
+/// Returns whether the process may run on each CPU, indexed by CPU number.
+#[cfg(target_os = "linux")]
+pub fn get_process_affinity(pid: Pid) -> error::Result<Vec<bool>> {
+    let num_cpus = get_num_cpus();
+    let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let output = unsafe {
+        libc::sched_getaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &mut cpu_set,
+        )
+    };
+    if output != 0 {
+        return Err(get_last_error());
+    }
+
+    Ok((0..num_cpus)
+        .map(|cpu| unsafe { libc::CPU_ISSET(cpu, &cpu_set) })
+        .collect())
+}
+
+/// Restricts the process to the given CPUs, indexed by CPU number.
+#[cfg(target_os = "linux")]
+pub fn set_process_affinity(pid: Pid, cpus: &[bool]) -> error::Result<()> {
+    let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    unsafe { libc::CPU_ZERO(&mut cpu_set) };
+    for (cpu, _) in cpus
+        .iter()
+        .enumerate()
+        .filter(|(_, is_allowed)| **is_allowed)
+    {
+        unsafe { libc::CPU_SET(cpu, &mut cpu_set) };
+    }
+
+    let output = unsafe {
+        libc::sched_setaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &cpu_set,
+        )
+    };
+    if output != 0 {
+        return Err(get_last_error());
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn get_num_cpus() -> usize {
+    let num_cpus = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) };
+    if num_cpus > 0 {
+        std::cmp::min(num_cpus as usize, libc::CPU_SETSIZE as usize)
+    } else {
+        1
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn get_last_error() -> BottomError {
+    let err_code = std::io::Error::last_os_error().raw_os_error();
+    let err = match err_code {
+        Some(libc::ESRCH) => "the target process did not exist.",
+        Some(libc::EPERM) => {
+            "the calling process does not have the permissions to change the target process."
+        }
+        Some(libc::EINVAL) => "the affinity mask does not contain any usable CPUs.",
+        _ => "Unknown error occurred.",
+    };
+
+    if let Some(err_code) = err_code {
+        BottomError::GenericError(format!("Error code {} - {}", err_code, err))
+    } else {
+        BottomError::GenericError(format!("Error code ??? - {}", err))
+    }
+}
+
+/// How many CPUs an affinity mask can cover.
+#[cfg(target_os = "windows")]
+const MASK_BITS: usize = std::mem::size_of::<DWORD_PTR>() * 8;
+
+#[cfg(target_os = "windows")]
+struct Process(HANDLE);
+
+#[cfg(target_os = "windows")]
+impl Process {
+    fn open(pid: DWORD) -> error::Result<Process> {
+        let pc =
+            unsafe { OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_SET_INFORMATION, 0, pid) };
+        if pc.is_null() {
+            return Err(BottomError::GenericError(
+                "Failed to open the process".to_string(),
+            ));
+        }
+        Ok(Process(pc))
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for Process {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.0) };
+    }
+}
+
+/// Returns whether the process may run on each CPU, indexed by CPU number.
+#[cfg(target_os = "windows")]
+pub fn get_process_affinity(pid: Pid) -> error::Result<Vec<bool>> {
+    let process = Process::open(pid as DWORD)?;
+    let mut process_mask: DWORD_PTR = 0;
+    let mut system_mask: DWORD_PTR = 0;
+    let result = unsafe { GetProcessAffinityMask(process.0, &mut process_mask, &mut system_mask) };
+    if result == 0 {
+        return Err(BottomError::GenericError(
+            "Failed to get the process affinity".to_string(),
+        ));
+    }
+
+    // The system mask covers every CPU we can use, so only show up to its highest one.
+    let num_cpus = MASK_BITS - system_mask.leading_zeros() as usize;
+    Ok((0..num_cpus)
+        .map(|cpu| process_mask & (1 << cpu) != 0)
+        .collect())
+}
+
+/// Restricts the process to the given CPUs, indexed by CPU number.
+#[cfg(target_os = "windows")]
+pub fn set_process_affinity(pid: Pid, cpus: &[bool]) -> error::Result<()> {
+    let process = Process::open(pid as DWORD)?;
+    let process_mask = cpus
+        .iter()
+        .enumerate()
+        .take(MASK_BITS)
+        .filter(|(_, is_allowed)| **is_allowed)
+        .fold(0, |mask: DWORD_PTR, (cpu, _)| mask | (1 << cpu));
+    let result = unsafe { SetProcessAffinityMask(process.0, process_mask) };
+    if result == 0 {
+        return Err(BottomError::GenericError(
+            "Failed to set the process affinity".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn get_process_affinity(_pid: Pid) -> error::Result<Vec<bool>> {
+    Err(BottomError::GenericError(
+        "Process affinity is not supported on this platform".to_string(),
+    ))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn set_process_affinity(_pid: Pid, _cpus: &[bool]) -> error::Result<()> {
+    Err(BottomError::GenericError(
+        "Process affinity is not supported on this platform".to_string(),
+    ))
+}
@@ -9,7 +9,6 @@ use winapi::{
 };
 
 /// This file is meant to house (OS specific) implementations on how to kill processes.
-#[cfg(target_family = "unix")]
 use crate::utils::error::BottomError;
 use crate::Pid;
 
@@ -76,3 +75,122 @@ pub fn kill_process_given_pid(pid: Pid) -> crate::utils::error::Result<()> {
 
     Ok(())
 }
+
+/// What can be done to a process besides killing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessAction {
+    Suspend,
+    Resume,
+}
+
+impl ProcessAction {
+    pub fn as_command(&self) -> &'static str {
+        match self {
+            ProcessAction::Suspend => "suspend",
+            ProcessAction::Resume => "resume",
+        }
+    }
+
+    /// How the action is shown once it's done.
+    pub fn as_done(&self) -> &'static str {
+        match self {
+            ProcessAction::Suspend => "Suspended",
+            ProcessAction::Resume => "Resumed",
+        }
+    }
+}
+
+/// Suspends a process with `SIGSTOP`, or resumes it with `SIGCONT`, for unix.
+#[cfg(target_family = "unix")]
+pub fn control_process(pid: Pid, action: ProcessAction) -> crate::utils::error::Result<()> {
+    let signal = match action {
+        ProcessAction::Suspend => libc::SIGSTOP,
+        ProcessAction::Resume => libc::SIGCONT,
+    };
+    kill_process_given_pid(pid, signal as usize)
+}
+
+/// Suspending processes isn't supported on windows.
+#[cfg(target_os = "windows")]
+pub fn control_process(_pid: Pid, _action: ProcessAction) -> crate::utils::error::Result<()> {
+    Err(BottomError::GenericError(
+        "Suspending processes is not supported on this platform".to_string(),
+    ))
+}
+
+/// The lowest niceness, which is the highest priority.
+pub const MIN_NICENESS: i32 = -20;
+/// The highest niceness, which is the lowest priority.
+pub const MAX_NICENESS: i32 = 19;
+
+#[cfg(target_os = "linux")]
+fn clear_errno() {
+    unsafe { *libc::__errno_location() = 0 };
+}
+
+#[cfg(target_os = "macos")]
+fn clear_errno() {
+    unsafe { *libc::__error() = 0 };
+}
+
+/// Returns the niceness of a process, for unix.
+#[cfg(target_family = "unix")]
+pub fn get_process_niceness(pid: Pid) -> crate::utils::error::Result<i32> {
+    // -1 is also a valid niceness, so errors can only be told apart by errno.
+    clear_errno();
+    let niceness = unsafe { libc::getpriority(libc::PRIO_PROCESS, pid as libc::id_t) };
+    if niceness == -1 {
+        if let Some(err_code) = std::io::Error::last_os_error().raw_os_error() {
+            if err_code != 0 {
+                return Err(get_priority_error(err_code));
+            }
+        }
+    }
+
+    Ok(niceness)
+}
+
+/// Sets the niceness of a process with `setpriority`, for unix.
+#[cfg(target_family = "unix")]
+pub fn set_process_niceness(pid: Pid, niceness: i32) -> crate::utils::error::Result<()> {
+    let output = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, niceness) };
+    if output != 0 {
+        let err_code = std::io::Error::last_os_error().raw_os_error();
+        return Err(match err_code {
+            Some(err_code) => get_priority_error(err_code),
+            None => {
+                BottomError::GenericError("Error code ??? - Unknown error occurred.".to_string())
+            }
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(target_family = "unix")]
+fn get_priority_error(err_code: i32) -> BottomError {
+    let err = match err_code {
+        libc::ESRCH => "the target process did not exist.",
+        libc::EPERM => "the calling process does not have the permissions to change the target process.",
+        libc::EACCES => "the calling process does not have the permissions to lower the niceness of the target process.",
+        _ => "Unknown error occurred.",
+    };
+
+    BottomError::GenericError(format!("Error code {} - {}", err_code, err))
+}
+
+/// Changing the niceness of processes isn't supported on windows.
+#[cfg(target_os = "windows")]
+pub fn get_process_niceness(_pid: Pid) -> crate::utils::error::Result<i32> {
+    Err(BottomError::GenericError(
+        "Changing the niceness of processes is not supported on this platform".to_string(),
+    ))
+}
+
+/// Changing the niceness of processes isn't supported on windows.
+#[cfg(target_os = "windows")]
+pub fn set_process_niceness(_pid: Pid, _niceness: i32) -> crate::utils::error::Result<()> {
+    Err(BottomError::GenericError(
+        "Changing the niceness of processes is not supported on this platform".to_string(),
+    ))
+}
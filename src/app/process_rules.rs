@@ -0,0 +1,301 @@
+//! Rules that signal processes once they go over a memory or CPU threshold, set with the
+//! `[[process_rule]]` sections of the config file.
+//!
+//! A rule fires once per process while that process stays over its threshold; it's only
+//! re-armed for that process once it drops back under.  Each firing can also be sent to a webhook
+//! or by email.
+
+use fxhash::FxHashSet;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    data_harvester::processes::ProcessHarvest,
+    notifications::{send_notifications, EmailConfig, WebhookConfig},
+};
+use crate::{
+    units::formatting::{NumberFormat, UnitPrefix},
+    utils::error::{self, BottomError},
+    Pid,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProcessRuleConfig {
+    /// The exact name of the processes to watch.
+    pub name: String,
+    /// Memory usage to trigger at, like `8GB`, `512MiB`, or a number of bytes.
+    pub mem_above: Option<String>,
+    /// CPU usage to trigger at, as a percentage.
+    pub cpu_above: Option<f64>,
+    /// The signal to send, like `TERM` or `SIGKILL`, or its number.  Defaults to `TERM`.  This is
+    /// ignored on Windows, where the process is always terminated.
+    pub signal: Option<String>,
+    pub mode: Option<ProcessRuleMode>,
+    /// Also post to a webhook whenever the rule fires.
+    pub webhook: Option<WebhookConfig>,
+    /// Also send an email whenever the rule fires.
+    pub email: Option<EmailConfig>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessRuleMode {
+    /// Open the kill dialog for the process, and let the user decide.
+    Confirm,
+    /// Only show what would have been sent.
+    DryRun,
+    /// Send the signal right away.
+    Auto,
+}
+
+impl Default for ProcessRuleMode {
+    fn default() -> Self {
+        ProcessRuleMode::Confirm
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ProcessRule {
+    name: String,
+    mem_above_bytes: Option<u64>,
+    cpu_above_percent: Option<f64>,
+    signal: usize,
+    mode: ProcessRuleMode,
+    webhook: Option<WebhookConfig>,
+    email: Option<EmailConfig>,
+}
+
+impl ProcessRule {
+    fn from_config(config: &ProcessRuleConfig) -> error::Result<Self> {
+        if config.mem_above.is_none() && config.cpu_above.is_none() {
+            return Err(BottomError::ConfigError(format!(
+                "the rule for \"{}\" needs a mem_above or cpu_above threshold.",
+                config.name
+            )));
+        }
+
+        Ok(ProcessRule {
+            name: config.name.clone(),
+            mem_above_bytes: match &config.mem_above {
+                Some(mem_above) => Some(parse_bytes(mem_above)?),
+                None => None,
+            },
+            cpu_above_percent: config.cpu_above,
+            signal: match &config.signal {
+                Some(signal) => parse_signal(signal)?,
+                None => DEFAULT_SIGNAL,
+            },
+            mode: config.mode.unwrap_or_default(),
+            webhook: config.webhook.clone(),
+            email: config.email.clone(),
+        })
+    }
+
+    /// Returns why the process broke the rule, if it did.
+    fn get_broken_threshold(
+        &self, process: &ProcessHarvest, number_format: &NumberFormat,
+    ) -> Option<String> {
+        if *process.name != self.name {
+            return None;
+        }
+
+        if let Some(mem_above_bytes) = self.mem_above_bytes {
+            if process.mem_usage_bytes > mem_above_bytes {
+                return Some(format!(
+                    "using {} of memory",
+                    number_format.format_bytes(process.mem_usage_bytes, UnitPrefix::Si, 1)
+                ));
+            }
+        }
+
+        if let Some(cpu_above_percent) = self.cpu_above_percent {
+            if process.cpu_usage_percent > cpu_above_percent {
+                return Some(format!("using {:.1}% CPU", process.cpu_usage_percent));
+            }
+        }
+
+        None
+    }
+}
+
+/// A process that just broke a rule.
+#[derive(Clone, Debug)]
+pub struct ProcessRuleMatch {
+    rule_index: usize,
+    pub name: String,
+    pub pid: Pid,
+    pub signal: usize,
+    pub mode: ProcessRuleMode,
+    /// What the process is doing to break the rule, like "using 8.2GB of memory".
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ProcessRules {
+    rules: Vec<ProcessRule>,
+    /// Which processes are currently over which rules, as rule indices and PIDs.
+    triggered: FxHashSet<(usize, Pid)>,
+}
+
+impl ProcessRules {
+    pub fn from_config(configs: &[ProcessRuleConfig]) -> error::Result<Self> {
+        Ok(ProcessRules {
+            rules: configs
+                .iter()
+                .map(ProcessRule::from_config)
+                .collect::<error::Result<_>>()?,
+            triggered: FxHashSet::default(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Checks the latest processes against the rules, and returns any that just broke one.
+    pub fn check(
+        &mut self, processes: &[ProcessHarvest], number_format: &NumberFormat,
+    ) -> Vec<ProcessRuleMatch> {
+        let mut matches = Vec::new();
+        let mut triggered = FxHashSet::default();
+
+        for (rule_index, rule) in self.rules.iter().enumerate() {
+            for process in processes {
+                if let Some(reason) = rule.get_broken_threshold(process, number_format) {
+                    if !self.triggered.contains(&(rule_index, process.pid)) {
+                        matches.push(ProcessRuleMatch {
+                            rule_index,
+                            name: process.name.to_string(),
+                            pid: process.pid,
+                            signal: rule.signal,
+                            mode: rule.mode,
+                            reason,
+                        });
+                    }
+                    triggered.insert((rule_index, process.pid));
+                }
+            }
+        }
+
+        self.triggered = triggered;
+        matches
+    }
+
+    /// Sends what was done about a match to the rule's webhook and by email, if it has them.
+    pub fn notify(&self, rule_match: &ProcessRuleMatch, message: &str) {
+        if let Some(rule) = self.rules.get(rule_match.rule_index) {
+            send_notifications(
+                rule.webhook.as_ref(),
+                rule.email.as_ref(),
+                message,
+                &[
+                    ("process_name".to_string(), rule_match.name.clone()),
+                    ("process_pid".to_string(), rule_match.pid.to_string()),
+                    ("signal".to_string(), get_signal_name(rule_match.signal)),
+                    ("reason".to_string(), rule_match.reason.clone()),
+                ],
+            );
+        }
+    }
+
+    /// Re-arms the rule for a match that couldn't be handled, so it fires again on the next check
+    /// if the process is still over the threshold.
+    pub fn rearm(&mut self, rule_match: &ProcessRuleMatch) {
+        self.triggered
+            .remove(&(rule_match.rule_index, rule_match.pid));
+    }
+}
+
+/// Parses a size like `8GB`, `1.5GiB`, or `1048576` into bytes.  Units are case-insensitive.
+fn parse_bytes(size: &str) -> error::Result<u64> {
+    let size = size.trim();
+    let split_index = size
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or_else(|| size.len());
+    let (value, unit) = size.split_at(split_index);
+
+    let multiplier: u64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1_000,
+        "m" | "mb" => 1_000_000,
+        "g" | "gb" => 1_000_000_000,
+        "t" | "tb" => 1_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => {
+            return Err(BottomError::ConfigError(format!(
+                "\"{}\" is not a valid size unit.",
+                unit.trim()
+            )))
+        }
+    };
+
+    match value.parse::<f64>() {
+        Ok(value) if value >= 0.0 => Ok((value * multiplier as f64) as u64),
+        _ => Err(BottomError::ConfigError(format!(
+            "\"{}\" is not a valid size.",
+            size
+        ))),
+    }
+}
+
+#[cfg(target_family = "unix")]
+const DEFAULT_SIGNAL: usize = libc::SIGTERM as usize;
+#[cfg(target_os = "windows")]
+const DEFAULT_SIGNAL: usize = 1;
+
+/// Signals that can be given by name, without the `SIG` prefix.
+#[cfg(target_family = "unix")]
+const SIGNAL_NAMES: [(&str, libc::c_int); 11] = [
+    ("HUP", libc::SIGHUP),
+    ("INT", libc::SIGINT),
+    ("QUIT", libc::SIGQUIT),
+    ("ABRT", libc::SIGABRT),
+    ("KILL", libc::SIGKILL),
+    ("USR1", libc::SIGUSR1),
+    ("USR2", libc::SIGUSR2),
+    ("TERM", libc::SIGTERM),
+    ("CONT", libc::SIGCONT),
+    ("STOP", libc::SIGSTOP),
+    ("TSTP", libc::SIGTSTP),
+];
+
+/// Parses a signal name like `TERM` or `SIGTERM`, or a signal number.
+#[cfg(target_family = "unix")]
+fn parse_signal(signal: &str) -> error::Result<usize> {
+    let signal = signal.trim().to_uppercase();
+    if let Ok(number) = signal.parse::<usize>() {
+        if number > 0 && number <= super::MAX_SIGNAL {
+            return Ok(number);
+        }
+    }
+
+    SIGNAL_NAMES
+        .iter()
+        .find(|(name, _)| *name == signal.trim_start_matches("SIG"))
+        .map(|(_, number)| *number as usize)
+        .ok_or_else(|| BottomError::ConfigError(format!("\"{}\" is not a valid signal.", signal)))
+}
+
+#[cfg(target_os = "windows")]
+fn parse_signal(_signal: &str) -> error::Result<usize> {
+    Ok(DEFAULT_SIGNAL)
+}
+
+/// Returns a signal's name, like `SIGTERM`, for messages.
+#[cfg(target_family = "unix")]
+pub fn get_signal_name(signal: usize) -> String {
+    SIGNAL_NAMES
+        .iter()
+        .find(|(_, number)| *number as usize == signal)
+        .map(|(name, _)| format!("SIG{}", name))
+        .unwrap_or_else(|| format!("signal {}", signal))
+}
+
+/// Returns a signal's name for messages.  Windows has no signals, so processes are always
+/// terminated.
+#[cfg(target_os = "windows")]
+pub fn get_signal_name(_signal: usize) -> String {
+    "a termination".to_string()
+}
@@ -0,0 +1,76 @@
+//! This file is meant to house (OS specific) implementations on how to list the threads of a
+//! process, and how much CPU each of them is using.
+
+use fxhash::FxHashMap;
+
+use crate::utils::error;
+#[cfg(not(target_os = "linux"))]
+use crate::utils::error::BottomError;
+use crate::Pid;
+
+#[derive(Clone, Debug)]
+pub struct ThreadInfo {
+    pub tid: Pid,
+    pub name: String,
+    pub state: char,
+    /// How much of one CPU the thread used since the last read.
+    pub cpu_usage_percent: f64,
+}
+
+/// Each thread's total CPU time as of the last read, by thread ID, which is needed to work out
+/// how much each used since then.
+pub type ThreadCpuTimes = FxHashMap<Pid, u64>;
+
+/// Returns the threads of the process, busiest first.  `prev_cpu_times` is updated for the next
+/// read; threads that weren't in it are shown as using no CPU.
+#[cfg(target_os = "linux")]
+pub fn get_process_threads(
+    pid: Pid, prev_cpu_times: &mut ThreadCpuTimes, elapsed_secs: f64,
+) -> error::Result<Vec<ThreadInfo>> {
+    use procfs::process::Process;
+
+    let ticks_per_second = procfs::ticks_per_second()? as f64;
+    let mut cpu_times = ThreadCpuTimes::default();
+    let mut threads = Vec::new();
+
+    // Threads can exit while we're reading them, so skip any that can't be read.
+    for task in Process::new(pid)?.tasks()?.flatten() {
+        if let Ok(stat) = task.stat() {
+            let cpu_time = stat.utime + stat.stime;
+            let cpu_usage_percent = match prev_cpu_times.get(&task.tid) {
+                Some(prev_cpu_time) if elapsed_secs > 0.0 => {
+                    cpu_time.saturating_sub(*prev_cpu_time) as f64 / ticks_per_second / elapsed_secs
+                        * 100.0
+                }
+                _ => 0.0,
+            };
+
+            cpu_times.insert(task.tid, cpu_time);
+            threads.push(ThreadInfo {
+                tid: task.tid,
+                name: stat.comm,
+                state: stat.state,
+                cpu_usage_percent,
+            });
+        }
+    }
+
+    *prev_cpu_times = cpu_times;
+    threads.sort_by(|a, b| {
+        b.cpu_usage_percent
+            .partial_cmp(&a.cpu_usage_percent)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.tid.cmp(&b.tid))
+    });
+
+    Ok(threads)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_process_threads(
+    _pid: Pid, _prev_cpu_times: &mut ThreadCpuTimes, _elapsed_secs: f64,
+) -> error::Result<Vec<ThreadInfo>> {
+    Err(BottomError::GenericError(
+        "Listing threads is not supported on this platform".to_string(),
+    ))
+}
@@ -1,6 +1,7 @@
 use super::ProcWidgetState;
 use crate::{
     data_conversion::ConvertedProcessData,
+    data_harvester::processes::ProcessNameType,
     utils::error::{
         BottomError::{self, QueryError},
         Result,
@@ -467,10 +468,8 @@ impl Query {
         Ok(())
     }
 
-    pub fn check(&self, process: &ConvertedProcessData, is_using_command: bool) -> bool {
-        self.query
-            .iter()
-            .all(|ok| ok.check(process, is_using_command))
+    pub fn check(&self, process: &ConvertedProcessData, name_type: ProcessNameType) -> bool {
+        self.query.iter().all(|ok| ok.check(process, name_type))
     }
 }
 
@@ -507,11 +506,11 @@ impl Or {
         Ok(())
     }
 
-    pub fn check(&self, process: &ConvertedProcessData, is_using_command: bool) -> bool {
+    pub fn check(&self, process: &ConvertedProcessData, name_type: ProcessNameType) -> bool {
         if let Some(rhs) = &self.rhs {
-            self.lhs.check(process, is_using_command) || rhs.check(process, is_using_command)
+            self.lhs.check(process, name_type) || rhs.check(process, name_type)
         } else {
-            self.lhs.check(process, is_using_command)
+            self.lhs.check(process, name_type)
         }
     }
 }
@@ -552,11 +551,11 @@ impl And {
         Ok(())
     }
 
-    pub fn check(&self, process: &ConvertedProcessData, is_using_command: bool) -> bool {
+    pub fn check(&self, process: &ConvertedProcessData, name_type: ProcessNameType) -> bool {
         if let Some(rhs) = &self.rhs {
-            self.lhs.check(process, is_using_command) && rhs.check(process, is_using_command)
+            self.lhs.check(process, name_type) && rhs.check(process, name_type)
         } else {
-            self.lhs.check(process, is_using_command)
+            self.lhs.check(process, name_type)
         }
     }
 }
@@ -662,7 +661,7 @@ impl Prefix {
         Ok(())
     }
 
-    pub fn check(&self, process: &ConvertedProcessData, is_using_command: bool) -> bool {
+    pub fn check(&self, process: &ConvertedProcessData, name_type: ProcessNameType) -> bool {
         fn matches_condition(condition: &QueryComparison, lhs: f64, rhs: f64) -> bool {
             match condition {
                 QueryComparison::Equal => (lhs - rhs).abs() < std::f64::EPSILON,
@@ -674,15 +673,11 @@ impl Prefix {
         }
 
         if let Some(and) = &self.or {
-            and.check(process, is_using_command)
+            and.check(process, name_type)
         } else if let Some((prefix_type, query_content)) = &self.regex_prefix {
             if let StringQuery::Regex(r) = query_content {
                 match prefix_type {
-                    PrefixType::Name => r.is_match(if is_using_command {
-                        process.command.as_str()
-                    } else {
-                        process.name.as_str()
-                    }),
+                    PrefixType::Name => r.is_match(process.get_name(name_type)),
                     PrefixType::Pid => r.is_match(process.pid.to_string().as_str()),
                     PrefixType::State => r.is_match(process.process_state.as_str()),
                     PrefixType::User => {
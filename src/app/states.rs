@@ -1,13 +1,31 @@
-use std::{collections::HashMap, time::Instant};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    path::PathBuf,
+    time::Instant,
+};
 
 use unicode_segmentation::GraphemeCursor;
+use unicode_width::UnicodeWidthStr;
 
-use tui::widgets::TableState;
+use regex::Regex;
+use tui::{style::Style, widgets::TableState};
 
 use crate::{
-    app::{layout_manager::BottomWidgetType, query::*},
+    app::{
+        layout_manager::BottomWidgetType,
+        process_threads::{ThreadCpuTimes, ThreadInfo},
+        query::*,
+    },
     constants,
-    data_harvester::processes::{self, ProcessSorting},
+    data_harvester::{
+        disk_usage::{DiskUsageEntry, DiskUsageScan},
+        docker::ContainerAction,
+        log_tail::{self, LogHarvest},
+        processes::{self, ProcessMemType, ProcessNameType, ProcessSorting},
+        remote::{RemoteData, SshTunnels},
+        services::ServiceAction,
+    },
+    Pid,
 };
 use ProcessSorting::*;
 
@@ -40,6 +58,17 @@ pub struct AppScrollWidgetState {
     pub table_state: TableState,
 }
 
+impl AppScrollWidgetState {
+    /// Moves the selection onto the last row if the table has shrunk out from under it.
+    pub fn clamp_to_rows(&mut self, num_rows: usize) {
+        if self.current_scroll_position >= num_rows {
+            self.current_scroll_position = num_rows.saturating_sub(1);
+            self.previous_scroll_position = 0;
+            self.scroll_direction = ScrollDirection::Down;
+        }
+    }
+}
+
 #[derive(PartialEq)]
 pub enum KillSignal {
     Cancel,
@@ -66,6 +95,96 @@ pub struct AppDeleteDialogState {
     pub keyboard_signal_select: usize,
     pub last_number_press: Option<Instant>,
     pub scroll_pos: usize,
+    /// Whether the dialog is for the marked processes, rather than the selected one.
+    pub is_killing_marked: bool,
+}
+
+/// The state of the disk usage dialog.
+#[derive(Default)]
+pub struct AppDiskUsageState {
+    pub is_showing_disk_usage: bool,
+    /// The mount point the first scan started from.  The dialog won't go above this.
+    pub root_path: PathBuf,
+    pub scan: Option<DiskUsageScan>,
+    /// The selected entry, or the first one if `None`.  This is kept by path, as entries move
+    /// around while the scan finds more.
+    pub selected_path: Option<PathBuf>,
+}
+
+impl AppDiskUsageState {
+    /// Returns the index of the selected entry, or of the first entry if it isn't there.
+    pub fn get_selected_index(&self, entries: &[DiskUsageEntry]) -> usize {
+        self.selected_path
+            .as_ref()
+            .and_then(|selected_path| {
+                entries
+                    .iter()
+                    .position(|entry| &entry.path == selected_path)
+            })
+            .unwrap_or(0)
+    }
+}
+
+/// The state of the process affinity dialog.
+#[derive(Default)]
+pub struct AppAffinityState {
+    pub is_showing_affinity: bool,
+    /// The name of the process being edited, and its PIDs (there are several if grouped).
+    pub process: (String, Vec<Pid>),
+    /// Whether the process may run on each CPU, indexed by CPU number.
+    pub cpus: Vec<bool>,
+    pub selected_index: usize,
+    pub error: Option<String>,
+}
+
+/// The state of the dialog for changing the niceness of processes.
+#[derive(Default)]
+pub struct AppReniceState {
+    pub is_showing_renice: bool,
+    /// The name of the processes being changed, and their PIDs.
+    pub process: (String, Vec<Pid>),
+    /// The niceness typed so far.
+    pub niceness: String,
+    pub error: Option<String>,
+}
+
+/// The state of the process threads dialog.
+#[derive(Default)]
+pub struct AppThreadsState {
+    pub is_showing_threads: bool,
+    /// The name and PID of the process whose threads are shown.
+    pub process: (String, Pid),
+    pub threads: Vec<ThreadInfo>,
+    pub scroll_index: usize,
+    pub error: Option<String>,
+    pub prev_cpu_times: ThreadCpuTimes,
+    pub last_update: Option<Instant>,
+}
+
+/// A row matching the global search, in one of the widgets it covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlobalSearchMatch {
+    pub widget_id: u64,
+    pub row: usize,
+}
+
+/// The state of the search across all widgets, typed into the status line.
+#[derive(Default)]
+pub struct AppGlobalSearchState {
+    pub is_searching: bool,
+    pub query: String,
+    pub matches: Vec<GlobalSearchMatch>,
+    /// The index of the match that was last jumped to, if any.
+    pub current_match: Option<usize>,
+}
+
+/// The state of the process column picker.
+#[derive(Default)]
+pub struct AppColumnPickerState {
+    pub is_showing_picker: bool,
+    /// The widget ID of the process widget the picker is editing.
+    pub widget_id: u64,
+    pub selected_index: usize,
 }
 
 pub struct AppHelpDialogState {
@@ -144,6 +263,8 @@ pub struct ProcessSearchState {
     pub is_ignoring_case: bool,
     pub is_searching_whole_word: bool,
     pub is_searching_with_regex: bool,
+    /// Whether matches are only highlighted, rather than everything else being filtered out.
+    pub is_highlighting_only: bool,
 }
 
 impl Default for ProcessSearchState {
@@ -153,6 +274,7 @@ impl Default for ProcessSearchState {
             is_ignoring_case: true,
             is_searching_whole_word: false,
             is_searching_with_regex: false,
+            is_highlighting_only: false,
         }
     }
 }
@@ -169,11 +291,18 @@ impl ProcessSearchState {
     pub fn search_toggle_regex(&mut self) {
         self.is_searching_with_regex = !self.is_searching_with_regex;
     }
+
+    pub fn search_toggle_highlight_only(&mut self) {
+        self.is_highlighting_only = !self.is_highlighting_only;
+    }
 }
 
 pub struct ColumnInfo {
     pub enabled: bool,
     pub shortcut: Option<&'static str>,
+    /// Whether the user has chosen to hide this column.  Unlike `enabled`, this is not touched by
+    /// grouping or the other column toggles.
+    pub hidden: bool,
     // FIXME: Move column width logic here!
     // pub hard_width: Option<u16>,
     // pub max_soft_width: Option<f64>,
@@ -200,16 +329,32 @@ impl Default for ProcColumn {
             Count,
             Pid,
             ProcessName,
+            ExePath,
             Command,
             CpuPercent,
             Mem,
             MemPercent,
+            VirtMem,
+            Swap,
             ReadPerSecond,
             WritePerSecond,
             TotalRead,
             TotalWrite,
             User,
             State,
+            GpuPercent,
+            GpuMem,
+            TcpRetransmits,
+            TcpRtt,
+            Pod,
+            Namespace,
+            StartTime,
+            Runtime,
+            FdCount,
+            CpuTrend,
+            CpuTime,
+            Capabilities,
+            Seccomp,
         ];
 
         let mut column_mapping = HashMap::new();
@@ -223,6 +368,7 @@ impl Default for ProcColumn {
                         ColumnInfo {
                             enabled: true,
                             shortcut: Some("c"),
+                            hidden: false,
                             // hard_width: None,
                             // max_soft_width: None,
                         },
@@ -234,17 +380,19 @@ impl Default for ProcColumn {
                         ColumnInfo {
                             enabled: true,
                             shortcut: Some("m"),
+                            hidden: false,
                             // hard_width: None,
                             // max_soft_width: None,
                         },
                     );
                 }
-                Mem => {
+                Mem | VirtMem => {
                     column_mapping.insert(
                         column,
                         ColumnInfo {
                             enabled: false,
                             shortcut: Some("m"),
+                            hidden: false,
                             // hard_width: None,
                             // max_soft_width: None,
                         },
@@ -256,17 +404,19 @@ impl Default for ProcColumn {
                         ColumnInfo {
                             enabled: true,
                             shortcut: Some("n"),
+                            hidden: false,
                             // hard_width: None,
                             // max_soft_width: None,
                         },
                     );
                 }
-                Command => {
+                Command | ExePath => {
                     column_mapping.insert(
                         column,
                         ColumnInfo {
                             enabled: false,
                             shortcut: Some("n"),
+                            hidden: false,
                             // hard_width: None,
                             // max_soft_width: None,
                         },
@@ -278,6 +428,7 @@ impl Default for ProcColumn {
                         ColumnInfo {
                             enabled: true,
                             shortcut: Some("p"),
+                            hidden: false,
                             // hard_width: None,
                             // max_soft_width: None,
                         },
@@ -289,6 +440,7 @@ impl Default for ProcColumn {
                         ColumnInfo {
                             enabled: false,
                             shortcut: None,
+                            hidden: false,
                             // hard_width: None,
                             // max_soft_width: None,
                         },
@@ -300,6 +452,39 @@ impl Default for ProcColumn {
                         ColumnInfo {
                             enabled: cfg!(target_family = "unix"),
                             shortcut: None,
+                            hidden: false,
+                        },
+                    );
+                }
+                Swap | CpuTime | Capabilities | Seccomp => {
+                    // Only supported on Linux, and hidden until the user asks for them.
+                    column_mapping.insert(
+                        column,
+                        ColumnInfo {
+                            enabled: cfg!(target_os = "linux"),
+                            shortcut: None,
+                            hidden: true,
+                        },
+                    );
+                }
+                StartTime | Runtime | FdCount | CpuTrend => {
+                    // Hidden until the user asks for them.
+                    column_mapping.insert(
+                        column,
+                        ColumnInfo {
+                            enabled: true,
+                            shortcut: None,
+                            hidden: true,
+                        },
+                    );
+                }
+                GpuPercent | GpuMem | TcpRetransmits | TcpRtt | Pod | Namespace => {
+                    column_mapping.insert(
+                        column,
+                        ColumnInfo {
+                            enabled: false,
+                            shortcut: None,
+                            hidden: false,
                         },
                     );
                 }
@@ -309,6 +494,7 @@ impl Default for ProcColumn {
                         ColumnInfo {
                             enabled: true,
                             shortcut: None,
+                            hidden: false,
                             // hard_width: None,
                             // max_soft_width: None,
                         },
@@ -379,21 +565,115 @@ impl ProcColumn {
         }
     }
 
+    /// Whether a column is actually drawn; it must be both enabled and not hidden by the user.
+    pub fn is_shown(&self, column: &ProcessSorting) -> bool {
+        if let Some(mapping) = self.column_mapping.get(column) {
+            mapping.enabled && !mapping.hidden
+        } else {
+            false
+        }
+    }
+
     pub fn get_enabled_columns_len(&self) -> usize {
         self.ordered_columns
             .iter()
-            .filter_map(|column_type| {
-                if let Some(col_map) = self.column_mapping.get(&column_type) {
-                    if col_map.enabled {
-                        Some(1)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
+            .filter(|column_type| self.is_shown(column_type))
+            .count()
+    }
+
+    /// Returns the columns that are currently drawn, in order.
+    pub fn get_shown_columns(&self) -> Vec<ProcessSorting> {
+        self.ordered_columns
+            .iter()
+            .filter(|column_type| self.is_shown(column_type))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the "slots" the user can pick from, in order.  Some columns are pairs that are
+    /// swapped between by other toggles (for example, PID and count), and these share a slot.
+    pub fn get_picker_slots(&self) -> Vec<ProcessSorting> {
+        let mut slots: Vec<ProcessSorting> = Vec::new();
+        for column in &self.ordered_columns {
+            let slot = get_column_slot(column);
+            if !slots.contains(&slot) {
+                slots.push(slot);
+            }
+        }
+        slots
+    }
+
+    pub fn is_slot_hidden(&self, slot: &ProcessSorting) -> bool {
+        if let Some(mapping) = self.column_mapping.get(slot) {
+            mapping.hidden
+        } else {
+            true
+        }
+    }
+
+    /// Toggles whether all the columns in a slot are hidden.  Returns its new status.
+    pub fn toggle_slot_hidden(&mut self, slot: &ProcessSorting) -> bool {
+        let hidden = !self.is_slot_hidden(slot);
+        self.set_slot_hidden(slot, hidden);
+        hidden
+    }
+
+    pub fn set_slot_hidden(&mut self, slot: &ProcessSorting, hidden: bool) {
+        for column in get_slot_columns(slot) {
+            if let Some(mapping) = self.column_mapping.get_mut(column) {
+                mapping.hidden = hidden;
+            }
+        }
+    }
+
+    /// Moves a slot one position earlier or later.  Returns whether anything moved.
+    pub fn move_slot(&mut self, slot: &ProcessSorting, is_earlier: bool) -> bool {
+        let mut slots = self.get_picker_slots();
+        if let Some(index) = slots.iter().position(|s| s == slot) {
+            let new_index = if is_earlier {
+                if index == 0 {
+                    return false;
                 }
-            })
-            .sum()
+                index - 1
+            } else {
+                if index + 1 >= slots.len() {
+                    return false;
+                }
+                index + 1
+            };
+            slots.swap(index, new_index);
+            self.set_slot_order(&slots);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Rebuilds the column order from a list of slots.  Any slot not in the list keeps its
+    /// relative position, but is moved to the end.
+    pub fn set_slot_order(&mut self, slots: &[ProcessSorting]) {
+        let mut new_order: Vec<ProcessSorting> = Vec::with_capacity(self.ordered_columns.len());
+        for slot in slots {
+            for column in get_slot_columns(slot) {
+                if self.column_mapping.contains_key(column) && !new_order.contains(column) {
+                    new_order.push(column.clone());
+                }
+            }
+        }
+        for column in &self.ordered_columns {
+            if !new_order.contains(column) {
+                new_order.push(column.clone());
+            }
+        }
+        self.ordered_columns = new_order;
+    }
+
+    /// Shows exactly the given slots, in the given order, and hides the rest.
+    pub fn set_custom_columns(&mut self, slots: &[ProcessSorting]) {
+        self.set_slot_order(slots);
+        for slot in self.get_picker_slots() {
+            self.set_slot_hidden(&slot, !slots.contains(&slot));
+        }
     }
 
     /// NOTE: ALWAYS call this when opening the sorted window.
@@ -404,7 +684,7 @@ impl ProcColumn {
             if *column == *proc_sorting_type {
                 break;
             }
-            if self.column_mapping.get(column).unwrap().enabled {
+            if self.is_shown(column) {
                 true_index += 1;
             }
         }
@@ -421,9 +701,12 @@ impl ProcColumn {
 
     pub fn get_column_headers(
         &self, proc_sorting_type: &ProcessSorting, sort_reverse: bool,
+        secondary_sort_keys: &[(ProcessSorting, bool)],
     ) -> Vec<String> {
         const DOWN_ARROW: char = '▼';
         const UP_ARROW: char = '▲';
+        const SECONDARY_DOWN_ARROW: char = '▾';
+        const SECONDARY_UP_ARROW: char = '▴';
 
         // TODO: Gonna have to figure out how to do left/right GUI notation if we add it.
         self.ordered_columns
@@ -435,7 +718,7 @@ impl ProcColumn {
                     command_str = format!("({})", command);
                 }
 
-                if mapping.enabled {
+                if mapping.enabled && !mapping.hidden {
                     Some(format!(
                         "{}{}{}",
                         column_type.to_string(),
@@ -446,6 +729,15 @@ impl ProcColumn {
                             } else {
                                 UP_ARROW
                             }
+                        } else if let Some((_, is_descending)) = secondary_sort_keys
+                            .iter()
+                            .find(|(sort_type, _)| sort_type == column_type)
+                        {
+                            if *is_descending {
+                                SECONDARY_DOWN_ARROW
+                            } else {
+                                SECONDARY_UP_ARROW
+                            }
                         } else {
                             ' '
                         }
@@ -458,25 +750,153 @@ impl ProcColumn {
     }
 }
 
+/// Whether a column sorts in descending order when first selected.  Anything that sorts
+/// alphabetically or by PID goes in ascending order instead, as does the seccomp mode, so
+/// unconfined processes come first.
+pub fn is_sorted_descending_by_default(sort_type: &ProcessSorting) -> bool {
+    !matches!(
+        sort_type,
+        State | Pid | ProcessName | Command | ExePath | Pod | Namespace | Seccomp
+    )
+}
+
+/// Returns the slot a column belongs to.  See [`ProcColumn::get_picker_slots`].
+pub fn get_column_slot(column: &ProcessSorting) -> ProcessSorting {
+    match column {
+        Count => Pid,
+        Command | ExePath => ProcessName,
+        MemPercent | VirtMem => Mem,
+        GpuMem => GpuPercent,
+        TcpRtt => TcpRetransmits,
+        Namespace => Pod,
+        _ => column.clone(),
+    }
+}
+
+/// Returns all columns that belong to a slot.
+pub fn get_slot_columns(slot: &ProcessSorting) -> &'static [ProcessSorting] {
+    match get_column_slot(slot) {
+        Pid => &[Count, Pid],
+        ProcessName => &[ProcessName, ExePath, Command],
+        Mem => &[Mem, MemPercent, VirtMem],
+        CpuPercent => &[CpuPercent],
+        ReadPerSecond => &[ReadPerSecond],
+        WritePerSecond => &[WritePerSecond],
+        TotalRead => &[TotalRead],
+        TotalWrite => &[TotalWrite],
+        User => &[User],
+        State => &[State],
+        GpuPercent => &[GpuPercent, GpuMem],
+        Swap => &[Swap],
+        TcpRetransmits => &[TcpRetransmits, TcpRtt],
+        Pod => &[Pod, Namespace],
+        StartTime => &[StartTime],
+        Runtime => &[Runtime],
+        FdCount => &[FdCount],
+        CpuTrend => &[CpuTrend],
+        CpuTime => &[CpuTime],
+        Capabilities => &[Capabilities],
+        Seccomp => &[Seccomp],
+        _ => &[],
+    }
+}
+
+/// The name of a slot, as shown in the column picker.
+pub fn get_slot_name(slot: &ProcessSorting) -> &'static str {
+    match get_column_slot(slot) {
+        Pid => "PID/Count",
+        ProcessName => "Name/Path/Command",
+        Mem => "Memory",
+        CpuPercent => "CPU%",
+        ReadPerSecond => "R/s",
+        WritePerSecond => "W/s",
+        TotalRead => "T.Read",
+        TotalWrite => "T.Write",
+        User => "User",
+        State => "State",
+        GpuPercent => "GPU",
+        Swap => "Swap",
+        TcpRetransmits => "TCP",
+        Pod => "Pod/Namespace",
+        StartTime => "Start",
+        Runtime => "Runtime",
+        FdCount => "FDs",
+        CpuTrend => "CPU Trend",
+        CpuTime => "CPU ms",
+        Capabilities => "Caps",
+        Seccomp => "Seccomp",
+        _ => "",
+    }
+}
+
+/// How many readings of a pinned process' usage to keep for its sparklines.
+const PINNED_HISTORY_LEN: usize = 15;
+
+pub struct PinnedProcess {
+    pub pid: Pid,
+    /// The process' CPU and memory usage percentages, oldest first.
+    pub cpu_history: VecDeque<f64>,
+    pub mem_history: VecDeque<f64>,
+}
+
+impl PinnedProcess {
+    pub fn new(pid: Pid) -> Self {
+        PinnedProcess {
+            pid,
+            cpu_history: VecDeque::with_capacity(PINNED_HISTORY_LEN),
+            mem_history: VecDeque::with_capacity(PINNED_HISTORY_LEN),
+        }
+    }
+
+    pub fn push(&mut self, cpu_percent: f64, mem_percent: f64) {
+        if self.cpu_history.len() >= PINNED_HISTORY_LEN {
+            self.cpu_history.pop_front();
+            self.mem_history.pop_front();
+        }
+        self.cpu_history.push_back(cpu_percent);
+        self.mem_history.push_back(mem_percent);
+    }
+}
+
 pub struct ProcWidgetState {
     pub process_search_state: ProcessSearchState,
     pub is_grouped: bool,
+    /// Whether processes in a Kubernetes pod are grouped by their pod rather than their name.
+    /// Only used while grouped.
+    pub is_grouped_by_pod: bool,
     pub scroll_state: AppScrollWidgetState,
     pub process_sorting_type: processes::ProcessSorting,
     pub is_process_sort_descending: bool,
-    pub is_using_command: bool,
+    /// Keys used to order processes that tie on the main sort, and whether each is descending.
+    pub secondary_sort_keys: Vec<(processes::ProcessSorting, bool)>,
+    /// What the name column shows.
+    pub name_type: ProcessNameType,
+    /// What the memory column shows.
+    pub mem_type: ProcessMemType,
     pub current_column_index: usize,
     pub is_sort_open: bool,
     pub columns: ProcColumn,
     pub is_tree_mode: bool,
     pub table_width_state: CanvasTableWidthState,
     pub requires_redraw: bool,
+    /// A process kept at the top of the table, whatever the sort and search.
+    pub pinned_process: Option<PinnedProcess>,
+    /// Processes marked with space.  If there are any, kills, suspends, and renices act on all
+    /// of them rather than on the selected process.
+    pub marked_pids: HashSet<Pid>,
+    /// Processes matching the search, if it only highlights them.
+    pub highlighted_pids: HashSet<Pid>,
+    /// Whether to only show processes run by the current user.
+    pub is_showing_own_processes: bool,
 }
 
 impl ProcWidgetState {
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
         is_case_sensitive: bool, is_match_whole_word: bool, is_use_regex: bool, is_grouped: bool,
         show_memory_as_values: bool, is_tree_mode: bool, is_using_command: bool,
+        show_gpu_columns: bool, show_tcp_columns: bool, show_k8s_columns: bool,
+        custom_columns: Option<&[ProcessSorting]>,
     ) -> Self {
         let mut process_search_state = ProcessSearchState::default();
 
@@ -497,33 +917,77 @@ impl ProcWidgetState {
             (processes::ProcessSorting::CpuPercent, true)
         };
 
-        // TODO: If we add customizable columns, this should pull from config
         let mut columns = ProcColumn::default();
+        if let Some(custom_columns) = custom_columns {
+            columns.set_custom_columns(
+                &custom_columns
+                    .iter()
+                    .map(get_column_slot)
+                    .collect::<Vec<_>>(),
+            );
+        }
         columns.set_to_sorted_index_from_type(&process_sorting_type);
         if is_grouped {
             // Normally defaults to showing by PID, toggle count on instead.
             columns.toggle(&ProcessSorting::Count);
             columns.toggle(&ProcessSorting::Pid);
         }
-        if show_memory_as_values {
+        let mem_type = if show_memory_as_values {
             // Normally defaults to showing by percent, toggle value on instead.
             columns.toggle(&ProcessSorting::Mem);
             columns.toggle(&ProcessSorting::MemPercent);
+            ProcessMemType::Resident
+        } else {
+            ProcessMemType::Percent
+        };
+        // Listing the path or command column in the config starts the name column on it.
+        let listed_name_column = custom_columns.and_then(|custom_columns| {
+            custom_columns
+                .iter()
+                .find(|column| get_column_slot(column) == ProcessSorting::ProcessName)
+        });
+        let name_type = match listed_name_column {
+            Some(ProcessSorting::ExePath) => ProcessNameType::Path,
+            Some(ProcessSorting::Command) => ProcessNameType::Command,
+            _ if is_using_command => ProcessNameType::Command,
+            _ => ProcessNameType::Name,
+        };
+        for column in get_slot_columns(&ProcessSorting::ProcessName) {
+            columns.try_set(column, *column == name_type.sorting_type());
+        }
+        if show_gpu_columns && !is_grouped {
+            columns.try_enable(&ProcessSorting::GpuPercent);
+            columns.try_enable(&ProcessSorting::GpuMem);
+        }
+        if show_tcp_columns && !is_grouped {
+            columns.try_enable(&ProcessSorting::TcpRetransmits);
+            columns.try_enable(&ProcessSorting::TcpRtt);
+        }
+        if show_k8s_columns && !is_grouped {
+            columns.try_enable(&ProcessSorting::Pod);
+            columns.try_enable(&ProcessSorting::Namespace);
         }
 
         ProcWidgetState {
             process_search_state,
             is_grouped,
+            is_grouped_by_pod: false,
             scroll_state: AppScrollWidgetState::default(),
             process_sorting_type,
             is_process_sort_descending,
-            is_using_command,
+            secondary_sort_keys: Vec::new(),
+            name_type,
+            mem_type,
             current_column_index: 0,
             is_sort_open: false,
             columns,
             is_tree_mode,
             table_width_state: CanvasTableWidthState::default(),
             requires_redraw: false,
+            pinned_process: None,
+            marked_pids: HashSet::new(),
+            highlighted_pids: HashSet::new(),
+            is_showing_own_processes: false,
         }
     }
 
@@ -534,11 +998,93 @@ impl ProcWidgetState {
     /// Sorry, future me, you're gonna have to refactor this later.  Too busy getting
     /// the feature to work in the first place!  :)
     pub fn update_sorting_with_columns(&mut self) {
+        if let Some(new_sort_type) = self.get_selected_sort_column() {
+            if new_sort_type == self.process_sorting_type {
+                // Just reverse the search if we're reselecting!
+                self.is_process_sort_descending = !(self.is_process_sort_descending);
+            } else {
+                self.is_process_sort_descending = is_sorted_descending_by_default(&new_sort_type);
+                self.process_sorting_type = new_sort_type;
+            }
+        }
+    }
+
+    /// Adds the column selected in the sort widget as a secondary sort key.  Selecting it again
+    /// reverses it, and selecting it a third time removes it.
+    pub fn toggle_secondary_sort_with_columns(&mut self) {
+        if let Some(sort_type) = self.get_selected_sort_column() {
+            if sort_type == self.process_sorting_type {
+                return;
+            }
+
+            let is_default_descending = is_sorted_descending_by_default(&sort_type);
+            if let Some(position) = self
+                .secondary_sort_keys
+                .iter()
+                .position(|(secondary_sort_type, _)| *secondary_sort_type == sort_type)
+            {
+                if self.secondary_sort_keys[position].1 == is_default_descending {
+                    self.secondary_sort_keys[position].1 = !is_default_descending;
+                } else {
+                    self.secondary_sort_keys.remove(position);
+                }
+            } else {
+                self.secondary_sort_keys
+                    .push((sort_type, is_default_descending));
+            }
+        }
+    }
+
+    /// Sets the main sort to the first key, and any others as secondary sort keys.
+    /// In tree mode, the main sort is left alone.
+    pub fn set_sort_keys(&mut self, sort_keys: &[(ProcessSorting, bool)]) {
+        // If a key's column is hidden, use whatever is shown in its place (e.g. memory as a
+        // percentage rather than a value).
+        let sort_keys = sort_keys
+            .iter()
+            .map(|(sort_type, is_descending)| {
+                let shown_sort_type = if self.columns.is_shown(sort_type) {
+                    sort_type
+                } else {
+                    get_slot_columns(sort_type)
+                        .iter()
+                        .find(|column| self.columns.is_shown(column))
+                        .unwrap_or(sort_type)
+                };
+                (shown_sort_type.clone(), *is_descending)
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(((sort_type, is_descending), secondary_sort_keys)) = sort_keys.split_first() {
+            if !self.is_tree_mode {
+                self.process_sorting_type = sort_type.clone();
+                self.is_process_sort_descending = *is_descending;
+                self.columns
+                    .set_to_sorted_index_from_type(&self.process_sorting_type);
+            }
+            self.secondary_sort_keys = secondary_sort_keys.to_vec();
+        }
+    }
+
+    /// Switches any secondary sort key on one of the given columns over to a new column, for
+    /// when a column is swapped out for another in its slot.
+    pub fn replace_secondary_sort_keys(
+        &mut self, sort_types: &[ProcessSorting], new_sort_type: &ProcessSorting,
+    ) {
+        for (sort_type, _) in &mut self.secondary_sort_keys {
+            if sort_types.contains(sort_type) {
+                *sort_type = new_sort_type.clone();
+            }
+        }
+    }
+
+    /// Returns the column currently selected in the sort widget.
+    fn get_selected_sort_column(&self) -> Option<ProcessSorting> {
         let mut true_index = 0;
         let mut enabled_index = 0;
         let target_itx = self.columns.current_scroll_position;
         for column in &self.columns.ordered_columns {
-            let enabled = self.columns.column_mapping.get(column).unwrap().enabled;
+            let enabled = self.columns.is_shown(column);
             if enabled_index == target_itx && enabled {
                 break;
             }
@@ -548,43 +1094,47 @@ impl ProcWidgetState {
             true_index += 1;
         }
 
-        if let Some(new_sort_type) = self.columns.ordered_columns.get(true_index) {
-            if *new_sort_type == self.process_sorting_type {
-                // Just reverse the search if we're reselecting!
-                self.is_process_sort_descending = !(self.is_process_sort_descending);
-            } else {
-                self.process_sorting_type = new_sort_type.clone();
-                match self.process_sorting_type {
-                    ProcessSorting::State
-                    | ProcessSorting::Pid
-                    | ProcessSorting::ProcessName
-                    | ProcessSorting::Command => {
-                        // Also invert anything that uses alphabetical sorting by default.
-                        self.is_process_sort_descending = false;
-                    }
-                    _ => {
-                        self.is_process_sort_descending = true;
-                    }
-                }
-            }
+        self.columns.ordered_columns.get(true_index).cloned()
+    }
+
+    /// Call this after the shown columns or their order change.
+    pub fn on_columns_changed(&mut self) {
+        self.columns
+            .set_to_sorted_index_from_type(&self.process_sorting_type);
+        self.requires_redraw = true;
+    }
+
+    /// Switches what the name column shows.  If the table was sorted by the name column, it stays
+    /// sorted by it.
+    pub fn set_name_type(&mut self, name_type: ProcessNameType) {
+        self.name_type = name_type;
+        let name_column = name_type.sorting_type();
+        for column in get_slot_columns(&ProcessSorting::ProcessName) {
+            self.columns.try_set(column, *column == name_column);
+        }
+
+        if get_column_slot(&self.process_sorting_type) == ProcessSorting::ProcessName {
+            self.process_sorting_type = name_column.clone();
         }
+        self.replace_secondary_sort_keys(
+            get_slot_columns(&ProcessSorting::ProcessName),
+            &name_column,
+        );
     }
 
-    pub fn toggle_command_and_name(&mut self, is_using_command: bool) {
-        if let Some(pn) = self
-            .columns
-            .column_mapping
-            .get_mut(&ProcessSorting::ProcessName)
-        {
-            pn.enabled = !is_using_command;
+    /// Switches what the memory column shows.  If the table was sorted by the memory column, it
+    /// stays sorted by it.
+    pub fn set_mem_type(&mut self, mem_type: ProcessMemType) {
+        self.mem_type = mem_type;
+        let mem_column = mem_type.sorting_type();
+        for column in get_slot_columns(&ProcessSorting::Mem) {
+            self.columns.try_set(column, *column == mem_column);
         }
-        if let Some(c) = self
-            .columns
-            .column_mapping
-            .get_mut(&ProcessSorting::Command)
-        {
-            c.enabled = is_using_command;
+
+        if get_column_slot(&self.process_sorting_type) == ProcessSorting::Mem {
+            self.process_sorting_type = mem_column.clone();
         }
+        self.replace_secondary_sort_keys(get_slot_columns(&ProcessSorting::Mem), &mem_column);
     }
 
     pub fn get_search_cursor_position(&self) -> usize {
@@ -639,6 +1189,17 @@ impl ProcWidgetState {
         self.process_search_state.search_state.reset();
     }
 
+    /// Opens the search with the given query, with the cursor at its end.
+    pub fn set_search_query(&mut self, query: &str) {
+        let search_state = &mut self.process_search_state.search_state;
+        search_state.reset();
+        search_state.is_enabled = true;
+        search_state.current_search_query = query.to_string();
+        search_state.grapheme_cursor = GraphemeCursor::new(query.len(), query.len(), true);
+        search_state.char_cursor_position = UnicodeWidthStr::width(query);
+        self.update_query();
+    }
+
     pub fn search_walk_forward(&mut self, start_position: usize) {
         self.process_search_state
             .search_state
@@ -689,6 +1250,10 @@ impl ProcState {
 pub struct NetWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    /// The rate, in bits per second, that the top of the y-axis is pinned to, if it's pinned.
+    pub max_pin: Option<f64>,
+    /// Whether the legend also splits the rates by IPv4 and IPv6.
+    pub is_showing_ip_versions: bool,
     // pub draw_max_range_cache: f64,
     // pub draw_labels_cache: Vec<String>,
     // pub draw_time_start_cache: f64,
@@ -701,12 +1266,15 @@ impl NetWidgetState {
     pub fn init(
         current_display_time: u64,
         autohide_timer: Option<Instant>,
+        max_pin: Option<f64>,
         // unit_type: DataUnitTypes,
         // scale_type: AxisScaling,
     ) -> Self {
         NetWidgetState {
             current_display_time,
             autohide_timer,
+            max_pin,
+            is_showing_ip_versions: false,
             // draw_max_range_cache: 0.0,
             // draw_labels_cache: vec![],
             // draw_time_start_cache: 0.0,
@@ -744,6 +1312,8 @@ pub struct CpuWidgetState {
     pub autohide_timer: Option<Instant>,
     pub scroll_state: AppScrollWidgetState,
     pub is_multi_graph_mode: bool,
+    /// Whether the legend also shows each entry's average and max usage.
+    pub is_legend_expanded: bool,
     pub table_width_state: CanvasTableWidthState,
 }
 
@@ -755,6 +1325,7 @@ impl CpuWidgetState {
             autohide_timer,
             scroll_state: AppScrollWidgetState::default(),
             is_multi_graph_mode: false,
+            is_legend_expanded: false,
             table_width_state: CanvasTableWidthState::default(),
         }
     }
@@ -785,13 +1356,18 @@ impl CpuState {
 pub struct MemWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    /// Whether to graph memory in absolute units rather than percentages.
+    pub show_as_value: bool,
 }
 
 impl MemWidgetState {
-    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+    pub fn init(
+        current_display_time: u64, autohide_timer: Option<Instant>, show_as_value: bool,
+    ) -> Self {
         MemWidgetState {
             current_display_time,
             autohide_timer,
+            show_as_value,
         }
     }
 }
@@ -832,12 +1408,16 @@ impl TempWidgetState {
 }
 
 pub struct TempState {
+    pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, TempWidgetState>,
 }
 
 impl TempState {
     pub fn init(widget_states: HashMap<u64, TempWidgetState>) -> Self {
-        TempState { widget_states }
+        TempState {
+            force_update: None,
+            widget_states,
+        }
     }
 
     pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut TempWidgetState> {
@@ -864,12 +1444,16 @@ impl DiskWidgetState {
 }
 
 pub struct DiskState {
+    pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, DiskWidgetState>,
 }
 
 impl DiskState {
     pub fn init(widget_states: HashMap<u64, DiskWidgetState>) -> Self {
-        DiskState { widget_states }
+        DiskState {
+            force_update: None,
+            widget_states,
+        }
     }
 
     pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut DiskWidgetState> {
@@ -917,6 +1501,194 @@ impl BatteryState {
     }
 }
 
+pub struct ServicesWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+    /// An action waiting for the user to press the same key again to confirm it.
+    pub pending_action: Option<(ServiceAction, String)>,
+    /// Shown in the title, used for confirmation prompts and errors.
+    pub status_text: Option<String>,
+}
+
+impl ServicesWidgetState {
+    pub fn init() -> Self {
+        ServicesWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+            pending_action: None,
+            status_text: None,
+        }
+    }
+
+    pub fn clear_pending_action(&mut self) {
+        self.pending_action = None;
+        self.status_text = None;
+    }
+}
+
+pub struct ServicesState {
+    pub widget_states: HashMap<u64, ServicesWidgetState>,
+}
+
+impl ServicesState {
+    pub fn init(widget_states: HashMap<u64, ServicesWidgetState>) -> Self {
+        ServicesState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut ServicesWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&ServicesWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct CustomWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl CustomWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        CustomWidgetState {
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
+
+pub struct CustomState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, CustomWidgetState>,
+}
+
+impl CustomState {
+    pub fn init(widget_states: HashMap<u64, CustomWidgetState>) -> Self {
+        CustomState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut CustomWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&CustomWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct SchedWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl SchedWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        SchedWidgetState {
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
+
+pub struct SchedState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, SchedWidgetState>,
+}
+
+impl SchedState {
+    pub fn init(widget_states: HashMap<u64, SchedWidgetState>) -> Self {
+        SchedState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut SchedWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&SchedWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct PowerWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl PowerWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        PowerWidgetState {
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
+
+pub struct PowerState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, PowerWidgetState>,
+}
+
+impl PowerState {
+    pub fn init(widget_states: HashMap<u64, PowerWidgetState>) -> Self {
+        PowerState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut PowerWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&PowerWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct RemoteWidgetState {
+    /// The name of the remote this widget shows.
+    pub host: String,
+}
+
+impl RemoteWidgetState {
+    pub fn init(host: String) -> Self {
+        RemoteWidgetState { host }
+    }
+}
+
+pub struct RemoteState {
+    pub widget_states: HashMap<u64, RemoteWidgetState>,
+    /// Filled in by the remote client threads once they are started.
+    pub remote_data: RemoteData,
+    /// Tunnels to any remotes reached through SSH, which are closed when this is dropped.
+    pub ssh_tunnels: SshTunnels,
+}
+
+impl RemoteState {
+    pub fn init(widget_states: HashMap<u64, RemoteWidgetState>) -> Self {
+        RemoteState {
+            widget_states,
+            remote_data: RemoteData::default(),
+            ssh_tunnels: SshTunnels::default(),
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut RemoteWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&RemoteWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
 #[derive(Default)]
 pub struct ParagraphScrollState {
     pub current_scroll_index: u16,
@@ -938,3 +1710,288 @@ pub struct ConfigCategory {
 pub struct ConfigOption {
     pub set_function: Box<dyn Fn() -> anyhow::Result<()>>,
 }
+
+pub struct PortsWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl PortsWidgetState {
+    pub fn init() -> Self {
+        PortsWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct PortsState {
+    pub widget_states: HashMap<u64, PortsWidgetState>,
+}
+
+impl PortsState {
+    pub fn init(widget_states: HashMap<u64, PortsWidgetState>) -> Self {
+        PortsState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut PortsWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&PortsWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct ConnectionsWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl ConnectionsWidgetState {
+    pub fn init() -> Self {
+        ConnectionsWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct ConnectionsState {
+    pub widget_states: HashMap<u64, ConnectionsWidgetState>,
+}
+
+impl ConnectionsState {
+    pub fn init(widget_states: HashMap<u64, ConnectionsWidgetState>) -> Self {
+        ConnectionsState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut ConnectionsWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&ConnectionsWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct CgroupsWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl CgroupsWidgetState {
+    pub fn init() -> Self {
+        CgroupsWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct CgroupsState {
+    pub widget_states: HashMap<u64, CgroupsWidgetState>,
+}
+
+impl CgroupsState {
+    pub fn init(widget_states: HashMap<u64, CgroupsWidgetState>) -> Self {
+        CgroupsState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut CgroupsWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&CgroupsWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct DockerWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+    /// An action waiting for the user to press the same key again to confirm it.
+    pub pending_action: Option<(ContainerAction, String)>,
+    /// Shown in the title, used for confirmation prompts and errors.
+    pub status_text: Option<String>,
+}
+
+impl DockerWidgetState {
+    pub fn init() -> Self {
+        DockerWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+            pending_action: None,
+            status_text: None,
+        }
+    }
+
+    pub fn clear_pending_action(&mut self) {
+        self.pending_action = None;
+        self.status_text = None;
+    }
+}
+
+pub struct DockerState {
+    pub widget_states: HashMap<u64, DockerWidgetState>,
+}
+
+impl DockerState {
+    pub fn init(widget_states: HashMap<u64, DockerWidgetState>) -> Self {
+        DockerState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut DockerWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&DockerWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct VmsWidgetState {
+    pub scroll_state: AppScrollWidgetState,
+    pub table_width_state: CanvasTableWidthState,
+}
+
+impl VmsWidgetState {
+    pub fn init() -> Self {
+        VmsWidgetState {
+            scroll_state: AppScrollWidgetState::default(),
+            table_width_state: CanvasTableWidthState::default(),
+        }
+    }
+}
+
+pub struct VmsState {
+    pub widget_states: HashMap<u64, VmsWidgetState>,
+}
+
+impl VmsState {
+    pub fn init(widget_states: HashMap<u64, VmsWidgetState>) -> Self {
+        VmsState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut VmsWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&VmsWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct LogWidgetState {
+    pub path: PathBuf,
+    pub lines: VecDeque<String>,
+    /// Why the file couldn't be read the last time, if it couldn't.
+    pub error: Option<String>,
+    /// Lines matching a pattern are drawn in its style.  The first match wins.
+    pub highlights: Vec<(Regex, Style)>,
+    /// How many lines up from the end the widget is scrolled.  At 0, it follows new lines.
+    pub scroll_offset: usize,
+}
+
+impl LogWidgetState {
+    pub fn init(path: PathBuf, highlights: Vec<(Regex, Style)>) -> Self {
+        LogWidgetState {
+            path,
+            lines: VecDeque::new(),
+            error: None,
+            highlights,
+            scroll_offset: 0,
+        }
+    }
+
+    pub fn get_line_style(&self, line: &str) -> Option<Style> {
+        self.highlights
+            .iter()
+            .find(|(pattern, _)| pattern.is_match(line))
+            .map(|(_, style)| *style)
+    }
+}
+
+pub struct LogState {
+    pub widget_states: HashMap<u64, LogWidgetState>,
+}
+
+impl LogState {
+    pub fn init(widget_states: HashMap<u64, LogWidgetState>) -> Self {
+        LogState { widget_states }
+    }
+
+    /// The files each log widget follows, for the collection thread to read.
+    pub fn get_log_files(&self) -> Vec<(u64, PathBuf)> {
+        self.widget_states
+            .iter()
+            .map(|(widget_id, widget_state)| (*widget_id, widget_state.path.clone()))
+            .collect()
+    }
+
+    /// Adds newly read lines to their log widgets.  A widget that's scrolled back stays on the
+    /// same lines as new ones come in.
+    pub fn update(&mut self, log_harvests: Vec<LogHarvest>) {
+        for harvest in log_harvests {
+            if let Some(widget_state) = self.widget_states.get_mut(&harvest.widget_id) {
+                let new_lines = harvest.new_lines.len();
+                widget_state.lines.extend(harvest.new_lines);
+                while widget_state.lines.len() > log_tail::MAX_LINES {
+                    widget_state.lines.pop_front();
+                }
+                widget_state.error = harvest.error;
+
+                if widget_state.scroll_offset > 0 {
+                    widget_state.scroll_offset = (widget_state.scroll_offset + new_lines)
+                        .min(widget_state.lines.len().saturating_sub(1));
+                }
+            }
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut LogWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&LogWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+#[derive(Default)]
+pub struct ProcEventsWidgetState {
+    /// How many events up from the newest the widget is scrolled.  At 0, it follows new events.
+    pub scroll_offset: usize,
+    /// The number of events logged as of the last update, to tell how many have come in since.
+    num_events_seen: usize,
+}
+
+pub struct ProcEventsState {
+    pub widget_states: HashMap<u64, ProcEventsWidgetState>,
+}
+
+impl ProcEventsState {
+    pub fn init(widget_states: HashMap<u64, ProcEventsWidgetState>) -> Self {
+        ProcEventsState { widget_states }
+    }
+
+    /// Keeps any widget that's scrolled back on the same events as new ones are logged.
+    pub fn update(&mut self, num_events: usize, num_events_logged: usize) {
+        for widget_state in self.widget_states.values_mut() {
+            let num_new_events = num_events_logged.saturating_sub(widget_state.num_events_seen);
+            widget_state.num_events_seen = num_events_logged;
+            if widget_state.scroll_offset > 0 {
+                widget_state.scroll_offset =
+                    (widget_state.scroll_offset + num_new_events).min(num_events.saturating_sub(1));
+            }
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut ProcEventsWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&ProcEventsWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
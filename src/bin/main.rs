@@ -8,6 +8,7 @@ use bottom::{canvas, constants::*, data_conversion::*, options::*, *};
 
 use std::{
     boxed::Box,
+    collections::VecDeque,
     io::{stdout, Write},
     panic,
     sync::{
@@ -53,6 +54,29 @@ fn main() -> Result<()> {
         config_path,
     )?;
 
+    // A dump is a single harvest printed to stdout, so none of the terminal setup or threads
+    // below are needed.
+    if matches.is_present("dump") {
+        let dump_format = matches
+            .value_of("dump")
+            .unwrap_or("text")
+            .parse::<dump::DumpFormat>()?;
+        dump::print_dump(&app, dump_format);
+        return Ok(());
+    }
+
+    // Restore saved graph history, if enabled.  A missing or unreadable file just means we start fresh.
+    if let Some(history_path) = &app.app_config_fields.history_path {
+        let _ = app
+            .data_collection
+            .load_history(history_path, app.app_config_fields.history_retention);
+    }
+    let history_writer = app
+        .app_config_fields
+        .history_path
+        .clone()
+        .map(app::data_farmer::HistoryWriter::start);
+
     // Create painter and set colours.
     let mut painter = canvas::Painter::init(
         widget_layout,
@@ -60,6 +84,7 @@ fn main() -> Result<()> {
         app.app_config_fields.use_basic_mode,
         &config,
         get_color_scheme(&matches, &config)?,
+        &app.keybinds,
     )?;
 
     // Create termination mutex and cvar
@@ -70,6 +95,7 @@ fn main() -> Result<()> {
     // Set up input handling
     let (sender, receiver) = mpsc::channel();
     let _input_thread = create_input_thread(sender.clone(), thread_termination_lock.clone());
+    app.event_sender = Some(sender.clone());
 
     // Cleaning loop
     let _cleaning_thread = {
@@ -96,16 +122,50 @@ fn main() -> Result<()> {
         })
     };
 
+    // Start the websocket stream before we take over the terminal, so any error is visible.
+    let stream_server = if let Some(stream_address) = &app.app_config_fields.stream_address {
+        Some(
+            app::data_harvester::stream::StreamServer::start(
+                stream_address,
+                app.app_config_fields.stream_token.clone(),
+            )
+            .with_context(|| format!("Failed to start the stream at {}.", stream_address))?,
+        )
+    } else {
+        None
+    };
+
+    // Process events are worked out from the harvests as they come in, so only if they're shown.
+    app.data_collection.is_logging_process_events = app.used_widgets.use_proc_events;
+
+    // Only bother connecting to remotes if something will show them.
+    if app.used_widgets.use_remote {
+        if let Some(remotes) = &config.remote {
+            let (remote_data, ssh_tunnels) =
+                app::data_harvester::remote::start_remote_clients(remotes);
+            app.remote_state.remote_data = remote_data;
+            app.remote_state.ssh_tunnels = ssh_tunnels;
+        }
+    }
+
     // Event loop
     let (collection_thread_ctrl_sender, collection_thread_ctrl_receiver) = mpsc::channel();
     let _collection_thread = create_collection_thread(
         sender,
         collection_thread_ctrl_receiver,
         thread_termination_lock.clone(),
-        thread_termination_cvar.clone(),
         &app.app_config_fields,
         app.filters.clone(),
         app.used_widgets.clone(),
+        config.plugin.clone().unwrap_or_default(),
+        stream_server,
+        app::data_harvester::exporter::start_exporters(
+            &config.exporter.clone().unwrap_or_default(),
+        ),
+        app.is_showing_fd_counts(),
+        app.is_showing_process_status(),
+        config.battery_alert.clone(),
+        app.log_state.get_log_files(),
     );
 
     // Set up up tui and crossterm
@@ -128,8 +188,27 @@ fn main() -> Result<()> {
     })?;
     let mut first_run = true;
 
+    let terminal_alerts = config
+        .battery_alert
+        .as_ref()
+        .map(|battery_alert| battery_alert.terminal.clone())
+        .unwrap_or_default();
+    if terminal_alerts.contains(&app::data_harvester::batteries::alert::TerminalAlert::Title) {
+        // Not being able to put the title back isn't worth bailing out over.
+        let _ = save_terminal_title(&mut terminal);
+    }
+
+    // Events that were pulled off the channel while catching up on updates, and still need handling.
+    let mut pending_events = VecDeque::new();
+
     while !is_terminated.load(Ordering::SeqCst) {
-        if let Ok(recv) = receiver.recv_timeout(Duration::from_millis(TICK_RATE_IN_MILLISECONDS)) {
+        let recv = if let Some(event) = pending_events.pop_front() {
+            Ok(event)
+        } else {
+            receiver.recv_timeout(Duration::from_millis(TICK_RATE_IN_MILLISECONDS))
+        };
+
+        if let Ok(recv) = recv {
             match recv {
                 BottomEvent::KeyInput(event) => {
                     if handle_key_event_or_break(event, &mut app, &collection_thread_ctrl_sender) {
@@ -144,6 +223,24 @@ fn main() -> Result<()> {
                 BottomEvent::Update(data) => {
                     app.data_collection.eat_data(data);
 
+                    // With fast update rates, harvesting can outpace converting and drawing.  Rather
+                    // than fall further behind, take in any other queued updates now and only
+                    // convert and draw the newest data.
+                    while let Ok(event) = receiver.try_recv() {
+                        if let BottomEvent::Update(data) = event {
+                            app.data_collection.eat_data(data);
+                        } else {
+                            pending_events.push_back(event);
+                            break;
+                        }
+                    }
+
+                    app.check_process_rules();
+
+                    let alerts = std::mem::take(&mut app.data_collection.pending_alerts);
+                    // Missing an alert isn't worth bailing out over.
+                    let _ = write_terminal_alerts(&mut terminal, &terminal_alerts, &alerts);
+
                     // This thing is required as otherwise, some widgets can't draw correctly w/o
                     // some data (or they need to be re-drawn).
                     if first_run {
@@ -164,6 +261,7 @@ fn main() -> Result<()> {
                                 &app.app_config_fields.network_scale_type,
                                 &app.app_config_fields.network_unit_type,
                                 app.app_config_fields.network_use_binary_prefix,
+                                &app.app_config_fields.number_format,
                             );
                             app.canvas_data.network_data_rx = network_data.rx;
                             app.canvas_data.network_data_tx = network_data.tx;
@@ -175,11 +273,21 @@ fn main() -> Result<()> {
                             if let Some(total_tx_display) = network_data.total_tx_display {
                                 app.canvas_data.total_tx_display = total_tx_display;
                             }
+                            app.canvas_data.wireless_display = convert_wireless_data(
+                                &app.data_collection,
+                                &app.app_config_fields.number_format,
+                            );
                         }
 
                         // Disk
                         if app.used_widgets.use_disk {
-                            app.canvas_data.disk_data = convert_disk_row(&app.data_collection);
+                            app.canvas_data.disk_data = convert_disk_row(
+                                &app.data_collection,
+                                &app.app_config_fields.number_format,
+                                app.app_config_fields.show_disk_inodes,
+                                app.app_config_fields.disk_io_per_device,
+                                app.app_config_fields.group_disks_by_fs,
+                            );
                         }
 
                         // Temperatures
@@ -193,11 +301,20 @@ fn main() -> Result<()> {
                                 convert_mem_data_points(&app.data_collection, false);
                             app.canvas_data.swap_data =
                                 convert_swap_data_points(&app.data_collection, false);
-                            let (memory_labels, swap_labels) =
-                                convert_mem_labels(&app.data_collection);
+                            let (memory_labels, swap_labels) = convert_mem_labels(
+                                &app.data_collection,
+                                app.app_config_fields.memory_use_decimal_prefix,
+                                &app.app_config_fields.number_format,
+                            );
 
                             app.canvas_data.mem_labels = memory_labels;
                             app.canvas_data.swap_labels = swap_labels;
+                            app.canvas_data.numa_data = convert_numa_data(
+                                &app.data_collection,
+                                false,
+                                app.app_config_fields.memory_use_decimal_prefix,
+                                &app.app_config_fields.number_format,
+                            );
                         }
 
                         if app.used_widgets.use_cpu {
@@ -208,12 +325,21 @@ fn main() -> Result<()> {
                                 &mut app.canvas_data.cpu_data,
                                 false,
                             );
+                            if app.app_config_fields.show_top_cpu_processes {
+                                app.canvas_data.top_process_cpu_data =
+                                    convert_top_process_cpu_data(&app.data_collection, false);
+                            }
                             app.canvas_data.load_avg_data = app.data_collection.load_avg_harvest;
+                            app.canvas_data.cpu_breakdown =
+                                app.data_collection.cpu_breakdown_harvest;
+                            app.canvas_data.throttle = app.data_collection.throttle_harvest;
                         }
 
                         // Processes
                         if app.used_widgets.use_proc {
                             update_all_process_lists(&mut app);
+                            update_pinned_processes(&mut app);
+                            update_marked_processes(&mut app);
                         }
 
                         // Battery
@@ -221,11 +347,130 @@ fn main() -> Result<()> {
                             app.canvas_data.battery_data =
                                 convert_battery_harvest(&app.data_collection);
                         }
+
+                        // Services
+                        if app.used_widgets.use_services {
+                            app.canvas_data.service_data = convert_service_row(
+                                &app.data_collection,
+                                &app.app_config_fields.number_format,
+                            );
+                            for widget_state in app.services_state.widget_states.values_mut() {
+                                widget_state
+                                    .scroll_state
+                                    .clamp_to_rows(app.canvas_data.service_data.len());
+                            }
+                        }
+
+                        // Listening ports
+                        if app.used_widgets.use_ports {
+                            app.canvas_data.port_data = convert_port_row(&app.data_collection);
+                            for widget_state in app.ports_state.widget_states.values_mut() {
+                                widget_state
+                                    .scroll_state
+                                    .clamp_to_rows(app.canvas_data.port_data.len());
+                            }
+                        }
+
+                        // Established connections
+                        if app.used_widgets.use_connections {
+                            app.canvas_data.connection_data =
+                                convert_connection_row(&app.data_collection);
+                            for widget_state in app.connections_state.widget_states.values_mut() {
+                                widget_state
+                                    .scroll_state
+                                    .clamp_to_rows(app.canvas_data.connection_data.len());
+                            }
+                        }
+
+                        // Control groups
+                        if app.used_widgets.use_cgroups {
+                            app.canvas_data.cgroup_data = convert_cgroup_row(
+                                &app.data_collection,
+                                &app.app_config_fields.number_format,
+                            );
+                            for widget_state in app.cgroups_state.widget_states.values_mut() {
+                                widget_state
+                                    .scroll_state
+                                    .clamp_to_rows(app.canvas_data.cgroup_data.len());
+                            }
+                        }
+
+                        // Docker containers
+                        if app.used_widgets.use_docker {
+                            app.canvas_data.container_data = convert_container_row(
+                                &app.data_collection,
+                                &app.app_config_fields.number_format,
+                            );
+                            for widget_state in app.docker_state.widget_states.values_mut() {
+                                widget_state
+                                    .scroll_state
+                                    .clamp_to_rows(app.canvas_data.container_data.len());
+                            }
+                        }
+
+                        // Virtual machines
+                        if app.used_widgets.use_vms {
+                            app.canvas_data.vm_data = convert_vm_row(
+                                &app.data_collection,
+                                &app.app_config_fields.number_format,
+                            );
+                            for widget_state in app.vms_state.widget_states.values_mut() {
+                                widget_state
+                                    .scroll_state
+                                    .clamp_to_rows(app.canvas_data.vm_data.len());
+                            }
+                        }
+
+                        // Context switches and interrupts
+                        if app.used_widgets.use_sched {
+                            let (context_switch_data, interrupt_data) =
+                                convert_sched_data_points(&app.data_collection, false);
+                            app.canvas_data.context_switch_data = context_switch_data;
+                            app.canvas_data.interrupt_data = interrupt_data;
+                        }
+
+                        // Power consumption
+                        if app.used_widgets.use_power {
+                            let (package_power_data, dram_power_data) =
+                                convert_power_data_points(&app.data_collection, false);
+                            app.canvas_data.package_power_data = package_power_data;
+                            app.canvas_data.dram_power_data = dram_power_data;
+                        }
+
+                        // Log files
+                        app.log_state
+                            .update(std::mem::take(&mut app.data_collection.pending_log_lines));
+
+                        // Process events
+                        app.proc_events_state.update(
+                            app.data_collection.process_events.len(),
+                            app.data_collection.num_process_events_logged,
+                        );
+
+                        // Threads of the process in the threads dialog
+                        app.update_threads_dialog();
+
+                        // Plugins
+                        if app.used_widgets.use_custom {
+                            app.canvas_data.plugin_data =
+                                convert_plugin_data_points(&app.data_collection, false);
+                        }
                     }
                 }
+                BottomEvent::ActionResult(action_result) => {
+                    app.on_action_result(action_result);
+                }
                 BottomEvent::Clean => {
                     app.data_collection
-                        .clean_data(constants::STALE_MAX_MILLISECONDS);
+                        .clean_data(&app.app_config_fields.retention);
+
+                    // Also save periodically so a crash doesn't lose everything.
+                    if let Some(history_writer) = &history_writer {
+                        history_writer.save(
+                            &app.data_collection,
+                            app.app_config_fields.history_retention,
+                        );
+                    }
                 }
             }
         }
@@ -239,8 +484,22 @@ fn main() -> Result<()> {
     *thread_termination_lock.lock().unwrap() = true;
 
     thread_termination_cvar.notify_all();
+    drop(collection_thread_ctrl_sender);
+
+    if let Some(history_writer) = history_writer {
+        history_writer.save(
+            &app.data_collection,
+            app.app_config_fields.history_retention,
+        );
+        history_writer.finish();
+    }
 
     cleanup_terminal(&mut terminal)?;
 
+    if let Some(summary_output) = &app.app_config_fields.session_summary {
+        dump::write_session_summary(&app, summary_output)
+            .context("Unable to write the session summary.")?;
+    }
+
     Ok(())
 }
@@ -1,5 +1,9 @@
 use itertools::izip;
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    time::Instant,
+};
 
 use tui::{
     backend::Backend,
@@ -13,29 +17,37 @@ use tui::{
 
 use canvas_colours::*;
 use dialogs::*;
+use drawing_utils::centered_dialog_rect;
 use screens::*;
 use widgets::*;
 
 use crate::{
     app::{
         self,
-        layout_manager::{BottomColRow, BottomLayout, BottomWidgetType},
+        keybinds::Keybinds,
+        layout_manager::{BottomCol, BottomColRow, BottomLayout, BottomRow, BottomWidgetType},
         App,
     },
     constants::*,
-    data_conversion::{ConvertedBatteryData, ConvertedCpuData, ConvertedProcessData},
+    data_conversion::{
+        ConvertedBatteryData, ConvertedCpuData, ConvertedNumaData, ConvertedPluginData,
+        ConvertedProcessData, ConvertedTopProcessData, ProcessStateSummary,
+    },
     options::Config,
     utils::error,
     utils::error::BottomError,
     Pid,
 };
 
-mod canvas_colours;
+pub mod canvas_colours;
 mod dialogs;
 mod drawing_utils;
+mod frame_budget;
 mod screens;
 mod widgets;
 
+pub use frame_budget::FrameBudget;
+
 /// Point is of time, data
 type Point = (f64, f64);
 
@@ -45,22 +57,38 @@ pub struct DisplayableData {
     pub tx_display: String,
     pub total_rx_display: String,
     pub total_tx_display: String,
+    pub wireless_display: String,
     pub network_data_rx: Vec<Point>,
     pub network_data_tx: Vec<Point>,
     pub disk_data: Vec<Vec<String>>,
     pub temp_sensor_data: Vec<Vec<String>>,
     pub single_process_data: HashMap<Pid, ConvertedProcessData>, // Contains single process data, key is PID
     pub finalized_process_data_map: HashMap<u64, Vec<ConvertedProcessData>>, // What's actually displayed, key is the widget ID.
-    pub stringified_process_data_map: HashMap<u64, Vec<(Vec<(String, Option<String>)>, bool)>>, // Represents the row and whether it is disabled, key is the widget ID
+    pub process_summary: ProcessStateSummary,
 
     pub mem_labels: Option<(String, String)>,
     pub swap_labels: Option<(String, String)>,
 
     pub mem_data: Vec<Point>, // TODO: Switch this and all data points over to a better data structure...
     pub swap_data: Vec<Point>,
+    pub numa_data: Vec<ConvertedNumaData>,
     pub load_avg_data: [f32; 3],
+    pub cpu_breakdown: Option<app::data_harvester::cpu::CpuBreakdown>,
+    pub throttle: Option<app::data_harvester::cpu::ThrottleHarvest>,
     pub cpu_data: Vec<ConvertedCpuData>,
+    pub top_process_cpu_data: Vec<ConvertedTopProcessData>,
     pub battery_data: Vec<ConvertedBatteryData>,
+    pub service_data: Vec<Vec<String>>,
+    pub port_data: Vec<Vec<String>>,
+    pub connection_data: Vec<Vec<String>>,
+    pub cgroup_data: Vec<Vec<String>>,
+    pub container_data: Vec<Vec<String>>,
+    pub vm_data: Vec<Vec<String>>,
+    pub plugin_data: Vec<ConvertedPluginData>,
+    pub context_switch_data: Vec<Point>,
+    pub interrupt_data: Vec<Point>,
+    pub package_power_data: Vec<Point>,
+    pub dram_power_data: Vec<Point>,
 }
 
 #[derive(Debug)]
@@ -108,101 +136,42 @@ pub struct Painter {
     derived_widget_draw_locs: Vec<Vec<Vec<Vec<Rect>>>>,
     widget_layout: BottomLayout,
     table_height_offset: u16,
+    /// The hidden widgets the layout constraints were last generated for.
+    hidden_widgets: HashSet<u64>,
 }
 
 impl Painter {
     pub fn init(
         widget_layout: BottomLayout, table_gap: u16, is_basic_mode: bool, config: &Config,
-        colour_scheme: ColourScheme,
+        colour_scheme: ColourScheme, keybinds: &Keybinds,
     ) -> anyhow::Result<Self> {
-        // Now for modularity; we have to also initialize the base layouts!
-        // We want to do this ONCE and reuse; after this we can just construct
-        // based on the console size.
-
-        let mut row_constraints = Vec::new();
-        let mut col_constraints = Vec::new();
-        let mut col_row_constraints = Vec::new();
-        let mut layout_constraints = Vec::new();
-
-        widget_layout.rows.iter().for_each(|row| {
-            if row.canvas_handle_height {
-                row_constraints.push(Constraint::Length(0));
-            } else {
-                row_constraints.push(Constraint::Ratio(
-                    row.row_height_ratio,
-                    widget_layout.total_row_height_ratio,
-                ));
-            }
-
-            let mut new_col_constraints = Vec::new();
-            let mut new_widget_constraints = Vec::new();
-            let mut new_col_row_constraints = Vec::new();
-            row.children.iter().for_each(|col| {
-                if col.canvas_handle_width {
-                    new_col_constraints.push(Constraint::Length(0));
-                } else {
-                    new_col_constraints
-                        .push(Constraint::Ratio(col.col_width_ratio, row.total_col_ratio));
-                }
-
-                let mut new_new_col_row_constraints = Vec::new();
-                let mut new_new_widget_constraints = Vec::new();
-                col.children.iter().for_each(|col_row| {
-                    if col_row.canvas_handle_height {
-                        new_new_col_row_constraints.push(Constraint::Length(0));
-                    } else if col_row.flex_grow {
-                        new_new_col_row_constraints.push(Constraint::Min(0));
-                    } else {
-                        new_new_col_row_constraints.push(Constraint::Ratio(
-                            col_row.col_row_height_ratio,
-                            col.total_col_row_ratio,
-                        ));
-                    }
-
-                    let mut new_new_new_widget_constraints = Vec::new();
-                    col_row.children.iter().for_each(|widget| {
-                        if widget.canvas_handle_width {
-                            new_new_new_widget_constraints.push(Constraint::Length(0));
-                        } else if widget.flex_grow {
-                            new_new_new_widget_constraints.push(Constraint::Min(0));
-                        } else {
-                            new_new_new_widget_constraints.push(Constraint::Ratio(
-                                widget.width_ratio,
-                                col_row.total_widget_ratio,
-                            ));
-                        }
-                    });
-                    new_new_widget_constraints.push(new_new_new_widget_constraints);
-                });
-                new_col_row_constraints.push(new_new_col_row_constraints);
-                new_widget_constraints.push(new_new_widget_constraints);
-            });
-            col_row_constraints.push(new_col_row_constraints);
-            layout_constraints.push(new_widget_constraints);
-            col_constraints.push(new_col_constraints);
-        });
-
         let mut painter = Painter {
             colours: CanvasColours::default(),
             height: 0,
             width: 0,
             styled_help_text: Vec::default(),
             is_mac_os: cfg!(target_os = "macos"),
-            row_constraints,
-            col_constraints,
-            col_row_constraints,
-            layout_constraints,
+            row_constraints: Vec::default(),
+            col_constraints: Vec::default(),
+            col_row_constraints: Vec::default(),
+            layout_constraints: Vec::default(),
             widget_layout,
             derived_widget_draw_locs: Vec::default(),
             table_height_offset: if is_basic_mode { 2 } else { 4 } + table_gap,
+            hidden_widgets: HashSet::default(),
         };
 
+        // Now for modularity; we have to also initialize the base layouts!
+        // We want to do this ONCE and reuse; after this we can just construct
+        // based on the console size.
+        painter.generate_layout_constraints();
+
         if let ColourScheme::Custom = colour_scheme {
             painter.generate_config_colours(config)?;
         } else {
             painter.generate_colour_scheme(colour_scheme)?;
         }
-        painter.complete_painter_init();
+        painter.complete_painter_init(keybinds);
 
         Ok(painter)
     }
@@ -248,9 +217,139 @@ impl Painter {
         Ok(())
     }
 
+    /// Generates the layout's constraints.  Hidden widgets take up no space, and any row, column,
+    /// or column row that only has hidden widgets is hidden too, with the space going to whatever
+    /// is left.
+    fn generate_layout_constraints(&mut self) {
+        let hidden_widgets = &self.hidden_widgets;
+        let is_col_row_hidden = |col_row: &BottomColRow| {
+            !col_row.children.is_empty()
+                && col_row
+                    .children
+                    .iter()
+                    .all(|widget| hidden_widgets.contains(&widget.widget_id))
+        };
+        let is_col_hidden = |col: &BottomCol| {
+            !col.children.is_empty() && col.children.iter().all(&is_col_row_hidden)
+        };
+        let is_row_hidden =
+            |row: &BottomRow| !row.children.is_empty() && row.children.iter().all(&is_col_hidden);
+
+        let mut row_constraints = Vec::new();
+        let mut col_constraints = Vec::new();
+        let mut col_row_constraints = Vec::new();
+        let mut layout_constraints = Vec::new();
+
+        let total_row_height_ratio = get_visible_ratio_total(
+            self.widget_layout.total_row_height_ratio,
+            self.widget_layout.rows.iter().map(|row| {
+                (
+                    row.row_height_ratio,
+                    row.canvas_handle_height,
+                    false,
+                    is_row_hidden(row),
+                )
+            }),
+        );
+        self.widget_layout.rows.iter().for_each(|row| {
+            if row.canvas_handle_height || is_row_hidden(row) {
+                row_constraints.push(Constraint::Length(0));
+            } else {
+                row_constraints.push(Constraint::Ratio(
+                    row.row_height_ratio,
+                    total_row_height_ratio,
+                ));
+            }
+
+            let total_col_ratio = get_visible_ratio_total(
+                row.total_col_ratio,
+                row.children.iter().map(|col| {
+                    (
+                        col.col_width_ratio,
+                        col.canvas_handle_width,
+                        false,
+                        is_col_hidden(col),
+                    )
+                }),
+            );
+            let mut new_col_constraints = Vec::new();
+            let mut new_widget_constraints = Vec::new();
+            let mut new_col_row_constraints = Vec::new();
+            row.children.iter().for_each(|col| {
+                if col.canvas_handle_width || is_col_hidden(col) {
+                    new_col_constraints.push(Constraint::Length(0));
+                } else {
+                    new_col_constraints
+                        .push(Constraint::Ratio(col.col_width_ratio, total_col_ratio));
+                }
+
+                let total_col_row_ratio = get_visible_ratio_total(
+                    col.total_col_row_ratio,
+                    col.children.iter().map(|col_row| {
+                        (
+                            col_row.col_row_height_ratio,
+                            col_row.canvas_handle_height,
+                            col_row.flex_grow,
+                            is_col_row_hidden(col_row),
+                        )
+                    }),
+                );
+                let mut new_new_col_row_constraints = Vec::new();
+                let mut new_new_widget_constraints = Vec::new();
+                col.children.iter().for_each(|col_row| {
+                    if col_row.canvas_handle_height || is_col_row_hidden(col_row) {
+                        new_new_col_row_constraints.push(Constraint::Length(0));
+                    } else if col_row.flex_grow {
+                        new_new_col_row_constraints.push(Constraint::Min(0));
+                    } else {
+                        new_new_col_row_constraints.push(Constraint::Ratio(
+                            col_row.col_row_height_ratio,
+                            total_col_row_ratio,
+                        ));
+                    }
+
+                    let total_widget_ratio = get_visible_ratio_total(
+                        col_row.total_widget_ratio,
+                        col_row.children.iter().map(|widget| {
+                            (
+                                widget.width_ratio,
+                                widget.canvas_handle_width,
+                                widget.flex_grow,
+                                hidden_widgets.contains(&widget.widget_id),
+                            )
+                        }),
+                    );
+                    let mut new_new_new_widget_constraints = Vec::new();
+                    col_row.children.iter().for_each(|widget| {
+                        if widget.canvas_handle_width || hidden_widgets.contains(&widget.widget_id)
+                        {
+                            new_new_new_widget_constraints.push(Constraint::Length(0));
+                        } else if widget.flex_grow {
+                            new_new_new_widget_constraints.push(Constraint::Min(0));
+                        } else {
+                            new_new_new_widget_constraints
+                                .push(Constraint::Ratio(widget.width_ratio, total_widget_ratio));
+                        }
+                    });
+                    new_new_widget_constraints.push(new_new_new_widget_constraints);
+                });
+                new_col_row_constraints.push(new_new_col_row_constraints);
+                new_widget_constraints.push(new_new_widget_constraints);
+            });
+            col_row_constraints.push(new_col_row_constraints);
+            layout_constraints.push(new_widget_constraints);
+            col_constraints.push(new_col_constraints);
+        });
+
+        self.row_constraints = row_constraints;
+        self.col_constraints = col_constraints;
+        self.col_row_constraints = col_row_constraints;
+        self.layout_constraints = layout_constraints;
+    }
+
     /// Must be run once before drawing, but after setting colours.
     /// This is to set some remaining styles and text.
-    fn complete_painter_init(&mut self) {
+    fn complete_painter_init(&mut self, keybinds: &Keybinds) {
         let mut styled_help_spans = Vec::new();
 
         // Init help text:
@@ -266,13 +365,25 @@ impl Painter {
                 // Not required check but it runs only a few times... so whatever ig, prevents me from
                 // being dumb and leaving a help text section only one line long.
                 if section.len() > 1 {
+                    // Remaps don't apply while searching, and the search syntax isn't a key.
+                    let is_search_section = section[0] == SEARCH_HELP_TEXT[0];
+
                     styled_help_spans.push(Span::raw(""));
                     styled_help_spans
                         .push(Span::styled(section[0], self.colours.table_header_style));
                     styled_help_spans.extend(
                         section[1..]
                             .iter()
-                            .map(|&text| Span::styled(text, self.colours.text_style))
+                            .map(|&text| {
+                                if is_search_section {
+                                    Span::styled(text, self.colours.text_style)
+                                } else {
+                                    Span::styled(
+                                        keybinds.remap_help_line(text, HELP_SECTION_WIDGETS[itx]),
+                                        self.colours.text_style,
+                                    )
+                                }
+                            })
                             .collect::<Vec<_>>(),
                     );
                 }
@@ -285,16 +396,91 @@ impl Painter {
     // FIXME: [CONFIG] write this, should call painter init and any changed colour functions...
     pub fn update_painter_colours(&mut self) {}
 
-    fn draw_frozen_indicator<B: Backend>(&self, f: &mut Frame<'_, B>, draw_loc: Rect) {
+    /// Draws the global search if it's open, the status message if there is one, or otherwise
+    /// what the display is frozen at.
+    fn draw_status_line<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect) {
+        let draw_loc = Layout::default()
+            .horizontal_margin(1)
+            .constraints([Constraint::Length(1)])
+            .split(draw_loc)[0];
+
+        let global_search_state = &app_state.global_search_state;
+        if global_search_state.is_searching {
+            let match_text = if global_search_state.query.is_empty() {
+                "Search all widgets, Enter/Down for next match, Up for previous, Esc to close"
+                    .to_string()
+            } else if let Some((match_index, current_match)) =
+                global_search_state.current_match.and_then(|match_index| {
+                    global_search_state
+                        .matches
+                        .get(match_index)
+                        .map(|current_match| (match_index, current_match))
+                })
+            {
+                format!(
+                    "{} of {} matches, in {}",
+                    match_index + 1,
+                    global_search_state.matches.len(),
+                    app_state
+                        .widget_map
+                        .get(&current_match.widget_id)
+                        .map(|widget| widget.widget_type.get_pretty_name())
+                        .unwrap_or_default()
+                )
+            } else {
+                "No matches".to_string()
+            };
+
+            f.render_widget(
+                Paragraph::new(Spans::from(vec![
+                    Span::styled(
+                        format!("/{}", global_search_state.query),
+                        self.colours.currently_selected_text_style,
+                    ),
+                    Span::styled(
+                        format!("  {}", match_text),
+                        if global_search_state.query.is_empty()
+                            || global_search_state.current_match.is_some()
+                        {
+                            self.colours.text_style
+                        } else {
+                            self.colours.invalid_query_style
+                        },
+                    ),
+                ])),
+                draw_loc,
+            );
+            return;
+        }
+
+        let data_collection = &app_state.data_collection;
+        let frozen_offset = data_collection
+            .frozen_instant
+            .map(|frozen_instant| {
+                data_collection
+                    .current_instant
+                    .saturating_duration_since(frozen_instant)
+                    .as_secs()
+            })
+            .unwrap_or(0);
+
+        let status_text = if let Some(status_message) = app_state.get_status_message() {
+            status_message.to_string()
+        } else if frozen_offset > 0 {
+            format!(
+                "Frozen at -{}s, press 'f' to unfreeze, '[' and ']' to scroll",
+                frozen_offset
+            )
+        } else {
+            "Frozen, press 'f' to unfreeze, '[' and ']' to scroll".to_string()
+        };
+
         f.render_widget(
             Paragraph::new(Span::styled(
-                "Frozen, press 'f' to unfreeze",
+                status_text,
                 self.colours.currently_selected_text_style,
             )),
-            Layout::default()
-                .horizontal_margin(1)
-                .constraints([Constraint::Length(1)])
-                .split(draw_loc)[0],
+            draw_loc,
         )
     }
 
@@ -303,8 +489,17 @@ impl Painter {
     ) -> error::Result<()> {
         use BottomWidgetType::*;
 
+        if self.hidden_widgets != app_state.hidden_widgets {
+            self.hidden_widgets = app_state.hidden_widgets.clone();
+            self.generate_layout_constraints();
+            self.derived_widget_draw_locs = Vec::default();
+        }
+
         terminal.draw(|mut f| {
-            let (terminal_size, frozen_draw_loc) = if app_state.is_frozen {
+            let (terminal_size, status_draw_loc) = if app_state.is_frozen
+                || app_state.get_status_message().is_some()
+                || app_state.global_search_state.is_searching
+            {
                 let split_loc = Layout::default()
                     .constraints([Constraint::Min(0), Constraint::Length(1)])
                     .split(f.size());
@@ -391,13 +586,22 @@ impl Painter {
                     terminal_width * 50 / 100
                 };
 
-                let text_height = if cfg!(target_os = "windows")
+                // Make room for anything past the usual blank line and question, like the list
+                // of marked processes.
+                let extra_height = match &dd_text {
+                    Some(dd_text) if app_state.dd_err.is_none() => {
+                        dd_text.height().saturating_sub(2) as u16
+                    }
+                    _ => 0,
+                };
+                let base_text_height = if cfg!(target_os = "windows")
                     || !app_state.app_config_fields.is_advanced_kill
                 {
                     7
                 } else {
                     22
                 };
+                let text_height = std::cmp::min(terminal_height, base_text_height + extra_height);
 
                 // let (text_width, text_height) = if let Some(dd_text) = &dd_text {
                 //     let width = if current_width < 100 {
@@ -430,32 +634,59 @@ impl Painter {
                 //     )
                 // };
 
-                let vertical_bordering = terminal_height.saturating_sub(text_height) / 2;
-                let vertical_dialog_chunk = Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([
-                        Constraint::Length(vertical_bordering),
-                        Constraint::Length(text_height),
-                        Constraint::Length(vertical_bordering),
-                    ])
-                    .split(terminal_size);
-
-                let horizontal_bordering = terminal_width.saturating_sub(text_width) / 2;
-                let middle_dialog_chunk = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([
-                        Constraint::Length(horizontal_bordering),
-                        Constraint::Length(text_width),
-                        Constraint::Length(horizontal_bordering),
-                    ])
-                    .split(vertical_dialog_chunk[1]);
+                let dialog_rect = centered_dialog_rect(terminal_size, text_width, text_height);
 
                 // This is a bit nasty, but it works well... I guess.
                 app_state.delete_dialog_state.is_showing_dd =
-                    self.draw_dd_dialog(&mut f, dd_text, app_state, middle_dialog_chunk[1]);
+                    self.draw_dd_dialog(&mut f, dd_text, app_state, dialog_rect);
+            } else if app_state.column_picker_state.is_showing_picker {
+                let text_width = std::cmp::min(terminal_width, 40);
+                let text_height =
+                    std::cmp::min(terminal_height, self.get_column_picker_height(app_state));
+
+                let dialog_rect = centered_dialog_rect(terminal_size, text_width, text_height);
+
+                self.draw_column_picker_dialog(&mut f, app_state, dialog_rect);
+            } else if app_state.disk_usage_state.is_showing_disk_usage {
+                let text_width = std::cmp::min(terminal_width, 70);
+                let text_height = std::cmp::max(
+                    std::cmp::min(terminal_height, 12),
+                    terminal_height * 60 / 100,
+                );
+
+                let dialog_rect = centered_dialog_rect(terminal_size, text_width, text_height);
+
+                self.draw_disk_usage_dialog(&mut f, app_state, dialog_rect);
+            } else if app_state.affinity_state.is_showing_affinity {
+                let text_width = std::cmp::min(terminal_width, 50);
+                let text_height = std::cmp::max(
+                    std::cmp::min(terminal_height, 12),
+                    terminal_height * 60 / 100,
+                );
+
+                let dialog_rect = centered_dialog_rect(terminal_size, text_width, text_height);
+
+                self.draw_affinity_dialog(&mut f, app_state, dialog_rect);
+            } else if app_state.renice_state.is_showing_renice {
+                let text_width = std::cmp::min(terminal_width, 50);
+                let text_height = std::cmp::min(terminal_height, 8);
+
+                let dialog_rect = centered_dialog_rect(terminal_size, text_width, text_height);
+
+                self.draw_renice_dialog(&mut f, app_state, dialog_rect);
+            } else if app_state.threads_state.is_showing_threads {
+                let text_width = std::cmp::min(terminal_width, 50);
+                let text_height = std::cmp::max(
+                    std::cmp::min(terminal_height, 12),
+                    terminal_height * 60 / 100,
+                );
+
+                let dialog_rect = centered_dialog_rect(terminal_size, text_width, text_height);
+
+                self.draw_threads_dialog(&mut f, app_state, dialog_rect);
             } else if app_state.is_expanded {
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(&mut f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(&mut f, app_state, status_draw_loc);
                 }
 
                 let rect = Layout::default()
@@ -519,6 +750,108 @@ impl Painter {
                         true,
                         app_state.current_widget.widget_id,
                     ),
+                    Services => self.draw_services_table(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Ports => self.draw_ports_table(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Connections => self.draw_connections_table(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Cgroups => self.draw_cgroups_table(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Docker => self.draw_docker_table(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Vms => self.draw_vms_table(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Custom => self.draw_custom_graph(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Sched => self.draw_sched_graph(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Power => self.draw_power_graph(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Remote => self.draw_remote_display(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    CpuHeatmap => self.draw_cpu_heatmap(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Log => self.draw_log_display(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    KernelLimits => self.draw_kernel_limits_display(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Pressure => self.draw_pressure_display(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    ProcEvents => self.draw_process_events_display(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
                     _ => {}
                 }
             } else if app_state.is_config_open {
@@ -528,11 +861,23 @@ impl Painter {
                     .split(f.size())[0];
 
                 self.draw_config_screen(&mut f, app_state, rect)
+            } else if app_state.app_config_fields.use_mini_mode {
+                // Mini mode.  Just the basic mode meters, on one line.
+                let mini_draw_loc = Layout::default()
+                    .constraints([Constraint::Length(1), Constraint::Min(0)])
+                    .split(f.size())[0];
+
+                self.draw_basic_mini(
+                    &mut f,
+                    app_state,
+                    mini_draw_loc,
+                    &self.get_basic_meter_rows().concat(),
+                );
             } else if app_state.app_config_fields.use_basic_mode {
                 // Basic mode.  This basically removes all graphs but otherwise
                 // the same info.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(&mut f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(&mut f, app_state, status_draw_loc);
                 }
 
                 let actual_cpu_data_len = app_state.canvas_data.cpu_data.len().saturating_sub(1);
@@ -549,24 +894,69 @@ impl Painter {
                     }
                 };
 
+                let meter_rows = self.get_basic_meter_rows();
+                let mut constraints = meter_rows
+                    .iter()
+                    .map(|meter_row| {
+                        if meter_row
+                            .iter()
+                            .any(|(widget_type, _)| *widget_type == BasicCpu)
+                        {
+                            Constraint::Length(cpu_height)
+                        } else {
+                            Constraint::Length(2)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                constraints.push(Constraint::Length(2));
+                constraints.push(Constraint::Min(5));
+
                 let vertical_chunks = Layout::default()
                     .direction(Direction::Vertical)
                     .margin(0)
-                    .constraints([
-                        Constraint::Length(cpu_height),
-                        Constraint::Length(2),
-                        Constraint::Length(2),
-                        Constraint::Min(5),
-                    ])
+                    .constraints(constraints)
                     .split(terminal_size);
-
-                let middle_chunks = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-                    .split(vertical_chunks[1]);
-                self.draw_basic_cpu(&mut f, app_state, vertical_chunks[0], 1);
-                self.draw_basic_memory(&mut f, app_state, middle_chunks[0], 2);
-                self.draw_basic_network(&mut f, app_state, middle_chunks[1], 3);
+                let arrows_draw_loc = vertical_chunks[meter_rows.len()];
+                let table_draw_loc = vertical_chunks[meter_rows.len() + 1];
+
+                for (meter_row, meter_row_draw_loc) in meter_rows.iter().zip(&vertical_chunks) {
+                    let meter_chunks = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints(vec![
+                            Constraint::Ratio(1, meter_row.len() as u32);
+                            meter_row.len()
+                        ])
+                        .split(*meter_row_draw_loc);
+
+                    for ((widget_type, widget_id), meter_draw_loc) in
+                        meter_row.iter().zip(meter_chunks)
+                    {
+                        match widget_type {
+                            BasicCpu => {
+                                self.draw_basic_cpu(&mut f, app_state, meter_draw_loc, *widget_id)
+                            }
+                            BasicMem => self.draw_basic_memory(
+                                &mut f,
+                                app_state,
+                                meter_draw_loc,
+                                *widget_id,
+                            ),
+                            BasicNet => self.draw_basic_network(
+                                &mut f,
+                                app_state,
+                                meter_draw_loc,
+                                *widget_id,
+                            ),
+                            BasicBattery => self.draw_basic_battery(
+                                &mut f,
+                                app_state,
+                                meter_draw_loc,
+                                *widget_id,
+                            ),
+                            _ => {}
+                        }
+                    }
+                }
 
                 let mut later_widget_id: Option<u64> = None;
                 if let Some(basic_table_widget_state) = &app_state.basic_table_widget_state {
@@ -576,7 +966,7 @@ impl Painter {
                         Disk => self.draw_disk_table(
                             &mut f,
                             app_state,
-                            vertical_chunks[3],
+                            table_draw_loc,
                             false,
                             widget_id,
                         ),
@@ -590,7 +980,7 @@ impl Painter {
                             self.draw_process_features(
                                 &mut f,
                                 app_state,
-                                vertical_chunks[3],
+                                table_draw_loc,
                                 false,
                                 wid,
                             );
@@ -598,14 +988,14 @@ impl Painter {
                         Temp => self.draw_temp_table(
                             &mut f,
                             app_state,
-                            vertical_chunks[3],
+                            table_draw_loc,
                             false,
                             widget_id,
                         ),
                         Battery => self.draw_battery_display(
                             &mut f,
                             app_state,
-                            vertical_chunks[3],
+                            table_draw_loc,
                             false,
                             widget_id,
                         ),
@@ -614,12 +1004,12 @@ impl Painter {
                 }
 
                 if let Some(widget_id) = later_widget_id {
-                    self.draw_basic_table_arrows(&mut f, app_state, vertical_chunks[2], widget_id);
+                    self.draw_basic_table_arrows(&mut f, app_state, arrows_draw_loc, widget_id);
                 }
             } else {
                 // Draws using the passed in (or default) layout.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(&mut f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(&mut f, app_state, status_draw_loc);
                 }
 
                 if self.derived_widget_draw_locs.is_empty() || app_state.is_force_redraw {
@@ -708,18 +1098,67 @@ impl Painter {
             }
         })?;
 
+        app_state
+            .frame_budget
+            .end_frame(app_state.app_config_fields.frame_budget);
         app_state.is_force_redraw = false;
         app_state.is_determining_widget_boundary = false;
 
         Ok(())
     }
 
+    /// Returns the meters shown at the top of basic mode, by row, in the order they're shown.
+    fn get_basic_meter_rows(&self) -> Vec<Vec<(BottomWidgetType, u64)>> {
+        use BottomWidgetType::*;
+
+        self.widget_layout
+            .rows
+            .iter()
+            .map(|row| {
+                row.children
+                    .iter()
+                    .flat_map(|col| &col.children)
+                    .flat_map(|col_row| &col_row.children)
+                    .filter(|widget| {
+                        matches!(
+                            widget.widget_type,
+                            BasicCpu | BasicMem | BasicNet | BasicBattery
+                        )
+                    })
+                    .map(|widget| (widget.widget_type.clone(), widget.widget_id))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|meter_row| !meter_row.is_empty())
+            .collect()
+    }
+
     fn draw_widgets_with_constraints<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, widgets: &BottomColRow,
         widget_draw_locs: &[Rect],
     ) {
         use BottomWidgetType::*;
         for (widget, widget_draw_loc) in widgets.children.iter().zip(widget_draw_locs) {
+            if app_state.hidden_widgets.contains(&widget.widget_id) {
+                continue;
+            }
+
+            let data_instant = app_state.data_collection.current_instant;
+            let is_selected = widget.widget_id == app_state.current_widget.widget_id;
+            if !app_state.should_get_widget_bounds()
+                && app_state.frame_budget.can_skip(
+                    widget.widget_id,
+                    *widget_draw_loc,
+                    data_instant,
+                    is_selected,
+                )
+            {
+                app_state
+                    .frame_budget
+                    .draw_cached(f, widget.widget_id, *widget_draw_loc);
+                continue;
+            }
+
+            let draw_start = Instant::now();
             match &widget.widget_type {
                 Empty => {}
                 Cpu => self.draw_cpu(f, app_state, *widget_draw_loc, widget.widget_id),
@@ -745,8 +1184,111 @@ impl Painter {
                     true,
                     widget.widget_id,
                 ),
+                Services => {
+                    self.draw_services_table(f, app_state, *widget_draw_loc, true, widget.widget_id)
+                }
+                Ports => {
+                    self.draw_ports_table(f, app_state, *widget_draw_loc, true, widget.widget_id)
+                }
+                Connections => self.draw_connections_table(
+                    f,
+                    app_state,
+                    *widget_draw_loc,
+                    true,
+                    widget.widget_id,
+                ),
+                Cgroups => {
+                    self.draw_cgroups_table(f, app_state, *widget_draw_loc, true, widget.widget_id)
+                }
+                Docker => {
+                    self.draw_docker_table(f, app_state, *widget_draw_loc, true, widget.widget_id)
+                }
+                Vms => self.draw_vms_table(f, app_state, *widget_draw_loc, true, widget.widget_id),
+                Custom => self.draw_custom_graph(f, app_state, *widget_draw_loc, widget.widget_id),
+                Sched => self.draw_sched_graph(f, app_state, *widget_draw_loc, widget.widget_id),
+                Power => self.draw_power_graph(f, app_state, *widget_draw_loc, widget.widget_id),
+                Remote => {
+                    self.draw_remote_display(f, app_state, *widget_draw_loc, true, widget.widget_id)
+                }
+                CpuHeatmap => {
+                    self.draw_cpu_heatmap(f, app_state, *widget_draw_loc, true, widget.widget_id)
+                }
+                Log => {
+                    self.draw_log_display(f, app_state, *widget_draw_loc, true, widget.widget_id)
+                }
+                KernelLimits => self.draw_kernel_limits_display(
+                    f,
+                    app_state,
+                    *widget_draw_loc,
+                    true,
+                    widget.widget_id,
+                ),
+                Pressure => self.draw_pressure_display(
+                    f,
+                    app_state,
+                    *widget_draw_loc,
+                    true,
+                    widget.widget_id,
+                ),
+                ProcEvents => self.draw_process_events_display(
+                    f,
+                    app_state,
+                    *widget_draw_loc,
+                    true,
+                    widget.widget_id,
+                ),
                 _ => {}
             }
+
+            let has_budget = app_state.app_config_fields.frame_budget.is_some();
+            app_state.frame_budget.record(
+                f,
+                widget.widget_id,
+                *widget_draw_loc,
+                data_instant,
+                draw_start.elapsed(),
+                has_budget,
+            );
+        }
+    }
+}
+
+/// Returns what a ratio total should be once any hidden children are taken out, so whatever is
+/// left takes up their space.  Each child is its ratio, and whether it is sized by the canvas,
+/// grows to fill any leftover space, or is hidden.
+///
+/// Leftover space (the total minus every ratio) goes to the growing children, so it is only
+/// taken out if all of them are hidden.
+fn get_visible_ratio_total(
+    total_ratio: u32, children: impl Iterator<Item = (u32, bool, bool, bool)>,
+) -> u32 {
+    let mut ratio_sum = 0;
+    let mut hidden_ratio_sum = 0;
+    let mut has_growing_child = false;
+    let mut has_shown_growing_child = false;
+
+    for (ratio, is_canvas_handled, is_growing, is_hidden) in children {
+        if is_canvas_handled {
+            continue;
+        } else if is_growing {
+            has_growing_child = true;
+            has_shown_growing_child |= !is_hidden;
+        } else {
+            ratio_sum += ratio;
+            if is_hidden {
+                hidden_ratio_sum += ratio;
+            }
         }
     }
+
+    let leftover_ratio = if has_growing_child && !has_shown_growing_child {
+        total_ratio.saturating_sub(ratio_sum)
+    } else {
+        0
+    };
+
+    std::cmp::max(
+        total_ratio.saturating_sub(hidden_ratio_sum + leftover_ratio),
+        1,
+    )
 }
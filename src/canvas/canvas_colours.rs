@@ -2,7 +2,7 @@ use crate::{options::ConfigColours, utils::error};
 use anyhow::Context;
 use colour_utils::*;
 use tui::style::{Color, Style};
-mod colour_utils;
+pub mod colour_utils;
 
 pub struct CanvasColours {
     pub currently_selected_text_colour: Color,
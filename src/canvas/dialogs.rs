@@ -1,5 +1,15 @@
+pub mod affinity_dialog;
+pub mod column_picker_dialog;
 pub mod dd_dialog;
+pub mod disk_usage_dialog;
 pub mod help_dialog;
+pub mod renice_dialog;
+pub mod threads_dialog;
 
+pub use affinity_dialog::AffinityDialog;
+pub use column_picker_dialog::ColumnPickerDialog;
 pub use dd_dialog::KillDialog;
+pub use disk_usage_dialog::DiskUsageDialog;
 pub use help_dialog::HelpDialog;
+pub use renice_dialog::ReniceDialog;
+pub use threads_dialog::ThreadsDialog;
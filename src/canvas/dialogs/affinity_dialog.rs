@@ -0,0 +1,120 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{app::App, canvas::Painter};
+
+const AFFINITY_BASE: &str = " Affinity ── Esc to close ";
+const AFFINITY_HINT: &str = "Space to toggle, a for all, Enter to apply";
+
+/// The process, the error line, and a blank line.
+const HEADER_HEIGHT: usize = 3;
+/// A blank line and the hint.
+const FOOTER_HEIGHT: usize = 2;
+
+pub trait AffinityDialog {
+    fn draw_affinity_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl AffinityDialog for Painter {
+    fn draw_affinity_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let affinity_title = Spans::from(vec![
+            Span::styled(" Affinity ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(AFFINITY_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let affinity_state = &app_state.affinity_state;
+        let (process_name, pids) = &affinity_state.process;
+        let process_text = match pids.as_slice() {
+            [pid] => format!("{} (PID {})", process_name, pid),
+            _ => format!("{} ({} processes)", process_name, pids.len()),
+        };
+
+        let mut affinity_text = vec![
+            Spans::from(Span::styled(process_text, self.colours.table_header_style)),
+            if let Some(error) = &affinity_state.error {
+                Spans::from(Span::styled(
+                    error.clone(),
+                    self.colours.invalid_query_style,
+                ))
+            } else {
+                Spans::from(Span::styled(
+                    format!(
+                        "Allowed on {} of {} CPUs",
+                        affinity_state
+                            .cpus
+                            .iter()
+                            .filter(|is_allowed| **is_allowed)
+                            .count(),
+                        affinity_state.cpus.len()
+                    ),
+                    self.colours.text_style,
+                ))
+            },
+            Spans::default(),
+        ];
+
+        // Keep the selection on screen.
+        let num_visible_cpus = usize::from(draw_loc.height)
+            .saturating_sub(2 + HEADER_HEIGHT + FOOTER_HEIGHT)
+            .max(1);
+        let start_index = (affinity_state.selected_index + 1).saturating_sub(num_visible_cpus);
+
+        affinity_text.extend(
+            affinity_state
+                .cpus
+                .iter()
+                .enumerate()
+                .skip(start_index)
+                .take(num_visible_cpus)
+                .map(|(itx, is_allowed)| {
+                    let entry = format!("[{}] CPU{}", if *is_allowed { 'x' } else { ' ' }, itx);
+
+                    if itx == affinity_state.selected_index {
+                        Spans::from(Span::styled(
+                            entry,
+                            self.colours.currently_selected_text_style,
+                        ))
+                    } else {
+                        Spans::from(Span::styled(entry, self.colours.text_style))
+                    }
+                }),
+        );
+        affinity_text.push(Spans::default());
+        affinity_text.push(Spans::from(Span::styled(
+            AFFINITY_HINT,
+            self.colours.table_header_style,
+        )));
+
+        f.render_widget(
+            Paragraph::new(Text::from(affinity_text))
+                .block(
+                    Block::default()
+                        .title(affinity_title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left),
+            draw_loc,
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    app::{get_slot_name, App},
+    canvas::Painter,
+};
+
+const PICKER_BASE: &str = " Columns ── Esc to close ";
+const PICKER_HINT: &str = "Space to show/hide, K/J to move";
+
+pub trait ColumnPickerDialog {
+    /// Returns how many lines the column picker needs, including its borders.
+    fn get_column_picker_height(&self, app_state: &App) -> u16;
+
+    fn draw_column_picker_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl ColumnPickerDialog for Painter {
+    fn get_column_picker_height(&self, app_state: &App) -> u16 {
+        let num_slots = if let Some(proc_widget_state) = app_state
+            .proc_state
+            .get_widget_state(app_state.column_picker_state.widget_id)
+        {
+            proc_widget_state.columns.get_picker_slots().len()
+        } else {
+            0
+        };
+
+        // The slots, plus a blank line, the hint, and the borders.
+        num_slots as u16 + 4
+    }
+
+    fn draw_column_picker_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let picker_title = Spans::from(vec![
+            Span::styled(" Columns ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width).saturating_sub(PICKER_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let selected_index = app_state.column_picker_state.selected_index;
+        let mut picker_text = if let Some(proc_widget_state) = app_state
+            .proc_state
+            .get_widget_state(app_state.column_picker_state.widget_id)
+        {
+            proc_widget_state
+                .columns
+                .get_picker_slots()
+                .iter()
+                .enumerate()
+                .map(|(itx, slot)| {
+                    let entry = format!(
+                        "[{}] {}",
+                        if proc_widget_state.columns.is_slot_hidden(slot) {
+                            ' '
+                        } else {
+                            'x'
+                        },
+                        get_slot_name(slot)
+                    );
+
+                    if itx == selected_index {
+                        Spans::from(Span::styled(
+                            entry,
+                            self.colours.currently_selected_text_style,
+                        ))
+                    } else {
+                        Spans::from(Span::styled(entry, self.colours.text_style))
+                    }
+                })
+                .collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+        picker_text.push(Spans::default());
+        picker_text.push(Spans::from(Span::styled(
+            PICKER_HINT,
+            self.colours.table_header_style,
+        )));
+
+        f.render_widget(
+            Paragraph::new(Text::from(picker_text))
+                .block(
+                    Block::default()
+                        .title(picker_title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left),
+            draw_loc,
+        );
+    }
+}
@@ -16,6 +16,9 @@ use crate::{
 const DD_BASE: &str = " Confirm Kill Process ── Esc to close ";
 const DD_ERROR_BASE: &str = " Error ── Esc to close ";
 
+/// How many marked processes to list by name before just counting the rest.
+const MAX_LISTED_MARKED_PROCESSES: usize = 8;
+
 pub trait KillDialog {
     fn get_dd_spans(&self, app_state: &App) -> Option<Text<'_>>;
 
@@ -38,7 +41,33 @@ impl KillDialog for Painter {
                 Spans::from("Please press ENTER or ESC to close this dialog."),
             ]));
         } else if let Some(to_kill_processes) = app_state.get_to_delete_processes() {
-            if let Some(first_pid) = to_kill_processes.1.first() {
+            if app_state.delete_dialog_state.is_killing_marked {
+                let pids = &to_kill_processes.1;
+                let mut text = vec![
+                    Spans::from(""),
+                    Spans::from(format!(
+                        "Kill these {} marked processes?  Press ENTER to confirm.",
+                        pids.len()
+                    )),
+                ];
+                text.extend(pids.iter().take(MAX_LISTED_MARKED_PROCESSES).map(|pid| {
+                    let name = app_state
+                        .canvas_data
+                        .single_process_data
+                        .get(pid)
+                        .map(|process| &*process.name)
+                        .unwrap_or("?");
+                    Spans::from(format!("{} (PID {})", name, pid))
+                }));
+                if pids.len() > MAX_LISTED_MARKED_PROCESSES {
+                    text.push(Spans::from(format!(
+                        "...and {} more",
+                        pids.len() - MAX_LISTED_MARKED_PROCESSES
+                    )));
+                }
+
+                return Some(Text::from(text));
+            } else if let Some(first_pid) = to_kill_processes.1.first() {
                 return Some(Text::from(vec![
                     Spans::from(""),
                     if app_state.is_grouped(app_state.current_widget.widget_id) {
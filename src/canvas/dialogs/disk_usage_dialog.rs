@@ -0,0 +1,128 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{app::App, canvas::Painter, units::formatting::UnitPrefix};
+
+const DISK_USAGE_BASE: &str = " Disk usage ── Esc to close ";
+const DISK_USAGE_HINT: &str = "Enter to open a directory, Backspace to go up";
+
+/// The path, the status line, and a blank line.
+const HEADER_HEIGHT: usize = 3;
+/// A blank line and the hint.
+const FOOTER_HEIGHT: usize = 2;
+
+pub trait DiskUsageDialog {
+    fn draw_disk_usage_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl DiskUsageDialog for Painter {
+    fn draw_disk_usage_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let disk_usage_title = Spans::from(vec![
+            Span::styled(" Disk usage ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(DISK_USAGE_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let mut disk_usage_text = vec![];
+        if let Some(scan) = &app_state.disk_usage_state.scan {
+            let progress = scan.get_progress();
+
+            // Keep the selection on screen.
+            let selected_index = app_state
+                .disk_usage_state
+                .get_selected_index(&progress.entries);
+            let num_visible_entries = usize::from(draw_loc.height)
+                .saturating_sub(2 + HEADER_HEIGHT + FOOTER_HEIGHT)
+                .max(1);
+            let start_index = (selected_index + 1).saturating_sub(num_visible_entries);
+
+            let number_format = &app_state.app_config_fields.number_format;
+            let mut status = format!(
+                "{} {} files, {}",
+                if progress.is_done {
+                    "Done:"
+                } else {
+                    "Scanning..."
+                },
+                progress.files_scanned,
+                number_format.format_bytes(progress.bytes_scanned, UnitPrefix::Si, 1)
+            );
+            if progress.num_errors > 0 {
+                status.push_str(&format!(", {} unreadable", progress.num_errors));
+            }
+
+            disk_usage_text.push(Spans::from(Span::styled(
+                scan.get_path().to_string_lossy().to_string(),
+                self.colours.table_header_style,
+            )));
+            disk_usage_text.push(Spans::from(Span::styled(status, self.colours.text_style)));
+            disk_usage_text.push(Spans::default());
+
+            disk_usage_text.extend(
+                progress
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .skip(start_index)
+                    .take(num_visible_entries)
+                    .map(|(itx, entry)| {
+                        let entry_text = format!(
+                            "{:>8}  {}{}",
+                            number_format.format_bytes(entry.size_bytes, UnitPrefix::Si, 1),
+                            entry
+                                .path
+                                .file_name()
+                                .map(|file_name| file_name.to_string_lossy().to_string())
+                                .unwrap_or_default(),
+                            if entry.is_dir { "/" } else { "" }
+                        );
+
+                        if itx == selected_index {
+                            Spans::from(Span::styled(
+                                entry_text,
+                                self.colours.currently_selected_text_style,
+                            ))
+                        } else {
+                            Spans::from(Span::styled(entry_text, self.colours.text_style))
+                        }
+                    }),
+            );
+        }
+        disk_usage_text.push(Spans::default());
+        disk_usage_text.push(Spans::from(Span::styled(
+            DISK_USAGE_HINT,
+            self.colours.table_header_style,
+        )));
+
+        f.render_widget(
+            Paragraph::new(Text::from(disk_usage_text))
+                .block(
+                    Block::default()
+                        .title(disk_usage_title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left),
+            draw_loc,
+        );
+    }
+}
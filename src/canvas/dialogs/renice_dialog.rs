@@ -0,0 +1,90 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    app::{process_killer, App},
+    canvas::Painter,
+};
+
+const RENICE_BASE: &str = " Renice ── Esc to close ";
+const RENICE_HINT: &str = "Type a niceness or use up/down, Enter to apply";
+
+pub trait ReniceDialog {
+    fn draw_renice_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl ReniceDialog for Painter {
+    fn draw_renice_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let renice_title = Spans::from(vec![
+            Span::styled(" Renice ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width).saturating_sub(RENICE_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let renice_state = &app_state.renice_state;
+        let (process_name, pids) = &renice_state.process;
+        let process_text = match pids.as_slice() {
+            [pid] => format!("{} (PID {})", process_name, pid),
+            _ => format!("{} ({} processes)", process_name, pids.len()),
+        };
+
+        let renice_text = vec![
+            Spans::from(Span::styled(process_text, self.colours.table_header_style)),
+            if let Some(error) = &renice_state.error {
+                Spans::from(Span::styled(
+                    error.clone(),
+                    self.colours.invalid_query_style,
+                ))
+            } else {
+                Spans::from(Span::styled(
+                    format!(
+                        "From {} (highest priority) to {} (lowest)",
+                        process_killer::MIN_NICENESS,
+                        process_killer::MAX_NICENESS
+                    ),
+                    self.colours.text_style,
+                ))
+            },
+            Spans::default(),
+            Spans::from(vec![
+                Span::styled("Niceness: ", self.colours.text_style),
+                Span::styled(
+                    format!("{}_", renice_state.niceness),
+                    self.colours.currently_selected_text_style,
+                ),
+            ]),
+            Spans::default(),
+            Spans::from(Span::styled(RENICE_HINT, self.colours.table_header_style)),
+        ];
+
+        f.render_widget(
+            Paragraph::new(Text::from(renice_text))
+                .block(
+                    Block::default()
+                        .title(renice_title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left),
+            draw_loc,
+        );
+    }
+}
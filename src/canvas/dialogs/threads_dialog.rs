@@ -0,0 +1,116 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{app::App, canvas::Painter};
+
+const THREADS_BASE: &str = " Threads ── Esc to close ";
+
+/// The process, the error or thread count, a blank line, and the column headers.
+const HEADER_HEIGHT: usize = 4;
+
+pub trait ThreadsDialog {
+    fn draw_threads_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl ThreadsDialog for Painter {
+    fn draw_threads_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let threads_title = Spans::from(vec![
+            Span::styled(" Threads ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Esc to close ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(THREADS_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let threads_state = &app_state.threads_state;
+        let (process_name, pid) = &threads_state.process;
+
+        let mut threads_text = vec![
+            Spans::from(Span::styled(
+                format!("{} (PID {})", process_name, pid),
+                self.colours.table_header_style,
+            )),
+            if let Some(error) = &threads_state.error {
+                Spans::from(Span::styled(
+                    error.clone(),
+                    self.colours.invalid_query_style,
+                ))
+            } else {
+                Spans::from(Span::styled(
+                    format!(
+                        "{} threads, using {:.1}% CPU",
+                        threads_state.threads.len(),
+                        threads_state
+                            .threads
+                            .iter()
+                            .map(|thread| thread.cpu_usage_percent)
+                            .sum::<f64>()
+                    ),
+                    self.colours.text_style,
+                ))
+            },
+            Spans::default(),
+            Spans::from(Span::styled(
+                format!("{:<8} {:<16} {:<5} {:>6}", "TID", "Name", "State", "CPU%"),
+                self.colours.table_header_style,
+            )),
+        ];
+
+        let num_visible_threads = usize::from(draw_loc.height)
+            .saturating_sub(2 + HEADER_HEIGHT)
+            .max(1);
+        let start_index = std::cmp::min(
+            threads_state.scroll_index,
+            threads_state
+                .threads
+                .len()
+                .saturating_sub(num_visible_threads),
+        );
+
+        threads_text.extend(
+            threads_state
+                .threads
+                .iter()
+                .skip(start_index)
+                .take(num_visible_threads)
+                .map(|thread| {
+                    Spans::from(Span::styled(
+                        format!(
+                            "{:<8} {:<16} {:<5} {:>5.1}%",
+                            thread.tid, thread.name, thread.state, thread.cpu_usage_percent
+                        ),
+                        self.colours.text_style,
+                    ))
+                }),
+        );
+
+        f.render_widget(
+            Paragraph::new(Text::from(threads_text))
+                .block(
+                    Block::default()
+                        .title(threads_title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left),
+            draw_loc,
+        );
+    }
+}
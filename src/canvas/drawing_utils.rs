@@ -1,5 +1,11 @@
-use crate::app;
-use std::cmp::{max, min};
+use crate::app::{self, AppConfigFields, ClockFormat, GraphStyle, TimeLabelStyle};
+use chrono::Local;
+use std::{
+    borrow::Cow,
+    cmp::{max, min},
+    time::Instant,
+};
+use tui::{layout::Rect, symbols::Marker};
 
 /// Return a (hard)-width vector for column widths.
 ///
@@ -197,6 +203,18 @@ pub fn get_start_position(
     }
 }
 
+/// Returns where to draw a dialog of the given size, centred in `area` and shrunk to fit it.
+pub fn centered_dialog_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = min(width, area.width);
+    let height = min(height, area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
 /// Calculate how many bars are to be
 /// drawn within basic mode's components.
 pub fn calculate_basic_use_bars(use_percentage: f64, num_bars_available: usize) -> usize {
@@ -216,3 +234,101 @@ pub fn interpolate_points(point_one: &(f64, f64), point_two: &(f64, f64), time:
 
     (point_one.1 + (time - point_one.0) * slope).max(0.0)
 }
+
+/// The marker a graph's lines are drawn with in the given style.
+pub fn get_graph_marker(graph_style: GraphStyle) -> Marker {
+    match graph_style {
+        GraphStyle::Braille | GraphStyle::Filled => Marker::Braille,
+        GraphStyle::Block => Marker::Block,
+        GraphStyle::Dot => Marker::Dot,
+    }
+}
+
+/// Returns the points to draw a line graph of `points` with in the given style.  That's just the
+/// points themselves, unless the area under the line is filled in.  That's drawn as a vertical line
+/// from `y_min` up to the graph and back down again for each braille column of the graph, so
+/// `width` is the width of the graph in cells, and `x_bounds` is the range of x it shows.
+///
+/// As with the points themselves, x is expected to be increasing.  Gaps in the data, such as from
+/// missing values, are left empty.
+pub fn get_graph_points(
+    points: &[(f64, f64)], graph_style: GraphStyle, x_bounds: [f64; 2], y_min: f64, width: u16,
+) -> Cow<'_, [(f64, f64)]> {
+    if graph_style != GraphStyle::Filled || points.is_empty() || width == 0 {
+        return Cow::Borrowed(points);
+    }
+
+    let num_columns = usize::from(width) * 2;
+    let column_width = (x_bounds[1] - x_bounds[0]) / num_columns as f64;
+    let mut filled_points = Vec::with_capacity((num_columns + 1) * 3);
+    let mut right_index = 0;
+    for column in 0..=num_columns {
+        let x = x_bounds[0] + column as f64 * column_width;
+        while right_index < points.len() && points[right_index].0 < x {
+            right_index += 1;
+        }
+
+        let y = match (
+            right_index
+                .checked_sub(1)
+                .and_then(|index| points.get(index)),
+            points.get(right_index),
+        ) {
+            (Some(left), Some(right)) if left.1.is_finite() && right.1.is_finite() => {
+                interpolate_points(left, right, x)
+            }
+            (None, Some(right)) if right.0 == x && right.1.is_finite() => right.1,
+            _ => continue,
+        };
+
+        filled_points.push((x, y_min));
+        filled_points.push((x, y));
+        filled_points.push((x, y_min));
+    }
+
+    Cow::Owned(filled_points)
+}
+
+/// The most labels a graph's time axis is split into.
+const MAX_TIME_LABELS: u16 = 5;
+
+/// Returns the labels for the time axis of a graph `width` columns wide, which shows the
+/// `display_time` milliseconds leading up to `end_time`.  As many labels as fit are spread evenly
+/// across the axis, and each is worked out for its exact spot, so it matches the data under it.
+pub fn get_time_labels(
+    display_time: u64, end_time: Instant, width: u16, app_config_fields: &AppConfigFields,
+) -> Vec<String> {
+    let time_label_style = app_config_fields.time_label_style;
+    let clock_format = app_config_fields.clock_format;
+    let label_width = match (time_label_style, clock_format) {
+        (TimeLabelStyle::Relative, _) => 5,
+        (TimeLabelStyle::Absolute, ClockFormat::TwentyFourHour) => 8,
+        (TimeLabelStyle::Absolute, ClockFormat::TwelveHour) => 11,
+    };
+    // Leave at least as much space between labels as they take up.
+    let num_labels = max(2, min(MAX_TIME_LABELS, width / (label_width * 2)));
+
+    // Instants can't be turned into times of day, so work back from how long ago the end was.
+    let end_time_of_day = Local::now()
+        - chrono::Duration::from_std(Instant::now().saturating_duration_since(end_time))
+            .unwrap_or_else(|_| chrono::Duration::zero());
+
+    (0..num_labels)
+        .map(|itx| {
+            let offset = display_time * u64::from(num_labels - 1 - itx) / u64::from(num_labels - 1);
+            match time_label_style {
+                TimeLabelStyle::Relative => {
+                    if offset % 1000 == 0 {
+                        format!("{}s", offset / 1000)
+                    } else {
+                        format!("{:.1}s", offset as f64 / 1000.0)
+                    }
+                }
+                TimeLabelStyle::Absolute => (end_time_of_day
+                    - chrono::Duration::milliseconds(offset as i64))
+                .format(clock_format.time_format())
+                .to_string(),
+            }
+        })
+        .collect()
+}
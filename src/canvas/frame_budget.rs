@@ -0,0 +1,145 @@
+//! Keeping drawing under a time budget on slow terminals.  Each widget's draw is timed, and while
+//! drawing every widget would take longer than the budget, widgets that can't have changed are
+//! copied over from the last frame instead of being drawn again.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use tui::{backend::Backend, buffer::Buffer, layout::Rect, terminal::Frame, widgets::Widget};
+
+/// What a widget drew last, and for which data.
+#[derive(Debug)]
+struct CachedWidget {
+    cells: Buffer,
+    data_instant: Instant,
+}
+
+#[derive(Debug, Default)]
+pub struct FrameBudget {
+    /// How long each widget took to draw the last time it was drawn, by widget ID.
+    pub draw_times: HashMap<u64, Duration>,
+    cached_widgets: HashMap<u64, CachedWidget>,
+    /// The widgets drawn so far this frame, whether they were drawn or copied.
+    frame_widget_ids: HashSet<u64>,
+    /// Whether unchanged widgets are being reused rather than drawn.
+    pub is_skipping: bool,
+}
+
+impl FrameBudget {
+    /// Whether a widget can be copied from the last frame.  That's only when it's in the same
+    /// spot, it isn't selected (so it can't have been scrolled or otherwise changed by a key), and
+    /// there's been no new data since.  Widgets that aren't selected can still be changed by keys
+    /// that affect everything, which then only shows once new data comes in.
+    pub fn can_skip(
+        &self, widget_id: u64, draw_loc: Rect, data_instant: Instant, is_selected: bool,
+    ) -> bool {
+        self.is_skipping
+            && !is_selected
+            && self
+                .cached_widgets
+                .get(&widget_id)
+                .map(|cached_widget| {
+                    cached_widget.cells.area == draw_loc
+                        && cached_widget.data_instant == data_instant
+                })
+                .unwrap_or(false)
+    }
+
+    /// Copies what a widget drew last into the frame.
+    pub fn draw_cached<B: Backend>(
+        &mut self, f: &mut Frame<'_, B>, widget_id: u64, draw_loc: Rect,
+    ) {
+        self.frame_widget_ids.insert(widget_id);
+        if let Some(cached_widget) = self.cached_widgets.get(&widget_id) {
+            f.render_widget(RestoreCells(&cached_widget.cells), draw_loc);
+        }
+    }
+
+    /// Records how long a widget took to draw, and if there's a budget, saves what it drew.
+    pub fn record<B: Backend>(
+        &mut self, f: &mut Frame<'_, B>, widget_id: u64, draw_loc: Rect, data_instant: Instant,
+        draw_time: Duration, has_budget: bool,
+    ) {
+        self.frame_widget_ids.insert(widget_id);
+        self.draw_times.insert(widget_id, draw_time);
+
+        if has_budget {
+            let mut cells = Buffer::empty(draw_loc);
+            f.render_widget(CaptureCells(&mut cells), draw_loc);
+            self.cached_widgets.insert(
+                widget_id,
+                CachedWidget {
+                    cells,
+                    data_instant,
+                },
+            );
+        } else {
+            self.cached_widgets.clear();
+        }
+    }
+
+    /// Decides whether the next frame should reuse unchanged widgets, going by how long drawing
+    /// every widget took when each was last drawn.  Widgets that weren't on screen this frame are
+    /// forgotten.
+    pub fn end_frame(&mut self, budget: Option<Duration>) {
+        let frame_widget_ids = std::mem::take(&mut self.frame_widget_ids);
+        self.draw_times
+            .retain(|widget_id, _| frame_widget_ids.contains(widget_id));
+        self.cached_widgets
+            .retain(|widget_id, _| frame_widget_ids.contains(widget_id));
+
+        let frame_time = self.draw_times.values().sum::<Duration>();
+        let is_skipping = match budget {
+            Some(budget) => frame_time > budget,
+            None => false,
+        };
+
+        if is_skipping != self.is_skipping {
+            #[cfg(feature = "log")]
+            {
+                let slowest_widget = self
+                    .draw_times
+                    .iter()
+                    .max_by_key(|(_, draw_time)| **draw_time);
+                debug!(
+                    "Frames take about {:?} to draw; {} skipping unchanged widgets.  Slowest widget (ID, time): {:?}",
+                    frame_time,
+                    if is_skipping { "started" } else { "stopped" },
+                    slowest_widget
+                );
+            }
+            self.is_skipping = is_skipping;
+        }
+    }
+}
+
+/// Copies the cells in an area of the frame into a buffer, leaving the frame as it is.
+struct CaptureCells<'a>(&'a mut Buffer);
+
+impl Widget for CaptureCells<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        *self.0 = Buffer::empty(area);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                *self.0.get_mut(x, y) = buf.get(x, y).clone();
+            }
+        }
+    }
+}
+
+/// Draws cells saved by [`CaptureCells`] back into the frame.
+struct RestoreCells<'a>(&'a Buffer);
+
+impl Widget for RestoreCells<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(self.0.area).intersection(buf.area);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                *buf.get_mut(x, y) = self.0.get(x, y).clone();
+            }
+        }
+    }
+}
@@ -1,23 +1,63 @@
+pub mod basic_mini;
 pub mod basic_table_arrows;
+pub mod battery_basic;
 pub mod battery_display;
+pub mod cgroups_table;
+pub mod connections_table;
 pub mod cpu_basic;
 pub mod cpu_graph;
+pub mod cpu_heatmap;
+pub mod custom_graph;
 pub mod disk_table;
+pub mod docker_table;
+pub mod graph_crosshair;
+pub mod kernel_limits_display;
+pub mod log_display;
 pub mod mem_basic;
 pub mod mem_graph;
 pub mod network_basic;
 pub mod network_graph;
+pub mod ports_table;
+pub mod power_graph;
+pub mod pressure_display;
+pub mod process_events_display;
 pub mod process_table;
+pub mod remote_display;
+pub mod sched_graph;
+pub mod services_table;
+pub mod simple_table;
 pub mod temp_table;
+pub mod vms_table;
+pub mod widget_block;
 
+pub use basic_mini::BasicMiniWidget;
 pub use basic_table_arrows::BasicTableArrows;
+pub use battery_basic::BatteryBasicWidget;
 pub use battery_display::BatteryDisplayWidget;
+pub use cgroups_table::CgroupsTableWidget;
+pub use connections_table::ConnectionsTableWidget;
 pub use cpu_basic::CpuBasicWidget;
 pub use cpu_graph::CpuGraphWidget;
+pub use cpu_heatmap::CpuHeatmapWidget;
+pub use custom_graph::CustomGraphWidget;
 pub use disk_table::DiskTableWidget;
+pub use docker_table::DockerTableWidget;
+pub use graph_crosshair::GraphCrosshair;
+pub use kernel_limits_display::KernelLimitsDisplayWidget;
+pub use log_display::LogDisplayWidget;
 pub use mem_basic::MemBasicWidget;
 pub use mem_graph::MemGraphWidget;
 pub use network_basic::NetworkBasicWidget;
 pub use network_graph::NetworkGraphWidget;
+pub use ports_table::PortsTableWidget;
+pub use power_graph::PowerGraphWidget;
+pub use pressure_display::PressureDisplayWidget;
+pub use process_events_display::ProcessEventsDisplayWidget;
 pub use process_table::ProcessTableWidget;
+pub use remote_display::RemoteDisplayWidget;
+pub use sched_graph::SchedGraphWidget;
+pub use services_table::ServicesTableWidget;
+pub use simple_table::SimpleTableWidget;
 pub use temp_table::TempTableWidget;
+pub use vms_table::VmsTableWidget;
+pub use widget_block::WidgetBlock;
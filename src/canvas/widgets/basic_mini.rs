@@ -0,0 +1,102 @@
+use crate::{
+    app::{layout_manager::BottomWidgetType, App},
+    canvas::{widgets::battery_basic::get_battery_charge_style, Painter},
+};
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+
+pub trait BasicMiniWidget {
+    /// Draws the basic mode meters as one line, in the given order.
+    fn draw_basic_mini<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+        meters: &[(BottomWidgetType, u64)],
+    );
+}
+
+impl BasicMiniWidget for Painter {
+    fn draw_basic_mini<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+        meters: &[(BottomWidgetType, u64)],
+    ) {
+        let canvas_data = &app_state.canvas_data;
+        let mut spans = Vec::new();
+
+        for (widget_type, _widget_id) in meters {
+            if !spans.is_empty() {
+                spans.push(Span::raw("  "));
+            }
+
+            match widget_type {
+                BottomWidgetType::BasicCpu => {
+                    // Skip the first element, it's the "all" element.  If the average is shown,
+                    // it comes next; otherwise, work it out from the cores.
+                    let cpu_data = canvas_data.cpu_data.get(1..).unwrap_or_default();
+                    let last_value = |index: usize| {
+                        cpu_data
+                            .get(index)
+                            .and_then(|cpu| cpu.cpu_data.last())
+                            .map(|point| point.1)
+                            .unwrap_or(0.0)
+                    };
+                    let cpu_use_percentage = if app_state.app_config_fields.show_average_cpu {
+                        last_value(0)
+                    } else if cpu_data.is_empty() {
+                        0.0
+                    } else {
+                        (0..cpu_data.len()).map(last_value).sum::<f64>() / cpu_data.len() as f64
+                    };
+
+                    spans.push(Span::styled(
+                        format!("CPU {:3.0}%", cpu_use_percentage.round()),
+                        self.colours.avg_colour_style,
+                    ));
+                }
+                BottomWidgetType::BasicMem => {
+                    let ram_use_percentage =
+                        canvas_data.mem_data.last().map(|mem| mem.1).unwrap_or(0.0);
+
+                    spans.push(Span::styled(
+                        format!("MEM {:3.0}%", ram_use_percentage.round()),
+                        self.colours.ram_style,
+                    ));
+                }
+                BottomWidgetType::BasicNet => {
+                    spans.push(Span::styled(
+                        format!("RX {}", canvas_data.rx_display),
+                        self.colours.rx_style,
+                    ));
+                    spans.push(Span::raw(" "));
+                    spans.push(Span::styled(
+                        format!("TX {}", canvas_data.tx_display),
+                        self.colours.tx_style,
+                    ));
+                }
+                BottomWidgetType::BasicBattery => {
+                    if let Some(battery_details) = canvas_data.battery_data.first() {
+                        spans.push(Span::styled(
+                            format!("BAT {:3.0}%", battery_details.charge_percentage.round()),
+                            get_battery_charge_style(
+                                &self.colours,
+                                battery_details.charge_percentage,
+                            ),
+                        ));
+                    } else {
+                        spans.push(Span::styled("BAT N/A", self.colours.disabled_text_style));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        f.render_widget(
+            Paragraph::new(Spans::from(spans)).style(self.colours.text_style),
+            draw_loc,
+        );
+    }
+}
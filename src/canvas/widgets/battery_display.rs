@@ -1,18 +1,36 @@
 use crate::{
-    app::App,
-    canvas::{drawing_utils::calculate_basic_use_bars, Painter},
+    app::{data_farmer::DataCollection, App, AppConfigFields},
+    canvas::{
+        drawing_utils::{
+            calculate_basic_use_bars, get_graph_marker, get_graph_points, get_time_labels,
+            interpolate_points,
+        },
+        Painter,
+    },
     constants::*,
 };
 
+use std::time::{Duration, Instant};
+
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
     terminal::Frame,
     text::{Span, Spans},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Tabs},
+    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table, Tabs},
 };
 use unicode_segmentation::UnicodeSegmentation;
 
+/// The header and the five rows of battery details, not counting the table gap.
+const BATTERY_TABLE_HEIGHT: u16 = 6;
+
+/// How long a change in power source is pointed out for.
+const POWER_SOURCE_CHANGE_HIGHLIGHT: Duration = Duration::from_secs(60);
+
+/// How many rows must be left under the details to bother drawing the power graph.
+const MIN_POWER_GRAPH_HEIGHT: u16 = 6;
+
 pub trait BatteryDisplayWidget {
     fn draw_battery_display<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
@@ -154,9 +172,11 @@ impl BatteryDisplayWidget for Painter {
                     },
                     Row::new(vec!["Health %", &battery_details.health])
                         .style(self.colours.text_style),
+                    self.get_power_source_row(&app_state.data_collection),
                 ];
 
                 // Draw
+                let inner_draw_loc = battery_block.inner(margined_draw_loc);
                 f.render_widget(
                     Table::new(battery_rows)
                         .block(battery_block)
@@ -164,6 +184,25 @@ impl BatteryDisplayWidget for Painter {
                         .widths(&[Constraint::Percentage(50), Constraint::Percentage(50)]),
                     margined_draw_loc,
                 );
+
+                // If there's room left under the details, graph the power draw.
+                let table_height = BATTERY_TABLE_HEIGHT + table_gap;
+                if inner_draw_loc.height >= table_height + MIN_POWER_GRAPH_HEIGHT {
+                    let graph_draw_loc = Rect::new(
+                        inner_draw_loc.x,
+                        inner_draw_loc.y + table_height,
+                        inner_draw_loc.width,
+                        inner_draw_loc.height - table_height,
+                    );
+                    self.draw_battery_power_graph(
+                        f,
+                        &app_state.app_config_fields,
+                        &battery_details.power_data,
+                        app_state.data_collection.current_instant,
+                        graph_draw_loc,
+                        border_style,
+                    );
+                }
             } else {
                 let mut contents = vec![Spans::default(); table_gap as usize];
 
@@ -209,3 +248,115 @@ impl BatteryDisplayWidget for Painter {
         }
     }
 }
+
+impl Painter {
+    /// Returns the row showing where power is coming from, which stands out for a while after it
+    /// changes.
+    fn get_power_source_row(&self, data_collection: &DataCollection) -> Row<'_> {
+        let power_source = match data_collection.power_source {
+            Some(power_source) => power_source.as_str(),
+            None => return Row::new(vec!["Power source", "N/A"]).style(self.colours.text_style),
+        };
+
+        let was_just_changed = data_collection
+            .power_source_changed_instant
+            .map(|changed_instant| {
+                Instant::now().duration_since(changed_instant) < POWER_SOURCE_CHANGE_HIGHLIGHT
+            })
+            .unwrap_or(false);
+        if was_just_changed {
+            Row::new(vec![
+                "Power source".to_string(),
+                format!("{} (just switched)", power_source),
+            ])
+            .style(self.colours.medium_battery_colour)
+        } else {
+            Row::new(vec!["Power source", power_source]).style(self.colours.text_style)
+        }
+    }
+
+    /// Draws the power draw of a battery over the default time range, up to `graph_end_time`.
+    fn draw_battery_power_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_config_fields: &AppConfigFields,
+        power_data: &[(f64, f64)], graph_end_time: Instant, draw_loc: Rect, border_style: Style,
+    ) {
+        let time_start = -(app_config_fields.default_time_value as f64);
+
+        // Only keep what's visible, interpolating the first point to avoid a gap on the left.
+        let mut visible_points = Vec::new();
+        if let Some(start_pos) = power_data.iter().position(|(time, _)| *time >= time_start) {
+            if start_pos > 0 {
+                visible_points.push((
+                    time_start,
+                    interpolate_points(
+                        &power_data[start_pos - 1],
+                        &power_data[start_pos],
+                        time_start,
+                    ),
+                ));
+            }
+            visible_points.extend_from_slice(&power_data[start_pos..]);
+        }
+
+        let max_value = visible_points
+            .iter()
+            .fold(0.0_f64, |max, (_time, watts)| max.max(*watts));
+        let max_bound = if max_value > 0.0 {
+            max_value * 1.1
+        } else {
+            1.0
+        };
+
+        let x_axis = if app_config_fields.hide_time || draw_loc.height < TIME_LABEL_HEIGHT_LIMIT {
+            Axis::default().bounds([time_start, 0.0])
+        } else {
+            Axis::default()
+                .bounds([time_start, 0.0])
+                .style(self.colours.graph_style)
+                .labels(
+                    get_time_labels(
+                        app_config_fields.default_time_value,
+                        graph_end_time,
+                        draw_loc.width,
+                        app_config_fields,
+                    )
+                    .into_iter()
+                    .map(|label| Span::styled(label, self.colours.graph_style))
+                    .collect(),
+                )
+        };
+        let y_axis = Axis::default()
+            .style(self.colours.graph_style)
+            .bounds([0.0, max_bound])
+            .labels(vec![
+                Span::styled("0W", self.colours.graph_style),
+                Span::styled(format!("{:.1}W", max_bound), self.colours.graph_style),
+            ]);
+
+        let graph_style = app_config_fields.graph_styles.battery;
+        let graph_points = get_graph_points(
+            &visible_points,
+            graph_style,
+            [time_start, 0.0],
+            0.0,
+            draw_loc.width,
+        );
+
+        f.render_widget(
+            Chart::new(vec![Dataset::default()
+                .marker(get_graph_marker(graph_style))
+                .style(self.colours.high_battery_colour)
+                .data(&graph_points)
+                .graph_type(GraphType::Line)])
+            .block(
+                Block::default()
+                    .title(Span::styled(" Power ", self.colours.widget_title_style))
+                    .borders(Borders::TOP)
+                    .border_style(border_style),
+            )
+            .x_axis(x_axis)
+            .y_axis(y_axis),
+            draw_loc,
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        widgets::simple_table::{SimpleTableColumns, SimpleTableParts, SimpleTableWidget},
+        Painter,
+    },
+};
+
+const CGROUP_COLUMNS: SimpleTableColumns = SimpleTableColumns {
+    headers: &["Name", "CPU%", "Mem", "Limit", "R/s", "W/s"],
+    hard_widths: &[None, Some(7), Some(9), Some(9), Some(9), Some(9)],
+    max_width_ratios: &[Some(0.5), None, None, None, None, None],
+};
+
+pub trait CgroupsTableWidget {
+    fn draw_cgroups_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl CgroupsTableWidget for Painter {
+    fn draw_cgroups_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_simple_table(
+            f,
+            app_state,
+            draw_loc,
+            draw_border,
+            widget_id,
+            " Cgroups ",
+            &CGROUP_COLUMNS,
+            get_cgroups_table_parts,
+        );
+    }
+}
+
+fn get_cgroups_table_parts(
+    app_state: &mut app::App, widget_id: u64,
+) -> Option<SimpleTableParts<'_>> {
+    let cgroups_widget_state = app_state.cgroups_state.widget_states.get_mut(&widget_id)?;
+    Some(SimpleTableParts {
+        scroll_state: &mut cgroups_widget_state.scroll_state,
+        table_width_state: &mut cgroups_widget_state.table_width_state,
+        rows: &app_state.canvas_data.cgroup_data,
+        status_text: None,
+    })
+}
@@ -0,0 +1,55 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        widgets::simple_table::{SimpleTableColumns, SimpleTableParts, SimpleTableWidget},
+        Painter,
+    },
+};
+
+const CONNECTION_COLUMNS: SimpleTableColumns = SimpleTableColumns {
+    headers: &["Proto", "Local", "Remote", "PID", "Process"],
+    hard_widths: &[Some(5), None, None, Some(8), None],
+    max_width_ratios: &[None, Some(0.3), Some(0.4), None, Some(-1.0)],
+};
+
+pub trait ConnectionsTableWidget {
+    fn draw_connections_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl ConnectionsTableWidget for Painter {
+    fn draw_connections_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_simple_table(
+            f,
+            app_state,
+            draw_loc,
+            draw_border,
+            widget_id,
+            " Connections ",
+            &CONNECTION_COLUMNS,
+            get_connections_table_parts,
+        );
+    }
+}
+
+fn get_connections_table_parts(
+    app_state: &mut app::App, widget_id: u64,
+) -> Option<SimpleTableParts<'_>> {
+    let connections_widget_state = app_state
+        .connections_state
+        .widget_states
+        .get_mut(&widget_id)?;
+    Some(SimpleTableParts {
+        scroll_state: &mut connections_widget_state.scroll_state,
+        table_width_state: &mut connections_widget_state.table_width_state,
+        rows: &app_state.canvas_data.connection_data,
+        status_text: None,
+    })
+}
@@ -72,22 +72,36 @@ impl CpuBasicWidget for Painter {
                 const REDUCED_SPACING: usize = CPU_NAME_SPACE + PERCENTAGE_SPACE + MARGIN_SPACE;
                 let chunk_width = chunks[0].width as usize;
 
+                // Offline CPUs have no usage, rather than whatever they last had.
+                let get_use_percentage = |cpu_index: usize| {
+                    let cpu = &cpu_data[cpu_index];
+                    if cpu.is_offline {
+                        None
+                    } else if let Some(cpu_usage) = cpu.cpu_data.last() {
+                        Some(cpu_usage.1)
+                    } else {
+                        Some(0.0)
+                    }
+                };
+                let format_use_percentage = |use_percentage: Option<f64>| {
+                    if let Some(use_percentage) = use_percentage {
+                        format!("{:3.0}%", use_percentage.round())
+                    } else {
+                        format!("{:>4}", "Off")
+                    }
+                };
+
                 // Inspired by htop.
                 // We do +4 as if it's too few bars in the bar length, it's kinda pointless.
                 let cpu_bars = if chunk_width >= COMBINED_SPACING + 4 {
                     let bar_length = chunk_width - COMBINED_SPACING;
                     (0..num_cpus)
                         .map(|cpu_index| {
-                            let use_percentage =
-                                if let Some(cpu_usage) = cpu_data[cpu_index].cpu_data.last() {
-                                    cpu_usage.1
-                                } else {
-                                    0.0
-                                };
-
-                            let num_bars = calculate_basic_use_bars(use_percentage, bar_length);
+                            let use_percentage = get_use_percentage(cpu_index);
+                            let num_bars =
+                                calculate_basic_use_bars(use_percentage.unwrap_or(0.0), bar_length);
                             format!(
-                                "{:3}[{}{}{:3.0}%]",
+                                "{:3}[{}{}{}]",
                                 if app_state.app_config_fields.show_average_cpu {
                                     if cpu_index == 0 {
                                         "AVG".to_string()
@@ -99,22 +113,17 @@ impl CpuBasicWidget for Painter {
                                 },
                                 "|".repeat(num_bars),
                                 " ".repeat(bar_length - num_bars),
-                                use_percentage.round(),
+                                format_use_percentage(use_percentage),
                             )
                         })
                         .collect::<Vec<_>>()
                 } else if chunk_width >= REDUCED_SPACING {
                     (0..num_cpus)
                         .map(|cpu_index| {
-                            let use_percentage =
-                                if let Some(cpu_usage) = cpu_data[cpu_index].cpu_data.last() {
-                                    cpu_usage.1
-                                } else {
-                                    0.0
-                                };
+                            let use_percentage = get_use_percentage(cpu_index);
 
                             format!(
-                                "{:3} {:3.0}%",
+                                "{:3} {}",
                                 if app_state.app_config_fields.show_average_cpu {
                                     if cpu_index == 0 {
                                         "AVG".to_string()
@@ -124,22 +133,13 @@ impl CpuBasicWidget for Painter {
                                 } else {
                                     cpu_index.to_string()
                                 },
-                                use_percentage.round(),
+                                format_use_percentage(use_percentage),
                             )
                         })
                         .collect::<Vec<_>>()
                 } else {
                     (0..num_cpus)
-                        .map(|cpu_index| {
-                            let use_percentage =
-                                if let Some(cpu_usage) = cpu_data[cpu_index].cpu_data.last() {
-                                    cpu_usage.1
-                                } else {
-                                    0.0
-                                };
-
-                            format!("{:3.0}%", use_percentage.round(),)
-                        })
+                        .map(|cpu_index| format_use_percentage(get_use_percentage(cpu_index)))
                         .collect::<Vec<_>>()
                 };
 
@@ -162,7 +162,9 @@ impl CpuBasicWidget for Painter {
                             .map(|itx| {
                                 Spans::from(Span {
                                     content: (&cpu_bars[itx]).into(),
-                                    style: if show_avg_cpu {
+                                    style: if cpu_data[itx].is_offline {
+                                        self.colours.disabled_text_style
+                                    } else if show_avg_cpu {
                                         if itx == 0 {
                                             self.colours.avg_colour_style
                                         } else {
@@ -4,7 +4,12 @@ use unicode_segmentation::UnicodeSegmentation;
 use crate::{
     app::{layout_manager::WidgetDirection, App},
     canvas::{
-        drawing_utils::{get_column_widths, get_start_position, interpolate_points},
+        canvas_colours::CanvasColours,
+        drawing_utils::{
+            get_column_widths, get_graph_marker, get_graph_points, get_start_position,
+            get_time_labels, interpolate_points,
+        },
+        widgets::graph_crosshair::{get_boot_marker_points, get_crosshair, GraphCrosshair},
         Painter,
     },
     constants::*,
@@ -14,17 +19,21 @@ use crate::{
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    symbols::Marker,
+    style::Style,
     terminal::Frame,
     text::Span,
     text::{Spans, Text},
     widgets::{Axis, Block, Borders, Chart, Dataset, Row, Table},
 };
 
-const CPU_LEGEND_HEADER: [&str; 2] = ["CPU", "Use%"];
+const CPU_LEGEND_HEADER: [&str; 4] = ["CPU", "Use%", "Avg%", "Max%"];
 const AVG_POSITION: usize = 1;
 const ALL_POSITION: usize = 0;
 
+/// How many of the legend's columns are shown normally; the rest are only shown when it's
+/// expanded.
+const NUM_BASE_LEGEND_COLUMNS: usize = 2;
+
 static CPU_LEGEND_HEADER_LENS: Lazy<Vec<u16>> = Lazy::new(|| {
     CPU_LEGEND_HEADER
         .iter()
@@ -71,18 +80,29 @@ impl CpuGraphWidget for Painter {
                 }
             }
         } else {
+            // The expanded legend has twice the columns, so it gets twice the room.
+            let legend_percentage = match app_state.cpu_state.get_widget_state(widget_id) {
+                Some(cpu_widget_state) if cpu_widget_state.is_legend_expanded => 30,
+                _ => 15,
+            };
             let (graph_index, legend_index, constraints) =
                 if app_state.app_config_fields.left_legend {
                     (
                         1,
                         0,
-                        [Constraint::Percentage(15), Constraint::Percentage(85)],
+                        [
+                            Constraint::Percentage(legend_percentage),
+                            Constraint::Percentage(100 - legend_percentage),
+                        ],
                     )
                 } else {
                     (
                         0,
                         1,
-                        [Constraint::Percentage(85), Constraint::Percentage(15)],
+                        [
+                            Constraint::Percentage(100 - legend_percentage),
+                            Constraint::Percentage(legend_percentage),
+                        ],
                     )
                 };
 
@@ -136,14 +156,20 @@ impl CpuGraphWidget for Painter {
     ) {
         if let Some(cpu_widget_state) = app_state.cpu_state.widget_states.get_mut(&widget_id) {
             let cpu_data: &mut [ConvertedCpuData] = &mut app_state.canvas_data.cpu_data;
-
-            let display_time_labels = vec![
-                Span::styled(
-                    format!("{}s", cpu_widget_state.current_display_time / 1000),
-                    self.colours.graph_style,
-                ),
-                Span::styled("0s".to_string(), self.colours.graph_style),
-            ];
+            let show_top_processes = app_state.app_config_fields.show_top_cpu_processes;
+            let top_process_data = &mut app_state.canvas_data.top_process_cpu_data;
+
+            let display_time_labels = get_time_labels(
+                cpu_widget_state.current_display_time,
+                app_state
+                    .data_collection
+                    .get_graph_end_time(app_state.is_frozen),
+                draw_loc.width,
+                &app_state.app_config_fields,
+            )
+            .into_iter()
+            .map(|label| Span::styled(label, self.colours.graph_style))
+            .collect::<Vec<_>>();
 
             let y_axis_labels = vec![
                 Span::styled("  0%", self.colours.graph_style),
@@ -183,10 +209,26 @@ impl CpuGraphWidget for Painter {
                 .bounds([0.0, 100.5])
                 .labels(y_axis_labels);
 
-            let use_dot = app_state.app_config_fields.use_dot;
+            let graph_style = app_state.app_config_fields.graph_styles.cpu;
+            let marker = get_graph_marker(graph_style);
             let show_avg_cpu = app_state.app_config_fields.show_average_cpu;
             let current_scroll_position = cpu_widget_state.scroll_state.current_scroll_position;
 
+            let crosshair = get_crosshair(
+                &app_state.data_collection,
+                app_state.crosshair_time,
+                app_state.is_frozen,
+                time_start,
+                [0.0, 100.0],
+            );
+            let boot_marker_points = get_boot_marker_points(
+                &app_state
+                    .data_collection
+                    .get_boot_marker_offsets(app_state.is_frozen),
+                time_start,
+                [0.0, 100.0],
+            );
+
             let interpolated_cpu_points = cpu_data
                 .iter_mut()
                 .enumerate()
@@ -239,18 +281,60 @@ impl CpuGraphWidget for Painter {
                 })
                 .collect::<Vec<_>>();
 
-            let dataset_vector: Vec<Dataset<'_>> = if current_scroll_position == ALL_POSITION {
-                cpu_data
+            let mut interpolated_top_process_points = Vec::new();
+            if show_top_processes {
+                for (series_index, series) in top_process_data.iter_mut().enumerate() {
+                    if let Some(end_pos) = series
+                        .points
+                        .iter()
+                        .position(|(time, _data)| *time >= time_start)
+                    {
+                        if end_pos > 1 {
+                            let start_pos = end_pos - 1;
+                            if let (Some(outside_point), Some(inside_point)) =
+                                (series.points.get(start_pos), series.points.get(end_pos))
+                            {
+                                let old = *outside_point;
+                                let new_point = (
+                                    time_start,
+                                    interpolate_points(outside_point, inside_point, time_start),
+                                );
+
+                                if let Some(to_replace) = series.points.get_mut(start_pos) {
+                                    *to_replace = new_point;
+                                    interpolated_top_process_points.push((
+                                        series_index,
+                                        start_pos,
+                                        old,
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            let cpu_points = cpu_data
+                .iter()
+                .map(|cpu| {
+                    get_graph_points(
+                        &cpu.cpu_data,
+                        graph_style,
+                        [time_start, 0.0],
+                        0.0,
+                        draw_loc.width,
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let mut dataset_vector: Vec<Dataset<'_>> = if current_scroll_position == ALL_POSITION {
+                cpu_points
                     .iter()
                     .enumerate()
                     .rev()
-                    .map(|(itx, cpu)| {
+                    .map(|(itx, points)| {
                         Dataset::default()
-                            .marker(if use_dot {
-                                Marker::Dot
-                            } else {
-                                Marker::Braille
-                            })
+                            .marker(marker)
                             .style(if show_avg_cpu && itx == AVG_POSITION {
                                 self.colours.avg_colour_style
                             } else if itx == ALL_POSITION {
@@ -264,17 +348,13 @@ impl CpuGraphWidget for Painter {
                                         }))
                                     % self.colours.cpu_colour_styles.len()]
                             })
-                            .data(&cpu.cpu_data[..])
+                            .data(points)
                             .graph_type(tui::widgets::GraphType::Line)
                     })
                     .collect()
-            } else if let Some(cpu) = cpu_data.get(current_scroll_position) {
+            } else if let Some(points) = cpu_points.get(current_scroll_position) {
                 vec![Dataset::default()
-                    .marker(if use_dot {
-                        Marker::Dot
-                    } else {
-                        Marker::Braille
-                    })
+                    .marker(marker)
                     .style(if show_avg_cpu && current_scroll_position == AVG_POSITION {
                         self.colours.avg_colour_style
                     } else {
@@ -289,12 +369,90 @@ impl CpuGraphWidget for Painter {
                             }))
                             % self.colours.cpu_colour_styles.len()]
                     })
-                    .data(&cpu.cpu_data[..])
+                    .data(points)
                     .graph_type(tui::widgets::GraphType::Line)]
             } else {
                 vec![]
             };
 
+            // The busiest processes are drawn over whichever CPUs are shown.
+            if show_top_processes {
+                dataset_vector.extend(top_process_data.iter().enumerate().map(|(itx, series)| {
+                    Dataset::default()
+                        .marker(marker)
+                        .style(get_top_process_style(&self.colours, itx))
+                        .data(&series.points)
+                        .graph_type(tui::widgets::GraphType::Line)
+                }));
+            }
+
+            dataset_vector.extend(
+                boot_marker_points
+                    .iter()
+                    .map(|points| self.get_boot_marker_dataset(points, graph_style)),
+            );
+
+            // Shows the line that's selected in the legend, or the average if they all are.
+            let (crosshair_dataset, mut crosshair_readout) =
+                self.graph_crosshair(crosshair.as_ref(), graph_style, |crosshair_timed_data| {
+                    let crosshair_cpu_data = &crosshair_timed_data.cpu_data;
+                    if current_scroll_position == ALL_POSITION {
+                        if show_avg_cpu {
+                            crosshair_cpu_data
+                                .first()
+                                .map(|avg_value| {
+                                    Span::styled(
+                                        format!("AVG {:.0}%", avg_value),
+                                        self.colours.avg_colour_style,
+                                    )
+                                })
+                                .into_iter()
+                                .collect()
+                        } else if !crosshair_cpu_data.is_empty() {
+                            vec![Span::styled(
+                                format!(
+                                    "All {:.0}%",
+                                    crosshair_cpu_data.iter().sum::<f64>()
+                                        / crosshair_cpu_data.len() as f64
+                                ),
+                                self.colours.all_colour_style,
+                            )]
+                        } else {
+                            vec![]
+                        }
+                    } else if let (Some(cpu), Some(value)) = (
+                        cpu_data.get(current_scroll_position),
+                        crosshair_cpu_data.get(current_scroll_position - 1),
+                    ) {
+                        vec![Span::styled(
+                            format!("{} {:.0}%", cpu.cpu_name, value),
+                            if show_avg_cpu && current_scroll_position == AVG_POSITION {
+                                self.colours.avg_colour_style
+                            } else {
+                                self.colours.cpu_colour_styles[(current_scroll_position
+                                    - 1 // Because of the all position
+                                    - (if show_avg_cpu { AVG_POSITION } else { 0 }))
+                                    % self.colours.cpu_colour_styles.len()]
+                            },
+                        )]
+                    } else {
+                        vec![]
+                    }
+                });
+            dataset_vector.extend(crosshair_dataset);
+
+            // Without a crosshair, label the busiest processes' lines with their latest usage.
+            if show_top_processes && crosshair_readout.is_empty() {
+                crosshair_readout.extend(top_process_data.iter().enumerate().map(
+                    |(itx, series)| {
+                        Span::styled(
+                            format!("{} {}", series.name, series.value_display),
+                            get_top_process_style(&self.colours, itx),
+                        )
+                    },
+                ));
+            }
+
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let border_style = if is_on_widget {
                 self.colours.highlighted_border_style
@@ -304,37 +462,83 @@ impl CpuGraphWidget for Painter {
 
             let title = if cfg!(target_family = "unix") {
                 let load_avg = app_state.canvas_data.load_avg_data;
-                let load_avg_str = format!(
+                let mut title_details_str = format!(
                     "─ {:.2} {:.2} {:.2} ",
                     load_avg[0], load_avg[1], load_avg[2]
                 );
-                let load_avg_str_size =
-                    UnicodeSegmentation::graphemes(load_avg_str.as_str(), true).count();
+                if let Some(cpu_breakdown) = &app_state.canvas_data.cpu_breakdown {
+                    title_details_str.push_str(&format!(
+                        "─ usr {:.0}% sys {:.0}% iowait {:.0}% steal {:.0}% irq {:.0}% ",
+                        cpu_breakdown.user_percent,
+                        cpu_breakdown.system_percent,
+                        cpu_breakdown.io_wait_percent,
+                        cpu_breakdown.steal_percent,
+                        cpu_breakdown.irq_percent
+                    ));
+                }
+                let mut throttle_warnings = vec![];
+                if let Some(throttle) = &app_state.canvas_data.throttle {
+                    if let Some(core_voltage) = throttle.core_voltage {
+                        title_details_str.push_str(&format!("─ {:.2}V ", core_voltage));
+                    }
+
+                    // Problems right now are more urgent than ones that happened since boot.
+                    if throttle.is_under_voltage {
+                        throttle_warnings.push(Span::styled(
+                            "─ UNDER-VOLTAGE ",
+                            self.colours.low_battery_colour,
+                        ));
+                    } else if throttle.has_under_voltage_occurred {
+                        throttle_warnings.push(Span::styled(
+                            "─ under-voltage since boot ",
+                            self.colours.medium_battery_colour,
+                        ));
+                    }
+                    if throttle.is_throttled {
+                        throttle_warnings.push(Span::styled(
+                            "─ THROTTLED ",
+                            self.colours.low_battery_colour,
+                        ));
+                    } else if throttle.has_throttled_occurred {
+                        throttle_warnings.push(Span::styled(
+                            "─ throttled since boot ",
+                            self.colours.medium_battery_colour,
+                        ));
+                    }
+                }
+                let title_details_str_size =
+                    UnicodeSegmentation::graphemes(title_details_str.as_str(), true).count()
+                        + throttle_warnings
+                            .iter()
+                            .map(|warning| {
+                                UnicodeSegmentation::graphemes(warning.content.as_ref(), true)
+                                    .count()
+                            })
+                            .sum::<usize>();
+
+                let mut title_spans = vec![
+                    Span::styled(" CPU ", self.colours.widget_title_style),
+                    Span::styled(title_details_str, self.colours.widget_title_style),
+                ];
+                title_spans.extend(throttle_warnings);
 
                 if app_state.is_expanded {
                     const TITLE_BASE: &str = " CPU ── Esc to go back ";
 
-                    Spans::from(vec![
-                        Span::styled(" CPU ", self.colours.widget_title_style),
-                        Span::styled(load_avg_str, self.colours.widget_title_style),
-                        Span::styled(
-                            format!(
-                                "─{}─ Esc to go back ",
-                                "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                    load_avg_str_size
-                                        + UnicodeSegmentation::graphemes(TITLE_BASE, true).count()
-                                        + 2
-                                ))
-                            ),
-                            border_style,
+                    title_spans.push(Span::styled(
+                        format!(
+                            "─{}─ Esc to go back ",
+                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                                title_details_str_size
+                                    + UnicodeSegmentation::graphemes(TITLE_BASE, true).count()
+                                    + 2
+                            ))
                         ),
-                    ])
-                } else {
-                    Spans::from(vec![
-                        Span::styled(" CPU ", self.colours.widget_title_style),
-                        Span::styled(load_avg_str, self.colours.widget_title_style),
-                    ])
+                        border_style,
+                    ));
                 }
+
+                Spans::from(title_spans)
             } else if app_state.is_expanded {
                 const TITLE_BASE: &str = " CPU ── Esc to go back ";
 
@@ -366,6 +570,7 @@ impl CpuGraphWidget for Painter {
                     .y_axis(y_axis),
                 draw_loc,
             );
+            self.draw_crosshair_readout(f, draw_loc, crosshair_readout);
 
             // Reset interpolated points
             cpu_data
@@ -378,6 +583,14 @@ impl CpuGraphWidget for Painter {
                         }
                     }
                 });
+            for (series_index, index, old_value) in interpolated_top_process_points {
+                if let Some(to_replace) = top_process_data
+                    .get_mut(series_index)
+                    .and_then(|series| series.points.get_mut(index))
+                {
+                    *to_replace = old_value;
+                }
+            }
         }
     }
 
@@ -389,6 +602,14 @@ impl CpuGraphWidget for Painter {
         {
             cpu_widget_state.is_legend_hidden = false;
             let cpu_data: &mut [ConvertedCpuData] = &mut app_state.canvas_data.cpu_data;
+
+            // CPUs can be unplugged at runtime, which can leave the selection past the end.
+            let scroll_state = &mut cpu_widget_state.scroll_state;
+            scroll_state.current_scroll_position = std::cmp::min(
+                scroll_state.current_scroll_position,
+                cpu_data.len().saturating_sub(1),
+            );
+
             let cpu_table_state = &mut cpu_widget_state.scroll_state.table_state;
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
@@ -412,25 +633,32 @@ impl CpuGraphWidget for Painter {
                     .saturating_sub(start_position),
             ));
 
-            let sliced_cpu_data = &cpu_data[start_position..];
+            let sliced_cpu_data = cpu_data.get(start_position..).unwrap_or_default();
 
             let offset_scroll_index = cpu_widget_state
                 .scroll_state
                 .current_scroll_position
                 .saturating_sub(start_position);
             let show_avg_cpu = app_state.app_config_fields.show_average_cpu;
+            let num_columns = if cpu_widget_state.is_legend_expanded {
+                CPU_LEGEND_HEADER.len()
+            } else {
+                NUM_BASE_LEGEND_COLUMNS
+            };
 
             // Calculate widths
             if recalculate_column_widths {
-                cpu_widget_state.table_width_state.desired_column_widths = vec![6, 4];
+                cpu_widget_state.table_width_state.desired_column_widths =
+                    [6, 4, 4, 4][..num_columns].to_vec();
                 cpu_widget_state.table_width_state.calculated_column_widths = get_column_widths(
                     draw_loc.width,
-                    &[None, None],
+                    &vec![None; num_columns],
                     &(CPU_LEGEND_HEADER_LENS
                         .iter()
+                        .take(num_columns)
                         .map(|width| Some(*width))
                         .collect::<Vec<_>>()),
-                    &[Some(0.5), Some(0.5)],
+                    &vec![Some(1.0 / num_columns as f64); num_columns],
                     &(cpu_widget_state
                         .table_width_state
                         .desired_column_widths
@@ -444,7 +672,7 @@ impl CpuGraphWidget for Painter {
             let dcw = &cpu_widget_state.table_width_state.desired_column_widths;
             let ccw = &cpu_widget_state.table_width_state.calculated_column_widths;
             let cpu_rows = sliced_cpu_data.iter().enumerate().map(|(itx, cpu)| {
-                let mut truncated_name =
+                let truncated_name =
                     if let (Some(desired_column_width), Some(calculated_column_width)) =
                         (dcw.get(0), ccw.get(0))
                     {
@@ -470,17 +698,23 @@ impl CpuGraphWidget for Painter {
                     Text::raw(&cpu.legend_value)
                 };
 
+                let mut cpu_string_row = vec![truncated_name, truncated_legend];
+                if num_columns > NUM_BASE_LEGEND_COLUMNS {
+                    cpu_string_row.push(Text::raw(&cpu.avg_legend_value));
+                    cpu_string_row.push(Text::raw(&cpu.max_legend_value));
+                }
+
                 if !is_first_column_hidden
                     && itx == offset_scroll_index
                     && itx + start_position == ALL_POSITION
                 {
-                    truncated_name.patch_style(self.colours.currently_selected_text_style);
-                    Row::new(vec![truncated_name, truncated_legend])
+                    cpu_string_row[0].patch_style(self.colours.currently_selected_text_style);
+                    Row::new(cpu_string_row)
                 } else {
-                    let cpu_string_row = vec![truncated_name, truncated_legend];
-
                     Row::new(cpu_string_row).style(if itx == offset_scroll_index {
                         self.colours.currently_selected_text_style
+                    } else if cpu.is_offline {
+                        self.colours.disabled_text_style
                     } else if itx + start_position == ALL_POSITION {
                         self.colours.all_colour_style
                     } else if show_avg_cpu {
@@ -515,7 +749,7 @@ impl CpuGraphWidget for Painter {
                             .border_style(border_and_title_style),
                     )
                     .header(
-                        Row::new(CPU_LEGEND_HEADER.to_vec())
+                        Row::new(CPU_LEGEND_HEADER[..num_columns].to_vec())
                             .style(self.colours.table_header_style)
                             .bottom_margin(table_gap),
                     )
@@ -533,3 +767,14 @@ impl CpuGraphWidget for Painter {
         }
     }
 }
+
+/// The style of one of the busiest processes' lines on the CPU graph.  These are taken from the end
+/// of the CPU colours, so they're less likely to match the CPUs they're drawn over.
+fn get_top_process_style(colours: &CanvasColours, itx: usize) -> Style {
+    if colours.cpu_colour_styles.is_empty() {
+        colours.text_style
+    } else {
+        let num_styles = colours.cpu_colour_styles.len();
+        colours.cpu_colour_styles[num_styles - 1 - itx % num_styles]
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{app::App, canvas::Painter, constants::*};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The colour of each tenth of load, from idle to fully loaded.  These are from the 256-colour
+/// palette, and go from grey, through green and yellow, to red.
+const HEATMAP_COLOURS: [Color; 10] = [
+    Color::Indexed(238),
+    Color::Indexed(22),
+    Color::Indexed(28),
+    Color::Indexed(34),
+    Color::Indexed(70),
+    Color::Indexed(142),
+    Color::Indexed(178),
+    Color::Indexed(208),
+    Color::Indexed(202),
+    Color::Indexed(196),
+];
+
+/// The colours above that are dark enough to need light text on top.
+const NUM_DARK_HEATMAP_COLOURS: usize = 3;
+
+/// A cell showing the usage, and a gap.
+const LABELLED_CELL_WIDTH: usize = 4;
+/// A cell that is only coloured in, and a gap.
+const COMPACT_CELL_WIDTH: usize = 2;
+
+pub trait CpuHeatmapWidget {
+    fn draw_cpu_heatmap<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl CpuHeatmapWidget for Painter {
+    fn draw_cpu_heatmap<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        let border_style = if is_on_widget {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        };
+
+        // Skip the "all" entry, and the average if there is one; only cores go in the grid.
+        let num_skipped = if app_state.app_config_fields.show_average_cpu {
+            2
+        } else {
+            1
+        };
+        let core_usages: Vec<f64> = app_state
+            .canvas_data
+            .cpu_data
+            .iter()
+            .skip(num_skipped)
+            .map(|cpu| cpu.cpu_data.last().map(|point| point.1).unwrap_or(0.0))
+            .collect();
+
+        let heatmap_title = if core_usages.is_empty() {
+            " CPU Heatmap ".to_string()
+        } else {
+            format!(
+                " CPU Heatmap ({:.0}% avg) ",
+                core_usages.iter().sum::<f64>() / core_usages.len() as f64
+            )
+        };
+        let title = if app_state.is_expanded {
+            let title_base = format!("{}── Esc to go back ", heatmap_title);
+            Spans::from(vec![
+                Span::styled(heatmap_title, self.colours.widget_title_style),
+                Span::styled(
+                    format!(
+                        "─{}─ Esc to go back ",
+                        "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                            UnicodeSegmentation::graphemes(title_base.as_str(), true).count() + 2
+                        ))
+                    ),
+                    border_style,
+                ),
+            ])
+        } else {
+            Spans::from(Span::styled(heatmap_title, self.colours.widget_title_style))
+        };
+
+        let heatmap_block = if draw_border {
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(border_style)
+        } else if is_on_widget {
+            Block::default()
+                .borders(*SIDE_BORDERS)
+                .border_style(self.colours.highlighted_border_style)
+        } else {
+            Block::default().borders(Borders::NONE)
+        };
+
+        let margined_draw_loc = Layout::default()
+            .constraints([Constraint::Percentage(100)])
+            .horizontal_margin(if is_on_widget || draw_border { 0 } else { 1 })
+            .direction(Direction::Horizontal)
+            .split(draw_loc)[0];
+
+        let inner_loc = heatmap_block.inner(margined_draw_loc);
+        let num_cores = core_usages.len();
+
+        // Each row starts with the index of its first core.
+        let label_width = num_cores.saturating_sub(1).to_string().len() + 1;
+        let grid_width = usize::from(inner_loc.width).saturating_sub(label_width);
+        let grid_height = usize::from(inner_loc.height);
+
+        let (cell_width, num_columns) = {
+            let num_labelled_columns = grid_width / LABELLED_CELL_WIDTH;
+            if num_labelled_columns > 0
+                && (num_cores + num_labelled_columns - 1) / num_labelled_columns <= grid_height
+            {
+                (LABELLED_CELL_WIDTH, num_labelled_columns)
+            } else {
+                (COMPACT_CELL_WIDTH, (grid_width / COMPACT_CELL_WIDTH).max(1))
+            }
+        };
+
+        // Spread the cores evenly over the rows, rather than leaving the last one mostly empty.
+        let num_rows = (num_cores + num_columns - 1) / num_columns;
+        let num_columns = if num_rows > 0 {
+            (num_cores + num_rows - 1) / num_rows
+        } else {
+            num_columns
+        };
+
+        let heatmap_text: Vec<Spans<'_>> = core_usages
+            .chunks(num_columns)
+            .enumerate()
+            .map(|(row, row_usages)| {
+                let mut row_spans = vec![Span::styled(
+                    format!("{:>width$} ", row * num_columns, width = label_width - 1),
+                    self.colours.table_header_style,
+                )];
+
+                for usage in row_usages {
+                    let colour_index = ((usage / 10.0) as usize).min(HEATMAP_COLOURS.len() - 1);
+                    let colour = HEATMAP_COLOURS[colour_index];
+
+                    if cell_width == LABELLED_CELL_WIDTH {
+                        row_spans.push(Span::styled(
+                            format!("{:>3.0}", usage.round()),
+                            Style::default().bg(colour).fg(
+                                if colour_index < NUM_DARK_HEATMAP_COLOURS {
+                                    Color::White
+                                } else {
+                                    Color::Black
+                                },
+                            ),
+                        ));
+                    } else {
+                        row_spans.push(Span::styled("█", Style::default().fg(colour)));
+                    }
+                    row_spans.push(Span::raw(" "));
+                }
+
+                Spans::from(row_spans)
+            })
+            .collect();
+
+        f.render_widget(
+            Paragraph::new(heatmap_text)
+                .block(heatmap_block)
+                .style(self.colours.text_style),
+            margined_draw_loc,
+        );
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
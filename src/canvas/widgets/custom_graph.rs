@@ -0,0 +1,251 @@
+use crate::{
+    app::App,
+    canvas::{
+        drawing_utils::{get_graph_marker, get_graph_points, get_time_labels, interpolate_points},
+        widgets::graph_crosshair::{get_crosshair, GraphCrosshair},
+        Painter,
+    },
+    constants::*,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    terminal::Frame,
+    text::Span,
+    text::Spans,
+    widgets::{Axis, Block, Borders, Chart, Dataset},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub trait CustomGraphWidget {
+    fn draw_custom_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    );
+}
+
+impl CustomGraphWidget for Painter {
+    fn draw_custom_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        if let Some(custom_widget_state) = app_state.custom_state.widget_states.get_mut(&widget_id)
+        {
+            let plugin_data = &mut app_state.canvas_data.plugin_data;
+
+            let time_start = -(custom_widget_state.current_display_time as f64);
+
+            // Plugin values can be anything, so scale to the largest value currently visible.
+            let max_value = plugin_data
+                .iter()
+                .flat_map(|series| series.points.iter())
+                .filter(|(time, _value)| *time >= time_start)
+                .fold(0.0_f64, |max, (_time, value)| max.max(*value));
+            let max_bound = if max_value > 0.0 {
+                max_value * 1.1
+            } else {
+                1.0
+            };
+
+            let display_time_labels = get_time_labels(
+                custom_widget_state.current_display_time,
+                app_state
+                    .data_collection
+                    .get_graph_end_time(app_state.is_frozen),
+                draw_loc.width,
+                &app_state.app_config_fields,
+            )
+            .into_iter()
+            .map(|label| Span::styled(label, self.colours.graph_style))
+            .collect::<Vec<_>>();
+            let y_axis_label = vec![
+                Span::styled("0", self.colours.graph_style),
+                Span::styled(format!("{:.1}", max_bound), self.colours.graph_style),
+            ];
+
+            let x_axis = if app_state.app_config_fields.hide_time
+                || (app_state.app_config_fields.autohide_time
+                    && custom_widget_state.autohide_timer.is_none())
+            {
+                Axis::default().bounds([time_start, 0.0])
+            } else if let Some(time) = custom_widget_state.autohide_timer {
+                if std::time::Instant::now().duration_since(time).as_millis()
+                    < AUTOHIDE_TIMEOUT_MILLISECONDS as u128
+                {
+                    Axis::default()
+                        .bounds([time_start, 0.0])
+                        .style(self.colours.graph_style)
+                        .labels(display_time_labels)
+                } else {
+                    custom_widget_state.autohide_timer = None;
+                    Axis::default().bounds([time_start, 0.0])
+                }
+            } else if draw_loc.height < TIME_LABEL_HEIGHT_LIMIT {
+                Axis::default().bounds([time_start, 0.0])
+            } else {
+                Axis::default()
+                    .bounds([time_start, 0.0])
+                    .style(self.colours.graph_style)
+                    .labels(display_time_labels)
+            };
+
+            let y_axis = Axis::default()
+                .style(self.colours.graph_style)
+                .bounds([0.0, max_bound])
+                .labels(y_axis_label);
+
+            // Interpolate values to avoid ugly gaps
+            let mut interpolated_points = Vec::new();
+            for (series_index, series) in plugin_data.iter_mut().enumerate() {
+                if let Some(end_pos) = series
+                    .points
+                    .iter()
+                    .position(|(time, _data)| *time >= time_start)
+                {
+                    if end_pos > 1 {
+                        let start_pos = end_pos - 1;
+                        if let (Some(outside_point), Some(inside_point)) =
+                            (series.points.get(start_pos), series.points.get(end_pos))
+                        {
+                            let old = *outside_point;
+                            let new_point = (
+                                time_start,
+                                interpolate_points(outside_point, inside_point, time_start),
+                            );
+
+                            if let Some(to_replace) = series.points.get_mut(start_pos) {
+                                *to_replace = new_point;
+                                interpolated_points.push((series_index, start_pos, old));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let graph_style = app_state.app_config_fields.graph_styles.custom;
+            let marker = get_graph_marker(graph_style);
+            let series_points = plugin_data
+                .iter()
+                .map(|series| {
+                    get_graph_points(
+                        &series.points,
+                        graph_style,
+                        [time_start, 0.0],
+                        0.0,
+                        draw_loc.width,
+                    )
+                })
+                .collect::<Vec<_>>();
+            let mut custom_canvas_vec: Vec<Dataset<'_>> = plugin_data
+                .iter()
+                .zip(&series_points)
+                .enumerate()
+                .map(|(itx, (series, points))| {
+                    let style = if self.colours.cpu_colour_styles.is_empty() {
+                        self.colours.text_style
+                    } else {
+                        self.colours.cpu_colour_styles[itx % self.colours.cpu_colour_styles.len()]
+                    };
+
+                    Dataset::default()
+                        .name(format!("{}: {}", series.name, series.value_display))
+                        .marker(marker)
+                        .style(style)
+                        .data(points)
+                        .graph_type(tui::widgets::GraphType::Line)
+                })
+                .collect();
+
+            let crosshair = get_crosshair(
+                &app_state.data_collection,
+                app_state.crosshair_time,
+                app_state.is_frozen,
+                time_start,
+                [0.0, max_bound],
+            );
+            let (crosshair_dataset, crosshair_readout) =
+                self.graph_crosshair(crosshair.as_ref(), graph_style, |crosshair_timed_data| {
+                    // Match each value up with its series, so it's shown in the same colour.
+                    plugin_data
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(itx, series)| {
+                            let (_, value) = crosshair_timed_data
+                                .plugin_data
+                                .iter()
+                                .find(|(name, _)| *name == series.name)?;
+                            let style = if self.colours.cpu_colour_styles.is_empty() {
+                                self.colours.text_style
+                            } else {
+                                self.colours.cpu_colour_styles
+                                    [itx % self.colours.cpu_colour_styles.len()]
+                            };
+                            Some(Span::styled(format!("{} {:.1}", series.name, value), style))
+                        })
+                        .collect()
+                });
+            custom_canvas_vec.extend(crosshair_dataset);
+
+            let is_on_widget = widget_id == app_state.current_widget.widget_id;
+            let border_style = if is_on_widget {
+                self.colours.highlighted_border_style
+            } else {
+                self.colours.border_style
+            };
+
+            let title = if app_state.is_expanded {
+                const TITLE_BASE: &str = " Custom ── Esc to go back ";
+                Spans::from(vec![
+                    Span::styled(" Custom ", self.colours.widget_title_style),
+                    Span::styled(
+                        format!(
+                            "─{}─ Esc to go back ",
+                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
+                            ))
+                        ),
+                        border_style,
+                    ),
+                ])
+            } else {
+                Spans::from(Span::styled(
+                    " Custom ".to_string(),
+                    self.colours.widget_title_style,
+                ))
+            };
+
+            f.render_widget(
+                Chart::new(custom_canvas_vec)
+                    .block(
+                        Block::default()
+                            .title(title)
+                            .borders(Borders::ALL)
+                            .border_style(border_style),
+                    )
+                    .x_axis(x_axis)
+                    .y_axis(y_axis)
+                    .hidden_legend_constraints((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                draw_loc,
+            );
+            self.draw_crosshair_readout(f, draw_loc, crosshair_readout);
+
+            // Now if you're done, reset any interpolated points!
+            for (series_index, index, old_value) in interpolated_points {
+                if let Some(to_replace) = plugin_data
+                    .get_mut(series_index)
+                    .and_then(|series| series.points.get_mut(index))
+                {
+                    *to_replace = old_value;
+                }
+            }
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
@@ -1,4 +1,3 @@
-use once_cell::sync::Lazy;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
@@ -18,14 +17,40 @@ use crate::{
 };
 use unicode_segmentation::UnicodeSegmentation;
 
-const DISK_HEADERS: [&str; 7] = ["Disk", "Mount", "Used", "Free", "Total", "R/s", "W/s"];
+const DISK_HEADERS: [&str; 10] = [
+    "Disk", "Mount", "Type", "Used", "Inodes", "Free", "Total", "Trend", "R/s", "W/s",
+];
+/// The headers used when showing the I/O of the whole device each disk is on.
+const DEVICE_IO_DISK_HEADERS: [&str; 10] = [
+    "Disk", "Mount", "Type", "Used", "Inodes", "Free", "Total", "Trend", "Dev R/s", "Dev W/s",
+];
+const DISK_HARD_WIDTHS: [Option<u16>; 10] = [
+    None,
+    None,
+    None,
+    Some(4),
+    Some(6),
+    Some(6),
+    Some(6),
+    Some(15),
+    Some(7),
+    Some(7),
+];
+const DISK_SOFT_WIDTHS_MAX: [Option<f64>; 10] = [
+    Some(0.2),
+    Some(0.2),
+    Some(0.1),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+];
 
-static DISK_HEADERS_LENS: Lazy<Vec<u16>> = Lazy::new(|| {
-    DISK_HEADERS
-        .iter()
-        .map(|entry| entry.len() as u16)
-        .collect::<Vec<_>>()
-});
+/// The inodes column is only shown if asked for.
+const INODES_COLUMN: usize = 4;
 
 pub trait DiskTableWidget {
     fn draw_disk_table<B: Backend>(
@@ -65,11 +90,32 @@ impl DiskTableWidget for Painter {
             ));
             let sliced_vec = &app_state.canvas_data.disk_data[start_position..];
 
+            let show_inodes = app_state.app_config_fields.show_disk_inodes;
+            let shown_columns = (0..DISK_HEADERS.len())
+                .filter(|column| show_inodes || *column != INODES_COLUMN)
+                .collect::<Vec<_>>();
+            let column_headers = if app_state.app_config_fields.disk_io_per_device {
+                DEVICE_IO_DISK_HEADERS
+            } else {
+                DISK_HEADERS
+            };
+            let headers = shown_columns
+                .iter()
+                .map(|column| column_headers[*column])
+                .collect::<Vec<_>>();
+            let headers_lens = headers
+                .iter()
+                .map(|header| header.len() as u16)
+                .collect::<Vec<_>>();
+
             // Calculate widths
-            let hard_widths = [None, None, Some(4), Some(6), Some(6), Some(7), Some(7)];
+            let hard_widths = shown_columns
+                .iter()
+                .map(|column| DISK_HARD_WIDTHS[*column])
+                .collect::<Vec<_>>();
             if recalculate_column_widths {
                 disk_widget_state.table_width_state.desired_column_widths = {
-                    let mut column_widths = DISK_HEADERS_LENS.clone();
+                    let mut column_widths = headers_lens.clone();
                     for row in sliced_vec {
                         for (col, entry) in row.iter().enumerate() {
                             if entry.len() as u16 > column_widths[col] {
@@ -100,11 +146,11 @@ impl DiskTableWidget for Painter {
                 disk_widget_state.table_width_state.calculated_column_widths = get_column_widths(
                     draw_loc.width,
                     &hard_widths,
-                    &(DISK_HEADERS_LENS
+                    &(headers_lens.iter().map(|w| Some(*w)).collect::<Vec<_>>()),
+                    &(shown_columns
                         .iter()
-                        .map(|w| Some(*w))
+                        .map(|column| DISK_SOFT_WIDTHS_MAX[*column])
                         .collect::<Vec<_>>()),
-                    &[Some(0.2), Some(0.2), None, None, None, None, None],
                     &(disk_widget_state
                         .table_width_state
                         .desired_column_widths
@@ -243,7 +289,7 @@ impl DiskTableWidget for Painter {
                 Table::new(disk_rows)
                     .block(disk_block)
                     .header(
-                        Row::new(DISK_HEADERS.to_vec())
+                        Row::new(headers)
                             .style(self.colours.table_header_style)
                             .bottom_margin(table_gap),
                     )
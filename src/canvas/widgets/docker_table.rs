@@ -0,0 +1,60 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        widgets::simple_table::{SimpleTableColumns, SimpleTableParts, SimpleTableWidget},
+        Painter,
+    },
+};
+
+const CONTAINER_COLUMNS: SimpleTableColumns = SimpleTableColumns {
+    headers: &[
+        "Container",
+        "Image",
+        "Status",
+        "CPU%",
+        "Mem",
+        "RX/s",
+        "TX/s",
+    ],
+    hard_widths: &[None, None, None, Some(7), Some(9), Some(9), Some(9)],
+    max_width_ratios: &[Some(0.25), Some(0.25), Some(0.2), None, None, None, None],
+};
+
+pub trait DockerTableWidget {
+    fn draw_docker_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl DockerTableWidget for Painter {
+    fn draw_docker_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_simple_table(
+            f,
+            app_state,
+            draw_loc,
+            draw_border,
+            widget_id,
+            " Docker ",
+            &CONTAINER_COLUMNS,
+            get_docker_table_parts,
+        );
+    }
+}
+
+fn get_docker_table_parts(
+    app_state: &mut app::App, widget_id: u64,
+) -> Option<SimpleTableParts<'_>> {
+    let docker_widget_state = app_state.docker_state.widget_states.get_mut(&widget_id)?;
+    Some(SimpleTableParts {
+        scroll_state: &mut docker_widget_state.scroll_state,
+        table_width_state: &mut docker_widget_state.table_width_state,
+        rows: &app_state.canvas_data.container_data,
+        status_text: docker_widget_state.status_text.as_deref(),
+    })
+}
@@ -0,0 +1,159 @@
+use std::time::Instant;
+
+use crate::{
+    app::{
+        data_farmer::{DataCollection, TimedData},
+        GraphStyle,
+    },
+    canvas::{drawing_utils::get_graph_marker, Painter},
+};
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Dataset, Paragraph},
+};
+
+/// Where the crosshair is, and what was collected at that time.
+pub struct Crosshair<'a> {
+    /// How far back the crosshair is from the end of the graphs, in milliseconds and as a
+    /// negative number like the graphs' x-axis.
+    pub offset: f64,
+    pub timed_data: &'a TimedData,
+    /// The crosshair's line, if it's within the time the graph shows.
+    points: Option<[(f64, f64); 2]>,
+}
+
+/// Returns the crosshair for a graph showing from `time_start` with the given y-axis bounds, if
+/// the crosshair is on.
+pub fn get_crosshair(
+    data_collection: &DataCollection, crosshair_time: Option<Instant>, is_frozen: bool,
+    time_start: f64, y_bounds: [f64; 2],
+) -> Option<Crosshair<'_>> {
+    let (offset, timed_data) = data_collection.get_graph_data_at(crosshair_time?, is_frozen)?;
+    Some(Crosshair {
+        offset,
+        timed_data,
+        points: get_crosshair_points(offset, time_start, y_bounds),
+    })
+}
+
+pub trait GraphCrosshair {
+    /// Returns the crosshair's line, named after how far back it is.
+    fn get_crosshair_dataset<'a>(
+        &self, crosshair_points: &'a [(f64, f64)], crosshair_offset: f64, graph_style: GraphStyle,
+    ) -> Dataset<'a>;
+
+    /// Returns the crosshair's line on a graph, if it's on the graph, and the values at the
+    /// crosshair to draw with [`GraphCrosshair::draw_crosshair_readout`], which `get_readout`
+    /// picks out of what was collected at that time.
+    fn graph_crosshair<'a, F>(
+        &self, crosshair: Option<&'a Crosshair<'_>>, graph_style: GraphStyle, get_readout: F,
+    ) -> (Option<Dataset<'a>>, Vec<Span<'a>>)
+    where
+        F: FnOnce(&TimedData) -> Vec<Span<'a>>;
+
+    /// Returns the line marking a reboot.
+    fn get_boot_marker_dataset<'a>(
+        &self, boot_marker_points: &'a [(f64, f64)], graph_style: GraphStyle,
+    ) -> Dataset<'a>;
+
+    /// Draws the values at the crosshair over the bottom border of a graph.
+    fn draw_crosshair_readout<B: Backend>(
+        &self, f: &mut Frame<'_, B>, draw_loc: Rect, readout: Vec<Span<'_>>,
+    );
+}
+
+impl GraphCrosshair for Painter {
+    fn get_crosshair_dataset<'a>(
+        &self, crosshair_points: &'a [(f64, f64)], crosshair_offset: f64, graph_style: GraphStyle,
+    ) -> Dataset<'a> {
+        Dataset::default()
+            .name(format!("{:.1}s", crosshair_offset / 1000.0))
+            .marker(get_graph_marker(graph_style))
+            .style(self.colours.graph_style)
+            .data(crosshair_points)
+            .graph_type(tui::widgets::GraphType::Line)
+    }
+
+    fn graph_crosshair<'a, F>(
+        &self, crosshair: Option<&'a Crosshair<'_>>, graph_style: GraphStyle, get_readout: F,
+    ) -> (Option<Dataset<'a>>, Vec<Span<'a>>)
+    where
+        F: FnOnce(&TimedData) -> Vec<Span<'a>>,
+    {
+        match crosshair {
+            Some(crosshair) => (
+                crosshair.points.as_ref().map(|points| {
+                    self.get_crosshair_dataset(points, crosshair.offset, graph_style)
+                }),
+                get_readout(crosshair.timed_data),
+            ),
+            None => (None, vec![]),
+        }
+    }
+
+    fn get_boot_marker_dataset<'a>(
+        &self, boot_marker_points: &'a [(f64, f64)], graph_style: GraphStyle,
+    ) -> Dataset<'a> {
+        Dataset::default()
+            .name("Reboot")
+            .marker(get_graph_marker(graph_style))
+            .style(self.colours.border_style)
+            .data(boot_marker_points)
+            .graph_type(tui::widgets::GraphType::Line)
+    }
+
+    fn draw_crosshair_readout<B: Backend>(
+        &self, f: &mut Frame<'_, B>, draw_loc: Rect, readout: Vec<Span<'_>>,
+    ) {
+        if draw_loc.height < 2 || readout.is_empty() {
+            return;
+        }
+
+        let mut readout_spans = vec![Span::raw(" ")];
+        for (itx, span) in readout.into_iter().enumerate() {
+            if itx > 0 {
+                readout_spans.push(Span::raw("  "));
+            }
+            readout_spans.push(span);
+        }
+        readout_spans.push(Span::raw(" "));
+
+        let readout_draw_loc = Rect {
+            x: draw_loc.x + 1,
+            y: draw_loc.y + draw_loc.height - 1,
+            width: draw_loc.width.saturating_sub(2),
+            height: 1,
+        };
+        f.render_widget(Paragraph::new(Spans::from(readout_spans)), readout_draw_loc);
+    }
+}
+
+/// Returns the crosshair's line for a graph, if it's within the time the graph shows.
+pub fn get_crosshair_points(
+    crosshair_offset: f64, time_start: f64, y_bounds: [f64; 2],
+) -> Option<[(f64, f64); 2]> {
+    if crosshair_offset >= time_start && crosshair_offset <= 0.0 {
+        Some([
+            (crosshair_offset, y_bounds[0]),
+            (crosshair_offset, y_bounds[1]),
+        ])
+    } else {
+        None
+    }
+}
+
+/// Returns the lines marking any reboots within the time a graph shows.
+pub fn get_boot_marker_points(
+    boot_marker_offsets: &[f64], time_start: f64, y_bounds: [f64; 2],
+) -> Vec<[(f64, f64); 2]> {
+    boot_marker_offsets
+        .iter()
+        .filter_map(|boot_marker_offset| {
+            get_crosshair_points(*boot_marker_offset, time_start, y_bounds)
+        })
+        .collect()
+}
@@ -0,0 +1,144 @@
+use crate::{
+    app::App,
+    canvas::{canvas_colours::CanvasColours, Painter},
+    constants::*,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Usage, as a percentage of the limit, at which a limit is shown as getting close and as nearly
+/// hit.
+const WARNING_PERCENT: f64 = 75.0;
+const CRITICAL_PERCENT: f64 = 90.0;
+
+pub trait KernelLimitsDisplayWidget {
+    fn draw_kernel_limits_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl KernelLimitsDisplayWidget for Painter {
+    fn draw_kernel_limits_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        let border_style = if is_on_widget {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        };
+
+        let kernel_limits_title = " Kernel Limits ";
+        let title = if app_state.is_expanded {
+            let title_base = format!("{}── Esc to go back ", kernel_limits_title);
+            Spans::from(vec![
+                Span::styled(kernel_limits_title, self.colours.widget_title_style),
+                Span::styled(
+                    format!(
+                        "─{}─ Esc to go back ",
+                        "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                            UnicodeSegmentation::graphemes(title_base.as_str(), true).count() + 2
+                        ))
+                    ),
+                    border_style,
+                ),
+            ])
+        } else {
+            Spans::from(Span::styled(
+                kernel_limits_title,
+                self.colours.widget_title_style,
+            ))
+        };
+
+        let kernel_limits_block = if draw_border {
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(border_style)
+        } else if is_on_widget {
+            Block::default()
+                .borders(*SIDE_BORDERS)
+                .border_style(self.colours.highlighted_border_style)
+        } else {
+            Block::default().borders(Borders::NONE)
+        };
+
+        let margined_draw_loc = Layout::default()
+            .constraints([Constraint::Percentage(100)])
+            .horizontal_margin(if is_on_widget || draw_border { 0 } else { 1 })
+            .direction(Direction::Horizontal)
+            .split(draw_loc)[0];
+
+        let kernel_limits = &app_state.data_collection.kernel_limits_harvest;
+        let text = vec![
+            get_kernel_limit_line(&self.colours, "Files", kernel_limits.file_handles, false),
+            get_kernel_limit_line(&self.colours, "PIDs", kernel_limits.pids, false),
+            get_kernel_limit_line(&self.colours, "Entropy", kernel_limits.entropy, true),
+        ];
+
+        f.render_widget(
+            Paragraph::new(text).block(kernel_limits_block),
+            margined_draw_loc,
+        );
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((margined_draw_loc.x, margined_draw_loc.y));
+                widget.bottom_right_corner = Some((
+                    margined_draw_loc.x + margined_draw_loc.width,
+                    margined_draw_loc.y + margined_draw_loc.height,
+                ));
+            }
+        }
+    }
+}
+
+/// Returns a line for one limit, coloured by how close it is to being hit.  If `is_low_bad` is
+/// set, it's the amount left that matters, rather than the amount used.
+fn get_kernel_limit_line(
+    colours: &CanvasColours, name: &str, limit: Option<(u64, u64)>, is_low_bad: bool,
+) -> Spans<'static> {
+    let (used, max) = match limit {
+        Some((used, max)) if max > 0 => (used, max),
+        _ => {
+            return Spans::from(Span::styled(
+                format!("{:<9}N/A", name),
+                colours.disabled_text_style,
+            ))
+        }
+    };
+
+    // Some limits, like file-max on newer kernels, are effectively unlimited.
+    if max >= i64::MAX as u64 {
+        return Spans::from(Span::styled(
+            format!("{:<9}{} / unlimited", name, used),
+            colours.text_style,
+        ));
+    }
+
+    let percent = used as f64 / max as f64 * 100.0;
+    let pressure = if is_low_bad { 100.0 - percent } else { percent };
+    let style: Style = if pressure >= CRITICAL_PERCENT {
+        colours.low_battery_colour
+    } else if pressure >= WARNING_PERCENT {
+        colours.medium_battery_colour
+    } else {
+        colours.text_style
+    };
+
+    Spans::from(Span::styled(
+        format!("{:<9}{} / {} ({:.0}%)", name, used, max, percent),
+        style,
+    ))
+}
@@ -0,0 +1,122 @@
+use crate::{app::App, canvas::Painter, constants::*};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub trait LogDisplayWidget {
+    fn draw_log_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl LogDisplayWidget for Painter {
+    fn draw_log_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        if let Some(log_widget_state) = app_state.log_state.widget_states.get(&widget_id) {
+            let is_on_widget = widget_id == app_state.current_widget.widget_id;
+            let border_style = if is_on_widget {
+                self.colours.highlighted_border_style
+            } else {
+                self.colours.border_style
+            };
+
+            let file_name = log_widget_state
+                .path
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| log_widget_state.path.to_string_lossy().into_owned());
+            let log_title = if log_widget_state.scroll_offset > 0 {
+                format!(
+                    " {} ({} lines up) ",
+                    file_name, log_widget_state.scroll_offset
+                )
+            } else {
+                format!(" {} ", file_name)
+            };
+            let title = if app_state.is_expanded {
+                let title_base = format!("{}── Esc to go back ", log_title);
+                Spans::from(vec![
+                    Span::styled(log_title, self.colours.widget_title_style),
+                    Span::styled(
+                        format!(
+                            "─{}─ Esc to go back ",
+                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                                UnicodeSegmentation::graphemes(title_base.as_str(), true).count()
+                                    + 2
+                            ))
+                        ),
+                        border_style,
+                    ),
+                ])
+            } else {
+                Spans::from(Span::styled(log_title, self.colours.widget_title_style))
+            };
+
+            let log_block = if draw_border {
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+            } else if is_on_widget {
+                Block::default()
+                    .borders(*SIDE_BORDERS)
+                    .border_style(self.colours.highlighted_border_style)
+            } else {
+                Block::default().borders(Borders::NONE)
+            };
+
+            let margined_draw_loc = Layout::default()
+                .constraints([Constraint::Percentage(100)])
+                .horizontal_margin(if is_on_widget || draw_border { 0 } else { 1 })
+                .direction(Direction::Horizontal)
+                .split(draw_loc)[0];
+
+            let text = if let Some(error) = &log_widget_state.error {
+                vec![Spans::from(Span::styled(
+                    format!("Could not read {}: {}", file_name, error),
+                    self.colours.invalid_query_style,
+                ))]
+            } else {
+                // Show the lines that fit, ending at however far up the widget is scrolled.
+                // Long lines are cut off rather than wrapped, so each line takes one row.
+                let lines = &log_widget_state.lines;
+                let num_rows = usize::from(log_block.inner(margined_draw_loc).height);
+                let end = lines.len().saturating_sub(log_widget_state.scroll_offset);
+                let start = end.saturating_sub(num_rows);
+
+                lines
+                    .range(start..end)
+                    .map(|line| {
+                        let style = log_widget_state
+                            .get_line_style(line)
+                            .unwrap_or(self.colours.text_style);
+                        Spans::from(Span::styled(line.clone(), style))
+                    })
+                    .collect()
+            };
+
+            f.render_widget(Paragraph::new(text).block(log_block), margined_draw_loc);
+
+            if should_get_widget_bounds {
+                // Update draw loc in widget map
+                if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                    widget.top_left_corner = Some((margined_draw_loc.x, margined_draw_loc.y));
+                    widget.bottom_right_corner = Some((
+                        margined_draw_loc.x + margined_draw_loc.width,
+                        margined_draw_loc.y + margined_draw_loc.height,
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -1,17 +1,25 @@
 use crate::{
-    app::App,
-    canvas::{drawing_utils::interpolate_points, Painter},
+    app::{
+        data_harvester::memory::{HugepagesHarvest, ShmemHarvest},
+        App,
+    },
+    canvas::{
+        drawing_utils::{get_graph_marker, get_graph_points, get_time_labels, interpolate_points},
+        widgets::graph_crosshair::{get_boot_marker_points, get_crosshair, GraphCrosshair},
+        Painter,
+    },
     constants::*,
+    data_conversion::return_unit_and_denominator_for_mem_kib,
+    units::formatting::{NumberFormat, UnitPrefix},
 };
 
 use tui::{
     backend::Backend,
-    layout::{Constraint, Rect},
-    symbols::Marker,
+    layout::{Constraint, Direction, Layout, Rect},
     terminal::Frame,
     text::Span,
     text::Spans,
-    widgets::{Axis, Block, Borders, Chart, Dataset},
+    widgets::{Axis, Block, Borders, Chart, Dataset, Paragraph},
 };
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -25,23 +33,131 @@ impl MemGraphWidget for Painter {
     fn draw_memory_graph<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
+        // When expanded, the graph makes room below it for what's in shared memory and tmpfs, and
+        // how huge pages are being used.
+        let widget_loc = draw_loc;
+        let mut detail_panels = vec![];
+        if app_state.is_expanded {
+            let number_format = &app_state.app_config_fields.number_format;
+            if let Some(shmem) = &app_state.data_collection.shmem_harvest {
+                detail_panels.push((" Shared Memory ", get_shmem_lines(shmem, number_format)));
+            }
+            if let Some(hugepages) = &app_state.data_collection.hugepages_harvest {
+                detail_panels.push((
+                    " Huge Pages ",
+                    get_hugepages_lines(hugepages, number_format),
+                ));
+            }
+        }
+
+        let draw_loc = if detail_panels.is_empty() {
+            draw_loc
+        } else {
+            let num_lines = detail_panels
+                .iter()
+                .map(|(_, lines)| lines.len() + 2)
+                .sum::<usize>();
+            let details_height = std::cmp::min(num_lines as u16, draw_loc.height / 2);
+            let split_loc = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(details_height)])
+                .split(draw_loc);
+
+            let panel_locs = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    detail_panels
+                        .iter()
+                        .map(|(_, lines)| Constraint::Length(lines.len() as u16 + 2))
+                        .collect::<Vec<_>>(),
+                )
+                .split(split_loc[1]);
+            for ((title, lines), panel_loc) in detail_panels.into_iter().zip(panel_locs) {
+                f.render_widget(
+                    Paragraph::new(lines)
+                        .block(
+                            Block::default()
+                                .title(Span::styled(title, self.colours.widget_title_style))
+                                .borders(Borders::ALL)
+                                .border_style(self.colours.highlighted_border_style),
+                        )
+                        .style(self.colours.text_style),
+                    panel_loc,
+                );
+            }
+
+            split_loc[0]
+        };
+
         if let Some(mem_widget_state) = app_state.mem_state.widget_states.get_mut(&widget_id) {
             let mem_data: &mut [(f64, f64)] = &mut app_state.canvas_data.mem_data;
             let swap_data: &mut [(f64, f64)] = &mut app_state.canvas_data.swap_data;
+            let numa_data = &mut app_state.canvas_data.numa_data;
 
             let time_start = -(mem_widget_state.current_display_time as f64);
 
-            let display_time_labels = vec![
-                Span::styled(
-                    format!("{}s", mem_widget_state.current_display_time / 1000),
-                    self.colours.graph_style,
-                ),
-                Span::styled("0s".to_string(), self.colours.graph_style),
-            ];
-            let y_axis_label = vec![
-                Span::styled("  0%", self.colours.graph_style),
-                Span::styled("100%", self.colours.graph_style),
-            ];
+            let display_time_labels = get_time_labels(
+                mem_widget_state.current_display_time,
+                app_state
+                    .data_collection
+                    .get_graph_end_time(app_state.is_frozen),
+                draw_loc.width,
+                &app_state.app_config_fields,
+            )
+            .into_iter()
+            .map(|label| Span::styled(label, self.colours.graph_style))
+            .collect::<Vec<_>>();
+
+            // When graphing by value, everything is drawn in the unit of whichever of RAM and
+            // swap is larger, and the y-axis goes up to that.
+            let value_unit = if mem_widget_state.show_as_value {
+                let max_total_kib = app_state
+                    .data_collection
+                    .memory_harvest
+                    .mem_total_in_kib
+                    .max(app_state.data_collection.swap_harvest.mem_total_in_kib);
+
+                if max_total_kib > 0 {
+                    let (unit, denominator) = return_unit_and_denominator_for_mem_kib(
+                        max_total_kib,
+                        app_state.app_config_fields.memory_use_decimal_prefix,
+                    );
+                    Some((unit, denominator, max_total_kib as f64 / denominator))
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            let (y_axis_max, y_axis_label) = if let Some((unit, _, max_value)) = value_unit {
+                let max_label = format!(
+                    "{}{}",
+                    app_state
+                        .app_config_fields
+                        .number_format
+                        .format_number(max_value, 1),
+                    unit
+                );
+                (
+                    max_value * 1.005,
+                    vec![
+                        Span::styled(
+                            format!("{:>1$}", format!("0{}", unit), max_label.len()),
+                            self.colours.graph_style,
+                        ),
+                        Span::styled(max_label, self.colours.graph_style),
+                    ],
+                )
+            } else {
+                (
+                    100.5,
+                    vec![
+                        Span::styled("  0%", self.colours.graph_style),
+                        Span::styled("100%", self.colours.graph_style),
+                    ],
+                )
+            };
 
             let x_axis = if app_state.app_config_fields.hide_time
                 || (app_state.app_config_fields.autohide_time
@@ -71,7 +187,7 @@ impl MemGraphWidget for Painter {
 
             let y_axis = Axis::default()
                 .style(self.colours.graph_style)
-                .bounds([0.0, 100.5])
+                .bounds([0.0, y_axis_max])
                 .labels(y_axis_label);
 
             // Interpolate values to avoid ugly gaps
@@ -143,6 +259,92 @@ impl MemGraphWidget for Painter {
                 None // There is no point.
             };
 
+            let mut interpolated_numa_points = Vec::new();
+            for (series_index, series) in numa_data.iter_mut().enumerate() {
+                if let Some(end_pos) = series
+                    .points
+                    .iter()
+                    .position(|(time, _data)| *time >= time_start)
+                {
+                    if end_pos > 1 {
+                        let start_pos = end_pos - 1;
+                        if let (Some(outside_point), Some(inside_point)) =
+                            (series.points.get(start_pos), series.points.get(end_pos))
+                        {
+                            let old = *outside_point;
+                            let new_point = (
+                                time_start,
+                                interpolate_points(outside_point, inside_point, time_start),
+                            );
+
+                            if let Some(to_replace) = series.points.get_mut(start_pos) {
+                                *to_replace = new_point;
+                                interpolated_numa_points.push((series_index, start_pos, old));
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Points are stored as percentages, so they need converting if graphing by value.
+            let to_values = |points: &[(f64, f64)], total_kib: u64| {
+                value_unit.map(|(_, denominator, _)| {
+                    percent_points_to_values(points, total_kib as f64 / denominator)
+                })
+            };
+            let mem_values = to_values(
+                mem_data,
+                app_state.data_collection.memory_harvest.mem_total_in_kib,
+            );
+            let swap_values = to_values(
+                swap_data,
+                app_state.data_collection.swap_harvest.mem_total_in_kib,
+            );
+            let numa_values: Vec<Option<Vec<(f64, f64)>>> = numa_data
+                .iter()
+                .map(|series| {
+                    let node_total_kib = app_state
+                        .data_collection
+                        .numa_harvest
+                        .iter()
+                        .find(|numa| numa.node == series.node)
+                        .map(|numa| numa.mem_total_in_kib)
+                        .unwrap_or(0);
+                    to_values(&series.points, node_total_kib)
+                })
+                .collect();
+
+            let graph_style = app_state.app_config_fields.graph_styles.mem;
+            let marker = get_graph_marker(graph_style);
+            let x_bounds = [time_start, 0.0];
+            let mem_points = get_graph_points(
+                mem_values.as_deref().unwrap_or(mem_data),
+                graph_style,
+                x_bounds,
+                0.0,
+                draw_loc.width,
+            );
+            let swap_points = get_graph_points(
+                swap_values.as_deref().unwrap_or(swap_data),
+                graph_style,
+                x_bounds,
+                0.0,
+                draw_loc.width,
+            );
+            let numa_points = numa_data
+                .iter()
+                .zip(&numa_values)
+                .map(|(series, values)| {
+                    get_graph_points(
+                        values.as_deref().unwrap_or(&series.points),
+                        graph_style,
+                        x_bounds,
+                        0.0,
+                        draw_loc.width,
+                    )
+                })
+                .collect::<Vec<_>>();
+
             let mut mem_canvas_vec: Vec<Dataset<'_>> = vec![];
 
             if let Some((label_percent, label_frac)) = &app_state.canvas_data.mem_labels {
@@ -150,13 +352,9 @@ impl MemGraphWidget for Painter {
                 mem_canvas_vec.push(
                     Dataset::default()
                         .name(mem_label)
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
+                        .marker(marker)
                         .style(self.colours.ram_style)
-                        .data(&mem_data)
+                        .data(&mem_points)
                         .graph_type(tui::widgets::GraphType::Line),
                 );
             }
@@ -166,17 +364,73 @@ impl MemGraphWidget for Painter {
                 mem_canvas_vec.push(
                     Dataset::default()
                         .name(swap_label)
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
+                        .marker(marker)
                         .style(self.colours.swap_style)
-                        .data(&swap_data)
+                        .data(&swap_points)
+                        .graph_type(tui::widgets::GraphType::Line),
+                );
+            }
+
+            // Each NUMA node gets its own series, but only on machines with more than one.
+            for (itx, (series, points)) in numa_data.iter().zip(&numa_points).enumerate() {
+                let style = if self.colours.cpu_colour_styles.is_empty() {
+                    self.colours.text_style
+                } else {
+                    self.colours.cpu_colour_styles[itx % self.colours.cpu_colour_styles.len()]
+                };
+
+                mem_canvas_vec.push(
+                    Dataset::default()
+                        .name(format!(
+                            "N{}:{}{}",
+                            series.node, series.label_percent, series.label_frac
+                        ))
+                        .marker(marker)
+                        .style(style)
+                        .data(points)
                         .graph_type(tui::widgets::GraphType::Line),
                 );
             }
 
+            let crosshair = get_crosshair(
+                &app_state.data_collection,
+                app_state.crosshair_time,
+                app_state.is_frozen,
+                time_start,
+                [0.0, y_axis_max],
+            );
+            let boot_marker_points = get_boot_marker_points(
+                &app_state
+                    .data_collection
+                    .get_boot_marker_offsets(app_state.is_frozen),
+                time_start,
+                [0.0, y_axis_max],
+            );
+            mem_canvas_vec.extend(
+                boot_marker_points
+                    .iter()
+                    .map(|points| self.get_boot_marker_dataset(points, graph_style)),
+            );
+
+            let (crosshair_dataset, crosshair_readout) =
+                self.graph_crosshair(crosshair.as_ref(), graph_style, |crosshair_timed_data| {
+                    let mut readout = vec![];
+                    if let Some(mem_value) = crosshair_timed_data.mem_data {
+                        readout.push(Span::styled(
+                            format!("RAM {:.0}%", mem_value),
+                            self.colours.ram_style,
+                        ));
+                    }
+                    if let Some(swap_value) = crosshair_timed_data.swap_data {
+                        readout.push(Span::styled(
+                            format!("SWP {:.0}%", swap_value),
+                            self.colours.swap_style,
+                        ));
+                    }
+                    readout
+                });
+            mem_canvas_vec.extend(crosshair_dataset);
+
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let border_style = if is_on_widget {
                 self.colours.highlighted_border_style
@@ -222,6 +476,7 @@ impl MemGraphWidget for Painter {
                     .hidden_legend_constraints((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
                 draw_loc,
             );
+            self.draw_crosshair_readout(f, draw_loc, crosshair_readout);
 
             // Now if you're done, reset any interpolated points!
             if let Some((index, old_value)) = interpolated_mem_point {
@@ -235,15 +490,103 @@ impl MemGraphWidget for Painter {
                     *to_replace = old_value;
                 }
             }
+
+            for (series_index, index, old_value) in interpolated_numa_points {
+                if let Some(to_replace) = numa_data
+                    .get_mut(series_index)
+                    .and_then(|series| series.points.get_mut(index))
+                {
+                    *to_replace = old_value;
+                }
+            }
         }
 
         if app_state.should_get_widget_bounds() {
             // Update draw loc in widget map
             if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
-                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
-                widget.bottom_right_corner =
-                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+                widget.top_left_corner = Some((widget_loc.x, widget_loc.y));
+                widget.bottom_right_corner = Some((
+                    widget_loc.x + widget_loc.width,
+                    widget_loc.y + widget_loc.height,
+                ));
             }
         }
     }
 }
+
+/// Converts points from percentages into values, given what 100% is worth.
+fn percent_points_to_values(points: &[(f64, f64)], full_value: f64) -> Vec<(f64, f64)> {
+    points
+        .iter()
+        .map(|(time, percent)| (*time, percent / 100.0 * full_value))
+        .collect()
+}
+
+/// Returns the lines breaking down shared memory: the total, then each tmpfs mount, then the
+/// largest files in `/dev/shm`.
+fn get_shmem_lines(shmem: &ShmemHarvest, number_format: &NumberFormat) -> Vec<Spans<'static>> {
+    let mut lines = vec![Spans::from(format!(
+        "{:<12}{}",
+        "Shmem",
+        number_format.format_bytes(shmem.shmem_in_kib * 1024, UnitPrefix::Iec, 1)
+    ))];
+
+    lines.extend(shmem.tmpfs.iter().enumerate().map(|(itx, tmpfs)| {
+        Spans::from(format!(
+            "{:<12}{:<24} {} / {} ({:.0}%)",
+            if itx == 0 { "tmpfs" } else { "" },
+            tmpfs.mount_point,
+            number_format.format_bytes(tmpfs.used_bytes, UnitPrefix::Iec, 1),
+            number_format.format_bytes(tmpfs.total_bytes, UnitPrefix::Iec, 1),
+            tmpfs.used_bytes as f64 / tmpfs.total_bytes as f64 * 100.0
+        ))
+    }));
+
+    lines.extend(
+        shmem
+            .largest_shm_files
+            .iter()
+            .enumerate()
+            .map(|(itx, (name, bytes))| {
+                Spans::from(format!(
+                    "{:<12}{:<24} {}",
+                    if itx == 0 { "/dev/shm" } else { "" },
+                    name,
+                    number_format.format_bytes(*bytes, UnitPrefix::Iec, 1)
+                ))
+            }),
+    );
+
+    lines
+}
+
+fn get_hugepages_lines(
+    hugepages: &HugepagesHarvest, number_format: &NumberFormat,
+) -> Vec<Spans<'static>> {
+    let page_size_bytes = hugepages.page_size_in_kib * 1024;
+    let used_pages = hugepages.total_pages.saturating_sub(hugepages.free_pages);
+
+    vec![
+        Spans::from(format!(
+            "{:<12}{} / {} ({} / {} pages of {})",
+            "Huge pages",
+            number_format.format_bytes(used_pages * page_size_bytes, UnitPrefix::Iec, 1),
+            number_format.format_bytes(hugepages.total_pages * page_size_bytes, UnitPrefix::Iec, 1),
+            used_pages,
+            hugepages.total_pages,
+            number_format.format_bytes(page_size_bytes, UnitPrefix::Iec, 0)
+        )),
+        Spans::from(format!(
+            "{:<12}{} reserved, {} surplus",
+            "", hugepages.reserved_pages, hugepages.surplus_pages
+        )),
+        Spans::from(format!(
+            "{:<12}{} anon, {} shmem, {} file ({})",
+            "THP",
+            number_format.format_bytes(hugepages.thp_anon_in_kib * 1024, UnitPrefix::Iec, 1),
+            number_format.format_bytes(hugepages.thp_shmem_in_kib * 1024, UnitPrefix::Iec, 1),
+            number_format.format_bytes(hugepages.thp_file_in_kib * 1024, UnitPrefix::Iec, 1),
+            hugepages.thp_mode.as_deref().unwrap_or("mode unknown")
+        )),
+    ]
+}
@@ -1,28 +1,40 @@
 use once_cell::sync::Lazy;
-use std::cmp::max;
+use std::cmp::{max, min};
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
-    app::{App, AxisScaling},
+    app::{
+        data_farmer::{DataCollection, InterfaceCounterIncreases, TimedData},
+        data_harvester::network::{InterfaceCounters, InterfaceLink},
+        App, AxisScaling,
+    },
     canvas::{
-        drawing_utils::{get_column_widths, interpolate_points},
+        drawing_utils::{
+            get_column_widths, get_graph_marker, get_graph_points, get_time_labels,
+            interpolate_points,
+        },
+        widgets::graph_crosshair::{get_boot_marker_points, get_crosshair, GraphCrosshair},
         Painter,
     },
     constants::*,
-    units::data_units::DataUnit,
+    units::{data_units::DataUnit, formatting::NumberFormat},
     utils::gen_util::*,
 };
 
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    symbols::Marker,
     terminal::Frame,
     text::Span,
     text::{Spans, Text},
-    widgets::{Axis, Block, Borders, Chart, Dataset, Row, Table},
+    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, Row, Table},
 };
 
+/// How much of a link's speed can be in use, either way, before it's shown as busy, and as
+/// saturated.
+const LINK_WARNING_PERCENT: f64 = 75.0;
+const LINK_CRITICAL_PERCENT: f64 = 90.0;
+
 const NETWORK_HEADERS: [&str; 4] = ["RX", "TX", "Total RX", "Total TX"];
 
 static NETWORK_HEADERS_LENS: Lazy<Vec<u16>> = Lazy::new(|| {
@@ -52,14 +64,21 @@ impl NetworkGraphWidget for Painter {
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
         if app_state.app_config_fields.use_old_network_legend {
-            let network_chunk = Layout::default()
-                .direction(Direction::Vertical)
-                .margin(0)
-                .constraints([
-                    Constraint::Length(max(draw_loc.height as i64 - 5, 0) as u16),
-                    Constraint::Length(5),
-                ])
-                .split(draw_loc);
+            // Interfaces with errors get a row each, up to half of the widget.
+            let num_erroring_interfaces =
+                get_erroring_interfaces(&app_state.data_collection).len() as u16;
+            let labels_height = min(5 + num_erroring_interfaces, max(draw_loc.height / 2, 5));
+            let network_chunk =
+                Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints([
+                        Constraint::Length(
+                            max(draw_loc.height as i64 - labels_height as i64, 0) as u16
+                        ),
+                        Constraint::Length(labels_height),
+                    ])
+                    .split(draw_loc);
 
             self.draw_network_graph(f, app_state, network_chunk[0], widget_id, true);
             self.draw_network_labels(f, app_state, network_chunk[1], widget_id);
@@ -424,13 +443,17 @@ impl NetworkGraphWidget for Painter {
 
             let time_start = -(network_widget_state.current_display_time as f64);
 
-            let display_time_labels = vec![
-                Span::styled(
-                    format!("{}s", network_widget_state.current_display_time / 1000),
-                    self.colours.graph_style,
-                ),
-                Span::styled("0s".to_string(), self.colours.graph_style),
-            ];
+            let display_time_labels = get_time_labels(
+                network_widget_state.current_display_time,
+                app_state
+                    .data_collection
+                    .get_graph_end_time(app_state.is_frozen),
+                draw_loc.width,
+                &app_state.app_config_fields,
+            )
+            .into_iter()
+            .map(|label| Span::styled(label, self.colours.graph_style))
+            .collect::<Vec<_>>();
             let x_axis = if app_state.app_config_fields.hide_time
                 || (app_state.app_config_fields.autohide_time
                     && network_widget_state.autohide_timer.is_none())
@@ -545,36 +568,49 @@ impl NetworkGraphWidget for Painter {
             // - Old max time is off screen
             // - A new time interval is better and does not fit (check from end of vector to last checked; we only want to update if it is TOO big!)
 
-            // Find the maximal rx/tx so we know how to scale, and return it.
-
-            let (_best_time, max_entry) = get_max_entry(
-                network_data_rx,
-                network_data_tx,
-                time_start,
-                &app_state.app_config_fields.network_scale_type,
-                app_state.app_config_fields.network_use_binary_prefix,
-            );
-
-            let (max_range, labels) = adjust_network_data_point(
-                max_entry,
-                &app_state.app_config_fields.network_scale_type,
-                &app_state.app_config_fields.network_unit_type,
-                app_state.app_config_fields.network_use_binary_prefix,
-            );
+            // Find the maximal rx/tx so we know how to scale, and return it along with the labels.
+            let network_scale_type = &app_state.app_config_fields.network_scale_type;
+            let network_unit_type = &app_state.app_config_fields.network_unit_type;
+            let network_use_binary_prefix = app_state.app_config_fields.network_use_binary_prefix;
+            let max_pin = network_widget_state.max_pin;
+            let get_max_range_and_labels = |rx: &[Point], tx: &[Point]| {
+                let max_entry = if let Some(max_pin) = max_pin {
+                    // The pin is in bits per second, so it needs to be converted like the data is.
+                    let max_pin = match network_unit_type {
+                        DataUnit::Byte => max_pin / 8.0,
+                        DataUnit::Bit => max_pin,
+                    };
 
-            // Cache results.
-            // network_widget_state.draw_max_range_cache = max_range;
-            // network_widget_state.draw_time_start_cache = best_time;
-            // network_widget_state.draw_labels_cache = labels;
+                    match network_scale_type {
+                        AxisScaling::Log => {
+                            if network_use_binary_prefix {
+                                max_pin.log2()
+                            } else {
+                                max_pin.log10()
+                            }
+                        }
+                        // The linear scale leaves room above its max, so take that back out to
+                        // make the pin the top of the graph.
+                        AxisScaling::Linear => max_pin / 1.5,
+                    }
+                } else {
+                    get_max_entry(
+                        rx,
+                        tx,
+                        time_start,
+                        network_scale_type,
+                        network_use_binary_prefix,
+                    )
+                    .1
+                };
 
-            let y_axis_labels = labels
-                .iter()
-                .map(|label| Span::styled(label, self.colours.graph_style))
-                .collect::<Vec<_>>();
-            let y_axis = Axis::default()
-                .style(self.colours.graph_style)
-                .bounds([0.0, max_range])
-                .labels(y_axis_labels);
+                adjust_network_data_point(
+                    max_entry,
+                    network_scale_type,
+                    network_unit_type,
+                    network_use_binary_prefix,
+                )
+            };
 
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let border_style = if is_on_widget {
@@ -583,108 +619,390 @@ impl NetworkGraphWidget for Painter {
                 self.colours.border_style
             };
 
+            const ESCAPE_ENDING: &str = "── Esc to go back ";
+
+            // Tack on the wireless link details if there's room for them.
+            let title_base = {
+                let wireless_display = &app_state.canvas_data.wireless_display;
+                let title = format!(" Network ─ {} ", wireless_display);
+                let reserved_width = if app_state.is_expanded {
+                    UnicodeSegmentation::graphemes(ESCAPE_ENDING, true).count()
+                } else {
+                    0
+                };
+
+                if !wireless_display.is_empty()
+                    && UnicodeSegmentation::graphemes(title.as_str(), true).count()
+                        + reserved_width
+                        + 2
+                        <= usize::from(draw_loc.width)
+                {
+                    title
+                } else {
+                    " Network ".to_string()
+                }
+            };
+
             let title = if app_state.is_expanded {
-                const TITLE_BASE: &str = " Network ── Esc to go back ";
+                let expanded_title_base = format!("{}{}", title_base, ESCAPE_ENDING);
                 Spans::from(vec![
-                    Span::styled(" Network ", self.colours.widget_title_style),
+                    Span::styled(title_base, self.colours.widget_title_style),
                     Span::styled(
                         format!(
                             "─{}─ Esc to go back ",
-                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
-                            ))
+                            "─".repeat(
+                                usize::from(draw_loc.width).saturating_sub(
+                                    UnicodeSegmentation::graphemes(
+                                        expanded_title_base.as_str(),
+                                        true
+                                    )
+                                    .count()
+                                        + 2
+                                )
+                            )
                         ),
                         border_style,
                     ),
                 ])
             } else {
-                Spans::from(Span::styled(" Network ", self.colours.widget_title_style))
+                Spans::from(Span::styled(title_base, self.colours.widget_title_style))
             };
 
+            let boot_marker_offsets = app_state
+                .data_collection
+                .get_boot_marker_offsets(app_state.is_frozen);
+
             let legend_constraints = if hide_legend {
                 (Constraint::Ratio(0, 1), Constraint::Ratio(0, 1))
             } else {
                 (Constraint::Ratio(1, 1), Constraint::Ratio(3, 4))
             };
 
-            // TODO: Add support for clicking on legend to only show that value on chart.
-            let dataset = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
-                vec![
-                    Dataset::default()
-                        .name(format!("RX: {:7}", app_state.canvas_data.rx_display))
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
-                        .style(self.colours.rx_style)
-                        .data(&network_data_rx)
-                        .graph_type(tui::widgets::GraphType::Line),
-                    Dataset::default()
-                        .name(format!("TX: {:7}", app_state.canvas_data.tx_display))
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
-                        .style(self.colours.tx_style)
-                        .data(&network_data_tx)
-                        .graph_type(tui::widgets::GraphType::Line),
-                    Dataset::default()
-                        .name(format!(
-                            "Total RX: {:7}",
-                            app_state.canvas_data.total_rx_display
-                        ))
-                        .style(self.colours.total_rx_style),
-                    Dataset::default()
-                        .name(format!(
-                            "Total TX: {:7}",
-                            app_state.canvas_data.total_tx_display
-                        ))
-                        .style(self.colours.total_tx_style),
+            // The rates by IP version are listed in the legend if asked for, followed by each
+            // physical interface's link, coloured by how busy it is, and then any interfaces with
+            // errors, in red if they just got more.
+            let mut legend_entries = vec![];
+            if !hide_legend && network_widget_state.is_showing_ip_versions {
+                let network_harvest = &app_state.data_collection.network_harvest;
+                let number_format = &app_state.app_config_fields.number_format;
+                for (ip_version, ip_traffic) in [
+                    ("IPv4", network_harvest.ipv4),
+                    ("IPv6", network_harvest.ipv6),
                 ]
-            } else {
+                .iter()
+                {
+                    legend_entries.push(match ip_traffic {
+                        Some(ip_traffic) => (
+                            format!(
+                                "{} RX {} TX {}",
+                                ip_version,
+                                get_rate_display(
+                                    ip_traffic.rx as f64,
+                                    network_unit_type,
+                                    network_use_binary_prefix,
+                                    number_format,
+                                ),
+                                get_rate_display(
+                                    ip_traffic.tx as f64,
+                                    network_unit_type,
+                                    network_use_binary_prefix,
+                                    number_format,
+                                )
+                            ),
+                            self.colours.text_style,
+                        ),
+                        None => (
+                            format!("{} not counted here", ip_version),
+                            self.colours.disabled_text_style,
+                        ),
+                    });
+                }
+            }
+            if !hide_legend {
+                legend_entries.extend(app_state.data_collection.network_harvest.links.iter().map(
+                    |link| {
+                        let max_utilization = link
+                            .get_utilization()
+                            .map(|(rx_utilization, tx_utilization)| {
+                                rx_utilization.max(tx_utilization)
+                            })
+                            .unwrap_or(0.0);
+                        (
+                            get_link_display(link),
+                            if !link.has_carrier {
+                                self.colours.disabled_text_style
+                            } else if max_utilization >= LINK_CRITICAL_PERCENT {
+                                self.colours.low_battery_colour
+                            } else if max_utilization >= LINK_WARNING_PERCENT {
+                                self.colours.medium_battery_colour
+                            } else {
+                                self.colours.text_style
+                            },
+                        )
+                    },
+                ));
+                legend_entries.extend(
+                    get_erroring_interfaces(&app_state.data_collection)
+                        .into_iter()
+                        .map(|(counters, increases)| {
+                            (
+                                get_interface_counters_display(counters),
+                                if is_any_increase(&increases) {
+                                    self.colours.low_battery_colour
+                                } else {
+                                    self.colours.text_style
+                                },
+                            )
+                        }),
+                );
+            }
+
+            let number_format = &app_state.app_config_fields.number_format;
+            let get_crosshair_readout = |crosshair_timed_data: &TimedData| {
                 vec![
-                    Dataset::default()
-                        .name(&app_state.canvas_data.rx_display)
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
-                        .style(self.colours.rx_style)
-                        .data(&network_data_rx)
-                        .graph_type(tui::widgets::GraphType::Line),
-                    Dataset::default()
-                        .name(&app_state.canvas_data.tx_display)
-                        .marker(if app_state.app_config_fields.use_dot {
-                            Marker::Dot
-                        } else {
-                            Marker::Braille
-                        })
-                        .style(self.colours.tx_style)
-                        .data(&network_data_tx)
-                        .graph_type(tui::widgets::GraphType::Line),
+                    Span::styled(
+                        format!(
+                            "RX {}",
+                            get_rate_display(
+                                crosshair_timed_data.rx_data,
+                                network_unit_type,
+                                network_use_binary_prefix,
+                                number_format,
+                            )
+                        ),
+                        self.colours.rx_style,
+                    ),
+                    Span::styled(
+                        format!(
+                            "TX {}",
+                            get_rate_display(
+                                crosshair_timed_data.tx_data,
+                                network_unit_type,
+                                network_use_binary_prefix,
+                                number_format,
+                            )
+                        ),
+                        self.colours.tx_style,
+                    ),
                 ]
             };
+            let mut crosshair_readout = vec![];
 
-            f.render_widget(
-                Chart::new(dataset)
-                    .block(
-                        Block::default()
-                            .title(title)
-                            .borders(Borders::ALL)
-                            .border_style(if app_state.current_widget.widget_id == widget_id {
-                                self.colours.highlighted_border_style
-                            } else {
-                                self.colours.border_style
-                            }),
-                    )
-                    .x_axis(x_axis)
-                    .y_axis(y_axis)
-                    .hidden_legend_constraints(legend_constraints),
-                draw_loc,
-            );
+            let graph_style = app_state.app_config_fields.graph_styles.net;
+            let marker = get_graph_marker(graph_style);
+
+            let network_block = Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(border_style);
+            if app_state.app_config_fields.network_split {
+                // Upload is often dwarfed by download on a shared axis, so each gets its own pane,
+                // scaled to fit just itself.  Only the bottom pane shows the time labels.
+                let panes = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(network_block.inner(draw_loc));
+                f.render_widget(network_block, draw_loc);
+
+                let mut x_axis = Some(x_axis);
+                let pane_entries = [
+                    (
+                        &*network_data_rx,
+                        &app_state.canvas_data.rx_display,
+                        self.colours.rx_style,
+                    ),
+                    (
+                        &*network_data_tx,
+                        &app_state.canvas_data.tx_display,
+                        self.colours.tx_style,
+                    ),
+                ];
+                for (itx, (pane_data, pane_display, pane_style)) in pane_entries.iter().enumerate()
+                {
+                    let (pane_max_range, pane_labels) = get_max_range_and_labels(pane_data, &[]);
+                    let pane_crosshair = get_crosshair(
+                        &app_state.data_collection,
+                        app_state.crosshair_time,
+                        app_state.is_frozen,
+                        time_start,
+                        [0.0, pane_max_range],
+                    );
+                    let pane_boot_marker_points = get_boot_marker_points(
+                        &boot_marker_offsets,
+                        time_start,
+                        [0.0, pane_max_range],
+                    );
+
+                    let pane_points = get_graph_points(
+                        pane_data,
+                        graph_style,
+                        [time_start, 0.0],
+                        0.0,
+                        panes[itx].width,
+                    );
+
+                    let mut pane_dataset = vec![Dataset::default()
+                        .name(pane_display.as_str())
+                        .marker(marker)
+                        .style(*pane_style)
+                        .data(&pane_points)
+                        .graph_type(tui::widgets::GraphType::Line)];
+                    if itx == 0 {
+                        pane_dataset.extend(legend_entries.iter().map(|(display, style)| {
+                            Dataset::default().name(display.as_str()).style(*style)
+                        }));
+                    }
+                    pane_dataset.extend(
+                        pane_boot_marker_points
+                            .iter()
+                            .map(|points| self.get_boot_marker_dataset(points, graph_style)),
+                    );
+                    let (pane_crosshair_dataset, pane_crosshair_readout) = self.graph_crosshair(
+                        pane_crosshair.as_ref(),
+                        graph_style,
+                        &get_crosshair_readout,
+                    );
+                    pane_dataset.extend(pane_crosshair_dataset);
+                    // Both panes are at the same time, so they'd have the same readout.
+                    if itx == 0 {
+                        crosshair_readout = pane_crosshair_readout;
+                    }
+
+                    let pane_x_axis = if itx + 1 == pane_entries.len() {
+                        x_axis.take().unwrap_or_default()
+                    } else {
+                        Axis::default().bounds([time_start, 0.0])
+                    };
+                    let pane_y_axis = Axis::default()
+                        .style(self.colours.graph_style)
+                        .bounds([0.0, pane_max_range])
+                        .labels(
+                            pane_labels
+                                .iter()
+                                .map(|label| Span::styled(label, self.colours.graph_style))
+                                .collect::<Vec<_>>(),
+                        );
+
+                    f.render_widget(
+                        Chart::new(pane_dataset)
+                            .x_axis(pane_x_axis)
+                            .y_axis(pane_y_axis)
+                            .hidden_legend_constraints(legend_constraints),
+                        panes[itx],
+                    );
+                }
+            } else {
+                let (max_range, labels) =
+                    get_max_range_and_labels(network_data_rx, network_data_tx);
+
+                // Cache results.
+                // network_widget_state.draw_max_range_cache = max_range;
+                // network_widget_state.draw_time_start_cache = best_time;
+                // network_widget_state.draw_labels_cache = labels;
+
+                let y_axis_labels = labels
+                    .iter()
+                    .map(|label| Span::styled(label, self.colours.graph_style))
+                    .collect::<Vec<_>>();
+                let y_axis = Axis::default()
+                    .style(self.colours.graph_style)
+                    .bounds([0.0, max_range])
+                    .labels(y_axis_labels);
+
+                let crosshair = get_crosshair(
+                    &app_state.data_collection,
+                    app_state.crosshair_time,
+                    app_state.is_frozen,
+                    time_start,
+                    [0.0, max_range],
+                );
+                let boot_marker_points =
+                    get_boot_marker_points(&boot_marker_offsets, time_start, [0.0, max_range]);
+
+                let rx_points = get_graph_points(
+                    network_data_rx,
+                    graph_style,
+                    [time_start, 0.0],
+                    0.0,
+                    draw_loc.width,
+                );
+                let tx_points = get_graph_points(
+                    network_data_tx,
+                    graph_style,
+                    [time_start, 0.0],
+                    0.0,
+                    draw_loc.width,
+                );
+
+                // TODO: Add support for clicking on legend to only show that value on chart.
+                let mut dataset =
+                    if app_state.app_config_fields.use_old_network_legend && !hide_legend {
+                        vec![
+                            Dataset::default()
+                                .name(format!("RX: {:7}", app_state.canvas_data.rx_display))
+                                .marker(marker)
+                                .style(self.colours.rx_style)
+                                .data(&rx_points)
+                                .graph_type(tui::widgets::GraphType::Line),
+                            Dataset::default()
+                                .name(format!("TX: {:7}", app_state.canvas_data.tx_display))
+                                .marker(marker)
+                                .style(self.colours.tx_style)
+                                .data(&tx_points)
+                                .graph_type(tui::widgets::GraphType::Line),
+                            Dataset::default()
+                                .name(format!(
+                                    "Total RX: {:7}",
+                                    app_state.canvas_data.total_rx_display
+                                ))
+                                .style(self.colours.total_rx_style),
+                            Dataset::default()
+                                .name(format!(
+                                    "Total TX: {:7}",
+                                    app_state.canvas_data.total_tx_display
+                                ))
+                                .style(self.colours.total_tx_style),
+                        ]
+                    } else {
+                        vec![
+                            Dataset::default()
+                                .name(&app_state.canvas_data.rx_display)
+                                .marker(marker)
+                                .style(self.colours.rx_style)
+                                .data(&rx_points)
+                                .graph_type(tui::widgets::GraphType::Line),
+                            Dataset::default()
+                                .name(&app_state.canvas_data.tx_display)
+                                .marker(marker)
+                                .style(self.colours.tx_style)
+                                .data(&tx_points)
+                                .graph_type(tui::widgets::GraphType::Line),
+                        ]
+                    };
+
+                dataset.extend(legend_entries.iter().map(|(display, style)| {
+                    Dataset::default().name(display.as_str()).style(*style)
+                }));
+                dataset.extend(
+                    boot_marker_points
+                        .iter()
+                        .map(|points| self.get_boot_marker_dataset(points, graph_style)),
+                );
+                let (crosshair_dataset, readout) =
+                    self.graph_crosshair(crosshair.as_ref(), graph_style, &get_crosshair_readout);
+                dataset.extend(crosshair_dataset);
+                crosshair_readout = readout;
+
+                f.render_widget(
+                    Chart::new(dataset)
+                        .block(network_block)
+                        .x_axis(x_axis)
+                        .y_axis(y_axis)
+                        .hidden_legend_constraints(legend_constraints),
+                    draw_loc,
+                );
+            }
+            self.draw_crosshair_readout(f, draw_loc, crosshair_readout);
 
             // Now if you're done, reset any interpolated points!
             if let Some((index, old_value)) = interpolated_rx_point {
@@ -724,7 +1042,39 @@ impl NetworkGraphWidget for Painter {
         ]];
         let mapped_network = total_network
             .into_iter()
-            .map(|val| Row::new(val).style(self.colours.text_style));
+            .map(|val| Row::new(val).style(self.colours.text_style))
+            .chain(
+                get_erroring_interfaces(&app_state.data_collection)
+                    .into_iter()
+                    .map(|(counters, increases)| {
+                        let get_cell = |text: String, is_increase: bool| {
+                            Cell::from(text).style(if is_increase {
+                                self.colours.low_battery_colour
+                            } else {
+                                self.colours.text_style
+                            })
+                        };
+
+                        Row::new(vec![
+                            get_cell(counters.name.clone(), is_any_increase(&increases)),
+                            get_cell(format!("Err: {}", counters.errors), increases.errors),
+                            get_cell(
+                                counters
+                                    .drops
+                                    .map(|drops| format!("Drop: {}", drops))
+                                    .unwrap_or_default(),
+                                increases.drops,
+                            ),
+                            get_cell(
+                                counters
+                                    .collisions
+                                    .map(|collisions| format!("Coll: {}", collisions))
+                                    .unwrap_or_default(),
+                                increases.collisions,
+                            ),
+                        ])
+                    }),
+            );
 
         // Calculate widths
         let intrinsic_widths = get_column_widths(
@@ -768,3 +1118,96 @@ impl NetworkGraphWidget for Painter {
         );
     }
 }
+
+/// Returns the interfaces that have had any errors, drops, or collisions, along with which of
+/// those went up in the last harvest.
+fn get_erroring_interfaces(
+    data_collection: &DataCollection,
+) -> Vec<(&InterfaceCounters, InterfaceCounterIncreases)> {
+    data_collection
+        .network_harvest
+        .interface_counters
+        .iter()
+        .filter(|counters| {
+            counters.errors > 0
+                || counters.drops.unwrap_or(0) > 0
+                || counters.collisions.unwrap_or(0) > 0
+        })
+        .map(|counters| {
+            (
+                counters,
+                data_collection
+                    .network_counter_increases
+                    .get(&counters.name)
+                    .copied()
+                    .unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+fn is_any_increase(increases: &InterfaceCounterIncreases) -> bool {
+    increases.errors || increases.drops || increases.collisions
+}
+
+/// Formats an interface's counters for the legend, leaving out any that can't be read here.
+fn get_interface_counters_display(counters: &InterfaceCounters) -> String {
+    let mut display = format!("{} Err: {}", counters.name, counters.errors);
+    if let Some(drops) = counters.drops {
+        display.push_str(&format!(" Drop: {}", drops));
+    }
+    if let Some(collisions) = counters.collisions {
+        display.push_str(&format!(" Coll: {}", collisions));
+    }
+
+    display
+}
+
+/// Formats an interface's link for the legend, like "eth0 1Gb/s full RX 12.5% TX 0.3%", leaving out
+/// whatever the driver doesn't report.
+fn get_link_display(link: &InterfaceLink) -> String {
+    if !link.has_carrier {
+        return format!("{} no link", link.name);
+    }
+
+    let mut display = link.name.clone();
+    match link.speed_mbps {
+        Some(speed_mbps) if speed_mbps >= 1000 && speed_mbps % 1000 == 0 => {
+            display.push_str(&format!(" {}Gb/s", speed_mbps / 1000));
+        }
+        Some(speed_mbps) if speed_mbps >= 1000 => {
+            display.push_str(&format!(" {:.1}Gb/s", speed_mbps as f64 / 1000.0));
+        }
+        Some(speed_mbps) => display.push_str(&format!(" {}Mb/s", speed_mbps)),
+        None => display.push_str(" speed unknown"),
+    }
+    if let Some(is_full_duplex) = link.is_full_duplex {
+        display.push_str(if is_full_duplex { " full" } else { " half" });
+    }
+    if let Some((rx_utilization, tx_utilization)) = link.get_utilization() {
+        display.push_str(&format!(
+            " RX {:.1}% TX {:.1}%",
+            rx_utilization, tx_utilization
+        ));
+    }
+
+    display
+}
+
+/// Formats a rate, given in bits per second, in the unit the network widget is using.
+fn get_rate_display(
+    rate_in_bits: f64, network_unit_type: &DataUnit, network_use_binary_prefix: bool,
+    number_format: &NumberFormat,
+) -> String {
+    let (quantity, unit) = match network_unit_type {
+        DataUnit::Byte => (rate_in_bits as u64 / 8, "B/s"),
+        DataUnit::Bit => (rate_in_bits as u64, "b/s"),
+    };
+    let (value, unit) = if network_use_binary_prefix {
+        get_binary_prefix(quantity, unit)
+    } else {
+        get_decimal_prefix(quantity, unit)
+    };
+
+    format!("{}{}", number_format.format_number(value, 1), unit)
+}
@@ -0,0 +1,50 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        widgets::simple_table::{SimpleTableColumns, SimpleTableParts, SimpleTableWidget},
+        Painter,
+    },
+};
+
+const PORT_COLUMNS: SimpleTableColumns = SimpleTableColumns {
+    headers: &["Proto", "Address", "Port", "PID", "Process"],
+    hard_widths: &[Some(5), None, Some(6), Some(8), None],
+    max_width_ratios: &[None, Some(0.4), None, None, Some(-1.0)],
+};
+
+pub trait PortsTableWidget {
+    fn draw_ports_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl PortsTableWidget for Painter {
+    fn draw_ports_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_simple_table(
+            f,
+            app_state,
+            draw_loc,
+            draw_border,
+            widget_id,
+            " Ports ",
+            &PORT_COLUMNS,
+            get_ports_table_parts,
+        );
+    }
+}
+
+fn get_ports_table_parts(app_state: &mut app::App, widget_id: u64) -> Option<SimpleTableParts<'_>> {
+    let ports_widget_state = app_state.ports_state.widget_states.get_mut(&widget_id)?;
+    Some(SimpleTableParts {
+        scroll_state: &mut ports_widget_state.scroll_state,
+        table_width_state: &mut ports_widget_state.table_width_state,
+        rows: &app_state.canvas_data.port_data,
+        status_text: None,
+    })
+}
@@ -0,0 +1,286 @@
+use crate::{
+    app::{data_harvester::permissions::PARTIAL_DATA_HINT, App},
+    canvas::{
+        drawing_utils::{get_graph_marker, get_graph_points, get_time_labels, interpolate_points},
+        widgets::graph_crosshair::{get_crosshair, GraphCrosshair},
+        Painter,
+    },
+    constants::*,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    style::Style,
+    terminal::Frame,
+    text::Span,
+    text::Spans,
+    widgets::{Axis, Block, Borders, Chart, Dataset},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub trait PowerGraphWidget {
+    fn draw_power_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    );
+}
+
+impl PowerGraphWidget for Painter {
+    fn draw_power_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        if let Some(power_widget_state) = app_state.power_state.widget_states.get_mut(&widget_id) {
+            let package_power_data = &mut app_state.canvas_data.package_power_data;
+            let dram_power_data = &mut app_state.canvas_data.dram_power_data;
+
+            let time_start = -(power_widget_state.current_display_time as f64);
+
+            // Power draw varies a lot between laptops and servers, so scale to the largest visible.
+            let max_value = package_power_data
+                .iter()
+                .chain(dram_power_data.iter())
+                .filter(|(time, _value)| *time >= time_start)
+                .fold(0.0_f64, |max, (_time, value)| max.max(*value));
+            let max_bound = if max_value > 0.0 {
+                max_value * 1.1
+            } else {
+                1.0
+            };
+
+            let display_time_labels = get_time_labels(
+                power_widget_state.current_display_time,
+                app_state
+                    .data_collection
+                    .get_graph_end_time(app_state.is_frozen),
+                draw_loc.width,
+                &app_state.app_config_fields,
+            )
+            .into_iter()
+            .map(|label| Span::styled(label, self.colours.graph_style))
+            .collect::<Vec<_>>();
+            let y_axis_label = vec![
+                Span::styled("0", self.colours.graph_style),
+                Span::styled(get_watts_display(max_bound), self.colours.graph_style),
+            ];
+
+            let x_axis = if app_state.app_config_fields.hide_time
+                || (app_state.app_config_fields.autohide_time
+                    && power_widget_state.autohide_timer.is_none())
+            {
+                Axis::default().bounds([time_start, 0.0])
+            } else if let Some(time) = power_widget_state.autohide_timer {
+                if std::time::Instant::now().duration_since(time).as_millis()
+                    < AUTOHIDE_TIMEOUT_MILLISECONDS as u128
+                {
+                    Axis::default()
+                        .bounds([time_start, 0.0])
+                        .style(self.colours.graph_style)
+                        .labels(display_time_labels)
+                } else {
+                    power_widget_state.autohide_timer = None;
+                    Axis::default().bounds([time_start, 0.0])
+                }
+            } else if draw_loc.height < TIME_LABEL_HEIGHT_LIMIT {
+                Axis::default().bounds([time_start, 0.0])
+            } else {
+                Axis::default()
+                    .bounds([time_start, 0.0])
+                    .style(self.colours.graph_style)
+                    .labels(display_time_labels)
+            };
+
+            let y_axis = Axis::default()
+                .style(self.colours.graph_style)
+                .bounds([0.0, max_bound])
+                .labels(y_axis_label);
+
+            // Interpolate values to avoid ugly gaps
+            let mut interpolated_points = Vec::new();
+            for (series_index, series) in [&mut *package_power_data, &mut *dram_power_data]
+                .iter_mut()
+                .enumerate()
+            {
+                if let Some(end_pos) = series.iter().position(|(time, _data)| *time >= time_start) {
+                    if end_pos > 1 {
+                        let start_pos = end_pos - 1;
+                        if let (Some(outside_point), Some(inside_point)) =
+                            (series.get(start_pos), series.get(end_pos))
+                        {
+                            let old = *outside_point;
+                            let new_point = (
+                                time_start,
+                                interpolate_points(outside_point, inside_point, time_start),
+                            );
+
+                            if let Some(to_replace) = series.get_mut(start_pos) {
+                                *to_replace = new_point;
+                                interpolated_points.push((series_index, start_pos, old));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let graph_style = app_state.app_config_fields.graph_styles.power;
+            let marker = get_graph_marker(graph_style);
+            let package_points = get_graph_points(
+                package_power_data,
+                graph_style,
+                [time_start, 0.0],
+                0.0,
+                draw_loc.width,
+            );
+            let dram_points = get_graph_points(
+                dram_power_data,
+                graph_style,
+                [time_start, 0.0],
+                0.0,
+                draw_loc.width,
+            );
+            let package_style = self.get_power_style(0);
+            let dram_style = self.get_power_style(1);
+            let power_harvest = app_state.data_collection.power_harvest;
+            let package_display = power_harvest
+                .and_then(|power| power.package_watts)
+                .map(get_watts_display)
+                .unwrap_or_else(|| "N/A".to_string());
+            let mut power_canvas_vec: Vec<Dataset<'_>> = vec![Dataset::default()
+                .name(format!("Pkg: {}", package_display))
+                .marker(marker)
+                .style(package_style)
+                .data(&package_points)
+                .graph_type(tui::widgets::GraphType::Line)];
+            // Not every CPU reports memory's power draw, so only show it if it's there.
+            if let Some(dram_watts) = power_harvest.and_then(|power| power.dram_watts) {
+                power_canvas_vec.push(
+                    Dataset::default()
+                        .name(format!("DRAM: {}", get_watts_display(dram_watts)))
+                        .marker(marker)
+                        .style(dram_style)
+                        .data(&dram_points)
+                        .graph_type(tui::widgets::GraphType::Line),
+                );
+            }
+
+            let crosshair = get_crosshair(
+                &app_state.data_collection,
+                app_state.crosshair_time,
+                app_state.is_frozen,
+                time_start,
+                [0.0, max_bound],
+            );
+            let (crosshair_dataset, crosshair_readout) =
+                self.graph_crosshair(crosshair.as_ref(), graph_style, |crosshair_timed_data| {
+                    let (package_watts, dram_watts) = crosshair_timed_data.power_data;
+                    let mut readout = vec![];
+                    if let Some(package_watts) = package_watts {
+                        readout.push(Span::styled(
+                            format!("Pkg {}", get_watts_display(package_watts)),
+                            package_style,
+                        ));
+                    }
+                    if let Some(dram_watts) = dram_watts {
+                        readout.push(Span::styled(
+                            format!("DRAM {}", get_watts_display(dram_watts)),
+                            dram_style,
+                        ));
+                    }
+                    readout
+                });
+            power_canvas_vec.extend(crosshair_dataset);
+
+            let is_on_widget = widget_id == app_state.current_widget.widget_id;
+            let border_style = if is_on_widget {
+                self.colours.highlighted_border_style
+            } else {
+                self.colours.border_style
+            };
+
+            let power_title = if app_state
+                .data_collection
+                .restricted_harvest
+                .is_power_restricted
+            {
+                let title_string = format!(" Power ─ {} ", PARTIAL_DATA_HINT);
+                if UnicodeSegmentation::graphemes(title_string.as_str(), true).count()
+                    <= usize::from(draw_loc.width)
+                {
+                    title_string
+                } else {
+                    " Power ".to_string()
+                }
+            } else {
+                " Power ".to_string()
+            };
+            let title = if app_state.is_expanded {
+                let title_base = format!("{}── Esc to go back ", power_title);
+                Spans::from(vec![
+                    Span::styled(power_title, self.colours.widget_title_style),
+                    Span::styled(
+                        format!(
+                            "─{}─ Esc to go back ",
+                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                                UnicodeSegmentation::graphemes(title_base.as_str(), true).count()
+                                    + 2
+                            ))
+                        ),
+                        border_style,
+                    ),
+                ])
+            } else {
+                Spans::from(Span::styled(power_title, self.colours.widget_title_style))
+            };
+
+            f.render_widget(
+                Chart::new(power_canvas_vec)
+                    .block(
+                        Block::default()
+                            .title(title)
+                            .borders(Borders::ALL)
+                            .border_style(border_style),
+                    )
+                    .x_axis(x_axis)
+                    .y_axis(y_axis)
+                    .hidden_legend_constraints((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                draw_loc,
+            );
+            self.draw_crosshair_readout(f, draw_loc, crosshair_readout);
+
+            // Now if you're done, reset any interpolated points!
+            for (series_index, index, old_value) in interpolated_points {
+                let series = if series_index == 0 {
+                    &mut *package_power_data
+                } else {
+                    &mut *dram_power_data
+                };
+                if let Some(to_replace) = series.get_mut(index) {
+                    *to_replace = old_value;
+                }
+            }
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
+
+impl Painter {
+    fn get_power_style(&self, index: usize) -> Style {
+        if self.colours.cpu_colour_styles.is_empty() {
+            self.colours.text_style
+        } else {
+            self.colours.cpu_colour_styles[index % self.colours.cpu_colour_styles.len()]
+        }
+    }
+}
+
+/// Formats power like "45.2W".
+fn get_watts_display(watts: f64) -> String {
+    format!("{:.1}W", watts)
+}
@@ -0,0 +1,100 @@
+use crate::{
+    app::App,
+    canvas::{
+        canvas_colours::CanvasColours, drawing_utils::calculate_basic_use_bars,
+        widgets::WidgetBlock, Painter,
+    },
+};
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::Style,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+
+/// Scores at which the system is shown as under some pressure and as under heavy pressure.
+const WARNING_SCORE: f64 = 75.0;
+const CRITICAL_SCORE: f64 = 90.0;
+
+pub trait PressureDisplayWidget {
+    fn draw_pressure_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl PressureDisplayWidget for Painter {
+    fn draw_pressure_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        let (pressure_block, margined_draw_loc) =
+            self.get_widget_block(app_state, " Pressure ", draw_loc, draw_border, widget_id);
+
+        let text = match app_state
+            .data_collection
+            .get_pressure_score(&app_state.app_config_fields.pressure_weights)
+        {
+            Some((score, parts)) => {
+                // The bar takes up whatever is left of the line after the label and percentage.
+                let bar_length =
+                    usize::from(margined_draw_loc.width.saturating_sub(2)).saturating_sub(9 + 7);
+                let num_bars = calculate_basic_use_bars(score, bar_length);
+
+                let mut text = vec![
+                    Spans::from(Span::styled(
+                        format!(
+                            "{:<9}[{}{}{:4.0}%]",
+                            "Score",
+                            "|".repeat(num_bars),
+                            " ".repeat(bar_length - num_bars),
+                            score
+                        ),
+                        get_score_style(&self.colours, score),
+                    )),
+                    Spans::default(),
+                ];
+                text.extend(parts.into_iter().map(|(name, value)| {
+                    Spans::from(Span::styled(
+                        format!("{:<9}{:.1}%", name, value),
+                        get_score_style(&self.colours, value),
+                    ))
+                }));
+                text
+            }
+            None => vec![Spans::from(Span::styled(
+                "No data to score yet",
+                self.colours.disabled_text_style,
+            ))],
+        };
+
+        f.render_widget(
+            Paragraph::new(text).block(pressure_block),
+            margined_draw_loc,
+        );
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((margined_draw_loc.x, margined_draw_loc.y));
+                widget.bottom_right_corner = Some((
+                    margined_draw_loc.x + margined_draw_loc.width,
+                    margined_draw_loc.y + margined_draw_loc.height,
+                ));
+            }
+        }
+    }
+}
+
+fn get_score_style(colours: &CanvasColours, score: f64) -> Style {
+    if score >= CRITICAL_SCORE {
+        colours.low_battery_colour
+    } else if score >= WARNING_SCORE {
+        colours.medium_battery_colour
+    } else {
+        colours.text_style
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    app::{data_farmer::ProcessEventKind, App},
+    canvas::{widgets::WidgetBlock, Painter},
+};
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+
+pub trait ProcessEventsDisplayWidget {
+    fn draw_process_events_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl ProcessEventsDisplayWidget for Painter {
+    fn draw_process_events_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        let scroll_offset = app_state
+            .proc_events_state
+            .get_widget_state(widget_id)
+            .map(|widget_state| widget_state.scroll_offset)
+            .unwrap_or(0);
+        let process_events_title = if scroll_offset > 0 {
+            format!(" Process Events ({} up) ", scroll_offset)
+        } else {
+            " Process Events ".to_string()
+        };
+        let (process_events_block, margined_draw_loc) = self.get_widget_block(
+            app_state,
+            &process_events_title,
+            draw_loc,
+            draw_border,
+            widget_id,
+        );
+
+        // Like a log, the newest events are at the bottom, and older ones scroll off the top.
+        let process_events = &app_state.data_collection.process_events;
+        let num_rows = usize::from(process_events_block.inner(margined_draw_loc).height);
+        let end = process_events.len().saturating_sub(scroll_offset);
+        let start = end.saturating_sub(num_rows);
+        let text = if process_events.is_empty() {
+            vec![Spans::from(Span::styled(
+                "No processes have started, exited, or changed state yet",
+                self.colours.disabled_text_style,
+            ))]
+        } else {
+            process_events
+                .range(start..end)
+                .map(|event| {
+                    let style = match event.kind {
+                        ProcessEventKind::Zombie => self.colours.low_battery_colour,
+                        ProcessEventKind::Stopped => self.colours.medium_battery_colour,
+                        _ => self.colours.text_style,
+                    };
+                    Spans::from(Span::styled(
+                        format!(
+                            "{} {:>7} {} {}",
+                            event.time.format("%H:%M:%S"),
+                            event.pid,
+                            event.name,
+                            event.kind.as_str()
+                        ),
+                        style,
+                    ))
+                })
+                .collect()
+        };
+
+        f.render_widget(
+            Paragraph::new(text).block(process_events_block),
+            margined_draw_loc,
+        );
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((margined_draw_loc.x, margined_draw_loc.y));
+                widget.bottom_right_corner = Some((
+                    margined_draw_loc.x + margined_draw_loc.width,
+                    margined_draw_loc.y + margined_draw_loc.height,
+                ));
+            }
+        }
+    }
+}
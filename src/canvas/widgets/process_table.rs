@@ -1,10 +1,19 @@
+use std::collections::VecDeque;
+
 use crate::{
-    app::App,
+    app::{
+        data_harvester::{
+            permissions::PARTIAL_DATA_HINT,
+            processes::{ProcessNameType, ProcessSorting},
+        },
+        App,
+    },
     canvas::{
         drawing_utils::{get_column_widths, get_search_start_position, get_start_position},
         Painter,
     },
     constants::*,
+    data_conversion::stringify_process_data,
 };
 
 use tui::{
@@ -18,86 +27,76 @@ use tui::{
 use unicode_segmentation::{GraphemeIndices, UnicodeSegmentation};
 use unicode_width::UnicodeWidthStr;
 
-use once_cell::sync::Lazy;
-
-static PROCESS_HEADERS_HARD_WIDTH_NO_GROUP: Lazy<Vec<Option<u16>>> = Lazy::new(|| {
-    vec![
-        Some(7),
-        None,
-        Some(8),
-        Some(8),
-        Some(8),
-        Some(8),
-        Some(7),
-        Some(8),
-        #[cfg(target_family = "unix")]
-        None,
-        None,
-    ]
-});
-static PROCESS_HEADERS_HARD_WIDTH_GROUPED: Lazy<Vec<Option<u16>>> = Lazy::new(|| {
-    vec![
-        Some(7),
-        None,
-        Some(8),
-        Some(8),
-        Some(8),
-        Some(8),
-        Some(7),
-        Some(8),
-    ]
-});
-
-static PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_COMMAND: Lazy<Vec<Option<f64>>> =
-    Lazy::new(|| vec![None, Some(0.7), None, None, None, None, None, None]);
-static PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_ELSE: Lazy<Vec<Option<f64>>> =
-    Lazy::new(|| vec![None, Some(0.3), None, None, None, None, None, None]);
-
-static PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_COMMAND: Lazy<Vec<Option<f64>>> = Lazy::new(|| {
-    vec![
-        None,
-        Some(0.7),
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        #[cfg(target_family = "unix")]
-        Some(0.05),
-        Some(0.2),
-    ]
-});
-static PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_TREE: Lazy<Vec<Option<f64>>> = Lazy::new(|| {
-    vec![
-        None,
-        Some(0.5),
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        #[cfg(target_family = "unix")]
-        Some(0.05),
-        Some(0.2),
-    ]
-});
-static PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_ELSE: Lazy<Vec<Option<f64>>> = Lazy::new(|| {
-    vec![
-        None,
-        Some(0.3),
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
-        #[cfg(target_family = "unix")]
-        Some(0.05),
-        Some(0.2),
-    ]
-});
+const SPARKLINE_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Draws a history of values as a line of bars, scaled to the largest of them.
+fn get_sparkline(values: &VecDeque<f64>) -> String {
+    let max = values.iter().cloned().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            if max > 0.0 {
+                let index = (value / max * (SPARKLINE_BARS.len() - 1) as f64).round() as usize;
+                SPARKLINE_BARS[index.min(SPARKLINE_BARS.len() - 1)]
+            } else {
+                SPARKLINE_BARS[0]
+            }
+        })
+        .collect()
+}
+
+/// The hard width of a process column, if it has one.
+fn get_column_hard_width(column: &ProcessSorting) -> Option<u16> {
+    match column {
+        ProcessSorting::Count | ProcessSorting::Pid => Some(7),
+        ProcessSorting::CpuPercent
+        | ProcessSorting::Mem
+        | ProcessSorting::MemPercent
+        | ProcessSorting::VirtMem
+        | ProcessSorting::ReadPerSecond
+        | ProcessSorting::WritePerSecond
+        | ProcessSorting::TotalWrite
+        | ProcessSorting::Swap => Some(8),
+        ProcessSorting::TotalRead | ProcessSorting::GpuMem => Some(7),
+        ProcessSorting::GpuPercent | ProcessSorting::TcpRetransmits | ProcessSorting::FdCount => {
+            Some(6)
+        }
+        ProcessSorting::TcpRtt | ProcessSorting::CpuTime | ProcessSorting::Seccomp => Some(8),
+        ProcessSorting::StartTime | ProcessSorting::Runtime => Some(9),
+        ProcessSorting::CpuTrend => Some(10),
+        ProcessSorting::ProcessName
+        | ProcessSorting::Command
+        | ProcessSorting::ExePath
+        | ProcessSorting::User
+        | ProcessSorting::State
+        | ProcessSorting::Pod
+        | ProcessSorting::Namespace
+        | ProcessSorting::Capabilities => None,
+    }
+}
+
+/// The maximum soft width of a process column, as a fraction of the table's width.
+fn get_column_soft_width_max(
+    column: &ProcessSorting, name_type: ProcessNameType, is_tree_mode: bool,
+) -> Option<f64> {
+    match column {
+        ProcessSorting::ProcessName | ProcessSorting::Command | ProcessSorting::ExePath => {
+            if name_type != ProcessNameType::Name {
+                Some(0.7)
+            } else if is_tree_mode {
+                Some(0.5)
+            } else {
+                Some(0.3)
+            }
+        }
+        ProcessSorting::User => Some(0.05),
+        ProcessSorting::State => Some(0.2),
+        ProcessSorting::Pod => Some(0.2),
+        ProcessSorting::Namespace => Some(0.1),
+        ProcessSorting::Capabilities => Some(0.2),
+        _ => None,
+    }
+}
 
 pub trait ProcessTableWidget {
     /// Draws and handles all process-related drawing.  Use this.
@@ -212,7 +211,7 @@ impl ProcessTableWidget for Painter {
                 (self.colours.border_style, self.colours.text_style)
             };
 
-            let title_base = if app_state.app_config_fields.show_table_scroll_position {
+            let scroll_title_base = if app_state.app_config_fields.show_table_scroll_position {
                 if let Some(finalized_process_data) = app_state
                     .canvas_data
                     .finalized_process_data_map
@@ -239,6 +238,51 @@ impl ProcessTableWidget for Painter {
                 " Processes ".to_string()
             };
 
+            // Tack on whether only the user's processes are shown, the pinned process' usage,
+            // whether some I/O couldn't be read, and the process state summary if there's room.
+            let own_processes_title = if proc_widget_state.is_showing_own_processes {
+                Some("─ My processes ".to_string())
+            } else {
+                None
+            };
+            let pinned_title = proc_widget_state
+                .pinned_process
+                .as_ref()
+                .map(|pinned_process| {
+                    format!(
+                        "─ PID {} CPU {} {:.1}% Mem {} {:.1}% ",
+                        pinned_process.pid,
+                        get_sparkline(&pinned_process.cpu_history),
+                        pinned_process.cpu_history.back().unwrap_or(&0.0),
+                        get_sparkline(&pinned_process.mem_history),
+                        pinned_process.mem_history.back().unwrap_or(&0.0),
+                    )
+                });
+            let restricted_title = if app_state
+                .data_collection
+                .restricted_harvest
+                .process_io_count
+                > 0
+            {
+                Some(format!("─ I/O {} ", PARTIAL_DATA_HINT))
+            } else {
+                None
+            };
+            let summary_title = format!("─ {} ", app_state.canvas_data.process_summary);
+            let title_base = own_processes_title
+                .iter()
+                .chain(pinned_title.iter())
+                .chain(restricted_title.iter())
+                .chain(std::iter::once(&summary_title))
+                .fold(scroll_title_base, |title_base, section| {
+                    let title = format!("{}{}", title_base, section);
+                    if UnicodeWidthStr::width(title.as_str()) <= draw_loc.width as usize {
+                        title
+                    } else {
+                        title_base
+                    }
+                });
+
             let title = if app_state.is_expanded
                 && !proc_widget_state
                     .process_search_state
@@ -251,7 +295,7 @@ impl ProcessTableWidget for Painter {
                 let (chosen_title_base, expanded_title_base) = {
                     let temp_title_base = format!("{}{}", title_base, ESCAPE_ENDING);
 
-                    if temp_title_base.len() > draw_loc.width as usize {
+                    if UnicodeWidthStr::width(temp_title_base.as_str()) > draw_loc.width as usize {
                         (
                             " Processes ".to_string(),
                             format!("{}{}", " Processes ".to_string(), ESCAPE_ENDING),
@@ -297,9 +341,9 @@ impl ProcessTableWidget for Painter {
                 Block::default().borders(Borders::NONE)
             };
 
-            if let Some(process_data) = &app_state
+            if let Some(finalized_process_data) = app_state
                 .canvas_data
-                .stringified_process_data_map
+                .finalized_process_data_map
                 .get(&widget_id)
             {
                 let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
@@ -319,13 +363,26 @@ impl ProcessTableWidget for Painter {
                 );
 
                 // Sanity check
-                let start_position = if position >= process_data.len() {
-                    process_data.len().saturating_sub(1)
+                let start_position = if position >= finalized_process_data.len() {
+                    finalized_process_data.len().saturating_sub(1)
                 } else {
                     position
                 };
 
-                let sliced_vec = &process_data[start_position..];
+                // Only the rows that fit are formatted, truncated, and handed to the table, so
+                // drawing doesn't slow down with the number of processes.  The block's inner height
+                // is a little more than what's visible once the header is taken out, which is fine.
+                let num_visible_rows = usize::from(process_block.inner(margined_draw_loc).height);
+                let end_position = std::cmp::min(
+                    start_position + num_visible_rows,
+                    finalized_process_data.len(),
+                );
+                let visible_processes = &finalized_process_data[start_position..end_position];
+                let sliced_vec = stringify_process_data(
+                    proc_widget_state,
+                    visible_processes,
+                    &app_state.app_config_fields.number_format,
+                );
                 let processed_sliced_vec = sliced_vec.iter().map(|(data, disabled)| {
                     (
                         data.iter()
@@ -347,15 +404,16 @@ impl ProcessTableWidget for Painter {
                 let process_headers = proc_widget_state.columns.get_column_headers(
                     &proc_widget_state.process_sorting_type,
                     proc_widget_state.is_process_sort_descending,
+                    &proc_widget_state.secondary_sort_keys,
                 );
 
                 // Calculate widths
                 // FIXME: See if we can move this into the recalculate block?  I want to move column widths into the column widths
-                let hard_widths = if proc_widget_state.is_grouped {
-                    &*PROCESS_HEADERS_HARD_WIDTH_GROUPED
-                } else {
-                    &*PROCESS_HEADERS_HARD_WIDTH_NO_GROUP
-                };
+                let shown_columns = proc_widget_state.columns.get_shown_columns();
+                let hard_widths = shown_columns
+                    .iter()
+                    .map(get_column_hard_width)
+                    .collect::<Vec<_>>();
 
                 if recalculate_column_widths {
                     let mut column_widths = process_headers
@@ -386,7 +444,7 @@ impl ProcessTableWidget for Painter {
                         .table_width_state
                         .desired_column_widths
                         .iter()
-                        .zip(hard_widths)
+                        .zip(&hard_widths)
                         .map(|(current, hard)| {
                             if let Some(hard) = hard {
                                 if *hard > *current {
@@ -400,28 +458,23 @@ impl ProcessTableWidget for Painter {
                         })
                         .collect::<Vec<_>>();
 
-                    let soft_widths_max = if proc_widget_state.is_grouped {
-                        // Note grouped trees are not a thing.
-
-                        if proc_widget_state.is_using_command {
-                            &*PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_COMMAND
-                        } else {
-                            &*PROCESS_HEADERS_SOFT_WIDTH_MAX_GROUPED_ELSE
-                        }
-                    } else if proc_widget_state.is_using_command {
-                        &*PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_COMMAND
-                    } else if proc_widget_state.is_tree_mode {
-                        &*PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_TREE
-                    } else {
-                        &*PROCESS_HEADERS_SOFT_WIDTH_MAX_NO_GROUP_ELSE
-                    };
+                    let soft_widths_max = shown_columns
+                        .iter()
+                        .map(|column| {
+                            get_column_soft_width_max(
+                                column,
+                                proc_widget_state.name_type,
+                                proc_widget_state.is_tree_mode,
+                            )
+                        })
+                        .collect::<Vec<_>>();
 
                     proc_widget_state.table_width_state.calculated_column_widths =
                         get_column_widths(
                             draw_loc.width,
                             &hard_widths,
                             &soft_widths_min,
-                            soft_widths_max,
+                            &soft_widths_max,
                             &(proc_widget_state
                                 .table_width_state
                                 .desired_column_widths
@@ -443,9 +496,46 @@ impl ProcessTableWidget for Painter {
 
                 let dcw = &proc_widget_state.table_width_state.desired_column_widths;
                 let ccw = &proc_widget_state.table_width_state.calculated_column_widths;
+                let marked_pids = &proc_widget_state.marked_pids;
+                let highlighted_pids = &proc_widget_state.highlighted_pids;
+                let pinned_index =
+                    proc_widget_state
+                        .pinned_process
+                        .as_ref()
+                        .and_then(|pinned_process| {
+                            finalized_process_data.iter().position(|process| {
+                                process.pid == pinned_process.pid
+                                    || process.group_pids.contains(&pinned_process.pid)
+                            })
+                        });
+
+                let indexed_rows = sliced_vec.iter().enumerate();
+                let process_rows = indexed_rows.map(|(row_index, (data, disabled))| {
+                    let is_pinned = pinned_index == Some(start_position + row_index);
+                    let process = visible_processes.get(row_index);
+                    let is_stopped = process
+                        .map(|process| process.process_char == 'T')
+                        .unwrap_or(false);
+                    let is_marked = process
+                        .map(|process| {
+                            marked_pids.contains(&process.pid)
+                                || process
+                                    .group_pids
+                                    .iter()
+                                    .any(|pid| marked_pids.contains(pid))
+                        })
+                        .unwrap_or(false);
+                    let is_highlighted = process
+                        .map(|process| {
+                            highlighted_pids.contains(&process.pid)
+                                || process
+                                    .group_pids
+                                    .iter()
+                                    .any(|pid| highlighted_pids.contains(pid))
+                        })
+                        .unwrap_or(false);
 
-                let process_rows = sliced_vec.iter().map(|(data, disabled)| {
-                    let truncated_data = data.iter().zip(hard_widths).enumerate().map(
+                    let truncated_data = data.iter().zip(&hard_widths).enumerate().map(
                         |(itx, ((entry, alternative), width))| {
                             if let (Some(desired_col_width), Some(calculated_col_width)) =
                                 (dcw.get(itx), ccw.get(itx))
@@ -485,6 +575,14 @@ impl ProcessTableWidget for Painter {
 
                     if *disabled {
                         Row::new(truncated_data).style(self.colours.disabled_text_style)
+                    } else if is_marked {
+                        Row::new(truncated_data).style(self.colours.high_battery_colour)
+                    } else if is_highlighted {
+                        Row::new(truncated_data).style(self.colours.highlighted_border_style)
+                    } else if is_pinned {
+                        Row::new(truncated_data).style(self.colours.table_header_style)
+                    } else if is_stopped {
+                        Row::new(truncated_data).style(self.colours.medium_battery_colour)
                     } else {
                         Row::new(truncated_data)
                     }
@@ -688,6 +786,13 @@ impl ProcessTableWidget for Painter {
                 self.colours.text_style
             };
 
+            let highlight_only_style =
+                if proc_widget_state.process_search_state.is_highlighting_only {
+                    self.colours.currently_selected_text_style
+                } else {
+                    self.colours.text_style
+                };
+
             // FIXME: [MOUSE] Mouse support for these in search
             // FIXME: [MOVEMENT] Movement support for these in search
             let option_text = Spans::from(vec![
@@ -705,6 +810,11 @@ impl ProcessTableWidget for Painter {
                     format!("Regex({})", if self.is_mac_os { "F3" } else { "Alt+R" }),
                     regex_style,
                 ),
+                Span::raw("  "),
+                Span::styled(
+                    format!("Highlight({})", if self.is_mac_os { "F4" } else { "Alt+I" }),
+                    highlight_only_style,
+                ),
             ]);
 
             search_text.push(Spans::from(Span::styled(
@@ -799,14 +909,7 @@ impl ProcessTableWidget for Painter {
                 .columns
                 .ordered_columns
                 .iter()
-                .filter(|column_type| {
-                    proc_widget_state
-                        .columns
-                        .column_mapping
-                        .get(&column_type)
-                        .unwrap()
-                        .enabled
-                })
+                .filter(|column_type| proc_widget_state.columns.is_shown(column_type))
                 .map(|column_type| column_type.to_string())
                 .collect::<Vec<_>>();
 
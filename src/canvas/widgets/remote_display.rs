@@ -0,0 +1,225 @@
+use crate::{
+    app::{data_harvester::temperature::convert_celsius, App},
+    canvas::Painter,
+    constants::*,
+    units::formatting::UnitPrefix,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Row, Table},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// How long a remote can go without sending data before we point it out.
+const STALE_SECONDS: u64 = 5;
+
+pub trait RemoteDisplayWidget {
+    fn draw_remote_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl RemoteDisplayWidget for Painter {
+    fn draw_remote_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        if let Some(remote_widget_state) = app_state.remote_state.widget_states.get(&widget_id) {
+            let is_on_widget = widget_id == app_state.current_widget.widget_id;
+            let border_style = if is_on_widget {
+                self.colours.highlighted_border_style
+            } else {
+                self.colours.border_style
+            };
+            let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
+                0
+            } else {
+                app_state.app_config_fields.table_gap
+            };
+
+            let host_title = format!(" {} ", remote_widget_state.host);
+            let title = if app_state.is_expanded {
+                let title_base = format!("{}── Esc to go back ", host_title);
+                Spans::from(vec![
+                    Span::styled(host_title, self.colours.widget_title_style),
+                    Span::styled(
+                        format!(
+                            "─{}─ Esc to go back ",
+                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                                UnicodeSegmentation::graphemes(title_base.as_str(), true).count()
+                                    + 2
+                            ))
+                        ),
+                        border_style,
+                    ),
+                ])
+            } else {
+                Spans::from(Span::styled(host_title, self.colours.widget_title_style))
+            };
+
+            let remote_block = if draw_border {
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+            } else if is_on_widget {
+                Block::default()
+                    .borders(*SIDE_BORDERS)
+                    .border_style(self.colours.highlighted_border_style)
+            } else {
+                Block::default().borders(Borders::NONE)
+            };
+
+            let margined_draw_loc = Layout::default()
+                .constraints([Constraint::Percentage(100)])
+                .horizontal_margin(if is_on_widget || draw_border { 0 } else { 1 })
+                .direction(Direction::Horizontal)
+                .split(draw_loc)[0];
+
+            let snapshot = app_state
+                .remote_state
+                .remote_data
+                .lock()
+                .ok()
+                .and_then(|remote_data| remote_data.get(&remote_widget_state.host).cloned());
+
+            if let Some(snapshot) = snapshot {
+                let status = if let Some(error) = &snapshot.error {
+                    format!("Disconnected: {}", error)
+                } else if let Some(last_update) = snapshot.last_update {
+                    let elapsed = last_update.elapsed().as_secs();
+                    if elapsed >= STALE_SECONDS {
+                        format!("Last update {}s ago", elapsed)
+                    } else {
+                        "Connected".to_string()
+                    }
+                } else {
+                    "Connecting...".to_string()
+                };
+
+                let number_format = &app_state.app_config_fields.number_format;
+                let format_mem = |mem: Option<(u64, u64)>| {
+                    if let Some((used, total)) = mem {
+                        let use_percent = if total == 0 {
+                            0.0
+                        } else {
+                            used as f64 / total as f64 * 100.0
+                        };
+                        format!(
+                            "{} / {} ({:.0}%)",
+                            number_format.format_bytes(used * 1024, UnitPrefix::Iec, 1),
+                            number_format.format_bytes(total * 1024, UnitPrefix::Iec, 1),
+                            use_percent
+                        )
+                    } else {
+                        "N/A".to_string()
+                    }
+                };
+
+                let temp_type = &app_state.app_config_fields.temperature_type;
+                let network = if let Some((rx, tx)) = snapshot.network {
+                    format!(
+                        "{} / {}",
+                        number_format.format_prefixed(rx, "b/s", UnitPrefix::Si, 1),
+                        number_format.format_prefixed(tx, "b/s", UnitPrefix::Si, 1)
+                    )
+                } else {
+                    "N/A".to_string()
+                };
+
+                let mut remote_rows = vec![
+                    vec!["Status".to_string(), status],
+                    vec![
+                        "CPU".to_string(),
+                        snapshot
+                            .cpu_usage
+                            .map(|cpu_usage| format!("{:.1}%", cpu_usage))
+                            .unwrap_or_else(|| "N/A".to_string()),
+                    ],
+                    vec!["Memory".to_string(), format_mem(snapshot.mem)],
+                    vec!["Swap".to_string(), format_mem(snapshot.swap)],
+                    vec![
+                        "Load".to_string(),
+                        snapshot
+                            .load_avg
+                            .as_ref()
+                            .map(|load_avg| {
+                                load_avg
+                                    .iter()
+                                    .map(|load| format!("{:.2}", load))
+                                    .collect::<Vec<_>>()
+                                    .join(" ")
+                            })
+                            .unwrap_or_else(|| "N/A".to_string()),
+                    ],
+                    vec!["RX/TX".to_string(), network],
+                    vec![
+                        "Max temp".to_string(),
+                        snapshot
+                            .max_temperature
+                            .map(|temperature| {
+                                format!(
+                                    "{:.0}{}",
+                                    convert_celsius(temperature as f32, temp_type),
+                                    temp_type.as_symbol()
+                                )
+                            })
+                            .unwrap_or_else(|| "N/A".to_string()),
+                    ],
+                    vec![
+                        "Processes".to_string(),
+                        snapshot
+                            .process_count
+                            .map(|process_count| process_count.to_string())
+                            .unwrap_or_else(|| "N/A".to_string()),
+                    ],
+                ];
+                remote_rows.extend(snapshot.top_processes.iter().map(|(name, cpu_usage)| {
+                    vec![format!("  {}", name), format!("{:.1}%", cpu_usage)]
+                }));
+
+                // Draw
+                f.render_widget(
+                    Table::new(
+                        remote_rows
+                            .into_iter()
+                            .map(|row| Row::new(row).style(self.colours.text_style)),
+                    )
+                    .block(remote_block)
+                    .header(Row::new(vec![""]).bottom_margin(table_gap))
+                    .widths(&[Constraint::Percentage(40), Constraint::Percentage(60)]),
+                    margined_draw_loc,
+                );
+            } else {
+                let mut contents = vec![Spans::default(); table_gap as usize];
+
+                contents.push(Spans::from(Span::styled(
+                    "No data found for this remote",
+                    self.colours.text_style,
+                )));
+
+                f.render_widget(
+                    Paragraph::new(contents).block(remote_block),
+                    margined_draw_loc,
+                );
+            }
+
+            if should_get_widget_bounds {
+                // Update draw loc in widget map
+                if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                    widget.top_left_corner = Some((margined_draw_loc.x, margined_draw_loc.y));
+                    widget.bottom_right_corner = Some((
+                        margined_draw_loc.x + margined_draw_loc.width,
+                        margined_draw_loc.y + margined_draw_loc.height,
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{
+    app::App,
+    canvas::{
+        drawing_utils::{get_graph_marker, get_graph_points, get_time_labels, interpolate_points},
+        widgets::graph_crosshair::{get_crosshair, GraphCrosshair},
+        Painter,
+    },
+    constants::*,
+    units::formatting::{NumberFormat, UnitPrefix},
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    style::Style,
+    terminal::Frame,
+    text::Span,
+    text::Spans,
+    widgets::{Axis, Block, Borders, Chart, Dataset},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub trait SchedGraphWidget {
+    fn draw_sched_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    );
+}
+
+impl SchedGraphWidget for Painter {
+    fn draw_sched_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let number_format = app_state.app_config_fields.number_format.clone();
+        if let Some(sched_widget_state) = app_state.sched_state.widget_states.get_mut(&widget_id) {
+            let context_switch_data = &mut app_state.canvas_data.context_switch_data;
+            let interrupt_data = &mut app_state.canvas_data.interrupt_data;
+
+            let time_start = -(sched_widget_state.current_display_time as f64);
+
+            // Both rates can vary wildly between systems, so scale to the largest one visible.
+            let max_value = context_switch_data
+                .iter()
+                .chain(interrupt_data.iter())
+                .filter(|(time, _value)| *time >= time_start)
+                .fold(0.0_f64, |max, (_time, value)| max.max(*value));
+            let max_bound = if max_value > 0.0 {
+                max_value * 1.1
+            } else {
+                1.0
+            };
+
+            let display_time_labels = get_time_labels(
+                sched_widget_state.current_display_time,
+                app_state
+                    .data_collection
+                    .get_graph_end_time(app_state.is_frozen),
+                draw_loc.width,
+                &app_state.app_config_fields,
+            )
+            .into_iter()
+            .map(|label| Span::styled(label, self.colours.graph_style))
+            .collect::<Vec<_>>();
+            let y_axis_label = vec![
+                Span::styled("0", self.colours.graph_style),
+                Span::styled(
+                    get_rate_display(max_bound, &number_format),
+                    self.colours.graph_style,
+                ),
+            ];
+
+            let x_axis = if app_state.app_config_fields.hide_time
+                || (app_state.app_config_fields.autohide_time
+                    && sched_widget_state.autohide_timer.is_none())
+            {
+                Axis::default().bounds([time_start, 0.0])
+            } else if let Some(time) = sched_widget_state.autohide_timer {
+                if std::time::Instant::now().duration_since(time).as_millis()
+                    < AUTOHIDE_TIMEOUT_MILLISECONDS as u128
+                {
+                    Axis::default()
+                        .bounds([time_start, 0.0])
+                        .style(self.colours.graph_style)
+                        .labels(display_time_labels)
+                } else {
+                    sched_widget_state.autohide_timer = None;
+                    Axis::default().bounds([time_start, 0.0])
+                }
+            } else if draw_loc.height < TIME_LABEL_HEIGHT_LIMIT {
+                Axis::default().bounds([time_start, 0.0])
+            } else {
+                Axis::default()
+                    .bounds([time_start, 0.0])
+                    .style(self.colours.graph_style)
+                    .labels(display_time_labels)
+            };
+
+            let y_axis = Axis::default()
+                .style(self.colours.graph_style)
+                .bounds([0.0, max_bound])
+                .labels(y_axis_label);
+
+            // Interpolate values to avoid ugly gaps
+            let mut interpolated_points = Vec::new();
+            for (series_index, series) in [&mut *context_switch_data, &mut *interrupt_data]
+                .iter_mut()
+                .enumerate()
+            {
+                if let Some(end_pos) = series.iter().position(|(time, _data)| *time >= time_start) {
+                    if end_pos > 1 {
+                        let start_pos = end_pos - 1;
+                        if let (Some(outside_point), Some(inside_point)) =
+                            (series.get(start_pos), series.get(end_pos))
+                        {
+                            let old = *outside_point;
+                            let new_point = (
+                                time_start,
+                                interpolate_points(outside_point, inside_point, time_start),
+                            );
+
+                            if let Some(to_replace) = series.get_mut(start_pos) {
+                                *to_replace = new_point;
+                                interpolated_points.push((series_index, start_pos, old));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let graph_style = app_state.app_config_fields.graph_styles.sched;
+            let marker = get_graph_marker(graph_style);
+            let context_switch_points = get_graph_points(
+                context_switch_data,
+                graph_style,
+                [time_start, 0.0],
+                0.0,
+                draw_loc.width,
+            );
+            let interrupt_points = get_graph_points(
+                interrupt_data,
+                graph_style,
+                [time_start, 0.0],
+                0.0,
+                draw_loc.width,
+            );
+            let context_switch_style = self.get_sched_style(0);
+            let interrupt_style = self.get_sched_style(1);
+            let (context_switch_display, interrupt_display) =
+                match app_state.data_collection.sched_harvest {
+                    Some(sched) => (
+                        get_rate_display(sched.context_switches_per_sec, &number_format),
+                        get_rate_display(sched.interrupts_per_sec, &number_format),
+                    ),
+                    None => ("N/A".to_string(), "N/A".to_string()),
+                };
+            let mut sched_canvas_vec: Vec<Dataset<'_>> = vec![
+                Dataset::default()
+                    .name(format!("Ctx: {}", context_switch_display))
+                    .marker(marker)
+                    .style(context_switch_style)
+                    .data(&context_switch_points)
+                    .graph_type(tui::widgets::GraphType::Line),
+                Dataset::default()
+                    .name(format!("Intr: {}", interrupt_display))
+                    .marker(marker)
+                    .style(interrupt_style)
+                    .data(&interrupt_points)
+                    .graph_type(tui::widgets::GraphType::Line),
+            ];
+
+            let crosshair = get_crosshair(
+                &app_state.data_collection,
+                app_state.crosshair_time,
+                app_state.is_frozen,
+                time_start,
+                [0.0, max_bound],
+            );
+            let (crosshair_dataset, crosshair_readout) =
+                self.graph_crosshair(crosshair.as_ref(), graph_style, |crosshair_timed_data| {
+                    match crosshair_timed_data.sched_data {
+                        Some((context_switches, interrupts)) => vec![
+                            Span::styled(
+                                format!(
+                                    "Ctx {}",
+                                    get_rate_display(context_switches, &number_format)
+                                ),
+                                context_switch_style,
+                            ),
+                            Span::styled(
+                                format!("Intr {}", get_rate_display(interrupts, &number_format)),
+                                interrupt_style,
+                            ),
+                        ],
+                        None => vec![],
+                    }
+                });
+            sched_canvas_vec.extend(crosshair_dataset);
+
+            let is_on_widget = widget_id == app_state.current_widget.widget_id;
+            let border_style = if is_on_widget {
+                self.colours.highlighted_border_style
+            } else {
+                self.colours.border_style
+            };
+
+            let title = if app_state.is_expanded {
+                const TITLE_BASE: &str = " Scheduler ── Esc to go back ";
+                Spans::from(vec![
+                    Span::styled(" Scheduler ", self.colours.widget_title_style),
+                    Span::styled(
+                        format!(
+                            "─{}─ Esc to go back ",
+                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
+                            ))
+                        ),
+                        border_style,
+                    ),
+                ])
+            } else {
+                Spans::from(Span::styled(
+                    " Scheduler ".to_string(),
+                    self.colours.widget_title_style,
+                ))
+            };
+
+            f.render_widget(
+                Chart::new(sched_canvas_vec)
+                    .block(
+                        Block::default()
+                            .title(title)
+                            .borders(Borders::ALL)
+                            .border_style(border_style),
+                    )
+                    .x_axis(x_axis)
+                    .y_axis(y_axis)
+                    .hidden_legend_constraints((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                draw_loc,
+            );
+            self.draw_crosshair_readout(f, draw_loc, crosshair_readout);
+
+            // Now if you're done, reset any interpolated points!
+            for (series_index, index, old_value) in interpolated_points {
+                let series = if series_index == 0 {
+                    &mut *context_switch_data
+                } else {
+                    &mut *interrupt_data
+                };
+                if let Some(to_replace) = series.get_mut(index) {
+                    *to_replace = old_value;
+                }
+            }
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
+
+impl Painter {
+    fn get_sched_style(&self, index: usize) -> Style {
+        if self.colours.cpu_colour_styles.is_empty() {
+            self.colours.text_style
+        } else {
+            self.colours.cpu_colour_styles[index % self.colours.cpu_colour_styles.len()]
+        }
+    }
+}
+
+/// Formats a rate like "12.3K/s".
+fn get_rate_display(rate: f64, number_format: &NumberFormat) -> String {
+    number_format.format_prefixed(rate as u64, "/s", UnitPrefix::Si, 1)
+}
@@ -0,0 +1,52 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        widgets::simple_table::{SimpleTableColumns, SimpleTableParts, SimpleTableWidget},
+        Painter,
+    },
+};
+
+const SERVICE_COLUMNS: SimpleTableColumns = SimpleTableColumns {
+    headers: &["Service", "State", "PID", "Mem", "CPU%", "Description"],
+    hard_widths: &[None, None, Some(8), Some(8), Some(6), None],
+    max_width_ratios: &[Some(0.3), Some(0.25), None, None, None, Some(-1.0)],
+};
+
+pub trait ServicesTableWidget {
+    fn draw_services_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl ServicesTableWidget for Painter {
+    fn draw_services_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_simple_table(
+            f,
+            app_state,
+            draw_loc,
+            draw_border,
+            widget_id,
+            " Services ",
+            &SERVICE_COLUMNS,
+            get_services_table_parts,
+        );
+    }
+}
+
+fn get_services_table_parts(
+    app_state: &mut app::App, widget_id: u64,
+) -> Option<SimpleTableParts<'_>> {
+    let services_widget_state = app_state.services_state.widget_states.get_mut(&widget_id)?;
+    Some(SimpleTableParts {
+        scroll_state: &mut services_widget_state.scroll_state,
+        table_width_state: &mut services_widget_state.table_width_state,
+        rows: &app_state.canvas_data.service_data,
+        status_text: services_widget_state.status_text.as_deref(),
+    })
+}
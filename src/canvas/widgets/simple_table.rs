@@ -0,0 +1,282 @@
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+    text::Span,
+    text::{Spans, Text},
+    widgets::{Block, Borders, Row, Table},
+};
+
+use crate::{
+    app::{self, AppScrollWidgetState, CanvasTableWidthState},
+    canvas::{
+        drawing_utils::{get_column_widths, get_start_position},
+        Painter,
+    },
+    constants::*,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The columns of a table that's just rows of strings.
+pub struct SimpleTableColumns {
+    pub headers: &'static [&'static str],
+    /// Columns with a fixed width.  The rest share what's left, and get cut off if it's not enough.
+    pub hard_widths: &'static [Option<u16>],
+    /// The most of the table's width each of the other columns can take up, as a fraction.
+    pub max_width_ratios: &'static [Option<f64>],
+}
+
+/// The parts of a widget's state and data that a simple table needs, borrowed together.
+pub struct SimpleTableParts<'a> {
+    pub scroll_state: &'a mut AppScrollWidgetState,
+    pub table_width_state: &'a mut CanvasTableWidthState,
+    pub rows: &'a [Vec<String>],
+    /// Shown in the title instead of the scroll position, like for confirmation prompts.
+    pub status_text: Option<&'a str>,
+}
+
+pub trait SimpleTableWidget {
+    /// Draws a scrolling table of strings, like the ports or containers tables.  `get_parts`
+    /// borrows the widget's state and rows out of the app; the table isn't drawn if the widget
+    /// has no state.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_simple_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64, name: &str, columns: &SimpleTableColumns,
+        get_parts: for<'a> fn(&'a mut app::App, u64) -> Option<SimpleTableParts<'a>>,
+    );
+}
+
+impl SimpleTableWidget for Painter {
+    fn draw_simple_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64, name: &str, columns: &SimpleTableColumns,
+        get_parts: for<'a> fn(&'a mut app::App, u64) -> Option<SimpleTableParts<'a>>,
+    ) {
+        let recalculate_column_widths = app_state.should_get_widget_bounds();
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        let is_expanded = app_state.is_expanded;
+        let is_force_redraw = app_state.is_force_redraw;
+        let show_table_scroll_position = app_state.app_config_fields.show_table_scroll_position;
+        let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
+            0
+        } else {
+            app_state.app_config_fields.table_gap
+        };
+
+        let margined_draw_loc = if let Some(parts) = get_parts(app_state, widget_id) {
+            let SimpleTableParts {
+                scroll_state,
+                table_width_state,
+                rows,
+                status_text,
+            } = parts;
+
+            let start_position = get_start_position(
+                usize::from(
+                    (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
+                ),
+                &scroll_state.scroll_direction,
+                &mut scroll_state.previous_scroll_position,
+                scroll_state.current_scroll_position,
+                is_force_redraw,
+            );
+            scroll_state.table_state.select(Some(
+                scroll_state
+                    .current_scroll_position
+                    .saturating_sub(start_position),
+            ));
+            let sliced_vec = rows.get(start_position..).unwrap_or(&[]);
+
+            // Calculate widths
+            let header_lens = columns
+                .headers
+                .iter()
+                .map(|header| header.len() as u16)
+                .collect::<Vec<_>>();
+            if recalculate_column_widths {
+                table_width_state.desired_column_widths = {
+                    let mut column_widths = header_lens.clone();
+                    for row in sliced_vec {
+                        for (col, entry) in row.iter().enumerate() {
+                            if entry.len() as u16 > column_widths[col] {
+                                column_widths[col] = entry.len() as u16;
+                            }
+                        }
+                    }
+
+                    column_widths
+                };
+                table_width_state.calculated_column_widths = get_column_widths(
+                    draw_loc.width,
+                    columns.hard_widths,
+                    &(header_lens
+                        .iter()
+                        .map(|width| Some(*width))
+                        .collect::<Vec<_>>()),
+                    columns.max_width_ratios,
+                    &table_width_state
+                        .desired_column_widths
+                        .iter()
+                        .map(|width| Some(*width))
+                        .collect::<Vec<_>>(),
+                    true,
+                );
+            }
+
+            let dcw = &table_width_state.desired_column_widths;
+            let ccw = &table_width_state.calculated_column_widths;
+            let table_rows =
+                sliced_vec.iter().map(|row| {
+                    let truncated_data = row.iter().zip(columns.hard_widths).enumerate().map(
+                        |(itx, (entry, width))| {
+                            if width.is_none() {
+                                if let (Some(desired_col_width), Some(calculated_col_width)) =
+                                    (dcw.get(itx), ccw.get(itx))
+                                {
+                                    if *desired_col_width > *calculated_col_width
+                                        && *calculated_col_width > 0
+                                    {
+                                        let graphemes =
+                                            UnicodeSegmentation::graphemes(entry.as_str(), true)
+                                                .collect::<Vec<&str>>();
+
+                                        if graphemes.len() > *calculated_col_width as usize
+                                            && *calculated_col_width > 1
+                                        {
+                                            // Truncate with ellipsis
+                                            let first_n = graphemes
+                                                [..(*calculated_col_width as usize - 1)]
+                                                .concat();
+                                            return Text::raw(format!("{}…", first_n));
+                                        }
+                                    }
+                                }
+                            }
+
+                            Text::raw(entry)
+                        },
+                    );
+
+                    Row::new(truncated_data)
+                });
+
+            let (border_style, highlight_style) = if is_on_widget {
+                (
+                    self.colours.highlighted_border_style,
+                    self.colours.currently_selected_text_style,
+                )
+            } else {
+                (self.colours.border_style, self.colours.text_style)
+            };
+
+            let title_string = if let Some(status_text) = status_text {
+                Some(format!("{}── {} ", name, status_text))
+            } else if show_table_scroll_position {
+                Some(format!(
+                    "{}({} of {}) ",
+                    name,
+                    scroll_state.current_scroll_position.saturating_add(1),
+                    rows.len()
+                ))
+            } else {
+                None
+            };
+            let title_base = match title_string {
+                Some(title_string) if title_string.len() <= draw_loc.width as usize => title_string,
+                _ => name.to_string(),
+            };
+
+            let title = if is_expanded {
+                const ESCAPE_ENDING: &str = "── Esc to go back ";
+
+                let (chosen_title_base, expanded_title_base) = {
+                    let temp_title_base = format!("{}{}", title_base, ESCAPE_ENDING);
+
+                    if temp_title_base.len() > draw_loc.width as usize {
+                        (name.to_string(), format!("{}{}", name, ESCAPE_ENDING))
+                    } else {
+                        (title_base, temp_title_base)
+                    }
+                };
+
+                Spans::from(vec![
+                    Span::styled(chosen_title_base, self.colours.widget_title_style),
+                    Span::styled(
+                        format!(
+                            "─{}─ Esc to go back ",
+                            "─".repeat(
+                                usize::from(draw_loc.width).saturating_sub(
+                                    UnicodeSegmentation::graphemes(
+                                        expanded_title_base.as_str(),
+                                        true
+                                    )
+                                    .count()
+                                        + 2
+                                )
+                            )
+                        ),
+                        border_style,
+                    ),
+                ])
+            } else {
+                Spans::from(Span::styled(title_base, self.colours.widget_title_style))
+            };
+
+            let table_block = if draw_border {
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+            } else if is_on_widget {
+                Block::default()
+                    .borders(*SIDE_BORDERS)
+                    .border_style(self.colours.highlighted_border_style)
+            } else {
+                Block::default().borders(Borders::NONE)
+            };
+
+            let margined_draw_loc = Layout::default()
+                .constraints([Constraint::Percentage(100)])
+                .horizontal_margin(if is_on_widget || draw_border { 0 } else { 1 })
+                .direction(Direction::Horizontal)
+                .split(draw_loc)[0];
+
+            // Draw
+            f.render_stateful_widget(
+                Table::new(table_rows)
+                    .header(
+                        Row::new(columns.headers.to_vec())
+                            .style(self.colours.table_header_style)
+                            .bottom_margin(table_gap),
+                    )
+                    .block(table_block)
+                    .highlight_style(highlight_style)
+                    .style(self.colours.text_style)
+                    .widths(
+                        &(ccw
+                            .iter()
+                            .map(|calculated_width| Constraint::Length(*calculated_width as u16))
+                            .collect::<Vec<_>>()),
+                    ),
+                margined_draw_loc,
+                &mut scroll_state.table_state,
+            );
+
+            margined_draw_loc
+        } else {
+            return;
+        };
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((margined_draw_loc.x, margined_draw_loc.y));
+                widget.bottom_right_corner = Some((
+                    margined_draw_loc.x + margined_draw_loc.width,
+                    margined_draw_loc.y + margined_draw_loc.height,
+                ));
+            }
+        }
+    }
+}
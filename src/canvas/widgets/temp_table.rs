@@ -9,7 +9,10 @@ use tui::{
 };
 
 use crate::{
-    app,
+    app::{
+        self,
+        data_harvester::{permissions::PARTIAL_DATA_HINT, temperature::SensorStatus},
+    },
     canvas::{
         drawing_utils::{get_column_widths, get_start_position},
         Painter,
@@ -18,7 +21,7 @@ use crate::{
 };
 use unicode_segmentation::UnicodeSegmentation;
 
-const TEMP_HEADERS: [&str; 2] = ["Sensor", "Temp"];
+const TEMP_HEADERS: [&str; 3] = ["Sensor", "Temp", "Limits"];
 
 static TEMP_HEADERS_LENS: Lazy<Vec<u16>> = Lazy::new(|| {
     TEMP_HEADERS
@@ -66,7 +69,7 @@ impl TempTableWidget for Painter {
             let sliced_vec = &app_state.canvas_data.temp_sensor_data[start_position..];
 
             // Calculate widths
-            let hard_widths = [None, None];
+            let hard_widths = [None, None, None];
             if recalculate_column_widths {
                 temp_widget_state.table_width_state.desired_column_widths = {
                     let mut column_widths = TEMP_HEADERS_LENS.clone();
@@ -87,7 +90,7 @@ impl TempTableWidget for Painter {
                         .iter()
                         .map(|width| Some(*width))
                         .collect::<Vec<_>>()),
-                    &[Some(0.80), Some(-1.0)],
+                    &[Some(0.80), Some(-1.0), Some(0.40)],
                     &temp_widget_state
                         .table_width_state
                         .desired_column_widths
@@ -100,8 +103,12 @@ impl TempTableWidget for Painter {
 
             let dcw = &temp_widget_state.table_width_state.desired_column_widths;
             let ccw = &temp_widget_state.table_width_state.calculated_column_widths;
+            // Rows are in the same order as the sensors, and sensors that report their own limits
+            // are coloured by how close they are to them.
+            let sensors = &app_state.data_collection.temp_harvest
+                [start_position.min(app_state.data_collection.temp_harvest.len())..];
             let temperature_rows =
-                sliced_vec.iter().map(|temp_row| {
+                sliced_vec.iter().enumerate().map(|(row_index, temp_row)| {
                     let truncated_data = temp_row.iter().zip(&hard_widths).enumerate().map(
                         |(itx, (entry, width))| {
                             if width.is_none() {
@@ -138,7 +145,17 @@ impl TempTableWidget for Painter {
                         },
                     );
 
-                    Row::new(truncated_data)
+                    let row_style = match sensors
+                        .get(row_index)
+                        .and_then(|sensor| sensor.get_status())
+                    {
+                        Some(SensorStatus::Normal) => self.colours.high_battery_colour,
+                        Some(SensorStatus::Warning) => self.colours.medium_battery_colour,
+                        Some(SensorStatus::Critical) => self.colours.low_battery_colour,
+                        None => self.colours.text_style,
+                    };
+
+                    Row::new(truncated_data).style(row_style)
                 });
 
             let (border_style, highlight_style) = if is_on_widget {
@@ -168,6 +185,23 @@ impl TempTableWidget for Painter {
             } else {
                 " Temperatures ".to_string()
             };
+            let title_base = if app_state
+                .data_collection
+                .restricted_harvest
+                .is_smart_restricted
+            {
+                let title_string = format!("{}─ Drives {} ", title_base, PARTIAL_DATA_HINT);
+
+                if UnicodeSegmentation::graphemes(title_string.as_str(), true).count()
+                    <= draw_loc.width as usize
+                {
+                    title_string
+                } else {
+                    title_base
+                }
+            } else {
+                title_base
+            };
 
             let title = if app_state.is_expanded {
                 const ESCAPE_ENDING: &str = "── Esc to go back ";
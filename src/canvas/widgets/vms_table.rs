@@ -0,0 +1,61 @@
+use tui::{backend::Backend, layout::Rect, terminal::Frame};
+
+use crate::{
+    app,
+    canvas::{
+        widgets::simple_table::{SimpleTableColumns, SimpleTableParts, SimpleTableWidget},
+        Painter,
+    },
+};
+
+const VM_COLUMNS: SimpleTableColumns = SimpleTableColumns {
+    headers: &[
+        "Name", "vCPUs", "CPU%", "Mem", "Disk R/s", "Disk W/s", "RX/s", "TX/s",
+    ],
+    hard_widths: &[
+        None,
+        Some(6),
+        Some(7),
+        Some(9),
+        Some(9),
+        Some(9),
+        Some(9),
+        Some(9),
+    ],
+    max_width_ratios: &[Some(0.5), None, None, None, None, None, None, None],
+};
+
+pub trait VmsTableWidget {
+    fn draw_vms_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    );
+}
+
+impl VmsTableWidget for Painter {
+    fn draw_vms_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        self.draw_simple_table(
+            f,
+            app_state,
+            draw_loc,
+            draw_border,
+            widget_id,
+            " Virtual Machines ",
+            &VM_COLUMNS,
+            get_vms_table_parts,
+        );
+    }
+}
+
+fn get_vms_table_parts(app_state: &mut app::App, widget_id: u64) -> Option<SimpleTableParts<'_>> {
+    let vms_widget_state = app_state.vms_state.widget_states.get_mut(&widget_id)?;
+    Some(SimpleTableParts {
+        scroll_state: &mut vms_widget_state.scroll_state,
+        table_width_state: &mut vms_widget_state.table_width_state,
+        rows: &app_state.canvas_data.vm_data,
+        status_text: None,
+    })
+}
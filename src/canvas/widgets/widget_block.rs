@@ -0,0 +1,70 @@
+use crate::{app::App, canvas::Painter, constants::*};
+
+use tui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans},
+    widgets::{Block, Borders},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub trait WidgetBlock {
+    /// Returns the block around a widget with the given title, and where to draw the widget.
+    /// Expanded widgets also say how to go back in their title, and borderless widgets keep a
+    /// margin instead unless they're selected.
+    fn get_widget_block<'a>(
+        &self, app_state: &App, title: &'a str, draw_loc: Rect, draw_border: bool, widget_id: u64,
+    ) -> (Block<'a>, Rect);
+}
+
+impl WidgetBlock for Painter {
+    fn get_widget_block<'a>(
+        &self, app_state: &App, title: &'a str, draw_loc: Rect, draw_border: bool, widget_id: u64,
+    ) -> (Block<'a>, Rect) {
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        let border_style = if is_on_widget {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        };
+
+        let block = if draw_border {
+            let title = if app_state.is_expanded {
+                let title_base = format!("{}── Esc to go back ", title);
+                Spans::from(vec![
+                    Span::styled(title, self.colours.widget_title_style),
+                    Span::styled(
+                        format!(
+                            "─{}─ Esc to go back ",
+                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                                UnicodeSegmentation::graphemes(title_base.as_str(), true).count()
+                                    + 2
+                            ))
+                        ),
+                        border_style,
+                    ),
+                ])
+            } else {
+                Spans::from(Span::styled(title, self.colours.widget_title_style))
+            };
+
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(border_style)
+        } else if is_on_widget {
+            Block::default()
+                .borders(*SIDE_BORDERS)
+                .border_style(self.colours.highlighted_border_style)
+        } else {
+            Block::default().borders(Borders::NONE)
+        };
+
+        let margined_draw_loc = Layout::default()
+            .constraints([Constraint::Percentage(100)])
+            .horizontal_margin(if is_on_widget || draw_border { 0 } else { 1 })
+            .direction(Direction::Horizontal)
+            .split(draw_loc)[0];
+
+        (block, margined_draw_loc)
+    }
+}
@@ -66,6 +66,15 @@ disabled via --hide_time then this will have no effect.\n\n\n",
 Hides graphs and uses a more basic look.  Design is largely
 inspired by htop's.\n\n",
         );
+    let mini = Arg::with_name("mini")
+        .long("mini")
+        .help("Shows only basic mode's meters, on one line.")
+        .long_help(
+            "\
+Shows only basic mode's meters, on one line, and nothing else.
+Meant for small panes, like a tmux status pane.  Which meters
+are shown is set by \"basic_meters\" in the config file.\n\n",
+        );
     let battery = Arg::with_name("battery")
         .long("battery")
         .help("Shows the battery widget.")
@@ -91,6 +100,15 @@ When searching for a process, enables case sensitivity by default.\n\n",
 Sets process CPU% usage to be based on the current system CPU% usage
 rather than total CPU usage.\n\n",
         );
+    let cpu_top_processes = Arg::with_name("cpu_top_processes")
+        .long("cpu_top_processes")
+        .help("Shows the busiest processes on the CPU graph.")
+        .long_help(
+            "\
+Overlays the CPU usage of the three busiest processes on the CPU
+graph as extra lines, labelled with each process' name and usage.\n\n",
+        );
+
     // TODO: [DEBUG] Add a proper debugging solution.
     //     let debug = Arg::with_name("debug")
     //         .long("debug")
@@ -108,6 +126,15 @@ rather than total CPU usage.\n\n",
 Disables mouse clicks from interacting with the program.\n\n",
         );
 
+    let disk_inodes = Arg::with_name("disk_inodes")
+        .long("disk_inodes")
+        .help("Shows inode usage in the disk widget.")
+        .long_help(
+            "\
+Shows how much of each filesystem's inodes are used in the disk
+widget.  Filesystems can run out of inodes before space.\n\n",
+        );
+
     let dot_marker = Arg::with_name("dot_marker")
         .short("m")
         .long("dot_marker")
@@ -152,6 +179,32 @@ Hides the spacing between table headers and entries.\n\n",
 Completely hides the time scale from being shown.\n\n",
         );
 
+    let time_labels = Arg::with_name("time_labels")
+        .long("time_labels")
+        .takes_value(true)
+        .value_name("STYLE")
+        .possible_values(&["relative", "absolute"])
+        .help("Labels graph time scales with offsets or times of day.")
+        .long_help(
+            "\
+Labels the time scale of graphs with how long ago each point is
+(\"relative\", e.g. 60s) or with the time of day it was collected at
+(\"absolute\").  Labels are spread across the scale as room allows, and
+each one matches the data under it.  Defaults to \"relative\".\n\n",
+        );
+
+    let time_format = Arg::with_name("time_format")
+        .long("time_format")
+        .takes_value(true)
+        .value_name("FORMAT")
+        .possible_values(&["24h", "12h"])
+        .help("Shows times of day on a 24-hour or 12-hour clock.")
+        .long_help(
+            "\
+Shows times of day, such as absolute time scale labels, on a 24-hour
+(\"24h\") or 12-hour (\"12h\") clock.  Defaults to \"24h\".\n\n",
+        );
+
     let process_command = Arg::with_name("process_command")
         .long("process_command")
         .help("Show processes as their commands by default.")
@@ -286,7 +339,7 @@ it defaults to showing it by percentage.\n\n",
         .long_help(
             "\
 Default time value for graphs in milliseconds.  The minimum
-time is 30s (30000), and the default is 60s (60000).\n\n\n",
+time is 15s (15000), and the default is 60s (60000).\n\n\n",
         );
     let default_widget_count = Arg::with_name("default_widget_count")
         .long("default_widget_count")
@@ -350,6 +403,32 @@ Supported widget names:
 +--------------------------+
 |       batt, battery      |
 +--------------------------+
+|     services, service    |
++--------------------------+
+|        ports, port       |
++--------------------------+
+|      custom, plugin      |
++--------------------------+
+|       remote, host       |
++--------------------------+
+|         log, tail        |
++--------------------------+
+|   limits, kernel_limits  |
++--------------------------+
+|      cgroups, cgroup     |
++--------------------------+
+|     sched, scheduler     |
++--------------------------+
+|    docker, containers    |
++--------------------------+
+|     pressure, health     |
++--------------------------+
+|    proc_events, events   |
++--------------------------+
+|       vms, libvirt       |
++--------------------------+
+|        power, rapl       |
++--------------------------+
 \n\n",
         );
     let rate = Arg::with_name("rate")
@@ -360,7 +439,7 @@ Supported widget names:
         .help("Sets a refresh rate in ms.")
         .long_help(
             "\
-Sets a refresh rate in milliseconds.  The minimum is 250ms,
+Sets a refresh rate in milliseconds.  The minimum is 100ms,
 and defaults to 1000ms.  Smaller values may take more resources.\n\n\n",
         );
     let time_delta = Arg::with_name("time_delta")
@@ -400,6 +479,15 @@ Displays the network widget using bytes.  Defaults to bits.\n\n",
 Displays the network widget with a log scale.  Defaults to a non-log scale.\n\n",
         );
 
+    let network_split = Arg::with_name("network_split")
+        .long("network_split")
+        .help("Graphs download and upload in separate panes.")
+        .long_help(
+            "\
+Graphs download and upload in separate panes of the network widget,
+each with its own y-axis, so upload isn't dwarfed by download.\n\n",
+        );
+
     let network_use_binary_prefix = Arg::with_name("network_use_binary_prefix")
         .long("network_use_binary_prefix")
         .help("Displays the network widget with binary prefixes.")
@@ -408,6 +496,168 @@ Displays the network widget with a log scale.  Defaults to a non-log scale.\n\n"
 Displays the network widget with binary prefixes (i.e. kibibits, mebibits) rather than a decimal prefix (i.e. kilobits, megabits).  Defaults to decimal prefixes.\n\n\n",
         );
 
+    let mem_graph_as_value = Arg::with_name("mem_graph_as_value")
+        .long("mem_graph_as_value")
+        .help("Defaults to graphing memory usage by value.")
+        .long_help(
+            "\
+Defaults to graphing memory usage in the memory widget by value, with the y-axis scaled to the total
+amount of memory.  Otherwise, it defaults to graphing it by percentage.\n\n",
+        );
+
+    let memory_use_decimal_prefix = Arg::with_name("memory_use_decimal_prefix")
+        .long("memory_use_decimal_prefix")
+        .help("Displays memory with decimal prefixes.")
+        .long_help(
+            "\
+Displays memory with decimal prefixes (i.e. kilobytes, megabytes) rather than a binary prefix (i.e. kibibytes, mebibytes).  Defaults to binary prefixes.\n\n",
+        );
+
+    let unit_prefix = Arg::with_name("unit_prefix")
+        .long("unit_prefix")
+        .takes_value(true)
+        .value_name("PREFIX")
+        .possible_values(&["si", "iec"])
+        .help("Uses SI or IEC prefixes for all sizes and rates.")
+        .long_help(
+            "\
+Shows all sizes and rates with SI prefixes (i.e. kilobytes, megabytes)
+or IEC prefixes (i.e. kibibytes, mebibytes), across every widget.  This
+overrides --memory_use_decimal_prefix and --network_use_binary_prefix.
+By default, each widget uses its own prefixes.\n\n",
+        );
+
+    let decimal_places = Arg::with_name("decimal_places")
+        .long("decimal_places")
+        .takes_value(true)
+        .value_name("PLACES")
+        .help("Sets how many decimal places sizes and rates have.")
+        .long_help(
+            "\
+Sets how many decimal places sizes and rates are shown with, up to 9.
+By default, each widget uses its own number of decimal places.\n\n",
+        );
+
+    let process_gpu = Arg::with_name("process_gpu")
+        .long("process_gpu")
+        .help("Shows GPU usage columns in the process widget.")
+        .long_help(
+            "\
+Shows GPU utilization and GPU memory columns in the process widget.  This
+currently requires an NVIDIA GPU with nvidia-smi available.  The columns
+are hidden while processes are grouped.\n\n",
+        );
+
+    let process_child_cpu = Arg::with_name("process_child_cpu")
+        .long("process_child_cpu")
+        .help("Counts the CPU time of exited children towards their parents.")
+        .long_help(
+            "\
+Adds the CPU time of a process' children to its own once they exit,
+so build tools and shells that spawn many short-lived processes show
+how busy they really keep the CPU.  Only supported on Linux.\n\n",
+        );
+
+    let resolve_hostnames = Arg::with_name("resolve_hostnames")
+        .long("resolve_hostnames")
+        .help("Shows remote hostnames in the connections widget.")
+        .long_help(
+            "\
+Looks up the hostname of each remote address in the connections widget
+and shows it instead of the address.  Lookups happen in the background,
+so addresses are shown until their hostnames are found.  Only supported
+on Linux.\n\n",
+        );
+
+    let process_tcp = Arg::with_name("process_tcp")
+        .long("process_tcp")
+        .help("Shows TCP retransmit and RTT columns in the process widget.")
+        .long_help(
+            "\
+Shows TCP retransmit and round-trip time columns in the process widget.
+These are traced with eBPF, so this is only supported on Linux, requires
+bottom to be built with the \"ebpf\" feature, and requires bpftrace and
+root.  The columns are hidden while processes are grouped.\n\n",
+        );
+
+    let process_k8s = Arg::with_name("process_k8s")
+        .long("process_k8s")
+        .help("Shows Kubernetes pod and namespace columns in the process widget.")
+        .long_help(
+            "\
+Shows Kubernetes pod and namespace columns in the process widget, and
+allows grouping processes by pod.  Pods are found using the cgroup of
+each process and the kubelet's pod log directories, so this is only
+supported on Linux, when running on a Kubernetes node.\n\n",
+        );
+
+    let persist_history = Arg::with_name("persist_history")
+        .long("persist_history")
+        .help("Saves graph history on exit and restores it on the next start.")
+        .long_help(
+            "\
+Saves graph history on exit and restores it on the next start, so graphs
+aren't empty right after launching.  The file location and how much history
+is kept can be changed with 'history_path' and 'history_retention' in the
+config file.  Reboots since the history was saved are marked on the graphs.\n\n",
+        );
+
+    let network_max = Arg::with_name("network_max")
+        .long("network_max")
+        .takes_value(true)
+        .value_name("RATE")
+        .help("Pins the top of the network graph to a rate.")
+        .long_help(
+            "\
+Pins the top of the network graph's y-axis to a rate, such as
+1Gbit or 125MB, rather than scaling it to fit what is shown.
+This keeps the graph comparable over time.  Note that b is bits
+and B is bytes.  This can be toggled with 'M'.\n\n",
+        );
+
+    let stream_address = Arg::with_name("stream_address")
+        .long("stream_address")
+        .takes_value(true)
+        .value_name("ADDRESS")
+        .help("Streams harvested data as JSON over a websocket.")
+        .long_help(
+            "\
+Starts a websocket server on the given address (for example,
+127.0.0.1:9000) that sends each harvested data snapshot as JSON
+to all connected clients.  Only data for widgets in use is sent.\n\n",
+        );
+
+    let dump = Arg::with_name("dump")
+        .long("dump")
+        .takes_value(true)
+        .min_values(0)
+        .value_name("FORMAT")
+        .possible_values(&["text", "json"])
+        .help("Prints a one-time summary of the system and exits.")
+        .long_help(
+            "\
+Harvests once and prints CPU, memory, disk, network, sensor, and
+process data to stdout instead of starting the interface, like
+free, df, and top -bn1 at once.  Prints a text summary (\"text\",
+the default) or the same JSON as the stream (\"json\").  Filters
+and units from the config file still apply.\n\n",
+        );
+
+    let session_summary = Arg::with_name("session_summary")
+        .long("session_summary")
+        .takes_value(true)
+        .min_values(0)
+        .value_name("FILE")
+        .help("Prints a summary of CPU, memory, and network usage on exit.")
+        .long_help(
+            "\
+Keeps track of the lowest, average, and highest CPU, memory, and
+network usage while bottom runs, and prints a table of them to
+stdout on exit.  If a file is given, the table is written to it
+instead.  Only data for widgets in use is harvested, so anything
+not shown is left out.\n\n",
+        );
+
     App::new(crate_name!())
         .setting(AppSettings::UnifiedHelpMessage)
         .version(crate_version!())
@@ -423,18 +673,31 @@ Displays the network widget with binary prefixes (i.e. kibibits, mebibits) rathe
         .group(ArgGroup::with_name("TEMPERATURE_TYPE").args(&["kelvin", "fahrenheit", "celsius"]))
         .arg(autohide_time)
         .arg(basic)
+        .arg(mini)
         .arg(battery)
         .arg(case_sensitive)
+        .arg(cpu_top_processes)
         .arg(process_command)
+        .arg(process_gpu)
+        .arg(process_child_cpu)
+        .arg(resolve_hostnames)
+        .arg(process_tcp)
+        .arg(process_k8s)
         .arg(config_location)
         .arg(color)
         // .arg(debug)
         .arg(mem_as_value)
+        .arg(mem_graph_as_value)
+        .arg(memory_use_decimal_prefix)
+        .arg(unit_prefix)
+        .arg(decimal_places)
         .arg(default_time_value)
         .arg(default_widget_count)
         .arg(default_widget_type)
         .arg(disable_click)
+        .arg(disk_inodes)
         .arg(dot_marker)
+        .arg(dump)
         .arg(group)
         .arg(hide_avg_cpu)
         .arg(hide_table_gap)
@@ -446,10 +709,17 @@ Displays the network widget with binary prefixes (i.e. kibibits, mebibits) rathe
         .arg(rate)
         .arg(regex)
         .arg(time_delta)
+        .arg(time_format)
+        .arg(time_labels)
         .arg(tree)
         .arg(network_use_bytes)
         .arg(network_use_log)
+        .arg(network_split)
         .arg(network_use_binary_prefix)
+        .arg(network_max)
+        .arg(persist_history)
+        .arg(session_summary)
+        .arg(stream_address)
         .arg(current_usage)
         .arg(use_old_network_legend)
         .arg(whole_word)
@@ -1,4 +1,4 @@
-use crate::options::ConfigColours;
+use crate::{app::layout_manager::BottomWidgetType, options::ConfigColours};
 use once_cell::sync::Lazy;
 
 // Default widget ID
@@ -6,16 +6,18 @@ pub const DEFAULT_WIDGET_ID: u64 = 56709;
 
 // How long to store data.
 pub const STALE_MAX_MILLISECONDS: u64 = 600 * 1000; // Keep 10 minutes of data.
+pub const RETENTION_MAX_MILLISECONDS: u64 = 24 * 60 * 60 * 1000; // At most, keep a day of data.
 
 // How much data is SHOWN
 pub const DEFAULT_TIME_MILLISECONDS: u64 = 60 * 1000; // Defaults to 1 min.
-pub const STALE_MIN_MILLISECONDS: u64 = 30 * 1000; // Lowest is 30 seconds
+pub const STALE_MIN_MILLISECONDS: u64 = 15 * 1000; // Lowest is 15 seconds
 pub const TIME_CHANGE_MILLISECONDS: u64 = 15 * 1000; // How much to increment each time
 pub const AUTOHIDE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to autohide
 
 pub const TICK_RATE_IN_MILLISECONDS: u64 = 200;
 // How fast the screen refreshes
 pub const DEFAULT_REFRESH_RATE_IN_MILLISECONDS: u64 = 1000;
+pub const MIN_REFRESH_RATE_IN_MILLISECONDS: u64 = 100;
 pub const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
 
 // Limits for when we should stop showing table gaps/labels (anything less means not shown)
@@ -218,25 +220,34 @@ pub static NORD_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigC
 });
 
 // Help text
-pub const HELP_CONTENTS_TEXT: [&str; 8] = [
+pub const HELP_CONTENTS_TEXT: [&str; 12] = [
     "Press the corresponding numbers to jump to the section, or scroll:",
     "1 - General",
     "2 - CPU widget",
     "3 - Process widget",
     "4 - Process search widget",
     "5 - Process sort widget",
-    "6 - Battery widget",
-    "7 - Basic memory widget",
+    "6 - Battery and memory widgets",
+    "7 - Services widget",
+    "8 - Docker widget",
+    "9 - Disk widget",
+    "0 - Network widget",
+    "- - Temperature widget, at the end",
 ];
 
 // TODO [Help]: Search in help?
 // TODO [Help]: Move to using tables for easier formatting?
-pub const GENERAL_HELP_TEXT: [&str; 30] = [
+pub const GENERAL_HELP_TEXT: [&str; 37] = [
     "1 - General",
     "q, Ctrl-c        Quit",
     "Esc              Close dialog windows, search, widgets, or exit expanded mode",
     "Ctrl-r           Reset display and any collected data",
     "f                Freeze/unfreeze updating with new data",
+    "[, ]             Scroll frozen graphs earlier/later in time",
+    "X                Toggle a crosshair showing the values at one time across all graphs",
+    "Left, Right      Move the crosshair earlier/later while on a graph",
+    "E                Save a snapshot of the current data to a JSON file",
+    "/, Ctrl-f        Search all widgets, when not on the process widget",
     "Ctrl-Left,       ",
     "Shift-Left,      Move widget selection left",
     "H, A             ",
@@ -257,6 +268,8 @@ pub const GENERAL_HELP_TEXT: [&str; 30] = [
     "gg               Jump to the first entry",
     "G                Jump to the last entry",
     "e                Toggle expanding the currently selected widget",
+    "z                Hide the currently selected widget",
+    "Z                Show all hidden widgets",
     "+                Zoom in on chart (decrease time range)",
     "-                Zoom out on chart (increase time range)",
     "=                Reset zoom",
@@ -264,30 +277,43 @@ pub const GENERAL_HELP_TEXT: [&str; 30] = [
     "Mouse click      Selects the clicked widget, table entry, dialog option, or tab",
 ];
 
-pub const CPU_HELP_TEXT: [&str; 2] = [
+pub const CPU_HELP_TEXT: [&str; 3] = [
     "2 - CPU widget\n",
+    "v                Show or hide the average and max usage of each entry in the legend",
     "Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart",
 ];
 
-pub const PROCESS_HELP_TEXT: [&str; 15] = [
+pub const PROCESS_HELP_TEXT: [&str; 27] = [
     "3 - Process widget",
     "dd, F9           Kill the selected process",
+    "Space            Mark the selected process, so kills, suspends, and renices act on all marked ones",
+    "x                Suspend the selected process (SIGSTOP)",
+    "u                Resume the selected process (SIGCONT)",
+    "r                Change the niceness of the selected process",
     "c                Sort by CPU usage, press again to reverse sorting order",
     "m                Sort by memory usage, press again to reverse sorting order",
     "p                Sort by PID name, press again to reverse sorting order",
     "n                Sort by process name, press again to reverse sorting order",
-    "Tab              Group/un-group processes with the same name",
+    "Tab              Group/un-group processes by name (then by pod, if enabled)",
     "Ctrl-f, /        Open process search widget",
-    "P                Toggle between showing the full command or just the process name",
+    "P                Cycle between the process name, executable path, or command",
+    "U                Toggle only showing your own processes",
     "s, F6            Open process sort widget",
     "I                Invert current sort",
-    "%                Toggle between values and percentages for memory usage",
+    "%                Cycle memory usage between percent, resident, and virtual size",
     "t, F5            Toggle tree mode",
+    "N                Toggle between CPU usage relative to total or current usage",
     "+, -, click      Collapse/expand a branch while in tree mode",
+    "o                Open the column picker to show, hide, or reorder columns",
+    "b                View or change which CPUs the selected process may run on",
+    "T                View the threads of the selected process",
+    "F                Pin the selected process to the top of the table, or unpin it",
+    "y                Copy the selected process to the clipboard",
+    "Y                Copy every process in the table to the clipboard",
     "click on header  Sorts the entries by that column, click again to invert the sort",
 ];
 
-pub const SEARCH_HELP_TEXT: [&str; 49] = [
+pub const SEARCH_HELP_TEXT: [&str; 50] = [
     "4 - Process search widget",
     "Tab              Toggle between searching for PID and name",
     "Esc              Close the search widget (retains the filter)",
@@ -301,6 +327,7 @@ pub const SEARCH_HELP_TEXT: [&str; 49] = [
     "Alt-c, F1        Toggle matching case",
     "Alt-w, F2        Toggle matching the entire word",
     "Alt-r, F3        Toggle using regex",
+    "Alt-i, F4        Toggle only highlighting matches, rather than filtering",
     "Left, Alt-h      Move cursor left",
     "Right, Alt-l     Move cursor right",
     "",
@@ -339,24 +366,58 @@ pub const SEARCH_HELP_TEXT: [&str; 49] = [
     "TiB              ex: read > 1 tib",
 ];
 
-pub const SORT_HELP_TEXT: [&str; 6] = [
+pub const SORT_HELP_TEXT: [&str; 7] = [
     "5 - Sort widget\n",
     "Down, 'j'        Scroll down in list",
     "Up, 'k'          Scroll up in list",
     "Mouse scroll     Scroll through sort widget",
     "Esc              Close the sort widget",
     "Enter            Sort by current selected column",
+    "a                Add, reverse, or remove current column as a tiebreaker",
 ];
 
-pub const BATTERY_HELP_TEXT: [&str; 3] = [
-    "6 - Battery widget",
+pub const BATTERY_AND_MEM_HELP_TEXT: [&str; 4] = [
+    "6 - Battery and memory widgets",
     "Left             Go to previous battery",
     "Right            Go to next battery",
+    "%                Toggle between values and percentages for memory usage",
 ];
 
-pub const BASIC_MEM_HELP_TEXT: [&str; 2] = [
-    "7 - Basic memory widget",
-    "%                Toggle between values and percentages for memory usage",
+pub const SERVICES_HELP_TEXT: [&str; 5] = [
+    "7 - Services widget",
+    "u                Start the selected service, press again to confirm",
+    "x                Stop the selected service, press again to confirm",
+    "r                Restart the selected service, press again to confirm",
+    "Esc              Cancel the pending action",
+];
+
+pub const DOCKER_HELP_TEXT: [&str; 5] = [
+    "8 - Docker widget",
+    "u                Start the selected container, press again to confirm",
+    "x                Stop the selected container, press again to confirm",
+    "r                Restart the selected container, press again to confirm",
+    "Esc              Cancel the pending action",
+];
+
+pub const DISK_HELP_TEXT: [&str; 5] = [
+    "9 - Disk widget",
+    "Enter            Scan what is using space on the selected disk",
+    "i                Toggle between the I/O of each partition and of its whole device",
+    "w                Show the processes that are writing, heaviest first",
+    "t                Toggle grouping disks by filesystem type",
+];
+
+pub const NETWORK_HELP_TEXT: [&str; 5] = [
+    "0 - Network widget",
+    "B                Toggle between bits and bytes",
+    "O                Toggle between a linear and log scale",
+    "M                Pin the top of the graph, or let it scale to fit again",
+    "V                Toggle splitting the legend by IPv4 and IPv6",
+];
+
+pub const TEMP_HELP_TEXT: [&str; 2] = [
+    "Temperature widget",
+    "U                Cycle the temperature unit between Celsius, Fahrenheit, and Kelvin",
 ];
 
 pub static HELP_TEXT: Lazy<Vec<Vec<&'static str>>> = Lazy::new(|| {
@@ -367,11 +428,41 @@ pub static HELP_TEXT: Lazy<Vec<Vec<&'static str>>> = Lazy::new(|| {
         PROCESS_HELP_TEXT.to_vec(),
         SEARCH_HELP_TEXT.to_vec(),
         SORT_HELP_TEXT.to_vec(),
-        BATTERY_HELP_TEXT.to_vec(),
-        BASIC_MEM_HELP_TEXT.to_vec(),
+        BATTERY_AND_MEM_HELP_TEXT.to_vec(),
+        SERVICES_HELP_TEXT.to_vec(),
+        DOCKER_HELP_TEXT.to_vec(),
+        DISK_HELP_TEXT.to_vec(),
+        NETWORK_HELP_TEXT.to_vec(),
+        TEMP_HELP_TEXT.to_vec(),
     ]
 });
 
+/// The widgets each section of [`HELP_TEXT`] is about, so remaps that only apply to some widgets
+/// are only shown in their sections.
+pub const HELP_SECTION_WIDGETS: [&[BottomWidgetType]; 12] = [
+    &[],
+    &[],
+    &[
+        BottomWidgetType::Cpu,
+        BottomWidgetType::CpuLegend,
+        BottomWidgetType::BasicCpu,
+    ],
+    &[BottomWidgetType::Proc],
+    &[BottomWidgetType::ProcSearch],
+    &[BottomWidgetType::ProcSort],
+    &[
+        BottomWidgetType::Battery,
+        BottomWidgetType::BasicBattery,
+        BottomWidgetType::Mem,
+        BottomWidgetType::BasicMem,
+    ],
+    &[BottomWidgetType::Services],
+    &[BottomWidgetType::Docker],
+    &[BottomWidgetType::Disk],
+    &[BottomWidgetType::Net, BottomWidgetType::BasicNet],
+    &[BottomWidgetType::Temp],
+];
+
 // Default layouts
 pub const DEFAULT_LAYOUT: &str = r##"
 [[row]]
@@ -429,6 +520,7 @@ pub const DEFAULT_BATTERY_LAYOUT: &str = r##"
 
 // Config and flags
 pub const DEFAULT_CONFIG_FILE_PATH: &str = "bottom/bottom.toml";
+pub const DEFAULT_HISTORY_FILE_PATH: &str = "bottom/history";
 
 // TODO: Eventually deprecate this.
 pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  All of the settings are commented
@@ -450,6 +542,8 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #left_legend = false
 # Whether to set CPU% on a process to be based on the total CPU or just current usage.
 #current_usage = false
+# Whether to overlay the CPU usage of the busiest processes on the CPU graph.
+#cpu_top_processes = false
 # Whether to group processes with the same name together by default.
 #group_processes = false
 # Whether to make process searching case sensitive by default.
@@ -471,11 +565,19 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #time_delta = 15000
 # Hides the time scale.
 #hide_time = false
+# Label the time scale with "relative" offsets (e.g. 60s) or "absolute" times of day.
+#time_labels = "relative"
+# Show times of day on a "24h" or "12h" clock.
+#time_format = "24h"
 # Override layout default widget
 #default_widget_type = "proc"
 #default_widget_count = 1
 # Use basic mode
 #basic = false
+# Which meters to show at the top of basic mode, and in what order.  Valid values are "cpu", "mem", "net", and "battery".
+#basic_meters = ["cpu", "mem", "net"]
+# Only show the basic mode meters, on one line.  Meant for small panes, like a tmux status pane.
+#mini = false
 # Use the old network legend style
 #use_old_network_legend = false
 # Remove space in tables
@@ -484,24 +586,71 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #battery = false
 # Disable mouse clicks
 #disable_click = false
+# Show how much of each filesystem's inodes are used in the disk widget
+#disk_inodes = false
 # Built-in themes.  Valid values are "default", "default-light", "gruvbox", "gruvbox-light", "nord", "nord-light"
 #color = "default"
 # Show memory values in the processes widget as values by default
 #mem_as_value = false
+# Graph memory in the memory widget as values rather than percentages by default
+#mem_graph_as_value = false
+# Displays memory with decimal prefixes rather than binary prefixes.
+#memory_use_decimal_prefix = false
+# Use "si" or "iec" prefixes for all sizes and rates.  Overrides memory_use_decimal_prefix and network_use_binary_prefix.
+#unit_prefix = "iec"
+# How many decimal places to show sizes and rates with.
+#decimal_places = 1
 # Show tree mode by default in the processes widget.
 #tree = false
 # Shows an indicator in table widgets tracking where in the list you are.
 #show_table_scroll_position = false
 # Show processes as their commands by default in the process widget.
 #process_command = false
+# Show GPU usage columns in the process widget.  Requires nvidia-smi.
+#process_gpu = false
+# Show TCP retransmit and RTT columns in the process widget.  Requires the "ebpf" feature, bpftrace, and root.
+#process_tcp = false
+# Show Kubernetes pod and namespace columns in the process widget, and allow grouping by pod.  Linux only.
+#process_k8s = false
+# Count the CPU time of exited child processes towards their parents.  Linux only.
+#process_child_cpu = false
+# Show remote hostnames instead of addresses in the connections widget.  Linux only.
+#resolve_hostnames = false
+# Which process columns to show, and in what order.  Columns not listed are hidden.
+#process_columns = ["pid", "name", "cpu%", "mem", "r/s", "w/s", "t.read", "t.write", "user", "state"]
+# How to sort processes.  Keys after the first are used to break ties.
+#process_sort = ["cpu% desc", "mem desc"]
 # Displays the network widget with binary prefixes.
 #network_use_binary_prefix = false
 # Displays the network widget using bytes.
 #network_use_bytes = false
 # Displays the network widget with a log scale.
 #network_use_log = false
+# Graphs download and upload in separate panes, each with its own y-axis.
+#network_split = false
+# Pins the top of the network graph to a rate, rather than scaling it to fit.  "b" is bits and "B" is bytes.
+#network_max = "1Gbit"
 # Hides advanced options to stop a process on Unix-like systems.
 #disable_advanced_kill = false
+# Saves graph history on exit and restores it on the next start.
+#persist_history = false
+# Where to save graph history.  Defaults to a "bottom/history" file in your data directory.
+#history_path = "/path/to/history"
+# How much graph history to save, in milliseconds.  Must be between 30000 and 86400000.
+#history_retention = 600000
+# Prints the lowest, average, and highest CPU, memory, and network usage on exit.
+#session_summary = false
+# Writes the session summary to this file instead of printing it.
+#session_summary_path = "/path/to/summary.txt"
+# Streams each harvested data snapshot as JSON over a websocket at this address.
+#stream_address = "127.0.0.1:9000"
+# Only serve the stream to clients that send this token.
+#stream_token = "a long random string"
+# Where to save data snapshots (taken with 'E').  Defaults to the current directory.
+#snapshot_dir = "/path/to/snapshots"
+# On slow terminals, keeps drawing under this many milliseconds by reusing widgets that haven't
+# changed.  Off by default.
+#frame_budget = 50
 
 # These are all the components that support custom theming.  Note that colour support
 # will depend on terminal support.
@@ -588,6 +737,15 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #case_sensitive = false
 #whole_word = false
 
+# Only show (or hide) disks with these filesystem types.  Only showing some types also lets through virtual and network
+# filesystems, like tmpfs or NFS, which are hidden otherwise.
+#[fs_filter]
+#is_list_ignored = false
+#list = ["nfs4?", "cifs"]
+#regex = true
+#case_sensitive = false
+#whole_word = true
+
 #[temp_filter]
 #is_list_ignored = true
 #list = ["cpu", "wifi"]
@@ -595,12 +753,118 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #case_sensitive = false
 #whole_word = false
 
+# Friendly names for temperature sensors, keyed by the sensor's original name.
+#[temp_alias]
+#"k10temp: Tctl" = "CPU"
+
+# Units for particular temperature sensors, keyed by the name the sensor is shown with.
+#[temp_unit]
+#"amdgpu: edge" = "f"
+
 #[net_filter]
 #is_list_ignored = true
 #list = ["virbr0.*"]
 #regex = true
 #case_sensitive = false
 #whole_word = false
+
+# Only show (or hide) processes run by these users.  Only supported on Unix-like systems.
+#[user_filter]
+#is_list_ignored = false
+#list = ["root", "alice"]
+#regex = false
+#case_sensitive = false
+#whole_word = true
+
+# Plugins - commands that print JSON (a number, or an object of numbers) to be graphed in a "custom" widget.
+#[[plugin]]
+#name = "example"
+#command = "/path/to/command"
+#args = []
+
+# Remotes - other bottom instances run with stream_address set, to be shown in "remote" widgets.
+#[[remote]]
+#name = "server"
+#address = "10.0.0.2:9000"
+# Optionally, reach the stream through an SSH tunnel, with the address as seen from the SSH host.
+#ssh = "user@10.0.0.2"
+# The remote's stream_token, if it has one.
+#token = "a long random string"
+
+# Exporters - push harvested metrics to InfluxDB ("influx") or Graphite ("graphite") over TCP or UDP.
+#[[exporter]]
+#format = "influx"
+#address = "127.0.0.1:8089"
+#transport = "udp"
+#interval = 10000
+#prefix = "btm"
+#tags = { host = "server" }
+
+# Retention - how much history each graph keeps, in milliseconds.  Defaults to 10 minutes each, or
+# more if default_time_value or history_retention is longer.
+#[retention]
+#cpu = 600000
+#mem = 600000
+#net = 600000
+#custom = 600000
+#sched = 600000
+#power = 600000
+
+# Graph styles - how each graph's lines are drawn, as one of "braille", "block", "dot", or "filled".
+#[graph_style]
+#default = "braille"
+#cpu = "braille"
+#mem = "filled"
+#net = "braille"
+#battery = "braille"
+#custom = "braille"
+#sched = "braille"
+#power = "braille"
+
+# Pressure weights - how much each part counts for in the pressure widget's score, relative to the
+# others.  A weight of 0 leaves that part out.
+#[pressure_weights]
+#cpu = 1.0
+#mem = 1.0
+#psi = 1.0
+#io_wait = 1.0
+
+# Battery alerts - shows a desktop notification, or runs a command, when a discharging battery runs low.
+#[battery_alert]
+#charge_below = 15
+#minutes_below = 20
+# Also alert when the AC adapter is plugged in or unplugged.
+#on_power_change = false
+#command = "/path/to/script"
+#args = []
+# Also alert through the terminal, with any of "bell", "osc9", or "title".
+#terminal = ["bell"]
+# Also post alerts to a webhook, or email them.  Both are sent with curl.
+#[battery_alert.webhook]
+#url = "https://hooks.example.com/bottom"
+#[battery_alert.email]
+#url = "smtps://smtp.example.com:465"
+#from = "bottom@example.com"
+#to = ["admin@example.com"]
+#username = "bottom@example.com"
+#password = "password"
+
+# Process rules - signals a process once it goes over a threshold.  Mode is one of "confirm" (open
+# the kill dialog), "dry_run" (only show what would be sent), or "auto" (send it right away).
+#[[process_rule]]
+#name = "chrome"
+#mem_above = "8GB"
+#cpu_above = 90.0
+#signal = "TERM"
+#mode = "confirm"
+# Rules can also post to a webhook or send an email when they trigger, like battery alerts.
+#[process_rule.webhook]
+#url = "https://hooks.example.com/bottom"
+
+# Keybinds - remaps actions to different keys.  See the documentation for a list of actions.
+#[keybinds]
+#kill = "x"
+#search = "ctrl-s"
 "##;
 
 pub const CONFIG_TOP_HEAD: &str = r##"# This is bottom's config file.
@@ -1,14 +1,29 @@
 //! This mainly concerns converting collected data into things that the canvas
 //! can actually handle.
-use crate::{app::AxisScaling, units::data_units::DataUnit, Pid};
+use crate::{
+    app::AxisScaling,
+    units::{
+        data_units::DataUnit,
+        formatting::{NumberFormat, UnitPrefix},
+    },
+    Pid,
+};
 use crate::{
     app::{data_farmer, data_harvester, App, ProcWidgetState},
     utils::{self, gen_util::*},
 };
-use data_harvester::processes::ProcessSorting;
+use data_harvester::{
+    processes::{ProcessNameType, ProcessSorting, ALL_CAPABILITIES, CAPABILITY_NAMES},
+    temperature::{convert_celsius, SensorKind},
+};
 use fxhash::FxBuildHasher;
 use indexmap::IndexSet;
-use std::collections::{HashMap, VecDeque};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 /// Point is of time, data
 type Point = (f64, f64);
@@ -21,6 +36,8 @@ pub struct ConvertedBatteryData {
     pub duration_until_full: Option<String>,
     pub duration_until_empty: Option<String>,
     pub health: String,
+    /// Power draw in watts over time.
+    pub power_data: Vec<Point>,
 }
 
 #[derive(Default, Debug)]
@@ -40,18 +57,49 @@ pub struct ConvertedNetworkData {
     // mean_tx: f64,
 }
 
+#[derive(Default, Debug)]
+pub struct ConvertedPluginData {
+    pub name: String,
+    pub value_display: String,
+    pub points: Vec<Point>,
+}
+
+#[derive(Default, Debug)]
+pub struct ConvertedTopProcessData {
+    pub name: String,
+    pub value_display: String,
+    pub points: Vec<Point>,
+}
+
+#[derive(Default, Debug)]
+pub struct ConvertedNumaData {
+    pub node: u32,
+    pub label_percent: String,
+    pub label_frac: String,
+    pub points: Vec<Point>,
+}
+
 // TODO: [REFACTOR] Process data... stuff really needs a rewrite.  Again.
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Debug)]
 pub struct ConvertedProcessData {
     pub pid: Pid,
     pub ppid: Option<Pid>,
-    pub name: String,
-    pub command: String,
+    /// Shared with the harvested process, rather than copied every update.
+    pub name: Arc<str>,
+    pub command: Arc<str>,
+    /// The path of the process' executable, or its name if that can't be read.
+    pub exe_path: Arc<str>,
     pub is_thread: Option<bool>,
     pub cpu_percent_usage: f64,
+    /// CPU time used since the last harvest, in milliseconds.
+    pub cpu_time_delta_ms: Option<u64>,
+    /// Effective capabilities, as a bitmask.
+    pub cap_eff: Option<u64>,
+    pub seccomp_mode: Option<u32>,
     pub mem_percent_usage: f64,
     pub mem_usage_bytes: u64,
-    pub mem_usage_str: (f64, String),
+    pub mem_usage_str: String,
+    pub virt_mem_bytes: u64,
     pub group_pids: Vec<Pid>,
     pub read_per_sec: String,
     pub write_per_sec: String,
@@ -64,6 +112,22 @@ pub struct ConvertedProcessData {
     pub process_state: String,
     pub process_char: char,
     pub user: Option<String>,
+    pub gpu_util_percent: Option<f64>,
+    pub gpu_mem_bytes: Option<u64>,
+    pub swap_bytes: Option<u64>,
+    pub tcp_retransmits: Option<u64>,
+    pub tcp_rtt_us: Option<f64>,
+    pub pod_name: Option<String>,
+    pub pod_namespace: Option<String>,
+    /// When the process started, in seconds since the Unix epoch.
+    pub start_time: Option<u64>,
+    /// How long the process has been running for, in seconds.
+    pub runtime_secs: Option<u64>,
+    pub fd_count: Option<u64>,
+    /// The process' CPU usage readings over the last [`CPU_TREND_DURATION`], oldest first.
+    pub cpu_history: VecDeque<(Instant, f64)>,
+    /// Whether the process' I/O couldn't be read without more permissions.
+    pub is_io_restricted: bool,
 
     /// Prefix printed before the process when displayed.
     pub process_description_prefix: Option<String>,
@@ -73,6 +137,17 @@ pub struct ConvertedProcessData {
     pub is_collapsed_entry: bool,
 }
 
+impl ConvertedProcessData {
+    /// What the name column shows for the process.
+    pub fn get_name(&self, name_type: ProcessNameType) -> &str {
+        match name_type {
+            ProcessNameType::Name => &self.name,
+            ProcessNameType::Path => &self.exe_path,
+            ProcessNameType::Command => &self.command,
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct ConvertedCpuData {
     pub cpu_name: String,
@@ -81,77 +156,155 @@ pub struct ConvertedCpuData {
     pub cpu_data: Vec<Point>,
     /// Represents the value displayed on the legend.
     pub legend_value: String,
+    /// The average usage over the last minute and the highest usage this session, for the
+    /// expanded legend.
+    pub avg_legend_value: String,
+    pub max_legend_value: String,
+    /// Whether the CPU is offline, so it's grayed out rather than shown as idle.
+    pub is_offline: bool,
 }
 
 pub fn convert_temp_row(app: &App) -> Vec<Vec<String>> {
     let current_data = &app.data_collection;
     let temp_type = &app.app_config_fields.temperature_type;
+    let temp_units = &app.app_config_fields.temperature_units;
 
     let mut sensor_vector: Vec<Vec<String>> = current_data
         .temp_harvest
         .iter()
         .map(|temp_harvest| {
+            let format_reading = |value: f32| match temp_harvest.kind {
+                SensorKind::Temperature => {
+                    let temp_type = temp_units.get(&temp_harvest.name).unwrap_or(temp_type);
+                    (convert_celsius(value, temp_type).ceil() as u64).to_string()
+                        + temp_type.as_symbol()
+                }
+                SensorKind::Fan => format!("{:.0}RPM", value),
+                SensorKind::Voltage => format!("{:.2}V", value),
+                SensorKind::Power => format!("{:.1}W", value),
+            };
+
+            let thresholds = &temp_harvest.thresholds;
+            let limits = [
+                ("lo", thresholds.min),
+                ("hi", thresholds.max),
+                ("crit", thresholds.critical),
+            ]
+            .iter()
+            .filter_map(|(label, limit)| Some(format!("{} {}", label, format_reading((*limit)?))))
+            .collect::<Vec<_>>()
+            .join(" ");
+
             vec![
                 temp_harvest.name.clone(),
-                (temp_harvest.temperature.ceil() as u64).to_string()
-                    + match temp_type {
-                        data_harvester::temperature::TemperatureType::Celsius => "°C",
-                        data_harvester::temperature::TemperatureType::Kelvin => "K",
-                        data_harvester::temperature::TemperatureType::Fahrenheit => "°F",
-                    },
+                format_reading(temp_harvest.temperature),
+                limits,
             ]
         })
         .collect();
 
     if sensor_vector.is_empty() {
-        sensor_vector.push(vec!["No Sensors Found".to_string(), "".to_string()]);
+        sensor_vector.push(vec![
+            "No Sensors Found".to_string(),
+            "".to_string(),
+            "".to_string(),
+        ]);
     }
 
     sensor_vector
 }
 
-pub fn convert_disk_row(current_data: &data_farmer::DataCollection) -> Vec<Vec<String>> {
+pub fn convert_disk_row(
+    current_data: &data_farmer::DataCollection, number_format: &NumberFormat, show_inodes: bool,
+    is_io_per_device: bool, group_by_fs: bool,
+) -> Vec<Vec<String>> {
     let mut disk_vector: Vec<Vec<String>> = Vec::new();
 
-    current_data
+    let format_rate = |rate: u64| {
+        let decimal_places = if rate >= GIGA_LIMIT { 1 } else { 0 };
+        number_format.format_prefixed(rate, "B/s", UnitPrefix::Si, decimal_places)
+    };
+
+    let mut disks = current_data
         .disk_harvest
         .iter()
-        .zip(&current_data.io_labels)
-        .for_each(|(disk, (io_read, io_write))| {
-            let free_space_fmt = if let Some(free_space) = disk.free_space {
-                let converted_free_space = get_decimal_bytes(free_space);
-                format!("{:.*}{}", 0, converted_free_space.0, converted_free_space.1)
-            } else {
-                "N/A".to_string()
-            };
-            let total_space_fmt = if let Some(total_space) = disk.total_space {
-                let converted_total_space = get_decimal_bytes(total_space);
-                format!(
-                    "{:.*}{}",
-                    0, converted_total_space.0, converted_total_space.1
-                )
-            } else {
-                "N/A".to_string()
-            };
+        .zip(if is_io_per_device {
+            &current_data.device_io_rates
+        } else {
+            &current_data.io_rates
+        })
+        .zip(&current_data.disk_used_history)
+        .map(|((disk, io_rates), used_history)| (disk, io_rates, used_history))
+        .collect::<Vec<_>>();
 
-            let usage_fmt = if let (Some(used_space), Some(total_space)) =
-                (disk.used_space, disk.total_space)
-            {
+    // Disks are already sorted by name, which is kept within each type.
+    if group_by_fs {
+        disks.sort_by(|(a, _, _), (b, _, _)| a.fs_type.cmp(&b.fs_type));
+    }
+
+    disks.into_iter().for_each(|(disk, io_rates, history)| {
+        let free_space_fmt = if let Some(free_space) = disk.free_space {
+            number_format.format_bytes(free_space, UnitPrefix::Si, 0)
+        } else {
+            "N/A".to_string()
+        };
+        let total_space_fmt = if let Some(total_space) = disk.total_space {
+            number_format.format_bytes(total_space, UnitPrefix::Si, 0)
+        } else {
+            "N/A".to_string()
+        };
+        let (io_read, io_write) = if let Some((read_rate, write_rate)) = io_rates {
+            (format_rate(*read_rate), format_rate(*write_rate))
+        } else {
+            ("N/A".to_string(), "N/A".to_string())
+        };
+
+        let usage_fmt =
+            if let (Some(used_space), Some(total_space)) = (disk.used_space, disk.total_space) {
                 format!("{:.0}%", used_space as f64 / total_space as f64 * 100_f64)
             } else {
                 "N/A".to_string()
             };
 
-            disk_vector.push(vec![
-                disk.name.to_string(),
-                disk.mount_point.to_string(),
-                usage_fmt,
-                free_space_fmt,
-                total_space_fmt,
-                io_read.to_string(),
-                io_write.to_string(),
-            ]);
-        });
+        let trend_fmt = if let Some(total_space) = disk.total_space {
+            get_disk_trend_string(history, total_space)
+        } else {
+            "N/A".to_string()
+        };
+
+        let name = if disk.backing_devices.is_empty() {
+            disk.name.to_string()
+        } else {
+            format!("{} on {}", disk.name, disk.backing_devices.join(", "))
+        };
+
+        let mut disk_row = vec![
+            name,
+            disk.mount_point.to_string(),
+            disk.fs_type.to_string(),
+            usage_fmt,
+        ];
+        if show_inodes {
+            disk_row.push(
+                if let (Some(used_inodes), Some(total_inodes)) =
+                    (disk.used_inodes, disk.total_inodes)
+                {
+                    format!("{:.0}%", used_inodes as f64 / total_inodes as f64 * 100_f64)
+                } else {
+                    "N/A".to_string()
+                },
+            );
+        }
+        disk_row.extend(vec![
+            free_space_fmt,
+            total_space_fmt,
+            trend_fmt,
+            io_read,
+            io_write,
+        ]);
+
+        disk_vector.push(disk_row);
+    });
 
     if disk_vector.is_empty() {
         disk_vector.push(vec!["No Disks Found".to_string(), "".to_string()]);
@@ -160,6 +313,144 @@ pub fn convert_disk_row(current_data: &data_farmer::DataCollection) -> Vec<Vec<S
     disk_vector
 }
 
+/// The characters used to draw sparklines in tables, from lowest to highest.
+const SPARKLINE_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// How many points to show in a disk's usage trend.
+const DISK_TREND_POINTS: usize = 8;
+
+/// Returns a sparkline of how much of a disk has been used over the retained data, followed by
+/// how much its usage has changed over that time, in percentage points.  So that noise doesn't
+/// fill the sparkline, it always spans at least a percent of the disk.
+fn get_disk_trend_string(used_history: &VecDeque<(Instant, f64)>, total_space: u64) -> String {
+    let (first, last) = match (used_history.front(), used_history.back()) {
+        (Some((_, first)), Some((_, last))) if used_history.len() > 1 && total_space > 0 => {
+            (*first, *last)
+        }
+        _ => return "N/A".to_string(),
+    };
+
+    let num_points = used_history.len().min(DISK_TREND_POINTS);
+    let sampled_points = (0..num_points)
+        .map(|point| used_history[point * (used_history.len() - 1) / (num_points - 1)].1)
+        .collect::<Vec<_>>();
+
+    let min = sampled_points.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = sampled_points
+        .iter()
+        .cloned()
+        .fold(f64::NEG_INFINITY, f64::max);
+    let span = (max - min).max(total_space as f64 / 100.0);
+
+    let sparkline = sampled_points
+        .iter()
+        .map(|point| {
+            let level = ((point - min) / span * (SPARKLINE_CHARS.len() - 1) as f64).round();
+            SPARKLINE_CHARS[(level as usize).min(SPARKLINE_CHARS.len() - 1)]
+        })
+        .collect::<String>();
+
+    format!(
+        "{} {:+.1}%",
+        sparkline,
+        (last - first) / total_space as f64 * 100.0
+    )
+}
+
+/// How long to keep a process' CPU usage readings for its trend.
+pub const CPU_TREND_DURATION: Duration = Duration::from_secs(30);
+
+/// How many points to show in a process' CPU usage trend.
+const CPU_TREND_POINTS: usize = 8;
+
+/// Returns a sparkline of a process' CPU usage over its retained readings, with each point
+/// averaging an equal share of them.  So that idle processes don't look busy, it always spans at
+/// least a percent of CPU usage.
+pub fn get_cpu_trend_string(cpu_history: &VecDeque<(Instant, f64)>) -> String {
+    if cpu_history.is_empty() {
+        return "-".to_string();
+    }
+
+    let num_readings = cpu_history.len();
+    let num_points = num_readings.min(CPU_TREND_POINTS);
+    let points = (0..num_points)
+        .map(|point| {
+            let (start, end) = (
+                point * num_readings / num_points,
+                (point + 1) * num_readings / num_points,
+            );
+            cpu_history
+                .range(start..end)
+                .map(|(_, cpu_usage)| cpu_usage)
+                .sum::<f64>()
+                / (end - start) as f64
+        })
+        .collect::<Vec<_>>();
+    let span = points.iter().cloned().fold(1.0, f64::max);
+
+    points
+        .iter()
+        .map(|point| {
+            let level = (point / span * (SPARKLINE_CHARS.len() - 1) as f64).round();
+            SPARKLINE_CHARS[(level as usize).min(SPARKLINE_CHARS.len() - 1)]
+        })
+        .collect()
+}
+
+/// Lists the capabilities in an effective set, like "net_admin,net_raw".  Having every capability,
+/// as root usually does, is shown as "all".
+pub fn get_capabilities_string(cap_eff: u64) -> String {
+    if cap_eff == 0 {
+        "none".to_string()
+    } else if cap_eff & *ALL_CAPABILITIES == *ALL_CAPABILITIES {
+        "all".to_string()
+    } else {
+        (0..64)
+            .filter(|bit| cap_eff & (1 << bit) != 0)
+            .map(|bit| match CAPABILITY_NAMES.get(bit) {
+                Some(name) => name.to_string(),
+                None => format!("cap_{}", bit),
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Returns a process' average CPU usage over its retained readings, which is what its trend is
+/// sorted by.
+pub fn get_cpu_trend_average(cpu_history: &VecDeque<(Instant, f64)>) -> Option<f64> {
+    if cpu_history.is_empty() {
+        None
+    } else {
+        Some(
+            cpu_history
+                .iter()
+                .map(|(_, cpu_usage)| cpu_usage)
+                .sum::<f64>()
+                / cpu_history.len() as f64,
+        )
+    }
+}
+
+/// Adds a process' latest CPU usage reading to its history, and drops any that are too old to be
+/// part of its trend.
+fn push_cpu_history(
+    cpu_history: &mut VecDeque<(Instant, f64)>, current_instant: Instant, cpu_usage_percent: f64,
+) {
+    // Each process widget converts the same data, so only add a reading the first time.
+    if cpu_history.back().map(|(instant, _)| *instant) != Some(current_instant) {
+        cpu_history.push_back((current_instant, cpu_usage_percent));
+    }
+
+    while let Some((instant, _)) = cpu_history.front() {
+        if current_instant.duration_since(*instant) > CPU_TREND_DURATION {
+            cpu_history.pop_front();
+        } else {
+            break;
+        }
+    }
+}
+
 pub fn convert_cpu_data_points(
     current_data: &data_farmer::DataCollection, existing_cpu_data: &mut Vec<ConvertedCpuData>,
     is_frozen: bool,
@@ -182,6 +473,8 @@ pub fn convert_cpu_data_points(
                 short_cpu_name: "All".to_string(),
                 cpu_data: vec![],
                 legend_value: String::new(),
+                avg_legend_value: String::new(),
+                max_legend_value: String::new(),
             }];
 
             existing_cpu_data.extend(
@@ -208,8 +501,10 @@ pub fn convert_cpu_data_points(
                         } else {
                             String::default()
                         },
-                        legend_value: format!("{:.0}%", cpu_usage.round()),
+                        legend_value: get_cpu_legend_value(*cpu_usage),
                         cpu_data: vec![],
+                        is_offline: cpu_usage.is_nan(),
+                        ..ConvertedCpuData::default()
                     })
                     .collect::<Vec<ConvertedCpuData>>(),
             );
@@ -220,15 +515,28 @@ pub fn convert_cpu_data_points(
                 .zip(&data.cpu_data)
                 .for_each(|(cpu, cpu_usage)| {
                     cpu.cpu_data = vec![];
-                    cpu.legend_value = format!("{:.0}%", cpu_usage.round());
+                    cpu.legend_value = get_cpu_legend_value(*cpu_usage);
+                    cpu.is_offline = cpu_usage.is_nan();
                 });
         }
+
+        existing_cpu_data
+            .iter_mut()
+            .skip(1)
+            .zip(&current_data.cpu_stats)
+            .for_each(|(cpu, cpu_stats)| {
+                cpu.avg_legend_value = format!("{:.0}%", cpu_stats.get_average_usage().round());
+                cpu.max_legend_value = format!("{:.0}%", cpu_stats.max_usage.round());
+            });
     }
 
     for (time, data) in &current_data.timed_data_vec {
         let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
 
         for (itx, cpu) in data.cpu_data.iter().enumerate() {
+            if cpu.is_nan() {
+                continue;
+            }
             if let Some(cpu_data) = existing_cpu_data.get_mut(itx + 1) {
                 cpu_data.cpu_data.push((-time_from_start, *cpu));
             }
@@ -240,6 +548,15 @@ pub fn convert_cpu_data_points(
     }
 }
 
+/// Offline CPUs have no usage, which is stored as NaN.
+fn get_cpu_legend_value(cpu_usage: f64) -> String {
+    if cpu_usage.is_nan() {
+        "Off".to_string()
+    } else {
+        format!("{:.0}%", cpu_usage.round())
+    }
+}
+
 pub fn convert_mem_data_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool,
 ) -> Vec<Point> {
@@ -268,6 +585,178 @@ pub fn convert_mem_data_points(
     result
 }
 
+/// Converts the context switch and interrupt rates into two series, in that order.
+pub fn convert_sched_data_points(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> (Vec<Point>, Vec<Point>) {
+    let mut context_switches: Vec<Point> = Vec::new();
+    let mut interrupts: Vec<Point> = Vec::new();
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    for (time, data) in &current_data.timed_data_vec {
+        if let Some((context_switch_data, interrupt_data)) = data.sched_data {
+            let time_from_start: f64 =
+                (current_time.duration_since(*time).as_millis() as f64).floor();
+            context_switches.push((-time_from_start, context_switch_data));
+            interrupts.push((-time_from_start, interrupt_data));
+            if *time == current_time {
+                break;
+            }
+        }
+    }
+
+    (context_switches, interrupts)
+}
+
+/// Converts the power drawn by the CPU packages and by memory into two series, in that order.
+/// Either is left empty if it isn't reported.
+pub fn convert_power_data_points(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> (Vec<Point>, Vec<Point>) {
+    let mut package_power: Vec<Point> = Vec::new();
+    let mut dram_power: Vec<Point> = Vec::new();
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    for (time, data) in &current_data.timed_data_vec {
+        let (package_data, dram_data) = data.power_data;
+        if package_data.is_some() || dram_data.is_some() {
+            let time_from_start: f64 =
+                (current_time.duration_since(*time).as_millis() as f64).floor();
+            if let Some(package_data) = package_data {
+                package_power.push((-time_from_start, package_data));
+            }
+            if let Some(dram_data) = dram_data {
+                dram_power.push((-time_from_start, dram_data));
+            }
+            if *time == current_time {
+                break;
+            }
+        }
+    }
+
+    (package_power, dram_power)
+}
+
+/// Converts plugin data into one series per name, in the order the names were last reported.
+pub fn convert_plugin_data_points(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> Vec<ConvertedPluginData> {
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    let mut result: Vec<ConvertedPluginData> = current_data
+        .plugin_harvest
+        .iter()
+        .map(|plugin| ConvertedPluginData {
+            name: plugin.name.clone(),
+            value_display: format!("{:.1}", plugin.value),
+            points: Vec::new(),
+        })
+        .collect();
+
+    for (time, data) in &current_data.timed_data_vec {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+
+        for (name, value) in &data.plugin_data {
+            if let Some(series) = result.iter_mut().find(|series| series.name == *name) {
+                series.points.push((-time_from_start, *value));
+            }
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Converts the CPU usage of the processes that are busiest as of the latest data into one series
+/// each, busiest first.  Each only has points for when it was one of the busiest.
+pub fn convert_top_process_cpu_data(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> Vec<ConvertedTopProcessData> {
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    let top_process_cpu_data = match current_data
+        .timed_data_vec
+        .iter()
+        .rev()
+        .find(|(time, _)| *time <= current_time)
+    {
+        Some((_, data)) => &data.top_process_cpu_data,
+        None => return Vec::new(),
+    };
+
+    let mut result: Vec<(Pid, ConvertedTopProcessData)> = top_process_cpu_data
+        .iter()
+        .map(|(pid, value)| {
+            let name = current_data
+                .process_harvest
+                .iter()
+                .find(|process| process.pid == *pid)
+                .map(|process| process.name.to_string())
+                .unwrap_or_else(|| format!("PID {}", pid));
+
+            (
+                *pid,
+                ConvertedTopProcessData {
+                    name,
+                    value_display: format!("{:.1}%", value),
+                    points: Vec::new(),
+                },
+            )
+        })
+        .collect();
+
+    for (time, data) in &current_data.timed_data_vec {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+
+        for (pid, value) in &data.top_process_cpu_data {
+            if let Some((_, series)) = result.iter_mut().find(|(series_pid, _)| series_pid == pid) {
+                series.points.push((-time_from_start, *value));
+            }
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    result.into_iter().map(|(_, series)| series).collect()
+}
+
 pub fn convert_swap_data_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool,
 ) -> Vec<Point> {
@@ -296,26 +785,41 @@ pub fn convert_swap_data_points(
     result
 }
 
-pub fn convert_mem_labels(
-    current_data: &data_farmer::DataCollection,
-) -> (Option<(String, String)>, Option<(String, String)>) {
-    /// Returns the unit type and denominator for given total amount of memory in kibibytes.
-    fn return_unit_and_denominator_for_mem_kib(mem_total_kib: u64) -> (&'static str, f64) {
-        if mem_total_kib < 1024 {
-            // Stay with KiB
-            ("KiB", 1.0)
-        } else if mem_total_kib < MEBI_LIMIT {
-            // Use MiB
-            ("MiB", KIBI_LIMIT_F64)
-        } else if mem_total_kib < GIBI_LIMIT {
-            // Use GiB
-            ("GiB", MEBI_LIMIT_F64)
+/// Returns the unit type and denominator for given total amount of memory in kibibytes.  The
+/// denominator is always in kibibytes, even when using decimal prefixes.
+pub fn return_unit_and_denominator_for_mem_kib(
+    mem_total_kib: u64, use_decimal_prefix: bool,
+) -> (&'static str, f64) {
+    if use_decimal_prefix {
+        let mem_total_bytes = mem_total_kib.saturating_mul(KIBI_LIMIT);
+        if mem_total_bytes < MEGA_LIMIT {
+            ("kB", KILO_LIMIT_F64 / KIBI_LIMIT_F64)
+        } else if mem_total_bytes < GIGA_LIMIT {
+            ("MB", MEGA_LIMIT_F64 / KIBI_LIMIT_F64)
+        } else if mem_total_bytes < TERA_LIMIT {
+            ("GB", GIGA_LIMIT_F64 / KIBI_LIMIT_F64)
         } else {
-            // Use TiB
-            ("TiB", GIBI_LIMIT_F64)
+            ("TB", TERA_LIMIT_F64 / KIBI_LIMIT_F64)
         }
+    } else if mem_total_kib < 1024 {
+        // Stay with KiB
+        ("KiB", 1.0)
+    } else if mem_total_kib < MEBI_LIMIT {
+        // Use MiB
+        ("MiB", KIBI_LIMIT_F64)
+    } else if mem_total_kib < GIBI_LIMIT {
+        // Use GiB
+        ("GiB", MEBI_LIMIT_F64)
+    } else {
+        // Use TiB
+        ("TiB", GIBI_LIMIT_F64)
     }
+}
 
+pub fn convert_mem_labels(
+    current_data: &data_farmer::DataCollection, use_decimal_prefix: bool,
+    number_format: &NumberFormat,
+) -> (Option<(String, String)>, Option<(String, String)>) {
     (
         if current_data.memory_harvest.mem_total_in_kib > 0 {
             Some((
@@ -326,13 +830,20 @@ pub fn convert_mem_labels(
                 {
                     let (unit, denominator) = return_unit_and_denominator_for_mem_kib(
                         current_data.memory_harvest.mem_total_in_kib,
+                        use_decimal_prefix,
                     );
 
                     format!(
-                        "   {:.1}{}/{:.1}{}",
-                        current_data.memory_harvest.mem_used_in_kib as f64 / denominator,
+                        "   {}{}/{}{}",
+                        number_format.format_number(
+                            current_data.memory_harvest.mem_used_in_kib as f64 / denominator,
+                            1
+                        ),
                         unit,
-                        (current_data.memory_harvest.mem_total_in_kib as f64 / denominator),
+                        number_format.format_number(
+                            current_data.memory_harvest.mem_total_in_kib as f64 / denominator,
+                            1
+                        ),
                         unit
                     )
                 },
@@ -349,13 +860,20 @@ pub fn convert_mem_labels(
                 {
                     let (unit, denominator) = return_unit_and_denominator_for_mem_kib(
                         current_data.swap_harvest.mem_total_in_kib,
+                        use_decimal_prefix,
                     );
 
                     format!(
-                        "   {:.1}{}/{:.1}{}",
-                        current_data.swap_harvest.mem_used_in_kib as f64 / denominator,
+                        "   {}{}/{}{}",
+                        number_format.format_number(
+                            current_data.swap_harvest.mem_used_in_kib as f64 / denominator,
+                            1
+                        ),
                         unit,
-                        (current_data.swap_harvest.mem_total_in_kib as f64 / denominator),
+                        number_format.format_number(
+                            current_data.swap_harvest.mem_total_in_kib as f64 / denominator,
+                            1
+                        ),
                         unit
                     )
                 },
@@ -366,6 +884,61 @@ pub fn convert_mem_labels(
     )
 }
 
+/// Converts the usage of each NUMA node into its own series, along with its labels.
+pub fn convert_numa_data(
+    current_data: &data_farmer::DataCollection, is_frozen: bool, use_decimal_prefix: bool,
+    number_format: &NumberFormat,
+) -> Vec<ConvertedNumaData> {
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    let mut result: Vec<ConvertedNumaData> = current_data
+        .numa_harvest
+        .iter()
+        .map(|numa| {
+            let (unit, denominator) =
+                return_unit_and_denominator_for_mem_kib(numa.mem_total_in_kib, use_decimal_prefix);
+
+            ConvertedNumaData {
+                node: numa.node,
+                label_percent: format!("{:3.0}%", numa.use_percent.unwrap_or(0.0)),
+                label_frac: format!(
+                    "   {}{}/{}{}   {} free pages",
+                    number_format.format_number(numa.mem_used_in_kib as f64 / denominator, 1),
+                    unit,
+                    number_format.format_number(numa.mem_total_in_kib as f64 / denominator, 1),
+                    unit,
+                    number_format.format_prefixed(numa.free_pages, "", UnitPrefix::Si, 1)
+                ),
+                points: Vec::new(),
+            }
+        })
+        .collect();
+
+    for (time, data) in &current_data.timed_data_vec {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+
+        for (node, value) in &data.numa_data {
+            if let Some(series) = result.iter_mut().find(|series| series.node == *node) {
+                series.points.push((-time_from_start, *value));
+            }
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    result
+}
+
 pub fn get_rx_tx_data_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool, network_scale_type: &AxisScaling,
     network_unit_type: &DataUnit, network_use_binary_prefix: bool,
@@ -424,7 +997,7 @@ pub fn get_rx_tx_data_points(
 pub fn convert_network_data_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool, need_four_points: bool,
     network_scale_type: &AxisScaling, network_unit_type: &DataUnit,
-    network_use_binary_prefix: bool,
+    network_use_binary_prefix: bool, number_format: &NumberFormat,
 ) -> ConvertedNetworkData {
     let (rx, tx) = get_rx_tx_data_points(
         current_data,
@@ -481,15 +1054,25 @@ pub fn convert_network_data_points(
         };
 
     if need_four_points {
-        let rx_display = format!("{:.*}{}", 1, rx_converted_result.0, rx_converted_result.1);
+        let rx_display = format!(
+            "{}{}",
+            number_format.format_number(rx_converted_result.0, 1),
+            rx_converted_result.1
+        );
         let total_rx_display = Some(format!(
-            "{:.*}{}",
-            1, total_rx_converted_result.0, total_rx_converted_result.1
+            "{}{}",
+            number_format.format_number(total_rx_converted_result.0, 1),
+            total_rx_converted_result.1
         ));
-        let tx_display = format!("{:.*}{}", 1, tx_converted_result.0, tx_converted_result.1);
+        let tx_display = format!(
+            "{}{}",
+            number_format.format_number(tx_converted_result.0, 1),
+            tx_converted_result.1
+        );
         let total_tx_display = Some(format!(
-            "{:.*}{}",
-            1, total_tx_converted_result.0, total_tx_converted_result.1
+            "{}{}",
+            number_format.format_number(total_tx_converted_result.0, 1),
+            total_tx_converted_result.1
         ));
         ConvertedNetworkData {
             rx,
@@ -503,38 +1086,58 @@ pub fn convert_network_data_points(
         let rx_display = format!(
             "RX: {:<10}  All: {}",
             if network_use_binary_prefix {
-                format!("{:.1}{:3}", rx_converted_result.0, rx_converted_result.1)
+                format!(
+                    "{}{:3}",
+                    number_format.format_number(rx_converted_result.0, 1),
+                    rx_converted_result.1
+                )
             } else {
-                format!("{:.1}{:2}", rx_converted_result.0, rx_converted_result.1)
+                format!(
+                    "{}{:2}",
+                    number_format.format_number(rx_converted_result.0, 1),
+                    rx_converted_result.1
+                )
             },
             if network_use_binary_prefix {
                 format!(
-                    "{:.1}{:3}",
-                    total_rx_converted_result.0, total_rx_converted_result.1
+                    "{}{:3}",
+                    number_format.format_number(total_rx_converted_result.0, 1),
+                    total_rx_converted_result.1
                 )
             } else {
                 format!(
-                    "{:.1}{:2}",
-                    total_rx_converted_result.0, total_rx_converted_result.1
+                    "{}{:2}",
+                    number_format.format_number(total_rx_converted_result.0, 1),
+                    total_rx_converted_result.1
                 )
             }
         );
         let tx_display = format!(
             "TX: {:<10}  All: {}",
             if network_use_binary_prefix {
-                format!("{:.1}{:3}", tx_converted_result.0, tx_converted_result.1)
+                format!(
+                    "{}{:3}",
+                    number_format.format_number(tx_converted_result.0, 1),
+                    tx_converted_result.1
+                )
             } else {
-                format!("{:.1}{:2}", tx_converted_result.0, tx_converted_result.1)
+                format!(
+                    "{}{:2}",
+                    number_format.format_number(tx_converted_result.0, 1),
+                    tx_converted_result.1
+                )
             },
             if network_use_binary_prefix {
                 format!(
-                    "{:.1}{:3}",
-                    total_tx_converted_result.0, total_tx_converted_result.1
+                    "{}{:3}",
+                    number_format.format_number(total_tx_converted_result.0, 1),
+                    total_tx_converted_result.1
                 )
             } else {
                 format!(
-                    "{:.1}{:2}",
-                    total_tx_converted_result.0, total_tx_converted_result.1
+                    "{}{:2}",
+                    number_format.format_number(total_tx_converted_result.0, 1),
+                    total_tx_converted_result.1
                 )
             }
         );
@@ -550,6 +1153,40 @@ pub fn convert_network_data_points(
     }
 }
 
+/// Summarizes the wireless interfaces for the network widget, e.g. "wlan0: home -52 dBm 866.7 Mbit/s 5.2 GHz".
+pub fn convert_wireless_data(
+    current_data: &data_farmer::DataCollection, number_format: &NumberFormat,
+) -> String {
+    current_data
+        .wireless_harvest
+        .iter()
+        .map(|wireless| {
+            if let Some(ssid) = &wireless.ssid {
+                let mut summary = format!("{}: {}", wireless.interface, ssid);
+                if let Some(signal_dbm) = wireless.signal_dbm {
+                    summary.push_str(&format!(" {:.0} dBm", signal_dbm));
+                }
+                if let Some(bitrate_mbps) = wireless.tx_bitrate_mbps.or(wireless.rx_bitrate_mbps) {
+                    summary.push_str(&format!(
+                        " {} Mbit/s",
+                        number_format.format_number(bitrate_mbps, 1)
+                    ));
+                }
+                if let Some(frequency_mhz) = wireless.frequency_mhz {
+                    summary.push_str(&format!(" {:.1} GHz", frequency_mhz as f64 / 1000.0));
+                }
+                summary
+            } else if let Some(signal_dbm) = wireless.signal_dbm {
+                // Without `iw`, all we might know is the signal strength.
+                format!("{}: {:.0} dBm", wireless.interface, signal_dbm)
+            } else {
+                format!("{}: not connected", wireless.interface)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 pub enum ProcessGroupingType {
     Grouped,
     Ungrouped,
@@ -562,50 +1199,80 @@ pub enum ProcessNamingType {
 
 /// Given read/s, write/s, total read, and total write values, return 4 strings that represent read/s, write/s, total read, and total write
 fn get_disk_io_strings(
-    rps: u64, wps: u64, total_read: u64, total_write: u64,
+    rps: u64, wps: u64, total_read: u64, total_write: u64, number_format: &NumberFormat,
 ) -> (String, String, String, String) {
     // Note we always use bytes for total read/write here (for now).
-    let converted_rps = get_decimal_bytes(rps);
-    let converted_wps = get_decimal_bytes(wps);
-    let converted_total_read = get_decimal_bytes(total_read);
-    let converted_total_write = get_decimal_bytes(total_write);
+    let get_decimal_places = |bytes: u64| if bytes >= GIGA_LIMIT { 1 } else { 0 };
 
     (
-        if rps >= GIGA_LIMIT {
-            format!("{:.*}{}/s", 1, converted_rps.0, converted_rps.1)
-        } else {
-            format!("{:.*}{}/s", 0, converted_rps.0, converted_rps.1)
-        },
-        if wps >= GIGA_LIMIT {
-            format!("{:.*}{}/s", 1, converted_wps.0, converted_wps.1)
-        } else {
-            format!("{:.*}{}/s", 0, converted_wps.0, converted_wps.1)
-        },
-        if total_read >= GIGA_LIMIT {
-            format!("{:.*}{}", 1, converted_total_read.0, converted_total_read.1)
-        } else {
-            format!("{:.*}{}", 0, converted_total_read.0, converted_total_read.1)
-        },
-        if total_write >= GIGA_LIMIT {
-            format!(
-                "{:.*}{}",
-                1, converted_total_write.0, converted_total_write.1
-            )
-        } else {
-            format!(
-                "{:.*}{}",
-                0, converted_total_write.0, converted_total_write.1
-            )
-        },
+        number_format.format_prefixed(rps, "B/s", UnitPrefix::Si, get_decimal_places(rps)),
+        number_format.format_prefixed(wps, "B/s", UnitPrefix::Si, get_decimal_places(wps)),
+        number_format.format_bytes(total_read, UnitPrefix::Si, get_decimal_places(total_read)),
+        number_format.format_bytes(total_write, UnitPrefix::Si, get_decimal_places(total_write)),
     )
 }
 
+/// Returns a string for how much memory a process uses.
+fn get_mem_usage_string(
+    mem_usage_bytes: u64, default_prefix: UnitPrefix, number_format: &NumberFormat,
+) -> String {
+    let decimal_places = if mem_usage_bytes <= GIBI_LIMIT { 0 } else { 1 };
+    number_format.format_bytes(mem_usage_bytes, default_prefix, decimal_places)
+}
+
+/// A count of processes by state, shown in the process widget's title.
+#[derive(Default, Debug, Clone)]
+pub struct ProcessStateSummary {
+    pub total: usize,
+    pub running: usize,
+    pub sleeping: usize,
+    pub stopped: usize,
+    pub zombie: usize,
+}
+
+impl std::fmt::Display for ProcessStateSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} total, {} running, {} sleeping",
+            self.total, self.running, self.sleeping
+        )?;
+        if self.stopped > 0 {
+            write!(f, ", {} stopped", self.stopped)?;
+        }
+        if self.zombie > 0 {
+            write!(f, ", {} zombie", self.zombie)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn convert_process_summary(current_data: &data_farmer::DataCollection) -> ProcessStateSummary {
+    let mut summary = ProcessStateSummary {
+        total: current_data.process_harvest.len(),
+        ..ProcessStateSummary::default()
+    };
+
+    for process in &current_data.process_harvest {
+        match process.process_state_char {
+            'R' => summary.running += 1,
+            'S' | 'D' | 'I' => summary.sleeping += 1,
+            'T' | 't' => summary.stopped += 1,
+            'Z' => summary.zombie += 1,
+            _ => {}
+        }
+    }
+
+    summary
+}
+
 /// Because we needed to UPDATE data entries rather than REPLACING entries, we instead update
 /// the existing vector.
 pub fn convert_process_data(
     current_data: &data_farmer::DataCollection,
     existing_converted_process_data: &mut HashMap<Pid, ConvertedProcessData>,
     #[cfg(target_family = "unix")] user_table: &mut data_harvester::processes::UserTable,
+    number_format: &NumberFormat,
 ) {
     // TODO [THREAD]: Thread highlighting and hiding support
     // For macOS see https://github.com/hishamhm/htop/pull/848/files
@@ -613,15 +1280,29 @@ pub fn convert_process_data(
     let mut complete_pid_set: fxhash::FxHashSet<Pid> =
         existing_converted_process_data.keys().copied().collect();
 
+    let current_time_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
     for process in &current_data.process_harvest {
         let (read_per_sec, write_per_sec, total_read, total_write) = get_disk_io_strings(
             process.read_bytes_per_sec,
             process.write_bytes_per_sec,
             process.total_read_bytes,
             process.total_write_bytes,
+            number_format,
         );
 
-        let mem_usage_str = get_binary_bytes(process.mem_usage_bytes);
+        let mem_usage_str =
+            get_mem_usage_string(process.mem_usage_bytes, UnitPrefix::Iec, number_format);
+        let runtime_secs = process
+            .start_time
+            .map(|start_time| current_time_secs.saturating_sub(start_time));
+        let exe_path = process
+            .exe_path
+            .clone()
+            .unwrap_or_else(|| process.name.clone());
 
         let user = {
             #[cfg(target_family = "unix")]
@@ -643,12 +1324,17 @@ pub fn convert_process_data(
 
             // Very dumb way to see if there's PID reuse...
             if process_entry.ppid == process.parent_pid {
-                process_entry.name = process.name.to_string();
-                process_entry.command = process.command.to_string();
+                process_entry.name = process.name.clone();
+                process_entry.command = process.command.clone();
+                process_entry.exe_path = exe_path;
                 process_entry.cpu_percent_usage = process.cpu_usage_percent;
+                process_entry.cpu_time_delta_ms = process.cpu_time_delta_ms;
+                process_entry.cap_eff = process.cap_eff;
+                process_entry.seccomp_mode = process.seccomp_mode;
                 process_entry.mem_percent_usage = process.mem_usage_percent;
                 process_entry.mem_usage_bytes = process.mem_usage_bytes;
                 process_entry.mem_usage_str = mem_usage_str;
+                process_entry.virt_mem_bytes = process.virt_mem_bytes;
                 process_entry.group_pids = vec![process.pid];
                 process_entry.read_per_sec = read_per_sec;
                 process_entry.write_per_sec = write_per_sec;
@@ -663,18 +1349,39 @@ pub fn convert_process_data(
                 process_entry.process_description_prefix = None;
                 process_entry.is_disabled_entry = false;
                 process_entry.user = user;
+                process_entry.gpu_util_percent = process.gpu_util_percent;
+                process_entry.gpu_mem_bytes = process.gpu_mem_bytes;
+                process_entry.swap_bytes = process.swap_bytes;
+                process_entry.tcp_retransmits = process.tcp_retransmits;
+                process_entry.tcp_rtt_us = process.tcp_rtt_us;
+                process_entry.pod_name = process.pod_name.clone();
+                process_entry.pod_namespace = process.pod_namespace.clone();
+                process_entry.start_time = process.start_time;
+                process_entry.runtime_secs = runtime_secs;
+                process_entry.fd_count = process.fd_count;
+                push_cpu_history(
+                    &mut process_entry.cpu_history,
+                    current_data.current_instant,
+                    process.cpu_usage_percent,
+                );
+                process_entry.is_io_restricted = process.is_io_restricted;
             } else {
                 // ...I hate that I can't combine if let and an if statement in one line...
                 *process_entry = ConvertedProcessData {
                     pid: process.pid,
                     ppid: process.parent_pid,
                     is_thread: None,
-                    name: process.name.to_string(),
-                    command: process.command.to_string(),
+                    name: process.name.clone(),
+                    command: process.command.clone(),
+                    exe_path,
                     cpu_percent_usage: process.cpu_usage_percent,
+                    cpu_time_delta_ms: process.cpu_time_delta_ms,
+                    cap_eff: process.cap_eff,
+                    seccomp_mode: process.seccomp_mode,
                     mem_percent_usage: process.mem_usage_percent,
                     mem_usage_bytes: process.mem_usage_bytes,
                     mem_usage_str,
+                    virt_mem_bytes: process.virt_mem_bytes,
                     group_pids: vec![process.pid],
                     read_per_sec,
                     write_per_sec,
@@ -690,6 +1397,21 @@ pub fn convert_process_data(
                     is_disabled_entry: false,
                     is_collapsed_entry: false,
                     user,
+                    gpu_util_percent: process.gpu_util_percent,
+                    gpu_mem_bytes: process.gpu_mem_bytes,
+                    swap_bytes: process.swap_bytes,
+                    tcp_retransmits: process.tcp_retransmits,
+                    tcp_rtt_us: process.tcp_rtt_us,
+                    pod_name: process.pod_name.clone(),
+                    pod_namespace: process.pod_namespace.clone(),
+                    start_time: process.start_time,
+                    runtime_secs,
+                    fd_count: process.fd_count,
+                    cpu_history: VecDeque::from(vec![(
+                        current_data.current_instant,
+                        process.cpu_usage_percent,
+                    )]),
+                    is_io_restricted: process.is_io_restricted,
                 };
             }
         } else {
@@ -699,12 +1421,17 @@ pub fn convert_process_data(
                     pid: process.pid,
                     ppid: process.parent_pid,
                     is_thread: None,
-                    name: process.name.to_string(),
-                    command: process.command.to_string(),
+                    name: process.name.clone(),
+                    command: process.command.clone(),
+                    exe_path,
                     cpu_percent_usage: process.cpu_usage_percent,
+                    cpu_time_delta_ms: process.cpu_time_delta_ms,
+                    cap_eff: process.cap_eff,
+                    seccomp_mode: process.seccomp_mode,
                     mem_percent_usage: process.mem_usage_percent,
                     mem_usage_bytes: process.mem_usage_bytes,
                     mem_usage_str,
+                    virt_mem_bytes: process.virt_mem_bytes,
                     group_pids: vec![process.pid],
                     read_per_sec,
                     write_per_sec,
@@ -720,6 +1447,21 @@ pub fn convert_process_data(
                     is_disabled_entry: false,
                     is_collapsed_entry: false,
                     user,
+                    gpu_util_percent: process.gpu_util_percent,
+                    gpu_mem_bytes: process.gpu_mem_bytes,
+                    swap_bytes: process.swap_bytes,
+                    tcp_retransmits: process.tcp_retransmits,
+                    tcp_rtt_us: process.tcp_rtt_us,
+                    pod_name: process.pod_name.clone(),
+                    pod_namespace: process.pod_namespace.clone(),
+                    start_time: process.start_time,
+                    runtime_secs,
+                    fd_count: process.fd_count,
+                    cpu_history: VecDeque::from(vec![(
+                        current_data.current_instant,
+                        process.cpu_usage_percent,
+                    )]),
+                    is_io_restricted: process.is_io_restricted,
                 },
             );
         }
@@ -737,8 +1479,8 @@ const BRANCH_SPLIT: char = '├';
 const BRANCH_HORIZONTAL: char = '─';
 
 pub fn tree_process_data(
-    filtered_process_data: &[ConvertedProcessData], is_using_command: bool,
-    sorting_type: &ProcessSorting, is_sort_descending: bool,
+    filtered_process_data: &[ConvertedProcessData], name_type: ProcessNameType,
+    sorting_type: &ProcessSorting, is_sort_descending: bool, number_format: &NumberFormat,
 ) -> Vec<ConvertedProcessData> {
     // TODO: [TREE] Option to sort usage by total branch usage or individual value usage?
 
@@ -907,75 +1649,219 @@ pub fn tree_process_data(
                     )
                 });
             }
-            ProcessSorting::MemPercent => {
+            ProcessSorting::VirtMem => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.virt_mem_bytes,
+                        b.1.virt_mem_bytes,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::MemPercent => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.mem_percent_usage,
+                        b.1.mem_percent_usage,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::ProcessName => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        &a.1.name.to_lowercase(),
+                        &b.1.name.to_lowercase(),
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::Command => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    &a.1.command.to_lowercase(),
+                    &b.1.command.to_lowercase(),
+                    is_sort_descending,
+                )
+            }),
+            ProcessSorting::ExePath => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    &a.1.exe_path.to_lowercase(),
+                    &b.1.exe_path.to_lowercase(),
+                    is_sort_descending,
+                )
+            }),
+            ProcessSorting::Pid => {
+                if is_sort_descending {
+                    to_sort_vec.sort_by(|a, b| {
+                        utils::gen_util::get_ordering(a.0, b.0, is_sort_descending)
+                    });
+                }
+            }
+            ProcessSorting::ReadPerSecond => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(a.1.rps_f64, b.1.rps_f64, is_sort_descending)
+                });
+            }
+            ProcessSorting::WritePerSecond => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(a.1.wps_f64, b.1.wps_f64, is_sort_descending)
+                });
+            }
+            ProcessSorting::TotalRead => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(a.1.tr_f64, b.1.tr_f64, is_sort_descending)
+                });
+            }
+            ProcessSorting::TotalWrite => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(a.1.tw_f64, b.1.tw_f64, is_sort_descending)
+                });
+            }
+            ProcessSorting::State => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    &a.1.process_state.to_lowercase(),
+                    &b.1.process_state.to_lowercase(),
+                    is_sort_descending,
+                )
+            }),
+            ProcessSorting::User => to_sort_vec.sort_by(|a, b| match (&a.1.user, &b.1.user) {
+                (Some(user_a), Some(user_b)) => utils::gen_util::get_ordering(
+                    user_a.to_lowercase(),
+                    user_b.to_lowercase(),
+                    is_sort_descending,
+                ),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Less,
+            }),
+            ProcessSorting::GpuPercent => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.gpu_util_percent,
+                        b.1.gpu_util_percent,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::GpuMem => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.gpu_mem_bytes,
+                        b.1.gpu_mem_bytes,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::Swap => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.swap_bytes,
+                        b.1.swap_bytes,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::TcpRetransmits => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.tcp_retransmits,
+                        b.1.tcp_retransmits,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::TcpRtt => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.tcp_rtt_us,
+                        b.1.tcp_rtt_us,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::Pod => {
+                to_sort_vec.sort_by(|a, b| match (&a.1.pod_name, &b.1.pod_name) {
+                    (Some(pod_a), Some(pod_b)) => utils::gen_util::get_ordering(
+                        pod_a.to_lowercase(),
+                        pod_b.to_lowercase(),
+                        is_sort_descending,
+                    ),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                })
+            }
+            ProcessSorting::Namespace => {
+                to_sort_vec.sort_by(|a, b| match (&a.1.pod_namespace, &b.1.pod_namespace) {
+                    (Some(namespace_a), Some(namespace_b)) => utils::gen_util::get_ordering(
+                        namespace_a.to_lowercase(),
+                        namespace_b.to_lowercase(),
+                        is_sort_descending,
+                    ),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                })
+            }
+            ProcessSorting::StartTime => {
                 to_sort_vec.sort_by(|a, b| {
                     utils::gen_util::get_ordering(
-                        a.1.mem_percent_usage,
-                        b.1.mem_percent_usage,
+                        a.1.start_time,
+                        b.1.start_time,
                         is_sort_descending,
                     )
                 });
             }
-            ProcessSorting::ProcessName => {
+            ProcessSorting::Runtime => {
                 to_sort_vec.sort_by(|a, b| {
                     utils::gen_util::get_ordering(
-                        &a.1.name.to_lowercase(),
-                        &b.1.name.to_lowercase(),
+                        a.1.runtime_secs,
+                        b.1.runtime_secs,
                         is_sort_descending,
                     )
                 });
             }
-            ProcessSorting::Command => to_sort_vec.sort_by(|a, b| {
-                utils::gen_util::get_ordering(
-                    &a.1.command.to_lowercase(),
-                    &b.1.command.to_lowercase(),
-                    is_sort_descending,
-                )
-            }),
-            ProcessSorting::Pid => {
-                if is_sort_descending {
-                    to_sort_vec.sort_by(|a, b| {
-                        utils::gen_util::get_ordering(a.0, b.0, is_sort_descending)
-                    });
-                }
+            ProcessSorting::FdCount => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(a.1.fd_count, b.1.fd_count, is_sort_descending)
+                });
             }
-            ProcessSorting::ReadPerSecond => {
+            ProcessSorting::CpuTrend => {
                 to_sort_vec.sort_by(|a, b| {
-                    utils::gen_util::get_ordering(a.1.rps_f64, b.1.rps_f64, is_sort_descending)
+                    utils::gen_util::get_ordering(
+                        get_cpu_trend_average(&a.1.cpu_history),
+                        get_cpu_trend_average(&b.1.cpu_history),
+                        is_sort_descending,
+                    )
                 });
             }
-            ProcessSorting::WritePerSecond => {
+            ProcessSorting::CpuTime => {
                 to_sort_vec.sort_by(|a, b| {
-                    utils::gen_util::get_ordering(a.1.wps_f64, b.1.wps_f64, is_sort_descending)
+                    utils::gen_util::get_ordering(
+                        a.1.cpu_time_delta_ms,
+                        b.1.cpu_time_delta_ms,
+                        is_sort_descending,
+                    )
                 });
             }
-            ProcessSorting::TotalRead => {
+            ProcessSorting::Capabilities => {
                 to_sort_vec.sort_by(|a, b| {
-                    utils::gen_util::get_ordering(a.1.tr_f64, b.1.tr_f64, is_sort_descending)
+                    utils::gen_util::get_ordering(
+                        a.1.cap_eff.map(u64::count_ones),
+                        b.1.cap_eff.map(u64::count_ones),
+                        is_sort_descending,
+                    )
                 });
             }
-            ProcessSorting::TotalWrite => {
+            ProcessSorting::Seccomp => {
                 to_sort_vec.sort_by(|a, b| {
-                    utils::gen_util::get_ordering(a.1.tw_f64, b.1.tw_f64, is_sort_descending)
+                    utils::gen_util::get_ordering(
+                        a.1.seccomp_mode,
+                        b.1.seccomp_mode,
+                        is_sort_descending,
+                    )
                 });
             }
-            ProcessSorting::State => to_sort_vec.sort_by(|a, b| {
-                utils::gen_util::get_ordering(
-                    &a.1.process_state.to_lowercase(),
-                    &b.1.process_state.to_lowercase(),
-                    is_sort_descending,
-                )
-            }),
-            ProcessSorting::User => to_sort_vec.sort_by(|a, b| match (&a.1.user, &b.1.user) {
-                (Some(user_a), Some(user_b)) => utils::gen_util::get_ordering(
-                    user_a.to_lowercase(),
-                    user_b.to_lowercase(),
-                    is_sort_descending,
-                ),
-                (Some(_), None) => std::cmp::Ordering::Less,
-                (None, Some(_)) => std::cmp::Ordering::Greater,
-                (None, None) => std::cmp::Ordering::Less,
-            }),
             ProcessSorting::Count => {
                 // Should never occur in this case, tree mode explicitly disables grouping.
             }
@@ -1133,11 +2019,7 @@ pub fn tree_process_data(
                     "{}{}{}",
                     prefix,
                     if p.is_collapsed_entry { "+ " } else { "" }, // I do the + sign thing here because I'm kinda too lazy to do it in the prefix, tbh.
-                    if is_using_command {
-                        &p.command
-                    } else {
-                        &p.name
-                    }
+                    p.get_name(name_type)
                 ));
 
                 // As part of https://github.com/ClementTsang/bottom/issues/424, also append their statistics to the parent if
@@ -1185,9 +2067,11 @@ pub fn tree_process_data(
                             p.wps_f64 as u64,
                             p.tr_f64 as u64,
                             p.tw_f64 as u64,
+                            number_format,
                         );
 
-                        p.mem_usage_str = get_binary_bytes(p.mem_usage_bytes);
+                        p.mem_usage_str =
+                            get_mem_usage_string(p.mem_usage_bytes, UnitPrefix::Iec, number_format);
 
                         p.read_per_sec = disk_io_strings.0;
                         p.write_per_sec = disk_io_strings.1;
@@ -1206,58 +2090,65 @@ pub fn tree_process_data(
 // FIXME: [OPT] This is an easy target for optimization, too many to_strings!
 pub fn stringify_process_data(
     proc_widget_state: &ProcWidgetState, finalized_process_data: &[ConvertedProcessData],
+    number_format: &NumberFormat,
 ) -> Vec<(Vec<(String, Option<String>)>, bool)> {
-    let is_proc_widget_grouped = proc_widget_state.is_grouped;
-    let is_using_command = proc_widget_state.is_using_command;
     let is_tree = proc_widget_state.is_tree_mode;
-    let mem_enabled = proc_widget_state.columns.is_enabled(&ProcessSorting::Mem);
+    let shown_columns = proc_widget_state.columns.get_shown_columns();
 
     finalized_process_data
         .iter()
         .map(|process| {
-            (
-                vec![
-                    (
-                        if is_proc_widget_grouped {
-                            process.group_pids.len().to_string()
-                        } else {
-                            process.pid.to_string()
-                        },
-                        None,
-                    ),
-                    (
+            let row: Vec<(String, Option<String>)> = shown_columns
+                .iter()
+                .map(|column| match column {
+                    ProcessSorting::Count => (process.group_pids.len().to_string(), None),
+                    ProcessSorting::Pid => (process.pid.to_string(), None),
+                    ProcessSorting::ProcessName
+                    | ProcessSorting::Command
+                    | ProcessSorting::ExePath => (
                         if is_tree {
                             if let Some(prefix) = &process.process_description_prefix {
                                 prefix.clone()
                             } else {
                                 String::default()
                             }
-                        } else if is_using_command {
-                            process.command.clone()
                         } else {
-                            process.name.clone()
+                            match column {
+                                ProcessSorting::Command => process.command.to_string(),
+                                ProcessSorting::ExePath => process.exe_path.to_string(),
+                                _ => process.name.to_string(),
+                            }
                         },
                         None,
                     ),
-                    (format!("{:.1}%", process.cpu_percent_usage), None),
-                    (
-                        if mem_enabled {
-                            if process.mem_usage_bytes <= GIBI_LIMIT {
-                                format!("{:.0}{}", process.mem_usage_str.0, process.mem_usage_str.1)
-                            } else {
-                                format!("{:.1}{}", process.mem_usage_str.0, process.mem_usage_str.1)
-                            }
-                        } else {
-                            format!("{:.1}%", process.mem_percent_usage)
-                        },
+                    ProcessSorting::CpuPercent => {
+                        (format!("{:.1}%", process.cpu_percent_usage), None)
+                    }
+                    ProcessSorting::Mem => (process.mem_usage_str.clone(), None),
+                    ProcessSorting::MemPercent => {
+                        (format!("{:.1}%", process.mem_percent_usage), None)
+                    }
+                    ProcessSorting::VirtMem => (
+                        get_mem_usage_string(
+                            process.virt_mem_bytes,
+                            UnitPrefix::Iec,
+                            number_format,
+                        ),
                         None,
                     ),
-                    (process.read_per_sec.clone(), None),
-                    (process.write_per_sec.clone(), None),
-                    (process.total_read.clone(), None),
-                    (process.total_write.clone(), None),
-                    #[cfg(target_family = "unix")]
-                    (
+                    ProcessSorting::ReadPerSecond
+                    | ProcessSorting::WritePerSecond
+                    | ProcessSorting::TotalRead
+                    | ProcessSorting::TotalWrite
+                        if process.is_io_restricted =>
+                    {
+                        ("N/A".to_string(), None)
+                    }
+                    ProcessSorting::ReadPerSecond => (process.read_per_sec.clone(), None),
+                    ProcessSorting::WritePerSecond => (process.write_per_sec.clone(), None),
+                    ProcessSorting::TotalRead => (process.total_read.clone(), None),
+                    ProcessSorting::TotalWrite => (process.total_write.clone(), None),
+                    ProcessSorting::User => (
                         if let Some(user) = &process.user {
                             user.clone()
                         } else {
@@ -1265,83 +2156,289 @@ pub fn stringify_process_data(
                         },
                         None,
                     ),
-                    (
+                    ProcessSorting::State => (
                         process.process_state.clone(),
                         Some(process.process_char.to_string()),
                     ),
-                ],
-                process.is_disabled_entry,
-            )
+                    ProcessSorting::GpuPercent => (
+                        if let Some(gpu_util_percent) = process.gpu_util_percent {
+                            format!("{:.1}%", gpu_util_percent)
+                        } else {
+                            "-".to_string()
+                        },
+                        None,
+                    ),
+                    ProcessSorting::GpuMem => (
+                        if let Some(gpu_mem_bytes) = process.gpu_mem_bytes {
+                            number_format.format_bytes(gpu_mem_bytes, UnitPrefix::Iec, 0)
+                        } else {
+                            "-".to_string()
+                        },
+                        None,
+                    ),
+                    ProcessSorting::Swap => (
+                        if let Some(swap_bytes) = process.swap_bytes {
+                            number_format.format_bytes(swap_bytes, UnitPrefix::Iec, 0)
+                        } else {
+                            "-".to_string()
+                        },
+                        None,
+                    ),
+                    ProcessSorting::TcpRetransmits => (
+                        if let Some(tcp_retransmits) = process.tcp_retransmits {
+                            tcp_retransmits.to_string()
+                        } else {
+                            "-".to_string()
+                        },
+                        None,
+                    ),
+                    ProcessSorting::TcpRtt => (
+                        match process.tcp_rtt_us {
+                            Some(tcp_rtt_us) if tcp_rtt_us >= 1000.0 => {
+                                format!("{:.1}ms", tcp_rtt_us / 1000.0)
+                            }
+                            Some(tcp_rtt_us) => format!("{:.0}us", tcp_rtt_us),
+                            None => "-".to_string(),
+                        },
+                        None,
+                    ),
+                    ProcessSorting::Pod => (
+                        process.pod_name.clone().unwrap_or_else(|| "-".to_string()),
+                        None,
+                    ),
+                    ProcessSorting::Namespace => (
+                        process
+                            .pod_namespace
+                            .clone()
+                            .unwrap_or_else(|| "-".to_string()),
+                        None,
+                    ),
+                    ProcessSorting::StartTime => (
+                        if let Some(start_time) = process.start_time {
+                            get_start_time_string(start_time)
+                        } else {
+                            "-".to_string()
+                        },
+                        None,
+                    ),
+                    ProcessSorting::Runtime => (
+                        if let Some(runtime_secs) = process.runtime_secs {
+                            get_runtime_string(runtime_secs)
+                        } else {
+                            "-".to_string()
+                        },
+                        None,
+                    ),
+                    ProcessSorting::FdCount => (
+                        if let Some(fd_count) = process.fd_count {
+                            fd_count.to_string()
+                        } else {
+                            "-".to_string()
+                        },
+                        None,
+                    ),
+                    ProcessSorting::CpuTrend => (get_cpu_trend_string(&process.cpu_history), None),
+                    ProcessSorting::CpuTime => (
+                        if let Some(cpu_time_delta_ms) = process.cpu_time_delta_ms {
+                            cpu_time_delta_ms.to_string()
+                        } else {
+                            "-".to_string()
+                        },
+                        None,
+                    ),
+                    ProcessSorting::Capabilities => (
+                        if let Some(cap_eff) = process.cap_eff {
+                            get_capabilities_string(cap_eff)
+                        } else {
+                            "-".to_string()
+                        },
+                        None,
+                    ),
+                    ProcessSorting::Seccomp => (
+                        match process.seccomp_mode {
+                            Some(0) => "off".to_string(),
+                            Some(1) => "strict".to_string(),
+                            Some(2) => "filter".to_string(),
+                            Some(seccomp_mode) => seccomp_mode.to_string(),
+                            None => "-".to_string(),
+                        },
+                        None,
+                    ),
+                })
+                .collect();
+
+            (row, process.is_disabled_entry)
         })
         .collect()
 }
 
-/// Takes a set of converted process data and groups it together.
+/// Shows just the time for processes started today, the date for ones started this year, and
+/// otherwise just the year, like `ps` does.
+fn get_start_time_string(start_time: u64) -> String {
+    use chrono::{Datelike, TimeZone};
+
+    let start_time = chrono::Local.timestamp(start_time as i64, 0);
+    let now = chrono::Local::now();
+
+    if start_time.date() == now.date() {
+        start_time.format("%H:%M:%S").to_string()
+    } else if start_time.year() == now.year() {
+        start_time.format("%b %d").to_string()
+    } else {
+        start_time.format("%Y").to_string()
+    }
+}
+
+/// Shows the two largest units of a runtime, like `3h05m`.
+fn get_runtime_string(runtime_secs: u64) -> String {
+    let days = runtime_secs / 86400;
+    let hours = (runtime_secs / 3600) % 24;
+    let minutes = (runtime_secs / 60) % 60;
+    let seconds = runtime_secs % 60;
+
+    if days > 0 {
+        format!("{}d{:02}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h{:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m{:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Takes a set of converted process data and groups it together.  If grouping by pod, processes
+/// running in a Kubernetes pod are grouped by their pod instead of their name.
 ///
 /// To be honest, I really don't like how this is done, even though I've rewritten this like 3 times.
 pub fn group_process_data(
-    single_process_data: &[ConvertedProcessData], is_using_command: bool,
+    single_process_data: &[ConvertedProcessData], name_type: ProcessNameType,
+    is_grouped_by_pod: bool, number_format: &NumberFormat,
 ) -> Vec<ConvertedProcessData> {
     #[derive(Clone, Default, Debug)]
     struct SingleProcessData {
         pub pid: Pid,
         pub cpu_percent_usage: f64,
+        pub cpu_time_delta_ms: Option<u64>,
+        pub cap_eff: Option<u64>,
+        pub seccomp_mode: Option<u32>,
         pub mem_percent_usage: f64,
         pub mem_usage_bytes: u64,
+        pub virt_mem_bytes: u64,
         pub group_pids: Vec<Pid>,
         pub read_per_sec: f64,
         pub write_per_sec: f64,
         pub total_read: f64,
         pub total_write: f64,
         pub process_state: String,
+        pub swap_bytes: Option<u64>,
+        pub start_time: Option<u64>,
+        pub runtime_secs: Option<u64>,
+        pub fd_count: Option<u64>,
+        /// The summed CPU usage of the group's processes, by when it was read.
+        pub cpu_history: BTreeMap<Instant, f64>,
+        pub is_io_readable: bool,
     }
 
-    let mut grouped_hashmap: HashMap<String, SingleProcessData> = std::collections::HashMap::new();
+    // Keyed by the name (or pod), and for pods, the namespace.  An unknown namespace is left
+    // empty, so pods never share a key with a regular process of the same name.
+    let mut grouped_hashmap: HashMap<(String, Option<String>), SingleProcessData> =
+        std::collections::HashMap::new();
 
     single_process_data.iter().for_each(|process| {
-        let entry = grouped_hashmap
-            .entry(if is_using_command {
-                process.command.to_string()
-            } else {
-                process.name.to_string()
-            })
-            .or_insert(SingleProcessData {
-                pid: process.pid,
-                ..SingleProcessData::default()
-            });
+        let key = match &process.pod_name {
+            Some(pod_name) if is_grouped_by_pod => (
+                pod_name.to_string(),
+                Some(process.pod_namespace.clone().unwrap_or_default()),
+            ),
+            _ => (process.get_name(name_type).to_string(), None),
+        };
+
+        let entry = grouped_hashmap.entry(key).or_insert(SingleProcessData {
+            pid: process.pid,
+            ..SingleProcessData::default()
+        });
 
         (*entry).cpu_percent_usage += process.cpu_percent_usage;
+        (*entry).cpu_time_delta_ms = match (entry.cpu_time_delta_ms, process.cpu_time_delta_ms) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        // A group has every capability any of its processes has, and is only as confined as its
+        // least confined process.
+        (*entry).cap_eff = match (entry.cap_eff, process.cap_eff) {
+            (Some(a), Some(b)) => Some(a | b),
+            (a, b) => a.or(b),
+        };
+        (*entry).seccomp_mode = match (entry.seccomp_mode, process.seccomp_mode) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
         (*entry).mem_percent_usage += process.mem_percent_usage;
         (*entry).mem_usage_bytes += process.mem_usage_bytes;
+        (*entry).virt_mem_bytes += process.virt_mem_bytes;
         (*entry).group_pids.push(process.pid);
         (*entry).read_per_sec += process.rps_f64;
         (*entry).write_per_sec += process.wps_f64;
         (*entry).total_read += process.tr_f64;
         (*entry).total_write += process.tw_f64;
+        (*entry).swap_bytes = match (entry.swap_bytes, process.swap_bytes) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        // A group started when its oldest process did.
+        (*entry).start_time = match (entry.start_time, process.start_time) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        (*entry).runtime_secs = match (entry.runtime_secs, process.runtime_secs) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        (*entry).fd_count = match (entry.fd_count, process.fd_count) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        for (instant, cpu_usage) in &process.cpu_history {
+            *entry.cpu_history.entry(*instant).or_insert(0.0) += cpu_usage;
+        }
+        // Only count a group's I/O as unreadable if none of it could be read.
+        (*entry).is_io_readable |= !process.is_io_restricted;
     });
 
     grouped_hashmap
         .iter()
-        .map(|(identifier, process_details)| {
+        .map(|((identifier, pod_namespace), process_details)| {
             let p = process_details.clone();
+            let identifier: Arc<str> = Arc::from(identifier.as_str());
 
             let (read_per_sec, write_per_sec, total_read, total_write) = get_disk_io_strings(
                 p.read_per_sec as u64,
                 p.write_per_sec as u64,
                 p.total_read as u64,
                 p.total_write as u64,
+                number_format,
             );
 
             ConvertedProcessData {
                 pid: p.pid,
                 ppid: None,
                 is_thread: None,
-                name: identifier.to_string(),
-                command: identifier.to_string(),
+                name: identifier.clone(),
+                command: identifier.clone(),
+                exe_path: identifier.clone(),
                 cpu_percent_usage: p.cpu_percent_usage,
+                cpu_time_delta_ms: p.cpu_time_delta_ms,
+                cap_eff: p.cap_eff,
+                seccomp_mode: p.seccomp_mode,
                 mem_percent_usage: p.mem_percent_usage,
                 mem_usage_bytes: p.mem_usage_bytes,
-                mem_usage_str: get_decimal_bytes(p.mem_usage_bytes),
+                mem_usage_str: get_mem_usage_string(
+                    p.mem_usage_bytes,
+                    UnitPrefix::Si,
+                    number_format,
+                ),
+                virt_mem_bytes: p.virt_mem_bytes,
                 group_pids: p.group_pids,
                 read_per_sec,
                 write_per_sec,
@@ -1357,6 +2454,21 @@ pub fn group_process_data(
                 is_disabled_entry: false,
                 is_collapsed_entry: false,
                 user: None,
+                gpu_util_percent: None,
+                gpu_mem_bytes: None,
+                swap_bytes: p.swap_bytes,
+                tcp_retransmits: None,
+                tcp_rtt_us: None,
+                pod_name: pod_namespace.as_ref().map(|_| identifier.to_string()),
+                pod_namespace: pod_namespace
+                    .as_ref()
+                    .filter(|namespace| !namespace.is_empty())
+                    .cloned(),
+                start_time: p.start_time,
+                runtime_secs: p.runtime_secs,
+                fd_count: p.fd_count,
+                cpu_history: p.cpu_history.into_iter().collect(),
+                is_io_restricted: !p.is_io_readable,
             }
         })
         .collect::<Vec<_>>()
@@ -1365,6 +2477,8 @@ pub fn group_process_data(
 pub fn convert_battery_harvest(
     current_data: &data_farmer::DataCollection,
 ) -> Vec<ConvertedBatteryData> {
+    let current_time = current_data.current_instant;
+
     current_data
         .battery_harvest
         .iter()
@@ -1406,6 +2520,279 @@ pub fn convert_battery_harvest(
                 None
             },
             health: format!("{:.2}%", battery_harvest.health_percent),
+            power_data: current_data
+                .timed_data_vec
+                .iter()
+                .filter_map(|(time, data)| {
+                    data.battery_power_data.get(itx).map(|watts| {
+                        let time_from_start: f64 =
+                            (current_time.duration_since(*time).as_millis() as f64).floor();
+                        (-time_from_start, *watts)
+                    })
+                })
+                .collect(),
         })
         .collect()
 }
+
+pub fn convert_service_row(
+    current_data: &data_farmer::DataCollection, number_format: &NumberFormat,
+) -> Vec<Vec<String>> {
+    let mut service_vector: Vec<Vec<String>> = current_data
+        .service_harvest
+        .iter()
+        .map(|service| {
+            let mem_usage_fmt = if let Some(mem_usage_bytes) = service.mem_usage_bytes {
+                number_format.format_bytes(mem_usage_bytes, UnitPrefix::Iec, 1)
+            } else {
+                "N/A".to_string()
+            };
+
+            let cpu_usage_fmt = if let Some(cpu_usage_percent) = service.cpu_usage_percent {
+                format!("{}%", number_format.format_number(cpu_usage_percent, 1))
+            } else {
+                "N/A".to_string()
+            };
+
+            vec![
+                service.name.clone(),
+                format!("{} ({})", service.active_state, service.sub_state),
+                service
+                    .pid
+                    .map(|pid| pid.to_string())
+                    .unwrap_or_else(|| "N/A".to_string()),
+                mem_usage_fmt,
+                cpu_usage_fmt,
+                service.description.clone(),
+            ]
+        })
+        .collect();
+
+    if service_vector.is_empty() {
+        service_vector.push(vec!["No Services Found".to_string(), "".to_string()]);
+    }
+
+    service_vector
+}
+
+pub fn convert_port_row(current_data: &data_farmer::DataCollection) -> Vec<Vec<String>> {
+    let mut port_vector: Vec<Vec<String>> = current_data
+        .port_harvest
+        .iter()
+        .map(|port| {
+            let address = if port.address.is_unspecified() {
+                "*".to_string()
+            } else {
+                port.address.to_string()
+            };
+
+            vec![
+                port.protocol_name().to_string(),
+                address,
+                port.port.to_string(),
+                port.pid
+                    .map(|pid| pid.to_string())
+                    .unwrap_or_else(|| "N/A".to_string()),
+                port.process_name
+                    .clone()
+                    .unwrap_or_else(|| "N/A".to_string()),
+            ]
+        })
+        .collect();
+
+    if port_vector.is_empty() {
+        port_vector.push(vec!["No Listening Ports Found".to_string(), "".to_string()]);
+    }
+
+    port_vector
+}
+
+pub fn convert_connection_row(current_data: &data_farmer::DataCollection) -> Vec<Vec<String>> {
+    let mut connection_vector: Vec<Vec<String>> = current_data
+        .connection_harvest
+        .iter()
+        .map(|connection| {
+            let remote = match &connection.remote_hostname {
+                Some(hostname) => format!("{}:{}", hostname, connection.remote_port),
+                None => {
+                    SocketAddr::new(connection.remote_address, connection.remote_port).to_string()
+                }
+            };
+
+            vec![
+                connection.protocol_name().to_string(),
+                SocketAddr::new(connection.local_address, connection.local_port).to_string(),
+                remote,
+                connection
+                    .pid
+                    .map(|pid| pid.to_string())
+                    .unwrap_or_else(|| "N/A".to_string()),
+                connection
+                    .process_name
+                    .clone()
+                    .unwrap_or_else(|| "N/A".to_string()),
+            ]
+        })
+        .collect();
+
+    if connection_vector.is_empty() {
+        connection_vector.push(vec!["No Connections Found".to_string(), "".to_string()]);
+    }
+
+    connection_vector
+}
+
+pub fn convert_cgroup_row(
+    current_data: &data_farmer::DataCollection, number_format: &NumberFormat,
+) -> Vec<Vec<String>> {
+    let cgroups = &current_data.cgroup_harvest;
+    let mut cgroup_vector: Vec<Vec<String>> = cgroups
+        .iter()
+        .enumerate()
+        .map(|(itx, cgroup)| {
+            // Children are drawn as branches under their parent, the last one closing it off.
+            let name = if cgroup.depth == 0 {
+                cgroup.name.clone()
+            } else {
+                let is_last_child = cgroups
+                    .get(itx + 1)
+                    .map(|next| next.depth < cgroup.depth)
+                    .unwrap_or(true);
+                format!(
+                    "{}{} {}",
+                    "  ".repeat(cgroup.depth - 1),
+                    if is_last_child { "└─" } else { "├─" },
+                    cgroup.name
+                )
+            };
+
+            let format_bytes = |bytes: Option<u64>| {
+                if let Some(bytes) = bytes {
+                    number_format.format_bytes(bytes, UnitPrefix::Iec, 1)
+                } else {
+                    "N/A".to_string()
+                }
+            };
+            let format_rate = |bytes_per_sec: Option<u64>| {
+                if let Some(bytes_per_sec) = bytes_per_sec {
+                    number_format.format_prefixed(bytes_per_sec, "B/s", UnitPrefix::Si, 0)
+                } else {
+                    "N/A".to_string()
+                }
+            };
+
+            vec![
+                name,
+                cgroup
+                    .cpu_usage_percent
+                    .map(|cpu_usage_percent| {
+                        format!("{}%", number_format.format_number(cpu_usage_percent, 1))
+                    })
+                    .unwrap_or_else(|| "N/A".to_string()),
+                format_bytes(cgroup.mem_usage_bytes),
+                if cgroup.mem_limit_bytes.is_some() {
+                    format_bytes(cgroup.mem_limit_bytes)
+                } else {
+                    "max".to_string()
+                },
+                format_rate(cgroup.read_bytes_per_sec),
+                format_rate(cgroup.write_bytes_per_sec),
+            ]
+        })
+        .collect();
+
+    if cgroup_vector.is_empty() {
+        cgroup_vector.push(vec!["No Cgroups Found".to_string(), "".to_string()]);
+    }
+
+    cgroup_vector
+}
+
+pub fn convert_container_row(
+    current_data: &data_farmer::DataCollection, number_format: &NumberFormat,
+) -> Vec<Vec<String>> {
+    let mut container_vector: Vec<Vec<String>> = current_data
+        .container_harvest
+        .iter()
+        .map(|container| {
+            let format_rate = |bytes_per_sec: Option<u64>| {
+                if let Some(bytes_per_sec) = bytes_per_sec {
+                    number_format.format_prefixed(bytes_per_sec, "B/s", UnitPrefix::Si, 0)
+                } else {
+                    "N/A".to_string()
+                }
+            };
+
+            vec![
+                container.name.clone(),
+                container.image.clone(),
+                container.status.clone(),
+                container
+                    .cpu_usage_percent
+                    .map(|cpu_usage_percent| {
+                        format!("{}%", number_format.format_number(cpu_usage_percent, 1))
+                    })
+                    .unwrap_or_else(|| "N/A".to_string()),
+                container
+                    .mem_usage_bytes
+                    .map(|mem_usage_bytes| {
+                        number_format.format_bytes(mem_usage_bytes, UnitPrefix::Iec, 1)
+                    })
+                    .unwrap_or_else(|| "N/A".to_string()),
+                format_rate(container.rx_bytes_per_sec),
+                format_rate(container.tx_bytes_per_sec),
+            ]
+        })
+        .collect();
+
+    if container_vector.is_empty() {
+        container_vector.push(vec!["No Containers Found".to_string(), "".to_string()]);
+    }
+
+    container_vector
+}
+
+pub fn convert_vm_row(
+    current_data: &data_farmer::DataCollection, number_format: &NumberFormat,
+) -> Vec<Vec<String>> {
+    let mut vm_vector: Vec<Vec<String>> = current_data
+        .vm_harvest
+        .iter()
+        .map(|vm| {
+            let format_rate = |bytes_per_sec: Option<u64>| {
+                if let Some(bytes_per_sec) = bytes_per_sec {
+                    number_format.format_prefixed(bytes_per_sec, "B/s", UnitPrefix::Si, 0)
+                } else {
+                    "N/A".to_string()
+                }
+            };
+
+            vec![
+                vm.name.clone(),
+                vm.vcpus
+                    .map(|vcpus| vcpus.to_string())
+                    .unwrap_or_else(|| "N/A".to_string()),
+                vm.cpu_usage_percent
+                    .map(|cpu_usage_percent| {
+                        format!("{}%", number_format.format_number(cpu_usage_percent, 1))
+                    })
+                    .unwrap_or_else(|| "N/A".to_string()),
+                vm.balloon_bytes
+                    .map(|balloon_bytes| {
+                        number_format.format_bytes(balloon_bytes, UnitPrefix::Iec, 1)
+                    })
+                    .unwrap_or_else(|| "N/A".to_string()),
+                format_rate(vm.disk_read_bytes_per_sec),
+                format_rate(vm.disk_write_bytes_per_sec),
+                format_rate(vm.rx_bytes_per_sec),
+                format_rate(vm.tx_bytes_per_sec),
+            ]
+        })
+        .collect();
+
+    if vm_vector.is_empty() {
+        vm_vector.push(vec!["No VMs Running".to_string(), "".to_string()]);
+    }
+
+    vm_vector
+}
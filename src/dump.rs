@@ -0,0 +1,436 @@
+//! A one-shot summary of the system, printed instead of starting the TUI.  It's meant for scripts
+//! and for terminals that can't show the TUI, a bit like `free`, `df`, and `top -bn1` at once.
+//!
+//! The summary of a whole session, printed when bottom exits, is laid out the same way.
+
+use std::path::PathBuf;
+
+use chrono::Local;
+
+use crate::{
+    app::{
+        data_farmer::RunningStat,
+        data_harvester::{
+            stream::data_to_json,
+            temperature::{convert_celsius, SensorKind},
+            Data, DataCollector,
+        },
+        layout_manager::UsedWidgets,
+        App,
+    },
+    units::{data_units::DataUnit, formatting::UnitPrefix},
+    utils::error::{BottomError, Result},
+};
+
+/// How a dump is printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpFormat {
+    Text,
+    Json,
+}
+
+impl std::str::FromStr for DumpFormat {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(DumpFormat::Text),
+            "json" => Ok(DumpFormat::Json),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid dump format, use \"text\" or \"json\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// Where the session summary goes when bottom exits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SummaryOutput {
+    Stdout,
+    File(PathBuf),
+}
+
+/// Harvests once and prints the result to stdout.  The usual filters and units apply, but
+/// everything a dump shows is harvested whatever the layout is.
+pub fn print_dump(app: &App, dump_format: DumpFormat) {
+    let data = harvest(app);
+    match dump_format {
+        DumpFormat::Text => print!("{}", dump_text(app, &data)),
+        DumpFormat::Json => println!("{:#}", data_to_json(&data)),
+    }
+}
+
+/// Rates like CPU usage need two harvests to be worked out, so this waits for a moment between
+/// them, the same as at startup.
+fn harvest(app: &App) -> Data {
+    let mut collector = DataCollector::new(app.filters.clone());
+    collector.set_collected_data(UsedWidgets {
+        use_cpu: true,
+        use_mem: true,
+        use_net: true,
+        use_proc: true,
+        use_disk: true,
+        use_temp: true,
+        ..UsedWidgets::default()
+    });
+    collector.set_use_current_cpu_total(app.app_config_fields.use_current_cpu_total);
+    collector.set_include_child_cpu_time(app.app_config_fields.include_child_cpu_time);
+    collector.set_show_average_cpu(true);
+    collector.init();
+
+    futures::executor::block_on(collector.update_data());
+    collector.data
+}
+
+fn dump_text(app: &App, data: &Data) -> String {
+    let config = &app.app_config_fields;
+    let number_format = &config.number_format;
+    let mut output = String::new();
+
+    if let Some(cpu) = &data.cpu {
+        let rows = cpu
+            .iter()
+            .map(|cpu_data| {
+                vec![
+                    match cpu_data.cpu_count {
+                        Some(count) => format!("{}{}", cpu_data.cpu_prefix, count),
+                        None => cpu_data.cpu_prefix.clone(),
+                    },
+                    if cpu_data.is_offline {
+                        "offline".to_string()
+                    } else {
+                        format!("{:.1}%", cpu_data.cpu_usage)
+                    },
+                ]
+            })
+            .collect();
+        push_table(&mut output, "CPU", &[("Name", true), ("Use%", false)], rows);
+    }
+
+    if let Some(load_avg) = &data.load_avg {
+        output.push_str(&format!(
+            "Load average: {:.2} {:.2} {:.2}\n\n",
+            load_avg[0], load_avg[1], load_avg[2]
+        ));
+    }
+
+    let memory_prefix = if config.memory_use_decimal_prefix {
+        UnitPrefix::Si
+    } else {
+        UnitPrefix::Iec
+    };
+    let rows = [("RAM", &data.memory), ("Swap", &data.swap)]
+        .iter()
+        .filter_map(|(name, harvest)| {
+            let harvest = harvest.as_ref()?;
+            let total_bytes = harvest.mem_total_in_kib * 1024;
+            let used_bytes = harvest.mem_used_in_kib * 1024;
+            Some(vec![
+                name.to_string(),
+                number_format.format_bytes(total_bytes, memory_prefix, 1),
+                number_format.format_bytes(used_bytes, memory_prefix, 1),
+                number_format.format_bytes(
+                    total_bytes.saturating_sub(used_bytes),
+                    memory_prefix,
+                    1,
+                ),
+                match harvest.use_percent {
+                    Some(use_percent) => format!("{:.1}%", use_percent),
+                    None => "N/A".to_string(),
+                },
+            ])
+        })
+        .collect::<Vec<_>>();
+    if !rows.is_empty() {
+        push_table(
+            &mut output,
+            "Memory",
+            &[
+                ("", true),
+                ("Total", false),
+                ("Used", false),
+                ("Free", false),
+                ("Use%", false),
+            ],
+            rows,
+        );
+    }
+
+    if let Some(disks) = &data.disks {
+        let format_space = |space: Option<u64>| match space {
+            Some(space) => number_format.format_bytes(space, UnitPrefix::Si, 0),
+            None => "N/A".to_string(),
+        };
+        let rows = disks
+            .iter()
+            .map(|disk| {
+                vec![
+                    disk.name.clone(),
+                    disk.mount_point.clone(),
+                    disk.fs_type.clone(),
+                    format_space(disk.total_space),
+                    format_space(disk.used_space),
+                    format_space(disk.free_space),
+                    match (disk.used_space, disk.total_space) {
+                        (Some(used_space), Some(total_space)) if total_space > 0 => {
+                            format!("{:.0}%", used_space as f64 / total_space as f64 * 100.0)
+                        }
+                        _ => "N/A".to_string(),
+                    },
+                ]
+            })
+            .collect();
+        push_table(
+            &mut output,
+            "Disks",
+            &[
+                ("Disk", true),
+                ("Mount", true),
+                ("Type", true),
+                ("Size", false),
+                ("Used", false),
+                ("Free", false),
+                ("Use%", false),
+            ],
+            rows,
+        );
+    }
+
+    if let Some(network) = &data.network {
+        // Rates are harvested in bits, and totals are always shown in bytes.
+        let (rx, tx, unit) = match config.network_unit_type {
+            DataUnit::Byte => (network.rx / 8, network.tx / 8, "B/s"),
+            DataUnit::Bit => (network.rx, network.tx, "b/s"),
+        };
+        let network_prefix = if config.network_use_binary_prefix {
+            UnitPrefix::Iec
+        } else {
+            UnitPrefix::Si
+        };
+        push_table(
+            &mut output,
+            "Network",
+            &[("", true), ("Rate", false), ("Total", false)],
+            vec![
+                vec![
+                    "RX".to_string(),
+                    number_format.format_prefixed(rx, unit, network_prefix, 1),
+                    number_format.format_bytes(network.total_rx / 8, network_prefix, 1),
+                ],
+                vec![
+                    "TX".to_string(),
+                    number_format.format_prefixed(tx, unit, network_prefix, 1),
+                    number_format.format_bytes(network.total_tx / 8, network_prefix, 1),
+                ],
+            ],
+        );
+    }
+
+    if let Some(sensors) = &data.temperature_sensors {
+        let rows = sensors
+            .iter()
+            .map(|sensor| {
+                let reading = match sensor.kind {
+                    SensorKind::Temperature => {
+                        let temp_type = config
+                            .temperature_units
+                            .get(&sensor.name)
+                            .unwrap_or(&config.temperature_type);
+                        format!(
+                            "{:.0}{}",
+                            convert_celsius(sensor.temperature, temp_type).ceil(),
+                            temp_type.as_symbol()
+                        )
+                    }
+                    SensorKind::Fan => format!("{:.0}RPM", sensor.temperature),
+                    SensorKind::Voltage => format!("{:.2}V", sensor.temperature),
+                    SensorKind::Power => format!("{:.1}W", sensor.temperature),
+                };
+                vec![sensor.name.clone(), reading]
+            })
+            .collect();
+        push_table(
+            &mut output,
+            "Sensors",
+            &[("Sensor", true), ("Reading", false)],
+            rows,
+        );
+    }
+
+    if let Some(processes) = &data.list_of_processes {
+        let mut processes = processes.iter().collect::<Vec<_>>();
+        processes.sort_by(|a, b| {
+            b.cpu_usage_percent
+                .partial_cmp(&a.cpu_usage_percent)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.pid.cmp(&b.pid))
+        });
+        let rows = processes
+            .into_iter()
+            .map(|process| {
+                vec![
+                    process.pid.to_string(),
+                    format!("{:.1}%", process.cpu_usage_percent),
+                    format!("{:.1}%", process.mem_usage_percent),
+                    number_format.format_bytes(process.mem_usage_bytes, UnitPrefix::Iec, 0),
+                    process.process_state.clone(),
+                    process.name.to_string(),
+                ]
+            })
+            .collect();
+        push_table(
+            &mut output,
+            "Processes",
+            &[
+                ("PID", false),
+                ("CPU%", false),
+                ("Mem%", false),
+                ("Mem", false),
+                ("State", true),
+                ("Name", true),
+            ],
+            rows,
+        );
+    }
+
+    output
+}
+
+/// Prints the lowest, average, and highest CPU, memory, and network usage seen since bottom
+/// started, or writes them to a file.  This has to happen after the terminal is restored.
+pub fn write_session_summary(app: &App, summary_output: &SummaryOutput) -> Result<()> {
+    let summary = session_summary_text(app);
+    match summary_output {
+        SummaryOutput::Stdout => print!("{}", summary),
+        SummaryOutput::File(path) => std::fs::write(path, summary)?,
+    }
+    Ok(())
+}
+
+fn session_summary_text(app: &App) -> String {
+    let config = &app.app_config_fields;
+    let number_format = &config.number_format;
+    let stats = &app.data_collection.session_stats;
+    let end_time = Local::now();
+    let time_format = format!("%Y-%m-%d {}", config.clock_format.time_format());
+
+    let mut output = format!(
+        "Session from {} to {} ({})\n\n",
+        stats.start_time.format(&time_format),
+        end_time.format(&time_format),
+        get_duration_display(
+            end_time
+                .signed_duration_since(stats.start_time)
+                .num_seconds()
+                .max(0) as u64
+        )
+    );
+
+    let format_percent = |value: f64| format!("{:.1}%", value);
+    // Rates are harvested in bits.
+    let (network_divisor, network_unit) = match config.network_unit_type {
+        DataUnit::Byte => (8.0, "B/s"),
+        DataUnit::Bit => (1.0, "b/s"),
+    };
+    let network_prefix = if config.network_use_binary_prefix {
+        UnitPrefix::Iec
+    } else {
+        UnitPrefix::Si
+    };
+    let format_rate = |value: f64| {
+        number_format.format_prefixed(
+            (value / network_divisor) as u64,
+            network_unit,
+            network_prefix,
+            1,
+        )
+    };
+
+    let stat_rows: [(&str, &RunningStat, &dyn Fn(f64) -> String); 5] = [
+        ("CPU", &stats.cpu, &format_percent),
+        ("RAM", &stats.mem, &format_percent),
+        ("Swap", &stats.swap, &format_percent),
+        ("RX", &stats.rx, &format_rate),
+        ("TX", &stats.tx, &format_rate),
+    ];
+    let rows = stat_rows
+        .iter()
+        .filter_map(|(name, stat, format_value)| {
+            let (min, avg, max) = stat.get_min_avg_max()?;
+            Some(vec![
+                name.to_string(),
+                format_value(min),
+                format_value(avg),
+                format_value(max),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    if rows.is_empty() {
+        output.push_str("No CPU, memory, or network data was harvested.\n");
+    } else {
+        push_table(
+            &mut output,
+            "Usage",
+            &[("", true), ("Min", false), ("Avg", false), ("Max", false)],
+            rows,
+        );
+    }
+
+    output
+}
+
+/// Formats a number of seconds like "1h 2m 3s", leaving out any leading zero units.
+fn get_duration_display(seconds: u64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Adds a titled table to the dump, with each column as wide as its widest cell.  Columns marked
+/// as text are aligned left, and the rest right.
+fn push_table(output: &mut String, title: &str, columns: &[(&str, bool)], rows: Vec<Vec<String>>) {
+    let widths = columns
+        .iter()
+        .enumerate()
+        .map(|(itx, (header, _))| {
+            rows.iter()
+                .map(|row| row[itx].chars().count())
+                .chain(std::iter::once(header.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let headers = columns
+        .iter()
+        .map(|(header, _)| header.to_string())
+        .collect::<Vec<_>>();
+
+    output.push_str(title);
+    output.push('\n');
+    for row in std::iter::once(headers).chain(rows) {
+        let line = row
+            .iter()
+            .zip(columns)
+            .zip(&widths)
+            .map(|((cell, (_, is_text)), width)| {
+                if *is_text {
+                    format!("{:<width$}", cell, width = width)
+                } else {
+                    format!("{:>width$}", cell, width = width)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        output.push_str("  ");
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output.push('\n');
+}
@@ -8,12 +8,13 @@ extern crate log;
 
 use std::{
     boxed::Box,
+    collections::HashSet,
     fs,
     io::{stdout, Write},
     panic::PanicInfo,
     path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
     sync::Arc,
-    sync::Condvar,
     sync::Mutex,
     thread,
     time::{Duration, Instant},
@@ -46,6 +47,7 @@ pub mod canvas;
 pub mod clap;
 pub mod constants;
 pub mod data_conversion;
+pub mod dump;
 pub mod options;
 pub mod units;
 
@@ -61,6 +63,8 @@ pub enum BottomEvent<I, J> {
     MouseInput(J),
     Update(Box<data_harvester::Data>),
     Clean,
+    /// An action run in the background finished.
+    ActionResult(app::ActionResult),
 }
 
 #[derive(Debug)]
@@ -69,6 +73,8 @@ pub enum ThreadControlEvent {
     UpdateConfig(Box<app::AppConfigFields>),
     UpdateUsedWidgets(Box<UsedWidgets>),
     UpdateUpdateTime(u64),
+    UpdateCollectFdCounts(bool),
+    UpdateCollectProcessStatus(bool),
 }
 
 pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
@@ -101,6 +107,30 @@ pub fn handle_key_event_or_break(
     // We can solve this (when we do paste probably) while keeping the throttle (mainly meant for movement)
     // by throttling after *bulk+singular* actions, not just singular ones.
 
+    // The global search takes all keys while it's open, as it's being typed into.
+    if app.global_search_state.is_searching {
+        match (event.modifiers, event.code) {
+            (KeyModifiers::CONTROL, KeyCode::Char('c')) => return true,
+            (KeyModifiers::CONTROL, KeyCode::Char('u')) => app.clear_global_search(),
+            (KeyModifiers::NONE, KeyCode::Char(caught_char))
+            | (KeyModifiers::SHIFT, KeyCode::Char(caught_char)) => {
+                app.on_global_search_char(caught_char)
+            }
+            (_, KeyCode::Backspace) => app.on_global_search_backspace(),
+            (_, KeyCode::Enter) | (_, KeyCode::Down) => app.cycle_global_search_match(true),
+            (_, KeyCode::Up) => app.cycle_global_search_match(false),
+            (_, KeyCode::Esc) => app.close_global_search(),
+            _ => {}
+        }
+
+        return false;
+    }
+
+    let event = match app.remap_key_event(event) {
+        Some(event) => event,
+        None => return false,
+    };
+
     if event.modifiers.is_empty() {
         // Required catch for searching - otherwise you couldn't search with q.
         if event.code == KeyCode::Char('q') && !app.is_in_search_widget() {
@@ -122,6 +152,7 @@ pub fn handle_key_event_or_break(
             KeyCode::F(1) => app.toggle_ignore_case(),
             KeyCode::F(2) => app.toggle_search_whole_word(),
             KeyCode::F(3) => app.toggle_search_regex(),
+            KeyCode::F(4) => app.toggle_search_highlight_only(),
             KeyCode::F(5) => app.toggle_tree_mode(),
             KeyCode::F(6) => app.toggle_sort(),
             KeyCode::F(9) => app.start_killing_process(),
@@ -134,6 +165,7 @@ pub fn handle_key_event_or_break(
                 KeyCode::Char('c') | KeyCode::Char('C') => app.toggle_ignore_case(),
                 KeyCode::Char('w') | KeyCode::Char('W') => app.toggle_search_whole_word(),
                 KeyCode::Char('r') | KeyCode::Char('R') => app.toggle_search_regex(),
+                KeyCode::Char('i') | KeyCode::Char('I') => app.toggle_search_highlight_only(),
                 KeyCode::Char('h') => app.on_left_key(),
                 KeyCode::Char('l') => app.on_right_key(),
                 _ => {}
@@ -180,6 +212,12 @@ pub fn handle_key_event_or_break(
         }
     }
 
+    for thread_event in app.collection_thread_events.drain(..) {
+        if reset_sender.send(thread_event).is_err() {
+            break;
+        }
+    }
+
     false
 }
 
@@ -250,9 +288,36 @@ pub fn try_drawing(
     Ok(())
 }
 
+/// Whether the terminal's title was saved, so it needs putting back on exit.
+static HAS_SAVED_TERMINAL_TITLE: AtomicBool = AtomicBool::new(false);
+
+/// Saves the terminal's title on its title stack, so an alert can change it and it can still be
+/// put back on exit.  Terminals without a title stack ignore this.
+pub fn save_terminal_title(
+    terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
+) -> error::Result<()> {
+    let backend = terminal.backend_mut();
+    write!(backend, "\x1b[22;0t")?;
+    backend.flush()?;
+    HAS_SAVED_TERMINAL_TITLE.store(true, Ordering::SeqCst);
+
+    Ok(())
+}
+
+/// Puts back the title saved by [`save_terminal_title`], if it was saved.
+fn restore_terminal_title(writer: &mut impl Write) -> std::io::Result<()> {
+    if HAS_SAVED_TERMINAL_TITLE.swap(false, Ordering::SeqCst) {
+        write!(writer, "\x1b[23;0t")?;
+        writer.flush()?;
+    }
+
+    Ok(())
+}
+
 pub fn cleanup_terminal(
     terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
 ) -> error::Result<()> {
+    restore_terminal_title(terminal.backend_mut())?;
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
@@ -270,6 +335,26 @@ pub fn cleanup_terminal(
     Ok(())
 }
 
+/// Sends alerts to the terminal in each of the given ways, like ringing the bell.
+pub fn write_terminal_alerts(
+    terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
+    terminal_alerts: &[data_harvester::batteries::alert::TerminalAlert], messages: &[String],
+) -> error::Result<()> {
+    if terminal_alerts.is_empty() || messages.is_empty() {
+        return Ok(());
+    }
+
+    let backend = terminal.backend_mut();
+    for message in messages {
+        for terminal_alert in terminal_alerts {
+            write!(backend, "{}", terminal_alert.get_escape_sequence(message))?;
+        }
+    }
+    backend.flush()?;
+
+    Ok(())
+}
+
 /// Based on https://github.com/Rigellute/spotify-tui/blob/master/src/main.rs
 pub fn panic_hook(panic_info: &PanicInfo<'_>) {
     let mut stdout = stdout();
@@ -284,6 +369,7 @@ pub fn panic_hook(panic_info: &PanicInfo<'_>) {
 
     let stacktrace: String = format!("{:?}", backtrace::Backtrace::new());
 
+    let _ = restore_terminal_title(&mut stdout);
     disable_raw_mode().unwrap();
     execute!(stdout, DisableMouseCapture, LeaveAlternateScreen).unwrap();
 
@@ -317,7 +403,13 @@ pub fn handle_force_redraws(app: &mut App) {
             &mut app.canvas_data.cpu_data,
             app.is_frozen,
         );
+        if app.app_config_fields.show_top_cpu_processes {
+            app.canvas_data.top_process_cpu_data =
+                convert_top_process_cpu_data(&app.data_collection, app.is_frozen);
+        }
         app.canvas_data.load_avg_data = app.data_collection.load_avg_harvest;
+        app.canvas_data.cpu_breakdown = app.data_collection.cpu_breakdown_harvest;
+        app.canvas_data.throttle = app.data_collection.throttle_harvest;
         app.cpu_state.force_update = None;
     }
 
@@ -325,21 +417,76 @@ pub fn handle_force_redraws(app: &mut App) {
     if app.mem_state.force_update.is_some() {
         app.canvas_data.mem_data = convert_mem_data_points(&app.data_collection, app.is_frozen);
         app.canvas_data.swap_data = convert_swap_data_points(&app.data_collection, app.is_frozen);
+        app.canvas_data.numa_data = convert_numa_data(
+            &app.data_collection,
+            app.is_frozen,
+            app.app_config_fields.memory_use_decimal_prefix,
+            &app.app_config_fields.number_format,
+        );
         app.mem_state.force_update = None;
     }
 
     if app.net_state.force_update.is_some() {
-        let (rx, tx) = get_rx_tx_data_points(
+        // Convert the legend too, as the unit may have changed.
+        let network_data = convert_network_data_points(
             &app.data_collection,
             app.is_frozen,
+            app.app_config_fields.use_basic_mode || app.app_config_fields.use_old_network_legend,
             &app.app_config_fields.network_scale_type,
             &app.app_config_fields.network_unit_type,
             app.app_config_fields.network_use_binary_prefix,
+            &app.app_config_fields.number_format,
         );
-        app.canvas_data.network_data_rx = rx;
-        app.canvas_data.network_data_tx = tx;
+        app.canvas_data.network_data_rx = network_data.rx;
+        app.canvas_data.network_data_tx = network_data.tx;
+        app.canvas_data.rx_display = network_data.rx_display;
+        app.canvas_data.tx_display = network_data.tx_display;
+        if let Some(total_rx_display) = network_data.total_rx_display {
+            app.canvas_data.total_rx_display = total_rx_display;
+        }
+        if let Some(total_tx_display) = network_data.total_tx_display {
+            app.canvas_data.total_tx_display = total_tx_display;
+        }
         app.net_state.force_update = None;
     }
+
+    if app.temp_state.force_update.is_some() {
+        app.canvas_data.temp_sensor_data = convert_temp_row(app);
+        app.temp_state.force_update = None;
+    }
+
+    if app.disk_state.force_update.is_some() {
+        app.canvas_data.disk_data = convert_disk_row(
+            &app.data_collection,
+            &app.app_config_fields.number_format,
+            app.app_config_fields.show_disk_inodes,
+            app.app_config_fields.disk_io_per_device,
+            app.app_config_fields.group_disks_by_fs,
+        );
+        app.disk_state.force_update = None;
+    }
+
+    if app.custom_state.force_update.is_some() {
+        app.canvas_data.plugin_data =
+            convert_plugin_data_points(&app.data_collection, app.is_frozen);
+        app.custom_state.force_update = None;
+    }
+
+    if app.sched_state.force_update.is_some() {
+        let (context_switch_data, interrupt_data) =
+            convert_sched_data_points(&app.data_collection, app.is_frozen);
+        app.canvas_data.context_switch_data = context_switch_data;
+        app.canvas_data.interrupt_data = interrupt_data;
+        app.sched_state.force_update = None;
+    }
+
+    if app.power_state.force_update.is_some() {
+        let (package_power_data, dram_power_data) =
+            convert_power_data_points(&app.data_collection, app.is_frozen);
+        app.canvas_data.package_power_data = package_power_data;
+        app.canvas_data.dram_power_data = dram_power_data;
+        app.power_state.force_update = None;
+    }
 }
 
 #[allow(clippy::needless_collect)]
@@ -347,6 +494,8 @@ pub fn update_all_process_lists(app: &mut App) {
     // According to clippy, I can avoid a collect... but if I follow it,
     // I end up conflicting with the borrow checker since app is used within the closure... hm.
     if !app.is_frozen {
+        app.canvas_data.process_summary = convert_process_summary(&app.data_collection);
+
         let widget_ids = app
             .proc_state
             .widget_states
@@ -360,6 +509,46 @@ pub fn update_all_process_lists(app: &mut App) {
     }
 }
 
+/// Records the latest usage of each pinned process, and unpins any that have exited.
+pub fn update_pinned_processes(app: &mut App) {
+    if app.is_frozen {
+        return;
+    }
+
+    let single_process_data = &app.canvas_data.single_process_data;
+    for proc_widget_state in app.proc_state.widget_states.values_mut() {
+        let is_running = match &mut proc_widget_state.pinned_process {
+            Some(pinned_process) => match single_process_data.get(&pinned_process.pid) {
+                Some(process) => {
+                    pinned_process.push(process.cpu_percent_usage, process.mem_percent_usage);
+                    true
+                }
+                None => false,
+            },
+            None => true,
+        };
+
+        if !is_running {
+            proc_widget_state.pinned_process = None;
+        }
+    }
+}
+
+/// Unmarks any marked processes that have exited, so their PIDs can't be reused by a process
+/// that was never marked.
+pub fn update_marked_processes(app: &mut App) {
+    if app.is_frozen {
+        return;
+    }
+
+    let single_process_data = &app.canvas_data.single_process_data;
+    for proc_widget_state in app.proc_state.widget_states.values_mut() {
+        proc_widget_state
+            .marked_pids
+            .retain(|pid| single_process_data.contains_key(pid));
+    }
+}
+
 fn update_final_process_list(app: &mut App, widget_id: u64) {
     let process_states = app
         .proc_state
@@ -371,34 +560,81 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
                     .process_search_state
                     .search_state
                     .is_invalid_or_blank_search(),
-                process_state.is_using_command,
+                process_state.name_type,
                 process_state.is_grouped,
+                process_state.is_grouped_by_pod,
                 process_state.is_tree_mode,
+                process_state
+                    .pinned_process
+                    .as_ref()
+                    .map(|pinned_process| pinned_process.pid),
+                process_state.is_showing_own_processes,
+                process_state.process_search_state.is_highlighting_only,
             )
         });
 
-    if let Some((is_invalid_or_blank, is_using_command, is_grouped, is_tree)) = process_states {
+    if let Some((
+        is_invalid_or_blank,
+        name_type,
+        is_grouped,
+        is_grouped_by_pod,
+        is_tree,
+        pinned_pid,
+        is_showing_own_processes,
+        is_highlighting_only,
+    )) = process_states
+    {
         if !app.is_frozen {
             convert_process_data(
                 &app.data_collection,
                 &mut app.canvas_data.single_process_data,
                 #[cfg(target_family = "unix")]
                 &mut app.user_table,
+                &app.app_config_fields.number_format,
             );
         }
+        let own_user = if is_showing_own_processes {
+            get_own_user(app)
+        } else {
+            None
+        };
         let process_filter = app.get_process_filter(widget_id);
+        let is_matching_search = |process: &ConvertedProcessData| match process_filter {
+            Some(process_filter) if !is_invalid_or_blank => {
+                process_filter.check(process, name_type)
+            }
+            _ => true,
+        };
+        let is_shown = |process: &ConvertedProcessData| {
+            // The pinned process is always shown, whatever the search.
+            if Some(process.pid) == pinned_pid {
+                return true;
+            }
+
+            let is_own_process = match &own_user {
+                Some(own_user) => process.user.as_ref() == Some(own_user),
+                None => true,
+            };
+
+            is_own_process && (is_highlighting_only || is_matching_search(process))
+        };
+        let highlighted_pids = if is_highlighting_only && !is_invalid_or_blank {
+            app.canvas_data
+                .single_process_data
+                .iter()
+                .filter(|(_pid, process)| is_matching_search(process))
+                .map(|(pid, _process)| *pid)
+                .collect()
+        } else {
+            HashSet::new()
+        };
         let filtered_process_data: Vec<ConvertedProcessData> = if is_tree {
             app.canvas_data
                 .single_process_data
                 .iter()
                 .map(|(_pid, process)| {
                     let mut process_clone = process.clone();
-                    if !is_invalid_or_blank {
-                        if let Some(process_filter) = process_filter {
-                            process_clone.is_disabled_entry =
-                                !process_filter.check(&process_clone, is_using_command);
-                        }
-                    }
+                    process_clone.is_disabled_entry = !is_shown(&process_clone);
                     process_clone
                 })
                 .collect::<Vec<_>>()
@@ -406,35 +642,28 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
             app.canvas_data
                 .single_process_data
                 .iter()
-                .filter_map(|(_pid, process)| {
-                    if !is_invalid_or_blank {
-                        if let Some(process_filter) = process_filter {
-                            if process_filter.check(&process, is_using_command) {
-                                Some(process)
-                            } else {
-                                None
-                            }
-                        } else {
-                            Some(process)
-                        }
-                    } else {
-                        Some(process)
-                    }
-                })
-                .cloned()
+                .filter(|(_pid, process)| is_shown(process))
+                .map(|(_pid, process)| process.clone())
                 .collect::<Vec<_>>()
         };
 
         if let Some(proc_widget_state) = app.proc_state.get_mut_widget_state(widget_id) {
+            proc_widget_state.highlighted_pids = highlighted_pids;
             let mut finalized_process_data = if is_tree {
                 tree_process_data(
                     &filtered_process_data,
-                    is_using_command,
+                    name_type,
                     &proc_widget_state.process_sorting_type,
                     proc_widget_state.is_process_sort_descending,
+                    &app.app_config_fields.number_format,
                 )
             } else if is_grouped {
-                group_process_data(&filtered_process_data, is_using_command)
+                group_process_data(
+                    &filtered_process_data,
+                    name_type,
+                    is_grouped_by_pod,
+                    &app.app_config_fields.number_format,
+                )
             } else {
                 filtered_process_data
             };
@@ -442,21 +671,22 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
             // Note tree mode is sorted well before this, as it's special.
             if !is_tree {
                 sort_process_data(&mut finalized_process_data, proc_widget_state);
-            }
 
-            if proc_widget_state.scroll_state.current_scroll_position
-                >= finalized_process_data.len()
-            {
-                proc_widget_state.scroll_state.current_scroll_position =
-                    finalized_process_data.len().saturating_sub(1);
-                proc_widget_state.scroll_state.previous_scroll_position = 0;
-                proc_widget_state.scroll_state.scroll_direction = app::ScrollDirection::Down;
+                // Tree mode keeps the pinned process where it is, so as to not break up the tree.
+                if let Some(pinned_pid) = pinned_pid {
+                    if let Some(pinned_index) = finalized_process_data.iter().position(|process| {
+                        process.pid == pinned_pid || process.group_pids.contains(&pinned_pid)
+                    }) {
+                        let pinned_process = finalized_process_data.remove(pinned_index);
+                        finalized_process_data.insert(0, pinned_process);
+                    }
+                }
             }
 
-            app.canvas_data.stringified_process_data_map.insert(
-                widget_id,
-                stringify_process_data(&proc_widget_state, &finalized_process_data),
-            );
+            proc_widget_state
+                .scroll_state
+                .clamp_to_rows(finalized_process_data.len());
+
             app.canvas_data
                 .finalized_process_data_map
                 .insert(widget_id, finalized_process_data);
@@ -464,28 +694,65 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
     }
 }
 
+/// Returns the name of the user bottom is running as, to pick out their processes.
+#[cfg(target_family = "unix")]
+fn get_own_user(app: &mut App) -> Option<String> {
+    // SAFETY: geteuid always succeeds.
+    let uid = unsafe { libc::geteuid() };
+    app.user_table.get_uid_to_username_mapping(uid).ok()
+}
+
+/// Process owners aren't known outside of Unix-like systems.
+#[cfg(not(target_family = "unix"))]
+fn get_own_user(_app: &mut App) -> Option<String> {
+    None
+}
+
 fn sort_process_data(
     to_sort_vec: &mut Vec<ConvertedProcessData>, proc_widget_state: &app::ProcWidgetState,
 ) {
+    let is_grouped = proc_widget_state.is_grouped;
+    let is_descending = proc_widget_state.is_process_sort_descending;
     to_sort_vec.sort_by_cached_key(|c| c.name.to_lowercase());
 
-    match &proc_widget_state.process_sorting_type {
+    // All of these sorts are stable, so sorting by the least important key first means
+    // each later sort only reorders what the earlier ones couldn't tell apart.
+    for (sorting_type, is_descending) in proc_widget_state.secondary_sort_keys.iter().rev() {
+        if *sorting_type != proc_widget_state.process_sorting_type {
+            sort_by_process_key(to_sort_vec, sorting_type, *is_descending, is_grouped);
+        }
+    }
+
+    sort_by_process_key(
+        to_sort_vec,
+        &proc_widget_state.process_sorting_type,
+        is_descending,
+        is_grouped,
+    );
+}
+
+fn sort_by_process_key(
+    to_sort_vec: &mut Vec<ConvertedProcessData>, sorting_type: &ProcessSorting,
+    is_descending: bool, is_grouped: bool,
+) {
+    match sorting_type {
         ProcessSorting::CpuPercent => {
             to_sort_vec.sort_by(|a, b| {
                 utils::gen_util::get_ordering(
                     a.cpu_percent_usage,
                     b.cpu_percent_usage,
-                    proc_widget_state.is_process_sort_descending,
+                    is_descending,
                 )
             });
         }
         ProcessSorting::Mem => {
             to_sort_vec.sort_by(|a, b| {
-                utils::gen_util::get_ordering(
-                    a.mem_usage_bytes,
-                    b.mem_usage_bytes,
-                    proc_widget_state.is_process_sort_descending,
-                )
+                utils::gen_util::get_ordering(a.mem_usage_bytes, b.mem_usage_bytes, is_descending)
+            });
+        }
+        ProcessSorting::VirtMem => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.virt_mem_bytes, b.virt_mem_bytes, is_descending)
             });
         }
         ProcessSorting::MemPercent => {
@@ -493,95 +760,173 @@ fn sort_process_data(
                 utils::gen_util::get_ordering(
                     a.mem_percent_usage,
                     b.mem_percent_usage,
-                    proc_widget_state.is_process_sort_descending,
+                    is_descending,
                 )
             });
         }
         ProcessSorting::ProcessName => {
-            // Don't repeat if false... it sorts by name by default anyways.
-            if proc_widget_state.is_process_sort_descending {
+            // Avoid reversing the whole list here, as that would undo any ties we've already sorted.
+            if is_descending {
+                to_sort_vec.sort_by_cached_key(|c| std::cmp::Reverse(c.name.to_lowercase()));
+            } else {
                 to_sort_vec.sort_by_cached_key(|c| c.name.to_lowercase());
-                if proc_widget_state.is_process_sort_descending {
-                    to_sort_vec.reverse();
-                }
             }
         }
         ProcessSorting::Command => {
-            to_sort_vec.sort_by_cached_key(|c| c.command.to_lowercase());
-            if proc_widget_state.is_process_sort_descending {
-                to_sort_vec.reverse();
+            if is_descending {
+                to_sort_vec.sort_by_cached_key(|c| std::cmp::Reverse(c.command.to_lowercase()));
+            } else {
+                to_sort_vec.sort_by_cached_key(|c| c.command.to_lowercase());
+            }
+        }
+        ProcessSorting::ExePath => {
+            if is_descending {
+                to_sort_vec.sort_by_cached_key(|c| std::cmp::Reverse(c.exe_path.to_lowercase()));
+            } else {
+                to_sort_vec.sort_by_cached_key(|c| c.exe_path.to_lowercase());
             }
         }
         ProcessSorting::Pid => {
-            if !proc_widget_state.is_grouped {
-                to_sort_vec.sort_by(|a, b| {
-                    utils::gen_util::get_ordering(
-                        a.pid,
-                        b.pid,
-                        proc_widget_state.is_process_sort_descending,
-                    )
-                });
+            if !is_grouped {
+                to_sort_vec
+                    .sort_by(|a, b| utils::gen_util::get_ordering(a.pid, b.pid, is_descending));
             }
         }
         ProcessSorting::ReadPerSecond => {
+            to_sort_vec
+                .sort_by(|a, b| utils::gen_util::get_ordering(a.rps_f64, b.rps_f64, is_descending));
+        }
+        ProcessSorting::WritePerSecond => {
+            to_sort_vec
+                .sort_by(|a, b| utils::gen_util::get_ordering(a.wps_f64, b.wps_f64, is_descending));
+        }
+        ProcessSorting::TotalRead => {
+            to_sort_vec
+                .sort_by(|a, b| utils::gen_util::get_ordering(a.tr_f64, b.tr_f64, is_descending));
+        }
+        ProcessSorting::TotalWrite => {
+            to_sort_vec
+                .sort_by(|a, b| utils::gen_util::get_ordering(a.tw_f64, b.tw_f64, is_descending));
+        }
+        ProcessSorting::State => {
+            if is_descending {
+                to_sort_vec
+                    .sort_by_cached_key(|c| std::cmp::Reverse(c.process_state.to_lowercase()));
+            } else {
+                to_sort_vec.sort_by_cached_key(|c| c.process_state.to_lowercase());
+            }
+        }
+        ProcessSorting::User => to_sort_vec.sort_by(|a, b| match (&a.user, &b.user) {
+            (Some(user_a), Some(user_b)) => utils::gen_util::get_ordering(
+                user_a.to_lowercase(),
+                user_b.to_lowercase(),
+                is_descending,
+            ),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }),
+        ProcessSorting::GpuPercent => {
             to_sort_vec.sort_by(|a, b| {
-                utils::gen_util::get_ordering(
-                    a.rps_f64,
-                    b.rps_f64,
-                    proc_widget_state.is_process_sort_descending,
-                )
+                utils::gen_util::get_ordering(a.gpu_util_percent, b.gpu_util_percent, is_descending)
             });
         }
-        ProcessSorting::WritePerSecond => {
+        ProcessSorting::GpuMem => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.gpu_mem_bytes, b.gpu_mem_bytes, is_descending)
+            });
+        }
+        ProcessSorting::Swap => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.swap_bytes, b.swap_bytes, is_descending)
+            });
+        }
+        ProcessSorting::TcpRetransmits => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.tcp_retransmits, b.tcp_retransmits, is_descending)
+            });
+        }
+        ProcessSorting::TcpRtt => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.tcp_rtt_us, b.tcp_rtt_us, is_descending)
+            });
+        }
+        ProcessSorting::Pod => to_sort_vec.sort_by(|a, b| match (&a.pod_name, &b.pod_name) {
+            (Some(pod_a), Some(pod_b)) => utils::gen_util::get_ordering(
+                pod_a.to_lowercase(),
+                pod_b.to_lowercase(),
+                is_descending,
+            ),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }),
+        ProcessSorting::Namespace => {
+            to_sort_vec.sort_by(|a, b| match (&a.pod_namespace, &b.pod_namespace) {
+                (Some(namespace_a), Some(namespace_b)) => utils::gen_util::get_ordering(
+                    namespace_a.to_lowercase(),
+                    namespace_b.to_lowercase(),
+                    is_descending,
+                ),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            })
+        }
+        ProcessSorting::StartTime => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.start_time, b.start_time, is_descending)
+            });
+        }
+        ProcessSorting::Runtime => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.runtime_secs, b.runtime_secs, is_descending)
+            });
+        }
+        ProcessSorting::FdCount => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.fd_count, b.fd_count, is_descending)
+            });
+        }
+        ProcessSorting::CpuTrend => {
             to_sort_vec.sort_by(|a, b| {
                 utils::gen_util::get_ordering(
-                    a.wps_f64,
-                    b.wps_f64,
-                    proc_widget_state.is_process_sort_descending,
+                    get_cpu_trend_average(&a.cpu_history),
+                    get_cpu_trend_average(&b.cpu_history),
+                    is_descending,
                 )
             });
         }
-        ProcessSorting::TotalRead => {
+        ProcessSorting::CpuTime => {
             to_sort_vec.sort_by(|a, b| {
                 utils::gen_util::get_ordering(
-                    a.tr_f64,
-                    b.tr_f64,
-                    proc_widget_state.is_process_sort_descending,
+                    a.cpu_time_delta_ms,
+                    b.cpu_time_delta_ms,
+                    is_descending,
                 )
             });
         }
-        ProcessSorting::TotalWrite => {
+        ProcessSorting::Capabilities => {
             to_sort_vec.sort_by(|a, b| {
                 utils::gen_util::get_ordering(
-                    a.tw_f64,
-                    b.tw_f64,
-                    proc_widget_state.is_process_sort_descending,
+                    a.cap_eff.map(u64::count_ones),
+                    b.cap_eff.map(u64::count_ones),
+                    is_descending,
                 )
             });
         }
-        ProcessSorting::State => {
-            to_sort_vec.sort_by_cached_key(|c| c.process_state.to_lowercase());
-            if proc_widget_state.is_process_sort_descending {
-                to_sort_vec.reverse();
-            }
+        ProcessSorting::Seccomp => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(a.seccomp_mode, b.seccomp_mode, is_descending)
+            });
         }
-        ProcessSorting::User => to_sort_vec.sort_by(|a, b| match (&a.user, &b.user) {
-            (Some(user_a), Some(user_b)) => utils::gen_util::get_ordering(
-                user_a.to_lowercase(),
-                user_b.to_lowercase(),
-                proc_widget_state.is_process_sort_descending,
-            ),
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (None, None) => std::cmp::Ordering::Less,
-        }),
         ProcessSorting::Count => {
-            if proc_widget_state.is_grouped {
+            if is_grouped {
                 to_sort_vec.sort_by(|a, b| {
                     utils::gen_util::get_ordering(
                         a.group_pids.len(),
                         b.group_pids.len(),
-                        proc_widget_state.is_process_sort_descending,
+                        is_descending,
                     )
                 });
             }
@@ -637,25 +982,46 @@ pub fn create_collection_thread(
         BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>,
     >,
     control_receiver: std::sync::mpsc::Receiver<ThreadControlEvent>,
-    termination_ctrl_lock: Arc<Mutex<bool>>, termination_ctrl_cvar: Arc<Condvar>,
-    app_config_fields: &app::AppConfigFields, filters: app::DataFilters,
-    used_widget_set: UsedWidgets,
+    termination_ctrl_lock: Arc<Mutex<bool>>, app_config_fields: &app::AppConfigFields,
+    filters: app::DataFilters, used_widget_set: UsedWidgets,
+    plugins: Vec<data_harvester::plugins::PluginConfig>,
+    stream_server: Option<data_harvester::stream::StreamServer>,
+    mut exporters: Vec<data_harvester::exporter::Exporter>, collect_fd_counts: bool,
+    collect_process_status: bool,
+    battery_alert: Option<data_harvester::batteries::alert::BatteryAlertConfig>,
+    log_files: Vec<(u64, std::path::PathBuf)>,
 ) -> std::thread::JoinHandle<()> {
-    let temp_type = app_config_fields.temperature_type.clone();
     let use_current_cpu_total = app_config_fields.use_current_cpu_total;
     let show_average_cpu = app_config_fields.show_average_cpu;
     let update_rate_in_milliseconds = app_config_fields.update_rate_in_milliseconds;
+    let show_gpu_process_columns = app_config_fields.show_gpu_process_columns;
+    #[cfg(all(feature = "ebpf", target_os = "linux"))]
+    let show_tcp_process_columns = app_config_fields.show_tcp_process_columns;
+    let show_k8s_process_columns = app_config_fields.show_k8s_process_columns;
+    let include_child_cpu_time = app_config_fields.include_child_cpu_time;
+    let resolve_hostnames = app_config_fields.resolve_hostnames;
 
     thread::spawn(move || {
         let mut data_state = data_harvester::DataCollector::new(filters);
 
         data_state.set_collected_data(used_widget_set);
-        data_state.set_temperature_type(temp_type);
         data_state.set_use_current_cpu_total(use_current_cpu_total);
         data_state.set_show_average_cpu(show_average_cpu);
+        data_state.set_collect_gpu_processes(show_gpu_process_columns);
+        #[cfg(all(feature = "ebpf", target_os = "linux"))]
+        data_state.set_collect_tcp_processes(show_tcp_process_columns);
+        data_state.set_collect_k8s_processes(show_k8s_process_columns);
+        data_state.set_include_child_cpu_time(include_child_cpu_time);
+        data_state.set_resolve_hostnames(resolve_hostnames);
+        data_state.set_collect_fd_counts(collect_fd_counts);
+        data_state.set_collect_process_status(collect_process_status);
+        data_state.set_plugins(plugins);
+        data_state.set_battery_alert(battery_alert);
+        data_state.set_log_files(log_files);
 
         data_state.init();
 
+        let mut update_time = update_rate_in_milliseconds;
         loop {
             // Check once at the very top...
             if let Ok(is_terminated) = termination_ctrl_lock.try_lock() {
@@ -666,27 +1032,6 @@ pub fn create_collection_thread(
                 }
             }
 
-            let mut update_time = update_rate_in_milliseconds;
-            if let Ok(message) = control_receiver.try_recv() {
-                // trace!("Received message in collection thread: {:?}", message);
-                match message {
-                    ThreadControlEvent::Reset => {
-                        data_state.data.cleanup();
-                    }
-                    ThreadControlEvent::UpdateConfig(app_config_fields) => {
-                        data_state.set_temperature_type(app_config_fields.temperature_type.clone());
-                        data_state
-                            .set_use_current_cpu_total(app_config_fields.use_current_cpu_total);
-                        data_state.set_show_average_cpu(app_config_fields.show_average_cpu);
-                    }
-                    ThreadControlEvent::UpdateUsedWidgets(used_widget_set) => {
-                        data_state.set_collected_data(*used_widget_set);
-                    }
-                    ThreadControlEvent::UpdateUpdateTime(new_time) => {
-                        update_time = new_time;
-                    }
-                }
-            }
             futures::executor::block_on(data_state.update_data());
 
             // Yet another check to bail if needed...
@@ -698,21 +1043,77 @@ pub fn create_collection_thread(
                 }
             }
 
+            if let Some(stream_server) = &stream_server {
+                stream_server.broadcast(&data_state.data);
+            }
+
+            for exporter in &mut exporters {
+                exporter.push(&data_state.data);
+            }
+
             let event = BottomEvent::Update(Box::from(data_state.data));
             data_state.data = data_harvester::Data::default();
             if sender.send(event).is_err() {
                 break;
             }
 
-            if let Ok((is_terminated, _wait_timeout_result)) = termination_ctrl_cvar.wait_timeout(
-                termination_ctrl_lock.lock().unwrap(),
-                Duration::from_millis(update_time),
-            ) {
-                if *is_terminated {
-                    drop(is_terminated);
-                    break;
+            // Wait for the next update.  Control events are handled as soon as they come in rather
+            // than after the wait, so something like a newly shown widget doesn't have to sit out
+            // a slow update rate.  The other end of the channel being dropped means the app is
+            // shutting down, so that ends the thread too.
+            let wait_start = Instant::now();
+            while let Some(remaining_time) =
+                Duration::from_millis(update_time).checked_sub(wait_start.elapsed())
+            {
+                match control_receiver.recv_timeout(remaining_time) {
+                    Ok(message) => {
+                        if handle_thread_control_event(&mut data_state, &mut update_time, message) {
+                            break;
+                        }
+                    }
+                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => break,
+                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => return,
                 }
             }
         }
     })
 }
+
+/// Applies a control event to the collector.  Returns whether to harvest again right away,
+/// instead of waiting for the next update.
+fn handle_thread_control_event(
+    data_state: &mut data_harvester::DataCollector, update_time: &mut u64,
+    message: ThreadControlEvent,
+) -> bool {
+    // trace!("Received message in collection thread: {:?}", message);
+    match message {
+        ThreadControlEvent::Reset => {
+            data_state.data.cleanup();
+            true
+        }
+        ThreadControlEvent::UpdateConfig(app_config_fields) => {
+            data_state.set_use_current_cpu_total(app_config_fields.use_current_cpu_total);
+            data_state.set_show_average_cpu(app_config_fields.show_average_cpu);
+            data_state.set_include_child_cpu_time(app_config_fields.include_child_cpu_time);
+            data_state.set_resolve_hostnames(app_config_fields.resolve_hostnames);
+            false
+        }
+        ThreadControlEvent::UpdateUsedWidgets(used_widget_set) => {
+            data_state.set_collected_data(*used_widget_set);
+            data_state.set_up_harvesters();
+            true
+        }
+        ThreadControlEvent::UpdateUpdateTime(new_time) => {
+            *update_time = new_time;
+            false
+        }
+        ThreadControlEvent::UpdateCollectFdCounts(collect_fd_counts) => {
+            data_state.set_collect_fd_counts(collect_fd_counts);
+            false
+        }
+        ThreadControlEvent::UpdateCollectProcessStatus(collect_process_status) => {
+            data_state.set_collect_process_status(collect_process_status);
+            false
+        }
+    }
+}
@@ -5,15 +5,35 @@ use std::{
     collections::{HashMap, HashSet},
     path::PathBuf,
     str::FromStr,
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use crate::{
-    app::{layout_manager::*, *},
-    canvas::ColourScheme,
+    app::{
+        data_farmer::{DataRetention, PressureWeights},
+        keybinds::Keybinds,
+        layout_manager::*,
+        process_rules::{ProcessRuleConfig, ProcessRules},
+        *,
+    },
+    canvas::{canvas_colours::colour_utils::get_style_from_config, ColourScheme},
     constants::*,
-    units::data_units::DataUnit,
-    utils::error::{self, BottomError},
+    data_harvester::{
+        batteries::alert::BatteryAlertConfig, exporter::ExporterConfig, plugins::PluginConfig,
+        processes::ProcessSorting, remote::RemoteConfig,
+    },
+    dump::SummaryOutput,
+    units::{
+        data_units::DataUnit,
+        formatting::{NumberFormat, UnitPrefix},
+    },
+    utils::{
+        error::{self, BottomError},
+        gen_util::{
+            GIBI_LIMIT_F64, GIGA_LIMIT_F64, KIBI_LIMIT_F64, KILO_LIMIT_F64, MEBI_LIMIT_F64,
+            MEGA_LIMIT_F64, TEBI_LIMIT_F64, TERA_LIMIT_F64,
+        },
+    },
 };
 
 use typed_builder::*;
@@ -31,8 +51,21 @@ pub struct Config {
     pub row: Option<Vec<Row>>,
     pub disk_filter: Option<IgnoreList>,
     pub mount_filter: Option<IgnoreList>,
+    pub fs_filter: Option<IgnoreList>,
     pub temp_filter: Option<IgnoreList>,
+    pub temp_alias: Option<HashMap<String, String>>,
+    pub temp_unit: Option<HashMap<String, String>>,
     pub net_filter: Option<IgnoreList>,
+    pub user_filter: Option<IgnoreList>,
+    pub plugin: Option<Vec<PluginConfig>>,
+    pub remote: Option<Vec<RemoteConfig>>,
+    pub exporter: Option<Vec<ExporterConfig>>,
+    pub battery_alert: Option<BatteryAlertConfig>,
+    pub retention: Option<RetentionConfig>,
+    pub pressure_weights: Option<PressureWeightsConfig>,
+    pub graph_style: Option<GraphStyleConfig>,
+    pub process_rule: Option<Vec<ProcessRuleConfig>>,
+    pub keybinds: Option<HashMap<String, String>>,
 }
 
 impl Config {
@@ -47,6 +80,40 @@ impl Config {
     }
 }
 
+/// How long each graph keeps its history for, in milliseconds.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RetentionConfig {
+    pub cpu: Option<u64>,
+    pub mem: Option<u64>,
+    pub net: Option<u64>,
+    pub custom: Option<u64>,
+    pub sched: Option<u64>,
+    pub power: Option<u64>,
+}
+
+/// How much each part of the pressure widget's score counts for, relative to the others.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct PressureWeightsConfig {
+    pub cpu: Option<f64>,
+    pub mem: Option<f64>,
+    pub psi: Option<f64>,
+    pub io_wait: Option<f64>,
+}
+
+/// How each kind of graph is drawn, as "braille", "block", "dot", or "filled".  Any graph not set
+/// uses the default.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct GraphStyleConfig {
+    pub default: Option<String>,
+    pub cpu: Option<String>,
+    pub mem: Option<String>,
+    pub net: Option<String>,
+    pub battery: Option<String>,
+    pub custom: Option<String>,
+    pub sched: Option<String>,
+    pub power: Option<String>,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, TypedBuilder)]
 pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
@@ -67,6 +134,9 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub current_usage: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub cpu_top_processes: Option<bool>,
+
     #[builder(default, setter(strip_option))]
     pub group_processes: Option<bool>,
 
@@ -82,6 +152,12 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub basic: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub basic_meters: Option<Vec<String>>,
+
+    #[builder(default, setter(strip_option))]
+    pub mini: Option<bool>,
+
     #[builder(default, setter(strip_option))]
     pub default_time_value: Option<u64>,
 
@@ -94,6 +170,12 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub hide_time: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub time_labels: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub time_format: Option<String>,
+
     #[builder(default, setter(strip_option))]
     pub default_widget_type: Option<String>,
 
@@ -112,6 +194,9 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub disable_click: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub disk_inodes: Option<bool>,
+
     #[builder(default, setter(strip_option))]
     pub no_write: Option<bool>,
 
@@ -164,8 +249,74 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub network_use_log: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub network_split: Option<bool>,
+
     #[builder(default, setter(strip_option))]
     pub network_use_binary_prefix: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub network_max: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub mem_graph_as_value: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub memory_use_decimal_prefix: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub unit_prefix: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub decimal_places: Option<u64>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_gpu: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_tcp: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_k8s: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_child_cpu: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub resolve_hostnames: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub persist_history: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub history_path: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub history_retention: Option<u64>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_columns: Option<Vec<String>>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_sort: Option<Vec<String>>,
+
+    #[builder(default, setter(strip_option))]
+    pub session_summary: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub session_summary_path: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub stream_address: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub stream_token: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub snapshot_dir: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub frame_budget: Option<u64>,
 }
 
 #[derive(Clone, Default, Debug, Deserialize, Serialize)]
@@ -252,7 +403,21 @@ pub fn build_app(
     let autohide_time = get_autohide_time(&matches, &config);
     let default_time_value = get_default_time_value(&matches, &config)
         .context("Update 'default_time_value' in your config file.")?;
+    let history_path = get_history_path(matches, config);
+    let history_retention =
+        get_history_retention(config).context("Update 'history_retention' in your config file.")?;
+    // Graphs keep at least what they show by default and, when saving history, what's saved.
+    let default_retention = if history_path.is_some() {
+        STALE_MAX_MILLISECONDS
+            .max(default_time_value)
+            .max(history_retention)
+    } else {
+        STALE_MAX_MILLISECONDS.max(default_time_value)
+    };
+    let retention = get_data_retention(config, default_time_value, default_retention)
+        .context("Update the [retention] section of your config file.")?;
     let use_basic_mode = get_use_basic_mode(&matches, &config);
+    let use_mini_mode = get_use_mini_mode(&matches, &config);
 
     // For processes
     let is_grouped = get_app_grouping(matches, config);
@@ -268,6 +433,18 @@ pub fn build_app(
     let mut temp_state_map: HashMap<u64, TempWidgetState> = HashMap::new();
     let mut disk_state_map: HashMap<u64, DiskWidgetState> = HashMap::new();
     let mut battery_state_map: HashMap<u64, BatteryWidgetState> = HashMap::new();
+    let mut services_state_map: HashMap<u64, ServicesWidgetState> = HashMap::new();
+    let mut ports_state_map: HashMap<u64, PortsWidgetState> = HashMap::new();
+    let mut connections_state_map: HashMap<u64, ConnectionsWidgetState> = HashMap::new();
+    let mut cgroups_state_map: HashMap<u64, CgroupsWidgetState> = HashMap::new();
+    let mut docker_state_map: HashMap<u64, DockerWidgetState> = HashMap::new();
+    let mut vms_state_map: HashMap<u64, VmsWidgetState> = HashMap::new();
+    let mut custom_state_map: HashMap<u64, CustomWidgetState> = HashMap::new();
+    let mut sched_state_map: HashMap<u64, SchedWidgetState> = HashMap::new();
+    let mut power_state_map: HashMap<u64, PowerWidgetState> = HashMap::new();
+    let mut remote_state_map: HashMap<u64, RemoteWidgetState> = HashMap::new();
+    let mut log_state_map: HashMap<u64, LogWidgetState> = HashMap::new();
+    let mut proc_events_state_map: HashMap<u64, ProcEventsWidgetState> = HashMap::new();
 
     let autohide_timer = if autohide_time {
         Some(Instant::now())
@@ -283,11 +460,33 @@ pub fn build_app(
     let show_memory_as_values = get_mem_as_value(matches, config);
     let is_default_tree = get_is_default_tree(matches, config);
     let is_default_command = get_is_default_process_command(matches, config);
+    let show_gpu_process_columns = get_show_gpu_process_columns(matches, config);
+    let show_tcp_process_columns = get_show_tcp_process_columns(matches, config)
+        .context("Update 'process_tcp' in your config file.")?;
+    let show_k8s_process_columns = get_show_k8s_process_columns(matches, config)
+        .context("Update 'process_k8s' in your config file.")?;
+    let process_columns =
+        get_process_columns(config).context("Update 'process_columns' in your config file.")?;
+    let process_sort =
+        get_process_sort(config).context("Update 'process_sort' in your config file.")?;
     let is_advanced_kill = !get_is_advanced_kill_disabled(matches, config);
 
     let network_unit_type = get_network_unit_type(matches, config);
     let network_scale_type = get_network_scale_type(matches, config);
-    let network_use_binary_prefix = get_network_use_binary_prefix(matches, config);
+    let number_format = get_number_format(matches, config)
+        .context("Update 'unit_prefix' or 'decimal_places' in your config file.")?;
+    // An explicit unit prefix applies everywhere, so graphs and legends agree with the tables.
+    let network_use_binary_prefix = match number_format.unit_prefix {
+        Some(unit_prefix) => unit_prefix == UnitPrefix::Iec,
+        None => get_network_use_binary_prefix(matches, config),
+    };
+    let memory_use_decimal_prefix = match number_format.unit_prefix {
+        Some(unit_prefix) => unit_prefix == UnitPrefix::Si,
+        None => get_memory_use_decimal_prefix(matches, config),
+    };
+    let network_max =
+        get_network_max(matches, config).context("Update 'network_max' in your config file.")?;
+    let show_mem_graph_as_value = get_mem_graph_as_value(matches, config);
 
     for row in &widget_layout.rows {
         for col in &row.children {
@@ -337,7 +536,11 @@ pub fn build_app(
                         Mem => {
                             mem_state_map.insert(
                                 widget.widget_id,
-                                MemWidgetState::init(default_time_value, autohide_timer),
+                                MemWidgetState::init(
+                                    default_time_value,
+                                    autohide_timer,
+                                    show_mem_graph_as_value,
+                                ),
                             );
                         }
                         Net => {
@@ -346,24 +549,30 @@ pub fn build_app(
                                 NetWidgetState::init(
                                     default_time_value,
                                     autohide_timer,
+                                    network_max,
                                     // network_unit_type.clone(),
                                     // network_scale_type.clone(),
                                 ),
                             );
                         }
                         Proc => {
-                            proc_state_map.insert(
-                                widget.widget_id,
-                                ProcWidgetState::init(
-                                    is_case_sensitive,
-                                    is_match_whole_word,
-                                    is_use_regex,
-                                    is_grouped,
-                                    show_memory_as_values,
-                                    is_default_tree,
-                                    is_default_command,
-                                ),
+                            let mut proc_widget_state = ProcWidgetState::init(
+                                is_case_sensitive,
+                                is_match_whole_word,
+                                is_use_regex,
+                                is_grouped,
+                                show_memory_as_values,
+                                is_default_tree,
+                                is_default_command,
+                                show_gpu_process_columns,
+                                show_tcp_process_columns,
+                                show_k8s_process_columns,
+                                process_columns.as_deref(),
                             );
+                            if let Some(process_sort) = &process_sort {
+                                proc_widget_state.set_sort_keys(process_sort);
+                            }
+                            proc_state_map.insert(widget.widget_id, proc_widget_state);
                         }
                         Disk => {
                             disk_state_map.insert(widget.widget_id, DiskWidgetState::init());
@@ -375,6 +584,68 @@ pub fn build_app(
                             battery_state_map
                                 .insert(widget.widget_id, BatteryWidgetState::default());
                         }
+                        Services => {
+                            services_state_map
+                                .insert(widget.widget_id, ServicesWidgetState::init());
+                        }
+                        Ports => {
+                            ports_state_map.insert(widget.widget_id, PortsWidgetState::init());
+                        }
+                        Connections => {
+                            connections_state_map
+                                .insert(widget.widget_id, ConnectionsWidgetState::init());
+                        }
+                        Cgroups => {
+                            cgroups_state_map.insert(widget.widget_id, CgroupsWidgetState::init());
+                        }
+                        Docker => {
+                            docker_state_map.insert(widget.widget_id, DockerWidgetState::init());
+                        }
+                        Vms => {
+                            vms_state_map.insert(widget.widget_id, VmsWidgetState::init());
+                        }
+                        Custom => {
+                            custom_state_map.insert(
+                                widget.widget_id,
+                                CustomWidgetState::init(default_time_value, autohide_timer),
+                            );
+                        }
+                        Sched => {
+                            sched_state_map.insert(
+                                widget.widget_id,
+                                SchedWidgetState::init(default_time_value, autohide_timer),
+                            );
+                        }
+                        Power => {
+                            power_state_map.insert(
+                                widget.widget_id,
+                                PowerWidgetState::init(default_time_value, autohide_timer),
+                            );
+                        }
+                        Remote => {
+                            remote_state_map.insert(
+                                widget.widget_id,
+                                RemoteWidgetState::init(
+                                    get_remote_host(widget, config)
+                                        .context("Update 'host' in your layout.")?,
+                                ),
+                            );
+                        }
+                        Log => {
+                            log_state_map.insert(
+                                widget.widget_id,
+                                LogWidgetState::init(
+                                    get_log_file(widget)
+                                        .context("Update 'file' in your layout.")?,
+                                    get_log_highlights(widget)
+                                        .context("Update 'highlight' in your layout.")?,
+                                ),
+                            );
+                        }
+                        ProcEvents => {
+                            proc_events_state_map
+                                .insert(widget.widget_id, ProcEventsWidgetState::default());
+                        }
                         _ => {}
                     }
                 }
@@ -412,23 +683,36 @@ pub fn build_app(
             .context("Update 'rate' in your config file.")?,
         temperature_type: get_temperature(matches, config)
             .context("Update 'temperature_type' in your config file.")?,
+        temperature_units: get_temperature_units(config)
+            .context("Update 'temp_unit' in your config file.")?,
         show_average_cpu: get_show_average_cpu(matches, config),
-        use_dot: get_use_dot(matches, config),
+        graph_styles: get_graph_styles(matches, config)
+            .context("Update the [graph_style] section of your config file.")?,
         left_legend: get_use_left_legend(matches, config),
         use_current_cpu_total: get_use_current_cpu_total(matches, config),
+        show_top_cpu_processes: get_show_top_cpu_processes(matches, config),
         use_basic_mode,
+        use_mini_mode,
         default_time_value,
         time_interval: get_time_interval(matches, config)
             .context("Update 'time_delta' in your config file.")?,
         hide_time: get_hide_time(matches, config),
         autohide_time,
+        time_label_style: get_time_label_style(matches, config)
+            .context("Update 'time_labels' in your config file.")?,
+        clock_format: get_clock_format(matches, config)
+            .context("Update 'time_format' in your config file.")?,
         use_old_network_legend: get_use_old_network_legend(matches, config),
+        network_split: get_network_split(matches, config),
         table_gap: if get_hide_table_gap(matches, config) {
             0
         } else {
             1
         },
         disable_click: get_disable_click(matches, config),
+        show_disk_inodes: get_show_disk_inodes(matches, config),
+        disk_io_per_device: false,
+        group_disks_by_fs: false,
         // no_write: get_no_write(matches, config),
         no_write: false,
         show_table_scroll_position: get_show_table_scroll_position(matches, config),
@@ -436,26 +720,49 @@ pub fn build_app(
         network_scale_type,
         network_unit_type,
         network_use_binary_prefix,
+        network_max,
+        memory_use_decimal_prefix,
+        number_format,
+        show_gpu_process_columns,
+        show_tcp_process_columns,
+        show_k8s_process_columns,
+        include_child_cpu_time: get_include_child_cpu_time(matches, config),
+        resolve_hostnames: get_resolve_hostnames(matches, config),
+        history_path,
+        // There's no point saving more history than the graphs keep.
+        history_retention: history_retention.min(retention.get_max()),
+        session_summary: get_session_summary(matches, config),
+        stream_address: get_stream_address(matches, config),
+        stream_token: get_stream_token(config),
+        snapshot_dir: get_snapshot_dir(config),
+        retention,
+        pressure_weights: get_pressure_weights(config)
+            .context("Update the [pressure_weights] section of your config file.")?,
+        frame_budget: get_frame_budget(config),
     };
 
-    let used_widgets = UsedWidgets {
-        use_cpu: used_widget_set.get(&Cpu).is_some() || used_widget_set.get(&BasicCpu).is_some(),
-        use_mem: used_widget_set.get(&Mem).is_some() || used_widget_set.get(&BasicMem).is_some(),
-        use_net: used_widget_set.get(&Net).is_some() || used_widget_set.get(&BasicNet).is_some(),
-        use_proc: used_widget_set.get(&Proc).is_some(),
-        use_disk: used_widget_set.get(&Disk).is_some(),
-        use_temp: used_widget_set.get(&Temp).is_some(),
-        use_battery: used_widget_set.get(&Battery).is_some(),
-    };
+    let process_rules = get_process_rules(config)
+        .context("Update the [[process_rule]] sections of your config file.")?;
+
+    let mut used_widgets = UsedWidgets::from_widget_types(used_widget_set.iter());
+    used_widgets.apply_config_overrides(
+        &app_config_fields,
+        used_widget_set.contains(&BasicBattery),
+        !process_rules.is_empty(),
+    );
 
     let disk_filter =
         get_ignore_list(&config.disk_filter).context("Update 'disk_filter' in your config file")?;
     let mount_filter = get_ignore_list(&config.mount_filter)
         .context("Update 'mount_filter' in your config file")?;
+    let fs_filter =
+        get_ignore_list(&config.fs_filter).context("Update 'fs_filter' in your config file")?;
     let temp_filter =
         get_ignore_list(&config.temp_filter).context("Update 'temp_filter' in your config file")?;
     let net_filter =
         get_ignore_list(&config.net_filter).context("Update 'net_filter' in your config file")?;
+    let user_filter =
+        get_ignore_list(&config.user_filter).context("Update 'user_filter' in your config file")?;
 
     // One more thing - we have to update the search settings of our proc_state_map, and create the hashmaps if needed!
     // Note that if you change your layout, this might not actually match properly... not sure if/where we should deal with that...
@@ -508,6 +815,18 @@ pub fn build_app(
         .disk_state(DiskState::init(disk_state_map))
         .temp_state(TempState::init(temp_state_map))
         .battery_state(BatteryState::init(battery_state_map))
+        .services_state(ServicesState::init(services_state_map))
+        .ports_state(PortsState::init(ports_state_map))
+        .connections_state(ConnectionsState::init(connections_state_map))
+        .cgroups_state(CgroupsState::init(cgroups_state_map))
+        .docker_state(DockerState::init(docker_state_map))
+        .vms_state(VmsState::init(vms_state_map))
+        .custom_state(CustomState::init(custom_state_map))
+        .sched_state(SchedState::init(sched_state_map))
+        .power_state(PowerState::init(power_state_map))
+        .remote_state(RemoteState::init(remote_state_map))
+        .log_state(LogState::init(log_state_map))
+        .proc_events_state(ProcEventsState::init(proc_events_state_map))
         .basic_table_widget_state(basic_table_widget_state)
         .current_widget(widget_map.get(&initial_widget_id).unwrap().clone()) // TODO: [UNWRAP] - many of the unwraps are fine (like this one) but do a once-over and/or switch to expect?
         .widget_map(widget_map)
@@ -515,9 +834,16 @@ pub fn build_app(
         .filters(DataFilters {
             disk_filter,
             mount_filter,
+            fs_filter,
             temp_filter,
             net_filter,
+            user_filter,
+            temp_aliases: config.temp_alias.clone().unwrap_or_default(),
         })
+        .keybinds(
+            get_keybinds(config).context("Update the [keybinds] section of your config file.")?,
+        )
+        .process_rules(process_rules)
         .config(config.clone())
         .config_path(config_path)
         .build())
@@ -534,7 +860,10 @@ pub fn get_widget_layout(
     let bottom_layout = if get_use_basic_mode(matches, config) {
         default_widget_id = DEFAULT_WIDGET_ID;
 
-        BottomLayout::init_basic_default(get_use_battery(matches, config))
+        BottomLayout::init_basic_default(
+            get_use_battery(matches, config),
+            &get_basic_meters(config)?,
+        )
     } else {
         let ref_row: Vec<Row>; // Required to handle reference
         let rows = match &config.row {
@@ -603,10 +932,11 @@ fn get_update_rate_in_milliseconds(
         DEFAULT_REFRESH_RATE_IN_MILLISECONDS as u128
     };
 
-    if update_rate_in_milliseconds < 250 {
-        return Err(BottomError::ConfigError(
-            "set your update rate to be at least 250 milliseconds.".to_string(),
-        ));
+    if update_rate_in_milliseconds < MIN_REFRESH_RATE_IN_MILLISECONDS as u128 {
+        return Err(BottomError::ConfigError(format!(
+            "set your update rate to be at least {} milliseconds.",
+            MIN_REFRESH_RATE_IN_MILLISECONDS
+        )));
     } else if update_rate_in_milliseconds as u128 > std::u64::MAX as u128 {
         return Err(BottomError::ConfigError(
             "set your update rate to be at most unsigned INT_MAX.".to_string(),
@@ -628,20 +958,38 @@ fn get_temperature(
     } else if let Some(flags) = &config.flags {
         if let Some(temp_type) = &flags.temperature_type {
             // Give lowest priority to config.
-            return match temp_type.as_str() {
-                "fahrenheit" | "f" => Ok(data_harvester::temperature::TemperatureType::Fahrenheit),
-                "kelvin" | "k" => Ok(data_harvester::temperature::TemperatureType::Kelvin),
-                "celsius" | "c" => Ok(data_harvester::temperature::TemperatureType::Celsius),
-                _ => Err(BottomError::ConfigError(format!(
-                    "\"{}\" is an invalid temperature type, use \"<kelvin|k|celsius|c|fahrenheit|f>\".",
-                    temp_type
-                ))),
-            };
+            return parse_temperature_type(temp_type);
         }
     }
     Ok(data_harvester::temperature::TemperatureType::Celsius)
 }
 
+fn parse_temperature_type(
+    temp_type: &str,
+) -> error::Result<data_harvester::temperature::TemperatureType> {
+    match temp_type {
+        "fahrenheit" | "f" => Ok(data_harvester::temperature::TemperatureType::Fahrenheit),
+        "kelvin" | "k" => Ok(data_harvester::temperature::TemperatureType::Kelvin),
+        "celsius" | "c" => Ok(data_harvester::temperature::TemperatureType::Celsius),
+        _ => Err(BottomError::ConfigError(format!(
+            "\"{}\" is an invalid temperature type, use \"<kelvin|k|celsius|c|fahrenheit|f>\".",
+            temp_type
+        ))),
+    }
+}
+
+/// Gets the units set for particular temperature sensors, which override the usual one.
+fn get_temperature_units(
+    config: &Config,
+) -> error::Result<HashMap<String, data_harvester::temperature::TemperatureType>> {
+    config
+        .temp_unit
+        .iter()
+        .flatten()
+        .map(|(name, temp_type)| Ok((name.clone(), parse_temperature_type(temp_type)?)))
+        .collect()
+}
+
 /// Yes, this function gets whether to show average CPU (true) or not (false)
 fn get_show_average_cpu(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
     if matches.is_present("hide_avg_cpu") {
@@ -666,6 +1014,51 @@ fn get_use_dot(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
     false
 }
 
+/// The dot marker flag is kept as a shorthand for making dots the default style.  Given on the
+/// command line, it overrides the config file's default.
+fn get_graph_styles(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<GraphStyles> {
+    fn get_style(
+        name: &str, style: &Option<String>, default: GraphStyle,
+    ) -> error::Result<GraphStyle> {
+        match style.as_ref().map(|style| style.to_lowercase()).as_deref() {
+            None => Ok(default),
+            Some("braille") => Ok(GraphStyle::Braille),
+            Some("block") => Ok(GraphStyle::Block),
+            Some("dot") => Ok(GraphStyle::Dot),
+            Some("filled") => Ok(GraphStyle::Filled),
+            Some(_) => Err(BottomError::ConfigError(format!(
+                "set the {} graph style to be one of \"braille\", \"block\", \"dot\", or \"filled\".",
+                name
+            ))),
+        }
+    }
+
+    let style_config = config.graph_style.clone().unwrap_or_default();
+
+    let default = if matches.is_present("dot_marker") {
+        GraphStyle::Dot
+    } else {
+        let fallback = if get_use_dot(matches, config) {
+            GraphStyle::Dot
+        } else {
+            GraphStyle::Braille
+        };
+        get_style("default", &style_config.default, fallback)?
+    };
+
+    Ok(GraphStyles {
+        cpu: get_style("cpu", &style_config.cpu, default)?,
+        mem: get_style("mem", &style_config.mem, default)?,
+        net: get_style("net", &style_config.net, default)?,
+        battery: get_style("battery", &style_config.battery, default)?,
+        custom: get_style("custom", &style_config.custom, default)?,
+        sched: get_style("sched", &style_config.sched, default)?,
+        power: get_style("power", &style_config.power, default)?,
+    })
+}
+
 fn get_use_left_legend(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
     if matches.is_present("left_legend") {
         return true;
@@ -690,6 +1083,18 @@ fn get_use_current_cpu_total(matches: &clap::ArgMatches<'static>, config: &Confi
     false
 }
 
+fn get_show_top_cpu_processes(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("cpu_top_processes") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(cpu_top_processes) = flags.cpu_top_processes {
+            return cpu_top_processes;
+        }
+    }
+
+    false
+}
+
 fn get_use_basic_mode(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
     if matches.is_present("basic") {
         return true;
@@ -699,9 +1104,42 @@ fn get_use_basic_mode(matches: &clap::ArgMatches<'static>, config: &Config) -> b
         }
     }
 
+    // Mini mode is a smaller basic mode.
+    get_use_mini_mode(matches, config)
+}
+
+fn get_use_mini_mode(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("mini") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(mini) = flags.mini {
+            return mini;
+        }
+    }
+
     false
 }
 
+fn get_basic_meters(config: &Config) -> error::Result<Vec<BasicMeter>> {
+    if let Some(basic_meters) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.basic_meters.as_ref())
+    {
+        let mut meters = Vec::with_capacity(basic_meters.len());
+        for meter in basic_meters {
+            let meter = meter.parse::<BasicMeter>()?;
+            if !meters.contains(&meter) {
+                meters.push(meter);
+            }
+        }
+
+        Ok(meters)
+    } else {
+        Ok(DEFAULT_BASIC_METERS.to_vec())
+    }
+}
+
 fn get_default_time_value(
     matches: &clap::ArgMatches<'static>, config: &Config,
 ) -> error::Result<u64> {
@@ -717,14 +1155,15 @@ fn get_default_time_value(
         DEFAULT_TIME_MILLISECONDS as u128
     };
 
-    if default_time < 30000 {
-        return Err(BottomError::ConfigError(
-            "set your default value to be at least 30000 milliseconds.".to_string(),
-        ));
-    } else if default_time as u128 > STALE_MAX_MILLISECONDS as u128 {
+    if default_time < STALE_MIN_MILLISECONDS as u128 {
+        return Err(BottomError::ConfigError(format!(
+            "set your default value to be at least {} milliseconds.",
+            STALE_MIN_MILLISECONDS
+        )));
+    } else if default_time as u128 > RETENTION_MAX_MILLISECONDS as u128 {
         return Err(BottomError::ConfigError(format!(
             "set your default value to be at most {} milliseconds.",
-            STALE_MAX_MILLISECONDS
+            RETENTION_MAX_MILLISECONDS
         )));
     }
 
@@ -825,6 +1264,34 @@ fn get_autohide_time(matches: &clap::ArgMatches<'static>, config: &Config) -> bo
     false
 }
 
+fn get_time_label_style(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<TimeLabelStyle> {
+    if let Some(time_labels) = matches.value_of("time_labels") {
+        return time_labels.parse();
+    } else if let Some(flags) = &config.flags {
+        if let Some(time_labels) = &flags.time_labels {
+            return time_labels.parse();
+        }
+    }
+
+    Ok(TimeLabelStyle::Relative)
+}
+
+fn get_clock_format(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<ClockFormat> {
+    if let Some(time_format) = matches.value_of("time_format") {
+        return time_format.parse();
+    } else if let Some(flags) = &config.flags {
+        if let Some(time_format) = &flags.time_format {
+            return time_format.parse();
+        }
+    }
+
+    Ok(ClockFormat::TwentyFourHour)
+}
+
 fn get_default_widget_and_count(
     matches: &clap::ArgMatches<'static>, config: &Config,
 ) -> error::Result<(Option<BottomWidgetType>, u64)> {
@@ -889,6 +1356,28 @@ fn get_disable_click(matches: &clap::ArgMatches<'static>, config: &Config) -> bo
     false
 }
 
+fn get_show_disk_inodes(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("disk_inodes") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(disk_inodes) = flags.disk_inodes {
+            return disk_inodes;
+        }
+    }
+    false
+}
+
+fn get_network_split(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("network_split") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(network_split) = flags.network_split {
+            return network_split;
+        }
+    }
+    false
+}
+
 fn get_use_old_network_legend(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
     if matches.is_present("use_old_network_legend") {
         return true;
@@ -1042,6 +1531,219 @@ fn get_is_default_process_command(matches: &clap::ArgMatches<'static>, config: &
     false
 }
 
+fn get_show_gpu_process_columns(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("process_gpu") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_gpu) = flags.process_gpu {
+            return process_gpu;
+        }
+    }
+    false
+}
+
+fn get_include_child_cpu_time(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("process_child_cpu") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_child_cpu) = flags.process_child_cpu {
+            return process_child_cpu;
+        }
+    }
+    false
+}
+
+fn get_resolve_hostnames(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("resolve_hostnames") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(resolve_hostnames) = flags.resolve_hostnames {
+            return resolve_hostnames;
+        }
+    }
+    false
+}
+
+fn get_show_tcp_process_columns(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<bool> {
+    let process_tcp = if matches.is_present("process_tcp") {
+        true
+    } else if let Some(flags) = &config.flags {
+        flags.process_tcp.unwrap_or(false)
+    } else {
+        false
+    };
+
+    if process_tcp && !cfg!(all(feature = "ebpf", target_os = "linux")) {
+        return Err(BottomError::ConfigError(
+            "TCP process columns are only supported on Linux, with bottom built using the \"ebpf\" feature.".to_string(),
+        ));
+    }
+
+    Ok(process_tcp)
+}
+
+fn get_show_k8s_process_columns(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<bool> {
+    let process_k8s = if matches.is_present("process_k8s") {
+        true
+    } else if let Some(flags) = &config.flags {
+        flags.process_k8s.unwrap_or(false)
+    } else {
+        false
+    };
+
+    if process_k8s && !cfg!(target_os = "linux") {
+        return Err(BottomError::ConfigError(
+            "Kubernetes process columns are only supported on Linux.".to_string(),
+        ));
+    }
+
+    Ok(process_k8s)
+}
+
+fn get_process_columns(config: &Config) -> error::Result<Option<Vec<ProcessSorting>>> {
+    if let Some(flags) = &config.flags {
+        if let Some(process_columns) = &flags.process_columns {
+            let mut columns = Vec::with_capacity(process_columns.len());
+            for column in process_columns {
+                let column = match column.to_lowercase().as_str() {
+                    "pid" | "count" => ProcessSorting::Pid,
+                    "name" => ProcessSorting::ProcessName,
+                    "path" => ProcessSorting::ExePath,
+                    "command" => ProcessSorting::Command,
+                    "cpu" | "cpu%" => ProcessSorting::CpuPercent,
+                    "mem" | "mem%" | "memory" | "virt" | "virtual" | "vsz" => ProcessSorting::Mem,
+                    "swap" => ProcessSorting::Swap,
+                    "r/s" | "read" => ProcessSorting::ReadPerSecond,
+                    "w/s" | "write" => ProcessSorting::WritePerSecond,
+                    "t.read" | "total_read" => ProcessSorting::TotalRead,
+                    "t.write" | "total_write" => ProcessSorting::TotalWrite,
+                    "user" => ProcessSorting::User,
+                    "state" => ProcessSorting::State,
+                    "gpu" | "gpu%" | "gmem" => ProcessSorting::GpuPercent,
+                    "tcp" | "retr" | "rtt" => ProcessSorting::TcpRetransmits,
+                    "pod" | "namespace" => ProcessSorting::Pod,
+                    "start" | "start_time" => ProcessSorting::StartTime,
+                    "runtime" => ProcessSorting::Runtime,
+                    "fds" | "fd" => ProcessSorting::FdCount,
+                    "trend" | "cpu_trend" => ProcessSorting::CpuTrend,
+                    "cpu_time" | "cpu_ms" => ProcessSorting::CpuTime,
+                    "caps" | "capabilities" => ProcessSorting::Capabilities,
+                    "seccomp" => ProcessSorting::Seccomp,
+                    _ => {
+                        return Err(BottomError::ConfigError(format!(
+                            "\"{}\" is an invalid process column.",
+                            column
+                        )));
+                    }
+                };
+                // Columns sharing a slot, like the name, path, and command, can only be listed once.
+                if !columns
+                    .iter()
+                    .any(|listed| get_column_slot(listed) == get_column_slot(&column))
+                {
+                    columns.push(column);
+                }
+            }
+
+            return Ok(Some(columns));
+        }
+    }
+    Ok(None)
+}
+
+fn get_process_sort(config: &Config) -> error::Result<Option<Vec<(ProcessSorting, bool)>>> {
+    if let Some(flags) = &config.flags {
+        if let Some(process_sort) = &flags.process_sort {
+            let mut sort_keys: Vec<(ProcessSorting, bool)> = Vec::with_capacity(process_sort.len());
+            for sort_key in process_sort {
+                let mut split = sort_key.split_whitespace();
+                let sort_type = match split.next().map(|column| column.to_lowercase()).as_deref() {
+                    Some("pid") => ProcessSorting::Pid,
+                    Some("count") => ProcessSorting::Count,
+                    Some("name") => ProcessSorting::ProcessName,
+                    Some("command") => ProcessSorting::Command,
+                    Some("path") => ProcessSorting::ExePath,
+                    Some("cpu") | Some("cpu%") => ProcessSorting::CpuPercent,
+                    Some("mem") | Some("memory") => ProcessSorting::Mem,
+                    Some("mem%") => ProcessSorting::MemPercent,
+                    Some("virt") | Some("virtual") | Some("vsz") => ProcessSorting::VirtMem,
+                    Some("swap") => ProcessSorting::Swap,
+                    Some("r/s") | Some("read") => ProcessSorting::ReadPerSecond,
+                    Some("w/s") | Some("write") => ProcessSorting::WritePerSecond,
+                    Some("t.read") | Some("total_read") => ProcessSorting::TotalRead,
+                    Some("t.write") | Some("total_write") => ProcessSorting::TotalWrite,
+                    Some("user") => ProcessSorting::User,
+                    Some("state") => ProcessSorting::State,
+                    Some("gpu") | Some("gpu%") => ProcessSorting::GpuPercent,
+                    Some("gmem") => ProcessSorting::GpuMem,
+                    Some("retr") => ProcessSorting::TcpRetransmits,
+                    Some("rtt") => ProcessSorting::TcpRtt,
+                    Some("pod") => ProcessSorting::Pod,
+                    Some("namespace") => ProcessSorting::Namespace,
+                    Some("start") | Some("start_time") => ProcessSorting::StartTime,
+                    Some("runtime") => ProcessSorting::Runtime,
+                    Some("fds") | Some("fd") => ProcessSorting::FdCount,
+                    Some("trend") | Some("cpu_trend") => ProcessSorting::CpuTrend,
+                    Some("cpu_time") | Some("cpu_ms") => ProcessSorting::CpuTime,
+                    Some("caps") | Some("capabilities") => ProcessSorting::Capabilities,
+                    Some("seccomp") => ProcessSorting::Seccomp,
+                    _ => {
+                        return Err(BottomError::ConfigError(format!(
+                            "\"{}\" is an invalid process sort column.",
+                            sort_key
+                        )));
+                    }
+                };
+                let is_descending = match split.next().map(|order| order.to_lowercase()).as_deref()
+                {
+                    Some("asc") => false,
+                    Some("desc") => true,
+                    None => is_sorted_descending_by_default(&sort_type),
+                    _ => {
+                        return Err(BottomError::ConfigError(format!(
+                            "\"{}\" has an invalid sort order, use \"asc\" or \"desc\".",
+                            sort_key
+                        )));
+                    }
+                };
+                if split.next().is_some() {
+                    return Err(BottomError::ConfigError(format!(
+                        "\"{}\" is an invalid process sort key.",
+                        sort_key
+                    )));
+                }
+
+                if !sort_keys.iter().any(|(existing, _)| *existing == sort_type) {
+                    sort_keys.push((sort_type, is_descending));
+                }
+            }
+
+            return Ok(Some(sort_keys));
+        }
+    }
+    Ok(None)
+}
+
+fn get_keybinds(config: &Config) -> error::Result<Keybinds> {
+    if let Some(keybinds) = &config.keybinds {
+        Keybinds::from_config(keybinds)
+    } else {
+        Ok(Keybinds::default())
+    }
+}
+
+fn get_process_rules(config: &Config) -> error::Result<ProcessRules> {
+    if let Some(process_rules) = &config.process_rule {
+        ProcessRules::from_config(process_rules)
+    } else {
+        Ok(ProcessRules::default())
+    }
+}
+
 fn get_is_advanced_kill_disabled(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
     if matches.is_present("disable_advanced_kill") {
         return true;
@@ -1091,3 +1793,379 @@ fn get_network_use_binary_prefix(matches: &clap::ArgMatches<'static>, config: &C
     }
     false
 }
+
+fn get_network_max(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<Option<f64>> {
+    let network_max = if let Some(network_max) = matches.value_of("network_max") {
+        network_max
+    } else if let Some(network_max) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.network_max.as_deref())
+    {
+        network_max
+    } else {
+        return Ok(None);
+    };
+
+    parse_network_rate(network_max).map(Some)
+}
+
+/// Parses a rate like `1Gbit`, `100Mb`, `125MB/s`, or `1GiB` into bits per second.  Unlike
+/// elsewhere, the case of the unit matters here: `b` is bits and `B` is bytes.  A plain number is
+/// taken as bits.
+fn parse_network_rate(rate: &str) -> error::Result<f64> {
+    let invalid_rate = || {
+        BottomError::ConfigError(format!(
+            "\"{}\" is not a valid rate, try something like \"1Gbit\" or \"100MB\".",
+            rate
+        ))
+    };
+
+    let rate = rate.trim();
+    let split_index = rate
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or_else(|| rate.len());
+    let (value, unit) = rate.split_at(split_index);
+    let unit = unit.trim();
+    let unit = unit
+        .strip_suffix("/s")
+        .or_else(|| unit.strip_suffix("ps"))
+        .unwrap_or(unit);
+
+    let (multiplier, unit) = if let Some(unit) = unit.strip_prefix("Ki") {
+        (KIBI_LIMIT_F64, unit)
+    } else if let Some(unit) = unit.strip_prefix("Mi") {
+        (MEBI_LIMIT_F64, unit)
+    } else if let Some(unit) = unit.strip_prefix("Gi") {
+        (GIBI_LIMIT_F64, unit)
+    } else if let Some(unit) = unit.strip_prefix("Ti") {
+        (TEBI_LIMIT_F64, unit)
+    } else if let Some(unit) = unit.strip_prefix(|c: char| c == 'k' || c == 'K') {
+        (KILO_LIMIT_F64, unit)
+    } else if let Some(unit) = unit.strip_prefix('M') {
+        (MEGA_LIMIT_F64, unit)
+    } else if let Some(unit) = unit.strip_prefix('G') {
+        (GIGA_LIMIT_F64, unit)
+    } else if let Some(unit) = unit.strip_prefix('T') {
+        (TERA_LIMIT_F64, unit)
+    } else {
+        (1.0, unit)
+    };
+
+    let bits_per_unit = match unit {
+        "" | "b" | "bit" | "bits" => 1.0,
+        "B" | "byte" | "bytes" => 8.0,
+        _ => return Err(invalid_rate()),
+    };
+
+    match value.parse::<f64>() {
+        Ok(value) if value > 0.0 => Ok(value * multiplier * bits_per_unit),
+        _ => Err(invalid_rate()),
+    }
+}
+
+fn get_mem_graph_as_value(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("mem_graph_as_value") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(mem_graph_as_value) = flags.mem_graph_as_value {
+            return mem_graph_as_value;
+        }
+    }
+    false
+}
+
+fn get_memory_use_decimal_prefix(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("memory_use_decimal_prefix") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(memory_use_decimal_prefix) = flags.memory_use_decimal_prefix {
+            return memory_use_decimal_prefix;
+        }
+    }
+    false
+}
+
+/// The most decimal places that can be shown.
+const MAX_DECIMAL_PLACES: u64 = 9;
+
+fn get_number_format(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<NumberFormat> {
+    let flags = config.flags.as_ref();
+
+    let unit_prefix = if let Some(unit_prefix) = matches.value_of("unit_prefix") {
+        Some(unit_prefix.parse::<UnitPrefix>()?)
+    } else if let Some(unit_prefix) = flags.and_then(|flags| flags.unit_prefix.as_deref()) {
+        Some(unit_prefix.parse::<UnitPrefix>()?)
+    } else {
+        None
+    };
+
+    let decimal_places = if let Some(decimal_places) = matches.value_of("decimal_places") {
+        Some(decimal_places.parse::<u64>().map_err(|_| {
+            BottomError::ConfigError(
+                "could not parse as a valid 64-bit unsigned integer".to_string(),
+            )
+        })?)
+    } else {
+        flags.and_then(|flags| flags.decimal_places)
+    };
+    if let Some(decimal_places) = decimal_places {
+        if decimal_places > MAX_DECIMAL_PLACES {
+            return Err(BottomError::ConfigError(format!(
+                "set your decimal places to be at most {}.",
+                MAX_DECIMAL_PLACES
+            )));
+        }
+    }
+
+    Ok(NumberFormat {
+        unit_prefix,
+        decimal_places: decimal_places.map(|decimal_places| decimal_places as usize),
+    })
+}
+
+fn get_history_path(matches: &clap::ArgMatches<'static>, config: &Config) -> Option<PathBuf> {
+    let persist_history = if matches.is_present("persist_history") {
+        true
+    } else if let Some(flags) = &config.flags {
+        flags.persist_history.unwrap_or(false)
+    } else {
+        false
+    };
+
+    if !persist_history {
+        return None;
+    }
+
+    if let Some(flags) = &config.flags {
+        if let Some(history_path) = &flags.history_path {
+            return Some(PathBuf::from(history_path));
+        }
+    }
+    dirs::data_dir().map(|data_path| data_path.join(DEFAULT_HISTORY_FILE_PATH))
+}
+
+/// A file given on the command line wins over the config file.  Otherwise, the summary goes to
+/// `session_summary_path` if set, or to stdout.
+fn get_session_summary(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> Option<SummaryOutput> {
+    if let Some(session_summary_path) = matches.value_of("session_summary") {
+        return Some(SummaryOutput::File(PathBuf::from(session_summary_path)));
+    }
+
+    let session_summary = if matches.is_present("session_summary") {
+        true
+    } else if let Some(flags) = &config.flags {
+        flags.session_summary.unwrap_or(false)
+    } else {
+        false
+    };
+
+    if !session_summary {
+        return None;
+    }
+
+    match config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.session_summary_path.as_ref())
+    {
+        Some(session_summary_path) => {
+            Some(SummaryOutput::File(PathBuf::from(session_summary_path)))
+        }
+        None => Some(SummaryOutput::Stdout),
+    }
+}
+
+fn get_stream_address(matches: &clap::ArgMatches<'static>, config: &Config) -> Option<String> {
+    if let Some(stream_address) = matches.value_of("stream_address") {
+        return Some(stream_address.to_string());
+    } else if let Some(flags) = &config.flags {
+        if let Some(stream_address) = &flags.stream_address {
+            return Some(stream_address.clone());
+        }
+    }
+    None
+}
+
+fn get_stream_token(config: &Config) -> Option<String> {
+    if let Some(flags) = &config.flags {
+        if let Some(stream_token) = &flags.stream_token {
+            return Some(stream_token.clone());
+        }
+    }
+    None
+}
+
+fn get_snapshot_dir(config: &Config) -> Option<PathBuf> {
+    if let Some(flags) = &config.flags {
+        if let Some(snapshot_dir) = &flags.snapshot_dir {
+            return Some(PathBuf::from(snapshot_dir));
+        }
+    }
+    None
+}
+
+/// A budget of 0 is the same as not having one.
+fn get_frame_budget(config: &Config) -> Option<Duration> {
+    config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.frame_budget)
+        .filter(|frame_budget| *frame_budget > 0)
+        .map(Duration::from_millis)
+}
+
+fn get_history_retention(config: &Config) -> error::Result<u64> {
+    let history_retention = if let Some(flags) = &config.flags {
+        flags.history_retention.unwrap_or(STALE_MAX_MILLISECONDS)
+    } else {
+        STALE_MAX_MILLISECONDS
+    };
+
+    if history_retention < 30000 {
+        return Err(BottomError::ConfigError(
+            "set your history retention to be at least 30000 milliseconds.".to_string(),
+        ));
+    } else if history_retention > RETENTION_MAX_MILLISECONDS {
+        return Err(BottomError::ConfigError(format!(
+            "set your history retention to be at most {} milliseconds.",
+            RETENTION_MAX_MILLISECONDS
+        )));
+    }
+
+    Ok(history_retention)
+}
+
+/// Each graph has to keep at least as much as it shows by default, so `default_time_value` is
+/// the least any graph can keep.  Graphs that aren't set keep `default_retention`.
+fn get_data_retention(
+    config: &Config, default_time_value: u64, default_retention: u64,
+) -> error::Result<DataRetention> {
+    let retention_config = config.retention.clone().unwrap_or_default();
+    let check_retention = |name: &str, retention: Option<u64>| -> error::Result<u64> {
+        let retention = retention.unwrap_or(default_retention);
+        if retention < default_time_value {
+            Err(BottomError::ConfigError(format!(
+                "set the {} retention to be at least the default time value, {} milliseconds.",
+                name, default_time_value
+            )))
+        } else if retention > RETENTION_MAX_MILLISECONDS {
+            Err(BottomError::ConfigError(format!(
+                "set the {} retention to be at most {} milliseconds.",
+                name, RETENTION_MAX_MILLISECONDS
+            )))
+        } else {
+            Ok(retention)
+        }
+    };
+
+    Ok(DataRetention {
+        cpu: check_retention("cpu", retention_config.cpu)?,
+        mem: check_retention("mem", retention_config.mem)?,
+        net: check_retention("net", retention_config.net)?,
+        custom: check_retention("custom", retention_config.custom)?,
+        sched: check_retention("sched", retention_config.sched)?,
+        power: check_retention("power", retention_config.power)?,
+    })
+}
+
+fn get_pressure_weights(config: &Config) -> error::Result<PressureWeights> {
+    let weights_config = config.pressure_weights.clone().unwrap_or_default();
+    let default_weights = PressureWeights::default();
+    let check_weight = |name: &str, weight: Option<f64>, default: f64| -> error::Result<f64> {
+        let weight = weight.unwrap_or(default);
+        if weight.is_finite() && weight >= 0.0 {
+            Ok(weight)
+        } else {
+            Err(BottomError::ConfigError(format!(
+                "set the {} weight to be a number that is at least 0.",
+                name
+            )))
+        }
+    };
+
+    let weights = PressureWeights {
+        cpu: check_weight("cpu", weights_config.cpu, default_weights.cpu)?,
+        mem: check_weight("mem", weights_config.mem, default_weights.mem)?,
+        psi: check_weight("psi", weights_config.psi, default_weights.psi)?,
+        io_wait: check_weight("io_wait", weights_config.io_wait, default_weights.io_wait)?,
+    };
+
+    if weights.cpu + weights.mem + weights.psi + weights.io_wait <= 0.0 {
+        return Err(BottomError::ConfigError(
+            "set at least one of the pressure weights to be more than 0.".to_string(),
+        ));
+    }
+
+    Ok(weights)
+}
+
+/// Returns the remote a remote widget shows, making sure it is one of the configured `[[remote]]` entries.
+fn get_remote_host(widget: &BottomWidget, config: &Config) -> error::Result<String> {
+    let host = if let Some(host) = &widget.host {
+        host
+    } else {
+        return Err(BottomError::ConfigError(
+            "remote widgets need a 'host' to show.".to_string(),
+        ));
+    };
+
+    let is_configured = config
+        .remote
+        .as_ref()
+        .map(|remotes| remotes.iter().any(|remote| remote.name == *host))
+        .unwrap_or(false);
+
+    if is_configured {
+        Ok(host.clone())
+    } else {
+        Err(BottomError::ConfigError(format!(
+            "\"{}\" is not the name of any remote under the '[[remote]]' section.",
+            host
+        )))
+    }
+}
+
+/// Returns the file a log widget follows.  A leading `~` is expanded to the home directory.
+fn get_log_file(widget: &BottomWidget) -> error::Result<PathBuf> {
+    let file = if let Some(file) = &widget.file {
+        file
+    } else {
+        return Err(BottomError::ConfigError(
+            "log widgets need a 'file' to follow.".to_string(),
+        ));
+    };
+
+    if let Some(rest) = file.strip_prefix("~/") {
+        if let Some(home_dir) = dirs::home_dir() {
+            return Ok(home_dir.join(rest));
+        }
+    }
+
+    Ok(PathBuf::from(file))
+}
+
+/// Returns the patterns a log widget colours lines by, along with their styles.
+fn get_log_highlights(widget: &BottomWidget) -> error::Result<Vec<(Regex, tui::style::Style)>> {
+    widget
+        .highlight
+        .iter()
+        .flatten()
+        .map(|highlight| {
+            let pattern = Regex::new(&highlight.pattern).map_err(|err| {
+                BottomError::ConfigError(format!(
+                    "\"{}\" is not a valid pattern: {}",
+                    highlight.pattern, err
+                ))
+            })?;
+            let style = get_style_from_config(&highlight.color)?;
+            Ok((pattern, style))
+        })
+        .collect()
+}
@@ -1,4 +1,4 @@
-use crate::app::layout_manager::*;
+use crate::app::{data_harvester::log_tail::LogHighlightConfig, layout_manager::*};
 use crate::error::Result;
 use serde::{Deserialize, Serialize};
 
@@ -150,6 +150,9 @@ impl Row {
                                     .children(vec![BottomWidget::builder()
                                         .widget_type(widget_type)
                                         .widget_id(*iter_id)
+                                        .host(widget.host.clone())
+                                        .file(widget.file.clone())
+                                        .highlight(widget.highlight.clone())
                                         .build()])
                                     .build()])
                                 .build(),
@@ -289,6 +292,9 @@ impl Row {
                                         .children(vec![BottomWidget::builder()
                                             .widget_type(widget_type)
                                             .widget_id(*iter_id)
+                                            .host(widget.host.clone())
+                                            .file(widget.file.clone())
+                                            .highlight(widget.highlight.clone())
                                             .build()])
                                         .build(),
                                 ),
@@ -351,4 +357,10 @@ pub struct FinalWidget {
     #[serde(rename = "type")]
     pub widget_type: String,
     pub default: Option<bool>,
+    /// The name of the remote this widget shows, for remote widgets.
+    pub host: Option<String>,
+    /// The file this widget follows, for log widgets.
+    pub file: Option<String>,
+    /// Patterns to colour lines by, for log widgets.
+    pub highlight: Option<Vec<LogHighlightConfig>>,
 }
@@ -1 +1,2 @@
 pub mod data_units;
+pub mod formatting;
@@ -0,0 +1,72 @@
+//! Formatting of sizes and rates for display, so that every widget shows them the same way.
+
+use crate::utils::{
+    error::{self, BottomError},
+    gen_util::{get_binary_prefix, get_decimal_prefix},
+};
+
+/// Which prefixes to show sizes and rates with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitPrefix {
+    /// Powers of 1000, like kilobytes and megabytes.
+    Si,
+    /// Powers of 1024, like kibibytes and mebibytes.
+    Iec,
+}
+
+impl std::str::FromStr for UnitPrefix {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> error::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "si" | "decimal" => Ok(UnitPrefix::Si),
+            "iec" | "binary" => Ok(UnitPrefix::Iec),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid unit prefix, use \"si\" or \"iec\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// How to format sizes and rates.  Anything left unset falls back to what each widget shows by
+/// default.
+#[derive(Debug, Clone, Default)]
+pub struct NumberFormat {
+    pub unit_prefix: Option<UnitPrefix>,
+    pub decimal_places: Option<usize>,
+}
+
+impl NumberFormat {
+    /// Formats a number, using the configured number of decimal places if there is one.
+    pub fn format_number(&self, value: f64, default_decimal_places: usize) -> String {
+        format!(
+            "{:.*}",
+            self.decimal_places.unwrap_or(default_decimal_places),
+            value
+        )
+    }
+
+    /// Formats a quantity with a prefixed unit, like "1.5MiB" or "300Kb/s".
+    pub fn format_prefixed(
+        &self, quantity: u64, unit: &str, default_prefix: UnitPrefix, default_decimal_places: usize,
+    ) -> String {
+        let (value, prefixed_unit) = match self.unit_prefix.unwrap_or(default_prefix) {
+            UnitPrefix::Si => get_decimal_prefix(quantity, unit),
+            UnitPrefix::Iec => get_binary_prefix(quantity, unit),
+        };
+
+        format!(
+            "{}{}",
+            self.format_number(value, default_decimal_places),
+            prefixed_unit
+        )
+    }
+
+    /// Formats a number of bytes, like "1.5MiB".
+    pub fn format_bytes(
+        &self, bytes: u64, default_prefix: UnitPrefix, default_decimal_places: usize,
+    ) -> String {
+        self.format_prefixed(bytes, "B", default_prefix, default_decimal_places)
+    }
+}
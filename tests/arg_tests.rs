@@ -16,11 +16,29 @@ fn test_small_rate() {
         .arg("-C")
         .arg("./tests/empty_config.toml")
         .arg("-r")
-        .arg("249")
+        .arg("99")
         .assert()
         .failure()
         .stderr(predicate::str::contains(
-            "set your update rate to be at least 250 milliseconds.",
+            "set your update rate to be at least 100 milliseconds.",
+        ));
+}
+
+#[test]
+fn test_fast_rate() {
+    // The time delta is checked after the rate, so failing on it means the rate was accepted.
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/empty_config.toml")
+        .arg("-r")
+        .arg("150")
+        .arg("-d")
+        .arg("900")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("update rate").not())
+        .stderr(predicate::str::contains(
+            "set your time delta to be at least",
         ));
 }
 
@@ -0,0 +1,79 @@
+//! Checks how keys in the `[keybinds]` section are parsed and remapped.
+
+use std::collections::HashMap;
+
+use bottom::app::{keybinds::Keybinds, layout_manager::BottomWidgetType};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+fn create_keybinds(remaps: &[(&str, &str)]) -> Keybinds {
+    let config = remaps
+        .iter()
+        .map(|(name, key)| (name.to_string(), key.to_string()))
+        .collect::<HashMap<_, _>>();
+    Keybinds::from_config(&config).unwrap()
+}
+
+fn remap(keybinds: &Keybinds, code: KeyCode, modifiers: KeyModifiers) -> Option<KeyEvent> {
+    keybinds.remap(KeyEvent::new(code, modifiers), &BottomWidgetType::Proc)
+}
+
+#[test]
+fn test_alt_keeps_case() {
+    let keybinds = create_keybinds(&[("quit", "alt-X")]);
+    let quit = Some(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+
+    assert_eq!(
+        remap(
+            &keybinds,
+            KeyCode::Char('X'),
+            KeyModifiers::ALT | KeyModifiers::SHIFT
+        ),
+        quit
+    );
+    assert_eq!(
+        remap(&keybinds, KeyCode::Char('x'), KeyModifiers::ALT),
+        Some(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT))
+    );
+}
+
+#[test]
+fn test_alt_upper_and_lower_are_different_keys() {
+    let keybinds = create_keybinds(&[("quit", "alt-X"), ("help", "alt-x")]);
+
+    assert_eq!(
+        remap(&keybinds, KeyCode::Char('X'), KeyModifiers::ALT),
+        Some(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE))
+    );
+    assert_eq!(
+        remap(&keybinds, KeyCode::Char('x'), KeyModifiers::ALT),
+        Some(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE))
+    );
+}
+
+#[test]
+fn test_prefixes_ignore_case() {
+    let keybinds = create_keybinds(&[("quit", "ALT-X"), ("kill", "F8")]);
+
+    assert_eq!(
+        remap(&keybinds, KeyCode::Char('X'), KeyModifiers::ALT),
+        Some(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE))
+    );
+    assert_eq!(
+        remap(&keybinds, KeyCode::F(8), KeyModifiers::NONE),
+        Some(KeyEvent::new(KeyCode::F(9), KeyModifiers::NONE))
+    );
+}
+
+#[test]
+fn test_plain_key_keeps_case() {
+    let keybinds = create_keybinds(&[("up", "Q")]);
+
+    assert_eq!(
+        remap(&keybinds, KeyCode::Char('Q'), KeyModifiers::SHIFT),
+        Some(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE))
+    );
+    assert_eq!(
+        remap(&keybinds, KeyCode::Char('q'), KeyModifiers::NONE),
+        Some(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE))
+    );
+}
@@ -0,0 +1,113 @@
+//! Serves a stream and reads it back with a remote client, to check that the two agree.
+
+use std::net::TcpListener;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bottom::app::data_harvester::{
+    remote::{parse_snapshot, start_remote_clients, RemoteConfig, RemoteData, RemoteSnapshot},
+    stream::{data_to_json, StreamServer, STREAM_VERSION},
+    Data,
+};
+
+const TOKEN: &str = "correct horse battery staple";
+
+fn get_free_address() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().to_string()
+}
+
+/// Starts a stream that keeps broadcasting the default snapshot, since clients only get the
+/// snapshots sent after they connect.
+fn start_stream(address: &str, token: Option<&str>) {
+    let server = StreamServer::start(address, token.map(String::from)).unwrap();
+    thread::spawn(move || loop {
+        server.broadcast(&Data::default());
+        thread::sleep(Duration::from_millis(50));
+    });
+}
+
+/// Waits for the remote to get data or fail, and returns what it ended up with.
+fn wait_for_remote(remote_data: &RemoteData, name: &str) -> RemoteSnapshot {
+    let start = Instant::now();
+    loop {
+        let snapshot = remote_data.lock().unwrap()[name].clone();
+        if snapshot.last_update.is_some()
+            || snapshot.error.is_some()
+            || start.elapsed() > Duration::from_secs(10)
+        {
+            return snapshot;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+fn connect(address: &str, token: Option<&str>) -> RemoteSnapshot {
+    let remote = RemoteConfig {
+        name: "test".to_string(),
+        address: address.to_string(),
+        ssh: None,
+        token: token.map(String::from),
+    };
+    let (remote_data, _ssh_tunnels) = start_remote_clients(&[remote]);
+    wait_for_remote(&remote_data, "test")
+}
+
+#[test]
+fn test_snapshot_round_trip() {
+    let mut snapshot = data_to_json(&Data::default());
+    assert_eq!(snapshot["version"], STREAM_VERSION);
+    snapshot["memory"] = serde_json::json!({
+        "total_kib": 1024,
+        "used_kib": 512,
+        "use_percent": 50.0,
+    });
+
+    let snapshot = parse_snapshot(&snapshot).unwrap();
+    assert_eq!(snapshot.mem, Some((512, 1024)));
+    assert!(snapshot.last_update.is_some());
+}
+
+#[test]
+fn test_unsupported_version() {
+    let mut snapshot = data_to_json(&Data::default());
+    snapshot["version"] = (STREAM_VERSION + 1).into();
+    assert!(parse_snapshot(&snapshot).is_err());
+
+    snapshot.as_object_mut().unwrap().remove("version");
+    assert!(parse_snapshot(&snapshot).is_err());
+}
+
+#[test]
+fn test_stream_without_token() {
+    let address = get_free_address();
+    start_stream(&address, None);
+
+    let snapshot = connect(&address, None);
+    assert_eq!(snapshot.error, None);
+    assert!(snapshot.last_update.is_some());
+}
+
+#[test]
+fn test_stream_with_token() {
+    let address = get_free_address();
+    start_stream(&address, Some(TOKEN));
+
+    let snapshot = connect(&address, Some(TOKEN));
+    assert_eq!(snapshot.error, None);
+    assert!(snapshot.last_update.is_some());
+}
+
+#[test]
+fn test_stream_with_wrong_token() {
+    let address = get_free_address();
+    start_stream(&address, Some(TOKEN));
+
+    let snapshot = connect(&address, Some("wrong"));
+    assert!(snapshot.error.is_some());
+    assert!(snapshot.last_update.is_none());
+
+    let snapshot = connect(&address, None);
+    assert!(snapshot.error.is_some());
+    assert!(snapshot.last_update.is_none());
+}